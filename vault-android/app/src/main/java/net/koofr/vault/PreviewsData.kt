@@ -43,6 +43,7 @@ object PreviewsData {
             decryptedPath = "/example.jpg",
             name = "example example example example example example example example example.jpg",
             nameError = null,
+            nameErrorCode = null,
             ext = "jpg",
             contentType = "image/jpeg",
             typ = RepoFileType.FILE,
@@ -61,6 +61,12 @@ class MobileVaultProvider constructor(private val secureStorage: SecureStorage)
         return mobileVault!!
     }
 
+    @Synchronized
+    fun panicLock() {
+        // a vault that was not built yet has nothing to lock
+        mobileVault?.panicLock(wipeSecureStorage = false)
+    }
+
     @Synchronized
     fun loadConfigFromIntent(intent: Intent) {
         if (BuildConfig.BUILD_TYPE != "debug") {
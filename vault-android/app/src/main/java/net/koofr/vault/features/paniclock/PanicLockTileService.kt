@@ -0,0 +1,31 @@
+package net.koofr.vault.features.paniclock
+
+import android.os.Build
+import android.service.quicksettings.Tile
+import android.service.quicksettings.TileService
+import androidx.annotation.RequiresApi
+import dagger.hilt.android.AndroidEntryPoint
+import net.koofr.vault.features.mobilevault.MobileVaultProvider
+import javax.inject.Inject
+
+@RequiresApi(Build.VERSION_CODES.N)
+@AndroidEntryPoint
+class PanicLockTileService : TileService() {
+    @Inject
+    lateinit var mobileVaultProvider: MobileVaultProvider
+
+    override fun onStartListening() {
+        super.onStartListening()
+
+        qsTile?.let {
+            it.state = Tile.STATE_ACTIVE
+            it.updateTile()
+        }
+    }
+
+    override fun onClick() {
+        super.onClick()
+
+        mobileVaultProvider.panicLock()
+    }
+}
@@ -0,0 +1,88 @@
+use std::{path::PathBuf, sync::Arc};
+
+use vault_core::{oauth2::OAuth2Config, Vault};
+use vault_native::{
+    data_path::get_data_path,
+    file_audit_log_disk_storage::FileAuditLogDiskStorage,
+    file_offline_disk_cache::FileOfflineDiskCache,
+    file_secure_storage::{get_file_secure_storage_path, FileSecureStorage},
+    vault::build_vault,
+};
+
+use crate::errors::{CliError, CliErrorKind};
+
+pub const APP_ID: &str = "koofr-vault-cli";
+
+pub struct CliConfig {
+    pub base_url: String,
+    pub data_path: Option<PathBuf>,
+}
+
+pub fn build_cli_vault(
+    config: CliConfig,
+    tokio_runtime: Arc<tokio::runtime::Runtime>,
+) -> Result<Arc<Vault>, CliError> {
+    let data_path = match config.data_path {
+        Some(data_path) => {
+            std::fs::create_dir_all(&data_path)
+                .map_err(|err| CliError::new(CliErrorKind::Error, err.to_string()))?;
+
+            data_path
+        }
+        None => get_data_path(APP_ID)
+            .map_err(|err| CliError::new(CliErrorKind::Error, err.to_string()))?,
+    };
+
+    let secure_storage = FileSecureStorage::new(get_file_secure_storage_path(data_path.clone()))
+        .map_err(|err| {
+            CliError::new(
+                CliErrorKind::Error,
+                format!("Failed to load app data: {}", err),
+            )
+        })?;
+
+    let oauth2_config = OAuth2Config {
+        base_url: config.base_url.clone(),
+        auth_base_url: config.base_url.clone(),
+        client_id: String::from("7ZEK2BNCEVYEJIZC5OR3TR6PQDUJ4NP3"),
+        client_secret: None,
+        redirect_uri: String::from("http://127.0.0.1:1421/oauth2callback"),
+    };
+
+    let (vault, _, _) = build_vault(
+        config.base_url,
+        String::from("vault-cli"),
+        oauth2_config,
+        Box::new(secure_storage),
+        tokio_runtime,
+    );
+
+    match FileAuditLogDiskStorage::new(data_path.join("audit-log.json")) {
+        Ok(disk_storage) => vault.audit_log_set_disk_storage(Some(Box::new(disk_storage))),
+        Err(err) => log::warn!("Audit log disk storage not available: {}", err),
+    }
+
+    match FileOfflineDiskCache::new(data_path.join("offline")) {
+        Ok(disk_cache) => vault.repo_files_offline_set_disk_cache(Some(Box::new(disk_cache))),
+        Err(err) => log::warn!("Offline disk cache not available: {}", err),
+    }
+
+    Ok(vault)
+}
+
+/// Loads the stored token and the repos. Unlike `Vault::load` it does not
+/// connect to the eventstream.
+pub async fn load_authenticated(vault: &Vault) -> Result<(), CliError> {
+    vault.oauth2_service.load()?;
+
+    if !vault.oauth2_service.is_authenticated() {
+        return Err(CliError::new(
+            CliErrorKind::NotAuthenticated,
+            "Not logged in. Run `vault login` first.",
+        ));
+    }
+
+    vault.repos_load().await?;
+
+    Ok(())
+}
@@ -0,0 +1,342 @@
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand, ValueEnum};
+
+#[derive(Parser, Debug, Clone)]
+#[command(name = "vault", author, version, about = "Koofr Vault command-line client", long_about = None)]
+pub struct Args {
+    /// Print JSON output
+    #[arg(long, global = true)]
+    pub json: bool,
+
+    /// Koofr base URL
+    #[arg(
+        long,
+        env = "VAULT_BASE_URL",
+        default_value = "https://app.koofr.net",
+        global = true
+    )]
+    pub base_url: String,
+
+    /// Data path (default ~/.koofr-vault-cli)
+    #[arg(long, env = "VAULT_DATA_PATH", global = true)]
+    pub data_path: Option<PathBuf>,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum Command {
+    /// Log in to Koofr
+    Login {
+        /// URL you were redirected to after login (prompted if not set)
+        #[arg(long, conflicts_with = "device")]
+        url: Option<String>,
+        /// Log in with a code on another device (for machines without a browser)
+        #[arg(long)]
+        device: bool,
+    },
+    /// Log out of Koofr
+    Logout,
+    /// Safe Boxes
+    Repos {
+        #[command(subcommand)]
+        command: ReposCommand,
+    },
+    /// Check the Safe Box password
+    Unlock {
+        #[command(flatten)]
+        repo: RepoArgs,
+    },
+    /// List files in a dir
+    Ls {
+        #[command(flatten)]
+        repo: RepoArgs,
+        /// Path inside the Safe Box
+        #[arg(default_value = "/")]
+        path: String,
+    },
+    /// Download a file (dirs are downloaded as ZIP files)
+    Get {
+        #[command(flatten)]
+        repo: RepoArgs,
+        /// Path inside the Safe Box
+        path: String,
+        /// Local file or dir
+        #[arg(default_value = ".")]
+        local_path: PathBuf,
+    },
+    /// Upload local files and dirs
+    Put {
+        #[command(flatten)]
+        repo: RepoArgs,
+        /// Local files or dirs
+        #[arg(required = true)]
+        local_paths: Vec<PathBuf>,
+        /// Destination dir inside the Safe Box
+        #[arg(long, default_value = "/")]
+        to: String,
+    },
+    /// Move or rename a file or dir
+    Mv {
+        #[command(flatten)]
+        repo: RepoArgs,
+        /// Source path
+        from: String,
+        /// Destination path or an existing dir
+        to: String,
+    },
+    /// Delete files or dirs
+    Rm {
+        #[command(flatten)]
+        repo: RepoArgs,
+        /// Paths inside the Safe Box
+        #[arg(required = true)]
+        paths: Vec<String>,
+    },
+    /// Create a dir and its parents
+    Mkdir {
+        #[command(flatten)]
+        repo: RepoArgs,
+        /// Path inside the Safe Box
+        path: String,
+    },
+    /// Sync a local dir with a dir inside the Safe Box
+    Sync {
+        #[command(flatten)]
+        repo: RepoArgs,
+        #[command(flatten)]
+        sync: SyncArgs,
+    },
+    /// Watch a local dir and upload new files until stopped
+    Watch {
+        #[command(flatten)]
+        repo: RepoArgs,
+        #[command(flatten)]
+        watch: WatchArgs,
+    },
+    /// Print the Safe Box config backup
+    ConfigBackup {
+        #[command(flatten)]
+        repo: RepoArgs,
+    },
+    /// Calculate the Safe Box space usage
+    SpaceUsage {
+        #[command(flatten)]
+        repo: RepoArgs,
+    },
+    /// Files available offline
+    Offline {
+        #[command(subcommand)]
+        command: OfflineCommand,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ReposCommand {
+    /// List Safe Boxes
+    List,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum OfflineCommand {
+    /// Show the offline cache usage
+    Status,
+    /// Make a file or dir available offline
+    Pin {
+        #[command(flatten)]
+        repo: RepoArgs,
+        /// Path inside the Safe Box
+        path: String,
+    },
+    /// Stop keeping a file or dir available offline
+    Unpin {
+        #[command(flatten)]
+        repo: RepoArgs,
+        /// Path inside the Safe Box
+        path: String,
+    },
+    /// Set the offline cache size limit in bytes
+    Quota {
+        /// Size limit in bytes
+        quota: i64,
+    },
+    /// Download changes of pinned files and dirs in the Safe Box
+    Refresh {
+        #[command(flatten)]
+        repo: RepoArgs,
+    },
+    /// Remove all offline files
+    Clear,
+}
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct SyncArgs {
+    /// Local dir
+    pub local_path: PathBuf,
+
+    /// Dir inside the Safe Box
+    #[arg(long, default_value = "/")]
+    pub to: String,
+
+    #[arg(long, value_enum, default_value_t = SyncDirectionArg::Upload)]
+    pub direction: SyncDirectionArg,
+
+    /// Only print the planned changes
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Forget the state of the previous syncs
+    #[arg(long)]
+    pub reset: bool,
+}
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct WatchArgs {
+    /// Local dir
+    pub local_path: PathBuf,
+
+    /// Dir inside the Safe Box
+    #[arg(long, default_value = "/")]
+    pub to: String,
+
+    /// Only upload files matching the glob (e.g. "**/*.jpg")
+    #[arg(long)]
+    pub include: Vec<String>,
+
+    /// Skip files matching the glob
+    #[arg(long)]
+    pub exclude: Vec<String>,
+
+    /// Delete local files once they are uploaded
+    #[arg(long)]
+    pub delete_after_upload: bool,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncDirectionArg {
+    /// Mirror the local dir to the Safe Box
+    Upload,
+    /// Mirror the Safe Box dir to the local dir
+    Download,
+    /// Apply changes on either side to the other side
+    TwoWay,
+}
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct RepoArgs {
+    /// Safe Box name or ID (default is the only Safe Box)
+    #[arg(long, short)]
+    pub repo: Option<String>,
+
+    /// Safe Box password (prompted if not set)
+    #[arg(long, env = "VAULT_PASSWORD", hide_env_values = true)]
+    pub password: Option<String>,
+
+    /// Open the Safe Box read-only (files cannot be changed)
+    #[arg(long)]
+    pub read_only: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::{CommandFactory, Parser};
+
+    use super::{Args, Command, OfflineCommand, SyncDirectionArg};
+
+    #[test]
+    fn test_args() {
+        Args::command().debug_assert();
+
+        let args = Args::try_parse_from([
+            "vault",
+            "put",
+            "--json",
+            "-r",
+            "My safe box",
+            "a.txt",
+            "dir",
+            "--to",
+            "/docs",
+        ])
+        .unwrap();
+
+        assert!(args.json);
+
+        match args.command {
+            Command::Put {
+                repo,
+                local_paths,
+                to,
+            } => {
+                assert_eq!(repo.repo.as_deref(), Some("My safe box"));
+                assert_eq!(
+                    local_paths,
+                    vec!["a.txt".into(), "dir".into()] as Vec<std::path::PathBuf>
+                );
+                assert_eq!(to, "/docs");
+            }
+            _ => panic!("expected put"),
+        }
+
+        assert!(Args::try_parse_from(["vault", "rm"]).is_err());
+
+        let args = Args::try_parse_from([
+            "vault",
+            "sync",
+            "backup",
+            "--to",
+            "/backup",
+            "--direction",
+            "two-way",
+            "--dry-run",
+        ])
+        .unwrap();
+
+        match args.command {
+            Command::Sync { sync, .. } => {
+                assert_eq!(sync.local_path, std::path::PathBuf::from("backup"));
+                assert_eq!(sync.to, "/backup");
+                assert_eq!(sync.direction, SyncDirectionArg::TwoWay);
+                assert!(sync.dry_run);
+                assert!(!sync.reset);
+            }
+            _ => panic!("expected sync"),
+        }
+
+        let args = Args::try_parse_from([
+            "vault",
+            "watch",
+            "camera",
+            "--include",
+            "*.jpg",
+            "--include",
+            "*.png",
+            "--delete-after-upload",
+        ])
+        .unwrap();
+
+        match args.command {
+            Command::Watch { watch, .. } => {
+                assert_eq!(watch.include, vec!["*.jpg", "*.png"]);
+                assert!(watch.exclude.is_empty());
+                assert!(watch.delete_after_upload);
+            }
+            _ => panic!("expected watch"),
+        }
+
+        let args =
+            Args::try_parse_from(["vault", "offline", "pin", "-r", "Photos", "/2024"]).unwrap();
+
+        match args.command {
+            Command::Offline {
+                command: OfflineCommand::Pin { repo, path },
+            } => {
+                assert_eq!(repo.repo.as_deref(), Some("Photos"));
+                assert_eq!(path, "/2024");
+            }
+            _ => panic!("expected offline pin"),
+        }
+    }
+}
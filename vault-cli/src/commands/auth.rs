@@ -0,0 +1,133 @@
+use std::io::BufRead;
+
+use futures::stream::AbortHandle;
+use serde::Serialize;
+use vault_core::{user_error::UserError, Vault};
+
+use crate::{
+    errors::{CliError, CliErrorKind},
+    output::Output,
+};
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserJson {
+    pub id: String,
+    pub full_name: String,
+    pub email: String,
+}
+
+/// Without `url`, the login URL is printed and the URL the browser was
+/// redirected to is read from stdin. Scripts can pass the redirected URL with
+/// `--url` in a second run, the flow state is kept in the secure storage.
+pub async fn login(vault: &Vault, output: Output, url: Option<String>) -> Result<(), CliError> {
+    vault.oauth2_service.load()?;
+
+    let url = match url {
+        Some(url) => url,
+        None => {
+            let login_url = vault.oauth2_start_login_flow()?;
+
+            eprintln!(
+                "Open this URL in your browser and log in:\n\n{}\n",
+                login_url
+            );
+            eprintln!(
+                "The browser will be redirected to a page that might not load. \
+                 Copy its URL and paste it here (or run `vault login --url <URL>`):"
+            );
+
+            let mut url = String::new();
+
+            std::io::stdin()
+                .lock()
+                .read_line(&mut url)
+                .map_err(|err| CliError::new(CliErrorKind::Error, err.to_string()))?;
+
+            url.trim().to_owned()
+        }
+    };
+
+    if url.is_empty() {
+        return Err(CliError::new(CliErrorKind::Usage, "Missing redirect URL"));
+    }
+
+    vault.oauth2_finish_flow_url(&url).await?;
+
+    print_logged_in(vault, output);
+
+    Ok(())
+}
+
+/// Device authorization flow: the user opens the verification URL on any
+/// device and enters the printed code while we poll for the token. Ctrl+C
+/// cancels the polling.
+pub async fn login_device(vault: &Vault, output: Output) -> Result<(), CliError> {
+    vault.oauth2_service.load()?;
+
+    let device_authorization = vault.oauth2_start_device_flow().await?;
+
+    match &device_authorization.verification_uri_complete {
+        Some(verification_uri_complete) => eprintln!(
+            "Open this URL on any device and log in:\n\n{}\n",
+            verification_uri_complete
+        ),
+        None => eprintln!(
+            "Open this URL on any device and log in:\n\n{}\n",
+            device_authorization.verification_uri
+        ),
+    }
+    eprintln!(
+        "Confirm that the code is {}. Press Ctrl+C to cancel.",
+        device_authorization.user_code
+    );
+
+    let (abort_handle, abort_registration) = AbortHandle::new_pair();
+
+    let ctrl_c_task = tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            abort_handle.abort();
+        }
+    });
+
+    let res = vault
+        .oauth2_finish_device_flow(&device_authorization, abort_registration)
+        .await;
+
+    ctrl_c_task.abort();
+
+    res?;
+
+    print_logged_in(vault, output);
+
+    Ok(())
+}
+
+fn print_logged_in(vault: &Vault, output: Output) {
+    let user = vault.with_state(|state| {
+        state.user.user.as_ref().map(|user| UserJson {
+            id: user.id.clone(),
+            full_name: user.full_name.clone(),
+            email: user.email.clone(),
+        })
+    });
+
+    match user {
+        Some(user) => output.print(&user, |user| {
+            format!("Logged in as {} ({})", user.full_name, user.email)
+        }),
+        None => output.info("Logged in"),
+    }
+}
+
+pub fn logout(vault: &Vault, output: Output) -> Result<(), CliError> {
+    vault.oauth2_service.load()?;
+
+    vault
+        .logout()
+        .map_err(|err| CliError::new(CliErrorKind::Error, err.user_error()))?;
+
+    output.info("Logged out");
+
+    Ok(())
+}
@@ -0,0 +1,377 @@
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use chrono::TimeZone;
+use futures::future::join_all;
+use serde::Serialize;
+use vault_core::{
+    files::file_size::size_display,
+    repo_files::{
+        selectors as repo_files_selectors,
+        state::{RepoFile, RepoFileType},
+    },
+    transfers::{errors::TransferError, state::CreateUploadResultFuture},
+    types::{DecryptedPath, RepoId},
+    utils::{repo_encrypted_path_utils, repo_path_utils},
+    Vault,
+};
+use vault_native::transfers::{
+    file_downloadable::FileDownloadable, file_uploadable::FileUploadable, upload_helper,
+};
+
+use crate::{
+    args::RepoArgs,
+    errors::{CliError, CliErrorKind},
+    output::Output,
+    progress::TransfersProgress,
+    repo::{encrypt_path, open_repo, parse_path},
+};
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileJson {
+    pub name: String,
+    pub path: String,
+    #[serde(rename = "type")]
+    pub typ: &'static str,
+    pub size: Option<i64>,
+    pub modified: Option<i64>,
+}
+
+impl FileJson {
+    fn from_repo_file(file: &RepoFile) -> Option<Self> {
+        Some(Self {
+            name: file.decrypted_name().ok()?.0.clone(),
+            path: file.decrypted_path().ok()?.0.clone(),
+            typ: match file.typ {
+                RepoFileType::Dir => "dir",
+                RepoFileType::File => "file",
+            },
+            size: file.decrypted_size().ok().flatten(),
+            modified: file.modified,
+        })
+    }
+
+    fn to_text(&self) -> String {
+        let modified = self
+            .modified
+            .and_then(|modified| chrono::Local.timestamp_millis_opt(modified).single())
+            .map(|modified| modified.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_default();
+
+        match self.typ {
+            "dir" => format!("{:>10}  {:16}  {}/", "", modified, self.name),
+            _ => format!(
+                "{:>10}  {:16}  {}",
+                self.size.map(size_display).unwrap_or_default(),
+                modified,
+                self.name
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferResultJson {
+    pub name: String,
+    pub error: Option<String>,
+}
+
+fn print_transfer_results(output: Output, results: &[TransferResultJson]) -> Result<(), CliError> {
+    output.print(&results, |results| {
+        results
+            .iter()
+            .map(|result| match &result.error {
+                Some(error) => format!("{}: {}", result.name, error),
+                None => result.name.clone(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    });
+
+    match results
+        .iter()
+        .filter(|result| result.error.is_some())
+        .count()
+    {
+        0 => Ok(()),
+        failed => Err(CliError::new(
+            CliErrorKind::Error,
+            format!("{} of {} transfers failed", failed, results.len()),
+        )),
+    }
+}
+
+pub async fn ls(
+    vault: &Vault,
+    output: Output,
+    args: &RepoArgs,
+    path: &str,
+) -> Result<(), CliError> {
+    let repo_id = open_repo(vault, args)?;
+    let path = encrypt_path(vault, &repo_id, &parse_path(path)?)?;
+
+    vault.repo_files_service.load_files(&repo_id, &path).await?;
+
+    let mut files = vault.with_state(|state| {
+        repo_files_selectors::select_files(state, &repo_id, &path)
+            .filter_map(FileJson::from_repo_file)
+            .collect::<Vec<_>>()
+    });
+
+    files.sort_by(|a, b| {
+        (a.typ != "dir", a.name.to_lowercase()).cmp(&(b.typ != "dir", b.name.to_lowercase()))
+    });
+
+    output.print(&files, |files| {
+        files
+            .iter()
+            .map(FileJson::to_text)
+            .collect::<Vec<_>>()
+            .join("\n")
+    });
+
+    Ok(())
+}
+
+pub async fn get(
+    vault: Arc<Vault>,
+    output: Output,
+    args: &RepoArgs,
+    path: &str,
+    local_path: PathBuf,
+) -> Result<(), CliError> {
+    let repo_id = open_repo(&vault, args)?;
+    let decrypted_path = parse_path(path)?;
+    let path = encrypt_path(&vault, &repo_id, &decrypted_path)?;
+
+    vault.repo_files_service.load_file(&repo_id, &path).await?;
+
+    let reader_provider = vault.repo_files_get_file_reader(&repo_id, &path)?;
+
+    let progress = TransfersProgress::new(vault.clone(), output.json);
+
+    let (id, create_future) = vault.transfers_download(
+        reader_provider,
+        Box::new(FileDownloadable {
+            append_name: local_path.is_dir(),
+            original_path: local_path,
+            autorename: false,
+            on_open: None,
+            on_done: Box::new(|_, _| Ok(())),
+            path: None,
+            content_type: None,
+        }),
+    );
+
+    let res = match create_future.await {
+        Ok(future) => future.await,
+        Err(err) => Err(err),
+    }
+    .map_err(|err| progress.map_error(id, err));
+
+    drop(progress);
+
+    print_transfer_results(
+        output,
+        &[TransferResultJson {
+            name: repo_path_utils::path_to_name(&decrypted_path)
+                .map(|name| name.0)
+                .unwrap_or_else(|| String::from("/")),
+            error: res.err().map(|err| CliError::from(err).message),
+        }],
+    )
+}
+
+pub async fn put(
+    vault: Arc<Vault>,
+    output: Output,
+    args: &RepoArgs,
+    local_paths: Vec<PathBuf>,
+    to: &str,
+) -> Result<(), CliError> {
+    let repo_id = open_repo(&vault, args)?;
+    let parent_path = encrypt_path(&vault, &repo_id, &parse_path(to)?)?;
+
+    let progress = TransfersProgress::new(vault.clone(), output.json);
+
+    let uploads: Arc<Mutex<Vec<(String, u32, CreateUploadResultFuture)>>> =
+        Arc::new(Mutex::new(Vec::new()));
+    let errors: Arc<Mutex<Vec<TransferResultJson>>> = Arc::new(Mutex::new(Vec::new()));
+
+    for local_path in local_paths {
+        let upload_vault = vault.clone();
+        let upload_repo_id = repo_id.clone();
+        let upload_parent_path = parent_path.clone();
+        let upload_uploads = uploads.clone();
+        let error_errors = errors.clone();
+
+        upload_helper::handle_path(
+            local_path,
+            Box::new(move |path, name| {
+                let display_name = name.0.clone();
+
+                let (id, future) = upload_vault.transfers_upload(
+                    upload_repo_id.clone(),
+                    upload_parent_path.clone(),
+                    name,
+                    Box::new(FileUploadable {
+                        path,
+                        cleanup: None,
+                    }),
+                );
+
+                upload_uploads
+                    .lock()
+                    .unwrap()
+                    .push((display_name, id, future));
+            }),
+            Box::new(move |path, err| {
+                error_errors.lock().unwrap().push(TransferResultJson {
+                    name: path.to_string_lossy().to_string(),
+                    error: Some(err.to_string()),
+                });
+            }),
+        );
+    }
+
+    let uploads = std::mem::take(&mut *uploads.lock().unwrap());
+
+    let mut results = join_all(uploads.into_iter().map(|(name, id, create_future)| {
+        let progress = &progress;
+
+        async move {
+            let res: Result<_, TransferError> = match create_future.await {
+                Ok(future) => future.await.map(|_| ()),
+                Err(err) => Err(err),
+            };
+
+            TransferResultJson {
+                name,
+                error: res
+                    .err()
+                    .map(|err| CliError::from(progress.map_error(id, err)).message),
+            }
+        }
+    }))
+    .await;
+
+    drop(progress);
+
+    results.extend(std::mem::take(&mut *errors.lock().unwrap()));
+
+    print_transfer_results(output, &results)
+}
+
+/// If `to` is an existing dir, the file is moved into it.
+pub async fn mv(
+    vault: &Vault,
+    output: Output,
+    args: &RepoArgs,
+    from: &str,
+    to: &str,
+) -> Result<(), CliError> {
+    let repo_id = open_repo(vault, args)?;
+    let from = parse_path(from)?;
+    let mut to = parse_path(to)?;
+
+    if is_dir(vault, &repo_id, &to).await? {
+        let name = repo_path_utils::path_to_name(&from)
+            .ok_or_else(|| CliError::new(CliErrorKind::Usage, "Cannot move root"))?;
+
+        to = repo_path_utils::join_path_name(&to, &name);
+    }
+
+    vault
+        .repo_files_service
+        .move_file_to_path(
+            &repo_id,
+            &encrypt_path(vault, &repo_id, &from)?,
+            &encrypt_path(vault, &repo_id, &to)?,
+        )
+        .await?;
+
+    output.info(&format!("Moved {} to {}", from.0, to.0));
+
+    Ok(())
+}
+
+async fn is_dir(vault: &Vault, repo_id: &RepoId, path: &DecryptedPath) -> Result<bool, CliError> {
+    if path.is_root() {
+        return Ok(true);
+    }
+
+    let path = encrypt_path(vault, repo_id, path)?;
+
+    match vault.repo_files_service.load_file(repo_id, &path).await {
+        Ok(()) => Ok(vault.with_state(|state| {
+            repo_files_selectors::select_file(
+                state,
+                &repo_files_selectors::get_file_id(repo_id, &path),
+            )
+            .map(|file| file.typ == RepoFileType::Dir)
+            .unwrap_or(false)
+        })),
+        Err(err) => match CliError::from(err) {
+            err if err.kind == CliErrorKind::NotFound => Ok(false),
+            err => Err(err),
+        },
+    }
+}
+
+pub async fn rm(
+    vault: &Vault,
+    output: Output,
+    args: &RepoArgs,
+    paths: &[String],
+) -> Result<(), CliError> {
+    let repo_id = open_repo(vault, args)?;
+
+    for path in paths {
+        let path = parse_path(path)?;
+
+        if path.is_root() {
+            return Err(CliError::new(CliErrorKind::Usage, "Cannot delete root"));
+        }
+
+        vault
+            .repo_files_service
+            .delete_file(&repo_id, &encrypt_path(vault, &repo_id, &path)?)
+            .await?;
+
+        output.info(&format!("Deleted {}", path.0));
+    }
+
+    Ok(())
+}
+
+pub async fn mkdir(
+    vault: &Vault,
+    output: Output,
+    args: &RepoArgs,
+    path: &str,
+) -> Result<(), CliError> {
+    let repo_id = open_repo(vault, args)?;
+    let path = parse_path(path)?;
+    let encrypted_path = encrypt_path(vault, &repo_id, &path)?;
+
+    if repo_encrypted_path_utils::split_parent_name(&encrypted_path).is_none() {
+        return Err(CliError::new(
+            CliErrorKind::AlreadyExists,
+            "Root already exists",
+        ));
+    }
+
+    vault
+        .repo_files_service
+        .clone()
+        .ensure_dirs(&repo_id, &encrypted_path)
+        .await?;
+
+    output.info(&format!("Created {}", path.0));
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+pub mod auth;
+pub mod files;
+pub mod offline;
+pub mod repos;
+pub mod sync;
+pub mod watch;
+
+use std::sync::Arc;
+
+use vault_core::Vault;
+
+use crate::{
+    app::load_authenticated,
+    args::{Command, OfflineCommand, ReposCommand},
+    errors::CliError,
+    output::Output,
+};
+
+pub async fn run(vault: Arc<Vault>, output: Output, command: Command) -> Result<(), CliError> {
+    match command {
+        Command::Login { device: true, .. } => return auth::login_device(&vault, output).await,
+        Command::Login { url, .. } => return auth::login(&vault, output, url).await,
+        Command::Logout => return auth::logout(&vault, output),
+        _ => {}
+    }
+
+    load_authenticated(&vault).await?;
+
+    match command {
+        Command::Login { .. } | Command::Logout => unreachable!(),
+        Command::Repos { command } => match command {
+            ReposCommand::List => repos::list(&vault, output),
+        },
+        Command::Unlock { repo } => repos::unlock(&vault, output, &repo),
+        Command::ConfigBackup { repo } => repos::config_backup(&vault, output, &repo),
+        Command::SpaceUsage { repo } => repos::space_usage(&vault, output, &repo).await,
+        Command::Ls { repo, path } => files::ls(&vault, output, &repo, &path).await,
+        Command::Get {
+            repo,
+            path,
+            local_path,
+        } => files::get(vault, output, &repo, &path, local_path).await,
+        Command::Put {
+            repo,
+            local_paths,
+            to,
+        } => files::put(vault, output, &repo, local_paths, &to).await,
+        Command::Mv { repo, from, to } => files::mv(&vault, output, &repo, &from, &to).await,
+        Command::Rm { repo, paths } => files::rm(&vault, output, &repo, &paths).await,
+        Command::Mkdir { repo, path } => files::mkdir(&vault, output, &repo, &path).await,
+        Command::Sync { repo, sync } => sync::sync(vault, output, &repo, sync).await,
+        Command::Watch { repo, watch } => watch::watch(vault, output, &repo, watch).await,
+        Command::Offline { command } => match command {
+            OfflineCommand::Status => offline::status(&vault, output),
+            OfflineCommand::Pin { repo, path } => offline::pin(&vault, output, &repo, &path).await,
+            OfflineCommand::Unpin { repo, path } => {
+                offline::unpin(&vault, output, &repo, &path).await
+            }
+            OfflineCommand::Quota { quota } => offline::quota(&vault, output, quota).await,
+            OfflineCommand::Refresh { repo } => offline::refresh(&vault, output, &repo).await,
+            OfflineCommand::Clear => offline::clear(&vault, output).await,
+        },
+    }
+}
@@ -0,0 +1,107 @@
+use serde::Serialize;
+use vault_core::{files::file_size::size_display, Vault};
+
+use crate::{
+    args::RepoArgs,
+    errors::CliError,
+    output::Output,
+    repo::{encrypt_path, open_repo, parse_path},
+};
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OfflineInfoJson {
+    pub quota: i64,
+    pub used: i64,
+    pub pinned_used: i64,
+    pub pins_count: usize,
+    pub entries_count: usize,
+}
+
+pub fn status(vault: &Vault, output: Output) -> Result<(), CliError> {
+    let info = vault.repo_files_offline_get_info();
+
+    output.print(
+        &OfflineInfoJson {
+            quota: info.quota,
+            used: info.used,
+            pinned_used: info.pinned_used,
+            pins_count: info.pins_count,
+            entries_count: info.entries_count,
+        },
+        |info| {
+            format!(
+                "Used: {} / {}\nPinned: {} ({} pins)\nFiles: {}",
+                size_display(info.used),
+                size_display(info.quota),
+                size_display(info.pinned_used),
+                info.pins_count,
+                info.entries_count,
+            )
+        },
+    );
+
+    Ok(())
+}
+
+pub async fn pin(
+    vault: &Vault,
+    output: Output,
+    args: &RepoArgs,
+    path: &str,
+) -> Result<(), CliError> {
+    let repo_id = open_repo(vault, args)?;
+    let path = encrypt_path(vault, &repo_id, &parse_path(path)?)?;
+
+    vault.repo_files_offline_pin(repo_id, path).await?;
+
+    output.info("Available offline");
+
+    Ok(())
+}
+
+pub async fn unpin(
+    vault: &Vault,
+    output: Output,
+    args: &RepoArgs,
+    path: &str,
+) -> Result<(), CliError> {
+    let repo_id = open_repo(vault, args)?;
+    let path = encrypt_path(vault, &repo_id, &parse_path(path)?)?;
+
+    vault.repo_files_offline_unpin(&repo_id, &path).await?;
+
+    output.info("No longer available offline");
+
+    Ok(())
+}
+
+pub async fn quota(vault: &Vault, output: Output, quota: i64) -> Result<(), CliError> {
+    vault.repo_files_offline_set_quota(quota).await?;
+
+    output.info(&format!(
+        "Offline size limit set to {}",
+        size_display(quota)
+    ));
+
+    Ok(())
+}
+
+/// Pins in other Safe Boxes are skipped because they are locked.
+pub async fn refresh(vault: &Vault, output: Output, args: &RepoArgs) -> Result<(), CliError> {
+    open_repo(vault, args)?;
+
+    vault.repo_files_offline_refresh().await?;
+
+    output.info("Offline files refreshed");
+
+    Ok(())
+}
+
+pub async fn clear(vault: &Vault, output: Output) -> Result<(), CliError> {
+    vault.repo_files_offline_clear().await?;
+
+    output.info("Offline files removed");
+
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use serde::Serialize;
+use vault_core::{
+    files::file_size::size_display, repo_config_backup, repo_space_usage,
+    repos::selectors as repos_selectors, Vault,
+};
+
+use crate::{
+    args::RepoArgs,
+    errors::{CliError, CliErrorKind},
+    output::Output,
+    repo::{find_repo, get_password, open_repo},
+};
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepoJson {
+    pub id: String,
+    pub name: String,
+    pub mount_id: String,
+    pub path: String,
+    pub added: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepoConfigJson {
+    pub name: String,
+    pub mount_id: String,
+    pub path: String,
+    pub password: String,
+    pub salt: Option<String>,
+    pub rclone_config: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepoSpaceUsageJson {
+    pub space_used: i64,
+}
+
+pub fn list(vault: &Vault, output: Output) -> Result<(), CliError> {
+    let repos = vault.with_state(|state| {
+        repos_selectors::select_repos(state)
+            .into_iter()
+            .map(|repo| RepoJson {
+                id: repo.id.0.clone(),
+                name: repo.name.0.clone(),
+                mount_id: repo.mount_id.0.clone(),
+                path: repo.path.0.clone(),
+                added: repo.added,
+            })
+            .collect::<Vec<_>>()
+    });
+
+    output.print(&repos, |repos| {
+        repos
+            .iter()
+            .map(|repo| format!("{}\t{}", repo.id, repo.name))
+            .collect::<Vec<_>>()
+            .join("\n")
+    });
+
+    Ok(())
+}
+
+pub fn unlock(vault: &Vault, output: Output, args: &RepoArgs) -> Result<(), CliError> {
+    open_repo(vault, args)?;
+
+    output.info("Password is correct");
+
+    Ok(())
+}
+
+pub fn config_backup(vault: &Vault, output: Output, args: &RepoArgs) -> Result<(), CliError> {
+    let repo_id = find_repo(vault, args.repo.as_deref())?;
+
+    let password = get_password(args.password.as_deref())?;
+
+    let backup_id = vault.repo_config_backup_create(repo_id);
+
+    let res = vault
+        .repo_config_backup_generate(backup_id, &password)
+        .map_err(CliError::from)
+        .and_then(|()| {
+            vault.with_state(|state| {
+                repo_config_backup::selectors::select_info(state, backup_id)
+                    .and_then(|info| info.config)
+                    .map(|config| RepoConfigJson {
+                        name: config.name.0.clone(),
+                        mount_id: config.location.mount_id.0.clone(),
+                        path: config.location.path.0.clone(),
+                        password: config.password.to_string(),
+                        salt: config.salt.clone(),
+                        rclone_config: config.rclone_config.to_string(),
+                    })
+                    .ok_or_else(|| CliError::new(CliErrorKind::Error, "Config not generated"))
+            })
+        });
+
+    vault.repo_config_backup_destroy(backup_id);
+
+    output.print(&res?, |config| {
+        format!(
+            "Name: {}\nLocation: {}:{}\nPassword: {}\nSalt: {}\n\nrclone config:\n\n{}",
+            config.name,
+            config.mount_id,
+            config.path,
+            config.password,
+            config.salt.as_deref().unwrap_or(""),
+            config.rclone_config,
+        )
+    });
+
+    Ok(())
+}
+
+pub async fn space_usage(vault: &Vault, output: Output, args: &RepoArgs) -> Result<(), CliError> {
+    let repo_id = find_repo(vault, args.repo.as_deref())?;
+
+    let usage_id = vault.repo_space_usage_create(repo_id);
+
+    let res = match vault.repo_space_usage_calculate(usage_id).await {
+        Ok(()) => Ok(vault.with_state(|state| {
+            repo_space_usage::selectors::select_info(state, usage_id)
+                .and_then(|info| info.space_used)
+                .unwrap_or(0)
+        })),
+        Err(err) => Err(CliError::from(err)),
+    };
+
+    vault.repo_space_usage_destroy(usage_id);
+
+    output.print(&RepoSpaceUsageJson { space_used: res? }, |usage| {
+        size_display(usage.space_used)
+    });
+
+    Ok(())
+}
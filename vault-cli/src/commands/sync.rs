@@ -0,0 +1,193 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+use vault_core::{
+    files::file_size::size_display,
+    sync::{
+        local::BoxSyncLocal,
+        state::{
+            SyncAction, SyncActionReason, SyncActionType, SyncConfig, SyncDirection, SyncPlan,
+        },
+    },
+    user_error::UserError,
+    Vault,
+};
+use vault_native::sync_local::FileSyncLocal;
+
+use crate::{
+    args::{RepoArgs, SyncArgs, SyncDirectionArg},
+    errors::{CliError, CliErrorKind},
+    output::Output,
+    progress::TransfersProgress,
+    repo::{open_repo, parse_path},
+};
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncActionJson {
+    pub action: &'static str,
+    pub reason: &'static str,
+    pub path: String,
+    pub size: i64,
+    pub error: Option<String>,
+}
+
+impl SyncActionJson {
+    fn new(action: &SyncAction, error: Option<String>) -> Self {
+        Self {
+            action: match action.typ {
+                SyncActionType::Upload => "upload",
+                SyncActionType::Download => "download",
+                SyncActionType::DeleteLocal => "deleteLocal",
+                SyncActionType::DeleteRemote => "deleteRemote",
+            },
+            reason: match action.reason {
+                SyncActionReason::New => "new",
+                SyncActionReason::Changed => "changed",
+                SyncActionReason::Deleted => "deleted",
+                SyncActionReason::Conflict => "conflict",
+            },
+            path: action.path.0.clone(),
+            size: action.size,
+            error,
+        }
+    }
+
+    fn to_text(&self) -> String {
+        match &self.error {
+            Some(error) => format!("{:<13} {}: {}", self.action, self.path, error),
+            None => format!("{:<13} {}", self.action, self.path),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncResultJson {
+    pub dry_run: bool,
+    pub actions: Vec<SyncActionJson>,
+    pub unchanged_count: usize,
+    pub transfer_bytes: i64,
+}
+
+impl SyncResultJson {
+    fn new(plan: &SyncPlan, actions: Vec<SyncActionJson>, dry_run: bool) -> Self {
+        Self {
+            dry_run,
+            actions,
+            unchanged_count: plan.unchanged_count,
+            transfer_bytes: plan.transfer_bytes(),
+        }
+    }
+
+    fn to_text(&self) -> String {
+        let mut lines = self
+            .actions
+            .iter()
+            .map(SyncActionJson::to_text)
+            .collect::<Vec<_>>();
+
+        lines.push(format!(
+            "{} {}, {} unchanged, {} to transfer",
+            self.actions.len(),
+            if self.dry_run {
+                "planned changes"
+            } else {
+                "changes"
+            },
+            self.unchanged_count,
+            size_display(self.transfer_bytes)
+        ));
+
+        lines.join("\n")
+    }
+}
+
+pub async fn sync(
+    vault: Arc<Vault>,
+    output: Output,
+    args: &RepoArgs,
+    sync_args: SyncArgs,
+) -> Result<(), CliError> {
+    let repo_id = open_repo(&vault, args)?;
+
+    if !sync_args.local_path.is_dir() {
+        return Err(CliError::new(
+            CliErrorKind::Usage,
+            format!("Not a dir: {}", sync_args.local_path.display()),
+        ));
+    }
+
+    let local_path = sync_args
+        .local_path
+        .canonicalize()
+        .map_err(|err| CliError::new(CliErrorKind::Error, err.to_string()))?;
+
+    let config = SyncConfig {
+        repo_id,
+        remote_path: parse_path(&sync_args.to)?,
+        direction: match sync_args.direction {
+            SyncDirectionArg::Upload => SyncDirection::Upload,
+            SyncDirectionArg::Download => SyncDirection::Download,
+            SyncDirectionArg::TwoWay => SyncDirection::TwoWay,
+        },
+    };
+
+    let local: BoxSyncLocal = Box::new(FileSyncLocal::new(local_path));
+
+    if sync_args.reset {
+        vault.sync_reset(&config, &local).await?;
+    }
+
+    if sync_args.dry_run {
+        let plan = vault.sync_plan(&config, &local).await?;
+
+        let actions = plan
+            .actions
+            .iter()
+            .map(|action| SyncActionJson::new(action, None))
+            .collect();
+
+        output.print(&SyncResultJson::new(&plan, actions, true), |result| {
+            result.to_text()
+        });
+
+        return Ok(());
+    }
+
+    let progress = TransfersProgress::new(vault.clone(), output.json);
+
+    let res = vault.sync_run(&config, &local).await;
+
+    drop(progress);
+
+    let result = res?;
+
+    let actions = result
+        .plan
+        .actions
+        .iter()
+        .map(|action| {
+            let error = result
+                .errors
+                .iter()
+                .find(|error| &error.action == action)
+                .map(|error| error.error.user_error());
+
+            SyncActionJson::new(action, error)
+        })
+        .collect();
+
+    output.print(
+        &SyncResultJson::new(&result.plan, actions, false),
+        |result| result.to_text(),
+    );
+
+    match result.errors.len() {
+        0 => Ok(()),
+        failed => Err(CliError::new(
+            CliErrorKind::Error,
+            format!("{} of {} changes failed", failed, result.plan.actions.len()),
+        )),
+    }
+}
@@ -0,0 +1,48 @@
+use std::sync::Arc;
+
+use vault_core::{user_error::UserError, Vault};
+use vault_native::auto_upload::{config::AutoUploadConfig, AutoUploader};
+
+use crate::{
+    args::{RepoArgs, WatchArgs},
+    errors::{CliError, CliErrorKind},
+    output::Output,
+    progress::TransfersProgress,
+    repo::{open_repo, parse_path},
+};
+
+/// Runs until interrupted. Files that were already uploaded are remembered
+/// so they are not uploaded again on the next run.
+pub async fn watch(
+    vault: Arc<Vault>,
+    output: Output,
+    args: &RepoArgs,
+    watch_args: WatchArgs,
+) -> Result<(), CliError> {
+    let repo_id = open_repo(&vault, args)?;
+
+    let local_path = watch_args
+        .local_path
+        .canonicalize()
+        .map_err(|err| CliError::new(CliErrorKind::Usage, err.to_string()))?;
+
+    let mut config = AutoUploadConfig::new(local_path, repo_id, parse_path(&watch_args.to)?);
+    config.include = watch_args.include;
+    config.exclude = watch_args.exclude;
+    config.delete_after_upload = watch_args.delete_after_upload;
+
+    let _progress = TransfersProgress::new(vault.clone(), output.json);
+
+    let _auto_uploader =
+        AutoUploader::start(vault, &tokio::runtime::Handle::current(), config.clone())
+            .map_err(|err| CliError::new(CliErrorKind::Usage, err.user_error()))?;
+
+    output.info(&format!(
+        "Watching {}. Press Ctrl+C to stop.",
+        config.local_path.display()
+    ));
+
+    tokio::signal::ctrl_c()
+        .await
+        .map_err(|err| CliError::new(CliErrorKind::Error, err.to_string()))
+}
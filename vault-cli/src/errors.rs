@@ -0,0 +1,350 @@
+use serde::Serialize;
+use thiserror::Error;
+
+use vault_core::{
+    lifecycle::errors::{OAuth2FinishDeviceFlowError, OAuth2FinishFlowUrlError, OnLoginError},
+    oauth2::errors::OAuth2Error,
+    remote::{ApiErrorCode, RemoteError},
+    repo_files::errors::{
+        CreateDirError, DeleteFileError, EnsureDirError, LoadFileError, LoadFilesError,
+        MoveFileError,
+    },
+    repo_files_offline::errors::OfflineError,
+    repo_files_read::errors::GetFilesReaderError,
+    repo_space_usage::errors::RepoSpaceUsageError,
+    repos::errors::{GetCipherError, LoadReposError, UnlockRepoError},
+    sync::errors::SyncError,
+    transfers::errors::TransferError,
+    user_error::UserError,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CliErrorKind {
+    Error,
+    Usage,
+    NotAuthenticated,
+    NotFound,
+    AlreadyExists,
+    InvalidPassword,
+    RepoLocked,
+    RemoteError,
+    RepoReadOnly,
+}
+
+impl CliErrorKind {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Error => 1,
+            Self::Usage => 2,
+            Self::NotAuthenticated => 3,
+            Self::NotFound => 4,
+            Self::AlreadyExists => 5,
+            Self::InvalidPassword => 6,
+            Self::RepoLocked => 7,
+            Self::RemoteError => 8,
+            Self::RepoReadOnly => 9,
+        }
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq)]
+#[error("{message}")]
+pub struct CliError {
+    pub kind: CliErrorKind,
+    pub message: String,
+}
+
+impl CliError {
+    pub fn new(kind: CliErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+
+    pub fn from_user_error(kind: CliErrorKind, err: &impl UserError) -> Self {
+        Self::new(kind, err.user_error())
+    }
+
+    pub fn exit_code(&self) -> i32 {
+        self.kind.exit_code()
+    }
+}
+
+pub fn remote_error_kind(err: &RemoteError) -> CliErrorKind {
+    match err {
+        RemoteError::ApiError { code, .. } => match code {
+            ApiErrorCode::NotFound => CliErrorKind::NotFound,
+            ApiErrorCode::AlreadyExists => CliErrorKind::AlreadyExists,
+            _ => CliErrorKind::RemoteError,
+        },
+        RemoteError::UnexpectedStatus {
+            status_code: 401, ..
+        } => CliErrorKind::NotAuthenticated,
+        _ => CliErrorKind::RemoteError,
+    }
+}
+
+impl From<OAuth2Error> for CliError {
+    fn from(err: OAuth2Error) -> Self {
+        let kind = match &err {
+            OAuth2Error::HttpError(_) => CliErrorKind::RemoteError,
+            OAuth2Error::StorageError(_) | OAuth2Error::Unknown(_) => CliErrorKind::Error,
+            _ => CliErrorKind::NotAuthenticated,
+        };
+
+        Self::from_user_error(kind, &err)
+    }
+}
+
+impl From<OnLoginError> for CliError {
+    fn from(err: OnLoginError) -> Self {
+        match err {
+            OnLoginError::LoadUserError(err) | OnLoginError::LoadSpaceUsageError(err) => {
+                Self::from_user_error(remote_error_kind(&err), &err)
+            }
+            OnLoginError::LoadReposError(err) => err.into(),
+        }
+    }
+}
+
+impl From<OAuth2FinishFlowUrlError> for CliError {
+    fn from(err: OAuth2FinishFlowUrlError) -> Self {
+        match err {
+            OAuth2FinishFlowUrlError::OAuth2Error(err) => err.into(),
+            OAuth2FinishFlowUrlError::OnLoginError(err) => err.into(),
+            OAuth2FinishFlowUrlError::OnLogoutError(err) => {
+                Self::from_user_error(CliErrorKind::Error, &err)
+            }
+        }
+    }
+}
+
+impl From<OAuth2FinishDeviceFlowError> for CliError {
+    fn from(err: OAuth2FinishDeviceFlowError) -> Self {
+        match err {
+            OAuth2FinishDeviceFlowError::OAuth2Error(err) => err.into(),
+            OAuth2FinishDeviceFlowError::OnLoginError(err) => err.into(),
+        }
+    }
+}
+
+impl From<LoadReposError> for CliError {
+    fn from(err: LoadReposError) -> Self {
+        let kind = match &err {
+            LoadReposError::StorageError(_) => CliErrorKind::Error,
+            LoadReposError::RemoteError(err) => remote_error_kind(err),
+        };
+
+        Self::from_user_error(kind, &err)
+    }
+}
+
+impl From<UnlockRepoError> for CliError {
+    fn from(err: UnlockRepoError) -> Self {
+        let kind = match &err {
+            UnlockRepoError::RepoNotFound(_) => CliErrorKind::NotFound,
+            UnlockRepoError::RepoUnlocked(_) => CliErrorKind::Error,
+            UnlockRepoError::InvalidPassword(_) => CliErrorKind::InvalidPassword,
+            UnlockRepoError::TooManyAttempts(_) => CliErrorKind::Error,
+        };
+
+        Self::from_user_error(kind, &err)
+    }
+}
+
+impl From<GetCipherError> for CliError {
+    fn from(err: GetCipherError) -> Self {
+        let kind = match &err {
+            GetCipherError::RepoNotFound(_) => CliErrorKind::NotFound,
+            GetCipherError::RepoLocked(_) => CliErrorKind::RepoLocked,
+        };
+
+        Self::from_user_error(kind, &err)
+    }
+}
+
+impl From<LoadFilesError> for CliError {
+    fn from(err: LoadFilesError) -> Self {
+        let kind = match &err {
+            LoadFilesError::RepoNotFound(_) => CliErrorKind::NotFound,
+            LoadFilesError::RepoLocked(_) => CliErrorKind::RepoLocked,
+            LoadFilesError::RemoteError(err) => remote_error_kind(err),
+        };
+
+        Self::from_user_error(kind, &err)
+    }
+}
+
+impl From<LoadFileError> for CliError {
+    fn from(err: LoadFileError) -> Self {
+        let kind = match &err {
+            LoadFileError::RepoNotFound(_) => CliErrorKind::NotFound,
+            LoadFileError::RepoLocked(_) => CliErrorKind::RepoLocked,
+            LoadFileError::RemoteError(err) => remote_error_kind(err),
+        };
+
+        Self::from_user_error(kind, &err)
+    }
+}
+
+impl From<EnsureDirError> for CliError {
+    fn from(err: EnsureDirError) -> Self {
+        let kind = match &err {
+            EnsureDirError::RepoNotFound(_) => CliErrorKind::NotFound,
+            EnsureDirError::RepoLocked(_) => CliErrorKind::RepoLocked,
+            EnsureDirError::RepoReadOnly(_) => CliErrorKind::RepoReadOnly,
+            EnsureDirError::RemoteError(err) => remote_error_kind(err),
+            _ => CliErrorKind::Error,
+        };
+
+        Self::from_user_error(kind, &err)
+    }
+}
+
+impl From<CreateDirError> for CliError {
+    fn from(err: CreateDirError) -> Self {
+        let kind = match &err {
+            CreateDirError::RepoNotFound(_) => CliErrorKind::NotFound,
+            CreateDirError::RepoLocked(_) => CliErrorKind::RepoLocked,
+            CreateDirError::RepoReadOnly(_) => CliErrorKind::RepoReadOnly,
+            CreateDirError::RemoteError(err) => remote_error_kind(err),
+            _ => CliErrorKind::Error,
+        };
+
+        Self::from_user_error(kind, &err)
+    }
+}
+
+impl From<DeleteFileError> for CliError {
+    fn from(err: DeleteFileError) -> Self {
+        let kind = match &err {
+            DeleteFileError::RepoNotFound(_) => CliErrorKind::NotFound,
+            DeleteFileError::RepoLocked(_) => CliErrorKind::RepoLocked,
+            DeleteFileError::RepoReadOnly(_) => CliErrorKind::RepoReadOnly,
+            DeleteFileError::RemoteError(err) => remote_error_kind(err),
+            DeleteFileError::Canceled => CliErrorKind::Error,
+        };
+
+        Self::from_user_error(kind, &err)
+    }
+}
+
+impl From<MoveFileError> for CliError {
+    fn from(err: MoveFileError) -> Self {
+        let kind = match &err {
+            MoveFileError::InvalidPath | MoveFileError::MoveRoot => CliErrorKind::Usage,
+            MoveFileError::RepoNotFound(_) => CliErrorKind::NotFound,
+            MoveFileError::RepoLocked(_) => CliErrorKind::RepoLocked,
+            MoveFileError::RepoReadOnly(_) => CliErrorKind::RepoReadOnly,
+            MoveFileError::RemoteError(err) => remote_error_kind(err),
+            MoveFileError::DecryptFilenameError(_) => CliErrorKind::Error,
+        };
+
+        Self::from_user_error(kind, &err)
+    }
+}
+
+impl From<GetFilesReaderError> for CliError {
+    fn from(err: GetFilesReaderError) -> Self {
+        let kind = match &err {
+            GetFilesReaderError::RepoNotFound(_) | GetFilesReaderError::FileNotFound => {
+                CliErrorKind::NotFound
+            }
+            GetFilesReaderError::RepoLocked(_) => CliErrorKind::RepoLocked,
+            GetFilesReaderError::RemoteError(err) => remote_error_kind(err),
+            _ => CliErrorKind::Error,
+        };
+
+        Self::from_user_error(kind, &err)
+    }
+}
+
+impl From<TransferError> for CliError {
+    fn from(err: TransferError) -> Self {
+        let kind = match &err {
+            TransferError::RepoNotFound(_) | TransferError::RemoteFileNotFound => {
+                CliErrorKind::NotFound
+            }
+            TransferError::RepoLocked(_) => CliErrorKind::RepoLocked,
+            TransferError::RepoReadOnly(_) => CliErrorKind::RepoReadOnly,
+            TransferError::AlreadyExists => CliErrorKind::AlreadyExists,
+            TransferError::RemoteError(err) => remote_error_kind(err),
+            _ => CliErrorKind::Error,
+        };
+
+        Self::from_user_error(kind, &err)
+    }
+}
+
+impl From<RepoSpaceUsageError> for CliError {
+    fn from(err: RepoSpaceUsageError) -> Self {
+        let kind = match &err {
+            RepoSpaceUsageError::RepoNotFound(_) => CliErrorKind::NotFound,
+            RepoSpaceUsageError::RemoteError(err) => remote_error_kind(err),
+        };
+
+        Self::from_user_error(kind, &err)
+    }
+}
+
+impl From<OfflineError> for CliError {
+    fn from(err: OfflineError) -> Self {
+        let kind = match &err {
+            OfflineError::RepoNotFound(_) => CliErrorKind::NotFound,
+            OfflineError::RepoLocked(_) => CliErrorKind::RepoLocked,
+            OfflineError::RemoteError(err) => remote_error_kind(err),
+            _ => CliErrorKind::Error,
+        };
+
+        Self::from_user_error(kind, &err)
+    }
+}
+
+impl From<SyncError> for CliError {
+    fn from(err: SyncError) -> Self {
+        let kind = match &err {
+            SyncError::RepoNotFound(_) => CliErrorKind::NotFound,
+            SyncError::RepoLocked(_) => CliErrorKind::RepoLocked,
+            SyncError::InvalidPath(_) => CliErrorKind::Usage,
+            SyncError::RemoteError(err) => remote_error_kind(err),
+            SyncError::TransferError(err) => CliError::from(err.clone()).kind,
+            _ => CliErrorKind::Error,
+        };
+
+        Self::from_user_error(kind, &err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use similar_asserts::assert_eq;
+
+    use vault_core::{
+        remote::{ApiErrorCode, RemoteError},
+        repo_files::errors::LoadFilesError,
+        repos::errors::{InvalidPasswordError, RepoLockedError, UnlockRepoError},
+    };
+
+    use super::{CliError, CliErrorKind};
+
+    #[test]
+    fn test_cli_error_from() {
+        let err: CliError = UnlockRepoError::InvalidPassword(InvalidPasswordError).into();
+        assert_eq!(err.kind, CliErrorKind::InvalidPassword);
+        assert_eq!(err.exit_code(), 6);
+
+        let err: CliError = LoadFilesError::RepoLocked(RepoLockedError).into();
+        assert_eq!(err.kind, CliErrorKind::RepoLocked);
+        assert_eq!(err.message, "Safe Box is locked");
+
+        let err: CliError = LoadFilesError::RemoteError(RemoteError::from_code(
+            ApiErrorCode::NotFound,
+            "Not found",
+        ))
+        .into();
+        assert_eq!(err.kind, CliErrorKind::NotFound);
+        assert_eq!(err.exit_code(), 4);
+    }
+}
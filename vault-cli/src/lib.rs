@@ -0,0 +1,7 @@
+pub mod app;
+pub mod args;
+pub mod commands;
+pub mod errors;
+pub mod output;
+pub mod progress;
+pub mod repo;
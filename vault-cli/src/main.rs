@@ -0,0 +1,34 @@
+use std::sync::Arc;
+
+use clap::Parser;
+use vault_cli::{
+    app::{build_cli_vault, CliConfig},
+    args::Args,
+    commands,
+    output::Output,
+};
+
+fn main() {
+    env_logger::init();
+
+    let args = Args::parse();
+
+    let output = Output { json: args.json };
+
+    let tokio_runtime = Arc::new(tokio::runtime::Runtime::new().unwrap());
+
+    let res = build_cli_vault(
+        CliConfig {
+            base_url: args.base_url,
+            data_path: args.data_path,
+        },
+        tokio_runtime.clone(),
+    )
+    .and_then(|vault| tokio_runtime.block_on(commands::run(vault, output, args.command)));
+
+    if let Err(err) = res {
+        output.print_error(&err);
+
+        std::process::exit(err.exit_code());
+    }
+}
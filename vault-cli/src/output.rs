@@ -0,0 +1,58 @@
+use serde::Serialize;
+
+use crate::errors::{CliError, CliErrorKind};
+
+#[derive(Debug, Clone, Serialize)]
+struct ErrorJson<'a> {
+    error: ErrorJsonError<'a>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ErrorJsonError<'a> {
+    kind: CliErrorKind,
+    message: &'a str,
+}
+
+/// Results are printed to stdout, errors and progress to stderr.
+#[derive(Debug, Clone, Copy)]
+pub struct Output {
+    pub json: bool,
+}
+
+impl Output {
+    pub fn print<T: Serialize>(&self, value: &T, text: impl FnOnce(&T) -> String) {
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(value).unwrap());
+        } else {
+            let text = text(value);
+
+            if !text.is_empty() {
+                println!("{}", text);
+            }
+        }
+    }
+
+    pub fn print_error(&self, err: &CliError) {
+        if self.json {
+            eprintln!(
+                "{}",
+                serde_json::to_string_pretty(&ErrorJson {
+                    error: ErrorJsonError {
+                        kind: err.kind,
+                        message: &err.message,
+                    },
+                })
+                .unwrap()
+            );
+        } else {
+            eprintln!("Error: {}", err.message);
+        }
+    }
+
+    /// Messages for the user that are not part of the result.
+    pub fn info(&self, message: &str) {
+        if !self.json {
+            eprintln!("{}", message);
+        }
+    }
+}
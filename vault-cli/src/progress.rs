@@ -0,0 +1,132 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use indicatif::{ProgressBar, ProgressStyle};
+use vault_core::{
+    store,
+    transfers::{errors::TransferError, selectors, state::TransferState},
+    Vault,
+};
+
+/// Shows the progress of all transfers in a single progress bar on stderr.
+///
+/// Transfers that failed and will not be retried automatically wait for a
+/// manual retry, so their result futures never resolve. They are aborted
+/// and their errors are kept so that they can be reported instead of
+/// `TransferError::Aborted`.
+pub struct TransfersProgress {
+    vault: Arc<Vault>,
+    subscription_id: u32,
+    progress_bar: ProgressBar,
+    errors: Arc<Mutex<HashMap<u32, TransferError>>>,
+}
+
+impl TransfersProgress {
+    pub fn new(vault: Arc<Vault>, hidden: bool) -> Self {
+        let progress_bar = if hidden {
+            ProgressBar::hidden()
+        } else {
+            ProgressBar::new(0)
+        };
+
+        progress_bar.set_style(
+            ProgressStyle::with_template(
+                "{msg} [{bar:40}] {bytes}/{total_bytes} {bytes_per_sec} ({eta})",
+            )
+            .unwrap()
+            .progress_chars("=> "),
+        );
+
+        let errors = Arc::new(Mutex::new(HashMap::new()));
+
+        let subscription_id = vault.get_next_id();
+
+        let callback_vault = vault.clone();
+        let callback_progress_bar = progress_bar.clone();
+        let callback_errors = errors.clone();
+
+        vault.on(
+            subscription_id,
+            &[store::Event::Transfers],
+            Box::new(move |_, add_side_effect| {
+                let (done_bytes, total_bytes, remaining_count, failed) =
+                    callback_vault.with_state(|state| {
+                        let transfers = selectors::select_transfers(state);
+
+                        let transferring_bytes: i64 = transfers
+                            .iter()
+                            .filter(|transfer| {
+                                matches!(transfer.state, TransferState::Transferring)
+                            })
+                            .map(|transfer| transfer.transferred_bytes)
+                            .sum();
+
+                        let failed = transfers
+                            .iter()
+                            .filter_map(|transfer| match &transfer.state {
+                                TransferState::Failed { error }
+                                    if !selectors::can_retry(transfer)
+                                        || !selectors::select_can_autoretry(state, transfer) =>
+                                {
+                                    Some((transfer.id, error.clone()))
+                                }
+                                _ => None,
+                            })
+                            .collect::<Vec<_>>();
+
+                        (
+                            selectors::select_bytes_done(state) + transferring_bytes,
+                            state.transfers.total_bytes,
+                            state.transfers.total_count
+                                - state.transfers.done_count
+                                - state.transfers.failed_count,
+                            failed,
+                        )
+                    });
+
+                callback_progress_bar.set_length(total_bytes.max(0) as u64);
+                callback_progress_bar.set_position(done_bytes.max(0) as u64);
+                callback_progress_bar.set_message(format!("{} remaining", remaining_count));
+
+                if !failed.is_empty() {
+                    let side_effect_vault = callback_vault.clone();
+                    let side_effect_errors = callback_errors.clone();
+
+                    add_side_effect(Box::new(move || {
+                        for (id, error) in failed {
+                            side_effect_errors.lock().unwrap().insert(id, error);
+
+                            side_effect_vault.transfers_abort(id);
+                        }
+                    }));
+                }
+            }),
+        );
+
+        Self {
+            vault,
+            subscription_id,
+            progress_bar,
+            errors,
+        }
+    }
+
+    /// Replaces `TransferError::Aborted` with the error the transfer failed
+    /// with.
+    pub fn map_error(&self, id: u32, err: TransferError) -> TransferError {
+        match err {
+            TransferError::Aborted => self.errors.lock().unwrap().remove(&id).unwrap_or(err),
+            err => err,
+        }
+    }
+}
+
+impl Drop for TransfersProgress {
+    fn drop(&mut self) {
+        self.vault.remove_listener(self.subscription_id);
+
+        self.progress_bar.finish_and_clear();
+    }
+}
@@ -0,0 +1,131 @@
+use vault_core::{
+    repo_unlock::state::RepoUnlockOptions,
+    repos::selectors as repos_selectors,
+    repos::state::RepoUnlockMode,
+    types::{DecryptedPath, EncryptedPath, RepoId},
+    utils::repo_path_utils,
+    Vault,
+};
+
+use crate::{
+    args::RepoArgs,
+    errors::{CliError, CliErrorKind},
+};
+
+/// Finds the repo by ID or name. If there is only one repo, it can be
+/// omitted.
+pub fn find_repo(vault: &Vault, repo: Option<&str>) -> Result<RepoId, CliError> {
+    vault.with_state(|state| {
+        let repos = repos_selectors::select_repos(state);
+
+        match repo {
+            Some(repo) => {
+                let matching = repos
+                    .iter()
+                    .filter(|r| r.id.0 == repo || r.name.0 == repo)
+                    .collect::<Vec<_>>();
+
+                match matching.as_slice() {
+                    [r] => Ok(r.id.clone()),
+                    [] => Err(CliError::new(
+                        CliErrorKind::NotFound,
+                        format!("Safe Box not found: {}", repo),
+                    )),
+                    _ => Err(CliError::new(
+                        CliErrorKind::Usage,
+                        format!("Multiple Safe Boxes named {}. Use the ID instead.", repo),
+                    )),
+                }
+            }
+            None => match repos.as_slice() {
+                [r] => Ok(r.id.clone()),
+                [] => Err(CliError::new(CliErrorKind::NotFound, "No Safe Boxes found")),
+                _ => Err(CliError::new(
+                    CliErrorKind::Usage,
+                    "Multiple Safe Boxes found. Use --repo to select one.",
+                )),
+            },
+        }
+    })
+}
+
+pub fn get_password(password: Option<&str>) -> Result<String, CliError> {
+    match password {
+        Some(password) => Ok(password.to_owned()),
+        None => rpassword::prompt_password("Safe Box password: ")
+            .map_err(|err| CliError::new(CliErrorKind::Usage, err.to_string())),
+    }
+}
+
+pub fn unlock_repo(
+    vault: &Vault,
+    repo_id: &RepoId,
+    password: &str,
+    mode: RepoUnlockMode,
+) -> Result<(), CliError> {
+    let unlock_id = vault.repo_unlock_create(repo_id.clone(), RepoUnlockOptions { mode });
+
+    let res = vault.repo_unlock_unlock(unlock_id, password);
+
+    vault.repo_unlock_destroy(unlock_id);
+
+    Ok(res?)
+}
+
+/// Repos are unlocked only for the current process so the password is needed
+/// for every command that reads or writes files.
+pub fn open_repo(vault: &Vault, args: &RepoArgs) -> Result<RepoId, CliError> {
+    let repo_id = find_repo(vault, args.repo.as_deref())?;
+
+    let password = get_password(args.password.as_deref())?;
+
+    let mode = if args.read_only {
+        RepoUnlockMode::UnlockReadOnly
+    } else {
+        RepoUnlockMode::Unlock
+    };
+
+    unlock_repo(vault, &repo_id, &password, mode)?;
+
+    Ok(repo_id)
+}
+
+pub fn parse_path(path: &str) -> Result<DecryptedPath, CliError> {
+    repo_path_utils::normalize_path(&DecryptedPath(path.to_owned()))
+        .map_err(|err| CliError::new(CliErrorKind::Usage, format!("{}: {}", err, path)))
+}
+
+pub fn encrypt_path(
+    vault: &Vault,
+    repo_id: &RepoId,
+    path: &DecryptedPath,
+) -> Result<EncryptedPath, CliError> {
+    let cipher = vault.repos_service.get_cipher(repo_id)?;
+
+    Ok(cipher.encrypt_path(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use similar_asserts::assert_eq;
+
+    use vault_core::types::DecryptedPath;
+
+    use crate::errors::CliErrorKind;
+
+    use super::parse_path;
+
+    #[test]
+    fn test_parse_path() {
+        assert_eq!(parse_path("").unwrap(), DecryptedPath("/".into()));
+        assert_eq!(parse_path("docs/").unwrap(), DecryptedPath("/docs".into()));
+        assert_eq!(
+            parse_path("/docs//a.txt").unwrap(),
+            DecryptedPath("/docs/a.txt".into())
+        );
+        assert_eq!(
+            parse_path("/docs/../a").unwrap_err().kind,
+            CliErrorKind::Usage
+        );
+    }
+}
@@ -7,6 +7,7 @@ use vault_fake_remote::fake_remote::{
     self,
     app_state::AppState,
     errors::FakeRemoteServerStartError,
+    faults::FaultProfile,
     files::objects::{
         memory_object_provider::MemoryObjectProvider, object_provider::BoxObjectProvider,
     },
@@ -33,7 +34,7 @@ impl FakeRemote {
         let object_provider: Arc<BoxObjectProvider> =
             Arc::new(Box::new(MemoryObjectProvider::new()));
 
-        let mut app_state = AppState::new(object_provider);
+        let mut app_state = AppState::new(object_provider, None);
 
         let interceptor_container = Arc::new(InterceptorContainer {
             interceptor: Arc::new(Mutex::new(None)),
@@ -86,6 +87,10 @@ impl FakeRemote {
     pub fn intercept(&self, interceptor: Interceptor) {
         *self.interceptor_container.interceptor.lock().unwrap() = Some(interceptor)
     }
+
+    pub fn set_fault_profile(&self, profile: Option<FaultProfile>) {
+        self.app_state.fault_injector.set_profile(profile)
+    }
 }
 
 impl Drop for FakeRemote {
@@ -88,6 +88,15 @@ impl RepoFixture {
             .unwrap();
     }
 
+    /// Locks the repo and unlocks it again in read-only mode.
+    pub fn unlock_read_only(&self) {
+        self.lock();
+        self.vault
+            .repos_service
+            .unlock_repo(&self.repo_id, "password", RepoUnlockMode::UnlockReadOnly)
+            .unwrap();
+    }
+
     pub fn lock(&self) {
         self.vault.repos_service.lock_repo(&self.repo_id).unwrap();
     }
@@ -148,12 +157,21 @@ impl RepoFixture {
         &self,
         path: &str,
         content: &str,
+    ) -> (RepoFilesUploadResult, RepoFile) {
+        self.upload_file_bytes(path, content.as_bytes().to_vec())
+            .await
+    }
+
+    pub async fn upload_file_bytes(
+        &self,
+        path: &str,
+        bytes: Vec<u8>,
     ) -> (RepoFilesUploadResult, RepoFile) {
         let cipher = self.vault.repos_service.get_cipher(&self.repo_id).unwrap();
         let path = cipher.encrypt_path(&DecryptedPath(path.to_owned()));
         let (parent_path, name) = repo_encrypted_path_utils::split_parent_name(&path).unwrap();
 
-        self.upload_file_encrypted(&parent_path, name, content)
+        self.upload_file_encrypted_bytes(&parent_path, name, bytes)
             .await
     }
 
@@ -163,7 +181,16 @@ impl RepoFixture {
         name: EncryptedName,
         content: &str,
     ) -> (RepoFilesUploadResult, RepoFile) {
-        let bytes = content.as_bytes().to_vec();
+        self.upload_file_encrypted_bytes(parent_path, name, content.as_bytes().to_vec())
+            .await
+    }
+
+    pub async fn upload_file_encrypted_bytes(
+        &self,
+        parent_path: &EncryptedPath,
+        name: EncryptedName,
+        bytes: Vec<u8>,
+    ) -> (RepoFilesUploadResult, RepoFile) {
         let size = bytes.len();
         let reader = Box::pin(Cursor::new(bytes));
 
@@ -31,8 +31,9 @@ impl VaultFixture {
             base_url: fake_remote_fixture.base_url.clone(),
             auth_base_url: fake_remote_fixture.base_url.clone(),
             client_id: "7ZEK2BNCEVYEJIZC5OR3TR6PQDUJ4NP3".into(),
-            client_secret: "VWTMENEWUYWH6G523CEV5CWOCHH7FMECW36PPQENOASYYZOQJOSGQXSR2Y62N3HB"
-                .into(),
+            client_secret: Some(
+                "VWTMENEWUYWH6G523CEV5CWOCHH7FMECW36PPQENOASYYZOQJOSGQXSR2Y62N3HB".into(),
+            ),
             redirect_uri: "http://127.0.0.1:5173/oauth2callback".into(),
         };
 
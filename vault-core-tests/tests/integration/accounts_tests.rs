@@ -0,0 +1,207 @@
+use std::sync::{Arc, Mutex};
+
+use futures::FutureExt;
+use vault_core::{
+    accounts::{self, errors::AccountsError},
+    lifecycle::errors::SwitchAccountError,
+    repos,
+    types::{RemoteName, RepoId},
+    utils::remote_path_utils,
+};
+use vault_core_tests::{
+    fixtures::{repo_fixture::RepoFixture, user_fixture::UserFixture},
+    helpers::with_repo,
+};
+use vault_fake_remote::fake_remote::interceptor::InterceptorResult;
+
+#[test]
+fn test_add_switch_remove() {
+    with_repo(|fixture| {
+        async move {
+            let user_id1 = fixture.user_fixture.user_id.clone();
+
+            let repo_ids = |fixture: &RepoFixture| {
+                fixture.vault.with_state(|state| {
+                    repos::selectors::select_repos(state)
+                        .iter()
+                        .map(|repo| repo.id.clone())
+                        .collect::<Vec<_>>()
+                })
+            };
+            let accounts = |fixture: &RepoFixture| {
+                fixture.vault.with_state(|state| {
+                    accounts::selectors::select_accounts(state)
+                        .iter()
+                        .map(|info| (info.account.user_id.clone(), info.is_active))
+                        .collect::<Vec<_>>()
+                })
+            };
+
+            assert_eq!(accounts(&fixture), vec![(user_id1.clone(), true)]);
+            assert_eq!(repo_ids(&fixture), vec![fixture.repo_id.clone()]);
+
+            let user_fixture2 = UserFixture::create(fixture.user_fixture.vault_fixture.clone());
+            let user_id2 = user_fixture2.user_id.clone();
+
+            assert!(fixture
+                .vault
+                .accounts_add()
+                .unwrap()
+                .contains("/oauth2/auth"));
+            user_fixture2.login();
+            user_fixture2.load().await;
+
+            assert_eq!(
+                accounts(&fixture),
+                vec![(user_id1.clone(), false), (user_id2.clone(), true)]
+            );
+            // repos of the other account are kept locked but not listed
+            assert!(repo_ids(&fixture).is_empty());
+            assert!(fixture.vault.with_state(|state| {
+                state
+                    .repos
+                    .repos_by_id
+                    .get(&fixture.repo_id)
+                    .map(|repo| repo.state.is_locked())
+                    .unwrap_or(false)
+            }));
+
+            assert!(matches!(
+                fixture.vault.accounts_remove(&user_id2),
+                Err(AccountsError::ActiveAccount)
+            ));
+
+            fixture.vault.accounts_switch(&user_id1).await.unwrap();
+
+            assert_eq!(
+                accounts(&fixture),
+                vec![(user_id1.clone(), true), (user_id2.clone(), false)]
+            );
+            assert_eq!(repo_ids(&fixture), vec![fixture.repo_id.clone()]);
+            assert_eq!(
+                fixture
+                    .vault
+                    .accounts_service
+                    .get_account_token(&user_id2)
+                    .unwrap()
+                    .access_token,
+                user_fixture2.oauth2_access_token
+            );
+
+            fixture.vault.accounts_remove(&user_id2).unwrap();
+
+            assert_eq!(accounts(&fixture), vec![(user_id1.clone(), true)]);
+            assert!(matches!(
+                fixture.vault.accounts_switch(&user_id2).await,
+                Err(SwitchAccountError::AccountsError(
+                    AccountsError::AccountNotFound(_)
+                ))
+            ));
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_inactive_account_mount_operations() {
+    with_repo(|fixture| {
+        async move {
+            let user_fixture2 = UserFixture::create(fixture.user_fixture.vault_fixture.clone());
+
+            fixture.vault.accounts_add().unwrap();
+            user_fixture2.login();
+            user_fixture2.load().await;
+
+            // requests for the mount of the inactive account use its token
+            let authorizations = Arc::new(Mutex::new(Vec::new()));
+            let intercept_authorizations = authorizations.clone();
+            let mount_path = format!("/mounts/{}/", fixture.mount_id.0);
+
+            fixture.fake_remote.intercept(Box::new(move |parts| {
+                if parts.uri.path().contains(&mount_path) {
+                    intercept_authorizations.lock().unwrap().push(
+                        parts
+                            .headers
+                            .get("Authorization")
+                            .and_then(|value| value.to_str().ok())
+                            .map(str::to_owned),
+                    );
+                }
+
+                InterceptorResult::Ignore
+            }));
+
+            let remote_files_service = fixture.vault.remote_files_service.clone();
+
+            remote_files_service
+                .create_dir_name(&fixture.mount_id, &fixture.path, RemoteName("dir".into()))
+                .await
+                .unwrap();
+
+            let dir_path =
+                remote_path_utils::join_path_name(&fixture.path, &RemoteName("dir".into()));
+
+            remote_files_service
+                .load_files(&fixture.mount_id, &dir_path)
+                .await
+                .unwrap();
+
+            remote_files_service
+                .delete_file(&fixture.mount_id, &dir_path)
+                .await
+                .unwrap();
+
+            let authorizations = authorizations.lock().unwrap().clone();
+            assert_eq!(authorizations.len(), 3);
+            assert!(authorizations.iter().all(|authorization| authorization
+                == &Some(format!(
+                    "Bearer {}",
+                    fixture.user_fixture.oauth2_access_token
+                ))));
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_shared_mount_uses_active_account() {
+    with_repo(|fixture| {
+        async move {
+            let user_id1 = fixture.user_fixture.user_id.clone();
+            let user_fixture2 = UserFixture::create(fixture.user_fixture.vault_fixture.clone());
+            let user_id2 = user_fixture2.user_id.clone();
+
+            fixture.vault.accounts_add().unwrap();
+            user_fixture2.login();
+            user_fixture2.load().await;
+
+            // a repo of the second account on the same mount
+            fixture.vault.store.mutate(|state, _, _, _| {
+                let mut repo = state.repos.repos_by_id[&fixture.repo_id].clone();
+                repo.id = RepoId("shared".into());
+                repo.account_id = Some(user_id2.clone());
+                state.repos.repos_by_id.insert(repo.id.clone(), repo);
+            });
+
+            let inactive_mount_account = |fixture: &RepoFixture| {
+                fixture.vault.with_state(|state| {
+                    accounts::selectors::select_inactive_mount_account(state, &fixture.mount_id)
+                        .map(str::to_owned)
+                })
+            };
+
+            assert_eq!(inactive_mount_account(&fixture), None);
+
+            fixture.vault.accounts_switch(&user_id1).await.unwrap();
+
+            assert_eq!(inactive_mount_account(&fixture), None);
+
+            fixture.vault.store.mutate(|state, _, _, _| {
+                state.repos.repos_by_id.remove(&fixture.repo_id);
+            });
+
+            assert_eq!(inactive_mount_account(&fixture), Some(user_id2));
+        }
+        .boxed()
+    });
+}
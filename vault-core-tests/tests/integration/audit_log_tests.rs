@@ -0,0 +1,291 @@
+use std::sync::{Arc, Mutex};
+
+use futures::FutureExt;
+use vault_core::{
+    audit_log::{
+        errors::VerifyAuditLogError,
+        service::AUDIT_LOG_STORAGE_KEY,
+        state::{AuditLogEntry, AuditLogEvent, AuditLogFilter, RepoLockReason},
+        AuditLogDiskStorage, AuditLogService,
+    },
+    repos::state::{RepoAutoLock, RepoUnlockMode},
+};
+use vault_core_tests::{fixtures::repo_fixture::RepoFixture, helpers::with_repo};
+
+#[derive(Clone, Default)]
+struct MemoryAuditLogDiskStorage {
+    data: Arc<Mutex<Option<Vec<u8>>>>,
+}
+
+impl MemoryAuditLogDiskStorage {
+    fn entries(&self) -> Vec<AuditLogEntry> {
+        serde_json::from_slice(self.data.lock().unwrap().as_ref().unwrap()).unwrap()
+    }
+
+    fn set_entries(&self, entries: &[AuditLogEntry]) {
+        *self.data.lock().unwrap() = Some(serde_json::to_vec(entries).unwrap());
+    }
+}
+
+impl AuditLogDiskStorage for MemoryAuditLogDiskStorage {
+    fn get(&self) -> Result<Option<Vec<u8>>, String> {
+        Ok(self.data.lock().unwrap().clone())
+    }
+
+    fn set(&self, value: &[u8]) -> Result<(), String> {
+        *self.data.lock().unwrap() = Some(value.to_owned());
+
+        Ok(())
+    }
+}
+
+fn set_disk_storage(fixture: &RepoFixture) -> MemoryAuditLogDiskStorage {
+    let disk_storage = MemoryAuditLogDiskStorage::default();
+
+    fixture
+        .vault
+        .audit_log_set_disk_storage(Some(Box::new(disk_storage.clone())));
+
+    disk_storage
+}
+
+fn new_service(fixture: &RepoFixture, disk_storage: &MemoryAuditLogDiskStorage) -> AuditLogService {
+    let audit_log_service = AuditLogService::new(
+        fixture.vault.secure_storage_service.clone(),
+        fixture.vault.store.clone(),
+        fixture.vault.runtime.clone(),
+    );
+
+    audit_log_service.set_disk_storage(Some(Box::new(disk_storage.clone())));
+
+    audit_log_service
+}
+
+#[test]
+fn test_audit_log() {
+    with_repo(|fixture| {
+        async move {
+            let filter = AuditLogFilter {
+                repo_id: Some(fixture.repo_id.clone()),
+                ..Default::default()
+            };
+            let events = || {
+                fixture
+                    .vault
+                    .audit_log_entries(&filter)
+                    .into_iter()
+                    .map(|entry| entry.event)
+                    .collect::<Vec<_>>()
+            };
+
+            fixture.lock();
+            assert!(fixture
+                .vault
+                .repos_service
+                .unlock_repo(&fixture.repo_id, "wrong", RepoUnlockMode::Unlock)
+                .is_err());
+            fixture.unlock();
+            fixture.lock();
+
+            fixture
+                .vault
+                .repos_set_auto_lock(
+                    &fixture.repo_id,
+                    RepoAutoLock {
+                        after: None,
+                        on_app_hidden: true,
+                    },
+                )
+                .unwrap();
+            fixture.unlock();
+            fixture.vault.app_hidden();
+
+            let backup_id = fixture
+                .vault
+                .repo_config_backup_create(fixture.repo_id.clone());
+            fixture
+                .vault
+                .repo_config_backup_generate(backup_id, "password")
+                .unwrap();
+
+            assert_eq!(
+                events(),
+                vec![
+                    AuditLogEvent::RepoUnlock {
+                        repo_id: fixture.repo_id.clone(),
+                        read_only: false,
+                    },
+                    AuditLogEvent::RepoLock {
+                        repo_id: fixture.repo_id.clone(),
+                        reason: RepoLockReason::Manual,
+                    },
+                    AuditLogEvent::RepoUnlockFailure {
+                        repo_id: fixture.repo_id.clone(),
+                        error_code: "repo.invalid_password".into(),
+                    },
+                    AuditLogEvent::RepoUnlock {
+                        repo_id: fixture.repo_id.clone(),
+                        read_only: false,
+                    },
+                    AuditLogEvent::RepoLock {
+                        repo_id: fixture.repo_id.clone(),
+                        reason: RepoLockReason::Manual,
+                    },
+                    AuditLogEvent::RepoUnlock {
+                        repo_id: fixture.repo_id.clone(),
+                        read_only: false,
+                    },
+                    AuditLogEvent::RepoLock {
+                        repo_id: fixture.repo_id.clone(),
+                        reason: RepoLockReason::AppHidden,
+                    },
+                    AuditLogEvent::RepoConfigBackup {
+                        repo_id: fixture.repo_id.clone(),
+                    },
+                ]
+            );
+
+            let entries = fixture.vault.audit_log_entries(&Default::default());
+            assert!(entries
+                .iter()
+                .all(|entry| entry.user_id.as_deref() == Some(&fixture.user_fixture.user_id)));
+            assert_eq!(fixture.vault.audit_log_verify(), Ok(()));
+
+            let exported: Vec<AuditLogEntry> =
+                serde_json::from_str(&fixture.vault.audit_log_export()).unwrap();
+            assert_eq!(exported, entries);
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_audit_log_tampered() {
+    with_repo(|fixture| {
+        async move {
+            let disk_storage = set_disk_storage(&fixture);
+
+            fixture.lock();
+            fixture.unlock();
+            fixture.lock();
+
+            let mut entries = disk_storage.entries();
+            entries.remove(0);
+            disk_storage.set_entries(&entries);
+
+            assert_eq!(
+                new_service(&fixture, &disk_storage).verify(),
+                Err(VerifyAuditLogError::ChainBroken {
+                    seq: entries[0].seq
+                })
+            );
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_audit_log_logout() {
+    with_repo(|fixture| {
+        async move {
+            let disk_storage = set_disk_storage(&fixture);
+
+            fixture.vault.logout().unwrap();
+
+            let entries = disk_storage.entries();
+
+            assert_eq!(
+                entries,
+                fixture.vault.audit_log_entries(&Default::default())
+            );
+            assert_eq!(entries.last().unwrap().event, AuditLogEvent::Logout);
+            assert_eq!(fixture.vault.audit_log_verify(), Ok(()));
+            assert_eq!(
+                fixture
+                    .vault
+                    .secure_storage_service
+                    .get::<Vec<AuditLogEntry>>(AUDIT_LOG_STORAGE_KEY)
+                    .unwrap(),
+                None
+            );
+
+            assert_eq!(new_service(&fixture, &disk_storage).verify(), Ok(()));
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_audit_log_truncated() {
+    with_repo(|fixture| {
+        async move {
+            let disk_storage = set_disk_storage(&fixture);
+
+            fixture.lock();
+            fixture.unlock();
+
+            let mut entries = disk_storage.entries();
+            entries.pop();
+            disk_storage.set_entries(&entries);
+
+            assert_eq!(
+                new_service(&fixture, &disk_storage).verify(),
+                Err(VerifyAuditLogError::HeadMismatch)
+            );
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_audit_log_load_error() {
+    with_repo(|fixture| {
+        async move {
+            let disk_storage = MemoryAuditLogDiskStorage::default();
+            disk_storage.set(b"invalid").unwrap();
+
+            let audit_log_service = new_service(&fixture, &disk_storage);
+
+            audit_log_service.record(AuditLogEvent::Logout);
+
+            assert!(matches!(
+                audit_log_service.verify(),
+                Err(VerifyAuditLogError::DiskStorageError(_))
+            ));
+            assert_eq!(disk_storage.get().unwrap(), Some(b"invalid".to_vec()));
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_audit_log_secure_storage_load_error() {
+    with_repo(|fixture| {
+        async move {
+            let secure_storage_service = fixture.vault.secure_storage_service.clone();
+            secure_storage_service
+                .set(AUDIT_LOG_STORAGE_KEY, &"invalid")
+                .unwrap();
+
+            let audit_log_service = AuditLogService::new(
+                secure_storage_service.clone(),
+                fixture.vault.store.clone(),
+                fixture.vault.runtime.clone(),
+            );
+
+            audit_log_service.record(AuditLogEvent::Logout);
+
+            assert!(matches!(
+                audit_log_service.verify(),
+                Err(VerifyAuditLogError::LoadError(_))
+            ));
+            assert_eq!(
+                secure_storage_service
+                    .get::<String>(AUDIT_LOG_STORAGE_KEY)
+                    .unwrap(),
+                Some(String::from("invalid"))
+            );
+        }
+        .boxed()
+    });
+}
@@ -0,0 +1,134 @@
+use std::time::Duration;
+
+use futures::{AsyncReadExt, FutureExt};
+use similar_asserts::assert_eq;
+use vault_core::{repo_files::selectors as repo_files_selectors, types::DecryptedPath};
+use vault_core_tests::{fixtures::repo_fixture::RepoFixture, helpers::with_repo};
+use vault_native::auto_upload::{auto_uploader::AutoUploader, config::AutoUploadConfig};
+
+async fn try_read_file(fixture: &RepoFixture, path: &str) -> Option<String> {
+    let mut reader = fixture
+        .vault
+        .repo_files_get_file_reader(&fixture.repo_id, &fixture.encrypt_path(path))
+        .ok()?
+        .reader()
+        .await
+        .ok()?;
+
+    let mut content = String::new();
+
+    reader.reader.read_to_string(&mut content).await.ok()?;
+
+    Some(content)
+}
+
+async fn wait_for_content(fixture: &RepoFixture, path: &str, content: &str) {
+    for _ in 0..100 {
+        if try_read_file(fixture, path).await.as_deref() == Some(content) {
+            return;
+        }
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+
+    panic!("timeout waiting for {}", path);
+}
+
+#[test]
+fn test_changed_file_replaces_uploaded_file() {
+    with_repo(|fixture| {
+        async move {
+            fixture.create_dir("/auto").await;
+
+            let root = std::env::temp_dir().join(format!("vault-auto-{}", uuid::Uuid::new_v4()));
+            std::fs::create_dir_all(&root).unwrap();
+
+            let uploader = AutoUploader::start(
+                fixture.vault.clone(),
+                &tokio::runtime::Handle::current(),
+                AutoUploadConfig {
+                    debounce: Duration::from_millis(20),
+                    stable_duration: Duration::from_millis(50),
+                    poll_interval: Duration::from_millis(50),
+                    ..AutoUploadConfig::new(
+                        root.clone(),
+                        fixture.repo_id.clone(),
+                        DecryptedPath("/auto".into()),
+                    )
+                },
+            )
+            .unwrap();
+
+            std::fs::write(root.join("a.txt"), "one").unwrap();
+            wait_for_content(&fixture, "/auto/a.txt", "one").await;
+
+            std::fs::write(root.join("a.txt"), "changed").unwrap();
+            wait_for_content(&fixture, "/auto/a.txt", "changed").await;
+
+            drop(uploader);
+
+            assert_eq!(
+                fixture.vault.with_state(|state| {
+                    repo_files_selectors::select_file(
+                        state,
+                        &fixture.get_file_id("/auto/a (1).txt"),
+                    )
+                    .is_some()
+                }),
+                false
+            );
+
+            std::fs::remove_dir_all(&root).unwrap();
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_changed_file_uploaded_next_to_remotely_changed_file() {
+    with_repo(|fixture| {
+        async move {
+            fixture.create_dir("/auto").await;
+
+            let root = std::env::temp_dir().join(format!("vault-auto-{}", uuid::Uuid::new_v4()));
+            std::fs::create_dir_all(&root).unwrap();
+
+            let uploader = AutoUploader::start(
+                fixture.vault.clone(),
+                &tokio::runtime::Handle::current(),
+                AutoUploadConfig {
+                    debounce: Duration::from_millis(20),
+                    stable_duration: Duration::from_millis(50),
+                    poll_interval: Duration::from_millis(50),
+                    ..AutoUploadConfig::new(
+                        root.clone(),
+                        fixture.repo_id.clone(),
+                        DecryptedPath("/auto".into()),
+                    )
+                },
+            )
+            .unwrap();
+
+            std::fs::write(root.join("a.txt"), "one").unwrap();
+            wait_for_content(&fixture, "/auto/a.txt", "one").await;
+
+            fixture.upload_file("/auto/a.txt", "remote").await;
+
+            std::fs::write(root.join("a.txt"), "changed").unwrap();
+            wait_for_content(&fixture, "/auto/a (1).txt", "changed").await;
+
+            std::fs::write(root.join("a.txt"), "changed again").unwrap();
+            wait_for_content(&fixture, "/auto/a (1).txt", "changed again").await;
+
+            drop(uploader);
+
+            assert_eq!(
+                try_read_file(&fixture, "/auto/a.txt").await.as_deref(),
+                Some("remote")
+            );
+
+            std::fs::remove_dir_all(&root).unwrap();
+        }
+        .boxed()
+    });
+}
@@ -0,0 +1,214 @@
+use futures::FutureExt;
+use similar_asserts::assert_eq;
+use vault_core::{
+    repo_files_browsers::state::RepoFilesBrowserOptions,
+    transfers::state::TransferUploadRelativeName,
+    types::{DecryptedName, EncryptedPath},
+};
+use vault_core_tests::{
+    fixtures::repo_fixture::RepoFixture,
+    helpers::{
+        transfers::{download_string, with_transfers, TestUploadable},
+        with_repo,
+    },
+};
+use vault_fake_remote::fake_remote::faults::FaultProfile;
+
+fn profile(json: &str) -> FaultProfile {
+    FaultProfile::from_json(json).unwrap()
+}
+
+#[test]
+fn test_chaos_transfers() {
+    with_transfers(|fixture| {
+        async move {
+            fixture.fake_remote.set_fault_profile(Some(profile(
+                r#"{
+                    "seed": 7,
+                    "rules": [
+                        {"route": "**", "fault": {"type": "Latency", "minMs": 0, "maxMs": 20}},
+                        {"route": "/content/api/v2.1/mounts/*/files/put", "probability": 0.3, "fault": {"type": "Error", "status": 503}},
+                        {"route": "/content/api/v2.1/mounts/*/files/get", "probability": 0.3, "fault": {"type": "TruncatedBody", "afterBytes": 10}}
+                    ]
+                }"#,
+            )));
+
+            for i in 0..5 {
+                let (_, create_future) = fixture.vault.transfers_upload(
+                    fixture.repo_id.clone(),
+                    EncryptedPath("/".into()),
+                    TransferUploadRelativeName(format!("file{}.txt", i)),
+                    TestUploadable::string(&format!("file {} content", i)),
+                );
+
+                let res = create_future.await.unwrap().await.unwrap();
+                assert_eq!(res.name, DecryptedName(format!("file{}.txt", i)));
+            }
+
+            for i in 0..5 {
+                let (_, create_future, content_future) = download_string(
+                    &fixture.vault,
+                    &fixture.repo_id.0,
+                    &format!("/file{}.txt", i),
+                );
+
+                create_future.await.unwrap().await.unwrap();
+                assert_eq!(
+                    content_future.await.unwrap(),
+                    format!("file {} content", i)
+                );
+            }
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_chaos_browser_repeatable() {
+    with_repo(|fixture| {
+        async move {
+            let fault_profile = profile(
+                r#"{
+                    "seed": 3,
+                    "rules": [
+                        {"route": "/api/v2.1/mounts/*/bundle", "method": "GET", "probability": 0.5, "fault": {"type": "Error", "status": 503}}
+                    ]
+                }"#,
+            );
+
+            let run = |fixture: &RepoFixture| {
+                fixture
+                    .fake_remote
+                    .set_fault_profile(Some(fault_profile.clone()));
+
+                let (browser_id, load_future) = fixture.vault.repo_files_browsers_create(
+                    fixture.repo_id.clone(),
+                    &EncryptedPath("/".into()),
+                    RepoFilesBrowserOptions { select_name: None },
+                );
+
+                let vault = fixture.vault.clone();
+
+                async move {
+                    let mut results = vec![load_future.await.is_ok()];
+
+                    for _ in 0..10 {
+                        results.push(
+                            vault
+                                .repo_files_browsers_load_files(browser_id)
+                                .await
+                                .is_ok(),
+                        );
+                    }
+
+                    vault.repo_files_browsers_destroy(browser_id);
+
+                    results
+                }
+            };
+
+            let results = run(&fixture).await;
+
+            assert!(results.contains(&true));
+            assert!(results.contains(&false));
+            assert_eq!(run(&fixture).await, results);
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_token_expired() {
+    with_repo(|fixture| {
+        async move {
+            fixture.fake_remote.set_fault_profile(Some(profile(
+                r#"{
+                    "rules": [
+                        {"route": "/api/v2.1/mounts/*/bundle", "times": 1, "fault": {"type": "TokenExpired"}}
+                    ]
+                }"#,
+            )));
+
+            fixture
+                .vault
+                .repo_files_service
+                .load_files(&fixture.repo_id, &EncryptedPath("/".into()))
+                .await
+                .unwrap();
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_debug_faults_endpoint() {
+    with_repo(|fixture| {
+        async move {
+            let base_url = fixture
+                .user_fixture
+                .vault_fixture
+                .fake_remote_fixture
+                .base_url
+                .clone();
+            let client = reqwest::Client::builder()
+                .danger_accept_invalid_certs(true)
+                .build()
+                .unwrap();
+
+            let fault_profile = profile(
+                r#"{
+                    "seed": 1,
+                    "rules": [
+                        {"route": "/api/v2.1/user", "fault": {"type": "RateLimited", "status": 503, "retryAfterSecs": 5}}
+                    ]
+                }"#,
+            );
+
+            let res = client
+                .put(format!("{}/debug/faults", base_url))
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(serde_json::to_string(&fault_profile).unwrap())
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(res.status(), reqwest::StatusCode::NO_CONTENT);
+
+            let res = client
+                .get(format!("{}/debug/faults", base_url))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(
+                serde_json::from_slice::<Option<FaultProfile>>(&res.bytes().await.unwrap())
+                    .unwrap(),
+                Some(fault_profile)
+            );
+
+            let res = client
+                .get(format!("{}/api/v2.1/user", base_url))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(res.status(), reqwest::StatusCode::SERVICE_UNAVAILABLE);
+            assert_eq!(
+                res.headers().get(reqwest::header::RETRY_AFTER).unwrap(),
+                "5"
+            );
+
+            let res = client
+                .delete(format!("{}/debug/faults", base_url))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(res.status(), reqwest::StatusCode::NO_CONTENT);
+
+            let res = client
+                .get(format!("{}/api/v2.1/user", base_url))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(res.status(), reqwest::StatusCode::UNAUTHORIZED);
+        }
+        .boxed()
+    });
+}
@@ -0,0 +1,94 @@
+use futures::FutureExt;
+use vault_core::{
+    repo_files_browsers::{self, state::RepoFilesBrowserOptions},
+    store,
+    types::{DecryptedName, EncryptedPath},
+};
+use vault_core_tests::helpers::{eventstream::eventstream_wait_registered, with_repo};
+use vault_fake_remote::fake_remote::faults::FaultProfile;
+
+#[test]
+fn test_resync_after_reconnect() {
+    with_repo(|fixture| {
+        async move {
+            fixture.upload_file("/removed.txt", "test").await;
+
+            fixture.fake_remote.set_fault_profile(Some(
+                FaultProfile::from_json(
+                    r#"{
+                        "rules": [
+                            {"route": "/events", "times": 1, "fault": {"type": "EventstreamDisconnect", "afterMs": 500}}
+                        ]
+                    }"#,
+                )
+                .unwrap(),
+            ));
+
+            let fixture1 = fixture.new_session();
+            fixture1.user_fixture.login();
+            fixture1.user_fixture.load().await;
+            fixture1.unlock();
+
+            let (browser_id, load_future) = fixture1.vault.repo_files_browsers_create(
+                fixture1.repo_id.clone(),
+                &EncryptedPath("/".into()),
+                RepoFilesBrowserOptions { select_name: None },
+            );
+            load_future.await.unwrap();
+            eventstream_wait_registered(
+                fixture1.vault.store.clone(),
+                &fixture1.mount_id,
+                &fixture1.path,
+            )
+            .await;
+
+            let wait_for_store = fixture1.vault.store.clone();
+            store::wait_for(
+                wait_for_store.clone(),
+                &[store::Event::RepoFilesBrowsers, store::Event::Eventstream],
+                move |_| {
+                    wait_for_store.with_state(|state| {
+                        repo_files_browsers::selectors::select_info(state, browser_id)
+                            .filter(|info| info.is_reconnecting)
+                            .map(|_| ())
+                    })
+                },
+            )
+            .await;
+
+            // changes while disconnected are not received as events
+            fixture
+                .vault
+                .repo_files_service
+                .delete_file(&fixture.repo_id, &fixture.encrypt_path("/removed.txt"))
+                .await
+                .unwrap();
+            fixture.upload_file("/created.txt", "test").await;
+
+            let wait_for_store = fixture1.vault.store.clone();
+            store::wait_for(
+                wait_for_store.clone(),
+                &[store::Event::RepoFilesBrowsers, store::Event::Eventstream],
+                move |_| {
+                    wait_for_store.with_state(|state| {
+                        repo_files_browsers::selectors::select_info(state, browser_id)
+                            .filter(|info| {
+                                !info.is_reconnecting
+                                    && info
+                                        .items
+                                        .iter()
+                                        .map(|item| item.file.name.decrypted_name().ok())
+                                        .collect::<Vec<_>>()
+                                        == vec![Some(&DecryptedName("created.txt".into()))]
+                            })
+                            .map(|_| ())
+                    })
+                },
+            )
+            .await;
+
+            fixture1.vault.repo_files_browsers_destroy(browser_id);
+        }
+        .boxed()
+    });
+}
@@ -0,0 +1,96 @@
+use futures::FutureExt;
+use similar_asserts::assert_eq;
+use vault_core::types::EncryptedPath;
+use vault_core_tests::helpers::{transfers::download_string, with_repo};
+
+#[test]
+fn test_fake_remote_snapshots() {
+    with_repo(|fixture| {
+        async move {
+            let base_url = fixture
+                .user_fixture
+                .vault_fixture
+                .fake_remote_fixture
+                .base_url
+                .clone();
+            let client = reqwest::Client::builder()
+                .danger_accept_invalid_certs(true)
+                .build()
+                .unwrap();
+
+            let (_, file) = fixture.upload_file("/file.txt", "snapshot content").await;
+
+            let res = client
+                .put(format!("{}/debug/snapshots/fixture-1", base_url))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(res.status(), reqwest::StatusCode::NO_CONTENT);
+
+            let res = client
+                .get(format!("{}/debug/snapshots", base_url))
+                .send()
+                .await
+                .unwrap();
+            let snapshots =
+                serde_json::from_slice::<serde_json::Value>(&res.bytes().await.unwrap()).unwrap();
+            assert_eq!(snapshots[0]["name"], "fixture-1");
+            assert_eq!(snapshots.as_array().unwrap().len(), 1);
+
+            fixture
+                .vault
+                .repo_files_service
+                .delete_file(&fixture.repo_id, &file.encrypted_path)
+                .await
+                .unwrap();
+
+            let res = client
+                .post(format!("{}/debug/snapshots/fixture-1/restore", base_url))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(res.status(), reqwest::StatusCode::NO_CONTENT);
+
+            fixture
+                .vault
+                .repo_files_service
+                .load_files(&fixture.repo_id, &EncryptedPath("/".into()))
+                .await
+                .unwrap();
+
+            let (_, create_future, content_future) =
+                download_string(&fixture.vault, &fixture.repo_id.0, "/file.txt");
+            create_future.await.unwrap().await.unwrap();
+            assert_eq!(content_future.await.unwrap(), "snapshot content");
+
+            let res = client
+                .post(format!("{}/debug/snapshots/missing/restore", base_url))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(res.status(), reqwest::StatusCode::NOT_FOUND);
+
+            let res = client
+                .put(format!("{}/debug/snapshots/.hidden", base_url))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(res.status(), reqwest::StatusCode::BAD_REQUEST);
+
+            let res = client
+                .delete(format!("{}/debug/snapshots/fixture-1", base_url))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(res.status(), reqwest::StatusCode::NO_CONTENT);
+
+            let res = client
+                .get(format!("{}/debug/snapshots", base_url))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(res.bytes().await.unwrap(), "[]");
+        }
+        .boxed()
+    });
+}
@@ -0,0 +1,125 @@
+use futures::{join, FutureExt};
+use similar_asserts::assert_eq;
+use vault_core::{
+    dialogs, files::file_size, locale::errors::LocaleNotFoundError, notifications,
+    repo_files_browsers::state::RepoFilesBrowserOptions, repos::errors::RepoLockedError, store,
+    types::EncryptedPath, user_error::UserError,
+};
+use vault_core_tests::helpers::with_repo;
+
+#[test]
+fn test_locale_set() {
+    with_repo(|fixture| {
+        async move {
+            assert_eq!(fixture.vault.locale_get(), "en");
+
+            assert_eq!(
+                fixture.vault.locale_set("xx"),
+                Err(LocaleNotFoundError("xx".into()))
+            );
+            assert_eq!(fixture.vault.locale_get(), "en");
+
+            fixture.vault.locale_set("sl").unwrap();
+
+            assert_eq!(fixture.vault.locale_get(), "sl");
+            assert_eq!(
+                fixture.vault.with_state(|_| RepoLockedError.user_error()),
+                "Sef je zaklenjen"
+            );
+            assert_eq!(
+                fixture.vault.with_state(|_| file_size::size_display(1076)),
+                "1,1 KB"
+            );
+
+            let (browser_id, load_future) = fixture.vault.repo_files_browsers_create(
+                fixture.repo_id.clone(),
+                &EncryptedPath("/".into()),
+                RepoFilesBrowserOptions { select_name: None },
+            );
+            load_future.await.unwrap();
+
+            let create_dir_future = fixture.vault.repo_files_browsers_create_dir(browser_id);
+
+            let dialog_vault = fixture.vault.clone();
+            let dialog_future = fixture.fake_remote.tokio_runtime.spawn(async move {
+                let wait_store = dialog_vault.store.clone();
+                let dialog =
+                    store::wait_for(wait_store.clone(), &[store::Event::Dialogs], move |_| {
+                        wait_store.with_state(|state| {
+                            dialogs::selectors::select_dialogs(state)
+                                .into_iter()
+                                .next()
+                                .cloned()
+                        })
+                    })
+                    .await;
+
+                assert_eq!(dialog.title, "Vnesite ime nove mape");
+                assert_eq!(dialog.input_placeholder.as_deref(), Some("Ime mape"));
+                assert_eq!(dialog.confirm_button_text, "Ustvari mapo");
+                assert_eq!(dialog.cancel_button_text.as_deref(), Some("Prekliči"));
+
+                dialog_vault.dialogs_cancel(dialog.id);
+            });
+
+            let (create_dir_res, _) = join!(create_dir_future, dialog_future);
+            assert!(create_dir_res.is_err());
+
+            fixture.vault.repo_files_browsers_destroy(browser_id);
+
+            fixture.vault.locale_set("en").unwrap();
+
+            assert_eq!(
+                fixture.vault.with_state(|_| RepoLockedError.user_error()),
+                "Safe Box is locked"
+            );
+            assert_eq!(
+                fixture.vault.with_state(|_| file_size::size_display(1076)),
+                "1.1 KB"
+            );
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_locale_per_vault() {
+    with_repo(|fixture| {
+        async move {
+            let fixture1 = fixture.new_session();
+
+            fixture.vault.locale_set("sl").unwrap();
+
+            assert_eq!(fixture1.vault.locale_get(), "en");
+
+            fixture.vault.notifications_show_error(&RepoLockedError);
+            fixture1.vault.notifications_show_error(&RepoLockedError);
+
+            let notification_messages = |vault: &vault_core::Vault| {
+                vault.with_state(|state| {
+                    notifications::selectors::select_notifications(state)
+                        .into_iter()
+                        .map(|notification| notification.message.clone())
+                        .collect::<Vec<_>>()
+                })
+            };
+
+            assert_eq!(
+                notification_messages(&fixture.vault),
+                vec![String::from("Sef je zaklenjen")]
+            );
+            assert_eq!(
+                notification_messages(&fixture1.vault),
+                vec![String::from("Safe Box is locked")]
+            );
+            assert_eq!(
+                fixture1.vault.with_state(|_| file_size::size_display(1076)),
+                "1.1 KB"
+            );
+
+            // messages formatted outside of a vault use the default locale
+            assert_eq!(RepoLockedError.user_error(), "Safe Box is locked");
+        }
+        .boxed()
+    });
+}
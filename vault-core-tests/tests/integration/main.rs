@@ -1,12 +1,29 @@
+mod accounts_tests;
+mod audit_log_tests;
+mod auto_upload_tests;
+mod chaos_tests;
+mod eventstream_resync_tests;
+mod fake_remote_snapshots_tests;
+mod locale_tests;
+mod metadata_cache_tests;
 mod oauth2_tests;
+mod panic_lock_tests;
 mod remote_files_browsers_tests;
 mod remote_files_tests;
 mod repo_create_tests;
+mod repo_duplicates_tests;
 mod repo_files_browsers_tests;
 mod repo_files_details_tests;
+mod repo_files_offline_tests;
+mod repo_files_rename_tests;
 mod repo_files_tags_tests;
 mod repo_files_tests;
+mod repo_files_thumbnails_tests;
+mod repo_files_versions_tests;
 mod repo_locker_tests;
+mod repo_unlock_tests;
+mod space_usage_tests;
+mod sync_tests;
 mod transfers_download_reader_tests;
 mod transfers_download_tests;
 mod transfers_upload_tests;
@@ -0,0 +1,207 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use futures::FutureExt;
+use vault_core::{
+    metadata_cache::{selectors, state::MetadataCacheKey, MetadataDiskCache},
+    repo_files, repos,
+    types::EncryptedPath,
+};
+use vault_core_tests::{fixtures::user_fixture::UserFixture, helpers::with_repo};
+use vault_fake_remote::fake_remote::faults::FaultProfile;
+
+#[derive(Clone, Default)]
+struct MemoryMetadataDiskCache {
+    data: Arc<Mutex<HashMap<String, Vec<u8>>>>,
+}
+
+impl MetadataDiskCache for MemoryMetadataDiskCache {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, String> {
+        Ok(self.data.lock().unwrap().get(key).cloned())
+    }
+
+    fn set(&self, key: &str, value: &[u8]) -> Result<(), String> {
+        self.data
+            .lock()
+            .unwrap()
+            .insert(key.to_owned(), value.to_owned());
+
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> Result<(), String> {
+        self.data.lock().unwrap().remove(key);
+
+        Ok(())
+    }
+
+    fn remove_prefix(&self, prefix: &str) -> Result<(), String> {
+        self.data
+            .lock()
+            .unwrap()
+            .retain(|key, _| !key.starts_with(prefix));
+
+        Ok(())
+    }
+
+    fn clear(&self) -> Result<(), String> {
+        self.data.lock().unwrap().clear();
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_restore_cached() {
+    with_repo(|fixture| {
+        async move {
+            let disk_cache = MemoryMetadataDiskCache::default();
+
+            fixture
+                .vault
+                .metadata_cache_set_disk_cache(Some(Box::new(disk_cache.clone())));
+
+            fixture.upload_file("/file.txt", "test").await;
+
+            fixture.vault.repos_service.load_repos().await.unwrap();
+            fixture
+                .vault
+                .repo_files_service
+                .load_files(&fixture.repo_id, &EncryptedPath("/".into()))
+                .await
+                .unwrap();
+
+            let cached = disk_cache.data.lock().unwrap().clone();
+            assert_eq!(cached.len(), 2);
+            // disk cache values are encrypted
+            assert!(cached.values().all(|value| !String::from_utf8_lossy(value)
+                .contains(&fixture.repo_id.0)));
+
+            // the key is kept in the secure storage between sessions
+            let key_storage_key = selectors::get_key_storage_key(&fixture.user_fixture.user_id);
+            let key = fixture
+                .vault
+                .secure_storage_service
+                .get::<MetadataCacheKey>(&key_storage_key)
+                .unwrap()
+                .unwrap();
+
+            let fixture1 = fixture.new_session();
+            fixture1
+                .vault
+                .secure_storage_service
+                .set(&key_storage_key, &key)
+                .unwrap();
+            // the active account is restored from the secure storage
+            fixture1
+                .vault
+                .accounts_service
+                .set_active(Some(fixture.user_fixture.user_id.clone()))
+                .unwrap();
+            fixture1
+                .vault
+                .metadata_cache_set_disk_cache(Some(Box::new(disk_cache.clone())));
+            fixture1.user_fixture.login();
+
+            fixture1.fake_remote.set_fault_profile(Some(
+                FaultProfile::from_json(
+                    r#"{
+                        "rules": [
+                            {"route": "/api/v2.1/vault/repos", "method": "GET", "fault": {"type": "Error", "status": 503}},
+                            {"route": "/api/v2.1/mounts/*/bundle", "method": "GET", "fault": {"type": "Error", "status": 503}}
+                        ]
+                    }"#,
+                )
+                .unwrap(),
+            ));
+
+            assert!(fixture1.vault.repos_service.load_repos().await.is_err());
+            assert!(fixture1.vault.with_state(|state| {
+                repos::selectors::select_repo(state, &fixture1.repo_id).is_ok()
+            }));
+
+            fixture1.unlock();
+
+            assert!(fixture1
+                .vault
+                .repo_files_service
+                .load_files(&fixture1.repo_id, &EncryptedPath("/".into()))
+                .await
+                .is_err());
+
+            let file_id = fixture1.get_file_id("/file.txt");
+            assert!(fixture1.vault.with_state(|state| {
+                repo_files::selectors::select_file(state, &file_id).is_some()
+            }));
+
+            fixture1.fake_remote.set_fault_profile(None);
+
+            // logout wipes the cache
+            fixture1.user_fixture.logout();
+            assert!(disk_cache.data.lock().unwrap().is_empty());
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_switch_account_keeps_cache() {
+    with_repo(|fixture| {
+        async move {
+            let disk_cache = MemoryMetadataDiskCache::default();
+
+            fixture
+                .vault
+                .metadata_cache_set_disk_cache(Some(Box::new(disk_cache.clone())));
+
+            fixture.vault.repos_service.load_repos().await.unwrap();
+
+            let user_id1 = fixture.user_fixture.user_id.clone();
+            let get_key = |user_id: &str| {
+                fixture
+                    .vault
+                    .secure_storage_service
+                    .get::<MetadataCacheKey>(&selectors::get_key_storage_key(user_id))
+                    .unwrap()
+            };
+
+            let key1 = get_key(&user_id1).unwrap();
+            let cached = disk_cache.data.lock().unwrap().clone();
+            assert_eq!(cached.len(), 1);
+
+            let user_fixture2 = UserFixture::create(fixture.user_fixture.vault_fixture.clone());
+
+            fixture.vault.accounts_add().unwrap();
+            user_fixture2.login();
+            user_fixture2.load().await;
+            fixture.vault.repos_service.load_repos().await.unwrap();
+
+            // both accounts have their own key and entries
+            assert_eq!(get_key(&user_id1), Some(key1.clone()));
+            assert!(get_key(&user_fixture2.user_id).is_some());
+            assert_eq!(disk_cache.data.lock().unwrap().len(), 2);
+
+            fixture.vault.accounts_switch(&user_id1).await.unwrap();
+
+            assert_eq!(get_key(&user_id1), Some(key1));
+            assert!(cached
+                .keys()
+                .all(|key| disk_cache.data.lock().unwrap().contains_key(key)));
+            assert_eq!(disk_cache.data.lock().unwrap().len(), 2);
+
+            fixture
+                .vault
+                .accounts_remove(&user_fixture2.user_id)
+                .unwrap();
+
+            assert!(get_key(&user_fixture2.user_id).is_none());
+            // entries of the removed account are deleted, others are kept
+            let cached2 = disk_cache.data.lock().unwrap().clone();
+            assert_eq!(cached2.len(), 1);
+            assert!(cached.keys().all(|key| cached2.contains_key(key)));
+        }
+        .boxed()
+    });
+}
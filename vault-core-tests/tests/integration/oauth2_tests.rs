@@ -1,6 +1,16 @@
-use futures::FutureExt;
+use std::time::Duration;
+
+use futures::{join, stream::AbortHandle, FutureExt};
 use similar_asserts::assert_eq;
-use vault_core::common::state::Status;
+use vault_core::{
+    common::state::Status,
+    lifecycle::errors::{OAuth2FinishDeviceFlowError, OAuth2FinishFlowUrlError},
+    oauth2::{
+        errors::OAuth2Error,
+        service::STATE_STORAGE_KEY,
+        state::{DeviceAuthorization, OAuth2FlowState},
+    },
+};
 use vault_core_tests::{
     fixtures::{
         oauth2_fixture::OAuth2Fixture, user_fixture::UserFixture, vault_fixture::VaultFixture,
@@ -67,6 +77,195 @@ fn test_login() {
     });
 }
 
+#[test]
+fn test_login_pkce_invalid_code_verifier() {
+    with_vault(|vault_fixture| {
+        async move {
+            let user_fixture = UserFixture::create(vault_fixture.clone());
+            let oauth2_fixture = OAuth2Fixture::create(user_fixture.clone());
+
+            let login_url = vault_fixture.vault.oauth2_start_login_flow().unwrap();
+            assert!(login_url.contains("code_challenge="));
+            assert!(login_url.contains("code_challenge_method=S256"));
+
+            let url = oauth2_fixture
+                .oauth2_request(format!("{}&user_id={}", login_url, user_fixture.user_id))
+                .await;
+
+            let flow_state = vault_fixture
+                .vault
+                .secure_storage_service
+                .get::<OAuth2FlowState>(STATE_STORAGE_KEY)
+                .unwrap()
+                .unwrap();
+            vault_fixture
+                .vault
+                .secure_storage_service
+                .set(
+                    STATE_STORAGE_KEY,
+                    &OAuth2FlowState {
+                        code_verifier: "invalid".into(),
+                        ..flow_state
+                    },
+                )
+                .unwrap();
+
+            assert!(matches!(
+                vault_fixture.vault.oauth2_finish_flow_url(&url).await,
+                Err(OAuth2FinishFlowUrlError::OAuth2Error(
+                    OAuth2Error::InvalidGrant(_)
+                ))
+            ));
+            assert!(vault_fixture
+                .vault
+                .with_state(|state| { state.user.user.is_none() }));
+        }
+        .boxed()
+    });
+}
+
+async fn device_authorize(
+    oauth2_fixture: &OAuth2Fixture,
+    device_authorization: &DeviceAuthorization,
+    deny: bool,
+) {
+    let res = oauth2_fixture
+        .reqwest_client
+        .get(format!(
+            "{}&user_id={}&deny={}",
+            device_authorization
+                .verification_uri_complete
+                .as_ref()
+                .unwrap(),
+            oauth2_fixture.user_id,
+            deny
+        ))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), reqwest::StatusCode::OK);
+}
+
+#[test]
+fn test_device_flow() {
+    with_vault(|vault_fixture| {
+        async move {
+            let user_fixture = UserFixture::create(vault_fixture.clone());
+            let oauth2_fixture = OAuth2Fixture::create(user_fixture.clone());
+
+            let device_authorization = vault_fixture
+                .vault
+                .oauth2_start_device_flow()
+                .await
+                .unwrap();
+            assert!(device_authorization
+                .verification_uri_complete
+                .as_ref()
+                .unwrap()
+                .contains(&device_authorization.user_code));
+
+            // authorization is still pending on the first poll
+            let (res, _) = join!(
+                vault_fixture
+                    .vault
+                    .oauth2_finish_device_flow(&device_authorization, AbortHandle::new_pair().1),
+                async {
+                    tokio::time::sleep(Duration::from_millis(1500)).await;
+
+                    device_authorize(&oauth2_fixture, &device_authorization, false).await;
+                }
+            );
+            res.unwrap();
+
+            assert_eq!(oauth2_fixture.get_status(), Status::Loaded);
+            assert!(vault_fixture
+                .vault
+                .with_state(|state| { state.user.user.is_some() }));
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_device_flow_denied() {
+    with_vault(|vault_fixture| {
+        async move {
+            let user_fixture = UserFixture::create(vault_fixture.clone());
+            let oauth2_fixture = OAuth2Fixture::create(user_fixture.clone());
+
+            let device_authorization = vault_fixture
+                .vault
+                .oauth2_start_device_flow()
+                .await
+                .unwrap();
+
+            device_authorize(&oauth2_fixture, &device_authorization, true).await;
+
+            assert_eq!(
+                vault_fixture
+                    .vault
+                    .oauth2_finish_device_flow(&device_authorization, AbortHandle::new_pair().1)
+                    .await
+                    .unwrap_err()
+                    .to_string(),
+                "access denied"
+            );
+            assert!(matches!(
+                oauth2_fixture.get_status(),
+                Status::Error {
+                    error: OAuth2Error::AccessDenied,
+                    ..
+                }
+            ));
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_device_flow_cancel() {
+    with_vault(|vault_fixture| {
+        async move {
+            let user_fixture = UserFixture::create(vault_fixture.clone());
+            let oauth2_fixture = OAuth2Fixture::create(user_fixture.clone());
+
+            let device_authorization = vault_fixture
+                .vault
+                .oauth2_start_device_flow()
+                .await
+                .unwrap();
+
+            let (abort_handle, abort_registration) = AbortHandle::new_pair();
+
+            let (res, _) = join!(
+                vault_fixture
+                    .vault
+                    .oauth2_finish_device_flow(&device_authorization, abort_registration),
+                async {
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+
+                    assert!(matches!(
+                        oauth2_fixture.get_status(),
+                        Status::Loading { .. }
+                    ));
+
+                    abort_handle.abort();
+                }
+            );
+
+            assert_eq!(
+                res,
+                Err(OAuth2FinishDeviceFlowError::OAuth2Error(
+                    OAuth2Error::Canceled
+                ))
+            );
+            assert_eq!(oauth2_fixture.get_status(), Status::Initial);
+        }
+        .boxed()
+    });
+}
+
 #[test]
 fn test_oauth2_logout() {
     with_vault(|vault_fixture| {
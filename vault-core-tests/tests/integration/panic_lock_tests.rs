@@ -0,0 +1,170 @@
+use std::time::Duration;
+
+use futures::FutureExt;
+use vault_core::{
+    audit_log::state::{AuditLogEvent, RepoLockReason},
+    files::{file_category::FileCategory, files_filter::FilesFilter},
+    oauth2::service::TOKEN_STORAGE_KEY,
+    repo_files_details::state::RepoFilesDetailsOptions,
+    repo_files_versions::state::RepoFilesVersionsSettings,
+    repos::{self, state::RepoState},
+};
+use vault_core_tests::helpers::{repo_files_details::details_wait_content_loaded, with_repo};
+
+#[test]
+fn test_panic_lock() {
+    with_repo(|fixture| {
+        async move {
+            fixture.upload_file("/file.txt", "secret").await;
+
+            let (details_id, load_future) = fixture.vault.repo_files_details_create(
+                fixture.repo_id.clone(),
+                &fixture.encrypt_path("/file.txt"),
+                true,
+                RepoFilesDetailsOptions {
+                    autosave_interval: Duration::from_secs(20),
+                    load_content: FilesFilter {
+                        categories: vec![FileCategory::Text],
+                        exts: vec![],
+                    },
+                },
+            );
+            load_future.await.unwrap();
+
+            details_wait_content_loaded(fixture.vault.store.clone(), details_id).await;
+
+            fixture
+                .vault
+                .repo_files_details_set_content(details_id, "unsaved".as_bytes().to_vec())
+                .unwrap();
+
+            fixture.vault.panic_lock(false).unwrap();
+
+            fixture.vault.with_state(|state| {
+                assert!(matches!(
+                    repos::selectors::select_repo(state, &fixture.repo_id)
+                        .unwrap()
+                        .state,
+                    RepoState::Locked
+                ));
+                assert!(state.repo_files_details.details.is_empty());
+                assert!(state.repo_files.files.is_empty());
+                assert!(state.transfers.transfers.is_empty());
+            });
+
+            assert_eq!(
+                fixture
+                    .vault
+                    .audit_log_entries(&Default::default())
+                    .last()
+                    .unwrap()
+                    .event,
+                AuditLogEvent::RepoLock {
+                    repo_id: fixture.repo_id.clone(),
+                    reason: RepoLockReason::Panic,
+                }
+            );
+
+            // secure storage is kept
+            assert!(fixture
+                .vault
+                .secure_storage_service
+                .get::<serde_json::Value>(TOKEN_STORAGE_KEY)
+                .unwrap()
+                .is_some());
+
+            // locking again is a no-op
+            fixture.vault.panic_lock(false).unwrap();
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_panic_lock_clears_duplicates_and_versions() {
+    with_repo(|fixture| {
+        async move {
+            fixture.upload_file("/a.txt", "same").await;
+            fixture.upload_file("/b.txt", "same").await;
+
+            fixture
+                .vault
+                .repo_files_versions_set_settings(
+                    &fixture.repo_id,
+                    RepoFilesVersionsSettings {
+                        enabled: true,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+
+            let duplicates_id = fixture
+                .vault
+                .repo_duplicates_create(fixture.repo_id.clone());
+            fixture
+                .vault
+                .repo_duplicates_scan(duplicates_id)
+                .await
+                .unwrap();
+
+            fixture.vault.with_state(|state| {
+                assert!(!state.repo_duplicates.duplicates[&duplicates_id]
+                    .groups
+                    .is_empty());
+                assert!(state.repo_files_versions.settings_loaded);
+            });
+
+            fixture.vault.panic_lock(false).unwrap();
+
+            fixture.vault.with_state(|state| {
+                assert!(state.repo_duplicates.duplicates[&duplicates_id]
+                    .groups
+                    .is_empty());
+                assert!(state.repo_files_versions.settings.is_empty());
+                assert!(!state.repo_files_versions.settings_loaded);
+            });
+
+            // settings are loaded again from secure storage
+            assert!(
+                fixture
+                    .vault
+                    .repo_files_versions_get_settings(&fixture.repo_id)
+                    .unwrap()
+                    .enabled
+            );
+
+            fixture.vault.repo_duplicates_destroy(duplicates_id);
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_panic_lock_wipe_secure_storage() {
+    with_repo(|fixture| {
+        async move {
+            fixture.vault.panic_lock(true).unwrap();
+
+            assert!(fixture
+                .vault
+                .secure_storage_service
+                .get::<serde_json::Value>(TOKEN_STORAGE_KEY)
+                .unwrap()
+                .is_none());
+
+            // the user is logged out
+            fixture.vault.with_state(|state| {
+                assert!(state.oauth2.token.is_none());
+                assert!(state.user.user.is_none());
+            });
+
+            // the audit log survives the wipe
+            assert_eq!(fixture.vault.audit_log_verify(), Ok(()));
+            assert!(!fixture
+                .vault
+                .audit_log_entries(&Default::default())
+                .is_empty());
+        }
+        .boxed()
+    });
+}
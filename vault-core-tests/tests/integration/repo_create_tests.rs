@@ -91,14 +91,14 @@ fn test_create() {
                         state,
                         expected_create_form(&state, |form| {
                             patch_create_form_loaded(&fixture, form);
-                            form.password = "password".into();
+                            form.password = String::from("password").into();
                         })
                     ),
                     4 => assert_eq!(
                         state,
                         expected_create_form(&state, |form| {
                             patch_create_form_loaded(&fixture, form);
-                            form.password = "password".into();
+                            form.password = String::from("password").into();
                             form.create_repo_status = Status::Loading { loaded: false };
                         })
                     ),
@@ -151,14 +151,14 @@ fn test_create_custom_salt() {
                         state,
                         expected_create_form(&state, |form| {
                             patch_create_form_loaded(&fixture, form);
-                            form.password = "password".into();
+                            form.password = String::from("password").into();
                         })
                     ),
                     4 => assert_eq!(
                         state,
                         expected_create_form(&state, |form| {
                             patch_create_form_loaded(&fixture, form);
-                            form.password = "password".into();
+                            form.password = String::from("password").into();
                             form.salt = Some("salt".into());
                         })
                     ),
@@ -166,7 +166,7 @@ fn test_create_custom_salt() {
                         state,
                         expected_create_form(&state, |form| {
                             patch_create_form_loaded(&fixture, form);
-                            form.password = "password".into();
+                            form.password = String::from("password").into();
                             form.salt = Some("salt".into());
                             form.create_repo_status = Status::Loading { loaded: false };
                         })
@@ -238,7 +238,7 @@ fn test_create_custom_location() {
                                 mount_id: fixture.mount_id.clone(),
                                 path: RemotePath("/custom".into()),
                             });
-                            form.password = "password".into();
+                            form.password = String::from("password").into();
                         })
                     ),
                     5 => assert_eq!(
@@ -249,7 +249,7 @@ fn test_create_custom_location() {
                                 mount_id: fixture.mount_id.clone(),
                                 path: RemotePath("/custom".into()),
                             });
-                            form.password = "password".into();
+                            form.password = String::from("password").into();
                             form.create_repo_status = Status::Loading { loaded: false };
                         })
                     ),
@@ -333,14 +333,14 @@ fn test_create_location_error() {
                         state,
                         expected_create_form(&state, |form| {
                             patch_create_form_loaded(&fixture.user_fixture, form);
-                            form.password = "password".into();
+                            form.password = String::from("password").into();
                         })
                     ),
                     5 => assert_eq!(
                         state,
                         expected_create_form(&state, |form| {
                             patch_create_form_loaded(&fixture.user_fixture, form);
-                            form.password = "password".into();
+                            form.password = String::from("password").into();
                             form.create_repo_status = Status::Loading { loaded: false };
                         })
                     ),
@@ -348,7 +348,7 @@ fn test_create_location_error() {
                         state,
                         expected_create_form(&state, |form| {
                             patch_create_form_loaded(&fixture.user_fixture, form);
-                            form.password = "password".into();
+                            form.password = String::from("password").into();
                             form.create_repo_status = Status::Error {
                                 error: CreateRepoError::RemoteError(RemoteError::ApiError {
                                     code: ApiErrorCode::VaultReposAlreadyExists,
@@ -369,7 +369,7 @@ fn test_create_location_error() {
                                 mount_id: fixture.mount_id.clone(),
                                 path: RemotePath("/custom".into()),
                             });
-                            form.password = "password".into();
+                            form.password = String::from("password").into();
                         })
                     ),
                     8 => assert_eq!(
@@ -380,7 +380,7 @@ fn test_create_location_error() {
                                 mount_id: fixture.mount_id.clone(),
                                 path: RemotePath("/custom".into()),
                             });
-                            form.password = "password".into();
+                            form.password = String::from("password").into();
                             form.create_repo_status = Status::Loading { loaded: false };
                         })
                     ),
@@ -418,7 +418,7 @@ fn expected_create_form(
         primary_mount_id: None,
         location: None,
         location_dir_picker_id: None,
-        password: "".into(),
+        password: String::new().into(),
         salt: Some(
             state
                 .creates
@@ -501,7 +501,7 @@ fn expected_create_created(
                 mount_id: fixture.mount_id.clone(),
                 path: RemotePath("/My safe box".into()),
             },
-            password: "password".into(),
+            password: String::from("password").into(),
             salt: state_created.config.salt.clone(),
             rclone_config: state_created.config.rclone_config.clone(),
         },
@@ -0,0 +1,292 @@
+use std::time::Duration;
+
+use futures::{io::Cursor, join, FutureExt};
+use similar_asserts::assert_eq;
+use vault_core::{
+    common::state::Status,
+    dialogs,
+    remote::RemoteFileUploadConflictResolution,
+    repo_duplicates::{
+        selectors::select_info,
+        state::{RepoDuplicatesFile, RepoDuplicatesGroup},
+    },
+    repo_files::{selectors as repo_files_selectors, state::RepoFile},
+    repo_files_versions::state::RepoFilesVersionsSettings,
+    store,
+    types::{DecryptedName, DecryptedPath, EncryptedPath, RemoteName},
+};
+use vault_core_tests::{fixtures::repo_fixture::RepoFixture, helpers::with_repo};
+
+async fn upload_file(fixture: &RepoFixture, path: &str, content: &str) {
+    fixture.upload_file(path, content).await;
+
+    // groups are sorted by modified time
+    tokio::time::sleep(Duration::from_millis(5)).await;
+}
+
+async fn upload_file_without_tags(fixture: &RepoFixture, name: &str, content: &'static str) {
+    let cipher = fixture
+        .vault
+        .repos_service
+        .get_cipher(&fixture.repo_id)
+        .unwrap();
+    let (mount_id, remote_parent_path) = fixture
+        .vault
+        .repo_files_service
+        .get_repo_mount_path(&fixture.repo_id, &EncryptedPath("/".into()))
+        .unwrap();
+
+    fixture
+        .vault
+        .remote_files_service
+        .upload_file_reader(
+            &mount_id,
+            &remote_parent_path,
+            &RemoteName(cipher.encrypt_filename(&DecryptedName(name.into())).0),
+            Box::pin(cipher.encrypt_reader_async(Cursor::new(content.as_bytes()))),
+            None,
+            RemoteFileUploadConflictResolution::Error,
+            None,
+        )
+        .await
+        .unwrap();
+}
+
+async fn get_file(fixture: &RepoFixture, path: &str) -> RepoFile {
+    let path = fixture.encrypt_path(path);
+
+    fixture
+        .vault
+        .repo_files_service
+        .load_file(&fixture.repo_id, &path)
+        .await
+        .unwrap();
+
+    fixture.vault.with_state(|state| {
+        repo_files_selectors::select_file(
+            state,
+            &repo_files_selectors::get_file_id(&fixture.repo_id, &path),
+        )
+        .cloned()
+        .unwrap()
+    })
+}
+
+async fn duplicates_file(fixture: &RepoFixture, path: &str) -> RepoDuplicatesFile {
+    let file = get_file(fixture, path).await;
+
+    RepoDuplicatesFile {
+        file_id: file.id,
+        encrypted_path: file.encrypted_path,
+        path: DecryptedPath(path.into()),
+        modified: file.modified,
+    }
+}
+
+async fn scan(fixture: &RepoFixture) -> u32 {
+    let duplicates_id = fixture
+        .vault
+        .repo_duplicates_create(fixture.repo_id.clone());
+
+    fixture
+        .vault
+        .repo_duplicates_scan(duplicates_id)
+        .await
+        .unwrap();
+
+    duplicates_id
+}
+
+fn select_groups(fixture: &RepoFixture, duplicates_id: u32) -> (Vec<RepoDuplicatesGroup>, i64) {
+    fixture.vault.with_state(|state| {
+        let info = select_info(state, duplicates_id).unwrap();
+
+        assert_eq!(info.status, &Status::Loaded);
+
+        (info.groups.to_vec(), info.reclaimable_size)
+    })
+}
+
+#[test]
+fn test_scan() {
+    with_repo(|fixture| {
+        async move {
+            upload_file(&fixture, "/a.txt", "same").await;
+            fixture.create_dir("/dir").await;
+            upload_file(&fixture, "/dir/b.txt", "same").await;
+            upload_file(&fixture, "/c.txt", "diff").await;
+            upload_file(&fixture, "/d.txt", "unique").await;
+            upload_file(&fixture, "/e.txt", "bigger file").await;
+            upload_file(&fixture, "/f.txt", "bigger file").await;
+            upload_file(&fixture, "/g.txt", "bigger file").await;
+            upload_file(&fixture, "/empty1.txt", "").await;
+            upload_file(&fixture, "/empty2.txt", "").await;
+
+            let duplicates_id = scan(&fixture).await;
+
+            assert_eq!(
+                select_groups(&fixture, duplicates_id),
+                (
+                    vec![
+                        RepoDuplicatesGroup {
+                            hash: hex::encode(md5::compute("bigger file").0),
+                            size: 11,
+                            files: vec![
+                                duplicates_file(&fixture, "/g.txt").await,
+                                duplicates_file(&fixture, "/f.txt").await,
+                                duplicates_file(&fixture, "/e.txt").await,
+                            ],
+                        },
+                        RepoDuplicatesGroup {
+                            hash: hex::encode(md5::compute("same").0),
+                            size: 4,
+                            files: vec![
+                                duplicates_file(&fixture, "/dir/b.txt").await,
+                                duplicates_file(&fixture, "/a.txt").await,
+                            ],
+                        },
+                    ],
+                    26
+                )
+            );
+
+            fixture.vault.repo_duplicates_destroy(duplicates_id);
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_scan_hashes_files_without_tags() {
+    with_repo(|fixture| {
+        async move {
+            upload_file(&fixture, "/a.txt", "same").await;
+            upload_file_without_tags(&fixture, "b.txt", "same").await;
+            upload_file_without_tags(&fixture, "c.txt", "diff").await;
+
+            assert_eq!(get_file(&fixture, "/b.txt").await.hash(), None);
+
+            let duplicates_id = scan(&fixture).await;
+
+            let (groups, reclaimable_size) = select_groups(&fixture, duplicates_id);
+
+            assert_eq!(groups.len(), 1);
+            assert_eq!(groups[0].hash, hex::encode(md5::compute("same").0));
+            assert_eq!(
+                groups[0]
+                    .files
+                    .iter()
+                    .map(|file| file.path.0.as_str())
+                    .collect::<Vec<_>>(),
+                vec!["/b.txt", "/a.txt"]
+            );
+            assert_eq!(reclaimable_size, 4);
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_scan_ignores_versions() {
+    with_repo(|fixture| {
+        async move {
+            fixture
+                .vault
+                .repo_files_versions_set_settings(
+                    &fixture.repo_id,
+                    RepoFilesVersionsSettings {
+                        enabled: true,
+                        max_count: None,
+                        max_age_days: None,
+                    },
+                )
+                .unwrap();
+
+            upload_file(&fixture, "/file.txt", "v1").await;
+
+            fixture
+                .vault
+                .repo_files_versions_service
+                .clone()
+                .create_version(&fixture.repo_id, &fixture.encrypt_path("/file.txt"))
+                .await
+                .unwrap();
+
+            let duplicates_id = scan(&fixture).await;
+
+            assert_eq!(select_groups(&fixture, duplicates_id), (vec![], 0));
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_delete_all_but_newest() {
+    with_repo(|fixture| {
+        async move {
+            upload_file(&fixture, "/a.txt", "same").await;
+            upload_file(&fixture, "/b.txt", "same").await;
+            upload_file(&fixture, "/c.txt", "same").await;
+            upload_file(&fixture, "/d.txt", "other").await;
+            upload_file(&fixture, "/e.txt", "other").await;
+
+            let duplicates_id = scan(&fixture).await;
+
+            let delete_future = fixture.vault.repo_duplicates_delete_all_but_newest(
+                duplicates_id,
+                Some(vec![hex::encode(md5::compute("same").0)]),
+            );
+
+            let dialog_vault = fixture.vault.clone();
+            let dialog_future = fixture.fake_remote.tokio_runtime.spawn(async move {
+                let wait_store = dialog_vault.store.clone();
+                let dialog_id =
+                    store::wait_for(wait_store.clone(), &[store::Event::Dialogs], move |_| {
+                        wait_store.with_state(|state| {
+                            dialogs::selectors::select_dialogs(state)
+                                .first()
+                                .map(|dialog| dialog.id)
+                        })
+                    })
+                    .await;
+
+                dialog_vault.dialogs_confirm(dialog_id);
+            });
+
+            let (delete_res, _) = join!(delete_future, dialog_future);
+            delete_res.unwrap();
+
+            fixture
+                .vault
+                .repo_files_service
+                .load_files(&fixture.repo_id, &EncryptedPath("/".into()))
+                .await
+                .unwrap();
+
+            assert_eq!(
+                fixture.vault.with_state(|state| {
+                    let mut names = repo_files_selectors::select_files(
+                        state,
+                        &fixture.repo_id,
+                        &EncryptedPath("/".into()),
+                    )
+                    .map(|file| file.decrypted_name().unwrap().0.clone())
+                    .collect::<Vec<_>>();
+
+                    names.sort();
+
+                    names
+                }),
+                vec!["c.txt", "d.txt", "e.txt"]
+            );
+
+            // the other group is kept
+            let (groups, reclaimable_size) = select_groups(&fixture, duplicates_id);
+
+            assert_eq!(groups.len(), 1);
+            assert_eq!(groups[0].hash, hex::encode(md5::compute("other").0));
+            assert_eq!(reclaimable_size, 5);
+        }
+        .boxed()
+    });
+}
@@ -78,6 +78,8 @@ fn test_repo_not_loaded() {
                             breadcrumbs: None,
                             repo_status: Status::Initial,
                             is_locked: false,
+                            is_read_only: false,
+                            is_reconnecting: false,
                         }
                     ),
                     1 => assert_eq!(
@@ -105,6 +107,8 @@ fn test_repo_not_loaded() {
                             breadcrumbs: None,
                             repo_status: Status::Loading { loaded: false },
                             is_locked: false,
+                            is_read_only: false,
+                            is_reconnecting: false,
                         }
                     ),
                     2 => assert_eq!(
@@ -135,6 +139,8 @@ fn test_repo_not_loaded() {
                             breadcrumbs: None,
                             repo_status: Status::Loaded,
                             is_locked: true,
+                            is_read_only: false,
+                            is_reconnecting: false,
                         }
                     ),
                     _ => panic!("unexpected state: {:#?}", select_info(&state, browser_id)),
@@ -202,6 +208,8 @@ fn test_repo_locked_unlock() {
                             breadcrumbs: None,
                             repo_status: Status::Loaded,
                             is_locked: true,
+                            is_read_only: false,
+                            is_reconnecting: false,
                         }
                     ),
                     1 => assert_eq!(
@@ -235,6 +243,8 @@ fn test_repo_locked_unlock() {
                             }]),
                             repo_status: Status::Loaded,
                             is_locked: false,
+                            is_read_only: false,
+                            is_reconnecting: false,
                         }
                     ),
                     2 => assert_eq!(
@@ -268,6 +278,8 @@ fn test_repo_locked_unlock() {
                             }]),
                             repo_status: Status::Loaded,
                             is_locked: false,
+                            is_read_only: false,
+                            is_reconnecting: false,
                         }
                     ),
                     _ => panic!("unexpected state: {:#?}", select_info(&state, browser_id)),
@@ -326,10 +338,14 @@ fn test_repo_lock_unlock_remove() {
                         RepoFilesBrowserItem {
                             file: &dir,
                             is_selected: false,
+                            is_thumbnail_supported: false,
+                            thumbnail: None,
                         },
                         RepoFilesBrowserItem {
                             file: &file,
                             is_selected: false,
+                            is_thumbnail_supported: false,
+                            thumbnail: None,
                         }
                     ],
                     breadcrumbs: Some(&[RepoFilesBreadcrumb {
@@ -341,6 +357,8 @@ fn test_repo_lock_unlock_remove() {
                     }]),
                     repo_status: Status::Loaded,
                     is_locked: false,
+                    is_read_only: false,
+                    is_reconnecting: false,
                 }
             );
 
@@ -375,6 +393,8 @@ fn test_repo_lock_unlock_remove() {
                     breadcrumbs: None,
                     repo_status: Status::Loaded,
                     is_locked: true,
+                    is_read_only: false,
+                    is_reconnecting: false,
                 }
             );
 
@@ -424,6 +444,8 @@ fn test_repo_lock_unlock_remove() {
                         loaded: true
                     },
                     is_locked: false,
+                    is_read_only: false,
+                    is_reconnecting: false,
                 }
             );
 
@@ -484,6 +506,8 @@ fn test_repo_decrypt_path_error() {
                     items: vec![RepoFilesBrowserItem {
                         file: &file,
                         is_selected: false,
+                        is_thumbnail_supported: false,
+                        thumbnail: None,
                     }],
                     breadcrumbs: Some(&[
                         RepoFilesBreadcrumb {
@@ -503,6 +527,8 @@ fn test_repo_decrypt_path_error() {
                     ]),
                     repo_status: Status::Loaded,
                     is_locked: false,
+                    is_read_only: false,
+                    is_reconnecting: false,
                 }
             );
 
@@ -8,7 +8,7 @@ use std::{
 };
 
 use axum::{http::StatusCode, response::IntoResponse};
-use futures::FutureExt;
+use futures::{AsyncReadExt, FutureExt};
 use similar_asserts::assert_eq;
 use vault_core::{
     cipher::errors::DecryptSizeError,
@@ -22,6 +22,7 @@ use vault_core::{
             RepoFilesDetails, RepoFilesDetailsContent, RepoFilesDetailsContentData,
             RepoFilesDetailsContentDataBytes, RepoFilesDetailsContentLoading, RepoFilesDetailsInfo,
             RepoFilesDetailsLocation, RepoFilesDetailsOptions, RepoFilesDetailsState,
+            RepoFilesDetailsTextMergeChunk,
         },
     },
     repos,
@@ -155,7 +156,7 @@ fn test_content() {
                                 location.content.status = Status::Loaded;
                                 location.content.data = Some(RepoFilesDetailsContentData {
                                     bytes: RepoFilesDetailsContentDataBytes::Decrypted(
-                                        "test".as_bytes().to_owned(),
+                                        "test".as_bytes().to_owned().into(),
                                         cipher.clone(),
                                     ),
                                     remote_size: upload_result.remote_file.size,
@@ -604,6 +605,7 @@ fn test_repo_not_loaded() {
                             is_editing: false,
                             is_dirty: false,
                             should_destroy: false,
+                            can_edit: true,
                             can_save: false,
                             can_download: true,
                             can_copy: true,
@@ -611,6 +613,9 @@ fn test_repo_not_loaded() {
                             can_delete: true,
                             repo_status: Status::Initial,
                             is_locked: false,
+                            is_read_only: false,
+                            is_conflict: false,
+                            conflict_status: Status::Initial,
                         }
                     ),
                     1 => assert_eq!(
@@ -632,6 +637,7 @@ fn test_repo_not_loaded() {
                             is_editing: false,
                             is_dirty: false,
                             should_destroy: false,
+                            can_edit: true,
                             can_save: false,
                             can_download: true,
                             can_copy: true,
@@ -639,6 +645,9 @@ fn test_repo_not_loaded() {
                             can_delete: true,
                             repo_status: Status::Loading { loaded: false },
                             is_locked: false,
+                            is_read_only: false,
+                            is_conflict: false,
+                            conflict_status: Status::Initial,
                         }
                     ),
                     2 => assert_eq!(
@@ -663,6 +672,7 @@ fn test_repo_not_loaded() {
                             is_editing: false,
                             is_dirty: false,
                             should_destroy: false,
+                            can_edit: true,
                             can_save: false,
                             can_download: true,
                             can_copy: true,
@@ -670,6 +680,9 @@ fn test_repo_not_loaded() {
                             can_delete: true,
                             repo_status: Status::Loaded,
                             is_locked: true,
+                            is_read_only: false,
+                            is_conflict: false,
+                            conflict_status: Status::Initial,
                         }
                     ),
                     _ => panic!("unexpected state: {:#?}", select_info(&state, details_id)),
@@ -757,6 +770,7 @@ fn test_repo_locked_unlock() {
                             is_editing: false,
                             is_dirty: false,
                             should_destroy: false,
+                            can_edit: true,
                             can_save: false,
                             can_download: true,
                             can_copy: true,
@@ -764,6 +778,9 @@ fn test_repo_locked_unlock() {
                             can_delete: true,
                             repo_status: Status::Loaded,
                             is_locked: true,
+                            is_read_only: false,
+                            is_conflict: false,
+                            conflict_status: Status::Initial,
                         }
                     ),
                     1 => assert_eq!(
@@ -785,6 +802,7 @@ fn test_repo_locked_unlock() {
                             is_editing: false,
                             is_dirty: false,
                             should_destroy: false,
+                            can_edit: true,
                             can_save: false,
                             can_download: true,
                             can_copy: true,
@@ -792,6 +810,9 @@ fn test_repo_locked_unlock() {
                             can_delete: true,
                             repo_status: Status::Loaded,
                             is_locked: false,
+                            is_read_only: false,
+                            is_conflict: false,
+                            conflict_status: Status::Initial,
                         }
                     ),
                     2 => assert_eq!(
@@ -818,6 +839,7 @@ fn test_repo_locked_unlock() {
                             is_editing: false,
                             is_dirty: false,
                             should_destroy: false,
+                            can_edit: true,
                             can_save: false,
                             can_download: true,
                             can_copy: true,
@@ -825,6 +847,9 @@ fn test_repo_locked_unlock() {
                             can_delete: true,
                             repo_status: Status::Loaded,
                             is_locked: false,
+                            is_read_only: false,
+                            is_conflict: false,
+                            conflict_status: Status::Initial,
                         }
                     ),
                     3 => assert_eq!(
@@ -851,6 +876,7 @@ fn test_repo_locked_unlock() {
                             is_editing: false,
                             is_dirty: false,
                             should_destroy: false,
+                            can_edit: true,
                             can_save: false,
                             can_download: true,
                             can_copy: true,
@@ -858,6 +884,9 @@ fn test_repo_locked_unlock() {
                             can_delete: true,
                             repo_status: Status::Loaded,
                             is_locked: false,
+                            is_read_only: false,
+                            is_conflict: false,
+                            conflict_status: Status::Initial,
                         }
                     ),
                     4 => assert_eq!(
@@ -884,6 +913,7 @@ fn test_repo_locked_unlock() {
                             is_editing: false,
                             is_dirty: false,
                             should_destroy: false,
+                            can_edit: true,
                             can_save: false,
                             can_download: true,
                             can_copy: true,
@@ -891,6 +921,9 @@ fn test_repo_locked_unlock() {
                             can_delete: true,
                             repo_status: Status::Loaded,
                             is_locked: false,
+                            is_read_only: false,
+                            is_conflict: false,
+                            conflict_status: Status::Initial,
                         }
                     ),
                     _ => panic!("unexpected state: {:#?}", select_info(&state, details_id)),
@@ -969,6 +1002,7 @@ fn test_repo_lock_unlock_remove() {
                     is_editing: false,
                     is_dirty: false,
                     should_destroy: false,
+                    can_edit: true,
                     can_save: false,
                     can_download: true,
                     can_copy: true,
@@ -976,6 +1010,9 @@ fn test_repo_lock_unlock_remove() {
                     can_delete: true,
                     repo_status: Status::Loaded,
                     is_locked: false,
+                    is_read_only: false,
+                    is_conflict: false,
+                    conflict_status: Status::Initial,
                 }
             );
             assert_eq!(
@@ -993,7 +1030,7 @@ fn test_repo_lock_unlock_remove() {
                     .unwrap()
                     .bytes,
                 RepoFilesDetailsContentDataBytes::Decrypted(
-                    "test".as_bytes().to_owned(),
+                    "test".as_bytes().to_owned().into(),
                     repos::selectors::select_cipher_owned(&state_before_lock, &fixture.repo_id)
                         .unwrap()
                 )
@@ -1024,6 +1061,7 @@ fn test_repo_lock_unlock_remove() {
                     is_editing: false,
                     is_dirty: false,
                     should_destroy: false,
+                    can_edit: true,
                     can_save: false,
                     can_download: true,
                     can_copy: true,
@@ -1031,6 +1069,9 @@ fn test_repo_lock_unlock_remove() {
                     can_delete: true,
                     repo_status: Status::Loaded,
                     is_locked: true,
+                    is_read_only: false,
+                    is_conflict: false,
+                    conflict_status: Status::Initial,
                 }
             );
             assert!(matches!(
@@ -1072,7 +1113,7 @@ fn test_repo_lock_unlock_remove() {
                     .unwrap()
                     .bytes,
                 RepoFilesDetailsContentDataBytes::Decrypted(
-                    "test".as_bytes().to_owned(),
+                    "test".as_bytes().to_owned().into(),
                     repos::selectors::select_cipher_owned(&state_after_unlock, &fixture.repo_id)
                         .unwrap()
                 )
@@ -1103,6 +1144,7 @@ fn test_repo_lock_unlock_remove() {
                     is_editing: false,
                     is_dirty: false,
                     should_destroy: false,
+                    can_edit: true,
                     can_save: false,
                     can_download: true,
                     can_copy: true,
@@ -1113,6 +1155,9 @@ fn test_repo_lock_unlock_remove() {
                         loaded: true
                     },
                     is_locked: false,
+                    is_read_only: false,
+                    is_conflict: false,
+                    conflict_status: Status::Initial,
                 }
             );
             assert_eq!(
@@ -1130,7 +1175,7 @@ fn test_repo_lock_unlock_remove() {
                     .unwrap()
                     .bytes,
                 RepoFilesDetailsContentDataBytes::Decrypted(
-                    "test".as_bytes().to_owned(),
+                    "test".as_bytes().to_owned().into(),
                     repos::selectors::select_cipher_owned(&state_after_unlock, &fixture.repo_id)
                         .unwrap()
                 )
@@ -1180,12 +1225,14 @@ fn expected_details_state(
                 loading: None,
                 version: 0,
                 transfer_id: None,
+                base: None,
             },
             is_editing: false,
             is_dirty: false,
             save_status: Status::Initial,
             delete_status: Status::Initial,
             should_destroy: false,
+            conflict: None,
         }),
         status: Status::Initial,
         repo_status: Status::Loaded,
@@ -1392,3 +1439,235 @@ fn test_eventstream_not_loaded() {
         .boxed()
     });
 }
+
+async fn create_conflict(fixture: &RepoFixture, base: &str, mine: &str, theirs: &str) -> u32 {
+    fixture.upload_file("/file.txt", base).await;
+
+    let (details_id, load_future) = fixture.vault.repo_files_details_create(
+        fixture.repo_id.clone(),
+        &fixture.encrypt_path("/file.txt"),
+        true,
+        RepoFilesDetailsOptions {
+            autosave_interval: Duration::from_secs(20),
+            load_content: FilesFilter {
+                categories: vec![FileCategory::Text],
+                exts: vec![],
+            },
+        },
+    );
+    load_future.await.unwrap();
+
+    details_wait_content_loaded(fixture.vault.store.clone(), details_id).await;
+
+    fixture
+        .vault
+        .repo_files_details_set_content(details_id, mine.as_bytes().to_vec())
+        .unwrap();
+
+    // someone else changes the file
+    fixture.upload_file("/file.txt", theirs).await;
+
+    details_wait(fixture.vault.store.clone(), details_id, |details| {
+        details
+            .location
+            .as_ref()
+            .unwrap()
+            .conflict
+            .as_ref()
+            .filter(|conflict| matches!(conflict.status, Status::Loaded))
+            .is_some()
+    })
+    .await;
+
+    details_id
+}
+
+async fn read_file(fixture: &RepoFixture, path: &EncryptedPath) -> String {
+    let mut reader = fixture
+        .vault
+        .repo_files_get_file_reader(&fixture.repo_id, path)
+        .unwrap()
+        .reader()
+        .await
+        .unwrap();
+
+    let mut content = String::new();
+
+    reader.reader.read_to_string(&mut content).await.unwrap();
+
+    content
+}
+
+#[test]
+fn test_conflict_text_merge() {
+    with_repo(|fixture| {
+        async move {
+            let details_id = create_conflict(&fixture, "a\nb\nc\n", "A\nb\nc\n", "a\nb\nC\n").await;
+
+            let info = fixture
+                .vault
+                .with_state(|state| {
+                    repo_files_details::selectors::select_info(state, details_id)
+                        .map(|info| (info.is_conflict, info.conflict_status))
+                })
+                .unwrap();
+            assert_eq!(info, (true, Status::Loaded));
+
+            let text_merge = fixture
+                .vault
+                .repo_files_details_conflict_text_merge(details_id)
+                .unwrap();
+            assert!(!text_merge.has_conflicts());
+            let merged = text_merge.merged().unwrap();
+            assert_eq!(merged, "A\nb\nC\n");
+
+            fixture
+                .vault
+                .repo_files_details_conflict_merge(details_id, merged.into_bytes())
+                .await
+                .unwrap();
+
+            assert_eq!(
+                read_file(&fixture, &fixture.encrypt_path("/file.txt")).await,
+                "A\nb\nC\n"
+            );
+
+            let info = fixture
+                .vault
+                .with_state(|state| {
+                    repo_files_details::selectors::select_info(state, details_id)
+                        .map(|info| (info.is_conflict, info.is_dirty))
+                })
+                .unwrap();
+            assert_eq!(info, (false, false));
+
+            fixture
+                .vault
+                .repo_files_details_destroy(details_id)
+                .await
+                .unwrap();
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_conflict_text_merge_conflicts() {
+    with_repo(|fixture| {
+        async move {
+            let details_id = create_conflict(&fixture, "a\n", "mine\n", "theirs\n").await;
+
+            let text_merge = fixture
+                .vault
+                .repo_files_details_conflict_text_merge(details_id)
+                .unwrap();
+            assert_eq!(
+                text_merge.chunks,
+                vec![RepoFilesDetailsTextMergeChunk::Conflict {
+                    base: "a\n".into(),
+                    mine: "mine\n".into(),
+                    theirs: "theirs\n".into(),
+                }]
+            );
+
+            fixture
+                .vault
+                .repo_files_details_conflict_keep_mine(details_id)
+                .await
+                .unwrap();
+
+            assert_eq!(
+                read_file(&fixture, &fixture.encrypt_path("/file.txt")).await,
+                "mine\n"
+            );
+
+            fixture
+                .vault
+                .repo_files_details_destroy(details_id)
+                .await
+                .unwrap();
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_conflict_keep_theirs() {
+    with_repo(|fixture| {
+        async move {
+            let details_id = create_conflict(&fixture, "base", "mine", "theirs").await;
+
+            fixture
+                .vault
+                .repo_files_details_conflict_keep_theirs(details_id)
+                .unwrap();
+
+            let (bytes, _) = fixture.vault.with_state(|state| {
+                let (bytes, version) =
+                    repo_files_details::selectors::select_content_bytes_version(state, details_id);
+
+                (bytes.map(ToOwned::to_owned), version)
+            });
+            assert_eq!(bytes, Some("theirs".as_bytes().to_vec()));
+
+            let info = fixture
+                .vault
+                .with_state(|state| {
+                    repo_files_details::selectors::select_info(state, details_id)
+                        .map(|info| (info.is_conflict, info.is_dirty))
+                })
+                .unwrap();
+            assert_eq!(info, (false, false));
+
+            assert_eq!(
+                read_file(&fixture, &fixture.encrypt_path("/file.txt")).await,
+                "theirs"
+            );
+
+            fixture
+                .vault
+                .repo_files_details_destroy(details_id)
+                .await
+                .unwrap();
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_conflict_save_as_copy() {
+    with_repo(|fixture| {
+        async move {
+            let details_id = create_conflict(&fixture, "base", "mine", "theirs").await;
+
+            let copy_path = fixture
+                .vault
+                .repo_files_details_conflict_save_as_copy(details_id)
+                .await
+                .unwrap();
+
+            assert_eq!(copy_path, fixture.encrypt_path("/file (1).txt"));
+            assert_eq!(read_file(&fixture, &copy_path).await, "mine");
+            assert_eq!(
+                read_file(&fixture, &fixture.encrypt_path("/file.txt")).await,
+                "theirs"
+            );
+
+            let info = fixture
+                .vault
+                .with_state(|state| {
+                    repo_files_details::selectors::select_info(state, details_id)
+                        .map(|info| (info.is_conflict, info.is_dirty))
+                })
+                .unwrap();
+            assert_eq!(info, (false, false));
+
+            fixture
+                .vault
+                .repo_files_details_destroy(details_id)
+                .await
+                .unwrap();
+        }
+        .boxed()
+    });
+}
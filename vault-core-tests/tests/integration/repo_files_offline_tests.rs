@@ -0,0 +1,391 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use axum::{http::StatusCode, response::IntoResponse};
+use futures::{io::Cursor, AsyncReadExt, FutureExt};
+use similar_asserts::assert_eq;
+use vault_core::{
+    common::state::BoxAsyncRead,
+    repo_files_offline::{selectors, OfflineDiskCache},
+    types::EncryptedPath,
+};
+use vault_core_tests::helpers::{wait_for_async, with_repo};
+use vault_fake_remote::fake_remote::interceptor::InterceptorResult;
+
+#[derive(Clone, Default)]
+struct MemoryOfflineDiskCache {
+    data: Arc<Mutex<HashMap<String, Vec<u8>>>>,
+    index: Arc<Mutex<Option<Vec<u8>>>>,
+}
+
+#[async_trait]
+impl OfflineDiskCache for MemoryOfflineDiskCache {
+    async fn get(&self, key: &str) -> Result<Option<BoxAsyncRead>, String> {
+        Ok(self
+            .data
+            .lock()
+            .unwrap()
+            .get(key)
+            .cloned()
+            .map(|value| Box::pin(Cursor::new(value)) as BoxAsyncRead))
+    }
+
+    async fn set(&self, key: &str, mut reader: BoxAsyncRead) -> Result<i64, String> {
+        let mut value = Vec::new();
+
+        reader
+            .read_to_end(&mut value)
+            .await
+            .map_err(|err| err.to_string())?;
+
+        let size = value.len() as i64;
+
+        self.data.lock().unwrap().insert(key.to_owned(), value);
+
+        Ok(size)
+    }
+
+    async fn remove(&self, key: &str) -> Result<(), String> {
+        self.data.lock().unwrap().remove(key);
+
+        Ok(())
+    }
+
+    async fn clear(&self) -> Result<(), String> {
+        self.data.lock().unwrap().clear();
+        *self.index.lock().unwrap() = None;
+
+        Ok(())
+    }
+
+    fn get_index(&self) -> Result<Option<Vec<u8>>, String> {
+        Ok(self.index.lock().unwrap().clone())
+    }
+
+    fn set_index(&self, value: &[u8]) -> Result<(), String> {
+        *self.index.lock().unwrap() = Some(value.to_vec());
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_pin_file() {
+    with_repo(|fixture| {
+        async move {
+            let disk_cache = MemoryOfflineDiskCache::default();
+
+            fixture
+                .vault
+                .repo_files_offline_set_disk_cache(Some(Box::new(disk_cache.clone())));
+
+            fixture.upload_file("/file.txt", "test").await;
+
+            let path = fixture.encrypt_path("/file.txt");
+
+            fixture
+                .vault
+                .repo_files_offline_pin(fixture.repo_id.clone(), path.clone())
+                .await
+                .unwrap();
+
+            assert!(fixture
+                .vault
+                .repo_files_offline_is_pinned(&fixture.repo_id, &path));
+
+            let cached = disk_cache.data.lock().unwrap().clone();
+            assert_eq!(cached.len(), 1);
+            // disk cache values are encrypted
+            assert!(cached.values().all(|value| value != b"test"));
+
+            // reads are served from the cache
+            fixture.fake_remote.intercept(Box::new(|parts| {
+                if parts.uri.path().contains("/content/api")
+                    && parts.uri.path().contains("/files/get")
+                {
+                    InterceptorResult::Response(StatusCode::INTERNAL_SERVER_ERROR.into_response())
+                } else {
+                    InterceptorResult::Ignore
+                }
+            }));
+
+            let mut reader = fixture
+                .vault
+                .repo_files_get_file_reader(&fixture.repo_id, &path)
+                .unwrap()
+                .reader()
+                .await
+                .unwrap();
+
+            let mut content = String::new();
+            reader.reader.read_to_string(&mut content).await.unwrap();
+            assert_eq!(content, "test");
+
+            let info = fixture.vault.repo_files_offline_get_info();
+            assert_eq!(info.pins_count, 1);
+            assert_eq!(info.entries_count, 1);
+            assert_eq!(info.pinned_used, info.used);
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_pin_dir_refresh() {
+    with_repo(|fixture| {
+        async move {
+            let disk_cache = MemoryOfflineDiskCache::default();
+
+            fixture
+                .vault
+                .repo_files_offline_set_disk_cache(Some(Box::new(disk_cache.clone())));
+
+            fixture.create_dir("/dir").await;
+            fixture.upload_file("/dir/file1.txt", "test1").await;
+
+            fixture
+                .vault
+                .repo_files_offline_pin(fixture.repo_id.clone(), fixture.encrypt_path("/dir"))
+                .await
+                .unwrap();
+
+            assert_eq!(disk_cache.data.lock().unwrap().len(), 1);
+
+            fixture.upload_file("/dir/file2.txt", "test2").await;
+
+            let wait_disk_cache = disk_cache.clone();
+            assert!(
+                wait_for_async(5000, move || wait_disk_cache.data.lock().unwrap().len()
+                    == 2)
+                .await
+            );
+
+            fixture
+                .vault
+                .repo_files_service
+                .delete_file(&fixture.repo_id, &fixture.encrypt_path("/dir/file1.txt"))
+                .await
+                .unwrap();
+
+            let wait_disk_cache = disk_cache.clone();
+            assert!(
+                wait_for_async(5000, move || wait_disk_cache.data.lock().unwrap().len()
+                    == 1)
+                .await
+            );
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_unpin_evict() {
+    with_repo(|fixture| {
+        async move {
+            let disk_cache = MemoryOfflineDiskCache::default();
+
+            fixture
+                .vault
+                .repo_files_offline_set_disk_cache(Some(Box::new(disk_cache.clone())));
+
+            fixture.upload_file("/file1.txt", "test1").await;
+            fixture.upload_file("/file2.txt", "test2").await;
+
+            for path in ["/file1.txt", "/file2.txt"] {
+                fixture
+                    .vault
+                    .repo_files_offline_pin(fixture.repo_id.clone(), fixture.encrypt_path(path))
+                    .await
+                    .unwrap();
+            }
+
+            // pinned files are not evicted
+            fixture.vault.repo_files_offline_set_quota(0).await.unwrap();
+            assert_eq!(disk_cache.data.lock().unwrap().len(), 2);
+
+            fixture
+                .vault
+                .repo_files_offline_unpin(&fixture.repo_id, &fixture.encrypt_path("/file1.txt"))
+                .await
+                .unwrap();
+
+            assert_eq!(disk_cache.data.lock().unwrap().len(), 1);
+            assert_eq!(fixture.vault.repo_files_offline_get_info().entries_count, 1);
+
+            fixture.vault.repo_files_offline_clear().await.unwrap();
+
+            assert_eq!(disk_cache.data.lock().unwrap().len(), 0);
+            assert!(!fixture
+                .vault
+                .repo_files_offline_is_pinned(&fixture.repo_id, &EncryptedPath("/".into())));
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_read_populates_cache() {
+    with_repo(|fixture| {
+        async move {
+            let disk_cache = MemoryOfflineDiskCache::default();
+
+            fixture
+                .vault
+                .repo_files_offline_set_disk_cache(Some(Box::new(disk_cache.clone())));
+
+            fixture.upload_file("/file1.txt", "test1").await;
+            fixture.upload_file("/file2.txt", "test2").await;
+
+            let read = |path: &'static str| {
+                let fixture = fixture.clone();
+
+                async move {
+                    let mut reader = fixture
+                        .vault
+                        .repo_files_get_file_reader(&fixture.repo_id, &fixture.encrypt_path(path))
+                        .unwrap()
+                        .reader()
+                        .await
+                        .unwrap();
+
+                    let mut content = String::new();
+                    reader.reader.read_to_string(&mut content).await.unwrap();
+                    content
+                }
+            };
+
+            assert_eq!(read("/file1.txt").await, "test1");
+
+            let wait_disk_cache = disk_cache.clone();
+            assert!(
+                wait_for_async(5000, move || wait_disk_cache.data.lock().unwrap().len()
+                    == 1)
+                .await
+            );
+            assert_eq!(fixture.vault.repo_files_offline_get_info().entries_count, 1);
+
+            // only one file fits, the least recently accessed one is evicted
+            let used = fixture.vault.repo_files_offline_get_info().used;
+            fixture
+                .vault
+                .repo_files_offline_set_quota(used)
+                .await
+                .unwrap();
+
+            assert_eq!(read("/file2.txt").await, "test2");
+
+            let file2_key =
+                selectors::get_cache_key(&fixture.repo_id, &fixture.encrypt_path("/file2.txt"));
+            let wait_disk_cache = disk_cache.clone();
+            assert!(
+                wait_for_async(5000, move || {
+                    let data = wait_disk_cache.data.lock().unwrap();
+
+                    data.len() == 1 && data.contains_key(&file2_key)
+                })
+                .await
+            );
+            assert_eq!(fixture.vault.repo_files_offline_get_info().used, used);
+
+            // the cached file is read without the remote
+            fixture.fake_remote.intercept(Box::new(|parts| {
+                if parts.uri.path().contains("/content/api")
+                    && parts.uri.path().contains("/files/get")
+                {
+                    InterceptorResult::Response(StatusCode::INTERNAL_SERVER_ERROR.into_response())
+                } else {
+                    InterceptorResult::Ignore
+                }
+            }));
+
+            assert_eq!(read("/file2.txt").await, "test2");
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_index_stored_in_disk_cache() {
+    with_repo(|fixture| {
+        async move {
+            let disk_cache = MemoryOfflineDiskCache::default();
+
+            fixture
+                .vault
+                .repo_files_offline_set_disk_cache(Some(Box::new(disk_cache.clone())));
+
+            fixture.upload_file("/file.txt", "test").await;
+
+            let path = fixture.encrypt_path("/file.txt");
+
+            fixture
+                .vault
+                .repo_files_offline_pin(fixture.repo_id.clone(), path.clone())
+                .await
+                .unwrap();
+
+            // only the index key is in the secure storage
+            assert!(fixture
+                .vault
+                .secure_storage_service
+                .get::<serde_json::Value>(selectors::INDEX_KEY_STORAGE_KEY)
+                .unwrap()
+                .is_some());
+
+            // the index is encrypted
+            let index = disk_cache.index.lock().unwrap().clone().unwrap();
+            assert!(!String::from_utf8_lossy(&index).contains(&path.0));
+
+            // the index is loaded again when the disk cache is set
+            fixture
+                .vault
+                .repo_files_offline_set_disk_cache(Some(Box::new(disk_cache.clone())));
+
+            assert!(fixture
+                .vault
+                .repo_files_offline_is_pinned(&fixture.repo_id, &path));
+            assert_eq!(fixture.vault.repo_files_offline_get_info().entries_count, 1);
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_logout_clears_cache() {
+    with_repo(|fixture| {
+        async move {
+            let disk_cache = MemoryOfflineDiskCache::default();
+
+            fixture
+                .vault
+                .repo_files_offline_set_disk_cache(Some(Box::new(disk_cache.clone())));
+
+            fixture.upload_file("/file.txt", "test").await;
+
+            fixture
+                .vault
+                .repo_files_offline_pin(fixture.repo_id.clone(), fixture.encrypt_path("/file.txt"))
+                .await
+                .unwrap();
+
+            fixture.vault.logout().unwrap();
+
+            let info = fixture.vault.repo_files_offline_get_info();
+            assert_eq!(info.pins_count, 0);
+            assert_eq!(info.entries_count, 0);
+
+            let wait_disk_cache = disk_cache.clone();
+            assert!(
+                wait_for_async(5000, move || {
+                    wait_disk_cache.data.lock().unwrap().is_empty()
+                        && wait_disk_cache.index.lock().unwrap().is_none()
+                })
+                .await
+            );
+        }
+        .boxed()
+    });
+}
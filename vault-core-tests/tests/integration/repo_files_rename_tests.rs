@@ -0,0 +1,495 @@
+use futures::FutureExt;
+use similar_asserts::assert_eq;
+use vault_core::{
+    repo_files::{
+        errors::RenameFileError, errors::RepoFilesErrors, selectors as repo_files_selectors,
+    },
+    repo_files_browsers::state::RepoFilesBrowserOptions,
+    repo_files_rename::{
+        errors::RenameFilesError,
+        state::{
+            RepoFilesRenamePattern, RepoFilesRenamePreviewItem, RepoFilesRenamePreviewStatus,
+            RepoFilesRenameResult, RepoFilesRenameRule,
+        },
+    },
+    types::{DecryptedName, EncryptedName, EncryptedPath},
+};
+use vault_core_tests::{fixtures::repo_fixture::RepoFixture, helpers::with_repo};
+
+async fn create_browser(fixture: &RepoFixture, selected: &[&str]) -> u32 {
+    let (browser_id, load_future) = fixture.vault.repo_files_browsers_create(
+        fixture.repo_id.clone(),
+        &EncryptedPath("/".into()),
+        RepoFilesBrowserOptions { select_name: None },
+    );
+    load_future.await.unwrap();
+
+    fixture.vault.repo_files_browsers_set_selection(
+        browser_id,
+        selected
+            .iter()
+            .map(|path| fixture.get_file_id(path))
+            .collect(),
+    );
+
+    browser_id
+}
+
+fn select_names(fixture: &RepoFixture) -> Vec<String> {
+    fixture.vault.with_state(|state| {
+        let mut names =
+            repo_files_selectors::select_files(state, &fixture.repo_id, &EncryptedPath("/".into()))
+                .filter_map(|file| file.decrypted_name().ok().map(|name| name.0.clone()))
+                .collect::<Vec<_>>();
+
+        names.sort();
+
+        names
+    })
+}
+
+fn regex_pattern(pattern: &str, replace: &str) -> RepoFilesRenamePattern {
+    RepoFilesRenamePattern {
+        rules: vec![RepoFilesRenameRule::Regex {
+            pattern: pattern.into(),
+            replace: replace.into(),
+        }],
+        include_extension: false,
+    }
+}
+
+fn preview_item(
+    fixture: &RepoFixture,
+    name: &str,
+    new_name: &str,
+    status: RepoFilesRenamePreviewStatus,
+) -> RepoFilesRenamePreviewItem {
+    let path = format!("/{}", name);
+
+    RepoFilesRenamePreviewItem {
+        file_id: fixture.get_file_id(&path),
+        path: fixture.encrypt_path(&path),
+        name: DecryptedName(name.into()),
+        new_name: DecryptedName(new_name.into()),
+        status,
+    }
+}
+
+#[test]
+fn test_preview() {
+    with_repo(|fixture| {
+        async move {
+            fixture.upload_file("/IMG_1.jpg", "1").await;
+            fixture.upload_file("/IMG_2.jpg", "2").await;
+            fixture.upload_file("/photo 2.jpg", "x").await;
+            fixture.upload_file("/a1.txt", "a").await;
+            fixture.upload_file("/a2.txt", "a").await;
+            fixture.upload_file("/other.txt", "o").await;
+
+            let browser_id = create_browser(
+                &fixture,
+                &[
+                    "/IMG_1.jpg",
+                    "/IMG_2.jpg",
+                    "/a1.txt",
+                    "/a2.txt",
+                    "/other.txt",
+                ],
+            )
+            .await;
+
+            let preview = fixture
+                .vault
+                .repo_files_browsers_rename_selected_preview(
+                    browser_id,
+                    &RepoFilesRenamePattern {
+                        rules: vec![
+                            RepoFilesRenameRule::Regex {
+                                pattern: r"^IMG_(\d+)$".into(),
+                                replace: "photo $1".into(),
+                            },
+                            RepoFilesRenameRule::Regex {
+                                pattern: r"^a\d$".into(),
+                                replace: "a".into(),
+                            },
+                        ],
+                        include_extension: false,
+                    },
+                )
+                .unwrap();
+
+            // items follow the browser order (sorted by name)
+            assert_eq!(
+                preview,
+                vec![
+                    preview_item(
+                        &fixture,
+                        "a1.txt",
+                        "a.txt",
+                        RepoFilesRenamePreviewStatus::Duplicate
+                    ),
+                    preview_item(
+                        &fixture,
+                        "a2.txt",
+                        "a.txt",
+                        RepoFilesRenamePreviewStatus::Duplicate
+                    ),
+                    preview_item(
+                        &fixture,
+                        "IMG_1.jpg",
+                        "photo 1.jpg",
+                        RepoFilesRenamePreviewStatus::Ok
+                    ),
+                    preview_item(
+                        &fixture,
+                        "IMG_2.jpg",
+                        "photo 2.jpg",
+                        RepoFilesRenamePreviewStatus::AlreadyExists
+                    ),
+                    preview_item(
+                        &fixture,
+                        "other.txt",
+                        "other.txt",
+                        RepoFilesRenamePreviewStatus::Unchanged
+                    ),
+                ]
+            );
+
+            // preview does not rename anything
+            assert_eq!(
+                select_names(&fixture),
+                vec![
+                    "IMG_1.jpg",
+                    "IMG_2.jpg",
+                    "a1.txt",
+                    "a2.txt",
+                    "other.txt",
+                    "photo 2.jpg"
+                ]
+            );
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_preview_invalid_pattern() {
+    with_repo(|fixture| {
+        async move {
+            fixture.upload_file("/file.txt", "f").await;
+
+            let browser_id = create_browser(&fixture, &["/file.txt"]).await;
+
+            assert!(matches!(
+                fixture.vault.repo_files_browsers_rename_selected_preview(
+                    browser_id,
+                    &regex_pattern("(", "")
+                ),
+                Err(RenameFilesError::InvalidPattern(_))
+            ));
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_rename_selected_numbering() {
+    with_repo(|fixture| {
+        async move {
+            fixture.upload_file("/b.jpg", "b").await;
+            fixture.upload_file("/a.jpg", "a").await;
+            fixture.upload_file("/c.jpg", "c").await;
+
+            let browser_id = create_browser(&fixture, &["/a.jpg", "/b.jpg", "/c.jpg"]).await;
+
+            let results = fixture
+                .vault
+                .repo_files_browsers_rename_selected(
+                    browser_id,
+                    &RepoFilesRenamePattern {
+                        rules: vec![RepoFilesRenameRule::Numbering {
+                            template: "holiday {n}".into(),
+                            start: 1,
+                            step: 1,
+                            padding: 2,
+                        }],
+                        include_extension: false,
+                    },
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(
+                results
+                    .iter()
+                    .map(|res| (
+                        res.name.0.as_str(),
+                        res.new_name.0.as_str(),
+                        res.result.is_ok()
+                    ))
+                    .collect::<Vec<_>>(),
+                vec![
+                    ("a.jpg", "holiday 01.jpg", true),
+                    ("b.jpg", "holiday 02.jpg", true),
+                    ("c.jpg", "holiday 03.jpg", true),
+                ]
+            );
+            assert_eq!(results[0].new_path, fixture.encrypt_path("/holiday 01.jpg"));
+
+            assert_eq!(
+                select_names(&fixture),
+                vec!["holiday 01.jpg", "holiday 02.jpg", "holiday 03.jpg"]
+            );
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_rename_selected_partial_failure() {
+    with_repo(|fixture| {
+        async move {
+            fixture.upload_file("/IMG_1.jpg", "1").await;
+            fixture.upload_file("/IMG_2.jpg", "2").await;
+            fixture.upload_file("/photo 2.jpg", "x").await;
+
+            let browser_id = create_browser(&fixture, &["/IMG_1.jpg", "/IMG_2.jpg"]).await;
+
+            let results = fixture
+                .vault
+                .repo_files_browsers_rename_selected(
+                    browser_id,
+                    &regex_pattern(r"^IMG_(\d+)$", "photo $1"),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(
+                results,
+                vec![
+                    RepoFilesRenameResult {
+                        file_id: fixture.get_file_id("/IMG_1.jpg"),
+                        name: DecryptedName("IMG_1.jpg".into()),
+                        new_name: DecryptedName("photo 1.jpg".into()),
+                        new_path: fixture.encrypt_path("/photo 1.jpg"),
+                        result: Ok(()),
+                    },
+                    RepoFilesRenameResult {
+                        file_id: fixture.get_file_id("/IMG_2.jpg"),
+                        name: DecryptedName("IMG_2.jpg".into()),
+                        new_name: DecryptedName("photo 2.jpg".into()),
+                        new_path: fixture.encrypt_path("/photo 2.jpg"),
+                        result: Err(RenameFileError::RemoteError(
+                            RepoFilesErrors::already_exists()
+                        )),
+                    },
+                ]
+            );
+
+            assert_eq!(
+                select_names(&fixture),
+                vec!["IMG_2.jpg", "photo 1.jpg", "photo 2.jpg"]
+            );
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_rename_selected_read_only() {
+    with_repo(|fixture| {
+        async move {
+            fixture.upload_file("/IMG_1.jpg", "1").await;
+
+            let browser_id = create_browser(&fixture, &["/IMG_1.jpg"]).await;
+
+            fixture.unlock_read_only();
+
+            let results = fixture
+                .vault
+                .repo_files_browsers_rename_selected(
+                    browser_id,
+                    &regex_pattern(r"^IMG_(\d+)$", "photo $1"),
+                )
+                .await
+                .unwrap();
+
+            assert!(matches!(
+                results[0].result,
+                Err(RenameFileError::RepoReadOnly(_))
+            ));
+            assert_eq!(select_names(&fixture), vec!["IMG_1.jpg"]);
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_rename_selected_swap() {
+    with_repo(|fixture| {
+        async move {
+            let (_, a) = fixture.upload_file("/a.txt", "a").await;
+            let (_, b) = fixture.upload_file("/b.txt", "b").await;
+            fixture.upload_file("/c.txt", "c").await;
+
+            let browser_id = create_browser(&fixture, &["/a.txt", "/b.txt", "/c.txt"]).await;
+
+            // a -> b and b -> a swap, c -> d frees nothing
+            let pattern = RepoFilesRenamePattern {
+                rules: vec![
+                    RepoFilesRenameRule::Regex {
+                        pattern: "^a$".into(),
+                        replace: "x".into(),
+                    },
+                    RepoFilesRenameRule::Regex {
+                        pattern: "^b$".into(),
+                        replace: "a".into(),
+                    },
+                    RepoFilesRenameRule::Regex {
+                        pattern: "^x$".into(),
+                        replace: "b".into(),
+                    },
+                    RepoFilesRenameRule::Regex {
+                        pattern: "^c$".into(),
+                        replace: "d".into(),
+                    },
+                ],
+                include_extension: false,
+            };
+
+            assert_eq!(
+                fixture
+                    .vault
+                    .repo_files_browsers_rename_selected_preview(browser_id, &pattern)
+                    .unwrap(),
+                vec![
+                    preview_item(&fixture, "a.txt", "b.txt", RepoFilesRenamePreviewStatus::Ok),
+                    preview_item(&fixture, "b.txt", "a.txt", RepoFilesRenamePreviewStatus::Ok),
+                    preview_item(&fixture, "c.txt", "d.txt", RepoFilesRenamePreviewStatus::Ok),
+                ]
+            );
+
+            let results = fixture
+                .vault
+                .repo_files_browsers_rename_selected(browser_id, &pattern)
+                .await
+                .unwrap();
+
+            assert!(results.iter().all(|res| res.result.is_ok()));
+            assert_eq!(select_names(&fixture), vec!["a.txt", "b.txt", "d.txt"]);
+            let remote_hash = |path: &str| {
+                fixture.vault.with_state(|state| {
+                    repo_files_selectors::select_file(state, &fixture.get_file_id(path))
+                        .and_then(|file| file.remote_hash.clone())
+                })
+            };
+            assert_eq!(remote_hash("/a.txt"), b.remote_hash);
+            assert_eq!(remote_hash("/b.txt"), a.remote_hash);
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_rename_selected_chain() {
+    with_repo(|fixture| {
+        async move {
+            fixture.upload_file("/1.txt", "1").await;
+            fixture.upload_file("/2.txt", "2").await;
+            fixture.upload_file("/3.txt", "3").await;
+
+            let browser_id = create_browser(&fixture, &["/1.txt", "/2.txt"]).await;
+
+            // 1 -> 2 is fine because 2 is renamed too, 2 -> 3 is not because
+            // 3 stays
+            let pattern = RepoFilesRenamePattern {
+                rules: vec![RepoFilesRenameRule::Numbering {
+                    template: "{n}".into(),
+                    start: 2,
+                    step: 1,
+                    padding: 0,
+                }],
+                include_extension: false,
+            };
+
+            assert_eq!(
+                fixture
+                    .vault
+                    .repo_files_browsers_rename_selected_preview(browser_id, &pattern)
+                    .unwrap(),
+                vec![
+                    preview_item(
+                        &fixture,
+                        "1.txt",
+                        "2.txt",
+                        RepoFilesRenamePreviewStatus::AlreadyExists
+                    ),
+                    preview_item(
+                        &fixture,
+                        "2.txt",
+                        "3.txt",
+                        RepoFilesRenamePreviewStatus::AlreadyExists
+                    ),
+                ]
+            );
+
+            let browser_id = create_browser(&fixture, &["/1.txt", "/2.txt", "/3.txt"]).await;
+
+            let results = fixture
+                .vault
+                .repo_files_browsers_rename_selected(browser_id, &pattern)
+                .await
+                .unwrap();
+
+            assert!(results.iter().all(|res| res.result.is_ok()));
+            assert_eq!(select_names(&fixture), vec!["2.txt", "3.txt", "4.txt"]);
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_preview_decrypt_name_error() {
+    with_repo(|fixture| {
+        async move {
+            fixture.upload_file("/a.txt", "a").await;
+            let file = fixture
+                .create_dir_encrypted(&EncryptedPath("/".into()), EncryptedName("plain".into()))
+                .await;
+
+            let browser_id = create_browser(&fixture, &["/a.txt"]).await;
+            fixture.vault.repo_files_browsers_set_selection(
+                browser_id,
+                vec![fixture.get_file_id("/a.txt"), file.id.clone()],
+            );
+
+            let preview = fixture
+                .vault
+                .repo_files_browsers_rename_selected_preview(browser_id, &regex_pattern("^a$", "b"))
+                .unwrap();
+
+            // dirs are listed first
+            assert_eq!(preview.len(), 2);
+            assert_eq!(preview[0].file_id, file.id);
+            assert_eq!(preview[0].name, DecryptedName("plain".into()));
+            assert!(matches!(
+                preview[0].status,
+                RepoFilesRenamePreviewStatus::DecryptNameError(_)
+            ));
+            assert_eq!(preview[1].new_name, DecryptedName("b.txt".into()));
+
+            let results = fixture
+                .vault
+                .repo_files_browsers_rename_selected(browser_id, &regex_pattern("^a$", "b"))
+                .await
+                .unwrap();
+
+            assert!(matches!(
+                results[0].result,
+                Err(RenameFileError::DecryptFilenameError(_))
+            ));
+            assert!(results[1].result.is_ok());
+        }
+        .boxed()
+    });
+}
@@ -5,7 +5,11 @@ use similar_asserts::assert_eq;
 use vault_core::{
     remote::{models, remote::RemoteFileTagsSetConditions, RemoteFileUploadConflictResolution},
     remote_files, repo_files,
-    repo_files_tags::{self, errors::DecryptTagsError, state::RepoFileTags},
+    repo_files_tags::{
+        self,
+        errors::{DecryptTagsError, SetTagsError},
+        state::RepoFileTags,
+    },
     types::{DecryptedName, EncryptedPath, RemoteName},
     utils::remote_path_utils,
 };
@@ -22,6 +26,7 @@ fn test_upload_overwrite() {
                 Some(Ok(RepoFileTags {
                     encrypted_hash: Some(hex::decode(repo_file.remote_hash.unwrap()).unwrap()),
                     hash: Some(md5::compute("test").to_vec()),
+                    version: None,
                     unknown: HashMap::new(),
                 }))
             );
@@ -33,6 +38,7 @@ fn test_upload_overwrite() {
                 Some(Ok(RepoFileTags {
                     encrypted_hash: Some(hex::decode(repo_file.remote_hash.unwrap()).unwrap()),
                     hash: Some(md5::compute("test1").to_vec()),
+                    version: None,
                     unknown: HashMap::new(),
                 }))
             );
@@ -54,6 +60,7 @@ fn test_upload_overwrite_without_hash() {
                         hex::decode(repo_file.remote_hash.clone().unwrap()).unwrap()
                     ),
                     hash: Some(md5::compute("test").to_vec()),
+                    version: None,
                     unknown: HashMap::new(),
                 }))
             );
@@ -235,6 +242,7 @@ fn test_only_unknown() {
                 Some(Ok(RepoFileTags {
                     encrypted_hash: Some(hex::decode(repo_file.remote_hash.unwrap()).unwrap()),
                     hash: None,
+                    version: None,
                     unknown: HashMap::from([("k1".into(), "v1".into())]),
                 }))
             );
@@ -284,6 +292,7 @@ fn test_only_unknown_no_encrypted_hash() {
             let tags = RepoFileTags {
                 encrypted_hash: None,
                 hash: None,
+                version: None,
                 unknown: HashMap::from([("k1".into(), "v1".into())]),
             };
             let encrypted_tags = tags.to_string(&cipher).unwrap();
@@ -341,6 +350,7 @@ fn test_tags_conflict_retry() {
                         hex::decode(repo_file.remote_hash.clone().unwrap()).unwrap()
                     ),
                     hash: Some(md5::compute("test").to_vec()),
+                    version: None,
                     unknown: HashMap::new(),
                 }))
             );
@@ -407,6 +417,7 @@ fn test_tags_conflict_retry() {
                 Some(Ok(RepoFileTags {
                     encrypted_hash: Some(hex::decode(repo_file.remote_hash.unwrap()).unwrap()),
                     hash: Some(md5::compute("test").to_vec()),
+                    version: None,
                     unknown: HashMap::from([("k1".into(), "v1".into())]),
                 }))
             );
@@ -451,6 +462,7 @@ fn test_dir() {
                 Some(Ok(RepoFileTags {
                     encrypted_hash: None,
                     hash: None,
+                    version: None,
                     unknown: HashMap::from([("k1".into(), "v1".into())]),
                 }))
             );
@@ -458,3 +470,30 @@ fn test_dir() {
         .boxed()
     });
 }
+
+#[test]
+fn test_set_tags_read_only() {
+    with_repo(|fixture| {
+        async move {
+            fixture.create_dir("/dir").await;
+
+            fixture.unlock_read_only();
+
+            let res = fixture
+                .vault
+                .repo_files_tags_service
+                .set_tags(
+                    &fixture.repo_id,
+                    &fixture.encrypt_path("/dir"),
+                    Box::new(|_file, tags| {
+                        tags.unknown.insert("k1".into(), "v1".into());
+                        Ok(())
+                    }),
+                )
+                .await;
+
+            assert!(matches!(res, Err(SetTagsError::RepoReadOnly(_))));
+        }
+        .boxed()
+    });
+}
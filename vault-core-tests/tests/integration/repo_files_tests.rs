@@ -1,13 +1,18 @@
-use futures::{io::Cursor, FutureExt};
+use futures::{io::Cursor, AsyncReadExt, FutureExt};
 use similar_asserts::assert_eq;
 use vault_core::{
     cipher::errors::DecryptFilenameError,
     common::errors::InvalidNameError,
     files::file_category::FileCategory,
-    repo_files::state::{
-        RepoFile, RepoFileName, RepoFilePath, RepoFileSize, RepoFileType, RepoFilesState,
-        RepoFilesUploadConflictResolution,
+    repo_files::{
+        errors::{CreateDirError, DeleteFileError},
+        state::{
+            RepoFile, RepoFileName, RepoFilePath, RepoFileSize, RepoFileType, RepoFilesState,
+            RepoFilesUploadConflictResolution,
+        },
     },
+    repo_files_read::{errors::GetFilesReaderError, state::RepoFileReadRange},
+    repos::{selectors as repos_selectors, state::RepoUnlockMode},
     types::{DecryptedName, DecryptedPath, EncryptedName, EncryptedPath, RemotePath, RepoFileId},
 };
 use vault_core_tests::helpers::with_repo;
@@ -70,6 +75,103 @@ fn test_repo_lock_unlock_remove() {
     });
 }
 
+#[test]
+fn test_repo_unlock_read_only() {
+    with_repo(|fixture| {
+        async move {
+            let is_read_only = || {
+                fixture.vault.with_state(|state| {
+                    repos_selectors::select_is_read_only(state, &fixture.repo_id)
+                })
+            };
+
+            let (_, file) = fixture.upload_file("/file1.txt", "test").await;
+
+            fixture.lock();
+            fixture
+                .vault
+                .repos_service
+                .unlock_repo(&fixture.repo_id, "password", RepoUnlockMode::UnlockReadOnly)
+                .unwrap();
+
+            assert!(is_read_only());
+
+            let res = fixture
+                .vault
+                .repo_files_service
+                .clone()
+                .create_dir_name(
+                    &fixture.repo_id,
+                    &EncryptedPath("/".into()),
+                    fixture.encrypt_filename("dir1"),
+                )
+                .await;
+            assert!(matches!(res, Err(CreateDirError::RepoReadOnly(_))));
+
+            let res = fixture
+                .vault
+                .repo_files_service
+                .delete_file(&fixture.repo_id, &file.encrypted_path)
+                .await;
+            assert!(matches!(res, Err(DeleteFileError::RepoReadOnly(_))));
+
+            fixture.lock();
+            fixture.unlock();
+
+            assert!(!is_read_only());
+
+            fixture.create_dir("/dir1").await;
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_repo_mount_read_only() {
+    with_repo(|fixture| {
+        async move {
+            let is_read_only = || {
+                fixture.vault.with_state(|state| {
+                    repos_selectors::select_is_read_only(state, &fixture.repo_id)
+                })
+            };
+
+            assert!(!is_read_only());
+
+            fixture
+                .fake_remote
+                .app_state
+                .state
+                .write()
+                .unwrap()
+                .mounts
+                .get_mut(&fixture.mount_id.0)
+                .unwrap()
+                .permissions
+                .as_mut()
+                .unwrap()
+                .write = false;
+
+            fixture.vault.repos_service.load_repos().await.unwrap();
+
+            assert!(is_read_only());
+
+            let res = fixture
+                .vault
+                .repo_files_service
+                .clone()
+                .create_dir_name(
+                    &fixture.repo_id,
+                    &EncryptedPath("/".into()),
+                    fixture.encrypt_filename("dir1"),
+                )
+                .await;
+            assert!(matches!(res, Err(CreateDirError::RepoReadOnly(_))));
+        }
+        .boxed()
+    });
+}
+
 #[test]
 fn test_name_decryption_error() {
     with_repo(|fixture| {
@@ -336,3 +438,80 @@ fn test_invalid_name() {
         .boxed()
     });
 }
+
+#[test]
+fn test_get_file_range_reader() {
+    with_repo(|fixture| {
+        async move {
+            let content = (0..200 * 1024).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+            let size = content.len() as i64;
+
+            fixture
+                .upload_file_bytes("/file.bin", content.clone())
+                .await;
+
+            let path = fixture.encrypt_path("/file.bin");
+
+            let read_range = |range: Option<RepoFileReadRange>| {
+                let path = path.clone();
+                let fixture = fixture.clone();
+
+                async move {
+                    let reader = fixture
+                        .vault
+                        .repo_files_get_file_range_reader(&fixture.repo_id, &path, range.as_ref())
+                        .await?;
+
+                    let mut data = Vec::new();
+                    let mut inner = reader.reader;
+                    inner.read_to_end(&mut data).await.unwrap();
+
+                    Ok::<_, GetFilesReaderError>((reader.range, reader.size, data))
+                }
+            };
+
+            let (range, file_size, data) = read_range(None).await.unwrap();
+            assert_eq!(range, None);
+            assert_eq!(file_size, size);
+            assert_eq!(data, content);
+
+            // inside the first block
+            let (range, _, data) = read_range(Some(RepoFileReadRange::FromStart {
+                start: 10,
+                end: Some(19),
+            }))
+            .await
+            .unwrap();
+            assert_eq!(range, Some(10..=19));
+            assert_eq!(data, content[10..=19]);
+
+            // across the second and third block
+            let (range, _, data) = read_range(Some(RepoFileReadRange::FromStart {
+                start: 100_000,
+                end: Some(150_000),
+            }))
+            .await
+            .unwrap();
+            assert_eq!(range, Some(100_000..=150_000));
+            assert_eq!(data, content[100_000..=150_000]);
+
+            // the last bytes
+            let (range, _, data) = read_range(Some(RepoFileReadRange::Suffix(1000)))
+                .await
+                .unwrap();
+            assert_eq!(range, Some(size - 1000..=size - 1));
+            assert_eq!(data, content[content.len() - 1000..]);
+
+            assert_eq!(
+                read_range(Some(RepoFileReadRange::FromStart {
+                    start: size,
+                    end: None,
+                }))
+                .await
+                .unwrap_err(),
+                GetFilesReaderError::RangeNotSatisfiable
+            );
+        }
+        .boxed()
+    });
+}
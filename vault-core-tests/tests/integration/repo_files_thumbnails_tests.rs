@@ -0,0 +1,357 @@
+use std::{
+    collections::HashMap,
+    io::Cursor,
+    sync::{Arc, Mutex},
+};
+
+use futures::FutureExt;
+use image::{ImageOutputFormat, RgbImage};
+use similar_asserts::assert_eq;
+use vault_core::{
+    common::state::Status,
+    repo_files_thumbnails::{errors::GetThumbnailError, ThumbnailsDiskCache},
+    types::{EncryptedPath, TimeMillis},
+};
+use vault_core_tests::helpers::with_repo;
+
+fn png_bytes(width: u32, height: u32) -> Vec<u8> {
+    let mut buf = Cursor::new(Vec::new());
+
+    RgbImage::from_pixel(width, height, image::Rgb([200, 100, 50]))
+        .write_to(&mut buf, ImageOutputFormat::Png)
+        .unwrap();
+
+    buf.into_inner()
+}
+
+fn image_dimensions(bytes: &[u8]) -> (u32, u32) {
+    let image = image::load_from_memory(bytes).unwrap();
+
+    (image.width(), image.height())
+}
+
+#[derive(Clone, Default)]
+struct MemoryThumbnailsDiskCache {
+    data: Arc<Mutex<HashMap<String, Vec<u8>>>>,
+}
+
+impl ThumbnailsDiskCache for MemoryThumbnailsDiskCache {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, String> {
+        Ok(self.data.lock().unwrap().get(key).cloned())
+    }
+
+    fn set(&self, key: &str, value: &[u8]) -> Result<(), String> {
+        self.data
+            .lock()
+            .unwrap()
+            .insert(key.to_owned(), value.to_owned());
+
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> Result<(), String> {
+        self.data.lock().unwrap().remove(key);
+
+        Ok(())
+    }
+
+    fn clear(&self) -> Result<(), String> {
+        self.data.lock().unwrap().clear();
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_get_thumbnail() {
+    with_repo(|fixture| {
+        async move {
+            let (_, file) = fixture
+                .upload_file_bytes("/image.png", png_bytes(600, 300))
+                .await;
+
+            let thumbnail = fixture
+                .vault
+                .repo_files_thumbnails_get(file.id.clone())
+                .await
+                .unwrap();
+
+            assert_eq!(thumbnail.content_type, "image/jpeg");
+            assert_eq!(image_dimensions(&thumbnail.bytes), (256, 128));
+
+            fixture.vault.with_state(|state| {
+                let thumbnail = state
+                    .repo_files_thumbnails
+                    .thumbnails
+                    .get(&file.id)
+                    .unwrap();
+
+                assert_eq!(thumbnail.status, Status::Loaded);
+                assert_eq!(thumbnail.hash, file.hash());
+                // thumbnails are kept encrypted
+                assert!(thumbnail.encrypted_bytes.is_some());
+            });
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_get_thumbnail_file_changed() {
+    with_repo(|fixture| {
+        async move {
+            let (_, file) = fixture
+                .upload_file_bytes("/image.png", png_bytes(600, 300))
+                .await;
+
+            let thumbnail = fixture
+                .vault
+                .repo_files_thumbnails_get(file.id.clone())
+                .await
+                .unwrap();
+
+            assert_eq!(image_dimensions(&thumbnail.bytes), (256, 128));
+
+            let (_, file) = fixture
+                .upload_file_bytes("/image.png", png_bytes(300, 600))
+                .await;
+
+            let thumbnail = fixture
+                .vault
+                .repo_files_thumbnails_get(file.id.clone())
+                .await
+                .unwrap();
+
+            assert_eq!(image_dimensions(&thumbnail.bytes), (128, 256));
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_get_thumbnail_not_supported() {
+    with_repo(|fixture| {
+        async move {
+            let (_, file) = fixture.upload_file("/file.txt", "test").await;
+
+            assert_eq!(
+                fixture.vault.repo_files_thumbnails_get(file.id).await,
+                Err(GetThumbnailError::NotSupported)
+            );
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_get_thumbnail_invalid_image() {
+    with_repo(|fixture| {
+        async move {
+            let (_, file) = fixture.upload_file("/image.png", "not an image").await;
+
+            assert!(matches!(
+                fixture.vault.repo_files_thumbnails_get(file.id).await,
+                Err(GetThumbnailError::GenerateThumbnailError(_))
+            ));
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_get_thumbnail_error_retry() {
+    with_repo(|fixture| {
+        async move {
+            let (_, file) = fixture.upload_file("/image.png", "not an image").await;
+
+            let retry_after = || {
+                fixture.vault.with_state(|state| {
+                    state
+                        .repo_files_thumbnails
+                        .thumbnails
+                        .get(&file.id)
+                        .unwrap()
+                        .retry_after
+                })
+            };
+
+            assert!(fixture
+                .vault
+                .repo_files_thumbnails_get(file.id.clone())
+                .await
+                .is_err());
+            let first_retry_after = retry_after().unwrap();
+            assert!(first_retry_after > fixture.vault.runtime.now());
+
+            // the error is kept until the retry is due
+            assert!(fixture
+                .vault
+                .repo_files_thumbnails_get(file.id.clone())
+                .await
+                .is_err());
+            assert_eq!(retry_after(), Some(first_retry_after));
+
+            fixture.vault.store.mutate(|state, _, _, _| {
+                state
+                    .repo_files_thumbnails
+                    .thumbnails
+                    .get_mut(&file.id)
+                    .unwrap()
+                    .retry_after = Some(TimeMillis(0));
+            });
+
+            assert!(fixture
+                .vault
+                .repo_files_thumbnails_get(file.id.clone())
+                .await
+                .is_err());
+            assert!(retry_after().unwrap() >= first_retry_after);
+            assert_ne!(retry_after(), Some(TimeMillis(0)));
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_lock_removes_thumbnails() {
+    with_repo(|fixture| {
+        async move {
+            let (_, file) = fixture
+                .upload_file_bytes("/image.png", png_bytes(600, 300))
+                .await;
+
+            fixture
+                .vault
+                .repo_files_thumbnails_get(file.id.clone())
+                .await
+                .unwrap();
+
+            fixture.lock();
+
+            fixture.vault.with_state(|state| {
+                assert!(state.repo_files_thumbnails.thumbnails.is_empty());
+            });
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_disk_cache() {
+    with_repo(|fixture| {
+        async move {
+            let disk_cache = MemoryThumbnailsDiskCache::default();
+
+            fixture
+                .vault
+                .repo_files_thumbnails_set_disk_cache(Some(Box::new(disk_cache.clone())));
+
+            let (_, file) = fixture
+                .upload_file_bytes("/image.png", png_bytes(600, 300))
+                .await;
+
+            let thumbnail = fixture
+                .vault
+                .repo_files_thumbnails_get(file.id.clone())
+                .await
+                .unwrap();
+
+            let cached = disk_cache.data.lock().unwrap().clone();
+            assert_eq!(cached.len(), 1);
+            // disk cache values are encrypted
+            assert!(cached.values().all(|value| value != &thumbnail.bytes));
+
+            fixture.lock();
+            fixture.unlock();
+
+            fixture
+                .vault
+                .repo_files_service
+                .load_files(&fixture.repo_id, &EncryptedPath("/".into()))
+                .await
+                .unwrap();
+
+            let cached_thumbnail = fixture
+                .vault
+                .repo_files_thumbnails_get(file.id.clone())
+                .await
+                .unwrap();
+
+            assert_eq!(cached_thumbnail, thumbnail);
+            assert_eq!(disk_cache.data.lock().unwrap().clone(), cached);
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_disk_cache_file_changed() {
+    with_repo(|fixture| {
+        async move {
+            let disk_cache = MemoryThumbnailsDiskCache::default();
+
+            fixture
+                .vault
+                .repo_files_thumbnails_set_disk_cache(Some(Box::new(disk_cache.clone())));
+
+            let (_, file) = fixture
+                .upload_file_bytes("/image.png", png_bytes(600, 300))
+                .await;
+            fixture
+                .vault
+                .repo_files_thumbnails_get(file.id.clone())
+                .await
+                .unwrap();
+            let cached = disk_cache.data.lock().unwrap().clone();
+
+            let (_, file) = fixture
+                .upload_file_bytes("/image.png", png_bytes(300, 600))
+                .await;
+            fixture
+                .vault
+                .repo_files_thumbnails_get(file.id.clone())
+                .await
+                .unwrap();
+
+            // the thumbnail of the previous content is removed
+            let keys = disk_cache
+                .data
+                .lock()
+                .unwrap()
+                .keys()
+                .cloned()
+                .collect::<Vec<_>>();
+            assert_eq!(keys.len(), 1);
+            assert!(!cached.contains_key(&keys[0]));
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_logout_clears_disk_cache() {
+    with_repo(|fixture| {
+        async move {
+            let disk_cache = MemoryThumbnailsDiskCache::default();
+
+            fixture
+                .vault
+                .repo_files_thumbnails_set_disk_cache(Some(Box::new(disk_cache.clone())));
+
+            let (_, file) = fixture
+                .upload_file_bytes("/image.png", png_bytes(600, 300))
+                .await;
+            fixture
+                .vault
+                .repo_files_thumbnails_get(file.id.clone())
+                .await
+                .unwrap();
+            assert_eq!(disk_cache.data.lock().unwrap().len(), 1);
+
+            fixture.vault.logout().unwrap();
+
+            assert!(disk_cache.data.lock().unwrap().is_empty());
+        }
+        .boxed()
+    });
+}
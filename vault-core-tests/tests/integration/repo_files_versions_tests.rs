@@ -0,0 +1,430 @@
+use std::{collections::HashMap, time::Duration};
+
+use futures::{AsyncReadExt, FutureExt};
+use similar_asserts::assert_eq;
+use vault_core::{
+    files::{file_category::FileCategory, files_filter::FilesFilter},
+    repo_files_browsers,
+    repo_files_details::state::RepoFilesDetailsOptions,
+    repo_files_versions::{
+        errors::{CreateVersionError, PruneVersionsError, RestoreVersionError},
+        service::REPO_FILES_VERSIONS_SETTINGS_STORAGE_KEY,
+        state::RepoFilesVersionsSettings,
+    },
+    types::{DecryptedPath, EncryptedPath, RepoId},
+};
+use vault_core_tests::{
+    fixtures::repo_fixture::RepoFixture,
+    helpers::{repo_files_details::details_wait_content_loaded, with_repo},
+};
+
+fn enable_versions(fixture: &RepoFixture, max_count: Option<usize>) {
+    fixture
+        .vault
+        .repo_files_versions_set_settings(
+            &fixture.repo_id,
+            RepoFilesVersionsSettings {
+                enabled: true,
+                max_count,
+                max_age_days: None,
+            },
+        )
+        .unwrap();
+}
+
+async fn read_version(fixture: &RepoFixture, version_path: &EncryptedPath) -> String {
+    let mut reader = fixture
+        .vault
+        .repo_files_versions_get_reader(&fixture.repo_id, version_path)
+        .unwrap()
+        .reader()
+        .await
+        .unwrap();
+
+    let mut content = String::new();
+
+    reader.reader.read_to_string(&mut content).await.unwrap();
+
+    content
+}
+
+async fn read_file(fixture: &RepoFixture, path: &str) -> String {
+    let mut reader = fixture
+        .vault
+        .repo_files_get_file_reader(&fixture.repo_id, &fixture.encrypt_path(path))
+        .unwrap()
+        .reader()
+        .await
+        .unwrap();
+
+    let mut content = String::new();
+
+    reader.reader.read_to_string(&mut content).await.unwrap();
+
+    content
+}
+
+async fn create_version(fixture: &RepoFixture, path: &str) {
+    fixture
+        .vault
+        .repo_files_versions_service
+        .clone()
+        .create_version_if_enabled(&fixture.repo_id, &fixture.encrypt_path(path))
+        .await
+        .unwrap()
+        .unwrap();
+}
+
+#[test]
+fn test_settings() {
+    with_repo(|fixture| {
+        async move {
+            assert_eq!(
+                fixture
+                    .vault
+                    .repo_files_versions_get_settings(&fixture.repo_id)
+                    .unwrap(),
+                RepoFilesVersionsSettings::default()
+            );
+
+            enable_versions(&fixture, Some(3));
+
+            let expected_settings = RepoFilesVersionsSettings {
+                enabled: true,
+                max_count: Some(3),
+                max_age_days: None,
+            };
+
+            assert_eq!(
+                fixture
+                    .vault
+                    .repo_files_versions_get_settings(&fixture.repo_id)
+                    .unwrap(),
+                expected_settings
+            );
+
+            assert_eq!(
+                fixture
+                    .vault
+                    .secure_storage_service
+                    .get::<HashMap<RepoId, RepoFilesVersionsSettings>>(
+                        REPO_FILES_VERSIONS_SETTINGS_STORAGE_KEY
+                    )
+                    .unwrap(),
+                Some(HashMap::from([(
+                    fixture.repo_id.clone(),
+                    expected_settings
+                )]))
+            );
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_create_version_disabled() {
+    with_repo(|fixture| {
+        async move {
+            fixture.upload_file("/file.txt", "v1").await;
+
+            assert!(fixture
+                .vault
+                .repo_files_versions_service
+                .clone()
+                .create_version_if_enabled(&fixture.repo_id, &fixture.encrypt_path("/file.txt"))
+                .await
+                .unwrap()
+                .is_none());
+
+            assert!(fixture
+                .vault
+                .repo_files_versions_list(&fixture.repo_id, &fixture.encrypt_path("/file.txt"))
+                .await
+                .unwrap()
+                .is_empty());
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_details_save_creates_version() {
+    with_repo(|fixture| {
+        async move {
+            enable_versions(&fixture, None);
+
+            fixture.upload_file("/file.txt", "v1").await;
+
+            let (details_id, load_future) = fixture.vault.repo_files_details_create(
+                fixture.repo_id.clone(),
+                &fixture.encrypt_path("/file.txt"),
+                false,
+                RepoFilesDetailsOptions {
+                    autosave_interval: Duration::from_secs(20),
+                    load_content: FilesFilter {
+                        categories: vec![FileCategory::Text],
+                        exts: vec![],
+                    },
+                },
+            );
+            load_future.await.unwrap();
+
+            details_wait_content_loaded(fixture.vault.store.clone(), details_id).await;
+
+            fixture.vault.repo_files_details_edit(details_id);
+            fixture
+                .vault
+                .repo_files_details_set_content(details_id, "v2".as_bytes().to_vec())
+                .unwrap();
+            fixture
+                .vault
+                .repo_files_details_save(details_id)
+                .await
+                .unwrap();
+
+            assert_eq!(read_file(&fixture, "/file.txt").await, "v2");
+
+            let versions = fixture
+                .vault
+                .repo_files_versions_list(&fixture.repo_id, &fixture.encrypt_path("/file.txt"))
+                .await
+                .unwrap();
+
+            assert_eq!(versions.len(), 1);
+            assert_eq!(versions[0].original_path, DecryptedPath("/file.txt".into()));
+            assert_eq!(versions[0].size, Some(2));
+            assert_eq!(
+                read_version(&fixture, &versions[0].encrypted_path).await,
+                "v1"
+            );
+
+            fixture
+                .vault
+                .repo_files_details_destroy(details_id)
+                .await
+                .unwrap();
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_restore() {
+    with_repo(|fixture| {
+        async move {
+            enable_versions(&fixture, None);
+
+            fixture.upload_file("/file.txt", "v1").await;
+            create_version(&fixture, "/file.txt").await;
+            fixture.upload_file("/file.txt", "v2").await;
+
+            let path = fixture.encrypt_path("/file.txt");
+
+            let versions = fixture
+                .vault
+                .repo_files_versions_list(&fixture.repo_id, &path)
+                .await
+                .unwrap();
+
+            fixture
+                .vault
+                .repo_files_versions_restore(&fixture.repo_id, &path, &versions[0].encrypted_path)
+                .await
+                .unwrap();
+
+            assert_eq!(read_file(&fixture, "/file.txt").await, "v1");
+
+            // the overwritten file is kept as a new version
+            let versions = fixture
+                .vault
+                .repo_files_versions_list(&fixture.repo_id, &path)
+                .await
+                .unwrap();
+
+            assert_eq!(versions.len(), 2);
+            assert_eq!(
+                read_version(&fixture, &versions[0].encrypted_path).await,
+                "v2"
+            );
+            assert_eq!(
+                read_version(&fixture, &versions[1].encrypted_path).await,
+                "v1"
+            );
+
+            assert!(fixture.vault.with_state(|state| {
+                matches!(
+                    vault_core::repo_files::selectors::select_file(
+                        state,
+                        &fixture.get_file_id("/file.txt")
+                    )
+                    .unwrap()
+                    .tags
+                    .as_ref()
+                    .unwrap(),
+                    Ok(tags) if tags.version.is_none()
+                )
+            }));
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_restore_version_not_found() {
+    with_repo(|fixture| {
+        async move {
+            fixture.upload_file("/file.txt", "v1").await;
+
+            assert_eq!(
+                fixture
+                    .vault
+                    .repo_files_versions_restore(
+                        &fixture.repo_id,
+                        &fixture.encrypt_path("/file.txt"),
+                        &fixture.encrypt_path("/missing.txt"),
+                    )
+                    .await
+                    .unwrap_err(),
+                RestoreVersionError::VersionNotFound
+            );
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_prune_max_count() {
+    with_repo(|fixture| {
+        async move {
+            enable_versions(&fixture, Some(2));
+
+            for content in ["v1", "v2", "v3", "v4"] {
+                fixture.upload_file("/file.txt", content).await;
+                create_version(&fixture, "/file.txt").await;
+                // version names contain the creation time in millis
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+
+            let versions = fixture
+                .vault
+                .repo_files_versions_list(&fixture.repo_id, &fixture.encrypt_path("/file.txt"))
+                .await
+                .unwrap();
+
+            assert_eq!(versions.len(), 2);
+            assert_eq!(
+                read_version(&fixture, &versions[0].encrypted_path).await,
+                "v4"
+            );
+            assert_eq!(
+                read_version(&fixture, &versions[1].encrypted_path).await,
+                "v3"
+            );
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_versions_dir_hidden_in_browsers() {
+    with_repo(|fixture| {
+        async move {
+            enable_versions(&fixture, None);
+
+            fixture.upload_file("/file.txt", "v1").await;
+            create_version(&fixture, "/file.txt").await;
+
+            fixture
+                .vault
+                .repo_files_service
+                .load_files(&fixture.repo_id, &EncryptedPath("/".into()))
+                .await
+                .unwrap();
+
+            let file_ids = fixture.vault.with_state(|state| {
+                repo_files_browsers::selectors::select_file_ids(
+                    state,
+                    &fixture.repo_id,
+                    &EncryptedPath("/".into()),
+                )
+                .cloned()
+                .collect::<Vec<_>>()
+            });
+
+            assert_eq!(file_ids, vec![fixture.get_file_id("/file.txt")]);
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_read_only() {
+    with_repo(|fixture| {
+        async move {
+            enable_versions(&fixture, Some(1));
+
+            fixture.upload_file("/file.txt", "v1").await;
+            create_version(&fixture, "/file.txt").await;
+            fixture.upload_file("/file.txt", "v2").await;
+            create_version(&fixture, "/file.txt").await;
+
+            let path = fixture.encrypt_path("/file.txt");
+            let versions = fixture
+                .vault
+                .repo_files_versions_list(&fixture.repo_id, &path)
+                .await
+                .unwrap();
+
+            fixture.unlock_read_only();
+
+            assert!(matches!(
+                fixture
+                    .vault
+                    .repo_files_versions_service
+                    .clone()
+                    .create_version(&fixture.repo_id, &path)
+                    .await,
+                Err(CreateVersionError::RepoReadOnly(_))
+            ));
+
+            assert!(matches!(
+                fixture
+                    .vault
+                    .repo_files_versions_restore(
+                        &fixture.repo_id,
+                        &path,
+                        &versions[0].encrypted_path
+                    )
+                    .await,
+                Err(RestoreVersionError::RepoReadOnly(_))
+            ));
+
+            assert!(matches!(
+                fixture
+                    .vault
+                    .repo_files_versions_prune(&fixture.repo_id, &path)
+                    .await,
+                Err(PruneVersionsError::RepoReadOnly(_))
+            ));
+
+            assert_eq!(
+                fixture
+                    .vault
+                    .repo_files_versions_list(&fixture.repo_id, &path)
+                    .await
+                    .unwrap()
+                    .len(),
+                versions.len()
+            );
+
+            fixture
+                .vault
+                .repo_files_service
+                .load_files(&fixture.repo_id, &EncryptedPath("/".into()))
+                .await
+                .unwrap();
+            assert_eq!(read_file(&fixture, "/file.txt").await, "v2");
+        }
+        .boxed()
+    });
+}
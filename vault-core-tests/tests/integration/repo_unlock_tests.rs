@@ -0,0 +1,130 @@
+use std::{collections::HashMap, time::Duration};
+
+use futures::FutureExt;
+use vault_core::{
+    repo_unlock::{self, state::RepoUnlockOptions},
+    repos::{
+        errors::{InvalidPasswordError, TooManyAttemptsError, UnlockRepoError},
+        service::REPO_UNLOCK_ATTEMPTS_STORAGE_KEY,
+        state::{RepoUnlockAttempts, RepoUnlockLockout, RepoUnlockMode},
+    },
+    types::RepoId,
+};
+use vault_core_tests::helpers::with_repo;
+
+#[test]
+fn test_unlock_backoff() {
+    with_repo(|fixture| {
+        async move {
+            fixture.lock();
+
+            fixture.vault.store.mutate(|state, _, _, _| {
+                state.config.repos.unlock_backoff.base_delay = Duration::from_millis(200);
+            });
+
+            let unlock_id = fixture.vault.repo_unlock_create(
+                fixture.repo_id.clone(),
+                RepoUnlockOptions {
+                    mode: RepoUnlockMode::Unlock,
+                },
+            );
+            let get_info = || {
+                fixture.vault.with_state(|state| {
+                    repo_unlock::selectors::select_info(state, unlock_id)
+                        .map(|info| (info.failed_attempts, info.retry_at))
+                        .unwrap()
+                })
+            };
+
+            for _ in 0..3 {
+                assert_eq!(
+                    fixture.vault.repo_unlock_unlock(unlock_id, "wrong"),
+                    Err(UnlockRepoError::InvalidPassword(InvalidPasswordError))
+                );
+            }
+
+            let (failed_attempts, retry_at) = get_info();
+            assert_eq!(failed_attempts, 3);
+            assert!(retry_at.unwrap() > fixture.vault.runtime.now());
+
+            // the correct password is refused as well until the delay passes
+            assert!(matches!(
+                fixture.vault.repo_unlock_unlock(unlock_id, "password"),
+                Err(UnlockRepoError::TooManyAttempts(TooManyAttemptsError { retry_after }))
+                    if retry_after <= Duration::from_millis(200)
+            ));
+
+            let stored = fixture
+                .vault
+                .secure_storage_service
+                .get::<HashMap<RepoId, RepoUnlockAttempts>>(REPO_UNLOCK_ATTEMPTS_STORAGE_KEY)
+                .unwrap()
+                .unwrap();
+            assert_eq!(stored.get(&fixture.repo_id).unwrap().failed, 3);
+
+            fixture
+                .vault
+                .runtime
+                .sleep(Duration::from_millis(250))
+                .await;
+
+            fixture
+                .vault
+                .repo_unlock_unlock(unlock_id, "password")
+                .unwrap();
+
+            assert_eq!(get_info(), (0, None));
+
+            let stored = fixture
+                .vault
+                .secure_storage_service
+                .get::<HashMap<RepoId, RepoUnlockAttempts>>(REPO_UNLOCK_ATTEMPTS_STORAGE_KEY)
+                .unwrap()
+                .unwrap();
+            assert!(stored.is_empty());
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_unlock_lockout() {
+    with_repo(|fixture| {
+        async move {
+            fixture.lock();
+
+            fixture
+                .vault
+                .repos_set_unlock_lockout(Some(RepoUnlockLockout {
+                    max_failed_attempts: 2,
+                    duration: Duration::from_secs(60 * 60),
+                }));
+
+            for _ in 0..2 {
+                assert!(fixture
+                    .vault
+                    .repos_service
+                    .unlock_repo(&fixture.repo_id, "wrong", RepoUnlockMode::Unlock)
+                    .is_err());
+            }
+
+            // attempts are restored from the secure storage
+            fixture.vault.store.mutate(|state, _, _, _| {
+                state.repos.unlock_attempts.clear();
+            });
+            fixture.vault.repos_service.load_repos().await.unwrap();
+
+            match fixture.vault.repos_service.unlock_repo(
+                &fixture.repo_id,
+                "password",
+                RepoUnlockMode::Unlock,
+            ) {
+                Err(UnlockRepoError::TooManyAttempts(TooManyAttemptsError { retry_after })) => {
+                    assert!(retry_after > Duration::from_secs(59 * 60))
+                }
+                res => panic!("expected too many attempts got {:?}", res),
+            }
+        }
+        .boxed()
+    });
+}
@@ -0,0 +1,142 @@
+use futures::FutureExt;
+use similar_asserts::assert_eq;
+use vault_core::{
+    notifications,
+    remote::ApiErrorCode,
+    repo_files::errors::CopyFileError,
+    space_usage::state::SpaceUsageSeverity,
+    user_error::{ErrorCode, UserError},
+};
+use vault_core_tests::helpers::with_repo;
+
+const MIB: i64 = 1024 * 1024;
+
+#[test]
+fn test_space_usage() {
+    with_repo(|fixture| {
+        async move {
+            fixture
+                .fake_remote
+                .app_state
+                .files_service
+                .set_space_total(&fixture.mount_id.0, Some(4 * MIB))
+                .unwrap();
+
+            fixture
+                .upload_file_bytes("/file.bin", vec![0; (MIB + MIB / 2) as usize])
+                .await;
+
+            fixture.vault.space_usage_service.load().await.unwrap();
+
+            let space_usage = fixture
+                .vault
+                .with_state(|state| state.space_usage.space_usage.clone())
+                .unwrap();
+
+            assert_eq!(space_usage.used, 2 * MIB);
+            assert_eq!(space_usage.total, 4 * MIB);
+            assert_eq!(space_usage.percentage, 50);
+            assert!(matches!(space_usage.severity, SpaceUsageSeverity::Normal));
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_copy_quota_exceeded() {
+    with_repo(|fixture| {
+        async move {
+            let (_, file) = fixture.upload_file("/file.txt", "test").await;
+
+            let space_used = fixture
+                .fake_remote
+                .app_state
+                .state
+                .read()
+                .unwrap()
+                .filesystems
+                .get(&fixture.mount_id.0)
+                .unwrap()
+                .space_used();
+
+            fixture
+                .fake_remote
+                .app_state
+                .files_service
+                .set_space_total(&fixture.mount_id.0, Some(space_used + 10))
+                .unwrap();
+
+            let res = fixture
+                .vault
+                .repo_files_service
+                .copy_file_to_path(
+                    &fixture.repo_id,
+                    &file.encrypted_path,
+                    &fixture.encrypt_path("/file copy.txt"),
+                )
+                .await;
+
+            assert!(matches!(
+                res,
+                Err(CopyFileError::RemoteError(err))
+                    if err.is_api_error_code(ApiErrorCode::QuotaExceeded)
+            ));
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_copy_quota_exceeded_error_code() {
+    with_repo(|fixture| {
+        async move {
+            let (_, file) = fixture.upload_file("/file.txt", "test").await;
+
+            let space_used = fixture
+                .fake_remote
+                .app_state
+                .state
+                .read()
+                .unwrap()
+                .filesystems
+                .get(&fixture.mount_id.0)
+                .unwrap()
+                .space_used();
+
+            fixture
+                .fake_remote
+                .app_state
+                .files_service
+                .set_space_total(&fixture.mount_id.0, Some(space_used + 10))
+                .unwrap();
+
+            let res = fixture
+                .vault
+                .repo_files_service
+                .copy_file_to_path(
+                    &fixture.repo_id,
+                    &file.encrypted_path,
+                    &fixture.encrypt_path("/file copy.txt"),
+                )
+                .await;
+
+            let err = res.unwrap_err();
+
+            assert_eq!(err.error_code(), ErrorCode::RemoteQuotaExceeded);
+            assert_eq!(err.error_code().as_str(), "remote.quota_exceeded");
+
+            fixture.vault.notifications_show_error(&err);
+
+            assert_eq!(
+                fixture.vault.with_state(|state| {
+                    notifications::selectors::select_notifications(state)
+                        .into_iter()
+                        .map(|notification| notification.error_code.clone())
+                        .collect::<Vec<_>>()
+                }),
+                vec![Some(ErrorCode::RemoteQuotaExceeded)]
+            );
+        }
+        .boxed()
+    });
+}
@@ -0,0 +1,219 @@
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use futures::{AsyncReadExt, FutureExt};
+use similar_asserts::assert_eq;
+use vault_core::{
+    sync::{
+        local::BoxSyncLocal,
+        state::{SyncActionReason, SyncActionType, SyncConfig, SyncDirection},
+    },
+    types::DecryptedPath,
+};
+use vault_core_tests::{fixtures::repo_fixture::RepoFixture, helpers::with_repo};
+use vault_native::sync_local::FileSyncLocal;
+
+fn create_local_dir() -> PathBuf {
+    let root = std::env::temp_dir().join(format!("vault-sync-{}", uuid::Uuid::new_v4()));
+
+    std::fs::create_dir_all(&root).unwrap();
+
+    root
+}
+
+fn write_local_file(root: &Path, name: &str, content: &str, modified: SystemTime) {
+    let path = root.join(name);
+
+    std::fs::write(&path, content).unwrap();
+
+    std::fs::File::options()
+        .write(true)
+        .open(&path)
+        .unwrap()
+        .set_modified(modified)
+        .unwrap();
+}
+
+async fn read_file(fixture: &RepoFixture, path: &str) -> String {
+    let mut reader = fixture
+        .vault
+        .repo_files_get_file_reader(&fixture.repo_id, &fixture.encrypt_path(path))
+        .unwrap()
+        .reader()
+        .await
+        .unwrap();
+
+    let mut content = String::new();
+
+    reader.reader.read_to_string(&mut content).await.unwrap();
+
+    content
+}
+
+fn two_way_config(fixture: &RepoFixture) -> SyncConfig {
+    SyncConfig {
+        repo_id: fixture.repo_id.clone(),
+        remote_path: DecryptedPath("/sync".into()),
+        direction: SyncDirection::TwoWay,
+    }
+}
+
+#[test]
+fn test_two_way_conflict_local_wins_keeps_remote_version() {
+    with_repo(|fixture| {
+        async move {
+            fixture.upload_file("/sync/file.txt", "remote").await;
+
+            let root = create_local_dir();
+            write_local_file(
+                &root,
+                "file.txt",
+                "local newer",
+                SystemTime::now() + Duration::from_secs(3600),
+            );
+            let local: BoxSyncLocal = Box::new(FileSyncLocal::new(root.clone()));
+
+            let result = fixture
+                .vault
+                .sync_run(&two_way_config(&fixture), &local)
+                .await
+                .unwrap();
+
+            assert_eq!(result.errors, vec![]);
+            assert_eq!(result.plan.actions.len(), 1);
+            assert_eq!(result.plan.actions[0].typ, SyncActionType::Upload);
+            assert_eq!(result.plan.actions[0].reason, SyncActionReason::Conflict);
+
+            assert_eq!(read_file(&fixture, "/sync/file.txt").await, "local newer");
+
+            let versions = fixture
+                .vault
+                .repo_files_versions_list(&fixture.repo_id, &fixture.encrypt_path("/sync/file.txt"))
+                .await
+                .unwrap();
+            assert_eq!(versions.len(), 1);
+
+            let mut reader = fixture
+                .vault
+                .repo_files_versions_get_reader(&fixture.repo_id, &versions[0].encrypted_path)
+                .unwrap()
+                .reader()
+                .await
+                .unwrap();
+            let mut content = String::new();
+            reader.reader.read_to_string(&mut content).await.unwrap();
+            assert_eq!(content, "remote");
+
+            std::fs::remove_dir_all(&root).unwrap();
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_two_way_conflict_remote_wins_keeps_local_copy() {
+    with_repo(|fixture| {
+        async move {
+            fixture.upload_file("/sync/file.txt", "remote newer").await;
+
+            let root = create_local_dir();
+            write_local_file(
+                &root,
+                "file.txt",
+                "local",
+                SystemTime::now() - Duration::from_secs(3600),
+            );
+            let local: BoxSyncLocal = Box::new(FileSyncLocal::new(root.clone()));
+
+            let result = fixture
+                .vault
+                .sync_run(&two_way_config(&fixture), &local)
+                .await
+                .unwrap();
+
+            assert_eq!(result.errors, vec![]);
+            assert_eq!(result.plan.actions.len(), 1);
+            assert_eq!(result.plan.actions[0].typ, SyncActionType::Download);
+            assert_eq!(result.plan.actions[0].reason, SyncActionReason::Conflict);
+
+            assert_eq!(
+                std::fs::read_to_string(root.join("file.txt")).unwrap(),
+                "remote newer"
+            );
+            assert_eq!(read_file(&fixture, "/sync/file (1).txt").await, "local");
+
+            // the next run brings the copy to the local side
+            let result = fixture
+                .vault
+                .sync_run(&two_way_config(&fixture), &local)
+                .await
+                .unwrap();
+
+            assert_eq!(result.errors, vec![]);
+            assert_eq!(
+                std::fs::read_to_string(root.join("file (1).txt")).unwrap(),
+                "local"
+            );
+
+            std::fs::remove_dir_all(&root).unwrap();
+        }
+        .boxed()
+    });
+}
+
+#[test]
+fn test_two_way_state_survives_logout() {
+    with_repo(|fixture| {
+        async move {
+            fixture.upload_file("/sync/file.txt", "remote").await;
+
+            let root = create_local_dir();
+            let local: BoxSyncLocal = Box::new(FileSyncLocal::new(root.clone()));
+
+            let result = fixture
+                .vault
+                .sync_run(&two_way_config(&fixture), &local)
+                .await
+                .unwrap();
+
+            assert_eq!(result.errors, vec![]);
+            assert_eq!(result.plan.actions.len(), 1);
+            assert_eq!(result.plan.actions[0].typ, SyncActionType::Download);
+
+            write_local_file(
+                &root,
+                "file.txt",
+                "local changed",
+                SystemTime::now() + Duration::from_secs(3600),
+            );
+
+            fixture.user_fixture.logout();
+
+            let fixture1 = fixture.new_session();
+            fixture1.user_fixture.login();
+            fixture1.user_fixture.load().await;
+            fixture1.unlock();
+
+            // only the local side changed since the last run
+            let result = fixture1
+                .vault
+                .sync_run(&two_way_config(&fixture1), &local)
+                .await
+                .unwrap();
+
+            assert_eq!(result.errors, vec![]);
+            assert_eq!(result.plan.actions.len(), 1);
+            assert_eq!(result.plan.actions[0].typ, SyncActionType::Upload);
+            assert_eq!(result.plan.actions[0].reason, SyncActionReason::Changed);
+            assert_eq!(
+                read_file(&fixture1, "/sync/file.txt").await,
+                "local changed"
+            );
+
+            std::fs::remove_dir_all(&root).unwrap();
+        }
+        .boxed()
+    });
+}
@@ -181,6 +181,10 @@ fn expected_transfers_transferring(transfers: &TransfersState, attempts: usize)
                 transferred_bytes: 0,
                 attempts,
                 order: 0,
+                account_id: transfers
+                    .transfers
+                    .get(&1)
+                    .and_then(|t| t.account_id.clone()),
             },
         )]
         .into(),
@@ -227,6 +231,10 @@ fn expected_transfers_transferring_progress(
                 transferred_bytes: 4,
                 attempts,
                 order: 0,
+                account_id: transfers
+                    .transfers
+                    .get(&1)
+                    .and_then(|t| t.account_id.clone()),
             },
         )]
         .into(),
@@ -1074,6 +1074,10 @@ fn expected_transfers_waiting(transfers: &TransfersState) -> TransfersState {
                 transferred_bytes: 0,
                 attempts: 0,
                 order: 0,
+                account_id: transfers
+                    .transfers
+                    .get(&1)
+                    .and_then(|t| t.account_id.clone()),
             },
         )]
         .into(),
@@ -1117,6 +1121,10 @@ fn expected_transfers_processing(transfers: &TransfersState, attempts: usize) ->
                 transferred_bytes: 0,
                 attempts,
                 order: 0,
+                account_id: transfers
+                    .transfers
+                    .get(&1)
+                    .and_then(|t| t.account_id.clone()),
             },
         )]
         .into(),
@@ -1160,6 +1168,10 @@ fn expected_transfers_transferring(transfers: &TransfersState, attempts: usize)
                 transferred_bytes: 0,
                 attempts,
                 order: 0,
+                account_id: transfers
+                    .transfers
+                    .get(&1)
+                    .and_then(|t| t.account_id.clone()),
             },
         )]
         .into(),
@@ -1206,6 +1218,10 @@ fn expected_transfers_transferring_progress(
                 transferred_bytes: 4,
                 attempts,
                 order: 0,
+                account_id: transfers
+                    .transfers
+                    .get(&1)
+                    .and_then(|t| t.account_id.clone()),
             },
         )]
         .into(),
@@ -1246,6 +1262,10 @@ fn expected_transfers_waiting_failed(
                 transferred_bytes: 0,
                 attempts,
                 order: 0,
+                account_id: transfers
+                    .transfers
+                    .get(&1)
+                    .and_then(|t| t.account_id.clone()),
             },
         )]
         .into(),
@@ -1288,6 +1308,10 @@ fn expected_transfers_failed(transfers: &TransfersState, attempts: usize) -> Tra
                 transferred_bytes: 0,
                 attempts,
                 order: 0,
+                account_id: transfers
+                    .transfers
+                    .get(&1)
+                    .and_then(|t| t.account_id.clone()),
             },
         )]
         .into(),
@@ -1332,6 +1356,10 @@ fn expected_transfers_done_openable(transfers: &TransfersState, attempts: usize)
                 transferred_bytes: 4,
                 attempts,
                 order: 0,
+                account_id: transfers
+                    .transfers
+                    .get(&1)
+                    .and_then(|t| t.account_id.clone()),
             },
         )]
         .into(),
@@ -13,6 +13,7 @@ use similar_asserts::assert_eq;
 use vault_core::{
     common::state::{BoxAsyncRead, SizeInfo},
     files::file_category::FileCategory,
+    remote::ApiErrorCode,
     store::{self, NextId},
     transfers::{
         errors::{TransferError, UploadableError},
@@ -22,11 +23,12 @@ use vault_core::{
         },
     },
     types::{DecryptedName, EncryptedPath, RepoFileId, TimeMillis},
+    user_error::UserError,
 };
 use vault_core_tests::{
     fixtures::repo_fixture::RepoFixture,
     helpers::transfers::{
-        capture_upload_uri, patch_transfer, transfer_abort_when, transfer_do_when,
+        capture_upload_uri, patch_transfer, transfer_abort_when, transfer_do_when, transfer_wait,
         transfers_recorder, uploaded_server_error, with_transfers, TestUploadable,
     },
 };
@@ -1264,6 +1266,7 @@ fn test_upload_abort_all() {
                                     transferred_bytes: 0,
                                     attempts: 0,
                                     order: 0,
+                                    account_id: Some(fixture.user_fixture.user_id.clone()),
                                 },
                             )]
                             .into(),
@@ -1319,6 +1322,7 @@ fn test_upload_abort_all() {
                                     transferred_bytes: 0,
                                     attempts: 1,
                                     order: 0,
+                                    account_id: Some(fixture.user_fixture.user_id.clone()),
                                 },
                             )]
                             .into(),
@@ -1375,6 +1379,7 @@ fn test_upload_abort_all() {
                                         transferred_bytes: 0,
                                         attempts: 1,
                                         order: 0,
+                                        account_id: Some(fixture.user_fixture.user_id.clone()),
                                     },
                                 ),
                                 (
@@ -1405,6 +1410,7 @@ fn test_upload_abort_all() {
                                         transferred_bytes: 0,
                                         attempts: 0,
                                         order: 1,
+                                        account_id: Some(fixture.user_fixture.user_id.clone()),
                                     },
                                 )
                             ]
@@ -1462,6 +1468,7 @@ fn test_upload_abort_all() {
                                         transferred_bytes: 0,
                                         attempts: 1,
                                         order: 0,
+                                        account_id: Some(fixture.user_fixture.user_id.clone()),
                                     },
                                 ),
                                 (
@@ -1498,6 +1505,7 @@ fn test_upload_abort_all() {
                                         transferred_bytes: 0,
                                         attempts: 1,
                                         order: 1,
+                                        account_id: Some(fixture.user_fixture.user_id.clone()),
                                     },
                                 )
                             ]
@@ -2075,6 +2083,7 @@ fn expected_transfers_waiting(fixture: &RepoFixture, transfers: &TransfersState)
                 transferred_bytes: 0,
                 attempts: 0,
                 order: 0,
+                account_id: Some(fixture.user_fixture.user_id.clone()),
             },
         )]
         .into(),
@@ -2130,6 +2139,7 @@ fn expected_transfers_processing(
                 transferred_bytes: 0,
                 attempts,
                 order: 0,
+                account_id: Some(fixture.user_fixture.user_id.clone()),
             },
         )]
         .into(),
@@ -2185,6 +2195,7 @@ fn expected_transfers_transferring(
                 transferred_bytes: 0,
                 attempts,
                 order: 0,
+                account_id: Some(fixture.user_fixture.user_id.clone()),
             },
         )]
         .into(),
@@ -2240,6 +2251,7 @@ fn expected_transfers_transferring_progress(
                 transferred_bytes: 4,
                 attempts,
                 order: 0,
+                account_id: Some(fixture.user_fixture.user_id.clone()),
             },
         )]
         .into(),
@@ -2289,6 +2301,7 @@ fn expected_transfers_waiting_failed(
                 transferred_bytes: 0,
                 attempts,
                 order: 0,
+                account_id: Some(fixture.user_fixture.user_id.clone()),
             },
         )]
         .into(),
@@ -2343,6 +2356,7 @@ fn expected_transfers_failed(
                 transferred_bytes: 0,
                 attempts,
                 order: 0,
+                account_id: Some(fixture.user_fixture.user_id.clone()),
             },
         )]
         .into(),
@@ -2368,3 +2382,66 @@ fn expected_transfers_done() -> TransfersState {
         ..Default::default()
     }
 }
+
+#[test]
+fn test_upload_quota_exceeded() {
+    with_transfers(|fixture| {
+        async move {
+            fixture
+                .fake_remote
+                .app_state
+                .files_service
+                .set_space_total(&fixture.mount_id.0, Some(10))
+                .unwrap();
+
+            let (transfer_id, create_future) = fixture.vault.transfers_upload(
+                fixture.repo_id.clone(),
+                EncryptedPath("/".into()),
+                TransferUploadRelativeName("file.txt".into()),
+                TestUploadable::string("test"),
+            );
+            let future = create_future.await.unwrap();
+
+            transfer_wait(fixture.vault.store.clone(), transfer_id, |t| {
+                matches!(t.state, TransferState::Failed { .. })
+            })
+            .await;
+
+            fixture.vault.with_state(|state| {
+                let transfer = state.transfers.transfers.get(&transfer_id).unwrap();
+
+                // quota errors are not autoretried
+                assert_eq!(transfer.attempts, 1);
+                assert!(transfer.is_retriable);
+
+                match &transfer.state {
+                    TransferState::Failed { error } => {
+                        assert!(matches!(
+                            error,
+                            TransferError::RemoteError(err)
+                                if err.is_api_error_code(ApiErrorCode::QuotaExceeded)
+                        ));
+                        assert_eq!(
+                            error.user_error(),
+                            "Not enough space. Free up some space and try again."
+                        );
+                    }
+                    _ => panic!("unexpected state: {:#?}", transfer.state),
+                }
+            });
+
+            fixture
+                .fake_remote
+                .app_state
+                .files_service
+                .set_space_total(&fixture.mount_id.0, None)
+                .unwrap();
+
+            fixture.vault.transfers_retry(transfer_id);
+
+            let res = future.await.unwrap();
+            assert_eq!(res.name.0, "file.txt");
+        }
+        .boxed()
+    });
+}
@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    auth::{errors::AuthError, AuthProvider},
+    oauth2::OAuth2Service,
+};
+
+use super::selectors;
+
+/// Authorizes requests with the stored token of an account that is not
+/// necessarily the active one.
+pub struct AccountAuthProvider {
+    oauth2_service: Arc<OAuth2Service>,
+    user_id: String,
+}
+
+impl AccountAuthProvider {
+    pub fn new(oauth2_service: Arc<OAuth2Service>, user_id: String) -> Self {
+        Self {
+            oauth2_service,
+            user_id,
+        }
+    }
+}
+
+#[async_trait]
+impl AuthProvider for AccountAuthProvider {
+    async fn get_authorization(&self, force_refresh_token: bool) -> Result<String, AuthError> {
+        self.oauth2_service
+            .get_stored_authorization(
+                &selectors::get_token_storage_key(&self.user_id),
+                force_refresh_token,
+            )
+            .await
+    }
+}
@@ -0,0 +1,53 @@
+use thiserror::Error;
+
+use crate::{
+    oauth2::errors::OAuth2Error,
+    secure_storage::errors::SecureStorageError,
+    user_error::{ErrorCode, UserError},
+};
+
+#[derive(Error, Debug, Clone, PartialEq)]
+#[error("account not found")]
+pub struct AccountNotFoundError;
+
+impl UserError for AccountNotFoundError {
+    fn user_error(&self) -> String {
+        self.to_string()
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        ErrorCode::AccountNotFound
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum AccountsError {
+    #[error("{0}")]
+    AccountNotFound(#[from] AccountNotFoundError),
+    #[error("active account cannot be removed")]
+    ActiveAccount,
+    #[error("{0}")]
+    OAuth2Error(#[from] OAuth2Error),
+    #[error("{0}")]
+    SecureStorageError(#[from] SecureStorageError),
+}
+
+impl UserError for AccountsError {
+    fn user_error(&self) -> String {
+        match self {
+            Self::AccountNotFound(err) => err.user_error(),
+            Self::ActiveAccount => self.to_string(),
+            Self::OAuth2Error(err) => err.user_error(),
+            Self::SecureStorageError(err) => err.to_string(),
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::AccountNotFound(err) => err.error_code(),
+            Self::ActiveAccount => ErrorCode::AccountActive,
+            Self::OAuth2Error(err) => err.error_code(),
+            Self::SecureStorageError(_) => ErrorCode::Storage,
+        }
+    }
+}
@@ -0,0 +1,8 @@
+pub mod account_auth_provider;
+pub mod errors;
+pub mod mutations;
+pub mod selectors;
+pub mod service;
+pub mod state;
+
+pub use self::service::AccountsService;
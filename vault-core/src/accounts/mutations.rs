@@ -0,0 +1,74 @@
+use crate::store;
+
+use super::state::{Account, StoredAccounts};
+
+pub fn loaded(state: &mut store::State, notify: &store::Notify, stored_accounts: StoredAccounts) {
+    notify(store::Event::Accounts);
+
+    state.accounts.accounts = stored_accounts.accounts;
+    state.accounts.active_user_id = stored_accounts.active_user_id;
+}
+
+pub fn account_loaded(state: &mut store::State, notify: &store::Notify, account: Account) {
+    let user_id = account.user_id.clone();
+
+    // repos and transfers can be loaded before the user is loaded
+    tag_untagged(state, notify, &user_id);
+
+    match state
+        .accounts
+        .accounts
+        .iter_mut()
+        .find(|existing| existing.user_id == account.user_id)
+    {
+        Some(existing) => {
+            if existing == &account && state.accounts.active_user_id.as_ref() == Some(&user_id) {
+                return;
+            }
+
+            *existing = account;
+        }
+        None => state.accounts.accounts.push(account),
+    }
+
+    state.accounts.active_user_id = Some(user_id);
+
+    notify(store::Event::Accounts);
+}
+
+fn tag_untagged(state: &mut store::State, notify: &store::Notify, user_id: &str) {
+    for repo in state.repos.repos_by_id.values_mut() {
+        if repo.account_id.is_none() {
+            repo.account_id = Some(user_id.to_owned());
+
+            notify(store::Event::Repos);
+        }
+    }
+
+    for transfer in state.transfers.transfers.values_mut() {
+        if transfer.account_id.is_none() {
+            transfer.account_id = Some(user_id.to_owned());
+
+            notify(store::Event::Transfers);
+        }
+    }
+}
+
+pub fn set_active(state: &mut store::State, notify: &store::Notify, user_id: Option<String>) {
+    notify(store::Event::Accounts);
+
+    state.accounts.active_user_id = user_id;
+}
+
+pub fn account_removed(state: &mut store::State, notify: &store::Notify, user_id: &str) {
+    notify(store::Event::Accounts);
+
+    state
+        .accounts
+        .accounts
+        .retain(|account| account.user_id != user_id);
+
+    if state.accounts.active_user_id.as_deref() == Some(user_id) {
+        state.accounts.active_user_id = None;
+    }
+}
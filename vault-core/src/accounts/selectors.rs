@@ -0,0 +1,72 @@
+use std::collections::BTreeSet;
+
+use crate::{store, types::MountId};
+
+use super::state::{Account, AccountInfo, StoredAccounts};
+
+pub const ACCOUNTS_STORAGE_KEY: &str = "vaultAccounts";
+
+pub fn get_token_storage_key(user_id: &str) -> String {
+    format!("vaultOAuth2Token_{}", user_id)
+}
+
+pub fn select_active_user_id(state: &store::State) -> Option<&str> {
+    state.accounts.active_user_id.as_deref()
+}
+
+pub fn select_account<'a>(state: &'a store::State, user_id: &str) -> Option<&'a Account> {
+    state
+        .accounts
+        .accounts
+        .iter()
+        .find(|account| account.user_id == user_id)
+}
+
+pub fn select_accounts<'a>(state: &'a store::State) -> Vec<AccountInfo<'a>> {
+    state
+        .accounts
+        .accounts
+        .iter()
+        .map(|account| AccountInfo {
+            account,
+            is_active: state.accounts.active_user_id.as_ref() == Some(&account.user_id),
+        })
+        .collect()
+}
+
+pub fn select_stored_accounts(state: &store::State) -> StoredAccounts {
+    StoredAccounts {
+        accounts: state.accounts.accounts.clone(),
+        active_user_id: state.accounts.active_user_id.clone(),
+    }
+}
+
+/// Returns the accounts that have a repo on the mount, sorted by user id.
+pub fn select_mount_account_ids<'a>(
+    state: &'a store::State,
+    mount_id: &MountId,
+) -> BTreeSet<&'a str> {
+    state
+        .repos
+        .repos_by_id
+        .values()
+        .filter(|repo| &repo.mount_id == mount_id)
+        .filter_map(|repo| repo.account_id.as_deref())
+        .collect()
+}
+
+/// Returns an inactive account that has a repo on the mount. Mounts shared
+/// with the active account always use the active account.
+pub fn select_inactive_mount_account<'a>(
+    state: &'a store::State,
+    mount_id: &MountId,
+) -> Option<&'a str> {
+    let user_ids = select_mount_account_ids(state, mount_id);
+
+    match select_active_user_id(state) {
+        Some(active_user_id) if user_ids.contains(active_user_id) => None,
+        active_user_id => user_ids
+            .into_iter()
+            .find(|user_id| Some(*user_id) != active_user_id),
+    }
+}
@@ -0,0 +1,223 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    http::HttpClient,
+    oauth2::{state::OAuth2Token, OAuth2Service},
+    remote::Remote,
+    secure_storage::{errors::SecureStorageError, SecureStorageService},
+    store,
+    types::MountId,
+};
+
+use super::{
+    account_auth_provider::AccountAuthProvider,
+    errors::{AccountNotFoundError, AccountsError},
+    mutations, selectors,
+    state::{Account, StoredAccounts},
+};
+
+pub struct AccountsService {
+    base_url: String,
+    http_client: Arc<Box<dyn HttpClient + Send + Sync>>,
+    oauth2_service: Arc<OAuth2Service>,
+    secure_storage_service: Arc<SecureStorageService>,
+    store: Arc<store::Store>,
+
+    remotes: Mutex<HashMap<String, Arc<Remote>>>,
+    user_subscription_id: u32,
+    auth_subscription_id: u32,
+}
+
+impl AccountsService {
+    pub fn new(
+        base_url: String,
+        http_client: Arc<Box<dyn HttpClient + Send + Sync>>,
+        oauth2_service: Arc<OAuth2Service>,
+        secure_storage_service: Arc<SecureStorageService>,
+        store: Arc<store::Store>,
+    ) -> Arc<Self> {
+        match secure_storage_service.get::<StoredAccounts>(selectors::ACCOUNTS_STORAGE_KEY) {
+            Ok(Some(stored_accounts)) => store.mutate(|state, notify, _, _| {
+                mutations::loaded(state, notify, stored_accounts);
+            }),
+            Ok(None) => {}
+            Err(err) => log::warn!("Accounts load failed: {}", err),
+        }
+
+        let user_subscription_id = store.get_next_id();
+        let auth_subscription_id = store.get_next_id();
+
+        let accounts_service = Arc::new(Self {
+            base_url,
+            http_client,
+            oauth2_service,
+            secure_storage_service,
+            store: store.clone(),
+
+            remotes: Mutex::new(HashMap::new()),
+            user_subscription_id,
+            auth_subscription_id,
+        });
+
+        let user_subscription_service = Arc::downgrade(&accounts_service);
+        let user_subscription_store = store.clone();
+
+        store.on(
+            user_subscription_id,
+            &[store::Event::User],
+            Box::new(move |_, add_side_effect| {
+                let account = match user_subscription_store.with_state(|state| {
+                    state.user.user.as_ref().map(|user| Account {
+                        user_id: user.id.clone(),
+                        email: user.email.clone(),
+                        full_name: user.full_name.clone(),
+                    })
+                }) {
+                    Some(account) => account,
+                    None => return,
+                };
+
+                if let Some(service) = user_subscription_service.upgrade() {
+                    add_side_effect(Box::new(move || {
+                        service.account_loaded(account);
+                    }));
+                }
+            }),
+        );
+
+        let auth_subscription_service = Arc::downgrade(&accounts_service);
+
+        store.on(
+            auth_subscription_id,
+            &[store::Event::Auth],
+            Box::new(move |_, add_side_effect| {
+                if let Some(service) = auth_subscription_service.upgrade() {
+                    add_side_effect(Box::new(move || {
+                        service.save_active_token();
+                    }));
+                }
+            }),
+        );
+
+        accounts_service
+    }
+
+    fn save(&self) -> Result<(), SecureStorageError> {
+        let stored_accounts = self.store.with_state(selectors::select_stored_accounts);
+
+        self.secure_storage_service
+            .set(selectors::ACCOUNTS_STORAGE_KEY, &stored_accounts)
+    }
+
+    fn account_loaded(&self, account: Account) {
+        self.store.mutate(|state, notify, _, _| {
+            mutations::account_loaded(state, notify, account);
+        });
+
+        if let Err(err) = self.save() {
+            log::warn!("Accounts save failed: {}", err);
+        }
+
+        self.save_active_token();
+    }
+
+    /// Keeps the stored token of the active account up to date so that it is
+    /// available after switching to another account.
+    fn save_active_token(&self) {
+        let (user_id, token) = match self.store.with_state(|state| {
+            selectors::select_active_user_id(state)
+                .map(str::to_owned)
+                .zip(state.oauth2.token.clone())
+        }) {
+            Some(active) => active,
+            None => return,
+        };
+
+        if let Err(err) = self
+            .secure_storage_service
+            .set(&selectors::get_token_storage_key(&user_id), &token)
+        {
+            log::warn!("Account token save failed: {}", err);
+        }
+    }
+
+    pub fn get_account_token(&self, user_id: &str) -> Result<OAuth2Token, AccountsError> {
+        self.secure_storage_service
+            .get::<OAuth2Token>(&selectors::get_token_storage_key(user_id))?
+            .ok_or(AccountsError::AccountNotFound(AccountNotFoundError))
+    }
+
+    pub fn set_active(&self, user_id: Option<String>) -> Result<(), AccountsError> {
+        self.store.mutate(|state, notify, _, _| {
+            mutations::set_active(state, notify, user_id);
+        });
+
+        Ok(self.save()?)
+    }
+
+    pub fn remove_account(&self, user_id: &str) -> Result<(), AccountsError> {
+        let (exists, is_active) = self.store.with_state(|state| {
+            (
+                selectors::select_account(state, user_id).is_some(),
+                selectors::select_active_user_id(state) == Some(user_id),
+            )
+        });
+
+        if !exists {
+            return Err(AccountsError::AccountNotFound(AccountNotFoundError));
+        }
+
+        if is_active {
+            return Err(AccountsError::ActiveAccount);
+        }
+
+        self.store.mutate(|state, notify, _, _| {
+            mutations::account_removed(state, notify, user_id);
+        });
+
+        self.remotes.lock().unwrap().remove(user_id);
+
+        self.secure_storage_service
+            .remove(&selectors::get_token_storage_key(user_id))?;
+
+        Ok(self.save()?)
+    }
+
+    fn get_remote(&self, user_id: &str) -> Arc<Remote> {
+        self.remotes
+            .lock()
+            .unwrap()
+            .entry(user_id.to_owned())
+            .or_insert_with(|| {
+                Arc::new(Remote::new(
+                    self.base_url.clone(),
+                    self.http_client.clone(),
+                    Arc::new(Box::new(AccountAuthProvider::new(
+                        self.oauth2_service.clone(),
+                        user_id.to_owned(),
+                    ))),
+                ))
+            })
+            .clone()
+    }
+
+    /// Requests for mounts of other accounts (e.g. transfers that were started
+    /// before the account was switched) use the token of that account.
+    pub fn get_mount_remote(&self, mount_id: &MountId) -> Option<Arc<Remote>> {
+        let user_id = self.store.with_state(|state| {
+            selectors::select_inactive_mount_account(state, mount_id).map(str::to_owned)
+        })?;
+
+        Some(self.get_remote(&user_id))
+    }
+}
+
+impl Drop for AccountsService {
+    fn drop(&mut self) {
+        self.store.remove_listener(self.user_subscription_id);
+        self.store.remove_listener(self.auth_subscription_id);
+    }
+}
@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Account {
+    #[serde(rename = "u")]
+    pub user_id: String,
+    #[serde(rename = "e")]
+    pub email: String,
+    #[serde(rename = "n")]
+    pub full_name: String,
+}
+
+/// Accounts list stored in the secure storage. Tokens are stored separately
+/// for each account (see `selectors::get_token_storage_key`).
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct StoredAccounts {
+    #[serde(rename = "a")]
+    pub accounts: Vec<Account>,
+    #[serde(rename = "u", default, skip_serializing_if = "Option::is_none")]
+    pub active_user_id: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AccountsState {
+    pub accounts: Vec<Account>,
+    /// `None` while no account is logged in or a new account is being added.
+    pub active_user_id: Option<String>,
+}
+
+impl AccountsState {
+    pub fn reset(&mut self) {
+        *self = Default::default();
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountInfo<'a> {
+    pub account: &'a Account,
+    pub is_active: bool,
+}
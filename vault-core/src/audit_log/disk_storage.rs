@@ -0,0 +1,8 @@
+/// AuditLogDiskStorage stores the log entries in an app data file. The
+/// entries are only chained, not encrypted. The HMAC key and the head are kept
+/// in the secure storage, so any modification of the file is detected by
+/// `verify`.
+pub trait AuditLogDiskStorage {
+    fn get(&self) -> Result<Option<Vec<u8>>, String>;
+    fn set(&self, value: &[u8]) -> Result<(), String>;
+}
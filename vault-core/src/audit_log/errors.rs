@@ -0,0 +1,45 @@
+use thiserror::Error;
+
+use crate::{
+    locale,
+    secure_storage::errors::SecureStorageError,
+    user_error::{ErrorCode, UserError},
+};
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum VerifyAuditLogError {
+    #[error("audit log chain broken at entry {seq}")]
+    ChainBroken { seq: u64 },
+    #[error("audit log head mismatch")]
+    HeadMismatch,
+    #[error("failed to load audit log: {0}")]
+    LoadError(SecureStorageError),
+    #[error("failed to load audit log: {0}")]
+    DiskStorageError(String),
+}
+
+impl UserError for VerifyAuditLogError {
+    fn user_error(&self) -> String {
+        match self {
+            Self::ChainBroken { seq } => locale::message_args(
+                "error-audit-log-chain-broken",
+                &[("seq", seq.to_string().into())],
+            ),
+            Self::HeadMismatch => locale::message("error-audit-log-head-mismatch"),
+            Self::LoadError(err) => {
+                locale::message_args("error-storage", &[("error", err.to_string().into())])
+            }
+            Self::DiskStorageError(err) => {
+                locale::message_args("error-storage", &[("error", err.clone().into())])
+            }
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::ChainBroken { seq } => ErrorCode::AuditLogChainBroken { seq: *seq },
+            Self::HeadMismatch => ErrorCode::AuditLogHeadMismatch,
+            Self::LoadError(_) | Self::DiskStorageError(_) => ErrorCode::Storage,
+        }
+    }
+}
@@ -0,0 +1,211 @@
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+
+use super::{
+    errors::VerifyAuditLogError,
+    state::{AuditLogEntry, AuditLogEvent, AuditLogHead},
+};
+
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+type HmacSha256 = Hmac<Sha256>;
+
+#[derive(Serialize)]
+struct HashedFields<'a> {
+    seq: u64,
+    time: i64,
+    user_id: Option<&'a str>,
+    event: &'a AuditLogEvent,
+}
+
+pub fn entry_hash(
+    key: &[u8],
+    prev_hash: &str,
+    seq: u64,
+    time: i64,
+    user_id: Option<&str>,
+    event: &AuditLogEvent,
+) -> String {
+    let fields = serde_json::to_vec(&HashedFields {
+        seq,
+        time,
+        user_id,
+        event,
+    })
+    .unwrap();
+
+    // HMAC accepts keys of any length
+    let mut mac = HmacSha256::new_from_slice(key).unwrap();
+    mac.update(prev_hash.as_bytes());
+    mac.update(&fields);
+
+    hex::encode(mac.finalize().into_bytes())
+}
+
+pub fn next_entry(
+    key: &[u8],
+    prev: Option<&AuditLogEntry>,
+    time: i64,
+    user_id: Option<String>,
+    event: AuditLogEvent,
+) -> AuditLogEntry {
+    let (seq, prev_hash) = match prev {
+        Some(prev) => (prev.seq + 1, prev.hash.clone()),
+        None => (1, GENESIS_HASH.to_owned()),
+    };
+
+    let hash = entry_hash(key, &prev_hash, seq, time, user_id.as_deref(), &event);
+
+    AuditLogEntry {
+        seq,
+        time,
+        user_id,
+        event,
+        prev_hash,
+        hash,
+    }
+}
+
+/// Appends the entry and drops the oldest entries so that at most
+/// `max_entries` are kept.
+pub fn append(entries: &mut Vec<AuditLogEntry>, entry: AuditLogEntry, max_entries: usize) {
+    entries.push(entry);
+
+    if entries.len() > max_entries {
+        entries.drain(..entries.len() - max_entries);
+    }
+}
+
+pub fn head(entries: &[AuditLogEntry]) -> AuditLogHead {
+    AuditLogHead {
+        count: entries.len() as u64,
+        hash: entries
+            .last()
+            .map(|entry| entry.hash.clone())
+            .unwrap_or_else(|| GENESIS_HASH.to_owned()),
+    }
+}
+
+/// A log that does not start at the first entry must be full, otherwise
+/// entries were removed from the start.
+pub fn verify(
+    key: &[u8],
+    entries: &[AuditLogEntry],
+    head: &AuditLogHead,
+    max_entries: usize,
+) -> Result<(), VerifyAuditLogError> {
+    let (first_seq, mut prev_hash) = match entries.first() {
+        Some(first) if first.seq == 1 => (1, GENESIS_HASH),
+        Some(first) if entries.len() == max_entries => (first.seq, first.prev_hash.as_str()),
+        Some(first) => return Err(VerifyAuditLogError::ChainBroken { seq: first.seq }),
+        None => (1, GENESIS_HASH),
+    };
+
+    for (idx, entry) in entries.iter().enumerate() {
+        let expected_hash = entry_hash(
+            key,
+            &entry.prev_hash,
+            entry.seq,
+            entry.time,
+            entry.user_id.as_deref(),
+            &entry.event,
+        );
+
+        if entry.seq != first_seq + idx as u64
+            || entry.prev_hash != prev_hash
+            || entry.hash != expected_hash
+        {
+            return Err(VerifyAuditLogError::ChainBroken { seq: entry.seq });
+        }
+
+        prev_hash = &entry.hash;
+    }
+
+    if &self::head(entries) != head {
+        return Err(VerifyAuditLogError::HeadMismatch);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        audit_log::{errors::VerifyAuditLogError, state::AuditLogEvent},
+        types::RepoId,
+    };
+
+    use super::{append, head, next_entry, verify, GENESIS_HASH};
+
+    const KEY: &[u8] = b"key";
+
+    #[test]
+    fn test_verify() {
+        let first = next_entry(
+            KEY,
+            None,
+            1,
+            Some("u1".into()),
+            AuditLogEvent::RepoCreate {
+                repo_id: RepoId("r1".into()),
+            },
+        );
+        let second = next_entry(
+            KEY,
+            Some(&first),
+            2,
+            Some("u1".into()),
+            AuditLogEvent::Logout,
+        );
+        let entries = vec![first.clone(), second.clone()];
+
+        assert_eq!(first.prev_hash, GENESIS_HASH);
+        assert_eq!(second.prev_hash, first.hash);
+        assert_eq!(verify(KEY, &entries, &head(&entries), 10), Ok(()));
+
+        assert_eq!(
+            verify(b"other key", &entries, &head(&entries), 10),
+            Err(VerifyAuditLogError::ChainBroken { seq: 1 })
+        );
+
+        let mut edited = first.clone();
+        edited.user_id = Some("u2".into());
+        let edited = vec![edited, second.clone()];
+        assert!(verify(KEY, &edited, &head(&edited), 10).is_err());
+
+        let truncated_start = vec![second.clone()];
+        assert!(verify(KEY, &truncated_start, &head(&truncated_start), 10).is_err());
+
+        let reordered = vec![second, first.clone()];
+        assert!(verify(KEY, &reordered, &head(&reordered), 10).is_err());
+
+        assert_eq!(
+            verify(KEY, &[first], &head(&entries), 10),
+            Err(VerifyAuditLogError::HeadMismatch)
+        );
+    }
+
+    #[test]
+    fn test_append_max_entries() {
+        let mut entries = Vec::new();
+
+        for time in 0..5 {
+            let entry = next_entry(KEY, entries.last(), time, None, AuditLogEvent::Logout);
+
+            append(&mut entries, entry, 3);
+        }
+
+        assert_eq!(
+            entries.iter().map(|entry| entry.seq).collect::<Vec<_>>(),
+            vec![3, 4, 5]
+        );
+        assert_eq!(verify(KEY, &entries, &head(&entries), 3), Ok(()));
+
+        let dropped = entries[1..].to_vec();
+        assert_eq!(
+            verify(KEY, &dropped, &head(&dropped), 3),
+            Err(VerifyAuditLogError::ChainBroken { seq: 4 })
+        );
+    }
+}
@@ -0,0 +1,7 @@
+pub mod disk_storage;
+pub mod errors;
+pub mod hash_chain;
+pub mod service;
+pub mod state;
+
+pub use self::{disk_storage::AuditLogDiskStorage, service::AuditLogService};
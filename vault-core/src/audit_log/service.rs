@@ -0,0 +1,248 @@
+use std::sync::{Arc, Mutex, RwLock};
+
+use rand_core::{OsRng, RngCore};
+
+use crate::{
+    runtime,
+    secure_storage::{errors::SecureStorageError, SecureStorageService},
+    store,
+};
+
+use super::{
+    disk_storage::AuditLogDiskStorage,
+    errors::VerifyAuditLogError,
+    hash_chain,
+    state::{AuditLogEntry, AuditLogEvent, AuditLogFilter, AuditLogHead},
+};
+
+pub const AUDIT_LOG_STORAGE_KEY: &str = "vaultAuditLog";
+pub const AUDIT_LOG_HEAD_STORAGE_KEY: &str = "vaultAuditLogHead";
+pub const AUDIT_LOG_KEY_STORAGE_KEY: &str = "vaultAuditLogKey";
+
+/// The whole log is written on every event so it is capped, the oldest
+/// entries are dropped.
+pub const MAX_ENTRIES: usize = 1000;
+
+const KEY_LEN: usize = 32;
+
+struct AuditLogData {
+    entries: Vec<AuditLogEntry>,
+    head: AuditLogHead,
+    key: Vec<u8>,
+    // a log that cannot be loaded is kept as is and never overwritten,
+    // `verify` returns the load error
+    load_error: Option<VerifyAuditLogError>,
+}
+
+/// Local, append-only log of security relevant events. Entries are chained
+/// with an HMAC keyed from the secure storage so that any modification of the
+/// stored log is detected by `verify`. The log is device scoped and survives
+/// logout.
+///
+/// Only the key and the head are kept in the secure storage, the entries are
+/// written to the disk storage. Without a disk storage (web) the entries are
+/// kept in the secure storage too.
+pub struct AuditLogService {
+    secure_storage_service: Arc<SecureStorageService>,
+    store: Arc<store::Store>,
+    runtime: Arc<runtime::BoxRuntime>,
+
+    disk_storage: RwLock<Option<Box<dyn AuditLogDiskStorage + Send + Sync>>>,
+    data: Mutex<AuditLogData>,
+}
+
+impl AuditLogService {
+    pub fn new(
+        secure_storage_service: Arc<SecureStorageService>,
+        store: Arc<store::Store>,
+        runtime: Arc<runtime::BoxRuntime>,
+    ) -> Self {
+        let data = match load(&secure_storage_service) {
+            Ok(data) => data,
+            Err(err) => {
+                log::error!("Failed to load audit log: {}", err);
+
+                AuditLogData {
+                    entries: Vec::new(),
+                    head: hash_chain::head(&[]),
+                    key: generate_key(),
+                    load_error: Some(VerifyAuditLogError::LoadError(err)),
+                }
+            }
+        };
+
+        Self {
+            secure_storage_service,
+            store,
+            runtime,
+
+            disk_storage: RwLock::new(None),
+            data: Mutex::new(data),
+        }
+    }
+
+    /// Must be set before any event is recorded. The entries are reloaded
+    /// from the disk storage.
+    pub fn set_disk_storage(
+        &self,
+        disk_storage: Option<Box<dyn AuditLogDiskStorage + Send + Sync>>,
+    ) {
+        let mut data = self.data.lock().unwrap();
+
+        if let Some(disk_storage) = disk_storage.as_ref() {
+            if data.load_error.is_none() {
+                match load_entries(disk_storage.as_ref()) {
+                    Ok(entries) => data.entries = entries,
+                    Err(err) => {
+                        log::error!("Failed to load audit log: {}", err);
+
+                        data.entries = Vec::new();
+                        data.load_error = Some(VerifyAuditLogError::DiskStorageError(err));
+                    }
+                }
+            }
+        }
+
+        *self.disk_storage.write().unwrap() = disk_storage;
+    }
+
+    pub fn record(&self, event: AuditLogEvent) {
+        let user_id = self
+            .store
+            .with_state(|state| state.user.user.as_ref().map(|user| user.id.clone()));
+
+        self.record_for_user(user_id, event);
+    }
+
+    /// Must be used instead of `record` when called from inside a store
+    /// mutation.
+    pub fn record_for_user(&self, user_id: Option<String>, event: AuditLogEvent) {
+        let time = self.runtime.now().0;
+
+        let mut data = self.data.lock().unwrap();
+
+        let entry = hash_chain::next_entry(&data.key, data.entries.last(), time, user_id, event);
+
+        hash_chain::append(&mut data.entries, entry, MAX_ENTRIES);
+
+        data.head = hash_chain::head(&data.entries);
+
+        self.save(&data);
+    }
+
+    pub fn entries(&self, filter: &AuditLogFilter) -> Vec<AuditLogEntry> {
+        self.data
+            .lock()
+            .unwrap()
+            .entries
+            .iter()
+            .filter(|entry| filter.matches(entry))
+            .cloned()
+            .collect()
+    }
+
+    /// Exports the full log as JSON.
+    pub fn export(&self) -> String {
+        serde_json::to_string_pretty(&self.data.lock().unwrap().entries).unwrap()
+    }
+
+    pub fn verify(&self) -> Result<(), VerifyAuditLogError> {
+        let data = self.data.lock().unwrap();
+
+        if let Some(err) = &data.load_error {
+            return Err(err.clone());
+        }
+
+        hash_chain::verify(&data.key, &data.entries, &data.head, MAX_ENTRIES)
+    }
+
+    /// Writes the key and the head back after the secure storage was cleared
+    /// on logout.
+    pub fn restore(&self) {
+        let data = self.data.lock().unwrap();
+
+        self.save_key(&data);
+        self.save(&data);
+    }
+
+    fn save(&self, data: &AuditLogData) {
+        if data.load_error.is_some() {
+            return;
+        }
+
+        // entries are written before the head, an interrupted save is
+        // reported as a head mismatch
+        if let Err(err) = self.save_entries(data).and_then(|()| {
+            self.secure_storage_service
+                .set(AUDIT_LOG_HEAD_STORAGE_KEY, &data.head)
+                .map_err(|err| err.to_string())
+        }) {
+            log::warn!("Failed to save audit log: {}", err);
+        }
+    }
+
+    fn save_entries(&self, data: &AuditLogData) -> Result<(), String> {
+        match self.disk_storage.read().unwrap().as_ref() {
+            Some(disk_storage) => disk_storage.set(&serde_json::to_vec(&data.entries).unwrap()),
+            None => self
+                .secure_storage_service
+                .set(AUDIT_LOG_STORAGE_KEY, &data.entries)
+                .map_err(|err| err.to_string()),
+        }
+    }
+
+    fn save_key(&self, data: &AuditLogData) {
+        if data.load_error.is_some() {
+            return;
+        }
+
+        if let Err(err) = self
+            .secure_storage_service
+            .set(AUDIT_LOG_KEY_STORAGE_KEY, &hex::encode(&data.key))
+        {
+            log::warn!("Failed to save audit log key: {}", err);
+        }
+    }
+}
+
+fn load(secure_storage_service: &SecureStorageService) -> Result<AuditLogData, SecureStorageError> {
+    let entries: Vec<AuditLogEntry> = secure_storage_service
+        .get(AUDIT_LOG_STORAGE_KEY)?
+        .unwrap_or_default();
+    let head = secure_storage_service
+        .get(AUDIT_LOG_HEAD_STORAGE_KEY)?
+        .unwrap_or_else(|| hash_chain::head(&[]));
+
+    let key = match secure_storage_service.get::<String>(AUDIT_LOG_KEY_STORAGE_KEY)? {
+        Some(key) => hex::decode(key).map_err(|err| SecureStorageError::Error(err.to_string()))?,
+        None => {
+            let key = generate_key();
+
+            secure_storage_service.set(AUDIT_LOG_KEY_STORAGE_KEY, &hex::encode(&key))?;
+
+            key
+        }
+    };
+
+    Ok(AuditLogData {
+        entries,
+        head,
+        key,
+        load_error: None,
+    })
+}
+
+fn load_entries(disk_storage: &dyn AuditLogDiskStorage) -> Result<Vec<AuditLogEntry>, String> {
+    match disk_storage.get()? {
+        Some(bytes) => serde_json::from_slice(&bytes).map_err(|err| err.to_string()),
+        None => Ok(Vec::new()),
+    }
+}
+
+fn generate_key() -> Vec<u8> {
+    let mut key = vec![0; KEY_LEN];
+
+    OsRng.fill_bytes(&mut key);
+
+    key
+}
@@ -0,0 +1,111 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::RepoId;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RepoLockReason {
+    Manual,
+    Inactivity,
+    AppHidden,
+    Panic,
+    AccountSwitch,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum AuditLogEvent {
+    RepoUnlock {
+        #[serde(rename = "repoId")]
+        repo_id: RepoId,
+        #[serde(rename = "readOnly")]
+        read_only: bool,
+    },
+    RepoUnlockFailure {
+        #[serde(rename = "repoId")]
+        repo_id: RepoId,
+        #[serde(rename = "errorCode")]
+        error_code: String,
+    },
+    RepoLock {
+        #[serde(rename = "repoId")]
+        repo_id: RepoId,
+        reason: RepoLockReason,
+    },
+    RepoCreate {
+        #[serde(rename = "repoId")]
+        repo_id: RepoId,
+    },
+    RepoRemove {
+        #[serde(rename = "repoId")]
+        repo_id: RepoId,
+    },
+    RepoConfigBackup {
+        #[serde(rename = "repoId")]
+        repo_id: RepoId,
+    },
+    Logout,
+}
+
+impl AuditLogEvent {
+    pub fn repo_id(&self) -> Option<&RepoId> {
+        match self {
+            Self::RepoUnlock { repo_id, .. }
+            | Self::RepoUnlockFailure { repo_id, .. }
+            | Self::RepoLock { repo_id, .. }
+            | Self::RepoCreate { repo_id }
+            | Self::RepoRemove { repo_id }
+            | Self::RepoConfigBackup { repo_id } => Some(repo_id),
+            Self::Logout => None,
+        }
+    }
+}
+
+/// An append-only log entry. `hash` is an HMAC of `prev_hash` and all the
+/// other fields so that removing, reordering or editing entries breaks the
+/// chain and entries cannot be recomputed without the key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLogEntry {
+    pub seq: u64,
+    /// Unix timestamp in milliseconds
+    pub time: i64,
+    pub user_id: Option<String>,
+    pub event: AuditLogEvent,
+    pub prev_hash: String,
+    pub hash: String,
+}
+
+/// Stored separately from the entries so that dropping entries from the end
+/// of the log is detected.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLogHead {
+    pub count: u64,
+    pub hash: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuditLogFilter {
+    pub repo_id: Option<RepoId>,
+    /// Unix timestamp in milliseconds, inclusive
+    pub since: Option<i64>,
+}
+
+impl AuditLogFilter {
+    pub fn matches(&self, entry: &AuditLogEntry) -> bool {
+        if let Some(repo_id) = &self.repo_id {
+            if entry.event.repo_id() != Some(repo_id) {
+                return false;
+            }
+        }
+
+        if let Some(since) = self.since {
+            if entry.time < since {
+                return false;
+            }
+        }
+
+        true
+    }
+}
@@ -1,5 +1,12 @@
 use std::fmt::Debug;
 
+use data_encoding::BASE64URL_NOPAD;
+use vault_crypto::{
+    constants::{DATA_KEY_LEN, KEY_LEN, NAME_KEY_LEN},
+    random_password::random_password,
+};
+use zeroize::Zeroizing;
+
 use crate::{
     types::{DecryptedName, DecryptedPath, EncryptedName, EncryptedPath},
     utils::name_utils,
@@ -22,6 +29,34 @@ impl Cipher {
         Self { cipher }
     }
 
+    /// Generates base64 encoded random keys for local data that is not
+    /// encrypted with a repo password.
+    pub fn generate_random_keys() -> Result<Zeroizing<String>, String> {
+        random_password(KEY_LEN * 8).map_err(|err| err.to_string())
+    }
+
+    /// Creates a cipher from keys returned by `generate_random_keys`.
+    pub fn with_random_keys(keys: &str) -> Result<Self, String> {
+        let keys = Zeroizing::new(
+            BASE64URL_NOPAD
+                .decode(keys.as_bytes())
+                .map_err(|err| err.to_string())?,
+        );
+
+        if keys.len() != KEY_LEN {
+            return Err(format!("invalid keys length: {}", keys.len()));
+        }
+
+        let (data_key, rest) = keys.split_at(DATA_KEY_LEN);
+        let (name_key, name_tweak) = rest.split_at(NAME_KEY_LEN);
+
+        Ok(Self::new(vault_crypto::Cipher::with_keys(
+            data_key.try_into().unwrap(),
+            name_key.try_into().unwrap(),
+            name_tweak.try_into().unwrap(),
+        )))
+    }
+
     pub fn encrypt_filename(&self, plaintext: &DecryptedName) -> EncryptedName {
         EncryptedName(self.cipher.encrypt_filename(&plaintext.0))
     }
@@ -100,6 +135,16 @@ impl Cipher {
         self.cipher.decrypt_reader_async(reader)
     }
 
+    pub fn decrypt_blocks_reader_async<R>(
+        &self,
+        reader: R,
+        nonce: &vault_crypto::nonce::Nonce,
+        block_index: u64,
+    ) -> vault_crypto::decrypt_reader::AsyncDecryptReader<R> {
+        self.cipher
+            .decrypt_blocks_reader_async(reader, nonce, block_index)
+    }
+
     pub fn decrypt_reader_sync<R>(
         &self,
         reader: R,
@@ -1,6 +1,10 @@
 use thiserror::Error;
 
-use crate::{common::errors::InvalidNameError, user_error::UserError};
+use crate::{
+    common::errors::InvalidNameError,
+    locale,
+    user_error::{ErrorCode, UserError},
+};
 
 #[derive(Error, Debug, Clone, PartialEq)]
 pub enum DecryptSizeError {
@@ -11,7 +15,15 @@ pub enum DecryptSizeError {
 impl UserError for DecryptSizeError {
     fn user_error(&self) -> String {
         match self {
-            Self::DecryptSizeError(err) => format!("Failed to decrypt size: {}", err),
+            Self::DecryptSizeError(err) => {
+                locale::message_args("error-decrypt-size", &[("error", err.to_string().into())])
+            }
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::DecryptSizeError(_) => ErrorCode::CipherDecryptSize,
         }
     }
 }
@@ -29,10 +41,33 @@ impl UserError for DecryptFilenameError {
         use vault_crypto::errors::DecryptFilenameError;
 
         match self {
-            Self::DecryptFilenameError(DecryptFilenameError::DecodeError(_)) => "Failed to decode file name".into(),
-            Self::DecryptFilenameError(DecryptFilenameError::DecryptError) => "Failed to decrypt file name. Vault files can only be uploaded using Vault apps or rclone. If all your files have errors please check that you've used the correct salt.".into(),
-            Self::DecryptFilenameError(DecryptFilenameError::UnicodeError(_)) => "File name is not a valid Unicode text".into(),
+            Self::DecryptFilenameError(DecryptFilenameError::DecodeError(_)) => {
+                locale::message("error-decode-file-name")
+            }
+            Self::DecryptFilenameError(DecryptFilenameError::DecryptError) => {
+                locale::message("error-decrypt-file-name")
+            }
+            Self::DecryptFilenameError(DecryptFilenameError::UnicodeError(_)) => {
+                locale::message("error-file-name-unicode")
+            }
             Self::InvalidNameError(err) => err.user_error(),
         }
     }
+
+    fn error_code(&self) -> ErrorCode {
+        use vault_crypto::errors::DecryptFilenameError;
+
+        match self {
+            Self::DecryptFilenameError(DecryptFilenameError::DecodeError(_)) => {
+                ErrorCode::CipherDecodeName
+            }
+            Self::DecryptFilenameError(DecryptFilenameError::DecryptError) => {
+                ErrorCode::CipherDecryptName
+            }
+            Self::DecryptFilenameError(DecryptFilenameError::UnicodeError(_)) => {
+                ErrorCode::CipherNameUnicode
+            }
+            Self::InvalidNameError(err) => err.error_code(),
+        }
+    }
 }
@@ -1,6 +1,9 @@
 use thiserror::Error;
 
-use crate::user_error::UserError;
+use crate::{
+    locale,
+    user_error::{ErrorCode, UserError},
+};
 
 #[derive(Error, Debug, Clone, PartialEq)]
 #[error("invalid path")]
@@ -8,7 +11,11 @@ pub struct InvalidPathError;
 
 impl UserError for InvalidPathError {
     fn user_error(&self) -> String {
-        "Path is not valid".into()
+        locale::message("error-invalid-path")
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        ErrorCode::InvalidPath
     }
 }
 
@@ -39,7 +46,11 @@ impl InvalidNameError {
 
 impl UserError for InvalidNameError {
     fn user_error(&self) -> String {
-        "Name is not valid".into()
+        locale::message("error-invalid-name")
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        ErrorCode::InvalidName
     }
 }
 
@@ -1 +1,2 @@
+pub mod mutations;
 pub mod state;
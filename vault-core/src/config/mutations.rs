@@ -0,0 +1,12 @@
+use crate::store;
+
+use super::state::LocaleConfig;
+
+pub fn set_locale(state: &mut store::State, notify: &store::Notify, locale: LocaleConfig) {
+    state.config.locale = locale;
+
+    // every view can contain localized strings
+    for event in store::Event::all() {
+        notify(event);
+    }
+}
@@ -1,13 +1,14 @@
-use std::time::Duration;
+use std::{sync::Arc, time::Duration};
 
 use crate::{
     locale::{get_locale, BoxLocale},
-    repos::state::{RepoAutoLock, RepoAutoLockAfter},
+    repos::state::{RepoAutoLock, RepoAutoLockAfter, RepoUnlockLockout},
 };
 
+#[derive(Clone)]
 pub struct LocaleConfig {
     pub name: String,
-    pub locale: BoxLocale,
+    pub locale: Arc<BoxLocale>,
 }
 
 impl std::fmt::Debug for LocaleConfig {
@@ -18,15 +19,6 @@ impl std::fmt::Debug for LocaleConfig {
     }
 }
 
-impl Clone for LocaleConfig {
-    fn clone(&self) -> Self {
-        Self {
-            name: self.name.clone(),
-            locale: get_locale(&self.name).unwrap(),
-        }
-    }
-}
-
 #[derive(Debug, Clone)]
 pub struct TransfersConfig {
     pub upload_concurrency: usize,
@@ -63,9 +55,30 @@ impl Default for EventstreamConfig {
     }
 }
 
+/// The delay after a failed password attempt starts at `base_delay` once
+/// `free_attempts` have failed and doubles with every further failure.
+#[derive(Debug, Clone)]
+pub struct RepoUnlockBackoffConfig {
+    pub free_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RepoUnlockBackoffConfig {
+    fn default() -> Self {
+        Self {
+            free_attempts: 3,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(5 * 60),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ReposConfig {
     pub default_auto_lock: RepoAutoLock,
+    pub unlock_backoff: RepoUnlockBackoffConfig,
+    pub unlock_lockout: Option<RepoUnlockLockout>,
 }
 
 impl Default for ReposConfig {
@@ -75,6 +88,8 @@ impl Default for ReposConfig {
                 after: Some(RepoAutoLockAfter::Inactive1Hour),
                 on_app_hidden: false,
             },
+            unlock_backoff: RepoUnlockBackoffConfig::default(),
+            unlock_lockout: None,
         }
     }
 }
@@ -121,7 +136,7 @@ impl Default for ConfigState {
             base_url: String::from(""),
             locale: LocaleConfig {
                 name: String::from("en"),
-                locale: get_locale("en").unwrap(),
+                locale: Arc::new(get_locale("en").unwrap()),
             },
             transfers: TransfersConfig::default(),
             eventstream: EventstreamConfig::default(),
@@ -5,7 +5,7 @@ use std::{
 
 use futures::channel::oneshot;
 
-use crate::store;
+use crate::{locale, store};
 
 use super::{
     mutations, selectors,
@@ -29,45 +29,45 @@ impl DialogsService {
     }
 
     pub fn build_alert(&self, title: String) -> DialogShowOptions {
-        DialogShowOptions {
+        locale::with_store_locale(&self.store, || DialogShowOptions {
             typ: DialogType::Alert,
             title,
             message: None,
             input_value: String::from(""),
             input_value_selected: None,
             input_placeholder: None,
-            confirm_button_text: String::from("Ok"),
+            confirm_button_text: locale::message("dialog-ok"),
             confirm_button_style: DialogButtonStyle::Primary,
             cancel_button_text: None,
-        }
+        })
     }
 
     pub fn build_confirm(&self) -> DialogShowOptions {
-        DialogShowOptions {
+        locale::with_store_locale(&self.store, || DialogShowOptions {
             typ: DialogType::Confirm,
-            title: String::from("Are you sure?"),
+            title: locale::message("dialog-confirm-title"),
             message: None,
             input_value: String::from(""),
             input_value_selected: None,
             input_placeholder: None,
-            confirm_button_text: String::from("Yes"),
+            confirm_button_text: locale::message("dialog-yes"),
             confirm_button_style: DialogButtonStyle::Primary,
-            cancel_button_text: Some(String::from("No")),
-        }
+            cancel_button_text: Some(locale::message("dialog-no")),
+        })
     }
 
     pub fn build_prompt(&self, title: String) -> DialogShowOptions {
-        DialogShowOptions {
+        locale::with_store_locale(&self.store, || DialogShowOptions {
             typ: DialogType::Prompt,
             title,
             message: None,
             input_value: String::from(""),
             input_value_selected: None,
             input_placeholder: None,
-            confirm_button_text: String::from("Ok"),
+            confirm_button_text: locale::message("dialog-ok"),
             confirm_button_style: DialogButtonStyle::Primary,
-            cancel_button_text: Some(String::from("Cancel")),
-        }
+            cancel_button_text: Some(locale::message("dialog-cancel")),
+        })
     }
 
     pub async fn show(&self, options: DialogShowOptions) -> Option<String> {
@@ -39,6 +39,7 @@ pub fn disconnect(state: &mut store::State, notify: &store::Notify) -> bool {
     notify(store::Event::Eventstream);
 
     state.eventstream.connection_state = ConnectionState::Disconnected;
+    state.eventstream.reconnecting = false;
 
     true
 }
@@ -60,9 +61,11 @@ pub fn handle_closed(state: &mut store::State, notify: &store::Notify) -> bool {
     notify(store::Event::Eventstream);
 
     state.eventstream.connection_state = ConnectionState::Reconnecting;
+    state.eventstream.reconnecting = true;
 
     for mount_listener in state.eventstream.mount_listeners.values_mut() {
         mount_listener.state = MountListenerState::Unregistered;
+        mount_listener.resync = true;
     }
 
     true
@@ -80,6 +83,7 @@ pub fn handle_authenticated(
         request_id_to_mount_listener_id: Default::default(),
         listener_id_to_mount_listener_id: Default::default(),
     };
+    state.eventstream.reconnecting = false;
 
     let mount_listeners = state
         .eventstream
@@ -127,6 +131,17 @@ pub fn handle_registered(
 
                                 mount_listener.state =
                                     MountListenerState::Registered { listener_id };
+
+                                // events after the registration are received,
+                                // the subscribers can reload now
+                                if mount_listener.resync {
+                                    mount_listener.resync = false;
+
+                                    mutation_state
+                                        .eventstream
+                                        .resync_mount_listeners
+                                        .push(mount_listener.clone());
+                                }
                             }
                         }
                         _ => {}
@@ -293,6 +308,7 @@ pub fn add_mount_subscriber(
         path: path.clone(),
         state: MountListenerState::Unregistered,
         subscribers: HashSet::from([subscriber]),
+        resync: state.eventstream.reconnecting,
     };
 
     state
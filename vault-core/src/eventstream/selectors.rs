@@ -34,3 +34,17 @@ pub fn select_mount_listener_registered_by_file_id(
         })
         .is_some()
 }
+
+pub fn select_is_reconnecting(state: &store::State) -> bool {
+    state.eventstream.reconnecting
+}
+
+pub fn select_resync_subscribers(
+    mutation_state: &store::MutationState,
+) -> impl Iterator<Item = &String> {
+    mutation_state
+        .eventstream
+        .resync_mount_listeners
+        .iter()
+        .flat_map(|mount_listener| mount_listener.subscribers.iter())
+}
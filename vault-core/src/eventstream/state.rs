@@ -41,11 +41,17 @@ pub struct MountListener {
     pub path: RemotePath,
     pub state: MountListenerState,
     pub subscribers: HashSet<String>,
+    /// Events could have been missed while the connection was lost, the
+    /// subscribers have to reload once the listener is registered again.
+    pub resync: bool,
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct EventstreamState {
     pub connection_state: ConnectionState,
+    /// The connection was lost and is not authenticated again yet, the data
+    /// may be stale.
+    pub reconnecting: bool,
     pub mount_listeners: HashMap<u32, MountListener>,
     pub mount_listeners_by_remote_file_id: HashMap<RemoteFileId, u32>,
     pub next_mount_listener_id: NextId,
@@ -63,6 +69,7 @@ impl EventstreamState {
 #[derive(Debug, Clone, Default)]
 pub struct EventstreamMutationState {
     pub requests: Vec<Request>,
+    pub resync_mount_listeners: Vec<MountListener>,
 }
 
 #[derive(Debug, Clone, Default)]
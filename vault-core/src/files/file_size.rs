@@ -1,5 +1,7 @@
 use std::time::Duration;
 
+use crate::locale;
+
 #[derive(Copy, Clone)]
 enum SizeUnit {
     B = 0,
@@ -19,6 +21,20 @@ impl SizeUnit {
             Self::TB => None,
         }
     }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::B => "B",
+            Self::KB => "KB",
+            Self::MB => "MB",
+            Self::GB => "GB",
+            Self::TB => "TB",
+        }
+    }
+}
+
+fn number_display(locale: &locale::BoxLocale, size: f64) -> String {
+    size.to_string().replace('.', locale.decimal_separator())
 }
 
 pub fn size_display(bytes: i64) -> String {
@@ -37,13 +53,15 @@ pub fn size_display(bytes: i64) -> String {
 
     size = (size * 10.0).round() / 10.0;
 
-    match unit {
-        SizeUnit::B => format!("{} B", size),
-        SizeUnit::KB => format!("{} KB", size),
-        SizeUnit::MB => format!("{} MB", size),
-        SizeUnit::GB => format!("{} GB", size),
-        SizeUnit::TB => format!("{} TB", size),
-    }
+    let locale = locale::current();
+
+    locale.message(
+        "file-size",
+        &[
+            ("size", number_display(&locale, size).into()),
+            ("unit", unit.as_str().into()),
+        ],
+    )
 }
 
 pub fn size_of_display(bytes_current: i64, bytes_total: i64) -> String {
@@ -65,17 +83,23 @@ pub fn size_of_display(bytes_current: i64, bytes_total: i64) -> String {
     size_current = (size_current * 10.0).round() / 10.0;
     size_total = (size_total * 10.0).round() / 10.0;
 
-    match unit {
-        SizeUnit::B => format!("{} / {} B", size_current, size_total),
-        SizeUnit::KB => format!("{} / {} KB", size_current, size_total),
-        SizeUnit::MB => format!("{} / {} MB", size_current, size_total),
-        SizeUnit::GB => format!("{} / {} GB", size_current, size_total),
-        SizeUnit::TB => format!("{} / {} TB", size_current, size_total),
-    }
+    let locale = locale::current();
+
+    locale.message(
+        "file-size-of",
+        &[
+            ("current", number_display(&locale, size_current).into()),
+            ("total", number_display(&locale, size_total).into()),
+            ("unit", unit.as_str().into()),
+        ],
+    )
 }
 
 pub fn speed_display_bytes_per_second(bytes_per_second: i64) -> String {
-    format!("{}/s", size_display(bytes_per_second))
+    locale::message_args(
+        "file-speed",
+        &[("size", size_display(bytes_per_second).into())],
+    )
 }
 
 pub fn speed_display_bytes_duration(bytes: i64, duration: Duration) -> String {
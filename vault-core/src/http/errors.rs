@@ -1,6 +1,9 @@
 use thiserror::Error;
 
-use crate::user_error::UserError;
+use crate::{
+    locale,
+    user_error::{ErrorCode, UserError},
+};
 
 #[derive(Error, Debug, Clone, PartialEq)]
 pub enum HttpError {
@@ -11,7 +14,15 @@ pub enum HttpError {
 impl UserError for HttpError {
     fn user_error(&self) -> String {
         match self {
-            Self::ResponseError(err) => format!("HTTP error: {}", err),
+            Self::ResponseError(err) => {
+                locale::message_args("error-http", &[("error", err.to_string().into())])
+            }
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::ResponseError(_) => ErrorCode::HttpResponse,
         }
     }
 }
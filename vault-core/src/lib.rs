@@ -1,3 +1,5 @@
+pub mod accounts;
+pub mod audit_log;
 pub mod auth;
 pub mod cipher;
 pub mod common;
@@ -9,8 +11,10 @@ pub mod files;
 pub mod http;
 pub mod lifecycle;
 pub mod locale;
+pub mod metadata_cache;
 pub mod notifications;
 pub mod oauth2;
+pub mod panic_lock;
 pub mod rclone;
 pub mod relative_time;
 pub mod remote;
@@ -20,14 +24,19 @@ pub mod remote_files_dir_pickers;
 pub mod remote_files_tags;
 pub mod repo_config_backup;
 pub mod repo_create;
+pub mod repo_duplicates;
 pub mod repo_files;
 pub mod repo_files_browsers;
 pub mod repo_files_details;
 pub mod repo_files_dir_pickers;
 pub mod repo_files_list;
 pub mod repo_files_move;
+pub mod repo_files_offline;
 pub mod repo_files_read;
+pub mod repo_files_rename;
 pub mod repo_files_tags;
+pub mod repo_files_thumbnails;
+pub mod repo_files_versions;
 pub mod repo_locker;
 pub mod repo_remove;
 pub mod repo_space_usage;
@@ -39,6 +48,7 @@ pub mod selection;
 pub mod sort;
 pub mod space_usage;
 pub mod store;
+pub mod sync;
 pub mod transfers;
 pub mod types;
 pub mod user;
@@ -1,8 +1,13 @@
 use thiserror::Error;
 
 use crate::{
-    oauth2::errors::OAuth2Error, remote::RemoteError, repos::errors::LoadReposError,
-    secure_storage::errors::SecureStorageError, user_error::UserError,
+    accounts::errors::AccountsError,
+    locale,
+    oauth2::errors::OAuth2Error,
+    remote::RemoteError,
+    repos::errors::LoadReposError,
+    secure_storage::errors::SecureStorageError,
+    user_error::{ErrorCode, UserError},
 };
 
 #[derive(Error, Debug, Clone, PartialEq)]
@@ -20,6 +25,13 @@ impl UserError for LoadError {
             Self::OnLoginError(err) => err.user_error(),
         }
     }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::OAuth2LoadError(err) => err.error_code(),
+            Self::OnLoginError(err) => err.error_code(),
+        }
+    }
 }
 
 #[derive(Error, Debug, Clone, PartialEq)]
@@ -35,11 +47,25 @@ pub enum OnLoginError {
 impl UserError for OnLoginError {
     fn user_error(&self) -> String {
         match self {
-            Self::LoadUserError(err) => format!("Failed to load user: {}", err.user_error()),
-            Self::LoadReposError(err) => format!("Failed to load safe boxes: {}", err.user_error()),
-            Self::LoadSpaceUsageError(err) => {
-                format!("Failed to load space usage: {}", err.user_error())
+            Self::LoadUserError(err) => {
+                locale::message_args("error-load-user", &[("error", err.user_error().into())])
             }
+            Self::LoadReposError(err) => {
+                locale::message_args("error-load-repos", &[("error", err.user_error().into())])
+            }
+            Self::LoadSpaceUsageError(err) => locale::message_args(
+                "error-load-space-usage",
+                &[("error", err.user_error().into())],
+            ),
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        // the cause is more useful to the frontends than the failed step
+        match self {
+            Self::LoadUserError(err) => err.error_code(),
+            Self::LoadReposError(err) => err.error_code(),
+            Self::LoadSpaceUsageError(err) => err.error_code(),
         }
     }
 }
@@ -59,6 +85,13 @@ impl UserError for LogoutError {
             Self::OnLogoutError(err) => err.user_error(),
         }
     }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::OAuth2LogoutError(err) => err.error_code(),
+            Self::OnLogoutError(err) => err.error_code(),
+        }
+    }
 }
 
 #[derive(Error, Debug, Clone, PartialEq)]
@@ -70,7 +103,15 @@ pub enum OnLogoutError {
 impl UserError for OnLogoutError {
     fn user_error(&self) -> String {
         match self {
-            Self::ClearStorageError(err) => format!("Failed to clear storage: {}", err),
+            Self::ClearStorageError(err) => {
+                locale::message_args("error-clear-storage", &[("error", err.to_string().into())])
+            }
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::ClearStorageError(_) => ErrorCode::Storage,
         }
     }
 }
@@ -93,4 +134,64 @@ impl UserError for OAuth2FinishFlowUrlError {
             Self::OnLogoutError(err) => err.user_error(),
         }
     }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::OAuth2Error(err) => err.error_code(),
+            Self::OnLoginError(err) => err.error_code(),
+            Self::OnLogoutError(err) => err.error_code(),
+        }
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum OAuth2FinishDeviceFlowError {
+    #[error("{0}")]
+    OAuth2Error(#[from] OAuth2Error),
+    #[error("{0}")]
+    OnLoginError(#[from] OnLoginError),
+}
+
+impl UserError for OAuth2FinishDeviceFlowError {
+    fn user_error(&self) -> String {
+        match self {
+            Self::OAuth2Error(err) => err.user_error(),
+            Self::OnLoginError(err) => err.user_error(),
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::OAuth2Error(err) => err.error_code(),
+            Self::OnLoginError(err) => err.error_code(),
+        }
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum SwitchAccountError {
+    #[error("{0}")]
+    AccountsError(#[from] AccountsError),
+    #[error("{0}")]
+    OAuth2Error(#[from] OAuth2Error),
+    #[error("{0}")]
+    OnLoginError(#[from] OnLoginError),
+}
+
+impl UserError for SwitchAccountError {
+    fn user_error(&self) -> String {
+        match self {
+            Self::AccountsError(err) => err.user_error(),
+            Self::OAuth2Error(err) => err.user_error(),
+            Self::OnLoginError(err) => err.user_error(),
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::AccountsError(err) => err.error_code(),
+            Self::OAuth2Error(err) => err.error_code(),
+            Self::OnLoginError(err) => err.error_code(),
+        }
+    }
 }
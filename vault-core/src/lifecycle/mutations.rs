@@ -15,6 +15,34 @@ pub fn on_logout(state: &mut store::State, notify: &store::Notify) {
     }
 }
 
+/// Resets the state of the active account. Transfers and the (locked) repos
+/// of all accounts are kept so that transfers of the previous account that
+/// are already running can finish.
+pub fn on_account_switch(state: &mut store::State, notify: &store::Notify) {
+    let accounts = std::mem::take(&mut state.accounts);
+    let transfers = std::mem::take(&mut state.transfers);
+    let repos_by_id = std::mem::take(&mut state.repos.repos_by_id);
+    let repo_ids_by_remote_file_id = std::mem::take(&mut state.repos.repo_ids_by_remote_file_id);
+    let mount_repo_trees = std::mem::take(&mut state.repos.mount_repo_trees);
+
+    state.reset();
+
+    state.accounts = accounts;
+    state.transfers = transfers;
+    state.repos.repos_by_id = repos_by_id;
+    state.repos.repo_ids_by_remote_file_id = repo_ids_by_remote_file_id;
+    state.repos.mount_repo_trees = mount_repo_trees;
+
+    state.oauth2.status = Status::Initial;
+
+    // state.reset() sets connection_state to Initial
+    state.eventstream.connection_state = eventstream::state::ConnectionState::Disconnected;
+
+    for event in store::Event::all() {
+        notify(event);
+    }
+}
+
 pub fn app_visible(
     state: &mut store::State,
     notify: &store::Notify,
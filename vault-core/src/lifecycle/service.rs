@@ -2,14 +2,28 @@ use std::sync::Arc;
 
 use futures::{
     future::{self, BoxFuture},
-    join, FutureExt, TryFutureExt,
+    join,
+    stream::AbortRegistration,
+    FutureExt, TryFutureExt,
 };
 
 use crate::{
+    accounts::{errors::AccountsError, selectors as accounts_selectors, AccountsService},
+    audit_log::{
+        state::{AuditLogEvent, RepoLockReason},
+        AuditLogService,
+    },
     eventstream::EventStreamService,
+    locale,
+    metadata_cache::MetadataCacheService,
     notifications::NotificationsService,
-    oauth2::{state::FinishFlowResult, OAuth2Service},
+    oauth2::{
+        state::{DeviceAuthorization, FinishFlowResult},
+        OAuth2Service,
+    },
     remote::Remote,
+    repo_files_offline::RepoFilesOfflineService,
+    repo_files_thumbnails::RepoFilesThumbnailsService,
     repos::ReposService,
     secure_storage::SecureStorageService,
     space_usage::SpaceUsageService,
@@ -18,7 +32,10 @@ use crate::{
 };
 
 use super::{
-    errors::{LoadError, LogoutError, OAuth2FinishFlowUrlError, OnLoginError, OnLogoutError},
+    errors::{
+        LoadError, LogoutError, OAuth2FinishDeviceFlowError, OAuth2FinishFlowUrlError,
+        OnLoginError, OnLogoutError, SwitchAccountError,
+    },
     mutations,
 };
 
@@ -29,6 +46,11 @@ pub struct LifecycleService {
     repos_service: Arc<ReposService>,
     eventstream_service: Arc<EventStreamService>,
     space_usage_service: Arc<SpaceUsageService>,
+    metadata_cache_service: Arc<MetadataCacheService>,
+    repo_files_offline_service: Arc<RepoFilesOfflineService>,
+    repo_files_thumbnails_service: Arc<RepoFilesThumbnailsService>,
+    accounts_service: Arc<AccountsService>,
+    audit_log_service: Arc<AuditLogService>,
     store: Arc<store::Store>,
 }
 
@@ -41,6 +63,11 @@ impl LifecycleService {
         repos_service: Arc<ReposService>,
         eventstream_service: Arc<EventStreamService>,
         space_usage_service: Arc<SpaceUsageService>,
+        metadata_cache_service: Arc<MetadataCacheService>,
+        repo_files_offline_service: Arc<RepoFilesOfflineService>,
+        repo_files_thumbnails_service: Arc<RepoFilesThumbnailsService>,
+        accounts_service: Arc<AccountsService>,
+        audit_log_service: Arc<AuditLogService>,
         remote: Arc<Remote>,
         store: Arc<store::Store>,
     ) -> Arc<Self> {
@@ -51,6 +78,11 @@ impl LifecycleService {
             repos_service,
             eventstream_service,
             space_usage_service,
+            metadata_cache_service,
+            repo_files_offline_service,
+            repo_files_thumbnails_service,
+            accounts_service,
+            audit_log_service,
             store,
         });
 
@@ -58,11 +90,18 @@ impl LifecycleService {
 
         remote.set_logout(Box::new(move || {
             if let Some(lifecycle_service) = remote_logout_lifecycle_service.upgrade() {
-                match lifecycle_service.logout() {
-                    Ok(()) => notifications_service
-                        .show("You've been logged out. Please log in again.".into()),
-                    Err(err) => notifications_service.show(format!("logout error: {:?}", err)),
-                }
+                let res = lifecycle_service.logout();
+
+                notifications_service.show(locale::with_store_locale(
+                    &lifecycle_service.store,
+                    || match res {
+                        Ok(()) => locale::message("notification-logged-out"),
+                        Err(err) => locale::message_args(
+                            "notification-logout-error",
+                            &[("error", format!("{:?}", err).into())],
+                        ),
+                    },
+                ));
             }
         }));
 
@@ -131,16 +170,83 @@ impl LifecycleService {
     }
 
     pub fn on_logout(&self) -> Result<(), OnLogoutError> {
+        self.audit_log_service.record(AuditLogEvent::Logout);
+
         self.eventstream_service.disconnect();
 
         self.store.mutate(|state, notify, _, _| {
             mutations::on_logout(state, notify);
         });
 
+        self.metadata_cache_service.clear();
+
+        self.repo_files_offline_service.logout();
+
+        self.repo_files_thumbnails_service.logout();
+
         self.secure_storage_service
             .clear()
             .map_err(OnLogoutError::ClearStorageError)?;
 
+        self.audit_log_service.restore();
+
+        Ok(())
+    }
+
+    /// Resets the state of the active account but keeps its token so that it
+    /// can be switched back to. Its repos are locked so that their keys are not
+    /// kept in memory while another account is active.
+    fn deactivate_account(&self) {
+        self.repos_service
+            .lock_all_repos(RepoLockReason::AccountSwitch);
+
+        self.eventstream_service.disconnect();
+
+        self.store.mutate(|state, notify, _, _| {
+            mutations::on_account_switch(state, notify);
+        });
+    }
+
+    pub async fn switch_account(&self, user_id: &str) -> Result<(), SwitchAccountError> {
+        if self
+            .store
+            .with_state(|state| accounts_selectors::select_active_user_id(state) == Some(user_id))
+        {
+            return Ok(());
+        }
+
+        let token = self.accounts_service.get_account_token(user_id)?;
+
+        self.deactivate_account();
+
+        self.accounts_service.set_active(Some(user_id.to_owned()))?;
+
+        self.oauth2_service.set_token(token)?;
+
+        self.on_login().await?;
+
+        Ok(())
+    }
+
+    /// Returns the login URL. The new account becomes active when the login
+    /// flow is finished.
+    pub fn add_account(&self) -> Result<String, AccountsError> {
+        self.deactivate_account();
+
+        self.accounts_service.set_active(None)?;
+
+        self.oauth2_service.logout()?;
+
+        Ok(self.oauth2_service.start_login_flow()?)
+    }
+
+    /// Only accounts that are not active can be removed, logout removes all
+    /// accounts.
+    pub fn remove_account(&self, user_id: &str) -> Result<(), AccountsError> {
+        self.accounts_service.remove_account(user_id)?;
+
+        self.metadata_cache_service.remove_account(user_id);
+
         Ok(())
     }
 
@@ -157,6 +263,20 @@ impl LifecycleService {
         Ok(())
     }
 
+    pub async fn oauth2_finish_device_flow(
+        &self,
+        device_authorization: &DeviceAuthorization,
+        abort_registration: AbortRegistration,
+    ) -> Result<(), OAuth2FinishDeviceFlowError> {
+        self.oauth2_service
+            .finish_device_flow(device_authorization, abort_registration)
+            .await?;
+
+        self.on_login().await?;
+
+        Ok(())
+    }
+
     pub fn app_visible(&self) {
         self.store
             .mutate(|state, notify, mutation_state, mutation_notify| {
@@ -0,0 +1,375 @@
+use std::collections::HashMap;
+
+/// CLDR plural categories used by select expressions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PluralCategory {
+    Zero,
+    One,
+    Two,
+    Few,
+    Many,
+    Other,
+}
+
+impl PluralCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Zero => "zero",
+            Self::One => "one",
+            Self::Two => "two",
+            Self::Few => "few",
+            Self::Many => "many",
+            Self::Other => "other",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MessageArg {
+    String(String),
+    Number(i64),
+}
+
+impl From<&str> for MessageArg {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_owned())
+    }
+}
+
+impl From<String> for MessageArg {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<i64> for MessageArg {
+    fn from(value: i64) -> Self {
+        Self::Number(value)
+    }
+}
+
+impl From<usize> for MessageArg {
+    fn from(value: usize) -> Self {
+        Self::Number(value as i64)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum PatternElement {
+    Text(String),
+    Variable(String),
+    Select {
+        variable: String,
+        variants: Vec<(String, Pattern)>,
+        default: usize,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Pattern(Vec<PatternElement>);
+
+impl Pattern {
+    fn format(
+        &self,
+        args: &[(&str, MessageArg)],
+        plural_category: &dyn Fn(i64) -> PluralCategory,
+        out: &mut String,
+    ) {
+        for element in &self.0 {
+            match element {
+                PatternElement::Text(text) => out.push_str(text),
+                PatternElement::Variable(name) => match get_arg(args, name) {
+                    Some(MessageArg::String(value)) => out.push_str(value),
+                    Some(MessageArg::Number(value)) => out.push_str(&value.to_string()),
+                    None => {
+                        out.push_str("{$");
+                        out.push_str(name);
+                        out.push('}');
+                    }
+                },
+                PatternElement::Select {
+                    variable,
+                    variants,
+                    default,
+                } => {
+                    let matches = |key: &str| match get_arg(args, variable) {
+                        Some(MessageArg::String(value)) => value == key,
+                        Some(MessageArg::Number(value)) => {
+                            value.to_string() == key || plural_category(*value).as_str() == key
+                        }
+                        None => false,
+                    };
+
+                    // exact number matches take precedence over plural categories
+                    let exact = variants
+                        .iter()
+                        .find(|(key, _)| match get_arg(args, variable) {
+                            Some(MessageArg::Number(value)) => &value.to_string() == key,
+                            _ => false,
+                        });
+
+                    let (_, pattern) = exact
+                        .or_else(|| variants.iter().find(|(key, _)| matches(key)))
+                        .unwrap_or(&variants[*default]);
+
+                    pattern.format(args, plural_category, out);
+                }
+            }
+        }
+    }
+}
+
+fn get_arg<'a>(args: &'a [(&str, MessageArg)], name: &str) -> Option<&'a MessageArg> {
+    args.iter()
+        .find(|(arg_name, _)| *arg_name == name)
+        .map(|(_, value)| value)
+}
+
+/// Message catalog in a subset of the Fluent syntax: `id = pattern`,
+/// `{ $variable }` placeables and `{ $variable -> [key] ... *[other] ... }`
+/// select expressions with one variant per line.
+#[derive(Debug, Clone, Default)]
+pub struct Catalog {
+    messages: HashMap<String, Pattern>,
+}
+
+impl Catalog {
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let mut messages = HashMap::new();
+        let mut current: Option<(String, String)> = None;
+
+        let mut finish = |current: &mut Option<(String, String)>| -> Result<(), String> {
+            if let Some((id, value)) = current.take() {
+                let pattern = parse_pattern(&value).map_err(|err| format!("{}: {}", id, err))?;
+
+                messages.insert(id, pattern);
+            }
+
+            Ok(())
+        };
+
+        for line in source.lines() {
+            if line.trim_start().starts_with('#') || line.trim().is_empty() {
+                continue;
+            }
+
+            // the closing brace of a select expression can start a line
+            if line.starts_with(char::is_whitespace) || line.starts_with('}') {
+                match current.as_mut() {
+                    Some((_, value)) => {
+                        value.push('\n');
+                        value.push_str(line.trim());
+                    }
+                    None => return Err(format!("unexpected indented line: {}", line)),
+                }
+
+                continue;
+            }
+
+            finish(&mut current)?;
+
+            let (id, value) = line
+                .split_once('=')
+                .ok_or_else(|| format!("expected message: {}", line))?;
+
+            current = Some((id.trim().to_owned(), value.trim().to_owned()));
+        }
+
+        finish(&mut current)?;
+
+        Ok(Self { messages })
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.messages.contains_key(id)
+    }
+
+    pub fn ids(&self) -> impl Iterator<Item = &str> {
+        self.messages.keys().map(|id| id.as_str())
+    }
+
+    pub fn format(
+        &self,
+        id: &str,
+        args: &[(&str, MessageArg)],
+        plural_category: &dyn Fn(i64) -> PluralCategory,
+    ) -> Option<String> {
+        let pattern = self.messages.get(id)?;
+
+        let mut out = String::new();
+
+        pattern.format(args, plural_category, &mut out);
+
+        Some(out)
+    }
+}
+
+fn parse_pattern(source: &str) -> Result<Pattern, String> {
+    let mut elements = Vec::new();
+    let mut text = String::new();
+    let mut chars = source.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        if c != '{' {
+            text.push(c);
+
+            continue;
+        }
+
+        let mut depth = 1;
+        let mut in_literal = false;
+        let mut end = None;
+
+        for (i, c) in chars.by_ref() {
+            match c {
+                '"' => in_literal = !in_literal,
+                _ if in_literal => {}
+                '{' => depth += 1,
+                '}' => {
+                    depth -= 1;
+
+                    if depth == 0 {
+                        end = Some(i);
+
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let end = end.ok_or_else(|| String::from("unclosed placeable"))?;
+
+        if !text.is_empty() {
+            elements.push(PatternElement::Text(std::mem::take(&mut text)));
+        }
+
+        elements.push(parse_placeable(source[start + 1..end].trim())?);
+    }
+
+    if !text.is_empty() {
+        elements.push(PatternElement::Text(text));
+    }
+
+    Ok(Pattern(elements))
+}
+
+fn parse_placeable(source: &str) -> Result<PatternElement, String> {
+    if let Some(literal) = source
+        .strip_prefix('"')
+        .and_then(|source| source.strip_suffix('"'))
+    {
+        return Ok(PatternElement::Text(literal.to_owned()));
+    }
+
+    let (variable, variants_source) = match source.split_once("->") {
+        Some((variable, variants_source)) => (variable.trim(), Some(variants_source)),
+        None => (source, None),
+    };
+
+    let variable = variable
+        .strip_prefix('$')
+        .ok_or_else(|| format!("expected variable: {}", variable))?
+        .to_owned();
+
+    let variants_source = match variants_source {
+        Some(variants_source) => variants_source,
+        None => return Ok(PatternElement::Variable(variable)),
+    };
+
+    let mut variants = Vec::new();
+    let mut default = None;
+
+    for line in variants_source.lines().map(str::trim) {
+        if line.is_empty() {
+            continue;
+        }
+
+        let (is_default, line) = match line.strip_prefix('*') {
+            Some(line) => (true, line),
+            None => (false, line),
+        };
+
+        let (key, value) = line
+            .strip_prefix('[')
+            .and_then(|line| line.split_once(']'))
+            .ok_or_else(|| format!("expected variant: {}", line))?;
+
+        if is_default {
+            default = Some(variants.len());
+        }
+
+        variants.push((key.trim().to_owned(), parse_pattern(value.trim())?));
+    }
+
+    let default = default.ok_or_else(|| format!("missing default variant: {}", variable))?;
+
+    Ok(PatternElement::Select {
+        variable,
+        variants,
+        default,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Catalog, MessageArg, PluralCategory};
+
+    fn plural_en(n: i64) -> PluralCategory {
+        if n == 1 {
+            PluralCategory::One
+        } else {
+            PluralCategory::Other
+        }
+    }
+
+    #[test]
+    fn test_catalog_format() {
+        let catalog = Catalog::parse(
+            r#"
+# comment
+simple = Simple message
+variable = Hello, { $name }!
+literal = { "{" }braces{ "}" }
+select = { $count ->
+    [0] No items
+    [one] { $count } item
+   *[other] { $count } items
+}
+"#,
+        )
+        .unwrap();
+
+        let format = |id: &str, args: &[(&str, MessageArg)]| catalog.format(id, args, &plural_en);
+
+        assert_eq!(format("simple", &[]), Some("Simple message".into()));
+        assert_eq!(
+            format("variable", &[("name", "World".into())]),
+            Some("Hello, World!".into())
+        );
+        assert_eq!(format("variable", &[]), Some("Hello, {$name}!".into()));
+        assert_eq!(format("literal", &[]), Some("{braces}".into()));
+        assert_eq!(
+            format("select", &[("count", 0usize.into())]),
+            Some("No items".into())
+        );
+        assert_eq!(
+            format("select", &[("count", 1usize.into())]),
+            Some("1 item".into())
+        );
+        assert_eq!(
+            format("select", &[("count", 5usize.into())]),
+            Some("5 items".into())
+        );
+        assert_eq!(format("select", &[]), Some("{$count} items".into()));
+        assert_eq!(format("missing", &[]), None);
+    }
+
+    #[test]
+    fn test_catalog_parse_errors() {
+        assert!(Catalog::parse("invalid").is_err());
+        assert!(Catalog::parse("id = { $count").is_err());
+        assert!(Catalog::parse("id = { $count ->\n    [one] one\n}").is_err());
+        assert!(Catalog::parse("id = { count }").is_err());
+    }
+}
@@ -0,0 +1,24 @@
+use thiserror::Error;
+
+use crate::user_error::{ErrorCode, UserError};
+
+use super::catalog::MessageArg;
+
+#[derive(Error, Debug, Clone, PartialEq)]
+#[error("locale not found: {0}")]
+pub struct LocaleNotFoundError(pub String);
+
+impl UserError for LocaleNotFoundError {
+    fn user_error(&self) -> String {
+        super::message_args(
+            "error-locale-not-found",
+            &[("name", MessageArg::from(self.0.as_str()))],
+        )
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        ErrorCode::LocaleNotFound {
+            name: self.0.clone(),
+        }
+    }
+}
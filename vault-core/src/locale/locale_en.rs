@@ -0,0 +1,55 @@
+use lazy_static::lazy_static;
+
+use crate::relative_time::{RelativeTimeDiff, RelativeTimeModifier};
+
+use super::{
+    catalog::{Catalog, PluralCategory},
+    Locale,
+};
+
+lazy_static! {
+    static ref CATALOG: Catalog = Catalog::parse(include_str!("messages/en.ftl")).unwrap();
+}
+
+#[derive(Debug, Default)]
+pub struct LocaleEn {}
+
+impl Locale for LocaleEn {
+    fn plural_category(&self, n: i64) -> PluralCategory {
+        if n == 1 {
+            PluralCategory::One
+        } else {
+            PluralCategory::Other
+        }
+    }
+
+    fn catalog(&self) -> &Catalog {
+        &CATALOG
+    }
+
+    fn relative_time(&self, diff: &RelativeTimeDiff, modifier: &RelativeTimeModifier) -> String {
+        use RelativeTimeDiff::*;
+        use RelativeTimeModifier::*;
+
+        let s = match diff {
+            FewSeconds => format!("a few seconds"),
+            Seconds(n) => format!("{} seconds", n),
+            Minute => format!("a minute"),
+            Minutes(n) => format!("{} minutes", n),
+            Hour => format!("an hour"),
+            Hours(n) => format!("{} hours", n),
+            Day => format!("a day"),
+            Days(n) => format!("{} days", n),
+            Month => format!("a month"),
+            Months(n) => format!("{} months", n),
+            Year => format!("a year"),
+            Years(n) => format!("{} years", n),
+        };
+
+        match modifier {
+            None => s,
+            Past => format!("{} ago", s),
+            Future => format!("in {}", s),
+        }
+    }
+}
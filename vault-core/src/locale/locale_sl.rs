@@ -0,0 +1,109 @@
+use lazy_static::lazy_static;
+
+use crate::relative_time::{RelativeTimeDiff, RelativeTimeModifier};
+
+use super::{
+    catalog::{Catalog, PluralCategory},
+    Locale,
+};
+
+lazy_static! {
+    static ref CATALOG: Catalog = Catalog::parse(include_str!("messages/sl.ftl")).unwrap();
+}
+
+fn plural_category(n: i64) -> PluralCategory {
+    match n % 100 {
+        1 => PluralCategory::One,
+        2 => PluralCategory::Two,
+        3 | 4 => PluralCategory::Few,
+        _ => PluralCategory::Other,
+    }
+}
+
+/// Forms for one, two, few and other.
+fn plural(n: &i64, forms: [&str; 4]) -> String {
+    let form = match plural_category(*n) {
+        PluralCategory::One => forms[0],
+        PluralCategory::Two => forms[1],
+        PluralCategory::Few => forms[2],
+        _ => forms[3],
+    };
+
+    format!("{} {}", n, form)
+}
+
+#[derive(Debug, Default)]
+pub struct LocaleSl {}
+
+impl Locale for LocaleSl {
+    fn plural_category(&self, n: i64) -> PluralCategory {
+        plural_category(n)
+    }
+
+    fn decimal_separator(&self) -> &'static str {
+        ","
+    }
+
+    fn catalog(&self) -> &Catalog {
+        &CATALOG
+    }
+
+    fn relative_time(&self, diff: &RelativeTimeDiff, modifier: &RelativeTimeModifier) -> String {
+        use RelativeTimeDiff::*;
+        use RelativeTimeModifier::*;
+
+        // the case of the noun depends on the modifier
+        match modifier {
+            None => match diff {
+                FewSeconds => format!("nekaj sekund"),
+                Seconds(n) => plural(n, ["sekunda", "sekundi", "sekunde", "sekund"]),
+                Minute => format!("minuta"),
+                Minutes(n) => plural(n, ["minuta", "minuti", "minute", "minut"]),
+                Hour => format!("ura"),
+                Hours(n) => plural(n, ["ura", "uri", "ure", "ur"]),
+                Day => format!("dan"),
+                Days(n) => plural(n, ["dan", "dneva", "dnevi", "dni"]),
+                Month => format!("mesec"),
+                Months(n) => plural(n, ["mesec", "meseca", "meseci", "mesecev"]),
+                Year => format!("leto"),
+                Years(n) => plural(n, ["leto", "leti", "leta", "let"]),
+            },
+            Past => {
+                let s = match diff {
+                    FewSeconds => format!("nekaj sekundami"),
+                    Seconds(n) => plural(n, ["sekundo", "sekundama", "sekundami", "sekundami"]),
+                    Minute => format!("minuto"),
+                    Minutes(n) => plural(n, ["minuto", "minutama", "minutami", "minutami"]),
+                    Hour => format!("eno uro"),
+                    Hours(n) => plural(n, ["uro", "urama", "urami", "urami"]),
+                    Day => format!("enim dnem"),
+                    Days(n) => plural(n, ["dnem", "dnevoma", "dnevi", "dnevi"]),
+                    Month => format!("enim mesecem"),
+                    Months(n) => plural(n, ["mesecem", "mesecema", "meseci", "meseci"]),
+                    Year => format!("enim letom"),
+                    Years(n) => plural(n, ["letom", "letoma", "leti", "leti"]),
+                };
+
+                format!("pred {}", s)
+            }
+            Future => {
+                let s = match diff {
+                    FewSeconds => format!("nekaj sekund"),
+                    Seconds(n) => plural(n, ["sekundo", "sekundi", "sekunde", "sekund"]),
+                    Minute => format!("minuto"),
+                    Minutes(n) => plural(n, ["minuto", "minuti", "minute", "minut"]),
+                    Hour => format!("eno uro"),
+                    Hours(n) => plural(n, ["uro", "uri", "ure", "ur"]),
+                    Day => format!("en dan"),
+                    Days(n) => plural(n, ["dan", "dneva", "dni", "dni"]),
+                    Month => format!("en mesec"),
+                    Months(n) => plural(n, ["mesec", "meseca", "mesece", "mesecev"]),
+                    Year => format!("eno leto"),
+                    Years(n) => plural(n, ["leto", "leti", "leta", "let"]),
+                };
+
+                format!("čez {}", s)
+            }
+        }
+    }
+}
@@ -0,0 +1,122 @@
+# Errors
+
+error-storage = Storage error: { $error }
+error-clear-storage = Failed to clear storage: { $error }
+error-http = HTTP error: { $error }
+error-api = API error: { $message }
+error-unexpected-status = unexpected HTTP status: { $status }: { $message }
+error-not-enough-space = Not enough space. Free up some space and try again.
+error-unknown = Unknown error: { $error }
+error-locale-not-found = Language not found: { $name }
+error-audit-log-chain-broken = Audit log was modified at entry { $seq }.
+error-audit-log-head-mismatch = Audit log entries are missing.
+
+error-invalid-path = Path is not valid
+error-invalid-name = Name is not valid
+error-invalid-pattern = Invalid pattern: { $error }
+
+error-repo-not-found = Safe Box not found
+error-repo-not-found-sentence = Safe Box not found.
+error-repo-locked = Safe Box is locked
+error-repo-unlocked = Safe Box is unlocked
+error-repo-read-only = Safe Box is read-only
+error-repo-already-exists = This location is already a Safe Box.
+error-repos-limit-exceeded = You cannot create more Safe Boxes. Please upgrade your account.
+error-invalid-password = Safe Key is not correct.
+error-too-many-attempts = Too many failed attempts. Try again in { $seconds } s.
+
+error-file-not-found = File not found
+error-folder-already-exists = Folder with this name already exists.
+error-file-already-exists = File with this name already exists.
+error-rename-root = Cannot rename root folder
+error-move-root = Cannot move root folder
+error-versions-not-a-file = Versions are only supported for files
+error-version-not-found = Version not found
+error-transfer-aborted = Transfer has been aborted.
+
+error-decrypt-size = Failed to decrypt size: { $error }
+error-decode-file-name = Failed to decode file name
+error-decrypt-file-name = Failed to decrypt file name. Vault files can only be uploaded using Vault apps or rclone. If all your files have errors please check that you've used the correct salt.
+error-file-name-unicode = File name is not a valid Unicode text
+
+error-tags-base64 = Failed to base64 decode tags: { $error }
+error-tags-decrypt = Failed to decrypt tags: { $error }
+error-tags-deserialize = Failed to deserialize tags: { $error }
+error-tags-serialize = Failed to serialize tags: { $error }
+error-tags-encrypt = Failed to encrypt tags: { $error }
+error-tags-multiple-values = Multiple tags values found
+error-tags-hash-mismatch = Encrypted file hash does not match the encrypted hash in tags: { $expected } != { $actual }
+
+error-thumbnail-decode = Image could not be decoded
+error-thumbnail-encode = Thumbnail could not be created
+error-thumbnail-not-supported = Thumbnail is not available for this file
+
+error-load-user = Failed to load user: { $error }
+error-load-repos = Failed to load safe boxes: { $error }
+error-load-space-usage = Failed to load space usage: { $error }
+
+error-invalid-oauth2-token = Invalid OAuth 2 token: { $error }
+error-invalid-oauth2-state = Invalid authentication state. Please try again.
+error-invalid-grant = Invalid authentication permissions: { $error }
+error-authorization-pending = Authorization is pending. Please try again.
+error-slow-down = Too many authorization requests. Please try again.
+error-access-denied = Authorization was denied.
+error-device-code-expired = Authorization code expired. Please try again.
+
+# Dialogs
+
+dialog-ok = Ok
+dialog-yes = Yes
+dialog-no = No
+dialog-cancel = Cancel
+dialog-confirm-title = Are you sure?
+
+dialog-delete-files-title = Delete files
+dialog-delete-files-message = { $count ->
+    [one] Do you really want to delete { $count } item?
+   *[other] Do you really want to delete { $count } items?
+}
+dialog-delete-files-confirm = Delete
+
+dialog-create-folder-title = Enter new folder name
+dialog-create-folder-placeholder = Folder name
+dialog-create-folder-confirm = Create folder
+
+dialog-create-file-title = Enter new file name
+dialog-create-file-placeholder = File name
+dialog-create-file-confirm = Create file
+
+dialog-rename-title = Enter new name for '{ $name }'
+dialog-rename-placeholder = New name
+dialog-rename-confirm = Rename
+
+dialog-save-failed-title = File could not be saved
+dialog-save-failed-message = File could not be saved ({ $error }). Do you want to Try again or Discard the changes?
+dialog-save-failed-confirm = Try again
+dialog-discard-changes = Discard changes
+
+dialog-file-not-accessible-title = File not accessible
+dialog-file-not-accessible-message = File { $name } is no longer accessible. Probably it was deleted or you no longer have access to it.
+dialog-file-not-accessible-save-message = File { $name } is no longer accessible. Probably it was deleted or you no longer have access to it. Do you want to Save the file to a new location?
+dialog-file-not-accessible-save-discard-message = File { $name } is no longer accessible. Probably it was deleted or you no longer have access to it. Do you want to Save the file to a new location or Discard the changes?
+dialog-file-not-accessible-confirm = Save to a new location
+
+dialog-file-conflict-title = File was changed by someone else since your last save
+dialog-file-conflict-message = Saving into the existing file is not possible. Do you want to Save your changes as a new file?
+dialog-file-conflict-discard-message = Saving into the existing file is not possible. Do you want to Save your changes as a new file or Discard them?
+dialog-file-conflict-confirm = Save as a new file
+
+dialog-file-location-changed-title = File location changed
+dialog-file-location-changed-message = File { $name } was saved here because it could not be saved in its original location.
+
+# Notifications
+
+notification-logged-out = You've been logged out. Please log in again.
+notification-logout-error = logout error: { $error }
+notification-panic-lock-shortcut-error = Failed to register the panic lock shortcut: { $error }
+
+# File sizes
+
+file-size = { $size } { $unit }
+file-size-of = { $current } / { $total } { $unit }
+file-speed = { $size }/s
@@ -0,0 +1,124 @@
+# Errors
+
+error-storage = Napaka shrambe: { $error }
+error-clear-storage = Brisanje shrambe ni uspelo: { $error }
+error-http = Napaka HTTP: { $error }
+error-api = Napaka API: { $message }
+error-unexpected-status = nepričakovan status HTTP: { $status }: { $message }
+error-not-enough-space = Ni dovolj prostora. Sprostite nekaj prostora in poskusite znova.
+error-unknown = Neznana napaka: { $error }
+error-locale-not-found = Jezik ni bil najden: { $name }
+error-audit-log-chain-broken = Revizijski dnevnik je bil spremenjen pri vnosu { $seq }.
+error-audit-log-head-mismatch = V revizijskem dnevniku manjkajo vnosi.
+
+error-invalid-path = Pot ni veljavna
+error-invalid-name = Ime ni veljavno
+error-invalid-pattern = Neveljaven vzorec: { $error }
+
+error-repo-not-found = Sef ni bil najden
+error-repo-not-found-sentence = Sef ni bil najden.
+error-repo-locked = Sef je zaklenjen
+error-repo-unlocked = Sef je odklenjen
+error-repo-read-only = Sef je samo za branje
+error-repo-already-exists = Ta lokacija je že sef.
+error-repos-limit-exceeded = Ne morete ustvariti več sefov. Prosimo, nadgradite svoj račun.
+error-invalid-password = Ključ sefa ni pravilen.
+error-too-many-attempts = Preveč neuspelih poskusov. Poskusite znova čez { $seconds } s.
+
+error-file-not-found = Datoteka ni bila najdena
+error-folder-already-exists = Mapa s tem imenom že obstaja.
+error-file-already-exists = Datoteka s tem imenom že obstaja.
+error-rename-root = Korenske mape ni mogoče preimenovati
+error-move-root = Korenske mape ni mogoče premakniti
+error-versions-not-a-file = Različice so podprte samo za datoteke
+error-version-not-found = Različica ni bila najdena
+error-transfer-aborted = Prenos je bil prekinjen.
+
+error-decrypt-size = Dešifriranje velikosti ni uspelo: { $error }
+error-decode-file-name = Dekodiranje imena datoteke ni uspelo
+error-decrypt-file-name = Dešifriranje imena datoteke ni uspelo. Datoteke v sefu je mogoče naložiti samo z aplikacijami Vault ali rclone. Če imajo napake vse vaše datoteke, preverite, ali ste uporabili pravilno sol.
+error-file-name-unicode = Ime datoteke ni veljavno besedilo Unicode
+
+error-tags-base64 = Dekodiranje oznak base64 ni uspelo: { $error }
+error-tags-decrypt = Dešifriranje oznak ni uspelo: { $error }
+error-tags-deserialize = Branje oznak ni uspelo: { $error }
+error-tags-serialize = Zapisovanje oznak ni uspelo: { $error }
+error-tags-encrypt = Šifriranje oznak ni uspelo: { $error }
+error-tags-multiple-values = Najdenih je več vrednosti oznak
+error-tags-hash-mismatch = Zgoščena vrednost šifrirane datoteke se ne ujema z zgoščeno vrednostjo v oznakah: { $expected } != { $actual }
+
+error-thumbnail-decode = Slike ni bilo mogoče dekodirati
+error-thumbnail-encode = Sličice ni bilo mogoče ustvariti
+error-thumbnail-not-supported = Sličica za to datoteko ni na voljo
+
+error-load-user = Nalaganje uporabnika ni uspelo: { $error }
+error-load-repos = Nalaganje sefov ni uspelo: { $error }
+error-load-space-usage = Nalaganje porabe prostora ni uspelo: { $error }
+
+error-invalid-oauth2-token = Neveljaven žeton OAuth 2: { $error }
+error-invalid-oauth2-state = Neveljavno stanje prijave. Poskusite znova.
+error-invalid-grant = Neveljavna dovoljenja za prijavo: { $error }
+error-authorization-pending = Odobritev še ni potrjena. Poskusite znova.
+error-slow-down = Preveč zahtev za odobritev. Poskusite znova.
+error-access-denied = Odobritev je bila zavrnjena.
+error-device-code-expired = Koda za odobritev je potekla. Poskusite znova.
+
+# Dialogs
+
+dialog-ok = V redu
+dialog-yes = Da
+dialog-no = Ne
+dialog-cancel = Prekliči
+dialog-confirm-title = Ali ste prepričani?
+
+dialog-delete-files-title = Izbriši datoteke
+dialog-delete-files-message = { $count ->
+    [one] Ali res želite izbrisati { $count } element?
+    [two] Ali res želite izbrisati { $count } elementa?
+    [few] Ali res želite izbrisati { $count } elemente?
+   *[other] Ali res želite izbrisati { $count } elementov?
+}
+dialog-delete-files-confirm = Izbriši
+
+dialog-create-folder-title = Vnesite ime nove mape
+dialog-create-folder-placeholder = Ime mape
+dialog-create-folder-confirm = Ustvari mapo
+
+dialog-create-file-title = Vnesite ime nove datoteke
+dialog-create-file-placeholder = Ime datoteke
+dialog-create-file-confirm = Ustvari datoteko
+
+dialog-rename-title = Vnesite novo ime za '{ $name }'
+dialog-rename-placeholder = Novo ime
+dialog-rename-confirm = Preimenuj
+
+dialog-save-failed-title = Datoteke ni bilo mogoče shraniti
+dialog-save-failed-message = Datoteke ni bilo mogoče shraniti ({ $error }). Ali želite Poskusiti znova ali Zavreči spremembe?
+dialog-save-failed-confirm = Poskusi znova
+dialog-discard-changes = Zavrzi spremembe
+
+dialog-file-not-accessible-title = Datoteka ni dostopna
+dialog-file-not-accessible-message = Datoteka { $name } ni več dostopna. Verjetno je bila izbrisana ali do nje nimate več dostopa.
+dialog-file-not-accessible-save-message = Datoteka { $name } ni več dostopna. Verjetno je bila izbrisana ali do nje nimate več dostopa. Ali želite Shraniti datoteko na novo lokacijo?
+dialog-file-not-accessible-save-discard-message = Datoteka { $name } ni več dostopna. Verjetno je bila izbrisana ali do nje nimate več dostopa. Ali želite Shraniti datoteko na novo lokacijo ali Zavreči spremembe?
+dialog-file-not-accessible-confirm = Shrani na novo lokacijo
+
+dialog-file-conflict-title = Datoteko je od vašega zadnjega shranjevanja spremenil nekdo drug
+dialog-file-conflict-message = Shranjevanje v obstoječo datoteko ni mogoče. Ali želite Shraniti spremembe kot novo datoteko?
+dialog-file-conflict-discard-message = Shranjevanje v obstoječo datoteko ni mogoče. Ali želite Shraniti spremembe kot novo datoteko ali jih Zavreči?
+dialog-file-conflict-confirm = Shrani kot novo datoteko
+
+dialog-file-location-changed-title = Lokacija datoteke se je spremenila
+dialog-file-location-changed-message = Datoteka { $name } je bila shranjena sem, ker je ni bilo mogoče shraniti na prvotno lokacijo.
+
+# Notifications
+
+notification-logged-out = Odjavljeni ste bili. Prosimo, prijavite se znova.
+notification-logout-error = napaka pri odjavi: { $error }
+notification-panic-lock-shortcut-error = Bližnjice za nujno zaklepanje ni bilo mogoče registrirati: { $error }
+
+# File sizes
+
+file-size = { $size } { $unit }
+file-size-of = { $current } / { $total } { $unit }
+file-speed = { $size }/s
@@ -1,7 +1,43 @@
-use crate::relative_time::{RelativeTimeDiff, RelativeTimeModifier};
+pub mod catalog;
+pub mod errors;
+mod locale_en;
+mod locale_sl;
+
+use std::{cell::RefCell, sync::Arc};
+
+use crate::{
+    relative_time::{RelativeTimeDiff, RelativeTimeModifier},
+    store,
+};
+
+use self::catalog::{Catalog, MessageArg, PluralCategory};
+pub use self::{locale_en::LocaleEn, locale_sl::LocaleSl};
+
+pub const LOCALE_NAMES: &[&str] = &["en", "sl"];
 
 pub trait Locale {
     fn relative_time(&self, diff: &RelativeTimeDiff, modifier: &RelativeTimeModifier) -> String;
+
+    fn plural_category(&self, n: i64) -> PluralCategory;
+
+    fn decimal_separator(&self) -> &'static str {
+        "."
+    }
+
+    fn catalog(&self) -> &Catalog;
+
+    /// Formats the message, falls back to English for missing messages and
+    /// to the message id for unknown messages.
+    fn message(&self, id: &str, args: &[(&str, MessageArg)]) -> String {
+        self.catalog()
+            .format(id, args, &|n| self.plural_category(n))
+            .or_else(|| {
+                let en = LocaleEn::default();
+
+                en.catalog().format(id, args, &|n| en.plural_category(n))
+            })
+            .unwrap_or_else(|| id.to_owned())
+    }
 }
 
 pub type BoxLocale = Box<dyn Locale + Send + Sync>;
@@ -9,39 +45,55 @@ pub type BoxLocale = Box<dyn Locale + Send + Sync>;
 pub fn get_locale(name: &str) -> Option<BoxLocale> {
     match name {
         "en" => Some(Box::new(LocaleEn {})),
+        "sl" => Some(Box::new(LocaleSl {})),
         _ => None,
     }
 }
 
-#[derive(Debug, Default)]
-pub struct LocaleEn {}
-
-impl Locale for LocaleEn {
-    fn relative_time(&self, diff: &RelativeTimeDiff, modifier: &RelativeTimeModifier) -> String {
-        use RelativeTimeDiff::*;
-        use RelativeTimeModifier::*;
-
-        let s = match diff {
-            FewSeconds => format!("a few seconds"),
-            Seconds(n) => format!("{} seconds", n),
-            Minute => format!("a minute"),
-            Minutes(n) => format!("{} minutes", n),
-            Hour => format!("an hour"),
-            Hours(n) => format!("{} hours", n),
-            Day => format!("a day"),
-            Days(n) => format!("{} days", n),
-            Month => format!("a month"),
-            Months(n) => format!("{} months", n),
-            Year => format!("a year"),
-            Years(n) => format!("{} years", n),
-        };
+thread_local! {
+    static CURRENT_LOCALE: RefCell<Option<Arc<BoxLocale>>> = const { RefCell::new(None) };
+}
 
-        match modifier {
-            None => s,
-            Past => format!("{} ago", s),
-            Future => format!("in {}", s),
+/// Locale used for messages that are not formatted with an explicit locale,
+/// e.g. `UserError::user_error`. It is the locale set by `with_locale` on the
+/// current thread, English otherwise.
+pub fn current() -> Arc<BoxLocale> {
+    CURRENT_LOCALE
+        .with(|current| current.borrow().clone())
+        .unwrap_or_else(|| Arc::new(Box::new(LocaleEn {})))
+}
+
+/// Formats messages in `f` with `locale`. Must not be held across an await.
+pub fn with_locale<R>(locale: Arc<BoxLocale>, f: impl FnOnce() -> R) -> R {
+    struct Restore(Option<Arc<BoxLocale>>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            CURRENT_LOCALE.with(|current| *current.borrow_mut() = self.0.take());
         }
     }
+
+    let _restore = Restore(CURRENT_LOCALE.with(|current| current.replace(Some(locale))));
+
+    f()
+}
+
+/// Formats messages in `f` with the locale from the store config
+/// (`Vault::locale_set`), so that vaults in the same process can use
+/// different locales.
+pub fn with_store_locale<R>(store: &store::Store, f: impl FnOnce() -> R) -> R {
+    with_locale(
+        store.with_state(|state| state.config.locale.locale.clone()),
+        f,
+    )
+}
+
+pub fn message(id: &str) -> String {
+    current().message(id, &[])
+}
+
+pub fn message_args(id: &str, args: &[(&str, MessageArg)]) -> String {
+    current().message(id, args)
 }
 
 #[cfg(test)]
@@ -51,7 +103,7 @@ mod tests {
     use chrono::Duration;
 
     use crate::{
-        locale::{BoxLocale, LocaleEn},
+        locale::{catalog::PluralCategory, BoxLocale, LocaleEn, LocaleSl},
         relative_time::{RelativeTimeDiff, RelativeTimeModifier},
     };
 
@@ -198,4 +250,78 @@ mod tests {
             ("2 years".into(), "2 years ago".into(), "in 2 years".into())
         );
     }
+
+    #[test]
+    fn test_locale_catalogs_complete() {
+        let en: BoxLocale = Box::new(LocaleEn::default());
+        let sl: BoxLocale = Box::new(LocaleSl::default());
+
+        let mut missing = en
+            .catalog()
+            .ids()
+            .filter(|id| !sl.catalog().contains(id))
+            .collect::<Vec<_>>();
+        missing.sort();
+
+        assert!(missing.is_empty(), "missing sl messages: {:?}", missing);
+    }
+
+    #[test]
+    fn test_locale_message() {
+        let en: BoxLocale = Box::new(LocaleEn::default());
+        let sl: BoxLocale = Box::new(LocaleSl::default());
+
+        let delete = |locale: &BoxLocale, count: usize| {
+            locale.message("dialog-delete-files-message", &[("count", count.into())])
+        };
+
+        assert_eq!(delete(&en, 1), "Do you really want to delete 1 item?");
+        assert_eq!(delete(&en, 2), "Do you really want to delete 2 items?");
+        assert_eq!(delete(&sl, 1), "Ali res želite izbrisati 1 element?");
+        assert_eq!(delete(&sl, 2), "Ali res želite izbrisati 2 elementa?");
+        assert_eq!(delete(&sl, 3), "Ali res želite izbrisati 3 elemente?");
+        assert_eq!(delete(&sl, 5), "Ali res želite izbrisati 5 elementov?");
+        assert_eq!(delete(&sl, 101), "Ali res želite izbrisati 101 element?");
+
+        assert_eq!(sl.message("missing-message", &[]), "missing-message");
+    }
+
+    #[test]
+    fn test_locale_sl_plural_category() {
+        let sl: BoxLocale = Box::new(LocaleSl::default());
+
+        assert_eq!(sl.plural_category(0), PluralCategory::Other);
+        assert_eq!(sl.plural_category(1), PluralCategory::One);
+        assert_eq!(sl.plural_category(2), PluralCategory::Two);
+        assert_eq!(sl.plural_category(4), PluralCategory::Few);
+        assert_eq!(sl.plural_category(5), PluralCategory::Other);
+        assert_eq!(sl.plural_category(102), PluralCategory::Two);
+        assert_eq!(sl.plural_category(111), PluralCategory::Other);
+    }
+
+    #[test]
+    fn test_locale_sl_relative_time() {
+        let sl: BoxLocale = Box::new(LocaleSl::default());
+
+        let case = |diff: RelativeTimeDiff| {
+            (
+                sl.relative_time(&diff, &RelativeTimeModifier::None),
+                sl.relative_time(&diff, &RelativeTimeModifier::Past),
+                sl.relative_time(&diff, &RelativeTimeModifier::Future),
+            )
+        };
+
+        assert_eq!(
+            case(RelativeTimeDiff::Minute),
+            ("minuta".into(), "pred minuto".into(), "čez minuto".into())
+        );
+        assert_eq!(
+            case(RelativeTimeDiff::Hours(2)),
+            ("2 uri".into(), "pred 2 urama".into(), "čez 2 uri".into())
+        );
+        assert_eq!(
+            case(RelativeTimeDiff::Days(5)),
+            ("5 dni".into(), "pred 5 dnevi".into(), "čez 5 dni".into())
+        );
+    }
 }
@@ -0,0 +1,11 @@
+/// MetadataDiskCache stores the last known repos and listings between
+/// sessions. Values are encrypted with the metadata cache key so
+/// implementations can write them to disk as they are.
+pub trait MetadataDiskCache {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, String>;
+    fn set(&self, key: &str, value: &[u8]) -> Result<(), String>;
+    fn remove(&self, key: &str) -> Result<(), String>;
+    /// Removes all entries whose key starts with the prefix.
+    fn remove_prefix(&self, prefix: &str) -> Result<(), String>;
+    fn clear(&self) -> Result<(), String>;
+}
@@ -0,0 +1,6 @@
+pub mod disk_cache;
+pub mod selectors;
+pub mod service;
+pub mod state;
+
+pub use self::{disk_cache::MetadataDiskCache, service::MetadataCacheService};
@@ -0,0 +1,50 @@
+use crate::{
+    accounts::selectors as accounts_selectors,
+    store,
+    types::{MountId, RemotePath},
+};
+
+/// Every account has its own key so switching accounts keeps the entries of
+/// the other accounts.
+pub fn get_key_storage_key(user_id: &str) -> String {
+    format!("vaultMetadataCacheKey_{}", user_id)
+}
+
+/// Keys are hex digests so they are safe to use as file names and do not
+/// contain user IDs or paths.
+fn get_cache_key(value: &str) -> String {
+    hex::encode(md5::compute(value.as_bytes()).0)
+}
+
+/// All keys of an account start with the same prefix so that its entries can
+/// be removed together.
+pub fn get_account_cache_key_prefix(user_id: &str) -> String {
+    format!("{}-", get_cache_key(user_id))
+}
+
+pub fn get_repos_cache_key(user_id: &str) -> String {
+    format!(
+        "{}{}",
+        get_account_cache_key_prefix(user_id),
+        get_cache_key("repos")
+    )
+}
+
+pub fn get_files_cache_key(user_id: &str, mount_id: &MountId, path: &RemotePath) -> String {
+    format!(
+        "{}{}",
+        get_account_cache_key_prefix(user_id),
+        get_cache_key(&format!("files:{}:{}", mount_id.0, path.to_lowercase().0))
+    )
+}
+
+/// Only listings inside repos of the active account are cached.
+pub fn select_is_repo_path(state: &store::State, mount_id: &MountId, path: &RemotePath) -> bool {
+    accounts_selectors::select_inactive_mount_account(state, mount_id).is_none()
+        && state
+            .repos
+            .mount_repo_trees
+            .get(mount_id)
+            .map(|repo_tree| !repo_tree.get(path).is_empty())
+            .unwrap_or(false)
+}
@@ -0,0 +1,346 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, RwLock},
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    accounts::selectors as accounts_selectors,
+    cipher::Cipher,
+    remote::models,
+    secure_storage::{errors::SecureStorageError, SecureStorageService},
+    store,
+    types::{MountId, RemotePath},
+};
+
+use super::{
+    disk_cache::MetadataDiskCache,
+    selectors,
+    state::{CachedRepos, MetadataCacheKey},
+};
+
+struct CacheCipher {
+    user_id: String,
+    cipher: Cipher,
+}
+
+impl CacheCipher {
+    fn new(key: &MetadataCacheKey) -> Result<Self, String> {
+        Ok(Self {
+            user_id: key.user_id.clone(),
+            cipher: Cipher::with_random_keys(&key.keys)?,
+        })
+    }
+}
+
+/// Caches the repos and the listings inside repos so that the last known
+/// state can be shown before the network responds. Entries are scoped to the
+/// account and encrypted with a random per-account key from the secure
+/// storage.
+pub struct MetadataCacheService {
+    secure_storage_service: Arc<SecureStorageService>,
+    store: Arc<store::Store>,
+
+    disk_cache: RwLock<Option<Box<dyn MetadataDiskCache + Send + Sync>>>,
+    cache_cipher: Mutex<Option<Arc<CacheCipher>>>,
+    user_subscription_id: u32,
+}
+
+impl MetadataCacheService {
+    pub fn new(
+        secure_storage_service: Arc<SecureStorageService>,
+        store: Arc<store::Store>,
+    ) -> Arc<Self> {
+        let user_subscription_id = store.get_next_id();
+
+        let metadata_cache_service = Arc::new(Self {
+            secure_storage_service,
+            store: store.clone(),
+
+            disk_cache: RwLock::new(None),
+            cache_cipher: Mutex::new(None),
+            user_subscription_id,
+        });
+
+        let user_subscription_service = Arc::downgrade(&metadata_cache_service);
+        let user_subscription_store = store.clone();
+
+        store.on(
+            user_subscription_id,
+            &[store::Event::User],
+            Box::new(move |_, add_side_effect| {
+                let user_id = match user_subscription_store
+                    .with_state(|state| state.user.user.as_ref().map(|user| user.id.clone()))
+                {
+                    Some(user_id) => user_id,
+                    None => return,
+                };
+
+                if let Some(service) = user_subscription_service.upgrade() {
+                    add_side_effect(Box::new(move || {
+                        service.user_loaded(&user_id);
+                    }));
+                }
+            }),
+        );
+
+        metadata_cache_service
+    }
+
+    pub fn set_disk_cache(&self, disk_cache: Option<Box<dyn MetadataDiskCache + Send + Sync>>) {
+        *self.disk_cache.write().unwrap() = disk_cache;
+    }
+
+    fn load_key(&self, user_id: &str) -> Result<Option<MetadataCacheKey>, SecureStorageError> {
+        self.secure_storage_service
+            .get::<MetadataCacheKey>(&selectors::get_key_storage_key(user_id))
+    }
+
+    /// Returns the cipher of the active account.
+    fn get_cache_cipher(&self) -> Option<Arc<CacheCipher>> {
+        let user_id = self.store.with_state(|state| {
+            accounts_selectors::select_active_user_id(state).map(str::to_owned)
+        })?;
+
+        let mut cache_cipher = self.cache_cipher.lock().unwrap();
+
+        if let Some(cache_cipher) = cache_cipher.as_ref() {
+            if cache_cipher.user_id == user_id {
+                return Some(cache_cipher.clone());
+            }
+        }
+
+        // the key is loaded lazily because the secure storage can be filled
+        // after the vault is created
+        let key = match self.load_key(&user_id) {
+            Ok(key) => key?,
+            Err(err) => {
+                log::warn!("Metadata cache key load failed: {}", err);
+
+                return None;
+            }
+        };
+
+        match CacheCipher::new(&key) {
+            Ok(new_cache_cipher) => {
+                let new_cache_cipher = Arc::new(new_cache_cipher);
+
+                *cache_cipher = Some(new_cache_cipher.clone());
+
+                Some(new_cache_cipher)
+            }
+            Err(err) => {
+                log::warn!("Metadata cache key invalid: {}", err);
+
+                None
+            }
+        }
+    }
+
+    /// A user without a key gets a new one. Keys of other accounts are kept.
+    fn user_loaded(&self, user_id: &str) {
+        match self.load_key(user_id) {
+            Ok(Some(_)) => return,
+            Ok(None) => {}
+            Err(err) => {
+                log::warn!("Metadata cache key load failed: {}", err);
+
+                return;
+            }
+        }
+
+        let key = match Cipher::generate_random_keys() {
+            Ok(keys) => MetadataCacheKey {
+                user_id: user_id.to_owned(),
+                keys,
+            },
+            Err(err) => {
+                log::warn!("Metadata cache key generate failed: {}", err);
+
+                return;
+            }
+        };
+
+        if let Err(err) = CacheCipher::new(&key) {
+            log::warn!("Metadata cache key invalid: {}", err);
+
+            return;
+        }
+
+        if let Err(err) = self
+            .secure_storage_service
+            .set(&selectors::get_key_storage_key(user_id), &key)
+        {
+            log::warn!("Metadata cache key save failed: {}", err);
+        }
+    }
+
+    fn get<T>(&self, get_key: impl FnOnce(&str) -> String) -> Option<T>
+    where
+        T: DeserializeOwned,
+    {
+        let cache_cipher = self.get_cache_cipher()?;
+
+        let encrypted_bytes = match self
+            .disk_cache
+            .read()
+            .unwrap()
+            .as_ref()?
+            .get(&get_key(&cache_cipher.user_id))
+        {
+            Ok(value) => value?,
+            Err(err) => {
+                log::warn!("Metadata disk cache get failed: {}", err);
+
+                return None;
+            }
+        };
+
+        let bytes = match cache_cipher.cipher.decrypt_vec(&encrypted_bytes) {
+            Ok(bytes) => bytes,
+            Err(err) => {
+                log::warn!("Metadata disk cache decrypt failed: {}", err);
+
+                return None;
+            }
+        };
+
+        match serde_json::from_slice(&bytes) {
+            Ok(value) => Some(value),
+            Err(err) => {
+                log::warn!("Metadata disk cache deserialize failed: {}", err);
+
+                None
+            }
+        }
+    }
+
+    fn set<T>(&self, get_key: impl FnOnce(&str) -> String, value: &T)
+    where
+        T: Serialize,
+    {
+        let cache_cipher = match self.get_cache_cipher() {
+            Some(cache_cipher) => cache_cipher,
+            None => return,
+        };
+
+        let disk_cache = self.disk_cache.read().unwrap();
+
+        let disk_cache = match disk_cache.as_ref() {
+            Some(disk_cache) => disk_cache,
+            None => return,
+        };
+
+        let encrypted_bytes = match serde_json::to_vec(value)
+            .map_err(|err| err.to_string())
+            .and_then(|bytes| {
+                cache_cipher
+                    .cipher
+                    .encrypt_vec(&bytes)
+                    .map_err(|err| err.to_string())
+            }) {
+            Ok(encrypted_bytes) => encrypted_bytes,
+            Err(err) => {
+                log::warn!("Metadata disk cache encrypt failed: {}", err);
+
+                return;
+            }
+        };
+
+        if let Err(err) = disk_cache.set(&get_key(&cache_cipher.user_id), &encrypted_bytes) {
+            log::warn!("Metadata disk cache set failed: {}", err);
+        }
+    }
+
+    fn disk_cache_clear(&self) {
+        if let Some(disk_cache) = self.disk_cache.read().unwrap().as_ref() {
+            if let Err(err) = disk_cache.clear() {
+                log::warn!("Metadata disk cache clear failed: {}", err);
+            }
+        }
+    }
+
+    pub fn get_repos(&self) -> Option<CachedRepos> {
+        self.get(selectors::get_repos_cache_key)
+    }
+
+    pub fn set_repos(
+        &self,
+        repos: &[models::VaultRepo],
+        mount_permissions: &HashMap<MountId, models::MountPermissions>,
+    ) {
+        self.set(
+            selectors::get_repos_cache_key,
+            &CachedRepos {
+                repos: repos.to_vec(),
+                mount_permissions: mount_permissions.clone(),
+            },
+        )
+    }
+
+    pub fn get_bundle(&self, mount_id: &MountId, path: &RemotePath) -> Option<models::Bundle> {
+        if !self
+            .store
+            .with_state(|state| selectors::select_is_repo_path(state, mount_id, path))
+        {
+            return None;
+        }
+
+        self.get(|user_id| selectors::get_files_cache_key(user_id, mount_id, path))
+    }
+
+    pub fn set_bundle(&self, mount_id: &MountId, path: &RemotePath, bundle: &models::Bundle) {
+        if !self
+            .store
+            .with_state(|state| selectors::select_is_repo_path(state, mount_id, path))
+        {
+            return;
+        }
+
+        self.set(
+            |user_id| selectors::get_files_cache_key(user_id, mount_id, path),
+            bundle,
+        )
+    }
+
+    /// Removes the key and the entries of the account.
+    pub fn remove_account(&self, user_id: &str) {
+        let mut cache_cipher = self.cache_cipher.lock().unwrap();
+
+        if cache_cipher
+            .as_ref()
+            .map(|cache_cipher| cache_cipher.user_id == user_id)
+            .unwrap_or(false)
+        {
+            *cache_cipher = None;
+        }
+
+        if let Err(err) = self
+            .secure_storage_service
+            .remove(&selectors::get_key_storage_key(user_id))
+        {
+            log::warn!("Metadata cache key remove failed: {}", err);
+        }
+
+        if let Some(disk_cache) = self.disk_cache.read().unwrap().as_ref() {
+            if let Err(err) =
+                disk_cache.remove_prefix(&selectors::get_account_cache_key_prefix(user_id))
+            {
+                log::warn!("Metadata disk cache remove account failed: {}", err);
+            }
+        }
+    }
+
+    pub fn clear(&self) {
+        *self.cache_cipher.lock().unwrap() = None;
+
+        self.disk_cache_clear();
+    }
+}
+
+impl Drop for MetadataCacheService {
+    fn drop(&mut self) {
+        self.store.remove_listener(self.user_subscription_id);
+    }
+}
@@ -0,0 +1,25 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::{remote::models, types::MountId};
+
+/// Stored in the secure storage so it is removed on logout together with the
+/// tokens.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetadataCacheKey {
+    #[serde(rename = "u")]
+    pub user_id: String,
+    /// Base64 encoded random cipher keys.
+    #[serde(rename = "k")]
+    pub keys: Zeroizing<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct CachedRepos {
+    #[serde(rename = "r")]
+    pub repos: Vec<models::VaultRepo>,
+    #[serde(rename = "m")]
+    pub mount_permissions: HashMap<MountId, models::MountPermissions>,
+}
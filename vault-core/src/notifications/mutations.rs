@@ -1,11 +1,20 @@
-use crate::{notifications::state::Notification, store};
-
-pub fn show(state: &mut store::State, notify: &store::Notify, message: String) {
+use crate::{notifications::state::Notification, store, user_error::ErrorCode};
+
+pub fn show(
+    state: &mut store::State,
+    notify: &store::Notify,
+    message: String,
+    error_code: Option<ErrorCode>,
+) {
     log::debug!("NotificationsService show: {}", message);
 
     let id = state.notifications.next_id.next();
 
-    let notification = Notification { id, message };
+    let notification = Notification {
+        id,
+        message,
+        error_code,
+    };
 
     state.notifications.notifications.insert(id, notification);
 
@@ -1,6 +1,6 @@
 use std::{sync::Arc, time::Duration};
 
-use crate::{runtime, store};
+use crate::{locale, runtime, store, user_error::UserError};
 
 use super::mutations;
 
@@ -16,7 +16,16 @@ impl NotificationsService {
 
     pub fn show(&self, message: String) {
         self.store.mutate(|state, notify, _, _| {
-            mutations::show(state, notify, message);
+            mutations::show(state, notify, message, None);
+        });
+    }
+
+    pub fn show_error(&self, error: &dyn UserError) {
+        let message = locale::with_store_locale(&self.store, || error.user_error());
+        let error_code = error.error_code();
+
+        self.store.mutate(|state, notify, _, _| {
+            mutations::show(state, notify, message, Some(error_code));
         });
     }
 
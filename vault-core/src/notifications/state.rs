@@ -1,11 +1,12 @@
 use std::collections::HashMap;
 
-use crate::store::NextId;
+use crate::{store::NextId, user_error::ErrorCode};
 
 #[derive(Debug, Clone, Default)]
 pub struct Notification {
     pub id: u32,
     pub message: String,
+    pub error_code: Option<ErrorCode>,
 }
 
 #[derive(Debug, Clone, Default)]
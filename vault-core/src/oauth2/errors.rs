@@ -1,6 +1,10 @@
 use thiserror::Error;
 
-use crate::{http, secure_storage::errors::SecureStorageError, user_error::UserError};
+use crate::{
+    http, locale,
+    secure_storage::errors::SecureStorageError,
+    user_error::{ErrorCode, UserError},
+};
 
 #[derive(Error, Debug, Clone, PartialEq)]
 pub enum OAuth2Error {
@@ -10,6 +14,16 @@ pub enum OAuth2Error {
     InvalidOAuth2State,
     #[error("{0}")]
     InvalidGrant(String),
+    #[error("authorization pending")]
+    AuthorizationPending,
+    #[error("slow down")]
+    SlowDown,
+    #[error("access denied")]
+    AccessDenied,
+    #[error("device code expired")]
+    DeviceCodeExpired,
+    #[error("canceled")]
+    Canceled,
     #[error("{0}")]
     HttpError(#[from] http::HttpError),
     #[error("storage error: {0}")]
@@ -21,12 +35,42 @@ pub enum OAuth2Error {
 impl UserError for OAuth2Error {
     fn user_error(&self) -> String {
         match self {
-            Self::InvalidOAuth2Token(err) => format!("Invalid OAuth 2 token: {}", err),
-            Self::InvalidOAuth2State => "Invalid authentication state. Please try again.".into(),
-            Self::InvalidGrant(err) => format!("Invalid authentication permissions: {}", err),
+            Self::InvalidOAuth2Token(err) => locale::message_args(
+                "error-invalid-oauth2-token",
+                &[("error", err.as_str().into())],
+            ),
+            Self::InvalidOAuth2State => locale::message("error-invalid-oauth2-state"),
+            Self::InvalidGrant(err) => {
+                locale::message_args("error-invalid-grant", &[("error", err.as_str().into())])
+            }
+            Self::AuthorizationPending => locale::message("error-authorization-pending"),
+            Self::SlowDown => locale::message("error-slow-down"),
+            Self::AccessDenied => locale::message("error-access-denied"),
+            Self::DeviceCodeExpired => locale::message("error-device-code-expired"),
+            Self::Canceled => self.to_string(),
             Self::HttpError(err) => err.user_error(),
-            Self::StorageError(err) => format!("Storage error: {}", err),
-            Self::Unknown(err) => format!("Unknown error: {}", err),
+            Self::StorageError(err) => {
+                locale::message_args("error-storage", &[("error", err.to_string().into())])
+            }
+            Self::Unknown(err) => {
+                locale::message_args("error-unknown", &[("error", err.as_str().into())])
+            }
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::InvalidOAuth2Token(_) => ErrorCode::OAuth2InvalidToken,
+            Self::InvalidOAuth2State => ErrorCode::OAuth2InvalidState,
+            Self::InvalidGrant(_) => ErrorCode::OAuth2InvalidGrant,
+            Self::AuthorizationPending => ErrorCode::OAuth2AuthorizationPending,
+            Self::SlowDown => ErrorCode::OAuth2SlowDown,
+            Self::AccessDenied => ErrorCode::OAuth2AccessDenied,
+            Self::DeviceCodeExpired => ErrorCode::OAuth2DeviceCodeExpired,
+            Self::Canceled => ErrorCode::Canceled,
+            Self::HttpError(err) => err.error_code(),
+            Self::StorageError(_) => ErrorCode::Storage,
+            Self::Unknown(_) => ErrorCode::OAuth2Unknown,
         }
     }
 }
@@ -69,6 +69,12 @@ pub fn logging_in(state: &mut store::State, notify: &store::Notify) {
     };
 }
 
+pub fn login_canceled(state: &mut store::State, notify: &store::Notify) {
+    notify(store::Event::Auth);
+
+    state.oauth2.status = Status::Initial;
+}
+
 pub fn logged_in(state: &mut store::State, notify: &store::Notify, token: OAuth2Token) {
     notify(store::Event::Auth);
 
@@ -1,10 +1,14 @@
 use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use data_encoding::BASE64URL_NOPAD;
-use futures::lock::Mutex as AsyncMutex;
+use futures::{
+    lock::Mutex as AsyncMutex,
+    stream::{AbortRegistration, Abortable, Aborted},
+};
 use http::{header::CONTENT_TYPE, HeaderMap, HeaderValue};
 use rand_core::{OsRng, RngCore};
 use serde::Deserialize;
+use sha2::{Digest, Sha256};
 use url::Url;
 
 use crate::{
@@ -18,17 +22,22 @@ use crate::{
 use super::{
     errors::OAuth2Error,
     mutations, selectors,
-    state::{FinishFlowResult, OAuth2Token},
+    state::{DeviceAuthorization, FinishFlowResult, OAuth2FlowState, OAuth2Token},
 };
 
 pub const TOKEN_STORAGE_KEY: &str = "vaultOAuth2Token";
 pub const STATE_STORAGE_KEY: &str = "vaultOAuth2State";
 
+const DEVICE_CODE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:device_code";
+const DEFAULT_DEVICE_INTERVAL: i32 = 5;
+
 pub struct OAuth2Config {
     pub base_url: String,
     pub auth_base_url: String,
     pub client_id: String,
-    pub client_secret: String,
+    /// Public clients (native, headless) do not have a secret and rely on
+    /// PKCE instead.
+    pub client_secret: Option<String>,
     pub redirect_uri: String,
 }
 
@@ -39,6 +48,21 @@ struct RawOAuth2Token {
     pub expires_in: i32,
 }
 
+#[derive(Deserialize)]
+struct RawDeviceAuthorization {
+    pub device_code: String,
+    pub user_code: String,
+    pub verification_uri: String,
+    pub verification_uri_complete: Option<String>,
+    pub expires_in: i32,
+    pub interval: Option<i32>,
+}
+
+#[derive(Deserialize)]
+struct RawOAuth2Error {
+    pub error: String,
+}
+
 pub struct OAuth2Service {
     config: OAuth2Config,
     secure_storage_service: Arc<SecureStorageService>,
@@ -133,6 +157,66 @@ impl OAuth2Service {
         Ok(Some(token))
     }
 
+    /// Same as `get_authorization` but for a token that is not loaded in the
+    /// state, e.g. the token of an account that is not active.
+    pub async fn get_stored_authorization(
+        &self,
+        storage_key: &str,
+        force_refresh_token: bool,
+    ) -> Result<String, AuthError> {
+        let token = match self
+            .get_stored_token(storage_key, force_refresh_token)
+            .await
+        {
+            Ok(Some(token)) => token,
+            Ok(None) => {
+                return Err(AuthError::Unauthenticated);
+            }
+            Err(err) => return Err(AuthError::OAuth2Error(err)),
+        };
+
+        Ok(format!("Bearer {}", token.access_token))
+    }
+
+    pub async fn get_stored_token(
+        &self,
+        storage_key: &str,
+        force_refresh_token: bool,
+    ) -> Result<Option<OAuth2Token>, OAuth2Error> {
+        let refresh_token_guard = self.refresh_token_mutex.lock().await;
+
+        let mut token = match self
+            .secure_storage_service
+            .get::<OAuth2Token>(storage_key)?
+        {
+            Some(token) => token,
+            None => {
+                return Ok(None);
+            }
+        };
+
+        if self.is_token_expired(&token) || force_refresh_token {
+            token = self.refresh_token(&token.refresh_token).await?;
+
+            self.secure_storage_service.set(storage_key, &token)?;
+        }
+
+        drop(refresh_token_guard);
+
+        Ok(Some(token))
+    }
+
+    /// Replaces the current token, e.g. when the active account is switched.
+    pub fn set_token(&self, token: OAuth2Token) -> Result<(), OAuth2Error> {
+        self.save_token(&token)?;
+
+        self.store.mutate(|state, notify, _, _| {
+            mutations::logged_in(state, notify, token);
+        });
+
+        Ok(())
+    }
+
     pub fn start_login_flow(&self) -> Result<String, OAuth2Error> {
         let flow_state = self.generate_flow_state()?;
 
@@ -142,7 +226,108 @@ impl OAuth2Service {
     pub fn start_logout_flow(&self) -> Result<String, OAuth2Error> {
         let flow_state = self.generate_flow_state()?;
 
-        Ok(self.get_logout_url(&flow_state))
+        Ok(self.get_logout_url(&flow_state.state))
+    }
+
+    pub async fn start_device_flow(&self) -> Result<DeviceAuthorization, OAuth2Error> {
+        let res_bytes = self
+            .post_form(
+                self.get_device_code_url(),
+                vec![("client_id", &self.config.client_id), ("scope", "public")],
+            )
+            .await?;
+
+        let raw: RawDeviceAuthorization = serde_json::from_slice(&res_bytes).map_err(|err| {
+            OAuth2Error::Unknown(format!("invalid device authorization: {}", err))
+        })?;
+
+        Ok(DeviceAuthorization {
+            device_code: raw.device_code,
+            user_code: raw.user_code,
+            verification_uri: raw.verification_uri,
+            verification_uri_complete: raw.verification_uri_complete,
+            expires_at: self.runtime.now() + Duration::from_secs(raw.expires_in as u64),
+            interval: Duration::from_secs(
+                raw.interval.unwrap_or(DEFAULT_DEVICE_INTERVAL).max(0) as u64
+            ),
+        })
+    }
+
+    /// Polls the token endpoint until the user approves or denies the device
+    /// authorization or the device code expires. Polling stops with
+    /// `OAuth2Error::Canceled` when the abort handle of `abort_registration`
+    /// is aborted.
+    pub async fn finish_device_flow(
+        &self,
+        device_authorization: &DeviceAuthorization,
+        abort_registration: AbortRegistration,
+    ) -> Result<(), OAuth2Error> {
+        self.store.mutate(|state, notify, _, _| {
+            mutations::logging_in(state, notify);
+        });
+
+        let token = match Abortable::new(
+            self.poll_device_token(device_authorization),
+            abort_registration,
+        )
+        .await
+        {
+            Ok(Ok(token)) => token,
+            Ok(Err(err)) => {
+                self.handle_error(err.clone());
+
+                return Err(err);
+            }
+            Err(Aborted) => {
+                self.store.mutate(|state, notify, _, _| {
+                    mutations::login_canceled(state, notify);
+                });
+
+                return Err(OAuth2Error::Canceled);
+            }
+        };
+
+        if let Err(err) = self.save_token(&token) {
+            self.handle_error(err.clone().into());
+
+            return Err(err.into());
+        }
+
+        self.store.mutate(|state, notify, _, _| {
+            mutations::logged_in(state, notify, token);
+        });
+
+        Ok(())
+    }
+
+    async fn poll_device_token(
+        &self,
+        device_authorization: &DeviceAuthorization,
+    ) -> Result<OAuth2Token, OAuth2Error> {
+        let mut interval = device_authorization.interval;
+
+        loop {
+            if self.runtime.now() >= device_authorization.expires_at {
+                return Err(OAuth2Error::DeviceCodeExpired);
+            }
+
+            self.runtime.sleep(interval).await;
+
+            match self
+                .exchange_token(
+                    DEVICE_CODE_GRANT_TYPE,
+                    vec![("device_code", &device_authorization.device_code)],
+                )
+                .await
+            {
+                Ok(token) => return Ok(token),
+                Err(OAuth2Error::AuthorizationPending) => {}
+                Err(OAuth2Error::SlowDown) => {
+                    interval += Duration::from_secs(5);
+                }
+                Err(err) => return Err(err),
+            }
+        }
     }
 
     pub async fn finish_flow_url(&self, url: &str) -> Result<FinishFlowResult, OAuth2Error> {
@@ -167,13 +352,16 @@ impl OAuth2Service {
             }
         };
 
-        if !self.is_flow_state_ok(state) {
-            let err = OAuth2Error::InvalidOAuth2State;
+        let code_verifier = match self.load_flow_state(state) {
+            Some(flow_state) => flow_state.code_verifier,
+            None => {
+                let err = OAuth2Error::InvalidOAuth2State;
 
-            self.handle_error(err.clone());
+                self.handle_error(err.clone());
 
-            return Err(err);
-        }
+                return Err(err);
+            }
+        };
 
         if query.get("loggedout").filter(|&x| x == "true").is_some() {
             self.finish_logout_flow()?;
@@ -192,7 +380,7 @@ impl OAuth2Service {
                 }
             };
 
-            self.finish_login_flow(&code).await?;
+            self.finish_login_flow(&code, &code_verifier).await?;
 
             Ok(FinishFlowResult::LoggedIn)
         }
@@ -220,13 +408,20 @@ impl OAuth2Service {
         });
     }
 
-    async fn finish_login_flow(&self, code: &str) -> Result<(), OAuth2Error> {
+    async fn finish_login_flow(&self, code: &str, code_verifier: &str) -> Result<(), OAuth2Error> {
         self.store.mutate(|state, notify, _, _| {
             mutations::logging_in(state, notify);
         });
 
         let token = match self
-            .exchange_token("authorization_code", "code", code)
+            .exchange_token(
+                "authorization_code",
+                vec![
+                    ("code", code),
+                    ("redirect_uri", &self.config.redirect_uri),
+                    ("code_verifier", code_verifier),
+                ],
+            )
             .await
         {
             Ok(token) => token,
@@ -273,20 +468,24 @@ impl OAuth2Service {
         self.logout()
     }
 
-    fn is_flow_state_ok(&self, state: &str) -> bool {
+    fn load_flow_state(&self, state: &str) -> Option<OAuth2FlowState> {
         match self.load_state() {
-            Ok(Some(saved_state)) => state == saved_state,
-            _ => false,
+            Ok(Some(flow_state)) if flow_state.state == state => Some(flow_state),
+            _ => None,
         }
     }
 
-    fn get_login_url(&self, state: &str) -> String {
+    fn get_login_url(&self, flow_state: &OAuth2FlowState) -> String {
+        let code_challenge = get_code_challenge(&flow_state.code_verifier);
+
         let mut params: HashMap<&str, &str> = HashMap::new();
         params.insert("client_id", &self.config.client_id);
         params.insert("redirect_uri", &self.config.redirect_uri);
-        params.insert("state", state);
+        params.insert("state", &flow_state.state);
         params.insert("response_type", "code");
         params.insert("scope", "public");
+        params.insert("code_challenge", &code_challenge);
+        params.insert("code_challenge_method", "S256");
 
         let mut auth_url =
             Url::parse(&format!("{}/oauth2/auth", &self.config.auth_base_url)).unwrap();
@@ -312,21 +511,30 @@ impl OAuth2Service {
         format!("{}/oauth2/token", &self.config.base_url)
     }
 
-    fn generate_flow_state(&self) -> Result<String, OAuth2Error> {
-        let mut state = vec![0; 16];
-
-        (&mut OsRng).try_fill_bytes(&mut state).unwrap();
+    fn get_device_code_url(&self) -> String {
+        format!("{}/oauth2/device/code", &self.config.base_url)
+    }
 
-        let state = BASE64URL_NOPAD.encode(&state);
+    fn generate_flow_state(&self) -> Result<OAuth2FlowState, OAuth2Error> {
+        let flow_state = OAuth2FlowState {
+            state: generate_random_string(16),
+            code_verifier: generate_random_string(32),
+        };
 
-        self.save_state(&state)?;
+        self.save_state(&flow_state)?;
 
-        Ok(state)
+        Ok(flow_state)
     }
 
     async fn refresh_token(&self, refresh_token: &str) -> Result<OAuth2Token, OAuth2Error> {
-        self.exchange_token("refresh_token", "refresh_token", refresh_token)
-            .await
+        self.exchange_token(
+            "refresh_token",
+            vec![
+                ("refresh_token", refresh_token),
+                ("redirect_uri", &self.config.redirect_uri),
+            ],
+        )
+        .await
     }
 
     fn is_token_expired(&self, token: &OAuth2Token) -> bool {
@@ -337,16 +545,36 @@ impl OAuth2Service {
     async fn exchange_token(
         &self,
         grant_type: &str,
-        key: &str,
-        value: &str,
+        grant_params: Vec<(&str, &str)>,
     ) -> Result<OAuth2Token, OAuth2Error> {
-        let mut params = HashMap::new();
-        params.insert("grant_type", grant_type);
-        params.insert("client_id", &self.config.client_id);
-        params.insert("client_secret", &self.config.client_secret);
-        params.insert("redirect_uri", &self.config.redirect_uri);
-        params.insert(key, value);
+        let mut params = vec![
+            ("grant_type", grant_type),
+            ("client_id", &self.config.client_id),
+        ];
+        if let Some(client_secret) = &self.config.client_secret {
+            params.push(("client_secret", client_secret));
+        }
+        params.extend(grant_params);
+
+        let res_bytes = self.post_form(self.get_token_url(), params).await?;
+
+        let raw_token: RawOAuth2Token = serde_json::from_slice(&res_bytes)
+            .map_err(|err| OAuth2Error::InvalidOAuth2Token(err.to_string()))?;
+
+        let token = OAuth2Token {
+            access_token: raw_token.access_token,
+            refresh_token: raw_token.refresh_token,
+            expires_at: self.runtime.now() + Duration::from_secs(raw_token.expires_in as u64),
+        };
+
+        Ok(token)
+    }
 
+    async fn post_form(
+        &self,
+        url: String,
+        params: Vec<(&str, &str)>,
+    ) -> Result<Vec<u8>, OAuth2Error> {
         let body = serde_urlencoded::to_string(params).unwrap();
 
         let mut headers = HeaderMap::new();
@@ -359,7 +587,7 @@ impl OAuth2Service {
             .http_client
             .request(HttpRequest {
                 method: String::from("POST"),
-                url: self.get_token_url(),
+                url,
                 headers,
                 body: Some(HttpRequestBody::Bytes(body.into_bytes())),
                 ..Default::default()
@@ -376,24 +604,26 @@ impl OAuth2Service {
                 return Err(OAuth2Error::InvalidGrant(str));
             }
 
+            if status_code == 400 {
+                if let Ok(raw_error) = serde_json::from_str::<RawOAuth2Error>(&str) {
+                    match raw_error.error.as_str() {
+                        "authorization_pending" => return Err(OAuth2Error::AuthorizationPending),
+                        "slow_down" => return Err(OAuth2Error::SlowDown),
+                        "access_denied" => return Err(OAuth2Error::AccessDenied),
+                        "expired_token" => return Err(OAuth2Error::DeviceCodeExpired),
+                        "invalid_grant" => return Err(OAuth2Error::InvalidGrant(str)),
+                        _ => {}
+                    }
+                }
+            }
+
             return Err(OAuth2Error::HttpError(HttpError::ResponseError(format!(
                 "unexpected status: {}: {}",
                 status_code, &str,
             ))));
         }
 
-        let res_bytes = res.bytes().await?;
-
-        let raw_token: RawOAuth2Token = serde_json::from_slice(&res_bytes)
-            .map_err(|err| OAuth2Error::InvalidOAuth2Token(err.to_string()))?;
-
-        let token = OAuth2Token {
-            access_token: raw_token.access_token,
-            refresh_token: raw_token.refresh_token,
-            expires_at: self.runtime.now() + Duration::from_secs(raw_token.expires_in as u64),
-        };
-
-        Ok(token)
+        Ok(res.bytes().await?)
     }
 
     fn load_token(&self) -> Result<Option<OAuth2Token>, SecureStorageError> {
@@ -408,15 +638,28 @@ impl OAuth2Service {
         self.secure_storage_service.remove(TOKEN_STORAGE_KEY)
     }
 
-    fn load_state(&self) -> Result<Option<String>, SecureStorageError> {
+    fn load_state(&self) -> Result<Option<OAuth2FlowState>, SecureStorageError> {
         self.secure_storage_service.get(STATE_STORAGE_KEY)
     }
 
-    fn save_state(&self, state: &str) -> Result<(), SecureStorageError> {
-        self.secure_storage_service.set(STATE_STORAGE_KEY, &state)
+    fn save_state(&self, flow_state: &OAuth2FlowState) -> Result<(), SecureStorageError> {
+        self.secure_storage_service
+            .set(STATE_STORAGE_KEY, flow_state)
     }
 
     fn remove_state(&self) -> Result<(), SecureStorageError> {
         self.secure_storage_service.remove(STATE_STORAGE_KEY)
     }
 }
+
+fn generate_random_string(len: usize) -> String {
+    let mut bytes = vec![0; len];
+
+    (&mut OsRng).try_fill_bytes(&mut bytes).unwrap();
+
+    BASE64URL_NOPAD.encode(&bytes)
+}
+
+pub fn get_code_challenge(code_verifier: &str) -> String {
+    BASE64URL_NOPAD.encode(&Sha256::digest(code_verifier.as_bytes()))
+}
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
 
 use crate::{common::state::Status, types::TimeMillis};
@@ -11,6 +13,26 @@ pub struct OAuth2Token {
     pub expires_at: TimeMillis,
 }
 
+/// Saved between starting and finishing the login or logout flow.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OAuth2FlowState {
+    pub state: String,
+    /// PKCE code verifier, the login URL only contains its SHA-256 challenge.
+    pub code_verifier: String,
+}
+
+/// Device authorization grant for clients without a browser. The user opens
+/// `verification_uri` on another device and enters `user_code`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeviceAuthorization {
+    pub device_code: String,
+    pub user_code: String,
+    pub verification_uri: String,
+    pub verification_uri_complete: Option<String>,
+    pub expires_at: TimeMillis,
+    pub interval: Duration,
+}
+
 #[derive(Debug, Clone)]
 pub enum FinishFlowResult {
     LoggedIn,
@@ -0,0 +1,26 @@
+use thiserror::Error;
+
+use crate::{
+    lifecycle::errors::LogoutError,
+    user_error::{ErrorCode, UserError},
+};
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum PanicLockError {
+    #[error("{0}")]
+    LogoutError(LogoutError),
+}
+
+impl UserError for PanicLockError {
+    fn user_error(&self) -> String {
+        match self {
+            Self::LogoutError(err) => err.user_error(),
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::LogoutError(err) => err.error_code(),
+        }
+    }
+}
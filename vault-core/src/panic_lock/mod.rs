@@ -0,0 +1,4 @@
+pub mod errors;
+pub mod service;
+
+pub use self::service::PanicLockService;
@@ -0,0 +1,55 @@
+use std::sync::Arc;
+
+use crate::{
+    audit_log::state::RepoLockReason, lifecycle::LifecycleService,
+    repo_files_details::RepoFilesDetailsService, repos::ReposService, transfers::TransfersService,
+};
+
+use super::errors::PanicLockError;
+
+pub struct PanicLockService {
+    repos_service: Arc<ReposService>,
+    transfers_service: Arc<TransfersService>,
+    repo_files_details_service: Arc<RepoFilesDetailsService>,
+    lifecycle_service: Arc<LifecycleService>,
+}
+
+impl PanicLockService {
+    pub fn new(
+        repos_service: Arc<ReposService>,
+        transfers_service: Arc<TransfersService>,
+        repo_files_details_service: Arc<RepoFilesDetailsService>,
+        lifecycle_service: Arc<LifecycleService>,
+    ) -> Self {
+        Self {
+            repos_service,
+            transfers_service,
+            repo_files_details_service,
+            lifecycle_service,
+        }
+    }
+
+    /// Locks all repos and drops all decrypted data immediately. Unsaved
+    /// changes are discarded without asking.
+    pub fn panic_lock(&self, wipe_secure_storage: bool) -> Result<(), PanicLockError> {
+        // transfers are aborted first so that no plaintext is read or written
+        // while the rest is being torn down
+        self.transfers_service.clone().abort_all();
+
+        self.repo_files_details_service.destroy_all();
+
+        // repo files, thumbnails, duplicates and versions settings of locked
+        // repos are removed by their repos mutation handlers
+        self.repos_service.lock_all_repos(RepoLockReason::Panic);
+
+        if wipe_secure_storage {
+            // logout clears the metadata cache and secure storage and resets
+            // the user state
+            self.lifecycle_service
+                .logout()
+                .map_err(PanicLockError::LogoutError)?;
+        }
+
+        Ok(())
+    }
+}
@@ -4,6 +4,7 @@ use ini::Ini;
 use slug::slugify;
 use thiserror::Error;
 use vault_crypto::rclone_obscure::{obscure, reveal};
+use zeroize::Zeroizing;
 
 use crate::utils::path_utils::normalize_path;
 
@@ -11,7 +12,7 @@ use crate::utils::path_utils::normalize_path;
 pub struct Config {
     pub name: Option<String>,
     pub path: String,
-    pub password: String,
+    pub password: Zeroizing<String>,
     pub salt: Option<String>,
 }
 
@@ -69,12 +70,12 @@ pub fn parse_config(config_str: &str) -> Result<Config, ParseConfigError> {
     Ok(Config {
         name: section_name.map(|name| name.to_string()),
         path: path.to_owned(),
-        password: password.to_string(),
+        password: Zeroizing::new(password),
         salt,
     })
 }
 
-pub fn generate_config(config: &Config) -> String {
+pub fn generate_config(config: &Config) -> Zeroizing<String> {
     let mut i = Ini::new();
 
     let section_name = slugify(config.name.as_deref().unwrap_or("vault"));
@@ -97,7 +98,7 @@ pub fn generate_config(config: &Config) -> String {
 
     i.write_to(&mut out).unwrap();
 
-    String::from_utf8(out).unwrap()
+    Zeroizing::new(String::from_utf8(out).unwrap())
 }
 
 #[cfg(test)]
@@ -114,7 +115,7 @@ pub mod tests {
             Config {
                 name: Some(String::from("vault-name")),
                 path: String::from("/Vault"),
-                password: String::from("testpassword"),
+                password: String::from("testpassword").into(),
                 salt: Some(String::from("testsalt")),
             }
         );
@@ -125,7 +126,7 @@ pub mod tests {
             Config {
                 name: Some(String::from("vault-name")),
                 path: String::from("/Vault"),
-                password: String::from("testpassword"),
+                password: String::from("testpassword").into(),
                 salt: None,
             }
         );
@@ -136,7 +137,7 @@ pub mod tests {
             Config {
                 name: Some(String::from("vault-name")),
                 path: String::from("/Vault"),
-                password: String::from("testpassword"),
+                password: String::from("testpassword").into(),
                 salt: None,
             }
         );
@@ -147,7 +148,7 @@ pub mod tests {
             Config {
                 name: Some(String::from("vault-name")),
                 path: String::from("/"),
-                password: String::from("testpassword"),
+                password: String::from("testpassword").into(),
                 salt: None,
             }
         );
@@ -158,7 +159,7 @@ pub mod tests {
             Config {
                 name: Some(String::from("vault-name")),
                 path: String::from("/"),
-                password: String::from("testpassword"),
+                password: String::from("testpassword").into(),
                 salt: None,
             }
         );
@@ -216,7 +217,7 @@ pub mod tests {
         let config = generate_config(&Config {
             name: Some(String::from("Vault name")),
             path: String::from("/Vault"),
-            password: String::from("testpassword"),
+            password: String::from("testpassword").into(),
             salt: Some(String::from("testsalt")),
         });
         let expected =
@@ -226,7 +227,7 @@ pub mod tests {
         let config = generate_config(&Config {
             name: None,
             path: String::from("/Vault"),
-            password: String::from("testpassword"),
+            password: String::from("testpassword").into(),
             salt: Some(String::from("testsalt")),
         });
         let expected =
@@ -236,7 +237,7 @@ pub mod tests {
         let config = generate_config(&Config {
             name: Some(String::from("Vault name")),
             path: String::from("/Vault"),
-            password: String::from("testpassword"),
+            password: String::from("testpassword").into(),
             salt: None,
         });
         let expected = "^\\[vault-name\\]\ntype=crypt\nremote=koofr:/Vault\npassword=.*\n$";
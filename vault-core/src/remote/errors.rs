@@ -2,7 +2,10 @@ use std::collections::HashMap;
 
 use thiserror::Error;
 
-use crate::{http, user_error::UserError};
+use crate::{
+    http, locale,
+    user_error::{ErrorCode, UserError},
+};
 
 use super::models;
 
@@ -18,6 +21,7 @@ pub enum ApiErrorCode {
     VaultReposAlreadyExists,
     VaultReposMountNotAllowed,
     VaultReposMaxTotalLimitExceeded,
+    QuotaExceeded,
     Other(String),
 }
 
@@ -34,6 +38,7 @@ impl From<&str> for ApiErrorCode {
             "VaultReposAlreadyExists" => Self::VaultReposAlreadyExists,
             "VaultReposMountNotAllowed" => Self::VaultReposMountNotAllowed,
             "VaultReposMaxTotalLimitExceeded" => Self::VaultReposMaxTotalLimitExceeded,
+            "QuotaExceeded" => Self::QuotaExceeded,
             _ => Self::Other(code.to_owned()),
         }
     }
@@ -52,6 +57,7 @@ impl Into<String> for ApiErrorCode {
             Self::VaultReposAlreadyExists => "VaultReposAlreadyExists".into(),
             Self::VaultReposMountNotAllowed => "VaultReposMountNotAllowed".into(),
             Self::VaultReposMaxTotalLimitExceeded => "VaultReposMaxTotalLimitExceeded".into(),
+            Self::QuotaExceeded => "QuotaExceeded".into(),
             Self::Other(code) => code.clone(),
         }
     }
@@ -117,12 +123,40 @@ impl RemoteError {
 impl UserError for RemoteError {
     fn user_error(&self) -> String {
         match self {
-            Self::ApiError { message, .. } => format!("API error: {}", message),
+            Self::ApiError {
+                code: ApiErrorCode::QuotaExceeded,
+                ..
+            } => locale::message("error-not-enough-space"),
+            Self::ApiError { message, .. } => {
+                locale::message_args("error-api", &[("message", message.as_str().into())])
+            }
             Self::UnexpectedStatus {
                 status_code,
                 message,
-            } => format!("unexpected HTTP status: {}: {}", status_code, message),
+            } => locale::message_args(
+                "error-unexpected-status",
+                &[
+                    ("status", status_code.to_string().into()),
+                    ("message", message.as_str().into()),
+                ],
+            ),
             Self::HttpError(err) => err.user_error(),
         }
     }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::ApiError {
+                code: ApiErrorCode::QuotaExceeded,
+                ..
+            } => ErrorCode::RemoteQuotaExceeded,
+            Self::ApiError { code, .. } => ErrorCode::RemoteApiError {
+                api_code: code.clone().into(),
+            },
+            Self::UnexpectedStatus { status_code, .. } => ErrorCode::RemoteUnexpectedStatus {
+                status_code: *status_code,
+            },
+            Self::HttpError(err) => err.error_code(),
+        }
+    }
 }
@@ -14,6 +14,7 @@ pub mod files_move_result;
 pub mod files_rename;
 pub mod files_tags_set;
 pub mod mount;
+pub mod mount_permissions;
 pub mod places;
 pub mod shared;
 pub mod shared_file;
@@ -28,7 +29,7 @@ pub use self::{
     files_copy_result::FilesCopyResult, files_file::FilesFile,
     files_folder_create::FilesFolderCreate, files_list_recursive_item::FilesListRecursiveItem,
     files_move::FilesMove, files_move_result::FilesMoveResult, files_rename::FilesRename,
-    files_tags_set::FilesTagsSet, mount::Mount, places::Places, shared::Shared,
-    shared_file::SharedFile, user::User, vault_repo::VaultRepo, vault_repo_create::VaultRepoCreate,
-    vault_repos_bundle::VaultReposBundle,
+    files_tags_set::FilesTagsSet, mount::Mount, mount_permissions::MountPermissions,
+    places::Places, shared::Shared, shared_file::SharedFile, user::User, vault_repo::VaultRepo,
+    vault_repo_create::VaultRepoCreate, vault_repos_bundle::VaultReposBundle,
 };
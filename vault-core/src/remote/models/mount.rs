@@ -2,6 +2,8 @@ use serde::{Deserialize, Serialize};
 
 use crate::types::{MountId, RemoteName};
 
+use super::MountPermissions;
+
 #[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
 pub struct Mount {
     pub id: MountId,
@@ -16,4 +18,6 @@ pub struct Mount {
     pub space_total: Option<i64>,
     #[serde(rename = "spaceUsed", skip_serializing_if = "Option::is_none")]
     pub space_used: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub permissions: Option<MountPermissions>,
 }
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct MountPermissions {
+    #[serde(rename = "READ", default)]
+    pub read: bool,
+    #[serde(rename = "WRITE", default)]
+    pub write: bool,
+    #[serde(rename = "OWNER", default)]
+    pub owner: bool,
+    #[serde(rename = "MOUNT", default)]
+    pub mount: bool,
+    #[serde(rename = "CREATE_RECEIVER", default)]
+    pub create_receiver: bool,
+    #[serde(rename = "CREATE_LINK", default)]
+    pub create_link: bool,
+    #[serde(rename = "COMMENT", default)]
+    pub comment: bool,
+}
@@ -1,5 +1,6 @@
 use std::{
     collections::HashMap,
+    ops::RangeInclusive,
     sync::{Arc, RwLock},
 };
 
@@ -472,6 +473,35 @@ impl Remote {
         mount_id: &MountId,
         path: &RemotePath,
     ) -> Result<RemoteFileReader, RemoteError> {
+        self.get_file_reader_range(mount_id, path, None).await
+    }
+
+    /// `range` is inclusive. The reader size is the size of the range.
+    pub async fn get_file_range_reader(
+        &self,
+        mount_id: &MountId,
+        path: &RemotePath,
+        range: RangeInclusive<u64>,
+    ) -> Result<RemoteFileReader, RemoteError> {
+        self.get_file_reader_range(mount_id, path, Some(range))
+            .await
+    }
+
+    async fn get_file_reader_range(
+        &self,
+        mount_id: &MountId,
+        path: &RemotePath,
+        range: Option<RangeInclusive<u64>>,
+    ) -> Result<RemoteFileReader, RemoteError> {
+        let mut headers = HeaderMap::new();
+
+        if let Some(range) = &range {
+            headers.insert(
+                header::RANGE,
+                HeaderValue::from_str(&format!("bytes={}-{}", range.start(), range.end())).unwrap(),
+            );
+        }
+
         let res = self
             .request(HttpRequest {
                 method: String::from("GET"),
@@ -480,12 +510,15 @@ impl Remote {
                     &mount_id.0,
                     encode(&path.0)
                 ),
+                headers,
                 is_retriable: true,
                 ..Default::default()
             })
             .await?;
 
-        if res.status_code() != 200 {
+        let expected_status_code = if range.is_some() { 206 } else { 200 };
+
+        if res.status_code() != expected_status_code {
             return res_error(res).await;
         }
 
@@ -1,8 +1,9 @@
 use thiserror::Error;
 
 use crate::{
+    locale,
     remote::{ApiErrorCode, RemoteError},
-    user_error::UserError,
+    user_error::{ErrorCode, UserError},
 };
 
 pub struct RemoteFilesErrors;
@@ -40,8 +41,19 @@ impl UserError for CreateDirError {
             Self::RemoteError(RemoteError::ApiError {
                 code: ApiErrorCode::AlreadyExists,
                 ..
-            }) => String::from("Folder with this name already exists."),
+            }) => locale::message("error-folder-already-exists"),
             Self::RemoteError(err) => err.user_error(),
         }
     }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::Canceled => ErrorCode::Canceled,
+            Self::RemoteError(RemoteError::ApiError {
+                code: ApiErrorCode::AlreadyExists,
+                ..
+            }) => ErrorCode::FileDirAlreadyExists,
+            Self::RemoteError(err) => err.error_code(),
+        }
+    }
 }
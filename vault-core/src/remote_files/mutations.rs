@@ -326,6 +326,25 @@ pub fn bundle_loaded(
         files: bundle_files,
     } = bundle;
 
+    // a reload is diffed so that changes missed by the eventstream are
+    // handled the same as events
+    let old_children: Option<HashMap<RemoteFileId, RemoteFile>> =
+        if state.remote_files.loaded_roots.contains(&root_file_id) {
+            state
+                .remote_files
+                .children
+                .get(&root_file_id)
+                .map(|children_ids| {
+                    children_ids
+                        .iter()
+                        .filter_map(|id| state.remote_files.files.get(id))
+                        .map(|file| (file.id.clone(), file.clone()))
+                        .collect()
+                })
+        } else {
+            None
+        };
+
     state.remote_files.files.insert(
         root_file_id.clone(),
         bundle_file_to_remote_file(
@@ -345,11 +364,50 @@ pub fn bundle_loaded(
             let remote_file =
                 bundle_file_to_remote_file(file_id.clone(), mount_id.to_owned(), file_path, file);
 
+            if let Some(old_children) = &old_children {
+                match old_children.get(&file_id) {
+                    Some(old_file) => {
+                        if old_file.typ != remote_file.typ {
+                            cleanup_file(state, &file_id);
+                        }
+
+                        if old_file.unique_id != remote_file.unique_id
+                            || old_file.name != remote_file.name
+                            || old_file.tags != remote_file.tags
+                        {
+                            mutation_state
+                                .remote_files
+                                .updated_files
+                                .push((mount_id.to_owned(), remote_file.path.clone()));
+                        }
+                    }
+                    None => {
+                        mutation_state
+                            .remote_files
+                            .created_files
+                            .push((mount_id.to_owned(), remote_file.path.clone()));
+                    }
+                }
+            }
+
             children.push(file_id.clone());
 
             state.remote_files.files.insert(file_id, remote_file);
         }
 
+        if let Some(old_children) = &old_children {
+            for (old_file_id, old_file) in old_children {
+                if !children.contains(old_file_id) {
+                    cleanup_file(state, old_file_id);
+
+                    mutation_state
+                        .remote_files
+                        .removed_files
+                        .push((mount_id.to_owned(), old_file.path.clone()));
+                }
+            }
+        }
+
         state
             .remote_files
             .children
@@ -1,10 +1,12 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, ops::RangeInclusive, sync::Arc};
 
 use crate::{
+    accounts::AccountsService,
     common::state::BoxAsyncRead,
-    dialogs,
+    dialogs, locale,
+    metadata_cache::MetadataCacheService,
     remote::{
-        remote::{ListRecursiveItemStream, RemoteFileTagsSetConditions},
+        remote::{ListRecursiveItemStream, RemoteFileReader, RemoteFileTagsSetConditions},
         Remote, RemoteError, RemoteFileUploadConflictResolution,
     },
     store,
@@ -20,7 +22,9 @@ use super::{
 
 pub struct RemoteFilesService {
     remote: Arc<Remote>,
+    accounts_service: Arc<AccountsService>,
     dialogs_service: Arc<dialogs::DialogsService>,
+    metadata_cache_service: Arc<MetadataCacheService>,
     store: Arc<store::Store>,
     eventstream_events_mutation_subscription_id: u32,
 }
@@ -28,14 +32,18 @@ pub struct RemoteFilesService {
 impl RemoteFilesService {
     pub fn new(
         remote: Arc<Remote>,
+        accounts_service: Arc<AccountsService>,
         dialogs_service: Arc<dialogs::DialogsService>,
+        metadata_cache_service: Arc<MetadataCacheService>,
         store: Arc<store::Store>,
     ) -> Self {
         let eventstream_events_mutation_subscription_id = store.get_next_id();
 
         let remote_files_service = Self {
             remote,
+            accounts_service,
             dialogs_service,
+            metadata_cache_service,
             store: store.clone(),
             eventstream_events_mutation_subscription_id,
         };
@@ -93,7 +101,7 @@ impl RemoteFilesService {
     }
 
     pub async fn load_mount(&self, mount_id: &MountId) -> Result<MountId, RemoteError> {
-        let mount = self.remote.get_mount(mount_id).await?;
+        let mount = self.get_mount_remote(mount_id).get_mount(mount_id).await?;
         // mount_id parameter can be "primary" but we want an actual id
         let mount_id = mount.id.clone();
 
@@ -106,12 +114,45 @@ impl RemoteFilesService {
         Ok(mount_id)
     }
 
+    /// The cached listing is shown until the loaded one replaces it.
+    fn restore_cached_files(&self, mount_id: &MountId, path: &RemotePath) {
+        if self.store.with_state(|state| {
+            selectors::select_is_root_loaded(state, mount_id, &path.to_lowercase())
+        }) {
+            return;
+        }
+
+        if let Some(bundle) = self.metadata_cache_service.get_bundle(mount_id, path) {
+            self.store
+                .mutate(|state, notify, mutation_state, mutation_notify| {
+                    notify(store::Event::RemoteFiles);
+
+                    mutations::bundle_loaded(
+                        state,
+                        mutation_state,
+                        mutation_notify,
+                        mount_id,
+                        path,
+                        bundle,
+                    );
+                });
+        }
+    }
+
     pub async fn load_files(
         &self,
         mount_id: &MountId,
         path: &RemotePath,
     ) -> Result<(), RemoteError> {
-        let bundle = self.remote.get_bundle(mount_id, path).await?;
+        self.restore_cached_files(mount_id, path);
+
+        let bundle = self
+            .get_mount_remote(mount_id)
+            .get_bundle(mount_id, path)
+            .await?;
+
+        self.metadata_cache_service
+            .set_bundle(mount_id, path, &bundle);
 
         self.store
             .mutate(|state, notify, mutation_state, mutation_notify| {
@@ -135,7 +176,10 @@ impl RemoteFilesService {
         mount_id: &MountId,
         path: &RemotePath,
     ) -> Result<(), RemoteError> {
-        let file = self.remote.get_file(mount_id, path).await?;
+        let file = self
+            .get_mount_remote(mount_id)
+            .get_file(mount_id, path)
+            .await?;
 
         self.store
             .mutate(|state, notify, mutation_state, mutation_notify| {
@@ -154,14 +198,47 @@ impl RemoteFilesService {
         Ok(())
     }
 
+    /// Transfers keep running after the active account is switched so
+    /// requests for mounts of other accounts use their own tokens.
+    fn get_mount_remote(&self, mount_id: &MountId) -> Arc<Remote> {
+        self.accounts_service
+            .get_mount_remote(mount_id)
+            .unwrap_or_else(|| self.remote.clone())
+    }
+
     pub async fn get_file_reader(
         &self,
         mount_id: &MountId,
         path: &RemotePath,
     ) -> Result<RemoteFilesFileReader, RemoteError> {
-        let reader = self.remote.get_file_reader(&mount_id, &path).await?;
+        let reader = self
+            .get_mount_remote(mount_id)
+            .get_file_reader(&mount_id, &path)
+            .await?;
 
-        Ok(RemoteFilesFileReader {
+        Ok(Self::file_reader(mount_id, path, reader))
+    }
+
+    pub async fn get_file_range_reader(
+        &self,
+        mount_id: &MountId,
+        path: &RemotePath,
+        range: RangeInclusive<u64>,
+    ) -> Result<RemoteFilesFileReader, RemoteError> {
+        let reader = self
+            .get_mount_remote(mount_id)
+            .get_file_range_reader(&mount_id, &path, range)
+            .await?;
+
+        Ok(Self::file_reader(mount_id, path, reader))
+    }
+
+    fn file_reader(
+        mount_id: &MountId,
+        path: &RemotePath,
+        reader: RemoteFileReader,
+    ) -> RemoteFilesFileReader {
+        RemoteFilesFileReader {
             file: mutations::files_file_to_remote_file(
                 selectors::get_file_id(mount_id, &path.to_lowercase()),
                 mount_id.to_owned(),
@@ -170,7 +247,7 @@ impl RemoteFilesService {
             ),
             size: reader.size,
             reader: reader.reader,
-        })
+        }
     }
 
     pub async fn get_list_recursive(
@@ -178,7 +255,9 @@ impl RemoteFilesService {
         mount_id: &MountId,
         path: &RemotePath,
     ) -> Result<ListRecursiveItemStream, RemoteError> {
-        self.remote.get_list_recursive(mount_id, path).await
+        self.get_mount_remote(mount_id)
+            .get_list_recursive(mount_id, path)
+            .await
     }
 
     pub async fn upload_file_reader(
@@ -192,7 +271,7 @@ impl RemoteFilesService {
         on_progress: Option<Box<dyn Fn(usize) + Send + Sync>>,
     ) -> Result<(RemoteFileId, RemoteFile), RemoteError> {
         let file = self
-            .remote
+            .get_mount_remote(mount_id)
             .upload_file_reader(
                 mount_id,
                 parent_path,
@@ -237,7 +316,7 @@ impl RemoteFilesService {
         mount_id: &MountId,
         path: &RemotePath,
     ) -> Result<(), RemoteError> {
-        self.remote
+        self.get_mount_remote(mount_id)
             .delete_file(mount_id, path, Default::default())
             .await?;
 
@@ -268,13 +347,13 @@ impl RemoteFilesService {
         let name = match self
             .dialogs_service
             .show_validator(
-                dialogs::state::DialogShowOptions {
-                    input_placeholder: Some(String::from("Folder name")),
-                    confirm_button_text: String::from("Create folder"),
+                locale::with_store_locale(&self.store, || dialogs::state::DialogShowOptions {
+                    input_placeholder: Some(locale::message("dialog-create-folder-placeholder")),
+                    confirm_button_text: locale::message("dialog-create-folder-confirm"),
                     ..self
                         .dialogs_service
-                        .build_prompt(String::from("Enter new folder name"))
-                },
+                        .build_prompt(locale::message("dialog-create-folder-title"))
+                }),
                 move |value| {
                     input_value_validator_store.with_state(|state| {
                         selectors::select_check_new_name_valid(
@@ -309,7 +388,7 @@ impl RemoteFilesService {
     ) -> Result<(), RemoteError> {
         let path = remote_path_utils::join_path_name(parent_path, &name);
 
-        self.remote
+        self.get_mount_remote(mount_id)
             .create_dir(mount_id, parent_path, name.clone())
             .await?;
 
@@ -335,7 +414,7 @@ impl RemoteFilesService {
         to_mount_id: &MountId,
         to_path: &RemotePath,
     ) -> Result<(), RemoteError> {
-        self.remote
+        self.get_mount_remote(mount_id)
             .copy_file(mount_id, path, to_mount_id, to_path)
             .await?;
 
@@ -351,7 +430,7 @@ impl RemoteFilesService {
         to_mount_id: &MountId,
         to_path: &RemotePath,
     ) -> Result<(), RemoteError> {
-        self.remote
+        self.get_mount_remote(mount_id)
             .move_file(mount_id, path, to_mount_id, to_path, Default::default())
             .await?;
 
@@ -366,7 +445,9 @@ impl RemoteFilesService {
         path: &RemotePath,
         new_name: RemoteName,
     ) -> Result<(), RemoteError> {
-        self.remote.rename_file(mount_id, path, new_name).await?;
+        self.get_mount_remote(mount_id)
+            .rename_file(mount_id, path, new_name)
+            .await?;
 
         // state is updated by eventstream event
 
@@ -380,7 +461,7 @@ impl RemoteFilesService {
         tags: HashMap<String, Vec<String>>,
         conditions: RemoteFileTagsSetConditions,
     ) -> Result<(), RemoteError> {
-        self.remote
+        self.get_mount_remote(mount_id)
             .file_set_tags(mount_id, path, tags.clone(), conditions.clone())
             .await?;
 
@@ -278,6 +278,8 @@ pub struct RemoteFilesMutationState {
     pub removed_files: Vec<(MountId, RemotePath)>,
     pub moved_files: Vec<(MountId, RemotePath, RemotePath)>,
     pub tags_updated: Vec<(MountId, RemotePath)>,
+    /// Files that changed since the previous load of their parent.
+    pub updated_files: Vec<(MountId, RemotePath)>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
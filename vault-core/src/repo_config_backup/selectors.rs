@@ -15,6 +15,11 @@ pub fn select_info<'a>(
                 repo_id: &backup.repo_id,
                 status: (&backup.status).into(),
                 repo_name: repos_selectors::select_repo_name(state, &backup.repo_id),
+                failed_attempts: repos_selectors::select_unlock_failed_attempts(
+                    state,
+                    &backup.repo_id,
+                ),
+                retry_at: repos_selectors::select_unlock_retry_at(state, &backup.repo_id),
             },
             config: backup.config.as_ref(),
         })
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use crate::{
+    audit_log::{state::AuditLogEvent, AuditLogService},
     repos::{errors::UnlockRepoError, ReposService},
     store,
     types::RepoId,
@@ -10,13 +11,19 @@ use super::mutations;
 
 pub struct RepoConfigBackupService {
     repos_service: Arc<ReposService>,
+    audit_log_service: Arc<AuditLogService>,
     store: Arc<store::Store>,
 }
 
 impl RepoConfigBackupService {
-    pub fn new(repos_service: Arc<ReposService>, store: Arc<store::Store>) -> Self {
+    pub fn new(
+        repos_service: Arc<ReposService>,
+        audit_log_service: Arc<AuditLogService>,
+        store: Arc<store::Store>,
+    ) -> Self {
         Self {
             repos_service,
+            audit_log_service,
             store,
         }
     }
@@ -35,6 +42,13 @@ impl RepoConfigBackupService {
 
         let res_err = res.as_ref().map(|_| ()).map_err(|err| err.clone());
 
+        if res_err.is_ok() {
+            self.audit_log_service
+                .record(AuditLogEvent::RepoConfigBackup {
+                    repo_id: repo_id.clone(),
+                });
+        }
+
         self.store
             .mutate(|state, notify, _, _| mutations::generated(state, notify, backup_id, res))?;
 
@@ -1,6 +1,10 @@
 use thiserror::Error;
 
-use crate::{remote::RemoteError, repos::errors::LoadReposError, user_error::UserError};
+use crate::{
+    remote::RemoteError,
+    repos::errors::LoadReposError,
+    user_error::{ErrorCode, UserError},
+};
 
 #[derive(Error, Debug, Clone, PartialEq)]
 pub enum CreateLoadError {
@@ -17,4 +21,11 @@ impl UserError for CreateLoadError {
             Self::LoadPrimaryMountError(err) => err.user_error(),
         }
     }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::LoadReposError(err) => err.error_code(),
+            Self::LoadPrimaryMountError(err) => err.error_code(),
+        }
+    }
 }
@@ -25,7 +25,7 @@ pub fn create(state: &mut store::State, notify: &store::Notify, salt: String) ->
         primary_mount_id: None,
         location: None,
         location_dir_picker_id: None,
-        password: String::from(""),
+        password: String::from("").into(),
         salt: Some(salt),
         fill_from_rclone_config_error: None,
         create_repo_status: Status::Initial,
@@ -160,7 +160,7 @@ pub fn set_password(
 
     notify(store::Event::RepoCreate);
 
-    form.password = password;
+    form.password = password.into();
 }
 
 pub fn set_salt(
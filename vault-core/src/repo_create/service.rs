@@ -43,7 +43,8 @@ impl RepoCreateService {
     }
 
     pub fn create(self: Arc<Self>) -> (u32, BoxFuture<'static, Result<(), CreateLoadError>>) {
-        let salt = random_password(1024).unwrap();
+        // the salt is not secret
+        let salt = random_password(1024).unwrap().to_string();
 
         let create_id = self
             .store
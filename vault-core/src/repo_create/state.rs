@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use zeroize::Zeroizing;
+
 use crate::{
     common::state::Status,
     rclone,
@@ -17,7 +19,7 @@ pub struct RepoCreateForm {
     pub primary_mount_id: Option<MountId>,
     pub location: Option<RemoteFilesLocation>,
     pub location_dir_picker_id: Option<u32>,
-    pub password: String,
+    pub password: Zeroizing<String>,
     pub salt: Option<String>,
     pub fill_from_rclone_config_error: Option<rclone::config::ParseConfigError>,
     pub create_repo_status: Status<CreateRepoError>,
@@ -0,0 +1,62 @@
+use thiserror::Error;
+
+use crate::{
+    remote::RemoteError,
+    repo_files::errors::LoadFileError,
+    repo_files_list::errors::{FilesListRecursiveItemError, GetListRecursiveError},
+    repos::errors::{RepoLockedError, RepoNotFoundError},
+    user_error::{ErrorCode, UserError},
+};
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum RepoDuplicatesError {
+    #[error("{0}")]
+    RepoNotFound(#[from] RepoNotFoundError),
+    #[error("{0}")]
+    RepoLocked(#[from] RepoLockedError),
+    #[error("{0}")]
+    ListError(#[from] FilesListRecursiveItemError),
+    #[error("{0}")]
+    RemoteError(#[from] RemoteError),
+}
+
+impl UserError for RepoDuplicatesError {
+    fn user_error(&self) -> String {
+        match self {
+            Self::RepoNotFound(err) => err.user_error(),
+            Self::RepoLocked(err) => err.user_error(),
+            Self::ListError(err) => err.user_error(),
+            Self::RemoteError(err) => err.user_error(),
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::RepoNotFound(err) => err.error_code(),
+            Self::RepoLocked(err) => err.error_code(),
+            Self::ListError(err) => err.error_code(),
+            Self::RemoteError(err) => err.error_code(),
+        }
+    }
+}
+
+impl From<LoadFileError> for RepoDuplicatesError {
+    fn from(err: LoadFileError) -> Self {
+        match err {
+            LoadFileError::RepoNotFound(err) => Self::RepoNotFound(err),
+            LoadFileError::RepoLocked(err) => Self::RepoLocked(err),
+            LoadFileError::RemoteError(err) => Self::RemoteError(err),
+        }
+    }
+}
+
+impl From<GetListRecursiveError> for RepoDuplicatesError {
+    fn from(err: GetListRecursiveError) -> Self {
+        match err {
+            GetListRecursiveError::RepoNotFound(err) => Self::RepoNotFound(err),
+            GetListRecursiveError::RepoLocked(err) => Self::RepoLocked(err),
+            GetListRecursiveError::DecryptFilenameError(err) => Self::ListError(err.into()),
+            GetListRecursiveError::RemoteError(err) => Self::RemoteError(err),
+        }
+    }
+}
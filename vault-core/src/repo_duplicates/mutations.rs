@@ -0,0 +1,127 @@
+use std::collections::HashSet;
+
+use crate::{
+    common::state::Status,
+    repos::errors::RepoNotFoundError,
+    store,
+    types::{EncryptedPath, RepoId},
+};
+
+use super::{
+    errors::RepoDuplicatesError,
+    state::{RepoDuplicates, RepoDuplicatesGroup},
+};
+
+pub fn create(state: &mut store::State, notify: &store::Notify, repo_id: RepoId) -> u32 {
+    notify(store::Event::RepoDuplicates);
+
+    let duplicates_id = state.repo_duplicates.next_id.next();
+
+    let duplicates = RepoDuplicates {
+        repo_id,
+        status: Status::Initial,
+        groups: vec![],
+    };
+
+    state
+        .repo_duplicates
+        .duplicates
+        .insert(duplicates_id, duplicates);
+
+    duplicates_id
+}
+
+pub fn scanning(
+    state: &mut store::State,
+    notify: &store::Notify,
+    duplicates_id: u32,
+) -> Result<RepoId, RepoDuplicatesError> {
+    let duplicates = match state.repo_duplicates.duplicates.get_mut(&duplicates_id) {
+        Some(duplicates) => duplicates,
+        None => return Err(RepoDuplicatesError::RepoNotFound(RepoNotFoundError)),
+    };
+
+    notify(store::Event::RepoDuplicates);
+
+    duplicates.status = Status::Loading {
+        loaded: duplicates.status.loaded(),
+    };
+
+    Ok(duplicates.repo_id.clone())
+}
+
+pub fn scanned(
+    state: &mut store::State,
+    notify: &store::Notify,
+    duplicates_id: u32,
+    groups: Vec<RepoDuplicatesGroup>,
+    res: Result<(), RepoDuplicatesError>,
+) {
+    let duplicates = match state.repo_duplicates.duplicates.get_mut(&duplicates_id) {
+        Some(duplicates) => duplicates,
+        None => return,
+    };
+
+    notify(store::Event::RepoDuplicates);
+
+    duplicates.status = match res {
+        Ok(()) => Status::Loaded,
+        Err(err) => Status::Error {
+            error: err,
+            loaded: duplicates.status.loaded(),
+        },
+    };
+
+    duplicates.groups = groups;
+}
+
+pub fn files_deleted(
+    state: &mut store::State,
+    notify: &store::Notify,
+    duplicates_id: u32,
+    paths: &HashSet<EncryptedPath>,
+) {
+    let duplicates = match state.repo_duplicates.duplicates.get_mut(&duplicates_id) {
+        Some(duplicates) => duplicates,
+        None => return,
+    };
+
+    notify(store::Event::RepoDuplicates);
+
+    for group in duplicates.groups.iter_mut() {
+        group
+            .files
+            .retain(|file| !paths.contains(&file.encrypted_path));
+    }
+
+    duplicates.groups.retain(|group| group.files.len() > 1);
+}
+
+pub fn destroy(state: &mut store::State, notify: &store::Notify, duplicates_id: u32) {
+    notify(store::Event::RepoDuplicates);
+
+    state.repo_duplicates.duplicates.remove(&duplicates_id);
+}
+
+pub fn handle_repos_mutation(
+    state: &mut store::State,
+    notify: &store::Notify,
+    mutation_state: &store::MutationState,
+) {
+    for repo_id in mutation_state
+        .repos
+        .locked_repos
+        .iter()
+        .map(|(repo_id, _)| repo_id)
+        .chain(mutation_state.repos.removed_repos.iter())
+    {
+        for duplicates in state.repo_duplicates.duplicates.values_mut() {
+            if &duplicates.repo_id == repo_id {
+                notify(store::Event::RepoDuplicates);
+
+                duplicates.status = Status::Initial;
+                duplicates.groups.clear();
+            }
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use std::{cmp::Reverse, collections::HashMap};
+
+use crate::{
+    repo_files::state::RepoFile,
+    repo_files_versions::selectors as repo_files_versions_selectors,
+    store,
+    types::{DecryptedPath, EncryptedPath, RepoId},
+};
+
+use super::state::{RepoDuplicatesFile, RepoDuplicatesGroup, RepoDuplicatesInfo};
+
+pub fn select_info<'a>(
+    state: &'a store::State,
+    duplicates_id: u32,
+) -> Option<RepoDuplicatesInfo<'a>> {
+    state
+        .repo_duplicates
+        .duplicates
+        .get(&duplicates_id)
+        .map(|duplicates| RepoDuplicatesInfo {
+            repo_id: &duplicates.repo_id,
+            status: &duplicates.status,
+            groups: &duplicates.groups,
+            reclaimable_size: duplicates
+                .groups
+                .iter()
+                .map(RepoDuplicatesGroup::reclaimable_size)
+                .sum(),
+        })
+}
+
+/// Versions are copies of older content and are not reported as duplicates.
+pub fn is_in_versions_dir(path: &DecryptedPath) -> bool {
+    path.0.starts_with(&format!(
+        "{}/",
+        repo_files_versions_selectors::get_versions_dir_path().0
+    ))
+}
+
+/// Files that have the same size as at least one other file. Empty files are
+/// ignored since deleting them does not free any space.
+pub fn get_candidates(files: Vec<(RepoFile, i64)>) -> Vec<(RepoFile, i64)> {
+    let mut sizes_count: HashMap<i64, usize> = HashMap::new();
+
+    for (_, size) in &files {
+        *sizes_count.entry(*size).or_default() += 1;
+    }
+
+    files
+        .into_iter()
+        .filter(|(_, size)| *size > 0 && sizes_count.get(size).cloned().unwrap_or(0) > 1)
+        .collect()
+}
+
+/// Groups are sorted by reclaimable size (largest first).
+pub fn get_groups(files: Vec<(RepoFile, i64, String)>) -> Vec<RepoDuplicatesGroup> {
+    let mut groups: HashMap<(i64, String), Vec<RepoDuplicatesFile>> = HashMap::new();
+
+    for (file, size, hash) in files {
+        let path = match file.decrypted_path() {
+            Ok(path) => path.to_owned(),
+            Err(_) => continue,
+        };
+
+        groups
+            .entry((size, hash))
+            .or_default()
+            .push(RepoDuplicatesFile {
+                file_id: file.id,
+                encrypted_path: file.encrypted_path,
+                path,
+                modified: file.modified,
+            });
+    }
+
+    let mut groups = groups
+        .into_iter()
+        .filter(|(_, files)| files.len() > 1)
+        .map(|((size, hash), mut files)| {
+            files.sort_by(|a, b| {
+                b.modified
+                    .cmp(&a.modified)
+                    .then_with(|| a.path.0.cmp(&b.path.0))
+            });
+
+            RepoDuplicatesGroup { hash, size, files }
+        })
+        .collect::<Vec<_>>();
+
+    groups.sort_by_key(|group| (Reverse(group.reclaimable_size()), group.hash.clone()));
+
+    groups
+}
+
+/// All files except the newest one in each group. If `hashes` is set only the
+/// matching groups are included.
+pub fn select_files_to_delete(
+    state: &store::State,
+    duplicates_id: u32,
+    hashes: Option<&[String]>,
+) -> Vec<(RepoId, EncryptedPath)> {
+    state
+        .repo_duplicates
+        .duplicates
+        .get(&duplicates_id)
+        .map(|duplicates| {
+            duplicates
+                .groups
+                .iter()
+                .filter(|group| {
+                    hashes
+                        .map(|hashes| hashes.contains(&group.hash))
+                        .unwrap_or(true)
+                })
+                .flat_map(|group| group.files.iter().skip(1))
+                .map(|file| (duplicates.repo_id.clone(), file.encrypted_path.clone()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
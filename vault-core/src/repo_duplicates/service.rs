@@ -0,0 +1,222 @@
+use std::{collections::HashSet, sync::Arc};
+
+use futures::{AsyncReadExt, StreamExt};
+
+use crate::{
+    repo_files::{
+        errors::DeleteFileError, selectors as repo_files_selectors, state::RepoFile,
+        RepoFilesService,
+    },
+    repo_files_list::{state::RepoFilesListRecursiveItem, RepoFilesListService},
+    repo_files_read::{errors::GetFilesReaderError, RepoFilesReadService},
+    repos::errors::RepoNotFoundError,
+    store,
+    types::{EncryptedPath, RepoId},
+};
+
+use super::{errors::RepoDuplicatesError, mutations, selectors, state::RepoDuplicatesGroup};
+
+pub struct RepoDuplicatesService {
+    repo_files_service: Arc<RepoFilesService>,
+    repo_files_list_service: Arc<RepoFilesListService>,
+    repo_files_read_service: Arc<RepoFilesReadService>,
+    store: Arc<store::Store>,
+    repos_mutation_subscription_id: u32,
+}
+
+impl RepoDuplicatesService {
+    pub fn new(
+        repo_files_service: Arc<RepoFilesService>,
+        repo_files_list_service: Arc<RepoFilesListService>,
+        repo_files_read_service: Arc<RepoFilesReadService>,
+        store: Arc<store::Store>,
+    ) -> Self {
+        let repos_mutation_subscription_id = store.get_next_id();
+
+        store.mutation_on(
+            repos_mutation_subscription_id,
+            &[store::MutationEvent::Repos],
+            Box::new(move |state, notify, mutation_state, _| {
+                mutations::handle_repos_mutation(state, notify, mutation_state);
+            }),
+        );
+
+        Self {
+            repo_files_service,
+            repo_files_list_service,
+            repo_files_read_service,
+            store,
+            repos_mutation_subscription_id,
+        }
+    }
+
+    pub fn create(&self, repo_id: RepoId) -> u32 {
+        self.store
+            .mutate(|state, notify, _, _| mutations::create(state, notify, repo_id))
+    }
+
+    /// Scans the whole repo. Hashes are taken from file tags, files without a
+    /// hash are downloaded only if another file has the same size.
+    pub async fn scan(&self, duplicates_id: u32) -> Result<(), RepoDuplicatesError> {
+        let repo_id = self
+            .store
+            .mutate(|state, notify, _, _| mutations::scanning(state, notify, duplicates_id))?;
+
+        let (groups, res) = match self.get_files(&repo_id).await {
+            Ok((files, res)) => (self.get_groups(files).await, res),
+            Err(err) => (vec![], Err(err)),
+        };
+
+        self.store.mutate(|state, notify, _, _| {
+            mutations::scanned(state, notify, duplicates_id, groups, res.clone())
+        });
+
+        res
+    }
+
+    /// Returns files with their decrypted sizes. Listing errors for single
+    /// items do not stop the scan but are returned as the scan result.
+    async fn get_files(
+        &self,
+        repo_id: &RepoId,
+    ) -> Result<(Vec<(RepoFile, i64)>, Result<(), RepoDuplicatesError>), RepoDuplicatesError> {
+        let root_path = EncryptedPath("/".into());
+
+        self.repo_files_service
+            .load_file(repo_id, &root_path)
+            .await?;
+
+        let root_file = self
+            .store
+            .with_state(|state| {
+                repo_files_selectors::select_file(
+                    state,
+                    &repo_files_selectors::get_file_id(repo_id, &root_path),
+                )
+                .cloned()
+            })
+            .ok_or(RepoDuplicatesError::RepoNotFound(RepoNotFoundError))?;
+
+        let mut items_stream = self
+            .repo_files_list_service
+            .get_list_recursive(&root_file)
+            .await?;
+
+        let mut files = Vec::new();
+        let mut res = Ok(());
+
+        while let Some(item) = items_stream.next().await {
+            match item {
+                RepoFilesListRecursiveItem::File { file, .. } => {
+                    if !file.typ.is_file() {
+                        continue;
+                    }
+
+                    match (file.decrypted_path(), file.decrypted_size()) {
+                        (Ok(path), Ok(Some(size))) if !selectors::is_in_versions_dir(path) => {
+                            files.push((file, size));
+                        }
+                        _ => {}
+                    }
+                }
+                RepoFilesListRecursiveItem::Error { error, .. } => {
+                    res = Err(RepoDuplicatesError::ListError(error));
+                }
+            }
+        }
+
+        Ok((files, res))
+    }
+
+    async fn get_groups(&self, files: Vec<(RepoFile, i64)>) -> Vec<RepoDuplicatesGroup> {
+        let mut hashed_files = Vec::new();
+
+        for (file, size) in selectors::get_candidates(files) {
+            let hash = match file.hash() {
+                Some(hash) => hash,
+                None => match self.hash_file(&file).await {
+                    Ok(hash) => hash,
+                    Err(err) => {
+                        log::warn!("RepoDuplicatesService failed to hash file: {}", err);
+
+                        continue;
+                    }
+                },
+            };
+
+            hashed_files.push((file, size, hash));
+        }
+
+        selectors::get_groups(hashed_files)
+    }
+
+    /// Reading the file also stores the missing hash in the file tags.
+    async fn hash_file(&self, file: &RepoFile) -> Result<String, GetFilesReaderError> {
+        let mut reader = self
+            .repo_files_read_service
+            .clone()
+            .get_files_reader(vec![file.clone()])?
+            .reader()
+            .await?
+            .reader;
+
+        let mut context = md5::Context::new();
+        let mut buf = vec![0; 64 * 1024];
+
+        loop {
+            let n = reader
+                .read(&mut buf)
+                .await
+                .map_err(|err| GetFilesReaderError::IOError(err.to_string()))?;
+
+            if n == 0 {
+                break;
+            }
+
+            context.consume(&buf[..n]);
+        }
+
+        Ok(hex::encode(context.compute().0))
+    }
+
+    /// Keeps the newest file in each group. If `hashes` is set only the
+    /// matching groups are processed.
+    pub async fn delete_all_but_newest(
+        &self,
+        duplicates_id: u32,
+        hashes: Option<Vec<String>>,
+    ) -> Result<(), DeleteFileError> {
+        let files = self.store.with_state(|state| {
+            selectors::select_files_to_delete(state, duplicates_id, hashes.as_deref())
+        });
+
+        if files.is_empty() {
+            return Ok(());
+        }
+
+        self.repo_files_service.delete_files(&files, None).await?;
+
+        let paths = files
+            .into_iter()
+            .map(|(_, path)| path)
+            .collect::<HashSet<_>>();
+
+        self.store.mutate(|state, notify, _, _| {
+            mutations::files_deleted(state, notify, duplicates_id, &paths);
+        });
+
+        Ok(())
+    }
+
+    pub fn destroy(&self, duplicates_id: u32) {
+        self.store
+            .mutate(|state, notify, _, _| mutations::destroy(state, notify, duplicates_id));
+    }
+}
+
+impl Drop for RepoDuplicatesService {
+    fn drop(&mut self) {
+        self.store
+            .mutation_remove_listener(self.repos_mutation_subscription_id);
+    }
+}
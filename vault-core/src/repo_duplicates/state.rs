@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+
+use crate::{
+    common::state::Status,
+    store::NextId,
+    types::{DecryptedPath, EncryptedPath, RepoFileId, RepoId},
+};
+
+use super::errors::RepoDuplicatesError;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepoDuplicatesFile {
+    pub file_id: RepoFileId,
+    pub encrypted_path: EncryptedPath,
+    pub path: DecryptedPath,
+    pub modified: Option<i64>,
+}
+
+/// Files with the same size and plaintext hash, newest first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepoDuplicatesGroup {
+    pub hash: String,
+    pub size: i64,
+    pub files: Vec<RepoDuplicatesFile>,
+}
+
+impl RepoDuplicatesGroup {
+    pub fn reclaimable_size(&self) -> i64 {
+        self.size * (self.files.len() as i64 - 1)
+    }
+}
+
+pub struct RepoDuplicatesInfo<'a> {
+    pub repo_id: &'a RepoId,
+    pub status: &'a Status<RepoDuplicatesError>,
+    pub groups: &'a [RepoDuplicatesGroup],
+    pub reclaimable_size: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct RepoDuplicates {
+    pub repo_id: RepoId,
+    pub status: Status<RepoDuplicatesError>,
+    pub groups: Vec<RepoDuplicatesGroup>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RepoDuplicatesState {
+    pub duplicates: HashMap<u32, RepoDuplicates>,
+    pub next_id: NextId,
+}
+
+impl RepoDuplicatesState {
+    pub fn reset(&mut self) {
+        *self = Self {
+            next_id: self.next_id.clone(),
+            ..Default::default()
+        };
+    }
+}
@@ -2,9 +2,10 @@ use thiserror::Error;
 
 use crate::{
     cipher::errors::DecryptFilenameError,
+    locale,
     remote::{ApiErrorCode, RemoteError},
-    repos::errors::{GetCipherError, RepoLockedError, RepoNotFoundError},
-    user_error::UserError,
+    repos::errors::{GetCipherError, RepoLockedError, RepoNotFoundError, RepoReadOnlyError},
+    user_error::{ErrorCode, UserError},
 };
 
 pub struct RepoFilesErrors;
@@ -55,6 +56,14 @@ impl UserError for LoadFilesError {
             Self::RemoteError(err) => err.user_error(),
         }
     }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::RepoNotFound(err) => err.error_code(),
+            Self::RepoLocked(err) => err.error_code(),
+            Self::RemoteError(err) => err.error_code(),
+        }
+    }
 }
 
 #[derive(Error, Debug, Clone, PartialEq)]
@@ -67,6 +76,24 @@ pub enum LoadFileError {
     RemoteError(#[from] RemoteError),
 }
 
+impl UserError for LoadFileError {
+    fn user_error(&self) -> String {
+        match self {
+            Self::RepoNotFound(err) => err.user_error(),
+            Self::RepoLocked(err) => err.user_error(),
+            Self::RemoteError(err) => err.user_error(),
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::RepoNotFound(err) => err.error_code(),
+            Self::RepoLocked(err) => err.error_code(),
+            Self::RemoteError(err) => err.error_code(),
+        }
+    }
+}
+
 #[derive(Error, Debug, Clone, PartialEq)]
 pub enum FileNameError {
     #[error("{0}")]
@@ -82,6 +109,13 @@ impl UserError for FileNameError {
             Self::DecryptFilenameError(err) => err.user_error(),
         }
     }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::RepoNotFound(err) => err.error_code(),
+            Self::DecryptFilenameError(err) => err.error_code(),
+        }
+    }
 }
 
 #[derive(Error, Debug, Clone, PartialEq)]
@@ -91,6 +125,8 @@ pub enum UploadFileReaderError {
     #[error("{0}")]
     RepoLocked(#[from] RepoLockedError),
     #[error("{0}")]
+    RepoReadOnly(#[from] RepoReadOnlyError),
+    #[error("{0}")]
     DecryptFilenameError(#[from] DecryptFilenameError),
     #[error("canceled")]
     Canceled,
@@ -103,11 +139,23 @@ impl UserError for UploadFileReaderError {
         match self {
             Self::RepoNotFound(err) => err.user_error(),
             Self::RepoLocked(err) => err.user_error(),
+            Self::RepoReadOnly(err) => err.user_error(),
             Self::DecryptFilenameError(err) => err.user_error(),
             Self::Canceled => self.to_string(),
             Self::RemoteError(err) => err.user_error(),
         }
     }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::RepoNotFound(err) => err.error_code(),
+            Self::RepoLocked(err) => err.error_code(),
+            Self::RepoReadOnly(err) => err.error_code(),
+            Self::DecryptFilenameError(err) => err.error_code(),
+            Self::Canceled => ErrorCode::Canceled,
+            Self::RemoteError(err) => err.error_code(),
+        }
+    }
 }
 
 impl From<GetCipherError> for UploadFileReaderError {
@@ -124,6 +172,7 @@ impl From<EnsureDirError> for UploadFileReaderError {
         match err {
             EnsureDirError::RepoNotFound(err) => Self::RepoNotFound(err),
             EnsureDirError::RepoLocked(err) => Self::RepoLocked(err),
+            EnsureDirError::RepoReadOnly(err) => Self::RepoReadOnly(err),
             EnsureDirError::DecryptFilenameError(err) => Self::DecryptFilenameError(err),
             EnsureDirError::Canceled => Self::Canceled,
             EnsureDirError::RemoteError(err) => Self::RemoteError(err),
@@ -137,6 +186,8 @@ pub enum DeleteFileError {
     RepoNotFound(#[from] RepoNotFoundError),
     #[error("{0}")]
     RepoLocked(#[from] RepoLockedError),
+    #[error("{0}")]
+    RepoReadOnly(#[from] RepoReadOnlyError),
     #[error("canceled")]
     Canceled,
     #[error("{0}")]
@@ -148,10 +199,21 @@ impl UserError for DeleteFileError {
         match self {
             Self::RepoNotFound(err) => err.user_error(),
             Self::RepoLocked(err) => err.user_error(),
+            Self::RepoReadOnly(err) => err.user_error(),
             Self::Canceled => self.to_string(),
             Self::RemoteError(err) => err.user_error(),
         }
     }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::RepoNotFound(err) => err.error_code(),
+            Self::RepoLocked(err) => err.error_code(),
+            Self::RepoReadOnly(err) => err.error_code(),
+            Self::Canceled => ErrorCode::Canceled,
+            Self::RemoteError(err) => err.error_code(),
+        }
+    }
 }
 
 #[derive(Error, Debug, Clone, PartialEq)]
@@ -161,6 +223,8 @@ pub enum CreateDirError {
     #[error("{0}")]
     RepoLocked(#[from] RepoLockedError),
     #[error("{0}")]
+    RepoReadOnly(#[from] RepoReadOnlyError),
+    #[error("{0}")]
     DecryptFilenameError(#[from] DecryptFilenameError),
     #[error("canceled")]
     Canceled,
@@ -173,15 +237,31 @@ impl UserError for CreateDirError {
         match self {
             Self::RepoNotFound(err) => err.user_error(),
             Self::RepoLocked(err) => err.user_error(),
+            Self::RepoReadOnly(err) => err.user_error(),
             Self::DecryptFilenameError(err) => err.user_error(),
             Self::Canceled => self.to_string(),
             Self::RemoteError(RemoteError::ApiError {
                 code: ApiErrorCode::AlreadyExists,
                 ..
-            }) => String::from("Folder with this name already exists."),
+            }) => locale::message("error-folder-already-exists"),
             Self::RemoteError(err) => err.user_error(),
         }
     }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::RepoNotFound(err) => err.error_code(),
+            Self::RepoLocked(err) => err.error_code(),
+            Self::RepoReadOnly(err) => err.error_code(),
+            Self::DecryptFilenameError(err) => err.error_code(),
+            Self::Canceled => ErrorCode::Canceled,
+            Self::RemoteError(RemoteError::ApiError {
+                code: ApiErrorCode::AlreadyExists,
+                ..
+            }) => ErrorCode::FileDirAlreadyExists,
+            Self::RemoteError(err) => err.error_code(),
+        }
+    }
 }
 
 impl From<GetCipherError> for CreateDirError {
@@ -210,6 +290,8 @@ pub enum CreateFileError {
     #[error("{0}")]
     RepoLocked(#[from] RepoLockedError),
     #[error("{0}")]
+    RepoReadOnly(#[from] RepoReadOnlyError),
+    #[error("{0}")]
     DecryptFilenameError(#[from] DecryptFilenameError),
     #[error("canceled")]
     Canceled,
@@ -222,15 +304,31 @@ impl UserError for CreateFileError {
         match self {
             Self::RepoNotFound(err) => err.user_error(),
             Self::RepoLocked(err) => err.user_error(),
+            Self::RepoReadOnly(err) => err.user_error(),
             Self::DecryptFilenameError(err) => err.user_error(),
             Self::Canceled => self.to_string(),
             Self::RemoteError(RemoteError::ApiError {
                 code: ApiErrorCode::AlreadyExists,
                 ..
-            }) => String::from("File with this name already exists."),
+            }) => locale::message("error-file-already-exists"),
             Self::RemoteError(err) => err.user_error(),
         }
     }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::RepoNotFound(err) => err.error_code(),
+            Self::RepoLocked(err) => err.error_code(),
+            Self::RepoReadOnly(err) => err.error_code(),
+            Self::DecryptFilenameError(err) => err.error_code(),
+            Self::Canceled => ErrorCode::Canceled,
+            Self::RemoteError(RemoteError::ApiError {
+                code: ApiErrorCode::AlreadyExists,
+                ..
+            }) => ErrorCode::FileAlreadyExists,
+            Self::RemoteError(err) => err.error_code(),
+        }
+    }
 }
 
 impl From<GetCipherError> for CreateFileError {
@@ -247,6 +345,7 @@ impl From<UploadFileReaderError> for CreateFileError {
         match err {
             UploadFileReaderError::RepoNotFound(err) => Self::RepoNotFound(err),
             UploadFileReaderError::RepoLocked(err) => Self::RepoLocked(err),
+            UploadFileReaderError::RepoReadOnly(err) => Self::RepoReadOnly(err),
             UploadFileReaderError::DecryptFilenameError(err) => Self::DecryptFilenameError(err),
             UploadFileReaderError::Canceled => Self::Canceled,
             UploadFileReaderError::RemoteError(err) => Self::RemoteError(err),
@@ -261,6 +360,8 @@ pub enum EnsureDirError {
     #[error("{0}")]
     RepoLocked(#[from] RepoLockedError),
     #[error("{0}")]
+    RepoReadOnly(#[from] RepoReadOnlyError),
+    #[error("{0}")]
     DecryptFilenameError(#[from] DecryptFilenameError),
     #[error("canceled")]
     Canceled,
@@ -268,11 +369,36 @@ pub enum EnsureDirError {
     RemoteError(#[from] RemoteError),
 }
 
+impl UserError for EnsureDirError {
+    fn user_error(&self) -> String {
+        match self {
+            Self::RepoNotFound(err) => err.user_error(),
+            Self::RepoLocked(err) => err.user_error(),
+            Self::RepoReadOnly(err) => err.user_error(),
+            Self::DecryptFilenameError(err) => err.user_error(),
+            Self::Canceled => self.to_string(),
+            Self::RemoteError(err) => err.user_error(),
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::RepoNotFound(err) => err.error_code(),
+            Self::RepoLocked(err) => err.error_code(),
+            Self::RepoReadOnly(err) => err.error_code(),
+            Self::DecryptFilenameError(err) => err.error_code(),
+            Self::Canceled => ErrorCode::Canceled,
+            Self::RemoteError(err) => err.error_code(),
+        }
+    }
+}
+
 impl From<CreateDirError> for EnsureDirError {
     fn from(err: CreateDirError) -> Self {
         match err {
             CreateDirError::RepoNotFound(err) => Self::RepoNotFound(err),
             CreateDirError::RepoLocked(err) => Self::RepoLocked(err),
+            CreateDirError::RepoReadOnly(err) => Self::RepoReadOnly(err),
             CreateDirError::DecryptFilenameError(err) => Self::DecryptFilenameError(err),
             CreateDirError::Canceled => Self::Canceled,
             CreateDirError::RemoteError(err) => Self::RemoteError(err),
@@ -297,6 +423,8 @@ pub enum RenameFileError {
     #[error("{0}")]
     RepoLocked(#[from] RepoLockedError),
     #[error("{0}")]
+    RepoReadOnly(#[from] RepoReadOnlyError),
+    #[error("{0}")]
     DecryptFilenameError(#[from] DecryptFilenameError),
     #[error("rename root")]
     RenameRoot,
@@ -309,11 +437,23 @@ impl UserError for RenameFileError {
         match self {
             Self::RepoNotFound(err) => err.user_error(),
             Self::RepoLocked(err) => err.user_error(),
+            Self::RepoReadOnly(err) => err.user_error(),
             Self::DecryptFilenameError(err) => err.user_error(),
-            Self::RenameRoot => "Cannot rename root folder".into(),
+            Self::RenameRoot => locale::message("error-rename-root"),
             Self::RemoteError(err) => err.user_error(),
         }
     }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::RepoNotFound(err) => err.error_code(),
+            Self::RepoLocked(err) => err.error_code(),
+            Self::RepoReadOnly(err) => err.error_code(),
+            Self::DecryptFilenameError(err) => err.error_code(),
+            Self::RenameRoot => ErrorCode::FileRenameRoot,
+            Self::RemoteError(err) => err.error_code(),
+        }
+    }
 }
 
 impl From<GetCipherError> for RenameFileError {
@@ -334,6 +474,8 @@ pub enum CopyFileError {
     #[error("{0}")]
     RepoLocked(#[from] RepoLockedError),
     #[error("{0}")]
+    RepoReadOnly(#[from] RepoReadOnlyError),
+    #[error("{0}")]
     DecryptFilenameError(#[from] DecryptFilenameError),
     #[error("{0}")]
     RemoteError(#[from] RemoteError),
@@ -345,10 +487,22 @@ impl UserError for CopyFileError {
             Self::InvalidPath => self.to_string(),
             Self::RepoNotFound(err) => err.user_error(),
             Self::RepoLocked(err) => err.user_error(),
+            Self::RepoReadOnly(err) => err.user_error(),
             Self::DecryptFilenameError(err) => err.user_error(),
             Self::RemoteError(err) => err.user_error(),
         }
     }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::InvalidPath => ErrorCode::InvalidPath,
+            Self::RepoNotFound(err) => err.error_code(),
+            Self::RepoLocked(err) => err.error_code(),
+            Self::RepoReadOnly(err) => err.error_code(),
+            Self::DecryptFilenameError(err) => err.error_code(),
+            Self::RemoteError(err) => err.error_code(),
+        }
+    }
 }
 
 #[derive(Error, Debug, Clone, PartialEq)]
@@ -360,6 +514,8 @@ pub enum MoveFileError {
     #[error("{0}")]
     RepoLocked(#[from] RepoLockedError),
     #[error("{0}")]
+    RepoReadOnly(#[from] RepoReadOnlyError),
+    #[error("{0}")]
     DecryptFilenameError(#[from] DecryptFilenameError),
     #[error("move root")]
     MoveRoot,
@@ -373,11 +529,24 @@ impl UserError for MoveFileError {
             Self::InvalidPath => self.to_string(),
             Self::RepoNotFound(err) => err.user_error(),
             Self::RepoLocked(err) => err.user_error(),
+            Self::RepoReadOnly(err) => err.user_error(),
             Self::DecryptFilenameError(err) => err.user_error(),
-            Self::MoveRoot => "Cannot move root folder".into(),
+            Self::MoveRoot => locale::message("error-move-root"),
             Self::RemoteError(err) => err.user_error(),
         }
     }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::InvalidPath => ErrorCode::InvalidPath,
+            Self::RepoNotFound(err) => err.error_code(),
+            Self::RepoLocked(err) => err.error_code(),
+            Self::RepoReadOnly(err) => err.error_code(),
+            Self::DecryptFilenameError(err) => err.error_code(),
+            Self::MoveRoot => ErrorCode::FileMoveRoot,
+            Self::RemoteError(err) => err.error_code(),
+        }
+    }
 }
 
 impl From<CopyFileError> for MoveFileError {
@@ -386,6 +555,7 @@ impl From<CopyFileError> for MoveFileError {
             CopyFileError::InvalidPath => Self::InvalidPath,
             CopyFileError::RepoNotFound(err) => Self::RepoNotFound(err),
             CopyFileError::RepoLocked(err) => Self::RepoLocked(err),
+            CopyFileError::RepoReadOnly(err) => Self::RepoReadOnly(err),
             CopyFileError::DecryptFilenameError(err) => Self::DecryptFilenameError(err),
             CopyFileError::RemoteError(err) => Self::RemoteError(err),
         }
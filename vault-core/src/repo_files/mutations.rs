@@ -137,6 +137,12 @@ pub fn handle_remote_files_mutation(
                 remote_path_utils::parent_path(path).map(|parent_path| (mount_id, parent_path))
             });
 
+    let remote_updated_files = mutation_state
+        .remote_files
+        .updated_files
+        .iter()
+        .map(|(mount_id, path)| (mount_id, path.to_owned()));
+
     let files_to_decrypt: HashSet<(MountId, RemotePath, RepoId, EncryptedPath)> =
         remote_files_to_repo_files(
             state,
@@ -150,7 +156,8 @@ pub fn handle_remote_files_mutation(
                 .chain(remote_moved_to_files)
                 .chain(remote_moved_to_files_parents)
                 .chain(remote_tags_updated)
-                .chain(remote_tags_updated_parents),
+                .chain(remote_tags_updated_parents)
+                .chain(remote_updated_files),
         )
         .collect();
 
@@ -13,15 +13,17 @@ use vault_crypto::data_cipher::encrypted_size;
 use crate::{
     cipher::decrypt_on_progress::decrypt_on_progress,
     common::state::BoxAsyncRead,
-    dialogs, remote,
+    dialogs, locale, remote,
     remote_files::RemoteFilesService,
     repo_files_read::{
-        errors::GetFilesReaderError, state::RepoFileReaderProvider, RepoFilesReadService,
+        errors::GetFilesReaderError,
+        state::{RepoFileRangeReader, RepoFileReadRange, RepoFileReaderProvider},
+        RepoFilesReadService,
     },
     repo_files_tags::RepoFilesTagsService,
     repos::{
-        errors::{GetCipherError, RepoNotFoundError},
-        ReposService,
+        errors::{GetCipherError, RepoNotFoundError, RepoReadOnlyError},
+        selectors as repos_selectors, ReposService,
     },
     store,
     types::{
@@ -110,6 +112,12 @@ impl RepoFilesService {
             .with_state(|state| selectors::select_repo_path_to_mount_path(state, repo_id, path))
     }
 
+    /// Files in read-only repos cannot be changed.
+    pub fn check_writable(&self, repo_id: &RepoId) -> Result<(), RepoReadOnlyError> {
+        self.store
+            .with_state(|state| repos_selectors::select_check_writable(state, repo_id))
+    }
+
     pub async fn load_files(
         &self,
         repo_id: &RepoId,
@@ -168,6 +176,38 @@ impl RepoFilesService {
             .get_files_reader(vec![file])
     }
 
+    /// The file is loaded if it is not in the state yet.
+    pub async fn get_file_range_reader(
+        &self,
+        repo_id: &RepoId,
+        path: &EncryptedPath,
+        range: Option<&RepoFileReadRange>,
+    ) -> Result<RepoFileRangeReader, GetFilesReaderError> {
+        let file_id = selectors::get_file_id(repo_id, path);
+
+        if self
+            .store
+            .with_state(|state| selectors::select_file(state, &file_id).is_none())
+        {
+            self.load_file(repo_id, path)
+                .await
+                .map_err(|err| match err {
+                    LoadFileError::RepoNotFound(err) => GetFilesReaderError::RepoNotFound(err),
+                    LoadFileError::RepoLocked(err) => GetFilesReaderError::RepoLocked(err),
+                    LoadFileError::RemoteError(err) => GetFilesReaderError::RemoteError(err),
+                })?;
+        }
+
+        let file = self
+            .store
+            .with_state(|state| selectors::select_file(state, &file_id).cloned())
+            .ok_or(GetFilesReaderError::FileNotFound)?;
+
+        self.repo_files_read_service
+            .get_file_range_reader(&file, range)
+            .await
+    }
+
     pub async fn upload_file_reader(
         self: Arc<Self>,
         repo_id: &RepoId,
@@ -178,6 +218,8 @@ impl RepoFilesService {
         conflict_resolution: RepoFilesUploadConflictResolution,
         on_progress: Option<Box<dyn Fn(usize) + Send + Sync>>,
     ) -> Result<RepoFilesUploadResult, UploadFileReaderError> {
+        self.check_writable(repo_id)?;
+
         self.clone().ensure_dirs(repo_id, parent_path).await?;
 
         let cipher = self.repos_service.get_cipher(&repo_id)?;
@@ -235,19 +277,24 @@ impl RepoFilesService {
         files: &[(RepoId, EncryptedPath)],
         before_delete: Option<Box<dyn Fn() + Send + 'static>>,
     ) -> Result<(), DeleteFileError> {
+        for (repo_id, _) in files {
+            self.check_writable(repo_id)?;
+        }
+
         if self
             .dialogs_service
-            .show(dialogs::state::DialogShowOptions {
-                title: String::from("Delete files"),
-                message: Some(if files.len() == 1 {
-                    String::from("Do you really want to delete 1 item?")
-                } else {
-                    format!("Do you really want to delete {} items?", files.len())
-                }),
-                confirm_button_text: String::from("Delete"),
-                cancel_button_text: Some(String::from("Cancel")),
-                ..self.dialogs_service.build_confirm()
-            })
+            .show(locale::with_store_locale(&self.store, || {
+                dialogs::state::DialogShowOptions {
+                    title: locale::message("dialog-delete-files-title"),
+                    message: Some(locale::message_args(
+                        "dialog-delete-files-message",
+                        &[("count", files.len().into())],
+                    )),
+                    confirm_button_text: locale::message("dialog-delete-files-confirm"),
+                    cancel_button_text: Some(locale::message("dialog-cancel")),
+                    ..self.dialogs_service.build_confirm()
+                }
+            }))
             .await
             .is_some()
         {
@@ -256,12 +303,7 @@ impl RepoFilesService {
             }
 
             for (repo_id, path) in files {
-                let (mount_id, remote_path) = self.get_repo_mount_path(repo_id, path)?;
-
-                self.remote_files_service
-                    .delete_file(&mount_id, &remote_path)
-                    .await
-                    .map_err(DeleteFileError::RemoteError)?;
+                self.delete_file(repo_id, path).await?;
             }
         } else {
             return Err(DeleteFileError::Canceled);
@@ -270,11 +312,29 @@ impl RepoFilesService {
         Ok(())
     }
 
+    /// Deletes the file without a confirmation dialog.
+    pub async fn delete_file(
+        &self,
+        repo_id: &RepoId,
+        path: &EncryptedPath,
+    ) -> Result<(), DeleteFileError> {
+        self.check_writable(repo_id)?;
+
+        let (mount_id, remote_path) = self.get_repo_mount_path(repo_id, path)?;
+
+        self.remote_files_service
+            .delete_file(&mount_id, &remote_path)
+            .await
+            .map_err(DeleteFileError::RemoteError)
+    }
+
     pub async fn create_dir(
         &self,
         repo_id: &RepoId,
         parent_path: &EncryptedPath,
     ) -> Result<(DecryptedName, EncryptedPath), CreateDirError> {
+        self.check_writable(repo_id)?;
+
         let input_value_validator_store = self.store.clone();
         let input_value_validator_repo_id = repo_id.to_owned();
         let input_value_validator_parent_path = parent_path.to_owned();
@@ -283,13 +343,13 @@ impl RepoFilesService {
         let name = match self
             .dialogs_service
             .show_validator(
-                dialogs::state::DialogShowOptions {
-                    input_placeholder: Some(String::from("Folder name")),
-                    confirm_button_text: String::from("Create folder"),
+                locale::with_store_locale(&self.store, || dialogs::state::DialogShowOptions {
+                    input_placeholder: Some(locale::message("dialog-create-folder-placeholder")),
+                    confirm_button_text: locale::message("dialog-create-folder-confirm"),
                     ..self
                         .dialogs_service
-                        .build_prompt(String::from("Enter new folder name"))
-                },
+                        .build_prompt(locale::message("dialog-create-folder-title"))
+                }),
                 move |value| {
                     let new_name = DecryptedName(value.clone());
                     let encrypted_new_name =
@@ -329,6 +389,8 @@ impl RepoFilesService {
         parent_path: &EncryptedPath,
         name: EncryptedName,
     ) -> Result<(), CreateDirError> {
+        self.check_writable(repo_id)?;
+
         let (mount_id, remote_parent_path) = self.get_repo_mount_path(repo_id, parent_path)?;
 
         self.remote_files_service
@@ -446,6 +508,8 @@ impl RepoFilesService {
         parent_path: &EncryptedPath,
         name: &str,
     ) -> Result<(DecryptedName, EncryptedPath), CreateFileError> {
+        self.check_writable(repo_id)?;
+
         let input_value_validator_store = self.store.clone();
         let input_value_validator_repo_id = repo_id.to_owned();
         let input_value_validator_parent_path = parent_path.to_owned();
@@ -455,15 +519,15 @@ impl RepoFilesService {
         let name = match self
             .dialogs_service
             .show_validator(
-                dialogs::state::DialogShowOptions {
+                locale::with_store_locale(&self.store, || dialogs::state::DialogShowOptions {
                     input_value: name.to_owned(),
                     input_value_selected,
-                    input_placeholder: Some(String::from("File name")),
-                    confirm_button_text: String::from("Create file"),
+                    input_placeholder: Some(locale::message("dialog-create-file-placeholder")),
+                    confirm_button_text: locale::message("dialog-create-file-confirm"),
                     ..self
                         .dialogs_service
-                        .build_prompt(String::from("Enter new file name"))
-                },
+                        .build_prompt(locale::message("dialog-create-file-title"))
+                }),
                 move |value| {
                     let new_name = DecryptedName(value.clone());
                     let encrypted_new_name =
@@ -522,11 +586,11 @@ impl RepoFilesService {
         repo_id: &RepoId,
         path: &EncryptedPath,
     ) -> Result<(), RenameFileError> {
-        let (mount_id, remote_path, original_name, typ) = match self.store.with_state(|state| {
+        self.check_writable(repo_id)?;
+
+        let (original_name, typ) = match self.store.with_state(|state| {
             selectors::select_file(state, &selectors::get_file_id(repo_id, path)).map(|file| {
                 (
-                    file.mount_id.clone(),
-                    file.remote_path.clone(),
                     file.decrypted_name().map(ToOwned::to_owned),
                     file.typ.clone(),
                 )
@@ -551,15 +615,16 @@ impl RepoFilesService {
         if let Some(name) = self
             .dialogs_service
             .show_validator(
-                dialogs::state::DialogShowOptions {
+                locale::with_store_locale(&self.store, || dialogs::state::DialogShowOptions {
                     input_value,
                     input_value_selected,
-                    input_placeholder: Some(String::from("New name")),
-                    confirm_button_text: String::from("Rename"),
-                    ..self
-                        .dialogs_service
-                        .build_prompt(format!("Enter new name for '{}'", original_name.0))
-                },
+                    input_placeholder: Some(locale::message("dialog-rename-placeholder")),
+                    confirm_button_text: locale::message("dialog-rename-confirm"),
+                    ..self.dialogs_service.build_prompt(locale::message_args(
+                        "dialog-rename-title",
+                        &[("name", original_name.0.as_str().into())],
+                    ))
+                }),
                 move |value| {
                     let new_name = DecryptedName(value.clone());
                     let encrypted_new_name =
@@ -580,17 +645,38 @@ impl RepoFilesService {
             .await
         {
             let name = DecryptedName(name?);
-            let encrypted_name = self.encrypt_filename(&repo_id, &name)?;
 
-            self.remote_files_service
-                .rename_file(&mount_id, &remote_path, RemoteName(encrypted_name.0))
-                .await
-                .map_err(RenameFileError::RemoteError)?;
+            self.rename_file_to_name(repo_id, path, &name).await?;
         }
 
         Ok(())
     }
 
+    /// Unlike `rename_file`, the new name is given instead of prompted for.
+    pub async fn rename_file_to_name(
+        &self,
+        repo_id: &RepoId,
+        path: &EncryptedPath,
+        name: &DecryptedName,
+    ) -> Result<(), RenameFileError> {
+        self.check_writable(repo_id)?;
+
+        let (mount_id, remote_path) = self.get_repo_mount_path(repo_id, path)?;
+
+        let encrypted_name = self.encrypt_filename(repo_id, name)?;
+
+        self.store.with_state(|state| {
+            selectors::select_check_rename_file(state, repo_id, path, name, &encrypted_name)
+        })?;
+
+        self.remote_files_service
+            .rename_file(&mount_id, &remote_path, RemoteName(encrypted_name.0))
+            .await
+            .map_err(RenameFileError::RemoteError)?;
+
+        Ok(())
+    }
+
     pub async fn copy_file(
         &self,
         repo_id: &RepoId,
@@ -600,12 +686,26 @@ impl RepoFilesService {
         let name =
             repo_encrypted_path_utils::path_to_name(path).ok_or(CopyFileError::InvalidPath)?;
 
-        let (mount_id, remote_path) = self.get_repo_mount_path(repo_id, path)?;
-
-        let (to_mount_id, to_remote_path) = self.get_repo_mount_path(
+        self.copy_file_to_path(
             repo_id,
+            path,
             &repo_encrypted_path_utils::join_path_name(to_parent_path, &name),
-        )?;
+        )
+        .await
+    }
+
+    /// Unlike `copy_file`, `to_path` also contains the new name.
+    pub async fn copy_file_to_path(
+        &self,
+        repo_id: &RepoId,
+        path: &EncryptedPath,
+        to_path: &EncryptedPath,
+    ) -> Result<(), CopyFileError> {
+        self.check_writable(repo_id)?;
+
+        let (mount_id, remote_path) = self.get_repo_mount_path(repo_id, path)?;
+
+        let (to_mount_id, to_remote_path) = self.get_repo_mount_path(repo_id, to_path)?;
 
         self.remote_files_service
             .copy_file(&mount_id, &remote_path, &to_mount_id, &to_remote_path)
@@ -622,15 +722,29 @@ impl RepoFilesService {
         let name =
             repo_encrypted_path_utils::path_to_name(path).ok_or(MoveFileError::InvalidPath)?;
 
-        let (mount_id, remote_path) = self.get_repo_mount_path(repo_id, path)?;
-
-        let (to_mount_id, to_path) = self.get_repo_mount_path(
+        self.move_file_to_path(
             repo_id,
+            path,
             &repo_encrypted_path_utils::join_path_name(to_parent_path, &name),
-        )?;
+        )
+        .await
+    }
+
+    /// Unlike `move_file`, `to_path` also contains the new name.
+    pub async fn move_file_to_path(
+        &self,
+        repo_id: &RepoId,
+        path: &EncryptedPath,
+        to_path: &EncryptedPath,
+    ) -> Result<(), MoveFileError> {
+        self.check_writable(repo_id)?;
+
+        let (mount_id, remote_path) = self.get_repo_mount_path(repo_id, path)?;
+
+        let (to_mount_id, to_remote_path) = self.get_repo_mount_path(repo_id, to_path)?;
 
         self.remote_files_service
-            .move_file(&mount_id, &remote_path, &to_mount_id, &to_path)
+            .move_file(&mount_id, &remote_path, &to_mount_id, &to_remote_path)
             .await
             .map_err(MoveFileError::RemoteError)
     }
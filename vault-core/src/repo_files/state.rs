@@ -227,6 +227,7 @@ pub struct RepoFilesMutationState {
     pub moved_files: Vec<(RepoId, EncryptedPath, EncryptedPath)>,
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub enum RepoFilesUploadConflictResolution {
     Overwrite {
         if_remote_size: Option<i64>,
@@ -234,6 +235,13 @@ pub enum RepoFilesUploadConflictResolution {
         if_remote_hash: Option<String>,
     },
     Error,
+    /// Like `Overwrite`, but if the remote file does not match the conditions
+    /// the file is uploaded with an unused name, like with `Error`.
+    OverwriteOrAutorename {
+        if_remote_size: Option<i64>,
+        if_remote_modified: Option<i64>,
+        if_remote_hash: Option<String>,
+    },
 }
 
 impl Into<RemoteFileUploadConflictResolution> for RepoFilesUploadConflictResolution {
@@ -243,6 +251,11 @@ impl Into<RemoteFileUploadConflictResolution> for RepoFilesUploadConflictResolut
                 if_remote_size,
                 if_remote_modified,
                 if_remote_hash,
+            }
+            | Self::OverwriteOrAutorename {
+                if_remote_size,
+                if_remote_modified,
+                if_remote_hash,
             } => RemoteFileUploadConflictResolution::Overwrite {
                 if_size: if_remote_size,
                 if_modified: if_remote_modified,
@@ -1,10 +1,15 @@
+use std::collections::HashSet;
+
 use crate::{
     common::state::Status,
+    eventstream::selectors as eventstream_selectors,
     repo_files::{
         errors::LoadFilesError,
         selectors as repo_files_selectors,
         state::{RepoFile, RepoFileSize, RepoFilesBreadcrumb},
     },
+    repo_files_thumbnails::selectors as repo_files_thumbnails_selectors,
+    repo_files_versions::selectors as repo_files_versions_selectors,
     repos::{
         errors::RepoLockedError,
         selectors as repos_selectors,
@@ -28,7 +33,9 @@ pub fn select_file_ids<'a>(
     repo_id: &RepoId,
     path: &EncryptedPath,
 ) -> impl Iterator<Item = &'a RepoFileId> {
-    repo_files_selectors::select_files(state, repo_id, path).map(|file| &file.id)
+    repo_files_selectors::select_files(state, repo_id, path)
+        .filter(|file| !repo_files_versions_selectors::is_versions_dir(file))
+        .map(|file| &file.id)
 }
 
 pub fn select_browser<'a>(
@@ -85,6 +92,10 @@ pub fn select_items<'a>(state: &'a store::State, browser_id: u32) -> Vec<RepoFil
                 .map(|file| RepoFilesBrowserItem {
                     file,
                     is_selected: select_is_selected(state, browser_id, &file.id),
+                    is_thumbnail_supported: repo_files_thumbnails_selectors::is_file_supported(
+                        file,
+                    ),
+                    thumbnail: repo_files_thumbnails_selectors::select_file_thumbnail(state, file),
                 })
                 .collect()
         })
@@ -107,7 +118,13 @@ pub fn select_selected_file_ids<'a>(
     browser_id: u32,
 ) -> Vec<&'a RepoFileId> {
     select_browser(state, browser_id)
-        .map(|browser| browser.selection.selection.iter().collect())
+        .map(|browser| {
+            browser
+                .file_ids
+                .iter()
+                .filter(|file_id| browser.selection.selection.contains(file_id))
+                .collect()
+        })
         .unwrap_or_else(|| Vec::new())
 }
 
@@ -189,12 +206,21 @@ pub fn select_info<'a>(state: &'a store::State, browser_id: u32) -> Option<RepoF
             .find(|item| item.is_selected)
             .map(|item| item.file)
             .filter(|_| selected_count == 1);
+        let is_read_only = repo_id
+            .map(|repo_id| repos_selectors::select_is_read_only(state, repo_id))
+            .unwrap_or(false);
         let can_download_selected = selected_count > 0;
-        let can_copy_selected = selected_count > 0;
-        let can_move_selected = selected_count > 0;
-        let can_delete_selected = selected_count > 0;
+        let can_copy_selected = selected_count > 0 && !is_read_only;
+        let can_move_selected = selected_count > 0 && !is_read_only;
+        let can_delete_selected = selected_count > 0 && !is_read_only;
         let repo_status = browser.repo_status.clone();
         let is_locked = browser.is_locked;
+        let is_reconnecting = browser
+            .location
+            .as_ref()
+            .filter(|loc| loc.eventstream_mount_subscription.is_some())
+            .is_some()
+            && eventstream_selectors::select_is_reconnecting(state);
 
         RepoFilesBrowserInfo {
             repo_id,
@@ -216,6 +242,8 @@ pub fn select_info<'a>(state: &'a store::State, browser_id: u32) -> Option<RepoF
             breadcrumbs,
             repo_status,
             is_locked,
+            is_read_only,
+            is_reconnecting,
         }
     })
 }
@@ -270,3 +298,29 @@ pub fn select_browsers_to_load(
 
     browser_ids
 }
+
+/// Browsers that could have missed events while the eventstream was
+/// reconnecting.
+pub fn select_browsers_to_resync(
+    state: &store::State,
+    mutation_state: &store::MutationState,
+) -> Vec<u32> {
+    let subscribers = eventstream_selectors::select_resync_subscribers(mutation_state)
+        .map(String::as_str)
+        .collect::<HashSet<_>>();
+
+    if subscribers.is_empty() {
+        return vec![];
+    }
+
+    state
+        .repo_files_browsers
+        .browsers
+        .values()
+        .filter(|browser| {
+            subscribers.contains(get_eventstream_mount_subscriber(browser.id).as_str())
+        })
+        .filter(|browser| select_is_unlocked(state, browser.id))
+        .map(|browser| browser.id)
+        .collect()
+}
@@ -17,6 +17,11 @@ use crate::{
     repo_files_read::{
         errors::GetFilesReaderError, state::RepoFileReaderProvider, RepoFilesReadService,
     },
+    repo_files_rename::{
+        errors::RenameFilesError,
+        state::{RepoFilesRenamePattern, RepoFilesRenamePreviewItem, RepoFilesRenameResult},
+        RepoFilesRenameService,
+    },
     runtime::runtime,
     sort::state::SortDirection,
     store,
@@ -29,8 +34,10 @@ pub struct RepoFilesBrowsersService {
     repo_files_service: Arc<RepoFilesService>,
     repo_files_read_service: Arc<RepoFilesReadService>,
     repo_files_move_service: Arc<RepoFilesMoveService>,
+    repo_files_rename_service: Arc<RepoFilesRenameService>,
     store: Arc<store::Store>,
     repos_subscription_id: u32,
+    eventstream_subscription_id: u32,
     mutation_subscription_id: u32,
 }
 
@@ -39,6 +46,7 @@ impl RepoFilesBrowsersService {
         repo_files_service: Arc<RepoFilesService>,
         repo_files_read_service: Arc<RepoFilesReadService>,
         repo_files_move_service: Arc<RepoFilesMoveService>,
+        repo_files_rename_service: Arc<RepoFilesRenameService>,
         store: Arc<store::Store>,
         runtime: Arc<runtime::BoxRuntime>,
     ) -> Self {
@@ -74,6 +82,38 @@ impl RepoFilesBrowsersService {
             }),
         );
 
+        let eventstream_subscription_id = store.get_next_id();
+        let eventstream_subscription_repo_files_service = repo_files_service.clone();
+        let eventstream_subscription_store = store.clone();
+        let eventstream_subscription_runtime = runtime.clone();
+
+        store.on(
+            eventstream_subscription_id,
+            &[store::Event::Eventstream],
+            Box::new(move |mutation_state, add_side_effect| {
+                for browser_id in eventstream_subscription_store
+                    .with_state(|state| selectors::select_browsers_to_resync(state, mutation_state))
+                {
+                    let repo_files_service = eventstream_subscription_repo_files_service.clone();
+                    let store = eventstream_subscription_store.clone();
+                    let runtime = eventstream_subscription_runtime.clone();
+
+                    add_side_effect(Box::new(move || {
+                        // load errors are displayed inside browser
+                        runtime.spawn(
+                            Self::load_files_inner(
+                                repo_files_service.clone(),
+                                store.clone(),
+                                browser_id,
+                            )
+                            .map(|_| ())
+                            .boxed(),
+                        )
+                    }))
+                }
+            }),
+        );
+
         let mutation_subscription_id = store.get_next_id();
 
         store.mutation_on(
@@ -88,8 +128,10 @@ impl RepoFilesBrowsersService {
             repo_files_service,
             repo_files_read_service,
             repo_files_move_service,
+            repo_files_rename_service,
             store,
             repos_subscription_id,
+            eventstream_subscription_id,
             mutation_subscription_id,
         }
     }
@@ -304,11 +346,52 @@ impl RepoFilesBrowsersService {
             .show(repo_id, paths, mode)
             .await
     }
+
+    fn select_selected_repo_id_paths(
+        &self,
+        browser_id: u32,
+    ) -> Option<(RepoId, Vec<EncryptedPath>)> {
+        self.store.with_state(|state| {
+            let repo_id = selectors::select_repo_id(state, browser_id)?.to_owned();
+            let paths = selectors::select_selected_paths(state, browser_id);
+
+            Some((repo_id, paths))
+        })
+    }
+
+    pub fn rename_selected_preview(
+        &self,
+        browser_id: u32,
+        pattern: &RepoFilesRenamePattern,
+    ) -> Result<Vec<RepoFilesRenamePreviewItem>, RenameFilesError> {
+        match self.select_selected_repo_id_paths(browser_id) {
+            Some((repo_id, paths)) => self
+                .repo_files_rename_service
+                .preview(&repo_id, &paths, pattern),
+            None => Ok(vec![]),
+        }
+    }
+
+    pub async fn rename_selected(
+        &self,
+        browser_id: u32,
+        pattern: &RepoFilesRenamePattern,
+    ) -> Result<Vec<RepoFilesRenameResult>, RenameFilesError> {
+        match self.select_selected_repo_id_paths(browser_id) {
+            Some((repo_id, paths)) => {
+                self.repo_files_rename_service
+                    .rename_files(&repo_id, &paths, pattern)
+                    .await
+            }
+            None => Ok(vec![]),
+        }
+    }
 }
 
 impl Drop for RepoFilesBrowsersService {
     fn drop(&mut self) {
         self.store.remove_listener(self.repos_subscription_id);
+        self.store.remove_listener(self.eventstream_subscription_id);
         self.store
             .mutation_remove_listener(self.mutation_subscription_id);
     }
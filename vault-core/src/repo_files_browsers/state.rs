@@ -7,6 +7,7 @@ use crate::{
         errors::LoadFilesError,
         state::{RepoFile, RepoFilesBreadcrumb, RepoFilesSort},
     },
+    repo_files_thumbnails::state::RepoFileThumbnail,
     repos::errors::RepoInfoError,
     selection::state::{Selection, SelectionSummary},
     store::NextId,
@@ -17,6 +18,8 @@ use crate::{
 pub struct RepoFilesBrowserItem<'a> {
     pub file: &'a RepoFile,
     pub is_selected: bool,
+    pub is_thumbnail_supported: bool,
+    pub thumbnail: Option<&'a RepoFileThumbnail>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -40,6 +43,9 @@ pub struct RepoFilesBrowserInfo<'a> {
     pub breadcrumbs: Option<&'a [RepoFilesBreadcrumb]>,
     pub repo_status: Status<RepoInfoError>,
     pub is_locked: bool,
+    pub is_read_only: bool,
+    /// The eventstream is reconnecting, the files may be stale.
+    pub is_reconnecting: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -2,11 +2,13 @@ use thiserror::Error;
 
 use crate::{
     cipher::errors::DecryptFilenameError,
+    locale,
     remote::RemoteError,
     repo_files::errors::{LoadFilesError, UploadFileReaderError},
-    repos::errors::{GetCipherError, RepoLockedError, RepoNotFoundError},
+    repo_files_versions::errors::CreateVersionError,
+    repos::errors::{GetCipherError, RepoLockedError, RepoNotFoundError, RepoReadOnlyError},
     transfers::errors::TransferError,
-    user_error::UserError,
+    user_error::{ErrorCode, UserError},
 };
 
 #[derive(Error, Debug, Clone, PartialEq)]
@@ -27,6 +29,14 @@ impl UserError for LoadDetailsError {
             Self::RemoteError(err) => err.user_error(),
         }
     }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::RepoNotFound(err) => err.error_code(),
+            Self::RepoLocked(err) => err.error_code(),
+            Self::RemoteError(err) => err.error_code(),
+        }
+    }
 }
 
 impl From<LoadFilesError> for LoadDetailsError {
@@ -63,12 +73,24 @@ impl UserError for LoadContentError {
             Self::RepoNotFound(err) => err.user_error(),
             Self::RepoLocked(err) => err.user_error(),
             Self::TransferError(err) => err.user_error(),
-            Self::FileNotFound => "File not found".into(),
+            Self::FileNotFound => locale::message("error-file-not-found"),
             Self::DecryptFilenameError(err) => err.user_error(),
             Self::AlreadyLoading => self.to_string(),
             Self::LoadFilterMismatch => self.to_string(),
         }
     }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::RepoNotFound(err) => err.error_code(),
+            Self::RepoLocked(err) => err.error_code(),
+            Self::TransferError(err) => err.error_code(),
+            Self::FileNotFound => ErrorCode::FileNotFound,
+            Self::DecryptFilenameError(err) => err.error_code(),
+            Self::AlreadyLoading => ErrorCode::DetailsAlreadyLoading,
+            Self::LoadFilterMismatch => ErrorCode::DetailsLoadFilterMismatch,
+        }
+    }
 }
 
 impl From<GetCipherError> for LoadContentError {
@@ -87,6 +109,8 @@ pub enum SaveError {
     #[error("{0}")]
     RepoLocked(#[from] RepoLockedError),
     #[error("{0}")]
+    RepoReadOnly(#[from] RepoReadOnlyError),
+    #[error("{0}")]
     DecryptFilenameError(#[from] DecryptFilenameError),
     #[error("{0}")]
     DecryptDataError(String),
@@ -104,6 +128,8 @@ pub enum SaveError {
     Canceled,
     #[error("cannot save root")]
     CannotSaveRoot,
+    #[error("failed to create version: {0}")]
+    CreateVersionError(#[from] CreateVersionError),
     #[error("{0}")]
     RemoteError(#[from] RemoteError),
 }
@@ -113,6 +139,7 @@ impl UserError for SaveError {
         match self {
             Self::RepoNotFound(err) => err.user_error(),
             Self::RepoLocked(err) => err.user_error(),
+            Self::RepoReadOnly(err) => err.user_error(),
             Self::DecryptFilenameError(err) => err.user_error(),
             Self::DecryptDataError(err) => err.clone(),
             Self::AlreadySaving => self.to_string(),
@@ -122,9 +149,29 @@ impl UserError for SaveError {
             Self::DiscardChanges { .. } => self.to_string(),
             Self::Canceled => self.to_string(),
             Self::CannotSaveRoot => self.to_string(),
+            Self::CreateVersionError(err) => err.user_error(),
             Self::RemoteError(err) => err.user_error(),
         }
     }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::RepoNotFound(err) => err.error_code(),
+            Self::RepoLocked(err) => err.error_code(),
+            Self::RepoReadOnly(err) => err.error_code(),
+            Self::DecryptFilenameError(err) => err.error_code(),
+            Self::DecryptDataError(_) => ErrorCode::CipherDecryptData,
+            Self::AlreadySaving => ErrorCode::DetailsAlreadySaving,
+            Self::NotDirty => ErrorCode::DetailsNotDirty,
+            Self::InvalidState => ErrorCode::DetailsInvalidState,
+            Self::AutosaveNotPossible => ErrorCode::DetailsAutosaveNotPossible,
+            Self::DiscardChanges { .. } => ErrorCode::DetailsDiscardChanges,
+            Self::Canceled => ErrorCode::Canceled,
+            Self::CannotSaveRoot => ErrorCode::DetailsCannotSaveRoot,
+            Self::CreateVersionError(err) => err.error_code(),
+            Self::RemoteError(err) => err.error_code(),
+        }
+    }
 }
 
 impl From<GetCipherError> for SaveError {
@@ -151,6 +198,7 @@ impl From<UploadFileReaderError> for SaveError {
         match err {
             UploadFileReaderError::RepoNotFound(err) => Self::RepoNotFound(err),
             UploadFileReaderError::RepoLocked(err) => Self::RepoLocked(err),
+            UploadFileReaderError::RepoReadOnly(err) => Self::RepoReadOnly(err),
             UploadFileReaderError::DecryptFilenameError(err) => Self::DecryptFilenameError(err),
             UploadFileReaderError::Canceled => Self::Canceled,
             UploadFileReaderError::RemoteError(err) => Self::RemoteError(err),
@@ -170,4 +218,10 @@ impl UserError for SetContentError {
             Self::RepoLocked(err) => err.user_error(),
         }
     }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::RepoLocked(err) => err.error_code(),
+        }
+    }
 }
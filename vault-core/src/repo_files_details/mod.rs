@@ -3,5 +3,6 @@ pub mod mutations;
 pub mod selectors;
 pub mod service;
 pub mod state;
+pub mod text_merge;
 
 pub use self::service::RepoFilesDetailsService;
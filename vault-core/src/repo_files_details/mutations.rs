@@ -1,11 +1,15 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
+
+use zeroize::Zeroizing;
 
 use crate::{
+    cipher::Cipher,
     common::state::Status,
     eventstream::{
         mutations::{add_mount_subscriber, remove_mount_subscriber},
         state::MountSubscription,
     },
+    files::file_category::FileCategory,
     remote_files::errors::RemoteFilesErrors,
     repo_files::{
         errors::{DeleteFileError, LoadFilesError},
@@ -24,10 +28,12 @@ use super::{
     errors::{LoadContentError, SaveError, SetContentError},
     selectors,
     state::{
-        RepoFilesDetails, RepoFilesDetailsContent, RepoFilesDetailsContentData,
-        RepoFilesDetailsContentDataBytes, RepoFilesDetailsContentLoading, RepoFilesDetailsLocation,
-        RepoFilesDetailsOptions, SaveInitiator,
+        RepoFilesDetails, RepoFilesDetailsConflict, RepoFilesDetailsContent,
+        RepoFilesDetailsContentData, RepoFilesDetailsContentDataBytes,
+        RepoFilesDetailsContentLoading, RepoFilesDetailsLocation, RepoFilesDetailsOptions,
+        RepoFilesDetailsTextMerge, SaveInitiator,
     },
+    text_merge,
 };
 
 pub fn create_location_eventstream_mount_subscription(
@@ -91,12 +97,14 @@ pub fn create_location(
             loading: None,
             version: 0,
             transfer_id: None,
+            base: None,
         },
         is_editing,
         is_dirty: false,
         save_status: Status::Initial,
         delete_status: Status::Initial,
         should_destroy: false,
+        conflict: None,
     })
 }
 
@@ -192,6 +200,19 @@ pub fn destroy(
     }
 }
 
+pub fn destroy_all(
+    state: &mut store::State,
+    notify: &store::Notify,
+    mutation_state: &mut store::MutationState,
+) -> Vec<(Option<u32>, Option<u32>)> {
+    let details_ids: Vec<u32> = state.repo_files_details.details.keys().cloned().collect();
+
+    details_ids
+        .into_iter()
+        .map(|details_id| destroy(state, notify, mutation_state, details_id))
+        .collect()
+}
+
 pub fn loading(state: &mut store::State, notify: &store::Notify, details_id: u32) {
     let details = match state.repo_files_details.details.get_mut(&details_id) {
         Some(details) => details,
@@ -240,6 +261,41 @@ pub fn loaded(
     }
 }
 
+/// Decrypts the bytes when the repo is unlocked and encrypts them when it is
+/// locked. Returns true if the bytes were changed.
+fn update_content_data_bytes(
+    bytes: &mut RepoFilesDetailsContentDataBytes,
+    cipher: Option<&Arc<Cipher>>,
+    repo_exists: bool,
+) -> bool {
+    match (&*bytes, cipher) {
+        (RepoFilesDetailsContentDataBytes::Encrypted(encrypted), Some(cipher)) => {
+            match cipher.decrypt_vec(encrypted) {
+                Ok(decrypted) => {
+                    *bytes = RepoFilesDetailsContentDataBytes::Decrypted(
+                        Zeroizing::new(decrypted),
+                        cipher.clone(),
+                    );
+
+                    true
+                }
+                Err(_) => false,
+            }
+        }
+        (RepoFilesDetailsContentDataBytes::Decrypted(decrypted, cipher), None) if repo_exists => {
+            match cipher.encrypt_vec(decrypted) {
+                Ok(encrypted) => {
+                    *bytes = RepoFilesDetailsContentDataBytes::Encrypted(encrypted);
+
+                    true
+                }
+                Err(_) => false,
+            }
+        }
+        _ => false,
+    }
+}
+
 pub fn update_details(
     state: &mut store::State,
     notify: &store::Notify,
@@ -319,30 +375,22 @@ pub fn update_details(
 
     if let Some(location) = &mut details.location {
         if let Some(data) = &mut location.content.data {
-            match (&mut data.bytes, &cipher) {
-                (RepoFilesDetailsContentDataBytes::Encrypted(bytes), Some(cipher)) => {
-                    if let Ok(decrypted) = cipher.decrypt_vec(&bytes) {
-                        data.bytes =
-                            RepoFilesDetailsContentDataBytes::Decrypted(decrypted, cipher.clone());
+            if update_content_data_bytes(&mut data.bytes, cipher.as_ref(), repo_exists) {
+                location.content.version += 1;
 
-                        location.content.version += 1;
+                dirty = true;
+            }
+        }
 
-                        dirty = true;
-                    }
-                }
-                (RepoFilesDetailsContentDataBytes::Decrypted(bytes, cipher), None)
-                    if repo_exists =>
-                {
-                    if let Ok(encrypted) = cipher.encrypt_vec(&bytes) {
-                        data.bytes = RepoFilesDetailsContentDataBytes::Encrypted(encrypted);
+        if let Some(base) = &mut location.content.base {
+            update_content_data_bytes(base, cipher.as_ref(), repo_exists);
+        }
 
-                        location.content.version += 1;
+        // the remote version is loaded again after the repo is unlocked
+        if cipher.is_none() && location.conflict.is_some() {
+            location.conflict = None;
 
-                        dirty = true;
-                    }
-                }
-                _ => {}
-            }
+            dirty = true;
         }
     }
 
@@ -543,6 +591,13 @@ pub fn content_transfer_removed(
 }
 
 pub fn edit(state: &mut store::State, notify: &store::Notify, details_id: u32) {
+    if selectors::select_details_location(state, details_id)
+        .map(|location| repos::selectors::select_is_read_only(state, &location.repo_id))
+        .unwrap_or(false)
+    {
+        return;
+    }
+
     let location = match selectors::select_details_location_mut(state, details_id) {
         Some(location) => location,
         _ => return,
@@ -568,6 +623,8 @@ pub fn edit_cancel(
         location.is_editing = false;
         location.is_dirty = false;
         location.save_status = Status::Initial;
+        location.content.base = None;
+        location.conflict = None;
 
         if is_discarded {
             // this will reload the content
@@ -596,9 +653,16 @@ pub fn set_content(
             RepoFilesDetailsContentDataBytes::Encrypted(_) => {
                 return Err(SetContentError::RepoLocked(RepoLockedError))
             }
-            RepoFilesDetailsContentDataBytes::Decrypted(bytes, _) => {
-                if bytes != &content {
-                    *bytes = content;
+            RepoFilesDetailsContentDataBytes::Decrypted(bytes, cipher) => {
+                if **bytes != content {
+                    let old_bytes = std::mem::replace(bytes, Zeroizing::new(content));
+
+                    if !location.is_dirty {
+                        location.content.base = Some(RepoFilesDetailsContentDataBytes::Decrypted(
+                            old_bytes,
+                            cipher.clone(),
+                        ));
+                    }
 
                     location.content.version += 1;
 
@@ -654,6 +718,7 @@ pub fn saving(
 
     if matches!(initiator, SaveInitiator::Autosave)
         && (selectors::get_is_conflict(true, Some(&data), remote_file, &location.save_status)
+            || location.conflict.is_some()
             || is_deleted)
     {
         return Err(SaveError::AutosaveNotPossible);
@@ -687,6 +752,7 @@ pub fn saved(
     notify: &store::Notify,
     details_id: u32,
     saved_version: u32,
+    saved_bytes: RepoFilesDetailsContentDataBytes,
     res: Result<(EncryptedPath, RepoFilesUploadResult, bool), SaveError>,
 ) {
     let location = match selectors::select_details_location_mut(state, details_id) {
@@ -708,8 +774,12 @@ pub fn saved(
             }
             if location.content.version == saved_version {
                 location.is_dirty = false;
+                location.content.base = None;
+            } else {
+                location.content.base = Some(saved_bytes);
             }
             location.save_status = Status::Initial;
+            location.conflict = None;
             if should_destroy {
                 location.should_destroy = true;
             }
@@ -736,6 +806,200 @@ pub fn saved(
     }
 }
 
+pub fn conflict_loading(
+    state: &mut store::State,
+    notify: &store::Notify,
+    details_id: u32,
+) -> Result<RepoFile, LoadContentError> {
+    let file = selectors::select_file(state, details_id)
+        .cloned()
+        .ok_or(LoadContentError::FileNotFound)?;
+
+    let remote_file = selectors::select_remote_file(state, details_id)
+        .cloned()
+        .ok_or(LoadContentError::FileNotFound)?;
+
+    let location = match selectors::select_details_location_mut(state, details_id) {
+        Some(location) => location,
+        _ => return Err(LoadContentError::FileNotFound),
+    };
+
+    if let Some(conflict) = &location.conflict {
+        if matches!(conflict.status, Status::Loading { .. }) {
+            return Err(LoadContentError::AlreadyLoading);
+        }
+    }
+
+    notify(store::Event::RepoFilesDetails);
+
+    location.conflict = Some(RepoFilesDetailsConflict {
+        status: Status::Loading { loaded: false },
+        remote_size: remote_file.size,
+        remote_modified: remote_file.modified,
+        remote_hash: remote_file.hash,
+        theirs: None,
+        text_merge: None,
+    });
+
+    Ok(file)
+}
+
+fn decrypted_text(bytes: &RepoFilesDetailsContentDataBytes) -> Option<&str> {
+    match bytes {
+        RepoFilesDetailsContentDataBytes::Encrypted(_) => None,
+        RepoFilesDetailsContentDataBytes::Decrypted(bytes, _) => std::str::from_utf8(bytes).ok(),
+    }
+}
+
+fn get_text_merge(
+    content: &RepoFilesDetailsContent,
+    theirs: &RepoFilesDetailsContentData,
+) -> Option<RepoFilesDetailsTextMerge> {
+    let base = match &content.base {
+        Some(base) => decrypted_text(base)?,
+        None => "",
+    };
+    let mine = decrypted_text(&content.data.as_ref()?.bytes)?;
+    let theirs = decrypted_text(&theirs.bytes)?;
+
+    Some(text_merge::merge(base, mine, theirs))
+}
+
+pub fn conflict_loaded(
+    state: &mut store::State,
+    notify: &store::Notify,
+    details_id: u32,
+    repo_id: &RepoId,
+    path: &EncryptedPath,
+    res: Result<RepoFilesDetailsContentData, TransferError>,
+) {
+    let is_text = selectors::select_file(state, details_id)
+        .map(|file| matches!(file.category, FileCategory::Text | FileCategory::Code))
+        .unwrap_or(false);
+
+    let location = match selectors::select_details_location_mut(state, details_id) {
+        Some(location) => location,
+        _ => return,
+    };
+
+    if &location.repo_id != repo_id || &location.path != path {
+        return;
+    }
+
+    let text_merge = match (&res, is_text) {
+        (Ok(theirs), true) => get_text_merge(&location.content, theirs),
+        _ => None,
+    };
+
+    let conflict = match &mut location.conflict {
+        Some(conflict) => conflict,
+        None => return,
+    };
+
+    notify(store::Event::RepoFilesDetails);
+
+    match res {
+        Ok(theirs) => {
+            conflict.status = Status::Loaded;
+            conflict.remote_size = theirs.remote_size;
+            conflict.remote_modified = theirs.remote_modified;
+            conflict.remote_hash = theirs.remote_hash.clone();
+            conflict.theirs = Some(theirs);
+            conflict.text_merge = text_merge;
+        }
+        Err(err) => {
+            conflict.status = Status::Error {
+                error: err,
+                loaded: false,
+            };
+        }
+    }
+}
+
+/// Keeps local changes (or `content` if given, e.g. merged text) and rebases
+/// them on the remote version so that the next save overwrites it.
+pub fn conflict_resolve(
+    state: &mut store::State,
+    notify: &store::Notify,
+    details_id: u32,
+    content: Option<Vec<u8>>,
+) -> Result<(), SaveError> {
+    let location = match selectors::select_details_location_mut(state, details_id) {
+        Some(location) => location,
+        _ => return Err(SaveError::InvalidState),
+    };
+
+    let theirs = location
+        .conflict
+        .as_ref()
+        .and_then(|conflict| conflict.theirs.clone())
+        .ok_or(SaveError::InvalidState)?;
+
+    let data = location
+        .content
+        .data
+        .as_mut()
+        .ok_or(SaveError::InvalidState)?;
+
+    if let Some(content) = content {
+        match &mut data.bytes {
+            RepoFilesDetailsContentDataBytes::Encrypted(_) => {
+                return Err(SaveError::RepoLocked(RepoLockedError))
+            }
+            RepoFilesDetailsContentDataBytes::Decrypted(bytes, _) => {
+                *bytes = Zeroizing::new(content);
+            }
+        }
+
+        location.content.version += 1;
+
+        notify(store::Event::RepoFilesDetailsContentData);
+    }
+
+    data.remote_size = theirs.remote_size;
+    data.remote_modified = theirs.remote_modified;
+    data.remote_hash = theirs.remote_hash;
+
+    location.content.base = Some(theirs.bytes);
+    location.is_dirty = true;
+    location.save_status = Status::Initial;
+    location.conflict = None;
+
+    notify(store::Event::RepoFilesDetails);
+
+    Ok(())
+}
+
+/// Discards local changes and shows the remote version.
+pub fn conflict_keep_theirs(
+    state: &mut store::State,
+    notify: &store::Notify,
+    details_id: u32,
+) -> Result<(), SaveError> {
+    let location = match selectors::select_details_location_mut(state, details_id) {
+        Some(location) => location,
+        _ => return Err(SaveError::InvalidState),
+    };
+
+    let theirs = location
+        .conflict
+        .as_ref()
+        .and_then(|conflict| conflict.theirs.clone())
+        .ok_or(SaveError::InvalidState)?;
+
+    location.content.data = Some(theirs);
+    location.content.version += 1;
+    location.content.base = None;
+    location.is_dirty = false;
+    location.save_status = Status::Initial;
+    location.conflict = None;
+
+    notify(store::Event::RepoFilesDetails);
+    notify(store::Event::RepoFilesDetailsContentData);
+
+    Ok(())
+}
+
 pub fn deleting(state: &mut store::State, notify: &store::Notify, details_id: u32) {
     let location = match selectors::select_details_location_mut(state, details_id) {
         Some(location) => location,
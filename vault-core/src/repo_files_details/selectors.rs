@@ -1,5 +1,8 @@
+use std::collections::HashSet;
+
 use crate::{
     common::state::Status,
+    eventstream::selectors as eventstream_selectors,
     remote::ApiErrorCode,
     remote_files::state::RemoteFile,
     repo_files::{
@@ -23,9 +26,10 @@ use crate::{
 use super::{
     errors::SaveError,
     state::{
-        RepoFilesDetails, RepoFilesDetailsContent, RepoFilesDetailsContentData,
-        RepoFilesDetailsContentDataBytes, RepoFilesDetailsContentLoading, RepoFilesDetailsInfo,
-        RepoFilesDetailsLocation,
+        RepoFilesDetails, RepoFilesDetailsConflict, RepoFilesDetailsContent,
+        RepoFilesDetailsContentData, RepoFilesDetailsContentDataBytes,
+        RepoFilesDetailsContentLoading, RepoFilesDetailsInfo, RepoFilesDetailsLocation,
+        RepoFilesDetailsTextMerge,
     },
 };
 
@@ -224,6 +228,15 @@ pub fn content_loading_matches_remote_file(
         && loading.remote_hash == remote_file.hash
 }
 
+pub fn conflict_matches_remote_file(
+    conflict: &RepoFilesDetailsConflict,
+    remote_file: &RemoteFile,
+) -> bool {
+    conflict.remote_size == remote_file.size
+        && conflict.remote_modified == remote_file.modified
+        && conflict.remote_hash == remote_file.hash
+}
+
 pub fn select_info<'a>(state: &'a store::State, details_id: u32) -> Option<RepoFilesDetailsInfo> {
     select_details(state, details_id).map(|details| {
         let location = details.location.as_ref();
@@ -282,17 +295,29 @@ pub fn select_info<'a>(state: &'a store::State, details_id: u32) -> Option<RepoF
         let content_data = location.and_then(|loc| loc.content.data.as_ref());
         let is_dirty = location.map(|loc| loc.is_dirty).unwrap_or(false);
         let should_destroy = location.map(|loc| loc.should_destroy).unwrap_or(false);
-        let is_conflict = get_is_conflict(is_dirty, content_data, remote_file, &save_status);
+        let conflict = location.and_then(|loc| loc.conflict.as_ref());
+        let is_conflict = get_is_conflict(is_dirty, content_data, remote_file, &save_status)
+            || conflict.is_some();
+        let conflict_status = conflict
+            .map(|conflict| conflict.status.clone())
+            .unwrap_or(Status::Initial);
         let error = get_save_error(&save_status)
             .or_else(|| get_load_error(&status))
             .or_else(|| get_content_error(&content_status))
             .or_else(|| get_conflict_error(is_conflict));
         let is_editing = location.map(|loc| loc.is_editing).unwrap_or(false);
-        let can_save = is_editing && is_dirty && !matches!(save_status, Status::Loading { .. });
+        let is_read_only = repo_id
+            .map(|repo_id| repos_selectors::select_is_read_only(state, repo_id))
+            .unwrap_or(false);
+        let can_edit = !is_read_only;
+        let can_save = is_editing
+            && is_dirty
+            && !is_read_only
+            && !matches!(save_status, Status::Loading { .. });
         let can_download = true;
-        let can_copy = true;
-        let can_move = true;
-        let can_delete = true;
+        let can_copy = !is_read_only;
+        let can_move = !is_read_only;
+        let can_delete = !is_read_only;
 
         RepoFilesDetailsInfo {
             repo_id,
@@ -311,6 +336,7 @@ pub fn select_info<'a>(state: &'a store::State, details_id: u32) -> Option<RepoF
             is_editing,
             is_dirty,
             should_destroy,
+            can_edit,
             can_save,
             can_download,
             can_copy,
@@ -318,6 +344,9 @@ pub fn select_info<'a>(state: &'a store::State, details_id: u32) -> Option<RepoF
             can_delete,
             repo_status,
             is_locked,
+            is_read_only,
+            is_conflict,
+            conflict_status,
         }
     })
 }
@@ -489,6 +518,48 @@ pub fn select_should_reload_content(
         && !select_was_removed(state, mutation_state, details_id)
 }
 
+/// The remote file was changed while there are unsaved changes and its
+/// content was not loaded yet.
+pub fn select_should_load_conflict(state: &store::State, details_id: u32) -> bool {
+    let location = match select_details_location(state, details_id) {
+        Some(location) => location,
+        None => return false,
+    };
+
+    if !location.is_dirty || matches!(location.save_status, Status::Loading { .. }) {
+        return false;
+    }
+
+    let (remote_file, data) = match (
+        select_remote_file(state, details_id),
+        location.content.data.as_ref(),
+    ) {
+        (Some(remote_file), Some(data)) => (remote_file, data),
+        _ => return false,
+    };
+
+    if content_data_matches_remote_file(data, remote_file) {
+        return false;
+    }
+
+    match &location.conflict {
+        Some(conflict) => {
+            !matches!(conflict.status, Status::Loading { .. })
+                && !conflict_matches_remote_file(conflict, remote_file)
+        }
+        None => true,
+    }
+}
+
+pub fn select_conflict_text_merge<'a>(
+    state: &'a store::State,
+    details_id: u32,
+) -> Option<&'a RepoFilesDetailsTextMerge> {
+    select_details_location(state, details_id)
+        .and_then(|loc| loc.conflict.as_ref())
+        .and_then(|conflict| conflict.text_merge.as_ref())
+}
+
 pub fn select_should_wait_for_loaded(state: &store::State, details_id: u32) -> Option<()> {
     match select_details(state, details_id) {
         Some(details) => match &details.status {
@@ -534,3 +605,29 @@ pub fn select_unlocked_details(
 
     details_ids
 }
+
+/// Details that could have missed events while the eventstream was
+/// reconnecting.
+pub fn select_details_to_resync(
+    state: &store::State,
+    mutation_state: &store::MutationState,
+) -> Vec<u32> {
+    let subscribers = eventstream_selectors::select_resync_subscribers(mutation_state)
+        .map(String::as_str)
+        .collect::<HashSet<_>>();
+
+    if subscribers.is_empty() {
+        return vec![];
+    }
+
+    state
+        .repo_files_details
+        .details
+        .values()
+        .filter(|details| {
+            subscribers.contains(get_eventstream_mount_subscriber(details.id).as_str())
+        })
+        .filter(|details| select_is_unlocked(state, details.id))
+        .map(|details| details.id)
+        .collect()
+}
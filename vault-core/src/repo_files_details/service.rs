@@ -9,11 +9,14 @@ use futures::{
     stream::{AbortHandle, Abortable},
     AsyncReadExt, FutureExt,
 };
+use zeroize::Zeroizing;
 
 use crate::{
+    cipher::Cipher,
     common::state::SizeInfo,
     dialogs::{self, state::DialogShowOptions},
     http::HttpError,
+    locale,
     remote::{ApiErrorCode, RemoteError},
     remote_files::errors::RemoteFilesErrors,
     repo_files::{
@@ -26,6 +29,7 @@ use crate::{
         state::{RepoFileReader, RepoFileReaderBuilder, RepoFileReaderProvider},
         RepoFilesReadService,
     },
+    repo_files_versions::RepoFilesVersionsService,
     repos::ReposService,
     runtime, store,
     transfers::{downloadable::BoxDownloadable, errors::TransferError, TransfersService},
@@ -47,6 +51,7 @@ pub struct RepoFilesDetailsService {
     repos_service: Arc<ReposService>,
     repo_files_service: Arc<RepoFilesService>,
     repo_files_read_service: Arc<RepoFilesReadService>,
+    repo_files_versions_service: Arc<RepoFilesVersionsService>,
     dialogs_service: Arc<dialogs::DialogsService>,
     transfers_service: Arc<TransfersService>,
     store: Arc<store::Store>,
@@ -54,6 +59,7 @@ pub struct RepoFilesDetailsService {
 
     autosave_abort_handles: Arc<Mutex<HashMap<u32, AbortHandle>>>,
     repos_subscription_id: u32,
+    eventstream_subscription_id: u32,
     mutation_subscription_id: u32,
 }
 
@@ -62,6 +68,7 @@ impl RepoFilesDetailsService {
         repos_service: Arc<ReposService>,
         repo_files_service: Arc<RepoFilesService>,
         repo_files_read_service: Arc<RepoFilesReadService>,
+        repo_files_versions_service: Arc<RepoFilesVersionsService>,
         dialogs_service: Arc<dialogs::DialogsService>,
         transfers_service: Arc<TransfersService>,
         store: Arc<store::Store>,
@@ -101,6 +108,38 @@ impl RepoFilesDetailsService {
             }),
         );
 
+        let eventstream_subscription_id = store.get_next_id();
+        let eventstream_subscription_repo_files_service = repo_files_service.clone();
+        let eventstream_subscription_store = store.clone();
+        let eventstream_subscription_runtime = runtime.clone();
+
+        store.on(
+            eventstream_subscription_id,
+            &[store::Event::Eventstream],
+            Box::new(move |mutation_state, add_side_effect| {
+                for details_id in eventstream_subscription_store
+                    .with_state(|state| selectors::select_details_to_resync(state, mutation_state))
+                {
+                    let repo_files_service = eventstream_subscription_repo_files_service.clone();
+                    let store = eventstream_subscription_store.clone();
+                    let runtime = eventstream_subscription_runtime.clone();
+
+                    add_side_effect(Box::new(move || {
+                        // load errors are displayed inside details
+                        runtime.spawn(
+                            Self::load_file_inner(
+                                repo_files_service.clone(),
+                                store.clone(),
+                                details_id,
+                            )
+                            .map(|_| ())
+                            .boxed(),
+                        )
+                    }))
+                }
+            }),
+        );
+
         let mutation_subscription_id = store.get_next_id();
 
         store.mutation_on(
@@ -115,6 +154,7 @@ impl RepoFilesDetailsService {
             repos_service,
             repo_files_service,
             repo_files_read_service,
+            repo_files_versions_service,
             dialogs_service,
             transfers_service,
             store,
@@ -122,6 +162,7 @@ impl RepoFilesDetailsService {
 
             autosave_abort_handles: Arc::new(Mutex::new(HashMap::new())),
             repos_subscription_id,
+            eventstream_subscription_id,
             mutation_subscription_id,
         }
     }
@@ -168,7 +209,7 @@ impl RepoFilesDetailsService {
             repo_files_subscription_id,
             &[store::Event::RepoFiles, store::Event::RepoFilesDetails],
             Box::new(move |mutation_state, add_side_effect| {
-                let (was_removed, should_reload) =
+                let (was_removed, should_reload, should_load_conflict) =
                     repo_files_subscription_self.store.with_state(|state| {
                         (
                             selectors::select_was_removed(state, mutation_state, details_id),
@@ -177,6 +218,7 @@ impl RepoFilesDetailsService {
                                 mutation_state,
                                 details_id,
                             ),
+                            selectors::select_should_load_conflict(state, details_id),
                         )
                     });
 
@@ -200,6 +242,17 @@ impl RepoFilesDetailsService {
                         }));
                     }))
                 }
+
+                if should_load_conflict {
+                    let side_effect_self = repo_files_subscription_self.clone();
+
+                    add_side_effect(Box::new(move || {
+                        side_effect_self.clone().runtime.spawn(Box::pin(async move {
+                            // errors will be stored in the store
+                            let _ = side_effect_self.load_conflict(details_id).await;
+                        }));
+                    }))
+                }
             }),
         );
 
@@ -211,21 +264,19 @@ impl RepoFilesDetailsService {
             match self.clone().edit_cancel(details_id).await {
                 Ok(()) => {}
                 Err(err) => {
-                    let message = format!("File could not be saved ({}). Do you want to Try again or Discard the changes?", err.user_error());
+                    let options = locale::with_store_locale(&self.store, || DialogShowOptions {
+                        title: locale::message("dialog-save-failed-title"),
+                        message: Some(locale::message_args(
+                            "dialog-save-failed-message",
+                            &[("error", err.user_error().into())],
+                        )),
+                        confirm_button_text: locale::message("dialog-save-failed-confirm"),
+                        cancel_button_text: Some(locale::message("dialog-discard-changes")),
+                        ..self.dialogs_service.build_confirm()
+                    });
 
-                    match self
-                        .dialogs_service
-                        .show(DialogShowOptions {
-                            title: String::from("File could not be saved"),
-                            message: Some(message),
-                            confirm_button_text: String::from("Try again"),
-                            cancel_button_text: Some(String::from("Discard changes")),
-                            ..self.dialogs_service.build_confirm()
-                        })
-                        .await
-                    {
-                        Some(_) => continue,
-                        None => {}
+                    if self.dialogs_service.show(options).await.is_some() {
+                        continue;
                     }
                 }
             }
@@ -247,6 +298,27 @@ impl RepoFilesDetailsService {
         }
     }
 
+    /// Destroys all details without saving unsaved changes.
+    pub fn destroy_all(&self) {
+        let destroyed = self.store.mutate(|state, notify, mutation_state, _| {
+            mutations::destroy_all(state, notify, mutation_state)
+        });
+
+        for (_, autosave_abort_handle) in self.autosave_abort_handles.lock().unwrap().drain() {
+            autosave_abort_handle.abort();
+        }
+
+        for (repo_files_subscription_id, transfer_id) in destroyed {
+            if let Some(repo_files_subscription_id) = repo_files_subscription_id {
+                self.store.remove_listener(repo_files_subscription_id);
+            }
+
+            if let Some(transfer_id) = transfer_id {
+                self.transfers_service.clone().abort(transfer_id);
+            }
+        }
+    }
+
     pub async fn load_file(&self, details_id: u32) -> Result<(), LoadDetailsError> {
         Self::load_file_inner(
             self.repo_files_service.clone(),
@@ -297,43 +369,70 @@ impl RepoFilesDetailsService {
 
         let cipher = self.repos_service.get_cipher(&repo_id)?;
 
-        let res = match match self
+        let res = self.read_content_data(file, cipher).await.map(Some);
+
+        let res_err = res.as_ref().map(|_| ()).map_err(|err| err.clone().into());
+
+        self.store.mutate(|state, notify, _, _| {
+            mutations::content_loaded(state, notify, details_id, &repo_id, &path, res.into());
+        });
+
+        res_err
+    }
+
+    async fn read_content_data(
+        &self,
+        file: RepoFile,
+        cipher: Arc<Cipher>,
+    ) -> Result<RepoFilesDetailsContentData, TransferError> {
+        let mut reader = self
             .repo_files_read_service
             .clone()
-            .get_files_reader(vec![file])
-        {
-            Ok(provider) => provider.reader().await,
-            Err(err) => Err(err),
-        } {
-            Ok(mut reader) => {
-                let mut buf = match reader.size {
-                    SizeInfo::Exact(n) | SizeInfo::Estimate(n) => Vec::with_capacity(n as usize),
-                    SizeInfo::Unknown => Vec::new(),
-                };
-
-                match reader.reader.read_to_end(&mut buf).await {
-                    Ok(_) => {
-                        let remote_file = reader.remote_file.unwrap();
-
-                        Ok(Some(RepoFilesDetailsContentData {
-                            bytes: RepoFilesDetailsContentDataBytes::Decrypted(buf, cipher),
-                            remote_size: remote_file.size,
-                            remote_modified: remote_file.modified,
-                            remote_hash: remote_file.hash,
-                        }))
-                    }
-                    Err(err) => Err(TransferError::RemoteError(RemoteError::HttpError(
-                        HttpError::ResponseError(err.to_string()),
-                    ))),
-                }
+            .get_files_reader(vec![file])?
+            .reader()
+            .await?;
+
+        let mut buf = match reader.size {
+            SizeInfo::Exact(n) | SizeInfo::Estimate(n) => Vec::with_capacity(n as usize),
+            SizeInfo::Unknown => Vec::new(),
+        };
+
+        match reader.reader.read_to_end(&mut buf).await {
+            Ok(_) => {
+                let remote_file = reader.remote_file.unwrap();
+
+                Ok(RepoFilesDetailsContentData {
+                    bytes: RepoFilesDetailsContentDataBytes::Decrypted(Zeroizing::new(buf), cipher),
+                    remote_size: remote_file.size,
+                    remote_modified: remote_file.modified,
+                    remote_hash: remote_file.hash,
+                })
             }
+            Err(err) => Err(TransferError::RemoteError(RemoteError::HttpError(
+                HttpError::ResponseError(err.to_string()),
+            ))),
+        }
+    }
+
+    /// Loads the remote version of a file that was changed by someone else
+    /// while it had unsaved changes.
+    pub async fn load_conflict(self: Arc<Self>, details_id: u32) -> Result<(), LoadContentError> {
+        let file = self
+            .store
+            .mutate(|state, notify, _, _| mutations::conflict_loading(state, notify, details_id))?;
+
+        let repo_id = file.repo_id.clone();
+        let path = file.encrypted_path.clone();
+
+        let res = match self.repos_service.get_cipher(&repo_id) {
+            Ok(cipher) => self.read_content_data(file, cipher).await,
             Err(err) => Err(err.into()),
         };
 
         let res_err = res.as_ref().map(|_| ()).map_err(|err| err.clone().into());
 
         self.store.mutate(|state, notify, _, _| {
-            mutations::content_loaded(state, notify, details_id, &repo_id, &path, res.into());
+            mutations::conflict_loaded(state, notify, details_id, &repo_id, &path, res);
         });
 
         res_err
@@ -541,6 +640,8 @@ impl RepoFilesDetailsService {
         let (repo_id, path, name, data, version, is_deleted) =
             self.clone().saving(details_id, &initiator).await?;
 
+        let saved_bytes = data.bytes.clone();
+
         let res = self
             .clone()
             .save_inner(initiator, repo_id, path, name, data, is_deleted)
@@ -549,7 +650,7 @@ impl RepoFilesDetailsService {
         let res_err = res.as_ref().map(|_| ()).map_err(|err| err.clone());
 
         self.store.mutate(|state, notify, _, _| {
-            mutations::saved(state, notify, details_id, version, res);
+            mutations::saved(state, notify, details_id, version, saved_bytes, res);
         });
 
         res_err
@@ -616,6 +717,7 @@ impl RepoFilesDetailsService {
         let bytes = match &data.bytes {
             RepoFilesDetailsContentDataBytes::Encrypted(bytes) => cipher
                 .decrypt_vec(&bytes)
+                .map(Zeroizing::new)
                 .map_err(|err| SaveError::DecryptDataError(err.to_string()))?,
             RepoFilesDetailsContentDataBytes::Decrypted(bytes, _) => bytes.clone(),
         };
@@ -644,6 +746,13 @@ impl RepoFilesDetailsService {
             let encrypted_name = cipher.encrypt_filename(&name);
             let path = repo_encrypted_path_utils::join_path_name(&parent_path, &encrypted_name);
 
+            if !autorename {
+                self.repo_files_versions_service
+                    .clone()
+                    .create_version_if_enabled(&repo_id, &path)
+                    .await?;
+            }
+
             let reader = Box::pin(Cursor::new(bytes.clone()));
 
             return match self
@@ -694,19 +803,18 @@ impl RepoFilesDetailsService {
     ) -> Result<(), SaveError> {
         match &initiator {
             SaveInitiator::User => {
-                let message = format!("File {} is no longer accessible. Probably it was deleted or you no longer have access to it. Do you want to Save the file to a new location?", name.0);
+                let options = locale::with_store_locale(&self.store, || DialogShowOptions {
+                    title: locale::message("dialog-file-not-accessible-title"),
+                    message: Some(locale::message_args(
+                        "dialog-file-not-accessible-save-message",
+                        &[("name", name.0.as_str().into())],
+                    )),
+                    confirm_button_text: locale::message("dialog-file-not-accessible-confirm"),
+                    cancel_button_text: Some(locale::message("dialog-cancel")),
+                    ..self.dialogs_service.build_confirm()
+                });
 
-                match self
-                    .dialogs_service
-                    .show(DialogShowOptions {
-                        title: String::from("File not accessible"),
-                        message: Some(message),
-                        confirm_button_text: String::from("Save to a new location"),
-                        cancel_button_text: Some(String::from("Cancel")),
-                        ..self.dialogs_service.build_confirm()
-                    })
-                    .await
-                {
+                match self.dialogs_service.show(options).await {
                     Some(_) => Ok(()),
                     None => Err(SaveError::Canceled),
                 }
@@ -716,19 +824,18 @@ impl RepoFilesDetailsService {
                 Err(SaveError::Canceled)
             }
             SaveInitiator::Cancel => {
-                let message = format!("File {} is no longer accessible. Probably it was deleted or you no longer have access to it. Do you want to Save the file to a new location or Discard the changes?", name.0);
+                let options = locale::with_store_locale(&self.store, || DialogShowOptions {
+                    title: locale::message("dialog-file-not-accessible-title"),
+                    message: Some(locale::message_args(
+                        "dialog-file-not-accessible-save-discard-message",
+                        &[("name", name.0.as_str().into())],
+                    )),
+                    confirm_button_text: locale::message("dialog-file-not-accessible-confirm"),
+                    cancel_button_text: Some(locale::message("dialog-discard-changes")),
+                    ..self.dialogs_service.build_confirm()
+                });
 
-                match self
-                    .dialogs_service
-                    .show(DialogShowOptions {
-                        title: String::from("File not accessible"),
-                        message: Some(message),
-                        confirm_button_text: String::from("Save to a new location"),
-                        cancel_button_text: Some(String::from("Discard changes")),
-                        ..self.dialogs_service.build_confirm()
-                    })
-                    .await
-                {
+                match self.dialogs_service.show(options).await {
                     Some(_) => Ok(()),
                     None => Err(SaveError::DiscardChanges {
                         should_destroy: true,
@@ -768,42 +875,30 @@ impl RepoFilesDetailsService {
     async fn save_handle_conflict(&self, initiator: &SaveInitiator) -> Result<bool, SaveError> {
         match &initiator {
             SaveInitiator::User => {
-                let message = String::from("Saving into the existing file is not possible. Do you want to Save your changes as a new file?");
+                let options = locale::with_store_locale(&self.store, || DialogShowOptions {
+                    title: locale::message("dialog-file-conflict-title"),
+                    message: Some(locale::message("dialog-file-conflict-message")),
+                    confirm_button_text: locale::message("dialog-file-conflict-confirm"),
+                    cancel_button_text: Some(locale::message("dialog-cancel")),
+                    ..self.dialogs_service.build_confirm()
+                });
 
-                match self
-                    .dialogs_service
-                    .show(DialogShowOptions {
-                        title: String::from(
-                            "File was changed by someone else since your last save",
-                        ),
-                        message: Some(message),
-                        confirm_button_text: String::from("Save as a new file"),
-                        cancel_button_text: Some(String::from("Cancel")),
-                        ..self.dialogs_service.build_confirm()
-                    })
-                    .await
-                {
+                match self.dialogs_service.show(options).await {
                     Some(_) => Ok(true),
                     None => Ok(false),
                 }
             }
             SaveInitiator::Autosave => panic!("unreachable"),
             SaveInitiator::Cancel => {
-                let message = String::from("Saving into the existing file is not possible. Do you want to Save your changes as a new file or Discard them?");
+                let options = locale::with_store_locale(&self.store, || DialogShowOptions {
+                    title: locale::message("dialog-file-conflict-title"),
+                    message: Some(locale::message("dialog-file-conflict-discard-message")),
+                    confirm_button_text: locale::message("dialog-file-conflict-confirm"),
+                    cancel_button_text: Some(locale::message("dialog-discard-changes")),
+                    ..self.dialogs_service.build_confirm()
+                });
 
-                match self
-                    .dialogs_service
-                    .show(DialogShowOptions {
-                        title: String::from(
-                            "File was changed by someone else since your last save",
-                        ),
-                        message: Some(message),
-                        confirm_button_text: String::from("Save as a new file"),
-                        cancel_button_text: Some(String::from("Discard changes")),
-                        ..self.dialogs_service.build_confirm()
-                    })
-                    .await
-                {
+                match self.dialogs_service.show(options).await {
                     Some(_) => Ok(true),
                     None => Err(SaveError::DiscardChanges {
                         should_destroy: false,
@@ -817,19 +912,21 @@ impl RepoFilesDetailsService {
         let location_changed_alert_self = self.clone();
 
         self.runtime.spawn(Box::pin(async move {
-            let message = format!(
-                "File {} was saved here because it could not be saved in its original location.",
-                name.0
-            );
+            let options = locale::with_store_locale(&location_changed_alert_self.store, || {
+                DialogShowOptions {
+                    message: Some(locale::message_args(
+                        "dialog-file-location-changed-message",
+                        &[("name", name.0.as_str().into())],
+                    )),
+                    ..location_changed_alert_self
+                        .dialogs_service
+                        .build_alert(locale::message("dialog-file-location-changed-title"))
+                }
+            });
 
             location_changed_alert_self
                 .dialogs_service
-                .show(DialogShowOptions {
-                    message: Some(message),
-                    ..location_changed_alert_self
-                        .dialogs_service
-                        .build_alert(String::from("File location changed"))
-                })
+                .show(options)
                 .await;
         }));
     }
@@ -847,6 +944,98 @@ impl RepoFilesDetailsService {
         }
     }
 
+    /// Overwrites the remote version with local changes.
+    pub async fn conflict_keep_mine(self: Arc<Self>, details_id: u32) -> Result<(), SaveError> {
+        self.clone().conflict_merge(details_id, None).await
+    }
+
+    /// Saves `content` (e.g. resolved text merge) over the remote version. If
+    /// `content` is `None` local changes are kept.
+    pub async fn conflict_merge(
+        self: Arc<Self>,
+        details_id: u32,
+        content: Option<Vec<u8>>,
+    ) -> Result<(), SaveError> {
+        self.store.mutate(|state, notify, _, _| {
+            mutations::conflict_resolve(state, notify, details_id, content)
+        })?;
+
+        self.save(details_id).await
+    }
+
+    /// Discards local changes.
+    pub fn conflict_keep_theirs(&self, details_id: u32) -> Result<(), SaveError> {
+        self.autosave_abort(details_id);
+
+        self.store.mutate(|state, notify, _, _| {
+            mutations::conflict_keep_theirs(state, notify, details_id)
+        })
+    }
+
+    /// Saves local changes as a new file next to the original and shows the
+    /// remote version. Returns the path of the new file.
+    pub async fn conflict_save_as_copy(
+        self: Arc<Self>,
+        details_id: u32,
+    ) -> Result<EncryptedPath, SaveError> {
+        let (repo_id, path, name, data) = self
+            .store
+            .with_state(|state| {
+                selectors::select_details_location(state, details_id).and_then(|loc| {
+                    loc.content.data.clone().map(|data| {
+                        (
+                            loc.repo_id.clone(),
+                            loc.path.clone(),
+                            loc.name.clone(),
+                            data,
+                        )
+                    })
+                })
+            })
+            .ok_or(SaveError::InvalidState)?;
+
+        let cipher = self.repos_service.get_cipher(&repo_id)?;
+
+        let name = cipher.decrypt_filename(&name)?;
+
+        let parent_path =
+            repo_encrypted_path_utils::parent_path(&path).ok_or(SaveError::CannotSaveRoot)?;
+
+        let bytes = match &data.bytes {
+            RepoFilesDetailsContentDataBytes::Encrypted(bytes) => cipher
+                .decrypt_vec(bytes)
+                .map(Zeroizing::new)
+                .map_err(|err| SaveError::DecryptDataError(err.to_string()))?,
+            RepoFilesDetailsContentDataBytes::Decrypted(bytes, _) => bytes.clone(),
+        };
+        let size = Some(bytes.len() as i64);
+
+        let copy_name = self
+            .repo_files_service
+            .get_unused_name(&repo_id, &parent_path, &name)
+            .await?;
+        let encrypted_copy_name = cipher.encrypt_filename(&copy_name);
+        let copy_path =
+            repo_encrypted_path_utils::join_path_name(&parent_path, &encrypted_copy_name);
+
+        self.repo_files_service
+            .clone()
+            .upload_file_reader(
+                &repo_id,
+                &parent_path,
+                encrypted_copy_name,
+                Box::pin(Cursor::new(bytes)),
+                size,
+                RepoFilesUploadConflictResolution::Error,
+                None,
+            )
+            .await?;
+
+        self.conflict_keep_theirs(details_id)?;
+
+        Ok(copy_path)
+    }
+
     pub async fn delete(&self, details_id: u32) -> Result<(), DeleteFileError> {
         match self
             .store
@@ -885,16 +1074,17 @@ impl RepoFilesDetailsService {
                 None
             }
         }) {
-            let message = format!("File {} is no longer accessible. Probably it was deleted or you no longer have access to it.", file_name.0);
+            let options = locale::with_store_locale(&self.store, || DialogShowOptions {
+                message: Some(locale::message_args(
+                    "dialog-file-not-accessible-message",
+                    &[("name", file_name.0.as_str().into())],
+                )),
+                ..self
+                    .dialogs_service
+                    .build_alert(locale::message("dialog-file-not-accessible-title"))
+            });
 
-            self.dialogs_service
-                .show(DialogShowOptions {
-                    message: Some(message),
-                    ..self
-                        .dialogs_service
-                        .build_alert(String::from("File not accessible"))
-                })
-                .await;
+            self.dialogs_service.show(options).await;
         }
     }
 
@@ -957,6 +1147,7 @@ impl RepoFilesDetailsService {
 impl Drop for RepoFilesDetailsService {
     fn drop(&mut self) {
         self.store.remove_listener(self.repos_subscription_id);
+        self.store.remove_listener(self.eventstream_subscription_id);
         self.store
             .mutation_remove_listener(self.mutation_subscription_id)
     }
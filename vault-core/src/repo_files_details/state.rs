@@ -1,5 +1,7 @@
 use std::{collections::HashMap, sync::Arc, time::Duration};
 
+use zeroize::Zeroizing;
+
 use crate::{
     cipher::{errors::DecryptFilenameError, Cipher},
     common::state::Status,
@@ -32,6 +34,7 @@ pub struct RepoFilesDetailsInfo<'a> {
     pub is_editing: bool,
     pub is_dirty: bool,
     pub should_destroy: bool,
+    pub can_edit: bool,
     pub can_save: bool,
     pub can_download: bool,
     pub can_copy: bool,
@@ -39,12 +42,16 @@ pub struct RepoFilesDetailsInfo<'a> {
     pub can_delete: bool,
     pub repo_status: Status<RepoInfoError>,
     pub is_locked: bool,
+    pub is_read_only: bool,
+    pub is_conflict: bool,
+    pub conflict_status: Status<TransferError>,
 }
 
 #[derive(Debug, Clone)]
 pub enum RepoFilesDetailsContentDataBytes {
     Encrypted(Vec<u8>),
-    Decrypted(Vec<u8>, Arc<Cipher>),
+    /// Plaintext is zeroized when dropped.
+    Decrypted(Zeroizing<Vec<u8>>, Arc<Cipher>),
 }
 
 impl PartialEq for RepoFilesDetailsContentDataBytes {
@@ -79,6 +86,53 @@ pub struct RepoFilesDetailsContent {
     pub loading: Option<RepoFilesDetailsContentLoading>,
     pub version: u32,
     pub transfer_id: Option<u32>,
+    /// bytes before the first unsaved change, used as the base for merging
+    pub base: Option<RepoFilesDetailsContentDataBytes>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RepoFilesDetailsTextMergeChunk {
+    Resolved(String),
+    Conflict {
+        base: String,
+        mine: String,
+        theirs: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepoFilesDetailsTextMerge {
+    pub chunks: Vec<RepoFilesDetailsTextMergeChunk>,
+}
+
+impl RepoFilesDetailsTextMerge {
+    pub fn has_conflicts(&self) -> bool {
+        self.chunks
+            .iter()
+            .any(|chunk| matches!(chunk, RepoFilesDetailsTextMergeChunk::Conflict { .. }))
+    }
+
+    /// Returns the merged text if there are no conflicts.
+    pub fn merged(&self) -> Option<String> {
+        self.chunks
+            .iter()
+            .map(|chunk| match chunk {
+                RepoFilesDetailsTextMergeChunk::Resolved(text) => Some(text.as_str()),
+                RepoFilesDetailsTextMergeChunk::Conflict { .. } => None,
+            })
+            .collect()
+    }
+}
+
+/// Remote version of a file that was changed while it had unsaved changes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepoFilesDetailsConflict {
+    pub status: Status<TransferError>,
+    pub remote_size: Option<i64>,
+    pub remote_modified: Option<i64>,
+    pub remote_hash: Option<String>,
+    pub theirs: Option<RepoFilesDetailsContentData>,
+    pub text_merge: Option<RepoFilesDetailsTextMerge>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -94,6 +148,7 @@ pub struct RepoFilesDetailsLocation {
     pub save_status: Status<SaveError>,
     pub delete_status: Status<DeleteFileError>,
     pub should_destroy: bool,
+    pub conflict: Option<RepoFilesDetailsConflict>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -0,0 +1,151 @@
+use similar::{capture_diff_slices, Algorithm, DiffOp};
+
+use super::state::{RepoFilesDetailsTextMerge, RepoFilesDetailsTextMergeChunk};
+
+/// For every base line returns the index of the matching line in `other`.
+fn match_lines(base: &[&str], other: &[&str]) -> Vec<Option<usize>> {
+    let mut matches = vec![None; base.len()];
+
+    for op in capture_diff_slices(Algorithm::Myers, base, other) {
+        if let DiffOp::Equal {
+            old_index,
+            new_index,
+            len,
+        } = op
+        {
+            for i in 0..len {
+                matches[old_index + i] = Some(new_index + i);
+            }
+        }
+    }
+
+    matches
+}
+
+fn push_resolved(chunks: &mut Vec<RepoFilesDetailsTextMergeChunk>, lines: &[&str]) {
+    if lines.is_empty() {
+        return;
+    }
+
+    if let Some(RepoFilesDetailsTextMergeChunk::Resolved(text)) = chunks.last_mut() {
+        text.push_str(&lines.concat());
+    } else {
+        chunks.push(RepoFilesDetailsTextMergeChunk::Resolved(lines.concat()));
+    }
+}
+
+/// Line based three-way merge (diff3). Changes made only on one side are
+/// applied automatically, overlapping changes are returned as conflicts.
+pub fn merge(base: &str, mine: &str, theirs: &str) -> RepoFilesDetailsTextMerge {
+    let base = base.split_inclusive('\n').collect::<Vec<_>>();
+    let mine = mine.split_inclusive('\n').collect::<Vec<_>>();
+    let theirs = theirs.split_inclusive('\n').collect::<Vec<_>>();
+
+    let mine_matches = match_lines(&base, &mine);
+    let theirs_matches = match_lines(&base, &theirs);
+
+    let mut chunks = Vec::new();
+
+    let (mut i, mut j, mut k) = (0, 0, 0);
+
+    loop {
+        // next base line that is unchanged on both sides
+        let stable = (i..base.len()).find_map(|base_i| {
+            match (mine_matches[base_i], theirs_matches[base_i]) {
+                (Some(mine_j), Some(theirs_k)) if mine_j >= j && theirs_k >= k => {
+                    Some((base_i, mine_j, theirs_k))
+                }
+                _ => None,
+            }
+        });
+
+        let (next_i, next_j, next_k) = stable.unwrap_or((base.len(), mine.len(), theirs.len()));
+
+        let base_lines = &base[i..next_i];
+        let mine_lines = &mine[j..next_j];
+        let theirs_lines = &theirs[k..next_k];
+
+        if mine_lines == base_lines {
+            push_resolved(&mut chunks, theirs_lines);
+        } else if theirs_lines == base_lines || mine_lines == theirs_lines {
+            push_resolved(&mut chunks, mine_lines);
+        } else {
+            chunks.push(RepoFilesDetailsTextMergeChunk::Conflict {
+                base: base_lines.concat(),
+                mine: mine_lines.concat(),
+                theirs: theirs_lines.concat(),
+            });
+        }
+
+        match stable {
+            Some(_) => {
+                push_resolved(&mut chunks, &base[next_i..next_i + 1]);
+
+                (i, j, k) = (next_i + 1, next_j + 1, next_k + 1);
+            }
+            None => break,
+        }
+    }
+
+    RepoFilesDetailsTextMerge { chunks }
+}
+
+#[cfg(test)]
+mod tests {
+    use similar_asserts::assert_eq;
+
+    use super::{
+        super::state::{RepoFilesDetailsTextMerge, RepoFilesDetailsTextMergeChunk},
+        merge,
+    };
+
+    #[test]
+    fn test_merge_non_overlapping() {
+        let res = merge("a\nb\nc\nd\n", "a\nB\nc\nd\n", "a\nb\nc\nD\n");
+
+        assert_eq!(res.merged(), Some("a\nB\nc\nD\n".into()));
+    }
+
+    #[test]
+    fn test_merge_same_change() {
+        let res = merge("a\nb\n", "a\nc\n", "a\nc\n");
+
+        assert_eq!(res.merged(), Some("a\nc\n".into()));
+    }
+
+    #[test]
+    fn test_merge_insert_and_delete() {
+        let res = merge("a\nb\nc\n", "x\na\nb\nc\n", "a\nc\n");
+
+        assert_eq!(res.merged(), Some("x\na\nc\n".into()));
+    }
+
+    #[test]
+    fn test_merge_conflict() {
+        let res = merge("a\nb\nc\n", "a\nmine\nc\n", "a\ntheirs\nc\n");
+
+        assert_eq!(
+            res,
+            RepoFilesDetailsTextMerge {
+                chunks: vec![
+                    RepoFilesDetailsTextMergeChunk::Resolved("a\n".into()),
+                    RepoFilesDetailsTextMergeChunk::Conflict {
+                        base: "b\n".into(),
+                        mine: "mine\n".into(),
+                        theirs: "theirs\n".into(),
+                    },
+                    RepoFilesDetailsTextMergeChunk::Resolved("c\n".into()),
+                ]
+            }
+        );
+        assert!(res.has_conflicts());
+        assert_eq!(res.merged(), None);
+    }
+
+    #[test]
+    fn test_merge_no_trailing_newline() {
+        let res = merge("a\nb", "a\nb\nc", "z\na\nb");
+
+        assert_eq!(res.merged(), Some("z\na\nb\nc".into()));
+    }
+}
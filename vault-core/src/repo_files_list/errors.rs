@@ -4,7 +4,7 @@ use crate::{
     cipher::errors::DecryptFilenameError,
     remote::RemoteError,
     repos::errors::{GetCipherError, RepoLockedError, RepoNotFoundError},
-    user_error::UserError,
+    user_error::{ErrorCode, UserError},
 };
 
 #[derive(Error, Debug, Clone, PartialEq)]
@@ -22,6 +22,13 @@ impl UserError for FilesListRecursiveItemError {
             Self::RemoteError(err) => err.user_error(),
         }
     }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::DecryptFilenameError(err) => err.error_code(),
+            Self::RemoteError(err) => err.error_code(),
+        }
+    }
 }
 
 #[derive(Error, Debug, Clone, PartialEq)]
@@ -45,6 +52,15 @@ impl UserError for GetListRecursiveError {
             Self::RemoteError(err) => err.user_error(),
         }
     }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::RepoNotFound(err) => err.error_code(),
+            Self::RepoLocked(err) => err.error_code(),
+            Self::DecryptFilenameError(err) => err.error_code(),
+            Self::RemoteError(err) => err.error_code(),
+        }
+    }
 }
 
 impl From<GetCipherError> for GetListRecursiveError {
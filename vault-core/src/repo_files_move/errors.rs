@@ -5,7 +5,7 @@ use crate::{
     remote::RemoteError,
     repo_files::errors::LoadFilesError,
     repos::errors::{RepoLockedError, RepoNotFoundError},
-    user_error::UserError,
+    user_error::{ErrorCode, UserError},
 };
 
 #[derive(Error, Debug, Clone, PartialEq)]
@@ -29,6 +29,15 @@ impl UserError for ShowError {
             Self::FilesEmpty => self.to_string(),
         }
     }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::RepoNotFound(err) => err.error_code(),
+            Self::RepoLocked(err) => err.error_code(),
+            Self::RemoteError(err) => err.error_code(),
+            Self::FilesEmpty => ErrorCode::FileFilesEmpty,
+        }
+    }
 }
 
 impl From<LoadFilesError> for ShowError {
@@ -62,6 +71,15 @@ impl UserError for DirPickerClickError {
             Self::RemoteError(err) => err.user_error(),
         }
     }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::RepoNotFound(err) => err.error_code(),
+            Self::RepoLocked(err) => err.error_code(),
+            Self::DecryptFilenameError(err) => err.error_code(),
+            Self::RemoteError(err) => err.error_code(),
+        }
+    }
 }
 
 impl From<LoadFilesError> for DirPickerClickError {
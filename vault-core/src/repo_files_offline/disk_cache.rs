@@ -0,0 +1,21 @@
+use async_trait::async_trait;
+
+use crate::common::state::BoxAsyncRead;
+
+/// OfflineDiskCache stores the content of files that are available offline.
+/// Values are the encrypted files as they are stored on the remote so there is
+/// no plaintext at rest.
+#[async_trait]
+pub trait OfflineDiskCache {
+    async fn get(&self, key: &str) -> Result<Option<BoxAsyncRead>, String>;
+    /// Returns the number of bytes written. The previous value must stay
+    /// readable until the new one is completely written.
+    async fn set(&self, key: &str, reader: BoxAsyncRead) -> Result<i64, String>;
+    async fn remove(&self, key: &str) -> Result<(), String>;
+    /// Removes all values and the index.
+    async fn clear(&self) -> Result<(), String>;
+    /// The index of cached files is encrypted with the index key from the
+    /// secure storage. It is small so it is read and written synchronously.
+    fn get_index(&self) -> Result<Option<Vec<u8>>, String>;
+    fn set_index(&self, value: &[u8]) -> Result<(), String>;
+}
@@ -0,0 +1,76 @@
+use thiserror::Error;
+
+use crate::{
+    locale,
+    remote::RemoteError,
+    repo_files_list::errors::{FilesListRecursiveItemError, GetListRecursiveError},
+    repos::errors::{GetCipherError, RepoLockedError, RepoNotFoundError},
+    secure_storage::errors::SecureStorageError,
+    user_error::{ErrorCode, UserError},
+};
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum OfflineError {
+    #[error("offline files are not available")]
+    DiskCacheNotAvailable,
+    #[error("disk cache error: {0}")]
+    DiskCacheError(String),
+    #[error("{0}")]
+    RepoNotFound(#[from] RepoNotFoundError),
+    #[error("{0}")]
+    RepoLocked(#[from] RepoLockedError),
+    #[error("{0}")]
+    ListError(#[from] FilesListRecursiveItemError),
+    #[error("{0}")]
+    RemoteError(#[from] RemoteError),
+    #[error("storage error: {0}")]
+    StorageError(#[from] SecureStorageError),
+}
+
+impl UserError for OfflineError {
+    fn user_error(&self) -> String {
+        match self {
+            Self::DiskCacheNotAvailable => self.to_string(),
+            Self::DiskCacheError(_) => self.to_string(),
+            Self::RepoNotFound(err) => err.user_error(),
+            Self::RepoLocked(err) => err.user_error(),
+            Self::ListError(err) => err.user_error(),
+            Self::RemoteError(err) => err.user_error(),
+            Self::StorageError(err) => {
+                locale::message_args("error-storage", &[("error", err.to_string().into())])
+            }
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::DiskCacheNotAvailable => ErrorCode::OfflineDiskCacheNotAvailable,
+            Self::DiskCacheError(_) => ErrorCode::OfflineDiskCache,
+            Self::RepoNotFound(err) => err.error_code(),
+            Self::RepoLocked(err) => err.error_code(),
+            Self::ListError(err) => err.error_code(),
+            Self::RemoteError(err) => err.error_code(),
+            Self::StorageError(_) => ErrorCode::Storage,
+        }
+    }
+}
+
+impl From<GetCipherError> for OfflineError {
+    fn from(err: GetCipherError) -> Self {
+        match err {
+            GetCipherError::RepoNotFound(err) => Self::RepoNotFound(err),
+            GetCipherError::RepoLocked(err) => Self::RepoLocked(err),
+        }
+    }
+}
+
+impl From<GetListRecursiveError> for OfflineError {
+    fn from(err: GetListRecursiveError) -> Self {
+        match err {
+            GetListRecursiveError::RepoNotFound(err) => Self::RepoNotFound(err),
+            GetListRecursiveError::RepoLocked(err) => Self::RepoLocked(err),
+            GetListRecursiveError::DecryptFilenameError(err) => Self::ListError(err.into()),
+            GetListRecursiveError::RemoteError(err) => Self::RemoteError(err),
+        }
+    }
+}
@@ -0,0 +1,7 @@
+pub mod disk_cache;
+pub mod errors;
+pub mod selectors;
+pub mod service;
+pub mod state;
+
+pub use self::{disk_cache::OfflineDiskCache, service::RepoFilesOfflineService};
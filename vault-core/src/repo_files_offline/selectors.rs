@@ -0,0 +1,232 @@
+use std::time::Duration;
+
+use crate::{
+    repo_files::{selectors as repo_files_selectors, state::RepoFile},
+    store,
+    types::{EncryptedPath, MountId, RemotePath, RepoId},
+    utils::{remote_path_utils, repo_encrypted_path_utils},
+};
+
+use super::state::{OfflineEntry, OfflineIndex, OfflineInfo, OfflinePin};
+
+pub const INDEX_KEY_STORAGE_KEY: &str = "vaultOfflineIndexKey";
+
+/// Delay before access time changes are saved.
+pub const SAVE_DELAY: Duration = Duration::from_secs(10);
+
+/// 1 GiB
+pub const DEFAULT_QUOTA: i64 = 1024 * 1024 * 1024;
+
+/// Keys are hex digests so they are safe to use as file names and do not
+/// contain paths.
+pub fn get_cache_key(repo_id: &RepoId, path: &EncryptedPath) -> String {
+    hex::encode(
+        md5::compute(
+            repo_files_selectors::get_file_id(repo_id, path)
+                .0
+                .as_bytes(),
+        )
+        .0,
+    )
+}
+
+pub fn is_path_in(path: &EncryptedPath, parent_path: &EncryptedPath) -> bool {
+    repo_encrypted_path_utils::paths_chain(path).contains(parent_path)
+}
+
+pub fn is_pinned(pins: &[OfflinePin], repo_id: &RepoId, path: &EncryptedPath) -> bool {
+    pins.iter()
+        .any(|pin| &pin.repo_id == repo_id && is_path_in(path, &pin.path))
+}
+
+/// The cached content is valid if the remote file did not change since it
+/// was cached.
+pub fn is_entry_valid(entry: &OfflineEntry, file: &RepoFile) -> bool {
+    match (&entry.remote_hash, &file.remote_hash) {
+        (Some(entry_hash), Some(file_hash)) => entry_hash == file_hash,
+        (None, None) => entry.remote_modified.is_some() && entry.remote_modified == file.modified,
+        _ => false,
+    }
+}
+
+/// Least recently accessed unpinned entries that have to be removed to get
+/// under the quota. Pinned entries are never evicted.
+pub fn get_eviction_keys(index: &OfflineIndex) -> Vec<String> {
+    let mut used: i64 = index.entries.values().map(|entry| entry.size).sum();
+
+    if used <= index.quota {
+        return Vec::new();
+    }
+
+    let mut unpinned = index
+        .entries
+        .iter()
+        .filter(|(_, entry)| !is_pinned(&index.pins, &entry.repo_id, &entry.path))
+        .collect::<Vec<_>>();
+
+    unpinned.sort_by(|(a_key, a), (b_key, b)| a.accessed.cmp(&b.accessed).then(a_key.cmp(b_key)));
+
+    let mut keys = Vec::new();
+
+    for (key, entry) in unpinned {
+        if used <= index.quota {
+            break;
+        }
+
+        used -= entry.size;
+
+        keys.push(key.clone());
+    }
+
+    keys
+}
+
+pub fn get_info(index: &OfflineIndex) -> OfflineInfo {
+    OfflineInfo {
+        quota: index.quota,
+        used: index.entries.values().map(|entry| entry.size).sum(),
+        pinned_used: index
+            .entries
+            .values()
+            .filter(|entry| is_pinned(&index.pins, &entry.repo_id, &entry.path))
+            .map(|entry| entry.size)
+            .sum(),
+        pins_count: index.pins.len(),
+        entries_count: index.entries.len(),
+    }
+}
+
+/// Pins that contain or are inside one of the changed remote paths.
+pub fn select_changed_pins(
+    state: &store::State,
+    pins: &[OfflinePin],
+    changed_paths: &[(MountId, RemotePath)],
+) -> Vec<OfflinePin> {
+    pins.iter()
+        .filter(|pin| {
+            let (pin_mount_id, pin_remote_path) =
+                match repo_files_selectors::select_repo_path_to_mount_path(
+                    state,
+                    &pin.repo_id,
+                    &pin.path,
+                ) {
+                    Ok(mount_path) => mount_path,
+                    Err(_) => return false,
+                };
+
+            changed_paths.iter().any(|(mount_id, path)| {
+                mount_id == &pin_mount_id
+                    && (remote_path_utils::paths_chain(path).contains(&pin_remote_path)
+                        || remote_path_utils::paths_chain(&pin_remote_path).contains(path))
+            })
+        })
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use similar_asserts::assert_eq;
+
+    use crate::types::{EncryptedPath, RepoId};
+
+    use super::{
+        super::state::{OfflineEntry, OfflineIndex, OfflinePin},
+        get_cache_key, get_eviction_keys, get_info, is_pinned,
+    };
+
+    fn entry(path: &str, size: i64, accessed: i64) -> OfflineEntry {
+        OfflineEntry {
+            repo_id: RepoId("r1".into()),
+            path: EncryptedPath(path.into()),
+            size,
+            remote_modified: None,
+            remote_hash: Some("hash".into()),
+            accessed,
+        }
+    }
+
+    #[test]
+    fn test_is_pinned() {
+        let pins = vec![OfflinePin {
+            repo_id: RepoId("r1".into()),
+            path: EncryptedPath("/dir".into()),
+        }];
+
+        assert!(is_pinned(
+            &pins,
+            &RepoId("r1".into()),
+            &EncryptedPath("/dir".into())
+        ));
+        assert!(is_pinned(
+            &pins,
+            &RepoId("r1".into()),
+            &EncryptedPath("/dir/a/b".into())
+        ));
+        assert!(!is_pinned(
+            &pins,
+            &RepoId("r1".into()),
+            &EncryptedPath("/dir2".into())
+        ));
+        assert!(!is_pinned(
+            &pins,
+            &RepoId("r2".into()),
+            &EncryptedPath("/dir/a".into())
+        ));
+    }
+
+    #[test]
+    fn test_get_eviction_keys() {
+        let mut entries = HashMap::new();
+
+        for (path, size, accessed) in [
+            ("/pinned/a", 50, 1),
+            ("/b", 20, 3),
+            ("/c", 20, 2),
+            ("/d", 20, 4),
+        ] {
+            entries.insert(
+                get_cache_key(&RepoId("r1".into()), &EncryptedPath(path.into())),
+                entry(path, size, accessed),
+            );
+        }
+
+        let mut index = OfflineIndex {
+            quota: 110,
+            pins: vec![OfflinePin {
+                repo_id: RepoId("r1".into()),
+                path: EncryptedPath("/pinned".into()),
+            }],
+            entries,
+        };
+
+        assert_eq!(get_eviction_keys(&index), Vec::<String>::new());
+
+        index.quota = 90;
+
+        assert_eq!(
+            get_eviction_keys(&index),
+            vec![get_cache_key(
+                &RepoId("r1".into()),
+                &EncryptedPath("/c".into())
+            )]
+        );
+
+        index.quota = 10;
+
+        assert_eq!(
+            get_eviction_keys(&index),
+            vec![
+                get_cache_key(&RepoId("r1".into()), &EncryptedPath("/c".into())),
+                get_cache_key(&RepoId("r1".into()), &EncryptedPath("/b".into())),
+                get_cache_key(&RepoId("r1".into()), &EncryptedPath("/d".into())),
+            ]
+        );
+
+        let info = get_info(&index);
+        assert_eq!(info.used, 110);
+        assert_eq!(info.pinned_used, 50);
+    }
+}
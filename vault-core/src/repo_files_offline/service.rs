@@ -0,0 +1,807 @@
+use std::{
+    collections::HashSet,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, RwLock,
+    },
+};
+
+use futures::{channel::mpsc, FutureExt, StreamExt, TryStreamExt};
+
+use crate::{
+    cipher::Cipher,
+    common::state::BoxAsyncRead,
+    remote::ApiErrorCode,
+    remote_files::{state::RemoteFilesFileReader, RemoteFilesService},
+    repo_files::{selectors as repo_files_selectors, state::RepoFile},
+    repo_files_list::{state::RepoFilesListRecursiveItem, RepoFilesListService},
+    repos::ReposService,
+    runtime,
+    secure_storage::SecureStorageService,
+    store,
+    types::{EncryptedPath, RepoId},
+    utils::tee_reader::TeeReader,
+};
+
+use super::{
+    disk_cache::OfflineDiskCache,
+    errors::OfflineError,
+    selectors,
+    state::{OfflineEntry, OfflineIndex, OfflineIndexKey, OfflineInfo, OfflinePin},
+};
+
+pub struct RepoFilesOfflineService {
+    repos_service: Arc<ReposService>,
+    remote_files_service: Arc<RemoteFilesService>,
+    repo_files_list_service: Arc<RepoFilesListService>,
+    secure_storage_service: Arc<SecureStorageService>,
+    store: Arc<store::Store>,
+    runtime: Arc<runtime::BoxRuntime>,
+
+    disk_cache: RwLock<Option<Arc<dyn OfflineDiskCache + Send + Sync>>>,
+    index: Mutex<OfflineIndex>,
+    index_cipher: Mutex<Option<Arc<Cipher>>>,
+    pending_pins: Mutex<Vec<OfflinePin>>,
+    refresh_running: AtomicBool,
+    save_scheduled: AtomicBool,
+    remote_files_subscription_id: u32,
+    repos_subscription_id: u32,
+}
+
+impl RepoFilesOfflineService {
+    pub fn new(
+        repos_service: Arc<ReposService>,
+        remote_files_service: Arc<RemoteFilesService>,
+        repo_files_list_service: Arc<RepoFilesListService>,
+        secure_storage_service: Arc<SecureStorageService>,
+        store: Arc<store::Store>,
+        runtime: Arc<runtime::BoxRuntime>,
+    ) -> Arc<Self> {
+        let remote_files_subscription_id = store.get_next_id();
+        let repos_subscription_id = store.get_next_id();
+
+        let repo_files_offline_service = Arc::new(Self {
+            repos_service,
+            remote_files_service,
+            repo_files_list_service,
+            secure_storage_service,
+            store: store.clone(),
+            runtime,
+
+            disk_cache: RwLock::new(None),
+            index: Mutex::new(Default::default()),
+            index_cipher: Mutex::new(None),
+            pending_pins: Mutex::new(Vec::new()),
+            refresh_running: AtomicBool::new(false),
+            save_scheduled: AtomicBool::new(false),
+            remote_files_subscription_id,
+            repos_subscription_id,
+        });
+
+        let remote_files_subscription_service = Arc::downgrade(&repo_files_offline_service);
+        let remote_files_subscription_store = store.clone();
+
+        store.on(
+            remote_files_subscription_id,
+            &[store::Event::RemoteFiles],
+            Box::new(move |mutation_state, add_side_effect| {
+                let remote_files = &mutation_state.remote_files;
+
+                let changed_paths = remote_files
+                    .created_files
+                    .iter()
+                    .chain(remote_files.removed_files.iter())
+                    .chain(remote_files.tags_updated.iter())
+                    .cloned()
+                    .chain(remote_files.moved_files.iter().flat_map(
+                        |(mount_id, old_path, new_path)| {
+                            [
+                                (mount_id.clone(), old_path.clone()),
+                                (mount_id.clone(), new_path.clone()),
+                            ]
+                        },
+                    ))
+                    .collect::<Vec<_>>();
+
+                if changed_paths.is_empty() {
+                    return;
+                }
+
+                if let Some(service) = remote_files_subscription_service.upgrade() {
+                    let pins = service.index.lock().unwrap().pins.clone();
+
+                    let changed_pins = remote_files_subscription_store.with_state(|state| {
+                        selectors::select_changed_pins(state, &pins, &changed_paths)
+                    });
+
+                    if !changed_pins.is_empty() {
+                        add_side_effect(Box::new(move || {
+                            service.schedule_refresh(changed_pins);
+                        }));
+                    }
+                }
+            }),
+        );
+
+        let repos_subscription_service = Arc::downgrade(&repo_files_offline_service);
+
+        store.on(
+            repos_subscription_id,
+            &[store::Event::Repos],
+            Box::new(move |mutation_state, add_side_effect| {
+                if mutation_state.repos.unlocked_repos.is_empty() {
+                    return;
+                }
+
+                if let Some(service) = repos_subscription_service.upgrade() {
+                    let unlocked_repo_ids = mutation_state
+                        .repos
+                        .unlocked_repos
+                        .iter()
+                        .map(|(repo_id, _)| repo_id)
+                        .collect::<HashSet<_>>();
+
+                    let pins = service
+                        .index
+                        .lock()
+                        .unwrap()
+                        .pins
+                        .iter()
+                        .filter(|pin| unlocked_repo_ids.contains(&pin.repo_id))
+                        .cloned()
+                        .collect::<Vec<_>>();
+
+                    if !pins.is_empty() {
+                        add_side_effect(Box::new(move || {
+                            service.schedule_refresh(pins);
+                        }));
+                    }
+                }
+            }),
+        );
+
+        repo_files_offline_service
+    }
+
+    /// The index is loaded from the disk cache when the disk cache is set.
+    pub fn set_disk_cache(&self, disk_cache: Option<Box<dyn OfflineDiskCache + Send + Sync>>) {
+        *self.disk_cache.write().unwrap() = disk_cache.map(Arc::from);
+
+        let index = self.load_index();
+
+        *self.index.lock().unwrap() = index;
+    }
+
+    fn get_disk_cache(&self) -> Result<Arc<dyn OfflineDiskCache + Send + Sync>, OfflineError> {
+        self.disk_cache
+            .read()
+            .unwrap()
+            .clone()
+            .ok_or(OfflineError::DiskCacheNotAvailable)
+    }
+
+    fn load_index_cipher(&self) -> Result<Option<Arc<Cipher>>, OfflineError> {
+        let mut index_cipher = self.index_cipher.lock().unwrap();
+
+        if let Some(index_cipher) = index_cipher.as_ref() {
+            return Ok(Some(index_cipher.clone()));
+        }
+
+        let key = match self
+            .secure_storage_service
+            .get::<OfflineIndexKey>(selectors::INDEX_KEY_STORAGE_KEY)?
+        {
+            Some(key) => key,
+            None => return Ok(None),
+        };
+
+        let new_index_cipher =
+            Arc::new(Cipher::with_random_keys(&key.keys).map_err(OfflineError::DiskCacheError)?);
+
+        *index_cipher = Some(new_index_cipher.clone());
+
+        Ok(Some(new_index_cipher))
+    }
+
+    fn get_or_create_index_cipher(&self) -> Result<Arc<Cipher>, OfflineError> {
+        if let Some(index_cipher) = self.load_index_cipher()? {
+            return Ok(index_cipher);
+        }
+
+        let key = OfflineIndexKey {
+            keys: Cipher::generate_random_keys().map_err(OfflineError::DiskCacheError)?,
+        };
+
+        let index_cipher =
+            Arc::new(Cipher::with_random_keys(&key.keys).map_err(OfflineError::DiskCacheError)?);
+
+        self.secure_storage_service
+            .set(selectors::INDEX_KEY_STORAGE_KEY, &key)?;
+
+        *self.index_cipher.lock().unwrap() = Some(index_cipher.clone());
+
+        Ok(index_cipher)
+    }
+
+    /// An index that cannot be read (e.g. its key was removed on logout)
+    /// leaves the cached files without entries so they are removed.
+    fn load_index(&self) -> OfflineIndex {
+        let disk_cache = match self.get_disk_cache() {
+            Ok(disk_cache) => disk_cache,
+            Err(_) => return Default::default(),
+        };
+
+        let res = match disk_cache.get_index() {
+            Ok(Some(encrypted_bytes)) => self.decrypt_index(&encrypted_bytes),
+            Ok(None) => return Default::default(),
+            Err(err) => Err(OfflineError::DiskCacheError(err)),
+        };
+
+        match res {
+            Ok(index) => index,
+            Err(err) => {
+                log::warn!("Offline index load failed: {}", err);
+
+                self.spawn_disk_cache_clear(disk_cache);
+
+                Default::default()
+            }
+        }
+    }
+
+    fn decrypt_index(&self, encrypted_bytes: &[u8]) -> Result<OfflineIndex, OfflineError> {
+        let index_cipher = self
+            .load_index_cipher()?
+            .ok_or_else(|| OfflineError::DiskCacheError("index key not found".into()))?;
+
+        let bytes = index_cipher
+            .decrypt_vec(encrypted_bytes)
+            .map_err(|err| OfflineError::DiskCacheError(err.to_string()))?;
+
+        serde_json::from_slice(&bytes).map_err(|err| OfflineError::DiskCacheError(err.to_string()))
+    }
+
+    fn save(&self, index: &OfflineIndex) -> Result<(), OfflineError> {
+        self.save_scheduled.store(false, Ordering::SeqCst);
+
+        // without a disk cache there are no cached files to index
+        let disk_cache = match self.get_disk_cache() {
+            Ok(disk_cache) => disk_cache,
+            Err(_) => return Ok(()),
+        };
+
+        let index_cipher = self.get_or_create_index_cipher()?;
+
+        let encrypted_bytes = serde_json::to_vec(index)
+            .map_err(|err| err.to_string())
+            .and_then(|bytes| {
+                index_cipher
+                    .encrypt_vec(&bytes)
+                    .map_err(|err| err.to_string())
+            })
+            .map_err(OfflineError::DiskCacheError)?;
+
+        disk_cache
+            .set_index(&encrypted_bytes)
+            .map_err(OfflineError::DiskCacheError)
+    }
+
+    fn spawn_disk_cache_clear(&self, disk_cache: Arc<dyn OfflineDiskCache + Send + Sync>) {
+        self.runtime.spawn(
+            async move {
+                if let Err(err) = disk_cache.clear().await {
+                    log::warn!("Offline disk cache clear failed: {}", err);
+                }
+            }
+            .boxed(),
+        );
+    }
+
+    /// Access times change on every cache hit so they are saved with a delay
+    /// instead of writing the whole index each time.
+    fn schedule_save(self: &Arc<Self>) {
+        if self.save_scheduled.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let sleep = self.runtime.sleep(selectors::SAVE_DELAY);
+        let this = Arc::downgrade(self);
+
+        self.runtime.spawn(
+            async move {
+                sleep.await;
+
+                if let Some(this) = this.upgrade() {
+                    if this.save_scheduled.load(Ordering::SeqCst) {
+                        let index = this.index.lock().unwrap();
+
+                        if let Err(err) = this.save(&index) {
+                            log::warn!("Offline index save failed: {}", err);
+                        }
+                    }
+                }
+            }
+            .boxed(),
+        );
+    }
+
+    pub fn get_info(&self) -> OfflineInfo {
+        selectors::get_info(&self.index.lock().unwrap())
+    }
+
+    pub fn is_pinned(&self, repo_id: &RepoId, path: &EncryptedPath) -> bool {
+        selectors::is_pinned(&self.index.lock().unwrap().pins, repo_id, path)
+    }
+
+    /// Pins the file or dir and downloads its content.
+    pub async fn pin(&self, repo_id: RepoId, path: EncryptedPath) -> Result<(), OfflineError> {
+        self.get_disk_cache()?;
+
+        let pin = OfflinePin { repo_id, path };
+
+        {
+            let mut index = self.index.lock().unwrap();
+
+            if !index.pins.contains(&pin) {
+                index.pins.push(pin.clone());
+
+                self.save(&index)?;
+            }
+        }
+
+        self.refresh_pin(&pin).await
+    }
+
+    /// Unpinned files stay in the cache until they are evicted.
+    pub async fn unpin(&self, repo_id: &RepoId, path: &EncryptedPath) -> Result<(), OfflineError> {
+        {
+            let mut index = self.index.lock().unwrap();
+
+            index
+                .pins
+                .retain(|pin| &pin.repo_id != repo_id || &pin.path != path);
+
+            self.save(&index)?;
+        }
+
+        self.evict().await
+    }
+
+    pub async fn set_quota(&self, quota: i64) -> Result<(), OfflineError> {
+        {
+            let mut index = self.index.lock().unwrap();
+
+            index.quota = quota;
+
+            self.save(&index)?;
+        }
+
+        self.evict().await
+    }
+
+    /// Refreshes all pins. Pins in locked repos are skipped.
+    pub async fn refresh(&self) -> Result<(), OfflineError> {
+        self.get_disk_cache()?;
+
+        let pins = self.index.lock().unwrap().pins.clone();
+
+        for pin in pins {
+            match self.refresh_pin(&pin).await {
+                Ok(()) | Err(OfflineError::RepoLocked(_)) => {}
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Removes all cached files and pins.
+    pub async fn clear(&self) -> Result<(), OfflineError> {
+        {
+            let mut index = self.index.lock().unwrap();
+
+            index.pins.clear();
+            index.entries.clear();
+
+            self.save(&index)?;
+        }
+
+        if let Ok(disk_cache) = self.get_disk_cache() {
+            disk_cache
+                .clear()
+                .await
+                .map_err(OfflineError::DiskCacheError)?;
+        }
+
+        Ok(())
+    }
+
+    /// The index key is removed from the secure storage on logout so the
+    /// index and the cached files are removed too.
+    pub fn logout(&self) {
+        self.save_scheduled.store(false, Ordering::SeqCst);
+
+        *self.index.lock().unwrap() = Default::default();
+        *self.index_cipher.lock().unwrap() = None;
+        self.pending_pins.lock().unwrap().clear();
+
+        if let Ok(disk_cache) = self.get_disk_cache() {
+            self.spawn_disk_cache_clear(disk_cache);
+        }
+    }
+
+    fn schedule_refresh(self: Arc<Self>, pins: Vec<OfflinePin>) {
+        if self.get_disk_cache().is_err() {
+            return;
+        }
+
+        {
+            let mut pending_pins = self.pending_pins.lock().unwrap();
+
+            for pin in pins {
+                if !pending_pins.contains(&pin) {
+                    pending_pins.push(pin);
+                }
+            }
+        }
+
+        if self.refresh_running.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let this = self.clone();
+
+        self.runtime.spawn(
+            async move {
+                loop {
+                    let pins = std::mem::take(&mut *this.pending_pins.lock().unwrap());
+
+                    if pins.is_empty() {
+                        this.refresh_running.store(false, Ordering::SeqCst);
+
+                        // pins could have been added before running was reset
+                        if this.pending_pins.lock().unwrap().is_empty()
+                            || this.refresh_running.swap(true, Ordering::SeqCst)
+                        {
+                            return;
+                        }
+
+                        continue;
+                    }
+
+                    for pin in pins {
+                        // the pin could have been removed in the meantime
+                        if !this.index.lock().unwrap().pins.contains(&pin) {
+                            continue;
+                        }
+
+                        if let Err(err) = this.refresh_pin(&pin).await {
+                            log::warn!("Offline refresh failed: {:?}: {}", pin.path, err);
+                        }
+                    }
+                }
+            }
+            .boxed(),
+        );
+    }
+
+    async fn get_pin_files(&self, pin: &OfflinePin) -> Result<Vec<RepoFile>, OfflineError> {
+        let (mount_id, remote_path) = self.store.with_state(|state| {
+            repo_files_selectors::select_repo_path_to_mount_path(state, &pin.repo_id, &pin.path)
+        })?;
+
+        match self
+            .remote_files_service
+            .load_file(&mount_id, &remote_path)
+            .await
+        {
+            Ok(()) => {}
+            Err(err) if err.is_api_error_code(ApiErrorCode::NotFound) => return Ok(Vec::new()),
+            Err(err) => return Err(err.into()),
+        }
+
+        let file = match self.store.with_state(|state| {
+            repo_files_selectors::select_file(
+                state,
+                &repo_files_selectors::get_file_id(&pin.repo_id, &pin.path),
+            )
+            .cloned()
+        }) {
+            Some(file) => file,
+            None => return Ok(Vec::new()),
+        };
+
+        if file.typ.is_file() {
+            return Ok(vec![file]);
+        }
+
+        let items = self
+            .repo_files_list_service
+            .get_list_recursive(&file)
+            .await?
+            .collect::<Vec<_>>()
+            .await;
+
+        let mut files = Vec::new();
+
+        for item in items {
+            match item {
+                RepoFilesListRecursiveItem::File { file, .. } => {
+                    if file.typ.is_file() {
+                        files.push(file);
+                    }
+                }
+                RepoFilesListRecursiveItem::Error { error, .. } => return Err(error.into()),
+            }
+        }
+
+        Ok(files)
+    }
+
+    /// Downloads missing and changed files and removes files that no longer
+    /// exist.
+    async fn refresh_pin(&self, pin: &OfflinePin) -> Result<(), OfflineError> {
+        let disk_cache = self.get_disk_cache()?;
+
+        // file names can only be listed in unlocked repos
+        self.repos_service.get_cipher(&pin.repo_id)?;
+
+        let files = self.get_pin_files(pin).await?;
+
+        let mut keys = HashSet::new();
+
+        for file in files {
+            let key = selectors::get_cache_key(&file.repo_id, &file.encrypted_path);
+
+            keys.insert(key.clone());
+
+            let is_valid = self
+                .index
+                .lock()
+                .unwrap()
+                .entries
+                .get(&key)
+                .map(|entry| selectors::is_entry_valid(entry, &file))
+                .unwrap_or(false);
+
+            if is_valid {
+                continue;
+            }
+
+            let reader = self
+                .remote_files_service
+                .get_file_reader(&file.mount_id, &file.remote_path)
+                .await?;
+
+            let size = disk_cache
+                .set(&key, reader.reader)
+                .await
+                .map_err(OfflineError::DiskCacheError)?;
+
+            let mut index = self.index.lock().unwrap();
+
+            index.entries.insert(
+                key,
+                OfflineEntry {
+                    repo_id: file.repo_id.clone(),
+                    path: file.encrypted_path.clone(),
+                    size,
+                    remote_modified: reader.file.modified,
+                    remote_hash: reader.file.hash,
+                    accessed: self.runtime.now().0,
+                },
+            );
+
+            self.save(&index)?;
+        }
+
+        let removed_keys = {
+            let mut index = self.index.lock().unwrap();
+
+            let removed_keys = index
+                .entries
+                .iter()
+                .filter(|(key, entry)| {
+                    entry.repo_id == pin.repo_id
+                        && selectors::is_path_in(&entry.path, &pin.path)
+                        && !keys.contains(*key)
+                })
+                .map(|(key, _)| key.clone())
+                .collect::<Vec<_>>();
+
+            if !removed_keys.is_empty() {
+                for key in &removed_keys {
+                    index.entries.remove(key);
+                }
+
+                self.save(&index)?;
+            }
+
+            removed_keys
+        };
+
+        for key in removed_keys {
+            self.disk_cache_remove(disk_cache.as_ref(), &key).await;
+        }
+
+        self.evict().await
+    }
+
+    async fn evict(&self) -> Result<(), OfflineError> {
+        let keys = {
+            let mut index = self.index.lock().unwrap();
+
+            let keys = selectors::get_eviction_keys(&index);
+
+            if !keys.is_empty() {
+                for key in &keys {
+                    index.entries.remove(key);
+                }
+
+                self.save(&index)?;
+            }
+
+            keys
+        };
+
+        if let Ok(disk_cache) = self.get_disk_cache() {
+            for key in keys {
+                self.disk_cache_remove(disk_cache.as_ref(), &key).await;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn disk_cache_remove(
+        &self,
+        disk_cache: &(dyn OfflineDiskCache + Send + Sync),
+        key: &str,
+    ) {
+        if let Err(err) = disk_cache.remove(key).await {
+            log::warn!("Offline disk cache remove failed: {}", err);
+        }
+    }
+
+    /// Returns the encrypted content and its size. Stale content is only
+    /// returned if `allow_stale` is set (e.g. when the remote is not
+    /// reachable).
+    pub async fn get_reader(
+        self: &Arc<Self>,
+        file: &RepoFile,
+        allow_stale: bool,
+    ) -> Option<(BoxAsyncRead, i64)> {
+        let disk_cache = self.get_disk_cache().ok()?;
+
+        let key = selectors::get_cache_key(&file.repo_id, &file.encrypted_path);
+
+        let size = {
+            let index = self.index.lock().unwrap();
+            let entry = index.entries.get(&key)?;
+
+            if !allow_stale && !selectors::is_entry_valid(entry, file) {
+                return None;
+            }
+
+            entry.size
+        };
+
+        match disk_cache.get(&key).await {
+            Ok(Some(reader)) => {
+                if let Some(entry) = self.index.lock().unwrap().entries.get_mut(&key) {
+                    entry.accessed = self.runtime.now().0;
+                }
+
+                self.schedule_save();
+
+                Some((reader, size))
+            }
+            Ok(None) => {
+                let mut index = self.index.lock().unwrap();
+
+                index.entries.remove(&key);
+
+                if let Err(err) = self.save(&index) {
+                    log::warn!("Offline index save failed: {}", err);
+                }
+
+                None
+            }
+            Err(err) => {
+                log::warn!("Offline disk cache get failed: {}", err);
+
+                None
+            }
+        }
+    }
+
+    /// Wraps the remote reader of a file so that its encrypted content is
+    /// also added to the cache while it is read. Content that is not read to
+    /// the end is not cached. Cached unpinned files are evicted least recently
+    /// accessed first when the quota is exceeded.
+    pub fn cache_reader(
+        self: &Arc<Self>,
+        file: &RepoFile,
+        reader: RemoteFilesFileReader,
+    ) -> RemoteFilesFileReader {
+        let disk_cache = match self.get_disk_cache() {
+            Ok(disk_cache) => disk_cache,
+            Err(_) => return reader,
+        };
+
+        if reader.size > self.index.lock().unwrap().quota {
+            return reader;
+        }
+
+        let RemoteFilesFileReader {
+            file: remote_file,
+            size,
+            reader,
+        } = reader;
+
+        let (sender, receiver) = mpsc::unbounded();
+
+        let key = selectors::get_cache_key(&file.repo_id, &file.encrypted_path);
+        let repo_id = file.repo_id.clone();
+        let path = file.encrypted_path.clone();
+        let remote_modified = remote_file.modified;
+        let remote_hash = remote_file.hash.clone();
+        let this = self.clone();
+
+        self.runtime.spawn(
+            async move {
+                let size = match disk_cache
+                    .set(&key, Box::pin(receiver.into_async_read()))
+                    .await
+                {
+                    Ok(size) => size,
+                    Err(err) => {
+                        log::debug!("Offline cache of read file failed: {}", err);
+
+                        return;
+                    }
+                };
+
+                this.index.lock().unwrap().entries.insert(
+                    key,
+                    OfflineEntry {
+                        repo_id,
+                        path,
+                        size,
+                        remote_modified,
+                        remote_hash,
+                        accessed: this.runtime.now().0,
+                    },
+                );
+
+                // reads are frequent so the index is saved with a delay
+                this.schedule_save();
+
+                if let Err(err) = this.evict().await {
+                    log::warn!("Offline evict failed: {}", err);
+                }
+            }
+            .boxed(),
+        );
+
+        RemoteFilesFileReader {
+            file: remote_file,
+            size,
+            reader: Box::pin(TeeReader::new(reader, sender)),
+        }
+    }
+}
+
+impl Drop for RepoFilesOfflineService {
+    fn drop(&mut self) {
+        if self.save_scheduled.load(Ordering::SeqCst) {
+            if let Err(err) = self.save(&self.index.lock().unwrap()) {
+                log::warn!("Offline index save failed: {}", err);
+            }
+        }
+
+        self.store
+            .remove_listener(self.remote_files_subscription_id);
+        self.store.remove_listener(self.repos_subscription_id);
+    }
+}
@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::types::{EncryptedPath, RepoId};
+
+/// Pinned files and dirs are kept in the cache and refreshed when they
+/// change. A pinned dir pins all files in it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OfflinePin {
+    #[serde(rename = "r")]
+    pub repo_id: RepoId,
+    #[serde(rename = "p")]
+    pub path: EncryptedPath,
+}
+
+/// A cached file. `size` is the size of the encrypted content.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OfflineEntry {
+    #[serde(rename = "r")]
+    pub repo_id: RepoId,
+    #[serde(rename = "p")]
+    pub path: EncryptedPath,
+    #[serde(rename = "s")]
+    pub size: i64,
+    #[serde(rename = "m", default, skip_serializing_if = "Option::is_none")]
+    pub remote_modified: Option<i64>,
+    #[serde(rename = "h", default, skip_serializing_if = "Option::is_none")]
+    pub remote_hash: Option<String>,
+    #[serde(rename = "a")]
+    pub accessed: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OfflineIndex {
+    #[serde(rename = "q")]
+    pub quota: i64,
+    #[serde(rename = "p")]
+    pub pins: Vec<OfflinePin>,
+    #[serde(rename = "e")]
+    pub entries: HashMap<String, OfflineEntry>,
+}
+
+impl Default for OfflineIndex {
+    fn default() -> Self {
+        Self {
+            quota: super::selectors::DEFAULT_QUOTA,
+            pins: Vec::new(),
+            entries: HashMap::new(),
+        }
+    }
+}
+
+/// Stored in the secure storage so the index cannot be read after logout.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OfflineIndexKey {
+    /// Base64 encoded random cipher keys.
+    #[serde(rename = "k")]
+    pub keys: Zeroizing<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OfflineInfo {
+    pub quota: i64,
+    pub used: i64,
+    pub pinned_used: i64,
+    pub pins_count: usize,
+    pub entries_count: usize,
+}
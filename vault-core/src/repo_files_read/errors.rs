@@ -4,7 +4,7 @@ use crate::{
     cipher::errors::{DecryptFilenameError, DecryptSizeError},
     remote::RemoteError,
     repos::errors::{GetCipherError, RepoLockedError, RepoNotFoundError},
-    user_error::UserError,
+    user_error::{ErrorCode, UserError},
 };
 
 #[derive(Error, Debug, Clone, PartialEq)]
@@ -17,6 +17,8 @@ pub enum GetFilesReaderError {
     FileNotFound,
     #[error("files empty")]
     FilesEmpty,
+    #[error("range not satisfiable")]
+    RangeNotSatisfiable,
     #[error("{0}")]
     DecryptFilenameError(#[from] DecryptFilenameError),
     #[error("{0}")]
@@ -36,6 +38,7 @@ impl UserError for GetFilesReaderError {
             Self::RepoLocked(err) => err.user_error(),
             Self::FileNotFound => self.to_string(),
             Self::FilesEmpty => self.to_string(),
+            Self::RangeNotSatisfiable => self.to_string(),
             Self::DecryptFilenameError(err) => err.user_error(),
             Self::DecryptSizeError(err) => err.user_error(),
             Self::RemoteError(err) => err.user_error(),
@@ -43,6 +46,21 @@ impl UserError for GetFilesReaderError {
             Self::Aborted => self.to_string(),
         }
     }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::RepoNotFound(err) => err.error_code(),
+            Self::RepoLocked(err) => err.error_code(),
+            Self::FileNotFound => ErrorCode::FileNotFound,
+            Self::FilesEmpty => ErrorCode::FileFilesEmpty,
+            Self::RangeNotSatisfiable => ErrorCode::FileRangeNotSatisfiable,
+            Self::DecryptFilenameError(err) => err.error_code(),
+            Self::DecryptSizeError(err) => err.error_code(),
+            Self::RemoteError(err) => err.error_code(),
+            Self::IOError(_) => ErrorCode::FileIO,
+            Self::Aborted => ErrorCode::FileAborted,
+        }
+    }
 }
 
 impl From<GetCipherError> for GetFilesReaderError {
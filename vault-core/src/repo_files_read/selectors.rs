@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, ops::RangeInclusive};
 
 use crate::{
     repo_files::{
@@ -11,6 +11,8 @@ use crate::{
     utils::repo_encrypted_path_utils,
 };
 
+use super::state::RepoFileReadRange;
+
 pub fn select_files_zip_name(state: &store::State, files: &[RepoFile]) -> DecryptedName {
     let files_len = files.len();
 
@@ -60,6 +62,28 @@ pub fn select_files_zip_name(state: &store::State, files: &[RepoFile]) -> Decryp
     }
 }
 
+/// Returns None if the range is not satisfiable for a file of `size` bytes.
+pub fn get_range(range: &RepoFileReadRange, size: i64) -> Option<RangeInclusive<i64>> {
+    match range {
+        RepoFileReadRange::FromStart { start, end } => {
+            let end = end.unwrap_or(size - 1).min(size - 1);
+
+            if *start < 0 || *start >= size || end < *start {
+                None
+            } else {
+                Some(*start..=end)
+            }
+        }
+        RepoFileReadRange::Suffix(length) => {
+            if *length <= 0 || size == 0 {
+                None
+            } else {
+                Some((size - length).max(0)..=size - 1)
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use similar_asserts::assert_eq;
@@ -71,7 +95,7 @@ mod tests {
         types::{DecryptedName, RepoFileId},
     };
 
-    use super::select_files_zip_name;
+    use super::{super::state::RepoFileReadRange, get_range, select_files_zip_name};
 
     #[test]
     fn test_select_files_zip_name() {
@@ -147,4 +171,25 @@ mod tests {
             DecryptedName("4-selected-items.zip".into())
         );
     }
+
+    #[test]
+    fn test_get_range() {
+        let from_start = |start, end| RepoFileReadRange::FromStart { start, end };
+
+        assert_eq!(get_range(&from_start(0, None), 100), Some(0..=99));
+        assert_eq!(get_range(&from_start(10, Some(19)), 100), Some(10..=19));
+        assert_eq!(get_range(&from_start(90, Some(200)), 100), Some(90..=99));
+        assert_eq!(get_range(&from_start(100, None), 100), None);
+        assert_eq!(get_range(&from_start(20, Some(10)), 100), None);
+        assert_eq!(get_range(&from_start(0, None), 0), None);
+        assert_eq!(
+            get_range(&RepoFileReadRange::Suffix(10), 100),
+            Some(90..=99)
+        );
+        assert_eq!(
+            get_range(&RepoFileReadRange::Suffix(200), 100),
+            Some(0..=99)
+        );
+        assert_eq!(get_range(&RepoFileReadRange::Suffix(0), 100), None);
+    }
 }
@@ -1,14 +1,19 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, ops::RangeInclusive, sync::Arc};
 
 use futures::{
-    channel::mpsc, io::BufReader, AsyncWrite, FutureExt, SinkExt, StreamExt, TryStreamExt,
+    channel::mpsc, io::BufReader, AsyncReadExt, AsyncWrite, FutureExt, SinkExt, StreamExt,
+    TryStreamExt,
+};
+use vault_crypto::{
+    constants::FILE_HEADER_SIZE,
+    data_cipher::{decrypt_size, encrypted_range, parse_file_header},
 };
-use vault_crypto::data_cipher::decrypt_size;
 
 use crate::{
     cipher::{errors::DecryptSizeError, Cipher},
     common::state::{BoxAsyncRead, SizeInfo},
-    remote_files::RemoteFilesService,
+    remote::RemoteError,
+    remote_files::{state::RemoteFilesFileReader, RemoteFilesService},
     repo_files::{
         selectors as repo_files_selectors,
         state::{RepoFile, RepoFileType},
@@ -16,6 +21,7 @@ use crate::{
     repo_files_list::{
         errors::GetListRecursiveError, state::RepoFilesListRecursiveItem, RepoFilesListService,
     },
+    repo_files_offline::RepoFilesOfflineService,
     repo_files_tags::RepoFilesTagsService,
     repos::ReposService,
     runtime, store,
@@ -26,7 +32,10 @@ use crate::{
 use super::{
     errors::GetFilesReaderError,
     mutations, selectors,
-    state::{GetRemoteZipEntries, RemoteZipEntry, RepoFileReader, RepoFileReaderProvider},
+    state::{
+        GetRemoteZipEntries, RemoteZipEntry, RepoFileRangeReader, RepoFileReadRange,
+        RepoFileReader, RepoFileReaderProvider,
+    },
 };
 
 pub struct RepoFilesReadService {
@@ -34,6 +43,7 @@ pub struct RepoFilesReadService {
     remote_files_service: Arc<RemoteFilesService>,
     repo_files_list_service: Arc<RepoFilesListService>,
     repo_files_tags_service: Arc<RepoFilesTagsService>,
+    repo_files_offline_service: Arc<RepoFilesOfflineService>,
     store: Arc<store::Store>,
     runtime: Arc<runtime::BoxRuntime>,
 }
@@ -44,6 +54,7 @@ impl RepoFilesReadService {
         remote_files_service: Arc<RemoteFilesService>,
         repo_files_list_service: Arc<RepoFilesListService>,
         repo_files_tags_service: Arc<RepoFilesTagsService>,
+        repo_files_offline_service: Arc<RepoFilesOfflineService>,
         store: Arc<store::Store>,
         runtime: Arc<runtime::BoxRuntime>,
     ) -> Self {
@@ -52,6 +63,7 @@ impl RepoFilesReadService {
             remote_files_service,
             repo_files_list_service,
             repo_files_tags_service,
+            repo_files_offline_service,
             store,
             runtime,
         }
@@ -71,6 +83,16 @@ impl RepoFilesReadService {
             .get_file_reader(&mount_id, &remote_path)
             .await?;
 
+        Self::decrypt_remote_file_reader(encrypted_reader, name, content_type, unique_name, cipher)
+    }
+
+    fn decrypt_remote_file_reader(
+        encrypted_reader: RemoteFilesFileReader,
+        name: DecryptedName,
+        content_type: Option<&str>,
+        unique_name: Option<&str>,
+        cipher: &Cipher,
+    ) -> Result<RepoFileReader, GetFilesReaderError> {
         let size = decrypt_size(encrypted_reader.size)
             .map_err(|err| GetFilesReaderError::DecryptSizeError(DecryptSizeError::from(err)))?;
 
@@ -86,6 +108,35 @@ impl RepoFilesReadService {
         })
     }
 
+    async fn get_offline_file_reader(
+        &self,
+        file: &RepoFile,
+        name: DecryptedName,
+        allow_stale: bool,
+        cipher: &Cipher,
+    ) -> Result<Option<RepoFileReader>, GetFilesReaderError> {
+        let (encrypted_reader, encrypted_size) = match self
+            .repo_files_offline_service
+            .get_reader(file, allow_stale)
+            .await
+        {
+            Some(reader) => reader,
+            None => return Ok(None),
+        };
+
+        let size = decrypt_size(encrypted_size)
+            .map_err(|err| GetFilesReaderError::DecryptSizeError(DecryptSizeError::from(err)))?;
+
+        Ok(Some(RepoFileReader {
+            name,
+            size: SizeInfo::Exact(size),
+            content_type: file.content_type.clone(),
+            remote_file: None,
+            unique_name: Some(file.unique_name.clone()),
+            reader: Box::pin(cipher.decrypt_reader_async(encrypted_reader)),
+        }))
+    }
+
     async fn get_file_reader_file(
         &self,
         file: &RepoFile,
@@ -94,16 +145,39 @@ impl RepoFilesReadService {
 
         let cipher = self.repos_service.get_cipher(&file.repo_id)?;
 
-        let reader = self
-            .get_remote_file_reader(
-                &file.mount_id,
-                &file.remote_path,
-                name,
+        if let Some(reader) = self
+            .get_offline_file_reader(file, name.clone(), false, &cipher)
+            .await?
+        {
+            return Ok(reader);
+        }
+
+        let reader = match self
+            .remote_files_service
+            .get_file_reader(&file.mount_id, &file.remote_path)
+            .await
+        {
+            // the content is added to the offline cache while it is read
+            Ok(encrypted_reader) => Self::decrypt_remote_file_reader(
+                self.repo_files_offline_service
+                    .cache_reader(file, encrypted_reader),
+                name.clone(),
                 file.content_type.as_deref(),
                 Some(&file.unique_name),
                 &cipher,
-            )
-            .await?;
+            )?,
+            // fall back to a stale offline copy if the remote is not reachable
+            Err(err @ RemoteError::HttpError(_)) => {
+                return match self
+                    .get_offline_file_reader(file, name, true, &cipher)
+                    .await?
+                {
+                    Some(reader) => Ok(reader),
+                    None => Err(err.into()),
+                };
+            }
+            Err(err) => return Err(err.into()),
+        };
 
         if file.hash().is_none() {
             if let Some(remote_file_hash) = reader
@@ -120,6 +194,124 @@ impl RepoFilesReadService {
         Ok(reader)
     }
 
+    /// Only the blocks that contain the range are downloaded and decrypted.
+    pub async fn get_file_range_reader(
+        &self,
+        file: &RepoFile,
+        range: Option<&RepoFileReadRange>,
+    ) -> Result<RepoFileRangeReader, GetFilesReaderError> {
+        let name = file.decrypted_name()?.to_owned();
+        let size = file
+            .decrypted_size()?
+            .ok_or(GetFilesReaderError::FileNotFound)?;
+
+        let cipher = self.repos_service.get_cipher(&file.repo_id)?;
+
+        let range = match range {
+            Some(range) => Some(
+                selectors::get_range(range, size)
+                    .ok_or(GetFilesReaderError::RangeNotSatisfiable)?,
+            ),
+            None => None,
+        };
+
+        let reader = match &range {
+            Some(range) => {
+                self.get_remote_file_range_reader(
+                    &file.mount_id,
+                    &file.remote_path,
+                    range,
+                    size,
+                    &cipher,
+                )
+                .await?
+            }
+            None => {
+                self.get_remote_file_reader(
+                    &file.mount_id,
+                    &file.remote_path,
+                    name.clone(),
+                    None,
+                    None,
+                    &cipher,
+                )
+                .await?
+                .reader
+            }
+        };
+
+        Ok(RepoFileRangeReader {
+            name,
+            content_type: file.content_type.clone(),
+            size,
+            range,
+            reader,
+        })
+    }
+
+    async fn get_remote_file_range_reader(
+        &self,
+        mount_id: &MountId,
+        remote_path: &RemotePath,
+        range: &RangeInclusive<i64>,
+        size: i64,
+        cipher: &Cipher,
+    ) -> Result<BoxAsyncRead, GetFilesReaderError> {
+        let encrypted_range = encrypted_range(*range.start(), *range.end(), size);
+
+        let mut reader: BoxAsyncRead = if encrypted_range.block_index == 0 {
+            // the file header is right before the first block
+            let encrypted_reader = self
+                .remote_files_service
+                .get_file_range_reader(mount_id, remote_path, 0..=encrypted_range.end as u64)
+                .await?;
+
+            Box::pin(cipher.decrypt_reader_async(encrypted_reader.reader))
+        } else {
+            let mut header_reader = self
+                .remote_files_service
+                .get_file_range_reader(mount_id, remote_path, 0..=FILE_HEADER_SIZE as u64 - 1)
+                .await?
+                .reader;
+
+            let mut header = [0; FILE_HEADER_SIZE];
+
+            header_reader
+                .read_exact(&mut header)
+                .await
+                .map_err(|err| GetFilesReaderError::from(&err))?;
+
+            let nonce = parse_file_header(&header)
+                .map_err(|err| GetFilesReaderError::IOError(err.to_string()))?;
+
+            let encrypted_reader = self
+                .remote_files_service
+                .get_file_range_reader(
+                    mount_id,
+                    remote_path,
+                    encrypted_range.start as u64..=encrypted_range.end as u64,
+                )
+                .await?;
+
+            Box::pin(cipher.decrypt_blocks_reader_async(
+                encrypted_reader.reader,
+                &nonce,
+                encrypted_range.block_index,
+            ))
+        };
+
+        futures::io::copy(
+            (&mut reader).take(encrypted_range.skip as u64),
+            &mut futures::io::sink(),
+        )
+        .await
+        .map_err(|err| GetFilesReaderError::from(&err))?;
+
+        Ok(Box::pin(
+            reader.take((range.end() - range.start() + 1) as u64),
+        ))
+    }
+
     fn generate_missing_hash_reader(
         &self,
         file: &RepoFile,
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{ops::RangeInclusive, sync::Arc};
 
 use futures::future::BoxFuture;
 
@@ -85,6 +85,26 @@ impl RepoFileReaderProvider {
     }
 }
 
+/// Range of decrypted bytes with the semantics of a single HTTP range
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RepoFileReadRange {
+    /// `bytes=start-` or `bytes=start-end` (end is inclusive)
+    FromStart { start: i64, end: Option<i64> },
+    /// `bytes=-length`, the last length bytes
+    Suffix(i64),
+}
+
+pub struct RepoFileRangeReader {
+    pub name: DecryptedName,
+    pub content_type: Option<String>,
+    /// decrypted size of the whole file
+    pub size: i64,
+    /// inclusive range of the returned bytes. None if the whole file is
+    /// returned
+    pub range: Option<RangeInclusive<i64>>,
+    pub reader: BoxAsyncRead,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct RemoteZipEntry {
     pub mount_id: MountId,
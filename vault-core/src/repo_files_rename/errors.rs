@@ -0,0 +1,47 @@
+use thiserror::Error;
+
+use crate::{
+    locale,
+    repos::errors::{GetCipherError, RepoLockedError, RepoNotFoundError},
+    user_error::{ErrorCode, UserError},
+};
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum RenameFilesError {
+    #[error("{0}")]
+    RepoNotFound(#[from] RepoNotFoundError),
+    #[error("{0}")]
+    RepoLocked(#[from] RepoLockedError),
+    #[error("invalid pattern: {0}")]
+    InvalidPattern(String),
+}
+
+impl UserError for RenameFilesError {
+    fn user_error(&self) -> String {
+        match self {
+            Self::RepoNotFound(err) => err.user_error(),
+            Self::RepoLocked(err) => err.user_error(),
+            Self::InvalidPattern(err) => locale::message_args(
+                "error-invalid-pattern",
+                &[("error", err.to_string().into())],
+            ),
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::RepoNotFound(err) => err.error_code(),
+            Self::RepoLocked(err) => err.error_code(),
+            Self::InvalidPattern(_) => ErrorCode::RenameInvalidPattern,
+        }
+    }
+}
+
+impl From<GetCipherError> for RenameFilesError {
+    fn from(err: GetCipherError) -> Self {
+        match err {
+            GetCipherError::RepoNotFound(err) => Self::RepoNotFound(err),
+            GetCipherError::RepoLocked(err) => Self::RepoLocked(err),
+        }
+    }
+}
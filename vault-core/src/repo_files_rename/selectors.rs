@@ -0,0 +1,424 @@
+use std::collections::{HashMap, HashSet};
+
+use regex::{NoExpand, Regex, RegexBuilder};
+
+use crate::{
+    repo_files::{selectors as repo_files_selectors, state::RepoFileName},
+    store,
+    types::{DecryptedName, DecryptedNameLower, EncryptedPath, RepoId},
+    utils::{name_utils, repo_encrypted_path_utils},
+};
+
+use super::{
+    errors::RenameFilesError,
+    state::{
+        RepoFilesRenameCase, RepoFilesRenamePattern, RepoFilesRenamePreviewItem,
+        RepoFilesRenamePreviewStatus, RepoFilesRenameRule,
+    },
+};
+
+/// Compiles regexes for `Regex` and case insensitive `Replace` rules. The
+/// result has the same length as `pattern.rules`.
+pub fn compile_regexes(
+    pattern: &RepoFilesRenamePattern,
+) -> Result<Vec<Option<Regex>>, RenameFilesError> {
+    pattern
+        .rules
+        .iter()
+        .map(|rule| match rule {
+            RepoFilesRenameRule::Replace {
+                find,
+                case_sensitive: false,
+                ..
+            } if !find.is_empty() => RegexBuilder::new(&regex::escape(find))
+                .case_insensitive(true)
+                .build()
+                .map(Some),
+            RepoFilesRenameRule::Regex { pattern, .. } => Regex::new(pattern).map(Some),
+            _ => Ok(None),
+        })
+        .collect::<Result<_, _>>()
+        .map_err(|err| RenameFilesError::InvalidPattern(err.to_string()))
+}
+
+fn title_case(name: &str) -> String {
+    let mut prev_alphanumeric = false;
+
+    name.chars()
+        .flat_map(|c| {
+            let is_word_start = !prev_alphanumeric;
+
+            prev_alphanumeric = c.is_alphanumeric();
+
+            if is_word_start {
+                c.to_uppercase().collect::<Vec<_>>()
+            } else {
+                c.to_lowercase().collect::<Vec<_>>()
+            }
+        })
+        .collect()
+}
+
+fn apply_rule(name: &str, rule: &RepoFilesRenameRule, regex: Option<&Regex>, index: u64) -> String {
+    match (rule, regex) {
+        (RepoFilesRenameRule::Replace { replace, .. }, Some(regex)) => {
+            regex.replace_all(name, NoExpand(replace)).into_owned()
+        }
+        (RepoFilesRenameRule::Replace { find, replace, .. }, None) if !find.is_empty() => {
+            name.replace(find, replace)
+        }
+        (RepoFilesRenameRule::Regex { replace, .. }, Some(regex)) => {
+            regex.replace_all(name, replace.as_str()).into_owned()
+        }
+        (
+            RepoFilesRenameRule::Numbering {
+                template,
+                start,
+                step,
+                padding,
+            },
+            _,
+        ) => {
+            let n = start.saturating_add(step.saturating_mul(index));
+
+            template
+                .replace("{n}", &format!("{:0width$}", n, width = *padding))
+                .replace("{name}", name)
+        }
+        (RepoFilesRenameRule::Case(RepoFilesRenameCase::Lower), _) => name.to_lowercase(),
+        (RepoFilesRenameRule::Case(RepoFilesRenameCase::Upper), _) => name.to_uppercase(),
+        (RepoFilesRenameRule::Case(RepoFilesRenameCase::Title), _) => title_case(name),
+        _ => name.to_owned(),
+    }
+}
+
+/// `index` is the position of the file in the batch and is used for
+/// numbering.
+pub fn get_new_name(
+    name: &DecryptedName,
+    is_dir: bool,
+    index: u64,
+    pattern: &RepoFilesRenamePattern,
+    regexes: &[Option<Regex>],
+) -> DecryptedName {
+    let mut name = name.0.clone();
+
+    for (rule, regex) in pattern.rules.iter().zip(regexes) {
+        name = match rule {
+            RepoFilesRenameRule::Extension(_) if is_dir => name,
+            RepoFilesRenameRule::Extension(ext) => name_utils::join_name_ext(
+                name_utils::split_name_ext(&name).0,
+                ext.as_deref()
+                    .map(|ext| ext.trim_start_matches('.'))
+                    .filter(|ext| !ext.is_empty()),
+            ),
+            _ if is_dir || pattern.include_extension => {
+                apply_rule(&name, rule, regex.as_ref(), index)
+            }
+            _ => {
+                let (base_name, ext) = name_utils::split_name_ext(&name);
+
+                name_utils::join_name_ext(&apply_rule(base_name, rule, regex.as_ref(), index), ext)
+            }
+        };
+    }
+
+    DecryptedName(name)
+}
+
+/// Names are checked against the dir listing after the batch, so a name that
+/// is freed by another file of the same batch (e.g. a swap) is not a
+/// conflict.
+pub fn select_preview(
+    state: &store::State,
+    repo_id: &RepoId,
+    paths: &[EncryptedPath],
+    pattern: &RepoFilesRenamePattern,
+    regexes: &[Option<Regex>],
+) -> Vec<RepoFilesRenamePreviewItem> {
+    let files = paths
+        .iter()
+        .filter_map(|path| {
+            repo_files_selectors::select_file(
+                state,
+                &repo_files_selectors::get_file_id(repo_id, path),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    // numbering skips files with names that cannot be decrypted
+    let mut index = 0;
+
+    let mut items = files
+        .into_iter()
+        .map(|file| {
+            let parent_path = repo_encrypted_path_utils::parent_path(&file.encrypted_path);
+
+            let (name, new_name, status) = match &file.name {
+                RepoFileName::Decrypted { name, .. } => {
+                    let new_name = get_new_name(name, file.typ.is_dir(), index, pattern, regexes);
+
+                    index += 1;
+
+                    let status = if &new_name == name {
+                        RepoFilesRenamePreviewStatus::Unchanged
+                    } else if parent_path.is_none()
+                        || repo_files_selectors::check_name_valid(&new_name).is_err()
+                    {
+                        RepoFilesRenamePreviewStatus::InvalidName
+                    } else {
+                        RepoFilesRenamePreviewStatus::Ok
+                    };
+
+                    (name.clone(), new_name, status)
+                }
+                RepoFileName::DecryptError {
+                    encrypted_name,
+                    error,
+                    ..
+                } => {
+                    let name = DecryptedName(encrypted_name.0.clone());
+
+                    (
+                        name.clone(),
+                        name,
+                        RepoFilesRenamePreviewStatus::DecryptNameError(error.clone()),
+                    )
+                }
+            };
+
+            (
+                parent_path.unwrap_or_else(|| file.encrypted_path.clone()),
+                RepoFilesRenamePreviewItem {
+                    file_id: file.id.clone(),
+                    path: file.encrypted_path.clone(),
+                    name,
+                    new_name,
+                    status,
+                },
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let mut new_names_count: HashMap<(EncryptedPath, DecryptedNameLower), usize> = HashMap::new();
+
+    for (parent_path, item) in &items {
+        if item.status == RepoFilesRenamePreviewStatus::Ok {
+            *new_names_count
+                .entry((parent_path.clone(), item.new_name.to_lowercase()))
+                .or_default() += 1;
+        }
+    }
+
+    for (parent_path, item) in &mut items {
+        if item.status == RepoFilesRenamePreviewStatus::Ok
+            && new_names_count
+                .get(&(parent_path.clone(), item.new_name.to_lowercase()))
+                .filter(|count| **count > 1)
+                .is_some()
+        {
+            item.status = RepoFilesRenamePreviewStatus::Duplicate;
+        }
+    }
+
+    let mut used_names: HashMap<EncryptedPath, HashSet<DecryptedNameLower>> = HashMap::new();
+
+    // a file that is not renamed keeps its name, which can make another
+    // rename a conflict, so this repeats until nothing changes
+    loop {
+        let freed_names = items
+            .iter()
+            .filter(|(_, item)| item.status == RepoFilesRenamePreviewStatus::Ok)
+            .map(|(parent_path, item)| (parent_path.clone(), item.name.to_lowercase()))
+            .collect::<HashSet<_>>();
+
+        let mut changed = false;
+
+        for (parent_path, item) in &mut items {
+            if item.status != RepoFilesRenamePreviewStatus::Ok {
+                continue;
+            }
+
+            let new_name_lower = item.new_name.to_lowercase();
+
+            let parent_used_names = used_names.entry(parent_path.clone()).or_insert_with(|| {
+                repo_files_selectors::select_used_names(state, repo_id, parent_path)
+            });
+
+            // case only renames are allowed
+            if new_name_lower != item.name.to_lowercase()
+                && parent_used_names.contains(&new_name_lower)
+                && !freed_names.contains(&(parent_path.clone(), new_name_lower))
+            {
+                item.status = RepoFilesRenamePreviewStatus::AlreadyExists;
+
+                changed = true;
+            }
+        }
+
+        if !changed {
+            break;
+        }
+    }
+
+    items.into_iter().map(|(_, item)| item).collect()
+}
+
+/// Returns true if the new name is still used by another file of the batch,
+/// which has to be renamed first.
+pub fn select_is_new_name_used(
+    state: &store::State,
+    repo_id: &RepoId,
+    item: &RepoFilesRenamePreviewItem,
+) -> bool {
+    let new_name_lower = item.new_name.to_lowercase();
+
+    match repo_encrypted_path_utils::parent_path(&item.path) {
+        Some(parent_path) => {
+            new_name_lower != item.name.to_lowercase()
+                && repo_files_selectors::select_used_names(state, repo_id, &parent_path)
+                    .contains(&new_name_lower)
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use similar_asserts::assert_eq;
+
+    use crate::types::DecryptedName;
+
+    use super::{
+        super::state::{RepoFilesRenameCase, RepoFilesRenamePattern, RepoFilesRenameRule},
+        compile_regexes, get_new_name,
+    };
+
+    fn new_name(name: &str, is_dir: bool, index: u64, pattern: RepoFilesRenamePattern) -> String {
+        let regexes = compile_regexes(&pattern).unwrap();
+
+        get_new_name(
+            &DecryptedName(name.into()),
+            is_dir,
+            index,
+            &pattern,
+            &regexes,
+        )
+        .0
+    }
+
+    fn rules(rules: Vec<RepoFilesRenameRule>) -> RepoFilesRenamePattern {
+        RepoFilesRenamePattern {
+            rules,
+            include_extension: false,
+        }
+    }
+
+    #[test]
+    fn test_get_new_name_replace() {
+        let replace = |case_sensitive| {
+            rules(vec![RepoFilesRenameRule::Replace {
+                find: "img".into(),
+                replace: "photo".into(),
+                case_sensitive,
+            }])
+        };
+
+        assert_eq!(
+            new_name("IMG img.img", false, 0, replace(true)),
+            "IMG photo.img"
+        );
+        assert_eq!(
+            new_name("IMG img.img", false, 0, replace(false)),
+            "photo photo.img"
+        );
+        assert_eq!(
+            new_name(
+                "img.img",
+                false,
+                0,
+                RepoFilesRenamePattern {
+                    include_extension: true,
+                    ..replace(true)
+                }
+            ),
+            "photo.photo"
+        );
+    }
+
+    #[test]
+    fn test_get_new_name_regex() {
+        let pattern = rules(vec![RepoFilesRenameRule::Regex {
+            pattern: r"^IMG_(\d{4})(\d{2})(\d{2})$".into(),
+            replace: "$1-$2-$3".into(),
+        }]);
+
+        assert_eq!(
+            new_name("IMG_20240131.jpg", false, 0, pattern.clone()),
+            "2024-01-31.jpg"
+        );
+        assert_eq!(new_name("other.jpg", false, 0, pattern), "other.jpg");
+    }
+
+    #[test]
+    fn test_get_new_name_numbering() {
+        let pattern = rules(vec![RepoFilesRenameRule::Numbering {
+            template: "{name} {n}".into(),
+            start: 1,
+            step: 2,
+            padding: 3,
+        }]);
+
+        assert_eq!(
+            new_name("photo.jpg", false, 0, pattern.clone()),
+            "photo 001.jpg"
+        );
+        assert_eq!(new_name("photo.jpg", false, 5, pattern), "photo 011.jpg");
+    }
+
+    #[test]
+    fn test_get_new_name_case_and_extension() {
+        assert_eq!(
+            new_name(
+                "my HOLIDAY-photo.JPG",
+                false,
+                0,
+                rules(vec![
+                    RepoFilesRenameRule::Case(RepoFilesRenameCase::Title),
+                    RepoFilesRenameRule::Extension(Some(".jpeg".into())),
+                ])
+            ),
+            "My Holiday-Photo.jpeg"
+        );
+        assert_eq!(
+            new_name(
+                "file.txt",
+                false,
+                0,
+                rules(vec![RepoFilesRenameRule::Extension(None)])
+            ),
+            "file"
+        );
+        assert_eq!(
+            new_name(
+                "dir.old",
+                true,
+                0,
+                rules(vec![
+                    RepoFilesRenameRule::Case(RepoFilesRenameCase::Upper),
+                    RepoFilesRenameRule::Extension(Some("new".into())),
+                ])
+            ),
+            "DIR.OLD"
+        );
+    }
+
+    #[test]
+    fn test_compile_regexes_invalid() {
+        assert!(compile_regexes(&rules(vec![RepoFilesRenameRule::Regex {
+            pattern: "(".into(),
+            replace: "".into(),
+        }]))
+        .is_err());
+    }
+}
@@ -0,0 +1,229 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use crate::{
+    cipher::Cipher,
+    repo_files::{
+        errors::{RenameFileError, RepoFilesErrors},
+        RepoFilesService,
+    },
+    repos::ReposService,
+    store,
+    types::{DecryptedName, EncryptedPath, RepoId},
+    utils::repo_encrypted_path_utils,
+};
+
+use super::{
+    errors::RenameFilesError,
+    selectors,
+    state::{
+        RepoFilesRenamePattern, RepoFilesRenamePreviewItem, RepoFilesRenamePreviewStatus,
+        RepoFilesRenameResult,
+    },
+};
+
+pub struct RepoFilesRenameService {
+    repos_service: Arc<ReposService>,
+    repo_files_service: Arc<RepoFilesService>,
+    store: Arc<store::Store>,
+}
+
+impl RepoFilesRenameService {
+    pub fn new(
+        repos_service: Arc<ReposService>,
+        repo_files_service: Arc<RepoFilesService>,
+        store: Arc<store::Store>,
+    ) -> Self {
+        Self {
+            repos_service,
+            repo_files_service,
+            store,
+        }
+    }
+
+    /// Numbering follows the order of `paths`.
+    pub fn preview(
+        &self,
+        repo_id: &RepoId,
+        paths: &[EncryptedPath],
+        pattern: &RepoFilesRenamePattern,
+    ) -> Result<Vec<RepoFilesRenamePreviewItem>, RenameFilesError> {
+        self.repos_service.get_cipher(repo_id)?;
+
+        let regexes = selectors::compile_regexes(pattern)?;
+
+        Ok(self.store.with_state(|state| {
+            selectors::select_preview(state, repo_id, paths, pattern, &regexes)
+        }))
+    }
+
+    /// Renames files one by one. Conflicting files from the preview are not
+    /// renamed and a failed rename does not stop the batch, the outcome is
+    /// reported for every file that would change. Files whose new name is
+    /// still used by another file of the batch (e.g. a swap) are first
+    /// renamed to a temporary name.
+    pub async fn rename_files(
+        &self,
+        repo_id: &RepoId,
+        paths: &[EncryptedPath],
+        pattern: &RepoFilesRenamePattern,
+    ) -> Result<Vec<RepoFilesRenameResult>, RenameFilesError> {
+        let cipher = self.repos_service.get_cipher(repo_id)?;
+
+        let items = self
+            .preview(repo_id, paths, pattern)?
+            .into_iter()
+            .filter(|item| item.status != RepoFilesRenamePreviewStatus::Unchanged)
+            .collect::<Vec<_>>();
+
+        let mut results: Vec<Option<Result<(), RenameFileError>>> = vec![None; items.len()];
+        let mut parent_paths = HashSet::new();
+
+        for (i, item) in items.iter().enumerate() {
+            if let Some(parent_path) = repo_encrypted_path_utils::parent_path(&item.path) {
+                parent_paths.insert(parent_path);
+            }
+
+            results[i] = match &item.status {
+                RepoFilesRenamePreviewStatus::Unchanged | RepoFilesRenamePreviewStatus::Ok => None,
+                RepoFilesRenamePreviewStatus::InvalidName => Some(Err(
+                    RenameFileError::RemoteError(RepoFilesErrors::invalid_path()),
+                )),
+                RepoFilesRenamePreviewStatus::AlreadyExists
+                | RepoFilesRenamePreviewStatus::Duplicate => Some(Err(
+                    RenameFileError::RemoteError(RepoFilesErrors::already_exists()),
+                )),
+                RepoFilesRenamePreviewStatus::DecryptNameError(err) => {
+                    Some(Err(RenameFileError::DecryptFilenameError(err.clone())))
+                }
+            };
+        }
+
+        let temp_indexes = self.store.with_state(|state| {
+            items
+                .iter()
+                .enumerate()
+                .filter(|(i, item)| {
+                    results[*i].is_none()
+                        && selectors::select_is_new_name_used(state, repo_id, item)
+                })
+                .map(|(i, _)| i)
+                .collect::<Vec<_>>()
+        });
+
+        // free the names first
+        let mut temp_paths = HashMap::new();
+
+        for i in &temp_indexes {
+            let item = &items[*i];
+            let temp_name = DecryptedName(format!(
+                "{}.rename-{}",
+                item.name.0,
+                &uuid::Uuid::new_v4().simple().to_string()[..8]
+            ));
+
+            match self
+                .repo_files_service
+                .rename_file_to_name(repo_id, &item.path, &temp_name)
+                .await
+            {
+                Ok(()) => {
+                    temp_paths.insert(*i, self.get_new_path(&cipher, &item.path, &temp_name));
+                }
+                Err(err) => results[*i] = Some(Err(err)),
+            }
+        }
+
+        for i in 0..items.len() {
+            if results[i].is_none() && !temp_paths.contains_key(&i) {
+                let item = &items[i];
+
+                results[i] = Some(
+                    self.repo_files_service
+                        .rename_file_to_name(repo_id, &item.path, &item.new_name)
+                        .await,
+                );
+            }
+        }
+
+        if !temp_paths.is_empty() {
+            // the state is updated by eventstream events, the temporary
+            // files have to be known before they are renamed again
+            self.reload_files(repo_id, &parent_paths).await;
+        }
+
+        for i in temp_indexes {
+            let item = &items[i];
+
+            let temp_path = match temp_paths.get(&i) {
+                Some(temp_path) => temp_path,
+                None => continue,
+            };
+
+            let res = self
+                .repo_files_service
+                .rename_file_to_name(repo_id, temp_path, &item.new_name)
+                .await;
+
+            if res.is_err() {
+                if let Err(err) = self
+                    .repo_files_service
+                    .rename_file_to_name(repo_id, temp_path, &item.name)
+                    .await
+                {
+                    log::warn!(
+                        "RepoFilesRenameService failed to restore the file name: {}",
+                        err
+                    );
+                }
+            }
+
+            results[i] = Some(res);
+        }
+
+        // eventstream events can arrive late, reload so that the state
+        // reflects the new names once the batch is done
+        self.reload_files(repo_id, &parent_paths).await;
+
+        Ok(items
+            .into_iter()
+            .zip(results)
+            .map(|(item, result)| RepoFilesRenameResult {
+                new_path: self.get_new_path(&cipher, &item.path, &item.new_name),
+                file_id: item.file_id,
+                name: item.name,
+                new_name: item.new_name,
+                result: result.unwrap(),
+            })
+            .collect())
+    }
+
+    fn get_new_path(
+        &self,
+        cipher: &Cipher,
+        path: &EncryptedPath,
+        new_name: &DecryptedName,
+    ) -> EncryptedPath {
+        match repo_encrypted_path_utils::parent_path(path) {
+            Some(parent_path) => repo_encrypted_path_utils::join_path_name(
+                &parent_path,
+                &cipher.encrypt_filename(new_name),
+            ),
+            None => path.clone(),
+        }
+    }
+
+    async fn reload_files(&self, repo_id: &RepoId, parent_paths: &HashSet<EncryptedPath>) {
+        for parent_path in parent_paths {
+            if let Err(err) = self
+                .repo_files_service
+                .load_files(repo_id, parent_path)
+                .await
+            {
+                log::warn!("RepoFilesRenameService failed to reload files: {}", err);
+            }
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use crate::{
+    cipher::errors::DecryptFilenameError,
+    repo_files::errors::RenameFileError,
+    types::{DecryptedName, EncryptedPath, RepoFileId},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepoFilesRenameCase {
+    Lower,
+    Upper,
+    /// First letter of every word uppercase, the rest lowercase.
+    Title,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RepoFilesRenameRule {
+    Replace {
+        find: String,
+        replace: String,
+        case_sensitive: bool,
+    },
+    /// `replace` supports `$1` / `${name}` capture group references.
+    Regex {
+        pattern: String,
+        replace: String,
+    },
+    /// `template` placeholders: `{name}` for the current name and `{n}` for
+    /// the sequence number (zero padded to `padding` digits).
+    Numbering {
+        template: String,
+        start: u64,
+        step: u64,
+        padding: usize,
+    },
+    Case(RepoFilesRenameCase),
+    /// Sets the extension or removes it if `None`. Ignored for dirs.
+    Extension(Option<String>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RepoFilesRenamePattern {
+    pub rules: Vec<RepoFilesRenameRule>,
+    /// By default the rules (except `Extension`) are only applied to the
+    /// file name without the extension.
+    pub include_extension: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RepoFilesRenamePreviewStatus {
+    Unchanged,
+    Ok,
+    InvalidName,
+    /// A file with the new name already exists and is not renamed by the
+    /// same batch.
+    AlreadyExists,
+    /// Another renamed file would get the same name.
+    Duplicate,
+    /// The name could not be decrypted, `name` and `new_name` are the
+    /// encrypted name.
+    DecryptNameError(DecryptFilenameError),
+}
+
+impl RepoFilesRenamePreviewStatus {
+    pub fn is_conflict(&self) -> bool {
+        matches!(
+            self,
+            Self::InvalidName | Self::AlreadyExists | Self::Duplicate | Self::DecryptNameError(_)
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepoFilesRenamePreviewItem {
+    pub file_id: RepoFileId,
+    pub path: EncryptedPath,
+    pub name: DecryptedName,
+    pub new_name: DecryptedName,
+    pub status: RepoFilesRenamePreviewStatus,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepoFilesRenameResult {
+    pub file_id: RepoFileId,
+    pub name: DecryptedName,
+    pub new_name: DecryptedName,
+    pub new_path: EncryptedPath,
+    pub result: Result<(), RenameFileError>,
+}
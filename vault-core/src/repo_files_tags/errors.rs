@@ -3,9 +3,10 @@ use thiserror::Error;
 use std::sync::Arc;
 
 use crate::{
+    locale,
     remote::RemoteError,
-    repos::errors::{GetCipherError, RepoLockedError, RepoNotFoundError},
-    user_error::UserError,
+    repos::errors::{GetCipherError, RepoLockedError, RepoNotFoundError, RepoReadOnlyError},
+    user_error::{ErrorCode, UserError},
 };
 
 #[derive(Error, Debug, Clone)]
@@ -27,9 +28,24 @@ impl PartialEq for RepoFileTagsDecodeError {
 impl UserError for RepoFileTagsDecodeError {
     fn user_error(&self) -> String {
         match self {
-            Self::Base64Error(err) => format!("Failed to base64 decode tags: {}", err.to_string()),
-            Self::DecryptError(err) => format!("Failed to decrypt tags: {}", err.to_string()),
-            Self::RMPSerdeError(err) => format!("Failed to deserialize tags: {}", err.to_string()),
+            Self::Base64Error(err) => {
+                locale::message_args("error-tags-base64", &[("error", err.to_string().into())])
+            }
+            Self::DecryptError(err) => {
+                locale::message_args("error-tags-decrypt", &[("error", err.to_string().into())])
+            }
+            Self::RMPSerdeError(err) => locale::message_args(
+                "error-tags-deserialize",
+                &[("error", err.to_string().into())],
+            ),
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::Base64Error(_) => ErrorCode::TagsBase64,
+            Self::DecryptError(_) => ErrorCode::TagsDecrypt,
+            Self::RMPSerdeError(_) => ErrorCode::TagsDeserialize,
         }
     }
 }
@@ -51,8 +67,19 @@ impl PartialEq for RepoFileTagsEncodeError {
 impl UserError for RepoFileTagsEncodeError {
     fn user_error(&self) -> String {
         match self {
-            Self::RMPSerdeError(err) => format!("Failed to serialize tags: {}", err.to_string()),
-            Self::EncryptError(err) => format!("Failed to encrypt tags: {}", err.to_string()),
+            Self::RMPSerdeError(err) => {
+                locale::message_args("error-tags-serialize", &[("error", err.to_string().into())])
+            }
+            Self::EncryptError(err) => {
+                locale::message_args("error-tags-encrypt", &[("error", err.to_string().into())])
+            }
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::RMPSerdeError(_) => ErrorCode::TagsSerialize,
+            Self::EncryptError(_) => ErrorCode::TagsEncrypt,
         }
     }
 }
@@ -73,17 +100,28 @@ pub enum DecryptTagsError {
 impl UserError for DecryptTagsError {
     fn user_error(&self) -> String {
         match self {
-            Self::MultipleValues => format!("Multiple tags values found"),
+            Self::MultipleValues => locale::message("error-tags-multiple-values"),
             Self::EncryptedHashMismatch {
                 expected_encrypted_hash,
                 encrypted_hash,
-            } => format!(
-                "Encrypted file hash does not match the encrypted hash in tags: {:?} != {:?}",
-                expected_encrypted_hash, encrypted_hash
+            } => locale::message_args(
+                "error-tags-hash-mismatch",
+                &[
+                    ("expected", format!("{:?}", expected_encrypted_hash).into()),
+                    ("actual", format!("{:?}", encrypted_hash).into()),
+                ],
             ),
             Self::DecodeError(err) => err.user_error(),
         }
     }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::MultipleValues => ErrorCode::TagsMultipleValues,
+            Self::EncryptedHashMismatch { .. } => ErrorCode::TagsHashMismatch,
+            Self::DecodeError(err) => err.error_code(),
+        }
+    }
 }
 
 #[derive(Error, Debug, Clone, PartialEq)]
@@ -92,6 +130,8 @@ pub enum SetTagsError {
     RepoNotFound(#[from] RepoNotFoundError),
     #[error("{0}")]
     RepoLocked(#[from] RepoLockedError),
+    #[error("{0}")]
+    RepoReadOnly(#[from] RepoReadOnlyError),
     #[error("file not found")]
     FileNotFound,
     #[error("missing encrypted hash")]
@@ -70,6 +70,7 @@ pub fn select_set_tags_info(
             RepoFileTags {
                 encrypted_hash,
                 hash: None,
+                version: None,
                 unknown: HashMap::new(),
             }
         }
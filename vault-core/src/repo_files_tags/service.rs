@@ -5,7 +5,7 @@ use crate::{
     remote_files::RemoteFilesService,
     repo_files::{self, state::RepoFile},
     repo_files_tags::selectors,
-    repos::ReposService,
+    repos::{selectors as repos_selectors, ReposService},
     store,
     types::{EncryptedPath, RepoId},
 };
@@ -39,6 +39,9 @@ impl RepoFilesTagsService {
     ) -> Result<(), SetTagsError> {
         let cipher = self.repos_service.get_cipher(&repo_id)?;
 
+        self.store
+            .with_state(|state| repos_selectors::select_check_writable(state, repo_id))?;
+
         let file_id = repo_files::selectors::get_file_id(&repo_id, &path);
 
         let max_retries = self
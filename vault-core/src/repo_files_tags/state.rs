@@ -23,10 +23,25 @@ pub struct RepoFileTags {
         skip_serializing_if = "Option::is_none"
     )]
     pub hash: Option<Vec<u8>>,
+    #[serde(default, rename = "v", skip_serializing_if = "Option::is_none")]
+    pub version: Option<RepoFileTagsVersion>,
     #[serde(flatten)]
     pub unknown: HashMap<String, rmpv::Value>,
 }
 
+/// Set on files in the versions dir.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RepoFileTagsVersion {
+    /// decrypted path of the original file
+    #[serde(rename = "p")]
+    pub path: String,
+    #[serde(rename = "c")]
+    pub created: i64,
+    /// modified time of the original file
+    #[serde(default, rename = "m", skip_serializing_if = "Option::is_none")]
+    pub modified: Option<i64>,
+}
+
 impl RepoFileTags {
     pub fn encrypted_hash_hex(&self) -> Option<String> {
         self.encrypted_hash.as_deref().map(|hash| hex::encode(hash))
@@ -73,7 +88,7 @@ mod tests {
         user_error::UserError,
     };
 
-    use super::RepoFileTags;
+    use super::{RepoFileTags, RepoFileTagsVersion};
 
     #[test]
     fn from_string() {
@@ -110,6 +125,7 @@ mod tests {
             hash: Some(vec![
                 150, 183, 185, 103, 121, 185, 70, 194, 171, 206, 238, 163, 192, 250, 45, 88,
             ]),
+            version: None,
             unknown: HashMap::from([("extra".into(), "value".into())]),
         };
 
@@ -171,6 +187,29 @@ mod tests {
             hash: Some(vec![
                 150, 183, 185, 103, 121, 185, 70, 194, 171, 206, 238, 163, 192, 250, 45, 88,
             ]),
+            version: None,
+            unknown: HashMap::from([("extra".into(), "value".into())]),
+        };
+
+        let encrypted = tags.to_string(&cipher).unwrap();
+
+        let tags1 = RepoFileTags::from_string(&encrypted, &cipher).unwrap();
+
+        assert_eq!(tags, tags1);
+    }
+
+    #[test]
+    fn to_string_version() {
+        let cipher = create_cipher();
+
+        let tags = RepoFileTags {
+            encrypted_hash: None,
+            hash: None,
+            version: Some(RepoFileTagsVersion {
+                path: "/file.txt".into(),
+                created: 1700000000000,
+                modified: Some(1600000000000),
+            }),
             unknown: HashMap::from([("extra".into(), "value".into())]),
         };
 
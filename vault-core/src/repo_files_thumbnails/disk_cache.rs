@@ -0,0 +1,9 @@
+/// ThumbnailsDiskCache stores encrypted thumbnails between sessions. Values are
+/// already encrypted with the repo cipher so implementations can write them to
+/// disk as they are.
+pub trait ThumbnailsDiskCache {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, String>;
+    fn set(&self, key: &str, value: &[u8]) -> Result<(), String>;
+    fn remove(&self, key: &str) -> Result<(), String>;
+    fn clear(&self) -> Result<(), String>;
+}
@@ -0,0 +1,89 @@
+use thiserror::Error;
+
+use crate::{
+    locale,
+    repo_files_read::errors::GetFilesReaderError,
+    repos::errors::{GetCipherError, RepoLockedError, RepoNotFoundError},
+    user_error::{ErrorCode, UserError},
+};
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum GenerateThumbnailError {
+    #[error("failed to decode image: {0}")]
+    DecodeError(String),
+    #[error("failed to encode thumbnail: {0}")]
+    EncodeError(String),
+}
+
+impl UserError for GenerateThumbnailError {
+    fn user_error(&self) -> String {
+        match self {
+            Self::DecodeError(_) => locale::message("error-thumbnail-decode"),
+            Self::EncodeError(_) => locale::message("error-thumbnail-encode"),
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::DecodeError(_) => ErrorCode::ThumbnailDecode,
+            Self::EncodeError(_) => ErrorCode::ThumbnailEncode,
+        }
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum GetThumbnailError {
+    #[error("{0}")]
+    RepoNotFound(#[from] RepoNotFoundError),
+    #[error("{0}")]
+    RepoLocked(#[from] RepoLockedError),
+    #[error("file not found")]
+    FileNotFound,
+    #[error("thumbnail not supported")]
+    NotSupported,
+    #[error("{0}")]
+    GetFilesReaderError(#[from] GetFilesReaderError),
+    #[error("{0}")]
+    GenerateThumbnailError(#[from] GenerateThumbnailError),
+    #[error("failed to encrypt thumbnail: {0}")]
+    EncryptError(String),
+    #[error("failed to decrypt thumbnail: {0}")]
+    DecryptError(String),
+}
+
+impl UserError for GetThumbnailError {
+    fn user_error(&self) -> String {
+        match self {
+            Self::RepoNotFound(err) => err.user_error(),
+            Self::RepoLocked(err) => err.user_error(),
+            Self::FileNotFound => locale::message("error-file-not-found"),
+            Self::NotSupported => locale::message("error-thumbnail-not-supported"),
+            Self::GetFilesReaderError(err) => err.user_error(),
+            Self::GenerateThumbnailError(err) => err.user_error(),
+            Self::EncryptError(_) => self.to_string(),
+            Self::DecryptError(_) => self.to_string(),
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::RepoNotFound(err) => err.error_code(),
+            Self::RepoLocked(err) => err.error_code(),
+            Self::FileNotFound => ErrorCode::FileNotFound,
+            Self::NotSupported => ErrorCode::ThumbnailNotSupported,
+            Self::GetFilesReaderError(err) => err.error_code(),
+            Self::GenerateThumbnailError(err) => err.error_code(),
+            Self::EncryptError(_) => ErrorCode::ThumbnailEncrypt,
+            Self::DecryptError(_) => ErrorCode::ThumbnailDecrypt,
+        }
+    }
+}
+
+impl From<GetCipherError> for GetThumbnailError {
+    fn from(err: GetCipherError) -> Self {
+        match err {
+            GetCipherError::RepoNotFound(err) => Self::RepoNotFound(err),
+            GetCipherError::RepoLocked(err) => Self::RepoLocked(err),
+        }
+    }
+}
@@ -0,0 +1,9 @@
+pub mod disk_cache;
+pub mod errors;
+pub mod mutations;
+pub mod selectors;
+pub mod service;
+pub mod state;
+pub mod thumbnail;
+
+pub use self::{disk_cache::ThumbnailsDiskCache, service::RepoFilesThumbnailsService};
@@ -0,0 +1,151 @@
+use crate::{
+    common::state::Status,
+    repo_files::{selectors as repo_files_selectors, state::RepoFile},
+    store,
+    types::{RepoFileId, RepoId, TimeMillis},
+};
+
+use super::{errors::GetThumbnailError, selectors, state::RepoFileThumbnail};
+
+/// Marks the thumbnail as loading and returns the file if the thumbnail needs
+/// to be generated.
+pub fn generating(
+    state: &mut store::State,
+    notify: &store::Notify,
+    file_id: &RepoFileId,
+    now: TimeMillis,
+) -> Option<RepoFile> {
+    if !selectors::select_should_generate(state, file_id, now) {
+        return None;
+    }
+
+    let file = repo_files_selectors::select_file(state, file_id)?.clone();
+
+    notify(store::Event::RepoFilesThumbnails);
+
+    state.repo_files_thumbnails.thumbnails.insert(
+        file_id.to_owned(),
+        RepoFileThumbnail {
+            file_id: file_id.to_owned(),
+            repo_id: file.repo_id.clone(),
+            hash: file.hash(),
+            remote_hash: file.remote_hash.clone(),
+            status: Status::Loading { loaded: false },
+            encrypted_bytes: None,
+            retry_after: None,
+        },
+    );
+
+    Some(file)
+}
+
+pub fn generated(
+    state: &mut store::State,
+    notify: &store::Notify,
+    file_id: &RepoFileId,
+    res: Result<Vec<u8>, GetThumbnailError>,
+    now: TimeMillis,
+) {
+    // the hash tag could have been set while the file was read
+    let file_hash = repo_files_selectors::select_file(state, file_id)
+        .map(|file| (file.hash(), file.remote_hash.clone()));
+
+    let thumbnail = match state.repo_files_thumbnails.thumbnails.get_mut(file_id) {
+        Some(thumbnail) => thumbnail,
+        None => return,
+    };
+
+    notify(store::Event::RepoFilesThumbnails);
+
+    if let Some((Some(hash), remote_hash)) = file_hash {
+        if thumbnail.remote_hash == remote_hash {
+            thumbnail.hash = Some(hash);
+        }
+    }
+
+    match res {
+        Ok(encrypted_bytes) => {
+            thumbnail.status = Status::Loaded;
+            thumbnail.encrypted_bytes = Some(encrypted_bytes);
+        }
+        Err(err) => {
+            thumbnail.status = Status::Error {
+                error: err,
+                loaded: false,
+            };
+            thumbnail.encrypted_bytes = None;
+            thumbnail.retry_after = Some(TimeMillis(
+                now.0 + selectors::THUMBNAIL_RETRY_INTERVAL.as_millis() as i64,
+            ));
+        }
+    }
+}
+
+fn remove_repo_thumbnails(state: &mut store::State, notify: &store::Notify, repo_id: &RepoId) {
+    let len = state.repo_files_thumbnails.thumbnails.len();
+
+    state
+        .repo_files_thumbnails
+        .thumbnails
+        .retain(|_, thumbnail| &thumbnail.repo_id != repo_id);
+
+    if state.repo_files_thumbnails.thumbnails.len() != len {
+        notify(store::Event::RepoFilesThumbnails);
+    }
+}
+
+fn remove_path_thumbnails(state: &mut store::State, notify: &store::Notify, file_id: &RepoFileId) {
+    let len = state.repo_files_thumbnails.thumbnails.len();
+    let children_prefix = format!("{}/", file_id.0);
+
+    state
+        .repo_files_thumbnails
+        .thumbnails
+        .retain(|key, _| key != file_id && !key.0.starts_with(&children_prefix));
+
+    if state.repo_files_thumbnails.thumbnails.len() != len {
+        notify(store::Event::RepoFilesThumbnails);
+    }
+}
+
+pub fn handle_repos_mutation(
+    state: &mut store::State,
+    notify: &store::Notify,
+    mutation_state: &store::MutationState,
+) {
+    for repo_id in mutation_state
+        .repos
+        .locked_repos
+        .iter()
+        .map(|(repo_id, _)| repo_id)
+        .chain(mutation_state.repos.removed_repos.iter())
+    {
+        remove_repo_thumbnails(state, notify, repo_id);
+    }
+}
+
+pub fn handle_repo_files_mutation(
+    state: &mut store::State,
+    notify: &store::Notify,
+    mutation_state: &store::MutationState,
+) {
+    let removed_file_ids = mutation_state
+        .repo_files
+        .removed_files
+        .iter()
+        .map(|(repo_id, path)| repo_files_selectors::get_file_id(repo_id, path))
+        .chain(
+            mutation_state
+                .repo_files
+                .moved_files
+                .iter()
+                .map(|(repo_id, from_path, _)| {
+                    repo_files_selectors::get_file_id(repo_id, from_path)
+                }),
+        )
+        .collect::<Vec<_>>();
+
+    for file_id in removed_file_ids {
+        remove_path_thumbnails(state, notify, &file_id);
+    }
+}
@@ -0,0 +1,109 @@
+use std::time::Duration;
+
+use crate::{
+    repo_files::{selectors as repo_files_selectors, state::RepoFile},
+    store,
+    types::{RepoFileId, TimeMillis},
+};
+
+use super::{
+    errors::GetThumbnailError, state::RepoFileThumbnail, thumbnail::is_content_type_supported,
+};
+
+pub const THUMBNAIL_MAX_SIZE: u32 = 256;
+
+/// larger images are not downloaded to generate a thumbnail
+pub const THUMBNAIL_MAX_FILE_SIZE: i64 = 50 * 1024 * 1024;
+
+/// failed thumbnails are generated again after this interval
+pub const THUMBNAIL_RETRY_INTERVAL: Duration = Duration::from_secs(60);
+
+pub fn is_file_supported(file: &RepoFile) -> bool {
+    file.typ.is_file()
+        && file
+            .content_type
+            .as_deref()
+            .map(is_content_type_supported)
+            .unwrap_or(false)
+        && matches!(
+            file.decrypted_size(),
+            Ok(Some(size)) if size <= THUMBNAIL_MAX_FILE_SIZE
+        )
+}
+
+/// A thumbnail is valid until the file hash tag changes. Files without the hash
+/// tag fall back to the remote hash.
+pub fn is_thumbnail_valid(thumbnail: &RepoFileThumbnail, file: &RepoFile) -> bool {
+    match (&thumbnail.hash, file.hash()) {
+        (Some(thumbnail_hash), Some(file_hash)) => thumbnail_hash == &file_hash,
+        _ => thumbnail.remote_hash == file.remote_hash,
+    }
+}
+
+pub fn get_disk_cache_key(file_id: &RepoFileId, remote_hash: &str) -> String {
+    hex::encode(md5::compute(format!("{}:{}", file_id.0, remote_hash).as_bytes()).0)
+}
+
+pub fn get_file_disk_cache_key(file: &RepoFile) -> Option<String> {
+    file.remote_hash
+        .as_ref()
+        .map(|remote_hash| get_disk_cache_key(&file.id, remote_hash))
+}
+
+pub fn select_thumbnail<'a>(
+    state: &'a store::State,
+    file_id: &RepoFileId,
+) -> Option<&'a RepoFileThumbnail> {
+    state.repo_files_thumbnails.thumbnails.get(file_id)
+}
+
+/// Returns the thumbnail only if it is still valid for the current file.
+pub fn select_file_thumbnail<'a>(
+    state: &'a store::State,
+    file: &RepoFile,
+) -> Option<&'a RepoFileThumbnail> {
+    select_thumbnail(state, &file.id).filter(|thumbnail| is_thumbnail_valid(thumbnail, file))
+}
+
+pub fn is_retry_due(thumbnail: &RepoFileThumbnail, now: TimeMillis) -> bool {
+    matches!(thumbnail.retry_after, Some(retry_after) if now >= retry_after)
+}
+
+/// Failed thumbnails are generated again once their retry is due.
+pub fn select_should_generate(state: &store::State, file_id: &RepoFileId, now: TimeMillis) -> bool {
+    match repo_files_selectors::select_file(state, file_id) {
+        Some(file) if is_file_supported(file) => match select_file_thumbnail(state, file) {
+            Some(thumbnail) => is_retry_due(thumbnail, now),
+            None => true,
+        },
+        _ => false,
+    }
+}
+
+/// Returns `None` while the thumbnail is being generated or its retry is due.
+pub fn select_thumbnail_result(
+    state: &store::State,
+    file_id: &RepoFileId,
+    now: TimeMillis,
+) -> Option<Result<Vec<u8>, GetThumbnailError>> {
+    let file = match repo_files_selectors::select_file(state, file_id) {
+        Some(file) => file,
+        None => return Some(Err(GetThumbnailError::FileNotFound)),
+    };
+
+    if !is_file_supported(file) {
+        return Some(Err(GetThumbnailError::NotSupported));
+    }
+
+    let thumbnail = select_file_thumbnail(state, file)?;
+
+    if is_retry_due(thumbnail, now) {
+        return None;
+    }
+
+    if let Some(err) = thumbnail.status.error() {
+        return Some(Err(err.clone()));
+    }
+
+    thumbnail.encrypted_bytes.clone().map(Ok)
+}
@@ -0,0 +1,300 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex, RwLock},
+};
+
+use futures::{AsyncReadExt, FutureExt};
+
+use crate::{
+    repo_files::state::RepoFile,
+    repo_files_read::{errors::GetFilesReaderError, RepoFilesReadService},
+    repos::ReposService,
+    runtime, store,
+    types::RepoFileId,
+};
+
+use super::{
+    disk_cache::ThumbnailsDiskCache,
+    errors::GetThumbnailError,
+    mutations, selectors,
+    state::RepoFileThumbnailBytes,
+    thumbnail::{generate_thumbnail, thumbnail_content_type},
+};
+
+/// number of thumbnails generated at the same time
+pub const THUMBNAILS_CONCURRENCY: usize = 3;
+
+#[derive(Default)]
+struct ThumbnailsQueue {
+    pending: VecDeque<RepoFileId>,
+    running: usize,
+}
+
+pub struct RepoFilesThumbnailsService {
+    repos_service: Arc<ReposService>,
+    repo_files_read_service: Arc<RepoFilesReadService>,
+    store: Arc<store::Store>,
+    runtime: Arc<runtime::BoxRuntime>,
+
+    disk_cache: RwLock<Option<Box<dyn ThumbnailsDiskCache + Send + Sync>>>,
+    queue: Mutex<ThumbnailsQueue>,
+    mutation_subscription_id: u32,
+}
+
+impl RepoFilesThumbnailsService {
+    pub fn new(
+        repos_service: Arc<ReposService>,
+        repo_files_read_service: Arc<RepoFilesReadService>,
+        store: Arc<store::Store>,
+        runtime: Arc<runtime::BoxRuntime>,
+    ) -> Self {
+        let mutation_subscription_id = store.get_next_id();
+
+        store.mutation_on(
+            mutation_subscription_id,
+            &[store::MutationEvent::Repos, store::MutationEvent::RepoFiles],
+            Box::new(move |state, notify, mutation_state, _| {
+                mutations::handle_repos_mutation(state, notify, mutation_state);
+                mutations::handle_repo_files_mutation(state, notify, mutation_state);
+            }),
+        );
+
+        Self {
+            repos_service,
+            repo_files_read_service,
+            store,
+            runtime,
+
+            disk_cache: RwLock::new(None),
+            queue: Mutex::new(Default::default()),
+            mutation_subscription_id,
+        }
+    }
+
+    pub fn set_disk_cache(&self, disk_cache: Option<Box<dyn ThumbnailsDiskCache + Send + Sync>>) {
+        *self.disk_cache.write().unwrap() = disk_cache;
+    }
+
+    /// Queues thumbnail generation for files that do not have a valid
+    /// thumbnail yet.
+    pub fn ensure_thumbnails(self: Arc<Self>, file_ids: Vec<RepoFileId>) {
+        let file_ids = self.store.with_state(|state| {
+            file_ids
+                .into_iter()
+                .filter(|file_id| {
+                    selectors::select_should_generate(state, file_id, self.runtime.now())
+                })
+                .collect::<Vec<_>>()
+        });
+
+        if file_ids.is_empty() {
+            return;
+        }
+
+        {
+            let mut queue = self.queue.lock().unwrap();
+
+            for file_id in file_ids {
+                if !queue.pending.contains(&file_id) {
+                    queue.pending.push_back(file_id);
+                }
+            }
+        }
+
+        self.process_queue();
+    }
+
+    fn process_queue(self: Arc<Self>) {
+        let mut queue = self.queue.lock().unwrap();
+
+        while queue.running < THUMBNAILS_CONCURRENCY {
+            let file_id = match queue.pending.pop_front() {
+                Some(file_id) => file_id,
+                None => break,
+            };
+
+            queue.running += 1;
+
+            let this = self.clone();
+
+            self.runtime.spawn(
+                async move {
+                    this.generate(file_id).await;
+
+                    this.queue.lock().unwrap().running -= 1;
+
+                    this.process_queue();
+                }
+                .boxed(),
+            );
+        }
+    }
+
+    async fn generate(&self, file_id: RepoFileId) {
+        // the thumbnail of the previous file content is not needed anymore
+        let stale_disk_cache_key = self.store.with_state(|state| {
+            selectors::select_thumbnail(state, &file_id).and_then(|thumbnail| {
+                thumbnail
+                    .remote_hash
+                    .as_deref()
+                    .map(|remote_hash| selectors::get_disk_cache_key(&file_id, remote_hash))
+            })
+        });
+
+        let file = match self.store.mutate(|state, notify, _, _| {
+            mutations::generating(state, notify, &file_id, self.runtime.now())
+        }) {
+            Some(file) => file,
+            None => return,
+        };
+
+        if let Some(key) = stale_disk_cache_key {
+            if selectors::get_file_disk_cache_key(&file).as_ref() != Some(&key) {
+                self.disk_cache_remove(&key);
+            }
+        }
+
+        let res = self.generate_encrypted(&file).await;
+
+        self.store.mutate(|state, notify, _, _| {
+            mutations::generated(state, notify, &file_id, res, self.runtime.now())
+        });
+    }
+
+    async fn generate_encrypted(&self, file: &RepoFile) -> Result<Vec<u8>, GetThumbnailError> {
+        let disk_cache_key = selectors::get_file_disk_cache_key(file);
+
+        if let Some(key) = &disk_cache_key {
+            if let Some(encrypted_bytes) = self.disk_cache_get(key) {
+                return Ok(encrypted_bytes);
+            }
+        }
+
+        let cipher = self.repos_service.get_cipher(&file.repo_id)?;
+
+        let mut reader = self
+            .repo_files_read_service
+            .clone()
+            .get_files_reader(vec![file.clone()])?
+            .reader()
+            .await?;
+
+        let mut buf = Vec::new();
+
+        reader
+            .reader
+            .read_to_end(&mut buf)
+            .await
+            .map_err(|err| GetFilesReaderError::from(&err))?;
+
+        let thumbnail = generate_thumbnail(&buf, selectors::THUMBNAIL_MAX_SIZE)?;
+
+        let encrypted_bytes = cipher
+            .encrypt_vec(&thumbnail.bytes)
+            .map_err(|err| GetThumbnailError::EncryptError(err.to_string()))?;
+
+        if let Some(key) = &disk_cache_key {
+            self.disk_cache_set(key, &encrypted_bytes);
+        }
+
+        Ok(encrypted_bytes)
+    }
+
+    /// Cached thumbnails are encrypted with the repo ciphers but they must not
+    /// be left behind for the next account.
+    pub fn logout(&self) {
+        self.queue.lock().unwrap().pending.clear();
+
+        if let Some(disk_cache) = self.disk_cache.read().unwrap().as_ref() {
+            if let Err(err) = disk_cache.clear() {
+                log::warn!("Thumbnails disk cache clear failed: {}", err);
+            }
+        }
+    }
+
+    fn disk_cache_get(&self, key: &str) -> Option<Vec<u8>> {
+        match self.disk_cache.read().unwrap().as_ref()?.get(key) {
+            Ok(value) => value,
+            Err(err) => {
+                log::warn!("Thumbnails disk cache get failed: {}", err);
+
+                None
+            }
+        }
+    }
+
+    fn disk_cache_set(&self, key: &str, value: &[u8]) {
+        if let Some(disk_cache) = self.disk_cache.read().unwrap().as_ref() {
+            if let Err(err) = disk_cache.set(key, value) {
+                log::warn!("Thumbnails disk cache set failed: {}", err);
+            }
+        }
+    }
+
+    fn disk_cache_remove(&self, key: &str) {
+        if let Some(disk_cache) = self.disk_cache.read().unwrap().as_ref() {
+            if let Err(err) = disk_cache.remove(key) {
+                log::warn!("Thumbnails disk cache remove failed: {}", err);
+            }
+        }
+    }
+
+    pub async fn get_thumbnail(
+        self: Arc<Self>,
+        file_id: RepoFileId,
+    ) -> Result<RepoFileThumbnailBytes, GetThumbnailError> {
+        let wait_self = self.clone();
+        let wait_file_id = file_id.clone();
+
+        let encrypted_bytes = store::wait_for(
+            self.store.clone(),
+            &[store::Event::RepoFilesThumbnails, store::Event::RepoFiles],
+            move |_| {
+                let now = wait_self.runtime.now();
+
+                let (res, should_generate) = wait_self.store.with_state(|state| {
+                    (
+                        selectors::select_thumbnail_result(state, &wait_file_id, now),
+                        selectors::select_should_generate(state, &wait_file_id, now),
+                    )
+                });
+
+                // the file could have changed while it was generated or a
+                // failed generation is due for a retry
+                if res.is_none() && should_generate {
+                    wait_self
+                        .clone()
+                        .ensure_thumbnails(vec![wait_file_id.clone()]);
+                }
+
+                res
+            },
+        )
+        .await?;
+
+        let repo_id = self
+            .store
+            .with_state(|state| {
+                selectors::select_thumbnail(state, &file_id).map(|x| x.repo_id.clone())
+            })
+            .ok_or(GetThumbnailError::FileNotFound)?;
+
+        let cipher = self.repos_service.get_cipher(&repo_id)?;
+
+        let bytes = cipher
+            .decrypt_vec(&encrypted_bytes)
+            .map_err(|err| GetThumbnailError::DecryptError(err.to_string()))?;
+
+        Ok(RepoFileThumbnailBytes {
+            content_type: thumbnail_content_type(&bytes).to_owned(),
+            bytes,
+        })
+    }
+}
+
+impl Drop for RepoFilesThumbnailsService {
+    fn drop(&mut self) {
+        self.store
+            .mutation_remove_listener(self.mutation_subscription_id)
+    }
+}
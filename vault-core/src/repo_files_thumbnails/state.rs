@@ -0,0 +1,41 @@
+use std::collections::HashMap;
+
+use crate::{
+    common::state::Status,
+    types::{RepoFileId, RepoId, TimeMillis},
+};
+
+use super::errors::GetThumbnailError;
+
+/// Thumbnails are kept encrypted with the repo cipher, both in memory and in
+/// the disk cache. They are only decrypted when requested.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepoFileThumbnail {
+    pub file_id: RepoFileId,
+    pub repo_id: RepoId,
+    /// plaintext hash from the file tags at the time of generation
+    pub hash: Option<String>,
+    /// remote (encrypted content) hash, used if the file has no hash tag
+    pub remote_hash: Option<String>,
+    pub status: Status<GetThumbnailError>,
+    pub encrypted_bytes: Option<Vec<u8>>,
+    /// set if the generation failed
+    pub retry_after: Option<TimeMillis>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepoFileThumbnailBytes {
+    pub bytes: Vec<u8>,
+    pub content_type: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RepoFilesThumbnailsState {
+    pub thumbnails: HashMap<RepoFileId, RepoFileThumbnail>,
+}
+
+impl RepoFilesThumbnailsState {
+    pub fn reset(&mut self) {
+        *self = Default::default();
+    }
+}
@@ -0,0 +1,141 @@
+use std::io::Cursor;
+
+use image::{DynamicImage, ImageFormat, ImageOutputFormat};
+
+use super::errors::GenerateThumbnailError;
+
+pub const THUMBNAIL_JPEG_QUALITY: u8 = 80;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Thumbnail {
+    pub bytes: Vec<u8>,
+    pub content_type: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+pub fn is_content_type_supported(content_type: &str) -> bool {
+    matches!(
+        content_type,
+        "image/jpeg" | "image/png" | "image/gif" | "image/webp"
+    )
+}
+
+pub fn thumbnail_content_type(thumbnail_bytes: &[u8]) -> &'static str {
+    match image::guess_format(thumbnail_bytes) {
+        Ok(ImageFormat::Png) => "image/png",
+        _ => "image/jpeg",
+    }
+}
+
+/// Decodes an image and scales it down to fit into `max_size`x`max_size`,
+/// keeping the aspect ratio. Images with an alpha channel are encoded as PNG,
+/// everything else as JPEG.
+pub fn generate_thumbnail(
+    image_bytes: &[u8],
+    max_size: u32,
+) -> Result<Thumbnail, GenerateThumbnailError> {
+    let image = image::io::Reader::new(Cursor::new(image_bytes))
+        .with_guessed_format()
+        .map_err(|err| GenerateThumbnailError::DecodeError(err.to_string()))?
+        .decode()
+        .map_err(|err| GenerateThumbnailError::DecodeError(err.to_string()))?;
+
+    let thumbnail = if image.width() > max_size || image.height() > max_size {
+        image.thumbnail(max_size, max_size)
+    } else {
+        image
+    };
+
+    let (thumbnail, format, content_type) = if thumbnail.color().has_alpha() {
+        (
+            DynamicImage::ImageRgba8(thumbnail.to_rgba8()),
+            ImageOutputFormat::Png,
+            "image/png",
+        )
+    } else {
+        (
+            DynamicImage::ImageRgb8(thumbnail.to_rgb8()),
+            ImageOutputFormat::Jpeg(THUMBNAIL_JPEG_QUALITY),
+            "image/jpeg",
+        )
+    };
+
+    let mut bytes = Vec::new();
+
+    thumbnail
+        .write_to(&mut Cursor::new(&mut bytes), format)
+        .map_err(|err| GenerateThumbnailError::EncodeError(err.to_string()))?;
+
+    Ok(Thumbnail {
+        bytes,
+        content_type: content_type.into(),
+        width: thumbnail.width(),
+        height: thumbnail.height(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use image::{DynamicImage, ImageOutputFormat, RgbImage, RgbaImage};
+
+    use super::{generate_thumbnail, thumbnail_content_type, GenerateThumbnailError};
+
+    fn encode(image: DynamicImage, format: ImageOutputFormat) -> Vec<u8> {
+        let mut bytes = Vec::new();
+
+        image
+            .write_to(&mut Cursor::new(&mut bytes), format)
+            .unwrap();
+
+        bytes
+    }
+
+    #[test]
+    fn test_generate_thumbnail_jpeg() {
+        let image = DynamicImage::ImageRgb8(RgbImage::new(1000, 500));
+        let bytes = encode(image, ImageOutputFormat::Jpeg(90));
+
+        let thumbnail = generate_thumbnail(&bytes, 256).unwrap();
+
+        assert_eq!(thumbnail.content_type, "image/jpeg");
+        assert_eq!((thumbnail.width, thumbnail.height), (256, 128));
+        assert_eq!(thumbnail_content_type(&thumbnail.bytes), "image/jpeg");
+
+        let decoded = image::load_from_memory(&thumbnail.bytes).unwrap();
+
+        assert_eq!((decoded.width(), decoded.height()), (256, 128));
+    }
+
+    #[test]
+    fn test_generate_thumbnail_png_alpha() {
+        let image = DynamicImage::ImageRgba8(RgbaImage::new(300, 600));
+        let bytes = encode(image, ImageOutputFormat::Png);
+
+        let thumbnail = generate_thumbnail(&bytes, 256).unwrap();
+
+        assert_eq!(thumbnail.content_type, "image/png");
+        assert_eq!((thumbnail.width, thumbnail.height), (128, 256));
+        assert_eq!(thumbnail_content_type(&thumbnail.bytes), "image/png");
+    }
+
+    #[test]
+    fn test_generate_thumbnail_small_image() {
+        let image = DynamicImage::ImageRgb8(RgbImage::new(100, 50));
+        let bytes = encode(image, ImageOutputFormat::Png);
+
+        let thumbnail = generate_thumbnail(&bytes, 256).unwrap();
+
+        assert_eq!((thumbnail.width, thumbnail.height), (100, 50));
+    }
+
+    #[test]
+    fn test_generate_thumbnail_invalid() {
+        assert!(matches!(
+            generate_thumbnail(b"not an image", 256),
+            Err(GenerateThumbnailError::DecodeError(_))
+        ));
+    }
+}
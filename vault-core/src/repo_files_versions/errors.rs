@@ -0,0 +1,245 @@
+use thiserror::Error;
+
+use crate::{
+    cipher::errors::DecryptFilenameError,
+    locale,
+    remote::RemoteError,
+    repo_files::errors::{EnsureDirError, LoadFileError, LoadFilesError},
+    repo_files_tags::errors::SetTagsError,
+    repos::errors::{GetCipherError, RepoLockedError, RepoNotFoundError, RepoReadOnlyError},
+    secure_storage::errors::SecureStorageError,
+    user_error::{ErrorCode, UserError},
+};
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum CreateVersionError {
+    #[error("{0}")]
+    RepoNotFound(#[from] RepoNotFoundError),
+    #[error("{0}")]
+    RepoLocked(#[from] RepoLockedError),
+    #[error("{0}")]
+    RepoReadOnly(#[from] RepoReadOnlyError),
+    #[error("file not found")]
+    FileNotFound,
+    #[error("not a file")]
+    NotAFile,
+    #[error("{0}")]
+    DecryptFilenameError(#[from] DecryptFilenameError),
+    #[error("storage error: {0}")]
+    StorageError(#[from] SecureStorageError),
+    #[error("failed to set version tags: {0}")]
+    SetTagsError(#[from] SetTagsError),
+    #[error("canceled")]
+    Canceled,
+    #[error("{0}")]
+    RemoteError(#[from] RemoteError),
+}
+
+impl UserError for CreateVersionError {
+    fn user_error(&self) -> String {
+        match self {
+            Self::RepoNotFound(err) => err.user_error(),
+            Self::RepoLocked(err) => err.user_error(),
+            Self::RepoReadOnly(err) => err.user_error(),
+            Self::FileNotFound => locale::message("error-file-not-found"),
+            Self::NotAFile => locale::message("error-versions-not-a-file"),
+            Self::DecryptFilenameError(err) => err.user_error(),
+            Self::StorageError(err) => {
+                locale::message_args("error-storage", &[("error", err.to_string().into())])
+            }
+            Self::SetTagsError(_) => self.to_string(),
+            Self::Canceled => self.to_string(),
+            Self::RemoteError(err) => err.user_error(),
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::RepoNotFound(err) => err.error_code(),
+            Self::RepoLocked(err) => err.error_code(),
+            Self::RepoReadOnly(err) => err.error_code(),
+            Self::FileNotFound => ErrorCode::FileNotFound,
+            Self::NotAFile => ErrorCode::VersionsNotAFile,
+            Self::DecryptFilenameError(err) => err.error_code(),
+            Self::StorageError(_) => ErrorCode::Storage,
+            Self::SetTagsError(_) => ErrorCode::TagsSet,
+            Self::Canceled => ErrorCode::Canceled,
+            Self::RemoteError(err) => err.error_code(),
+        }
+    }
+}
+
+impl From<GetCipherError> for CreateVersionError {
+    fn from(err: GetCipherError) -> Self {
+        match err {
+            GetCipherError::RepoNotFound(err) => Self::RepoNotFound(err),
+            GetCipherError::RepoLocked(err) => Self::RepoLocked(err),
+        }
+    }
+}
+
+impl From<EnsureDirError> for CreateVersionError {
+    fn from(err: EnsureDirError) -> Self {
+        match err {
+            EnsureDirError::RepoNotFound(err) => Self::RepoNotFound(err),
+            EnsureDirError::RepoLocked(err) => Self::RepoLocked(err),
+            EnsureDirError::RepoReadOnly(err) => Self::RepoReadOnly(err),
+            EnsureDirError::DecryptFilenameError(err) => Self::DecryptFilenameError(err),
+            EnsureDirError::Canceled => Self::Canceled,
+            EnsureDirError::RemoteError(err) => Self::RemoteError(err),
+        }
+    }
+}
+
+impl From<LoadFileError> for CreateVersionError {
+    fn from(err: LoadFileError) -> Self {
+        match err {
+            LoadFileError::RepoNotFound(err) => Self::RepoNotFound(err),
+            LoadFileError::RepoLocked(err) => Self::RepoLocked(err),
+            LoadFileError::RemoteError(err) => Self::RemoteError(err),
+        }
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ListVersionsError {
+    #[error("{0}")]
+    RepoNotFound(#[from] RepoNotFoundError),
+    #[error("{0}")]
+    RepoLocked(#[from] RepoLockedError),
+    #[error("{0}")]
+    DecryptFilenameError(#[from] DecryptFilenameError),
+    #[error("{0}")]
+    RemoteError(#[from] RemoteError),
+}
+
+impl UserError for ListVersionsError {
+    fn user_error(&self) -> String {
+        match self {
+            Self::RepoNotFound(err) => err.user_error(),
+            Self::RepoLocked(err) => err.user_error(),
+            Self::DecryptFilenameError(err) => err.user_error(),
+            Self::RemoteError(err) => err.user_error(),
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::RepoNotFound(err) => err.error_code(),
+            Self::RepoLocked(err) => err.error_code(),
+            Self::DecryptFilenameError(err) => err.error_code(),
+            Self::RemoteError(err) => err.error_code(),
+        }
+    }
+}
+
+impl From<GetCipherError> for ListVersionsError {
+    fn from(err: GetCipherError) -> Self {
+        match err {
+            GetCipherError::RepoNotFound(err) => Self::RepoNotFound(err),
+            GetCipherError::RepoLocked(err) => Self::RepoLocked(err),
+        }
+    }
+}
+
+impl From<LoadFilesError> for ListVersionsError {
+    fn from(err: LoadFilesError) -> Self {
+        match err {
+            LoadFilesError::RepoNotFound(err) => Self::RepoNotFound(err),
+            LoadFilesError::RepoLocked(err) => Self::RepoLocked(err),
+            LoadFilesError::RemoteError(err) => Self::RemoteError(err),
+        }
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum PruneVersionsError {
+    #[error("{0}")]
+    RepoNotFound(#[from] RepoNotFoundError),
+    #[error("{0}")]
+    RepoReadOnly(#[from] RepoReadOnlyError),
+    #[error("storage error: {0}")]
+    StorageError(#[from] SecureStorageError),
+    #[error("{0}")]
+    ListVersionsError(#[from] ListVersionsError),
+    #[error("{0}")]
+    RemoteError(#[from] RemoteError),
+}
+
+impl UserError for PruneVersionsError {
+    fn user_error(&self) -> String {
+        match self {
+            Self::RepoNotFound(err) => err.user_error(),
+            Self::RepoReadOnly(err) => err.user_error(),
+            Self::StorageError(err) => {
+                locale::message_args("error-storage", &[("error", err.to_string().into())])
+            }
+            Self::ListVersionsError(err) => err.user_error(),
+            Self::RemoteError(err) => err.user_error(),
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::RepoNotFound(err) => err.error_code(),
+            Self::RepoReadOnly(err) => err.error_code(),
+            Self::StorageError(_) => ErrorCode::Storage,
+            Self::ListVersionsError(err) => err.error_code(),
+            Self::RemoteError(err) => err.error_code(),
+        }
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum RestoreVersionError {
+    #[error("{0}")]
+    RepoNotFound(#[from] RepoNotFoundError),
+    #[error("{0}")]
+    RepoLocked(#[from] RepoLockedError),
+    #[error("{0}")]
+    RepoReadOnly(#[from] RepoReadOnlyError),
+    #[error("version not found")]
+    VersionNotFound,
+    #[error("{0}")]
+    CreateVersionError(#[from] CreateVersionError),
+    #[error("failed to set restored file tags: {0}")]
+    SetTagsError(#[from] SetTagsError),
+    #[error("{0}")]
+    RemoteError(#[from] RemoteError),
+}
+
+impl UserError for RestoreVersionError {
+    fn user_error(&self) -> String {
+        match self {
+            Self::RepoNotFound(err) => err.user_error(),
+            Self::RepoLocked(err) => err.user_error(),
+            Self::RepoReadOnly(err) => err.user_error(),
+            Self::VersionNotFound => locale::message("error-version-not-found"),
+            Self::CreateVersionError(err) => err.user_error(),
+            Self::SetTagsError(_) => self.to_string(),
+            Self::RemoteError(err) => err.user_error(),
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::RepoNotFound(err) => err.error_code(),
+            Self::RepoLocked(err) => err.error_code(),
+            Self::RepoReadOnly(err) => err.error_code(),
+            Self::VersionNotFound => ErrorCode::VersionNotFound,
+            Self::CreateVersionError(err) => err.error_code(),
+            Self::SetTagsError(_) => ErrorCode::TagsSet,
+            Self::RemoteError(err) => err.error_code(),
+        }
+    }
+}
+
+impl From<LoadFileError> for RestoreVersionError {
+    fn from(err: LoadFileError) -> Self {
+        match err {
+            LoadFileError::RepoNotFound(err) => Self::RepoNotFound(err),
+            LoadFileError::RepoLocked(err) => Self::RepoLocked(err),
+            LoadFileError::RemoteError(err) => Self::RemoteError(err),
+        }
+    }
+}
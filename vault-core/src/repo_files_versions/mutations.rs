@@ -0,0 +1,50 @@
+use std::collections::HashMap;
+
+use crate::{store, types::RepoId};
+
+use super::state::RepoFilesVersionsSettings;
+
+pub fn settings_loaded(
+    state: &mut store::State,
+    notify: &store::Notify,
+    settings: HashMap<RepoId, RepoFilesVersionsSettings>,
+) {
+    notify(store::Event::RepoFilesVersions);
+
+    state.repo_files_versions.settings = settings;
+    state.repo_files_versions.settings_loaded = true;
+}
+
+pub fn set_settings(
+    state: &mut store::State,
+    notify: &store::Notify,
+    repo_id: &RepoId,
+    settings: RepoFilesVersionsSettings,
+) {
+    notify(store::Event::RepoFilesVersions);
+
+    state
+        .repo_files_versions
+        .settings
+        .insert(repo_id.to_owned(), settings);
+}
+
+pub fn handle_repos_mutation(
+    state: &mut store::State,
+    notify: &store::Notify,
+    mutation_state: &store::MutationState,
+) {
+    for repo_id in mutation_state
+        .repos
+        .locked_repos
+        .iter()
+        .map(|(repo_id, _)| repo_id)
+        .chain(mutation_state.repos.removed_repos.iter())
+    {
+        notify(store::Event::RepoFilesVersions);
+
+        state.repo_files_versions.settings.remove(repo_id);
+        // settings are loaded again from secure storage on next access
+        state.repo_files_versions.settings_loaded = false;
+    }
+}
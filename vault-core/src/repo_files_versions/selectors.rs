@@ -0,0 +1,201 @@
+use std::cmp::Reverse;
+
+use crate::{
+    repo_files::{selectors as repo_files_selectors, state::RepoFile},
+    store,
+    types::{DecryptedName, DecryptedPath, EncryptedPath, RepoId},
+    utils::repo_path_utils,
+};
+
+use super::state::{RepoFileVersion, RepoFilesVersionsSettings};
+
+/// hidden dir in the repo root, its name is encrypted like any other name
+pub const VERSIONS_DIR_NAME: &str = ".vault-versions";
+
+const DAY_MILLIS: i64 = 24 * 60 * 60 * 1000;
+
+pub fn get_versions_dir_path() -> DecryptedPath {
+    DecryptedPath(format!("/{}", VERSIONS_DIR_NAME))
+}
+
+/// Versions of a file are stored in a dir named after the hash of the file
+/// path so that the dir name length does not depend on the path length.
+pub fn get_file_versions_dir_path(path: &DecryptedPath) -> DecryptedPath {
+    repo_path_utils::join_path_name(
+        &get_versions_dir_path(),
+        &DecryptedName(hex::encode(md5::compute(path.0.as_bytes()).0)),
+    )
+}
+
+/// The original name is kept so that the content type of the version is
+/// the same as the content type of the file.
+pub fn get_version_name(created: i64, name: &DecryptedName) -> DecryptedName {
+    DecryptedName(format!("{} {}", created, name.0))
+}
+
+pub fn is_versions_dir(file: &RepoFile) -> bool {
+    file.decrypted_path()
+        .map(|path| path == &get_versions_dir_path())
+        .unwrap_or(false)
+}
+
+pub fn select_settings(state: &store::State, repo_id: &RepoId) -> RepoFilesVersionsSettings {
+    state
+        .repo_files_versions
+        .settings
+        .get(repo_id)
+        .cloned()
+        .unwrap_or_default()
+}
+
+pub fn file_to_version(file: &RepoFile) -> Option<RepoFileVersion> {
+    let version = match &file.tags {
+        Some(Ok(tags)) => tags.version.as_ref()?,
+        _ => return None,
+    };
+
+    Some(RepoFileVersion {
+        id: file.id.clone(),
+        repo_id: file.repo_id.clone(),
+        encrypted_path: file.encrypted_path.clone(),
+        original_path: DecryptedPath(version.path.clone()),
+        created: version.created,
+        modified: version.modified,
+        size: file.decrypted_size().ok().flatten(),
+    })
+}
+
+/// Returns versions sorted from newest to oldest.
+pub fn select_versions(
+    state: &store::State,
+    repo_id: &RepoId,
+    versions_dir_path: &EncryptedPath,
+) -> Vec<RepoFileVersion> {
+    let mut versions = repo_files_selectors::select_files(state, repo_id, versions_dir_path)
+        .filter_map(file_to_version)
+        .collect::<Vec<_>>();
+
+    versions.sort_by_key(|version| Reverse(version.created));
+
+    versions
+}
+
+pub fn select_version(
+    state: &store::State,
+    repo_id: &RepoId,
+    version_path: &EncryptedPath,
+) -> Option<RepoFileVersion> {
+    repo_files_selectors::select_file(
+        state,
+        &repo_files_selectors::get_file_id(repo_id, version_path),
+    )
+    .and_then(file_to_version)
+}
+
+/// `versions` must be sorted from newest to oldest.
+pub fn get_versions_to_prune<'a>(
+    versions: &'a [RepoFileVersion],
+    settings: &RepoFilesVersionsSettings,
+    now: i64,
+) -> Vec<&'a RepoFileVersion> {
+    versions
+        .iter()
+        .enumerate()
+        .filter(|(idx, version)| {
+            settings
+                .max_count
+                .map(|max_count| *idx >= max_count)
+                .unwrap_or(false)
+                || settings
+                    .max_age_days
+                    .map(|max_age_days| now - version.created > max_age_days as i64 * DAY_MILLIS)
+                    .unwrap_or(false)
+        })
+        .map(|(_, version)| version)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use similar_asserts::assert_eq;
+
+    use crate::{
+        repo_files_versions::state::{RepoFileVersion, RepoFilesVersionsSettings},
+        types::{DecryptedPath, EncryptedPath, RepoFileId, RepoId},
+    };
+
+    use super::{get_file_versions_dir_path, get_versions_to_prune, DAY_MILLIS};
+
+    fn version(created: i64) -> RepoFileVersion {
+        RepoFileVersion {
+            id: RepoFileId(format!("r1:/v/{}", created)),
+            repo_id: RepoId("r1".into()),
+            encrypted_path: EncryptedPath(format!("/v/{}", created)),
+            original_path: DecryptedPath("/file.txt".into()),
+            created,
+            modified: None,
+            size: Some(1),
+        }
+    }
+
+    #[test]
+    fn test_get_file_versions_dir_path() {
+        assert_eq!(
+            get_file_versions_dir_path(&DecryptedPath("/file.txt".into())),
+            DecryptedPath("/.vault-versions/0ca69acb643ae682dd700b7c190b2564".into())
+        );
+    }
+
+    #[test]
+    fn test_get_versions_to_prune_max_count() {
+        let versions = vec![version(3), version(2), version(1)];
+
+        let settings = RepoFilesVersionsSettings {
+            enabled: true,
+            max_count: Some(2),
+            max_age_days: None,
+        };
+
+        assert_eq!(
+            get_versions_to_prune(&versions, &settings, 3),
+            vec![&versions[2]]
+        );
+    }
+
+    #[test]
+    fn test_get_versions_to_prune_max_age() {
+        let now = 100 * DAY_MILLIS;
+        let versions = vec![
+            version(now - DAY_MILLIS),
+            version(now - 5 * DAY_MILLIS),
+            version(now - 10 * DAY_MILLIS),
+        ];
+
+        let settings = RepoFilesVersionsSettings {
+            enabled: true,
+            max_count: None,
+            max_age_days: Some(5),
+        };
+
+        assert_eq!(
+            get_versions_to_prune(&versions, &settings, now),
+            vec![&versions[2]]
+        );
+    }
+
+    #[test]
+    fn test_get_versions_to_prune_unlimited() {
+        let versions = vec![version(2), version(1)];
+
+        let settings = RepoFilesVersionsSettings {
+            enabled: true,
+            max_count: None,
+            max_age_days: None,
+        };
+
+        assert_eq!(
+            get_versions_to_prune(&versions, &settings, i64::MAX),
+            Vec::<&RepoFileVersion>::new()
+        );
+    }
+}
@@ -0,0 +1,399 @@
+use std::{collections::HashMap, sync::Arc};
+
+use crate::{
+    remote::ApiErrorCode,
+    remote_files::RemoteFilesService,
+    repo_files::{errors::LoadFilesError, selectors as repo_files_selectors, RepoFilesService},
+    repo_files_read::{errors::GetFilesReaderError, state::RepoFileReaderProvider},
+    repo_files_tags::{state::RepoFileTagsVersion, RepoFilesTagsService},
+    repos::ReposService,
+    runtime,
+    secure_storage::{errors::SecureStorageError, SecureStorageService},
+    store,
+    types::{EncryptedPath, RepoId},
+    utils::repo_encrypted_path_utils,
+};
+
+use super::{
+    errors::{CreateVersionError, ListVersionsError, PruneVersionsError, RestoreVersionError},
+    mutations, selectors,
+    state::{RepoFileVersion, RepoFilesVersionsSettings},
+};
+
+type RepoFilesVersionsSettingsMap = HashMap<RepoId, RepoFilesVersionsSettings>;
+
+pub const REPO_FILES_VERSIONS_SETTINGS_STORAGE_KEY: &str = "vaultRepoFilesVersionsSettings";
+
+pub struct RepoFilesVersionsService {
+    repos_service: Arc<ReposService>,
+    remote_files_service: Arc<RemoteFilesService>,
+    repo_files_service: Arc<RepoFilesService>,
+    repo_files_tags_service: Arc<RepoFilesTagsService>,
+    secure_storage_service: Arc<SecureStorageService>,
+    store: Arc<store::Store>,
+    runtime: Arc<runtime::BoxRuntime>,
+    repos_mutation_subscription_id: u32,
+}
+
+impl RepoFilesVersionsService {
+    pub fn new(
+        repos_service: Arc<ReposService>,
+        remote_files_service: Arc<RemoteFilesService>,
+        repo_files_service: Arc<RepoFilesService>,
+        repo_files_tags_service: Arc<RepoFilesTagsService>,
+        secure_storage_service: Arc<SecureStorageService>,
+        store: Arc<store::Store>,
+        runtime: Arc<runtime::BoxRuntime>,
+    ) -> Self {
+        let repos_mutation_subscription_id = store.get_next_id();
+
+        store.mutation_on(
+            repos_mutation_subscription_id,
+            &[store::MutationEvent::Repos],
+            Box::new(move |state, notify, mutation_state, _| {
+                mutations::handle_repos_mutation(state, notify, mutation_state);
+            }),
+        );
+
+        Self {
+            repos_service,
+            remote_files_service,
+            repo_files_service,
+            repo_files_tags_service,
+            secure_storage_service,
+            store,
+            runtime,
+            repos_mutation_subscription_id,
+        }
+    }
+
+    fn ensure_settings_loaded(&self) -> Result<(), SecureStorageError> {
+        if self
+            .store
+            .with_state(|state| state.repo_files_versions.settings_loaded)
+        {
+            return Ok(());
+        }
+
+        let settings = self
+            .secure_storage_service
+            .get::<RepoFilesVersionsSettingsMap>(REPO_FILES_VERSIONS_SETTINGS_STORAGE_KEY)?
+            .unwrap_or_default();
+
+        self.store.mutate(|state, notify, _, _| {
+            mutations::settings_loaded(state, notify, settings);
+        });
+
+        Ok(())
+    }
+
+    pub fn get_settings(
+        &self,
+        repo_id: &RepoId,
+    ) -> Result<RepoFilesVersionsSettings, SecureStorageError> {
+        self.ensure_settings_loaded()?;
+
+        Ok(self
+            .store
+            .with_state(|state| selectors::select_settings(state, repo_id)))
+    }
+
+    pub fn set_settings(
+        &self,
+        repo_id: &RepoId,
+        settings: RepoFilesVersionsSettings,
+    ) -> Result<(), SecureStorageError> {
+        self.ensure_settings_loaded()?;
+
+        let all_settings = self.store.mutate(|state, notify, _, _| {
+            mutations::set_settings(state, notify, repo_id, settings);
+
+            state.repo_files_versions.settings.clone()
+        });
+
+        self.secure_storage_service
+            .set(REPO_FILES_VERSIONS_SETTINGS_STORAGE_KEY, &all_settings)
+    }
+
+    /// Called before a file is overwritten. Does nothing if versioning is
+    /// disabled for the repo or if the file does not exist yet.
+    pub async fn create_version_if_enabled(
+        self: Arc<Self>,
+        repo_id: &RepoId,
+        path: &EncryptedPath,
+    ) -> Result<Option<RepoFileVersion>, CreateVersionError> {
+        if !self.get_settings(repo_id)?.enabled {
+            return Ok(None);
+        }
+
+        let file_id = repo_files_selectors::get_file_id(repo_id, path);
+
+        if self
+            .store
+            .with_state(|state| repo_files_selectors::select_file(state, &file_id).is_none())
+        {
+            return Ok(None);
+        }
+
+        self.create_version(repo_id, path).await.map(Some)
+    }
+
+    pub async fn create_version(
+        self: Arc<Self>,
+        repo_id: &RepoId,
+        path: &EncryptedPath,
+    ) -> Result<RepoFileVersion, CreateVersionError> {
+        let version = self
+            .clone()
+            .create_version_inner(repo_id, path, true)
+            .await?;
+
+        self.prune_versions_log(repo_id, path).await;
+
+        Ok(version)
+    }
+
+    /// Copies (or moves if `keep_original` is false) the file into the
+    /// versions dir and sets the version tags. Copy is done server-side so the
+    /// version has the same ciphertext as the original.
+    async fn create_version_inner(
+        self: Arc<Self>,
+        repo_id: &RepoId,
+        path: &EncryptedPath,
+        keep_original: bool,
+    ) -> Result<RepoFileVersion, CreateVersionError> {
+        let cipher = self.repos_service.get_cipher(repo_id)?;
+
+        self.repo_files_service.check_writable(repo_id)?;
+
+        let file = self
+            .store
+            .with_state(|state| {
+                repo_files_selectors::select_file(
+                    state,
+                    &repo_files_selectors::get_file_id(repo_id, path),
+                )
+                .cloned()
+            })
+            .ok_or(CreateVersionError::FileNotFound)?;
+
+        if !file.typ.is_file() {
+            return Err(CreateVersionError::NotAFile);
+        }
+
+        let decrypted_path = file.decrypted_path()?.to_owned();
+        let name = file.decrypted_name()?.to_owned();
+        let created = self.runtime.now().0;
+
+        let versions_dir_path =
+            cipher.encrypt_path(&selectors::get_file_versions_dir_path(&decrypted_path));
+
+        self.repo_files_service
+            .clone()
+            .ensure_dirs(repo_id, &versions_dir_path)
+            .await?;
+
+        let version_path = repo_encrypted_path_utils::join_path_name(
+            &versions_dir_path,
+            &cipher.encrypt_filename(&selectors::get_version_name(created, &name)),
+        );
+
+        let (mount_id, remote_path) = self.repo_files_service.get_repo_mount_path(repo_id, path)?;
+        let (version_mount_id, version_remote_path) = self
+            .repo_files_service
+            .get_repo_mount_path(repo_id, &version_path)?;
+
+        if keep_original {
+            self.remote_files_service
+                .copy_file(
+                    &mount_id,
+                    &remote_path,
+                    &version_mount_id,
+                    &version_remote_path,
+                )
+                .await?;
+        } else {
+            self.remote_files_service
+                .move_file(
+                    &mount_id,
+                    &remote_path,
+                    &version_mount_id,
+                    &version_remote_path,
+                )
+                .await?;
+        }
+
+        self.repo_files_service
+            .load_file(repo_id, &version_path)
+            .await?;
+
+        let tags_version = RepoFileTagsVersion {
+            path: decrypted_path.0.clone(),
+            created,
+            modified: file.modified,
+        };
+
+        self.repo_files_tags_service
+            .set_tags(
+                repo_id,
+                &version_path,
+                Box::new(move |_, tags| {
+                    tags.version = Some(tags_version.clone());
+
+                    Ok(())
+                }),
+            )
+            .await?;
+
+        self.store
+            .with_state(|state| selectors::select_version(state, repo_id, &version_path))
+            .ok_or(CreateVersionError::FileNotFound)
+    }
+
+    /// Lists versions of the file at `path`, newest first. The file itself
+    /// does not need to exist anymore.
+    pub async fn list_versions(
+        &self,
+        repo_id: &RepoId,
+        path: &EncryptedPath,
+    ) -> Result<Vec<RepoFileVersion>, ListVersionsError> {
+        let cipher = self.repos_service.get_cipher(repo_id)?;
+
+        let decrypted_path = cipher.decrypt_path(path)?;
+
+        let versions_dir_path =
+            cipher.encrypt_path(&selectors::get_file_versions_dir_path(&decrypted_path));
+
+        match self
+            .repo_files_service
+            .load_files(repo_id, &versions_dir_path)
+            .await
+        {
+            Ok(()) => {}
+            Err(LoadFilesError::RemoteError(err))
+                if err.is_api_error_code(ApiErrorCode::NotFound) =>
+            {
+                return Ok(vec![]);
+            }
+            Err(err) => return Err(err.into()),
+        }
+
+        Ok(self
+            .store
+            .with_state(|state| selectors::select_versions(state, repo_id, &versions_dir_path)))
+    }
+
+    pub fn get_version_reader(
+        self: Arc<Self>,
+        repo_id: &RepoId,
+        version_path: &EncryptedPath,
+    ) -> Result<RepoFileReaderProvider, GetFilesReaderError> {
+        self.repo_files_service
+            .clone()
+            .get_file_reader(repo_id, version_path)
+    }
+
+    /// The current file is moved into the versions dir first so that the
+    /// restore can be undone.
+    pub async fn restore_version(
+        self: Arc<Self>,
+        repo_id: &RepoId,
+        path: &EncryptedPath,
+        version_path: &EncryptedPath,
+    ) -> Result<(), RestoreVersionError> {
+        self.repo_files_service.check_writable(repo_id)?;
+
+        self.store
+            .with_state(|state| selectors::select_version(state, repo_id, version_path))
+            .ok_or(RestoreVersionError::VersionNotFound)?;
+
+        let file_id = repo_files_selectors::get_file_id(repo_id, path);
+
+        if self
+            .store
+            .with_state(|state| repo_files_selectors::select_file(state, &file_id).is_some())
+        {
+            self.clone()
+                .create_version_inner(repo_id, path, false)
+                .await?;
+        } else if let Some(parent_path) = repo_encrypted_path_utils::parent_path(path) {
+            self.repo_files_service
+                .clone()
+                .ensure_dirs(repo_id, &parent_path)
+                .await
+                .map_err(CreateVersionError::from)?;
+        }
+
+        let (version_mount_id, version_remote_path) = self
+            .repo_files_service
+            .get_repo_mount_path(repo_id, version_path)?;
+        let (mount_id, remote_path) = self.repo_files_service.get_repo_mount_path(repo_id, path)?;
+
+        self.remote_files_service
+            .copy_file(
+                &version_mount_id,
+                &version_remote_path,
+                &mount_id,
+                &remote_path,
+            )
+            .await?;
+
+        self.repo_files_service.load_file(repo_id, path).await?;
+
+        self.repo_files_tags_service
+            .set_tags(
+                repo_id,
+                path,
+                Box::new(|_, tags| {
+                    tags.version = None;
+
+                    Ok(())
+                }),
+            )
+            .await?;
+
+        self.prune_versions_log(repo_id, path).await;
+
+        Ok(())
+    }
+
+    pub async fn prune_versions(
+        &self,
+        repo_id: &RepoId,
+        path: &EncryptedPath,
+    ) -> Result<(), PruneVersionsError> {
+        self.repo_files_service.check_writable(repo_id)?;
+
+        let settings = self.get_settings(repo_id)?;
+
+        let versions = self.list_versions(repo_id, path).await?;
+
+        let now = self.runtime.now().0;
+
+        for version in selectors::get_versions_to_prune(&versions, &settings, now) {
+            let (mount_id, remote_path) = self
+                .repo_files_service
+                .get_repo_mount_path(repo_id, &version.encrypted_path)?;
+
+            self.remote_files_service
+                .delete_file(&mount_id, &remote_path)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// pruning errors should not fail the operation that created the version
+    async fn prune_versions_log(&self, repo_id: &RepoId, path: &EncryptedPath) {
+        if let Err(err) = self.prune_versions(repo_id, path).await {
+            log::warn!("RepoFilesVersionsService failed to prune versions: {}", err);
+        }
+    }
+}
+
+impl Drop for RepoFilesVersionsService {
+    fn drop(&mut self) {
+        self.store
+            .mutation_remove_listener(self.repos_mutation_subscription_id);
+    }
+}
@@ -0,0 +1,48 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::{DecryptedPath, EncryptedPath, RepoFileId, RepoId};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RepoFilesVersionsSettings {
+    pub enabled: bool,
+    /// number of versions kept per file
+    pub max_count: Option<usize>,
+    /// versions older than this are pruned
+    pub max_age_days: Option<u32>,
+}
+
+impl Default for RepoFilesVersionsSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_count: Some(10),
+            max_age_days: Some(30),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepoFileVersion {
+    /// id of the version file in the versions dir
+    pub id: RepoFileId,
+    pub repo_id: RepoId,
+    pub encrypted_path: EncryptedPath,
+    pub original_path: DecryptedPath,
+    pub created: i64,
+    pub modified: Option<i64>,
+    pub size: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RepoFilesVersionsState {
+    pub settings: HashMap<RepoId, RepoFilesVersionsSettings>,
+    pub settings_loaded: bool,
+}
+
+impl RepoFilesVersionsState {
+    pub fn reset(&mut self) {
+        *self = Default::default();
+    }
+}
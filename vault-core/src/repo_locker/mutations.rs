@@ -2,6 +2,7 @@ use crate::{
     lifecycle::state::AppVisibility,
     repos::{self, state::RepoState},
     store,
+    types::RepoId,
 };
 
 pub fn handle_lifecycle_mutation(
@@ -9,7 +10,7 @@ pub fn handle_lifecycle_mutation(
     notify: &store::Notify,
     mutation_state: &mut store::MutationState,
     mutation_notify: &store::MutationNotify,
-) {
+) -> Vec<RepoId> {
     let app_visibility = &state.lifecycle.app_visibility;
 
     let mut lock_repo_ids = Vec::new();
@@ -28,8 +29,11 @@ pub fn handle_lifecycle_mutation(
         }
     }
 
-    for repo_id in lock_repo_ids {
-        let _ =
-            repos::mutations::lock_repo(state, notify, mutation_state, mutation_notify, &repo_id);
-    }
+    lock_repo_ids
+        .into_iter()
+        .filter(|repo_id| {
+            repos::mutations::lock_repo(state, notify, mutation_state, mutation_notify, repo_id)
+                .is_ok()
+        })
+        .collect()
 }
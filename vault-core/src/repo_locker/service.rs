@@ -6,7 +6,16 @@ use std::{
 
 use futures::stream::{AbortHandle, Abortable};
 
-use crate::{cipher::Cipher, repos::ReposService, runtime, store, types::RepoId};
+use crate::{
+    audit_log::{
+        state::{AuditLogEvent, RepoLockReason},
+        AuditLogService,
+    },
+    cipher::Cipher,
+    repos::ReposService,
+    runtime, store,
+    types::RepoId,
+};
 
 use super::{mutations, selectors};
 
@@ -23,6 +32,7 @@ pub struct RepoLockerService {
 impl RepoLockerService {
     pub fn new(
         repos_service: Arc<ReposService>,
+        audit_log_service: Arc<AuditLogService>,
         store: Arc<store::Store>,
         runtime: Arc<runtime::BoxRuntime>,
     ) -> Arc<Self> {
@@ -32,12 +42,24 @@ impl RepoLockerService {
             lifecycle_mutation_subscription_id,
             &[store::MutationEvent::Lifecycle],
             Box::new(move |state, notify, mutation_state, mutation_notify| {
-                mutations::handle_lifecycle_mutation(
+                let locked_repo_ids = mutations::handle_lifecycle_mutation(
                     state,
                     notify,
                     mutation_state,
                     mutation_notify,
                 );
+
+                let user_id = state.user.user.as_ref().map(|user| user.id.clone());
+
+                for repo_id in locked_repo_ids {
+                    audit_log_service.record_for_user(
+                        user_id.clone(),
+                        AuditLogEvent::RepoLock {
+                            repo_id,
+                            reason: RepoLockReason::AppHidden,
+                        },
+                    );
+                }
             }),
         );
 
@@ -153,7 +175,7 @@ impl RepoLockerService {
             let now = runtime.now();
 
             if store.with_state(|state| selectors::select_should_auto_lock(state, &repo_id, now)) {
-                let _ = repos_service.lock_repo(&repo_id);
+                let _ = repos_service.lock_repo_with_reason(&repo_id, RepoLockReason::Inactivity);
 
                 return;
             }
@@ -1,6 +1,10 @@
 use thiserror::Error;
 
-use crate::{remote, repos::errors::RepoNotFoundError, user_error::UserError};
+use crate::{
+    remote,
+    repos::errors::RepoNotFoundError,
+    user_error::{ErrorCode, UserError},
+};
 
 #[derive(Error, Debug, Clone, PartialEq)]
 pub enum RepoSpaceUsageError {
@@ -17,4 +21,11 @@ impl UserError for RepoSpaceUsageError {
             Self::RemoteError(err) => err.user_error(),
         }
     }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::RepoNotFound(err) => err.error_code(),
+            Self::RemoteError(err) => err.error_code(),
+        }
+    }
 }
@@ -11,5 +11,10 @@ pub fn select_info<'a>(state: &'a store::State, unlock_id: u32) -> Option<RepoUn
             repo_id: &repo_unlock.repo_id,
             status: (&repo_unlock.status).into(),
             repo_name: repos_selectors::select_repo_name(state, &repo_unlock.repo_id),
+            failed_attempts: repos_selectors::select_unlock_failed_attempts(
+                state,
+                &repo_unlock.repo_id,
+            ),
+            retry_at: repos_selectors::select_unlock_retry_at(state, &repo_unlock.repo_id),
         })
 }
@@ -4,13 +4,16 @@ use crate::{
     common::state::Status,
     repos::{errors::UnlockRepoError, state::RepoUnlockMode},
     store::NextId,
-    types::{DecryptedName, RepoId},
+    types::{DecryptedName, RepoId, TimeMillis},
 };
 
 pub struct RepoUnlockInfo<'a> {
     pub repo_id: &'a RepoId,
     pub status: &'a Status<UnlockRepoError>,
     pub repo_name: Option<&'a DecryptedName>,
+    pub failed_attempts: u32,
+    /// Password checks are refused until this time. Can be in the past.
+    pub retry_at: Option<TimeMillis>,
 }
 
 #[derive(Debug, Clone)]
@@ -1,9 +1,12 @@
+use std::time::Duration;
+
 use thiserror::Error;
 
 use crate::{
+    locale,
     remote::{self, RemoteError},
     secure_storage::errors::SecureStorageError,
-    user_error::UserError,
+    user_error::{ErrorCode, UserError},
 };
 
 #[derive(Error, Debug, Clone, PartialEq)]
@@ -12,7 +15,11 @@ pub struct RepoNotFoundError;
 
 impl UserError for RepoNotFoundError {
     fn user_error(&self) -> String {
-        return "Safe Box not found".into();
+        return locale::message("error-repo-not-found");
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        ErrorCode::RepoNotFound
     }
 }
 
@@ -22,7 +29,11 @@ pub struct RepoLockedError;
 
 impl UserError for RepoLockedError {
     fn user_error(&self) -> String {
-        return "Safe Box is locked".into();
+        return locale::message("error-repo-locked");
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        ErrorCode::RepoLocked
     }
 }
 
@@ -32,7 +43,25 @@ pub struct RepoUnlockedError;
 
 impl UserError for RepoUnlockedError {
     fn user_error(&self) -> String {
-        return "Safe Box is unlocked".into();
+        return locale::message("error-repo-unlocked");
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        ErrorCode::RepoUnlocked
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq)]
+#[error("repo read only")]
+pub struct RepoReadOnlyError;
+
+impl UserError for RepoReadOnlyError {
+    fn user_error(&self) -> String {
+        return locale::message("error-repo-read-only");
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        ErrorCode::RepoReadOnly
     }
 }
 
@@ -42,7 +71,39 @@ pub struct InvalidPasswordError;
 
 impl UserError for InvalidPasswordError {
     fn user_error(&self) -> String {
-        String::from("Safe Key is not correct.")
+        locale::message("error-invalid-password")
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        ErrorCode::RepoInvalidPassword
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq)]
+#[error("too many failed attempts, retry in {}s", retry_after_seconds(.retry_after))]
+pub struct TooManyAttemptsError {
+    pub retry_after: Duration,
+}
+
+fn retry_after_seconds(retry_after: &Duration) -> u64 {
+    retry_after.as_millis().div_ceil(1000) as u64
+}
+
+impl UserError for TooManyAttemptsError {
+    fn user_error(&self) -> String {
+        locale::message_args(
+            "error-too-many-attempts",
+            &[(
+                "seconds",
+                retry_after_seconds(&self.retry_after).to_string().into(),
+            )],
+        )
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        ErrorCode::RepoTooManyAttempts {
+            retry_after_seconds: retry_after_seconds(&self.retry_after),
+        }
     }
 }
 
@@ -52,6 +113,8 @@ pub enum BuildCipherError {
     RepoNotFound(#[from] RepoNotFoundError),
     #[error("{0}")]
     InvalidPassword(#[from] InvalidPasswordError),
+    #[error("{0}")]
+    TooManyAttempts(#[from] TooManyAttemptsError),
 }
 
 #[derive(Error, Debug, Clone, PartialEq)]
@@ -65,10 +128,17 @@ pub enum RepoInfoError {
 impl UserError for RepoInfoError {
     fn user_error(&self) -> String {
         match self {
-            Self::RepoNotFound(_) => String::from("Safe Box not found."),
+            Self::RepoNotFound(_) => locale::message("error-repo-not-found-sentence"),
             Self::RemoteError(err) => err.user_error(),
         }
     }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::RepoNotFound(_) => ErrorCode::RepoNotFound,
+            Self::RemoteError(err) => err.error_code(),
+        }
+    }
 }
 
 #[derive(Error, Debug, Clone, PartialEq)]
@@ -86,6 +156,13 @@ impl UserError for LockRepoError {
             Self::RepoLocked(err) => err.user_error(),
         }
     }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::RepoNotFound(err) => err.error_code(),
+            Self::RepoLocked(err) => err.error_code(),
+        }
+    }
 }
 
 #[derive(Error, Debug, Clone, PartialEq)]
@@ -99,10 +176,19 @@ pub enum LoadReposError {
 impl UserError for LoadReposError {
     fn user_error(&self) -> String {
         match self {
-            Self::StorageError(err) => format!("Storage error: {}", err),
+            Self::StorageError(err) => {
+                locale::message_args("error-storage", &[("error", err.to_string().into())])
+            }
             Self::RemoteError(err) => err.user_error(),
         }
     }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::StorageError(_) => ErrorCode::Storage,
+            Self::RemoteError(err) => err.error_code(),
+        }
+    }
 }
 
 #[derive(Error, Debug, Clone, PartialEq)]
@@ -113,6 +199,8 @@ pub enum UnlockRepoError {
     RepoUnlocked(#[from] RepoUnlockedError),
     #[error("{0}")]
     InvalidPassword(#[from] InvalidPasswordError),
+    #[error("{0}")]
+    TooManyAttempts(#[from] TooManyAttemptsError),
 }
 
 impl UserError for UnlockRepoError {
@@ -121,6 +209,16 @@ impl UserError for UnlockRepoError {
             Self::RepoNotFound(err) => err.user_error(),
             Self::RepoUnlocked(err) => err.user_error(),
             Self::InvalidPassword(err) => err.user_error(),
+            Self::TooManyAttempts(err) => err.user_error(),
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::RepoNotFound(err) => err.error_code(),
+            Self::RepoUnlocked(err) => err.error_code(),
+            Self::InvalidPassword(err) => err.error_code(),
+            Self::TooManyAttempts(err) => err.error_code(),
         }
     }
 }
@@ -130,6 +228,7 @@ impl From<BuildCipherError> for UnlockRepoError {
         match err {
             BuildCipherError::RepoNotFound(err) => Self::RepoNotFound(err),
             BuildCipherError::InvalidPassword(err) => Self::InvalidPassword(err),
+            BuildCipherError::TooManyAttempts(err) => Self::TooManyAttempts(err),
         }
     }
 }
@@ -149,6 +248,13 @@ impl UserError for GetCipherError {
             Self::RepoLocked(err) => err.user_error(),
         }
     }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::RepoNotFound(err) => err.error_code(),
+            Self::RepoLocked(err) => err.error_code(),
+        }
+    }
 }
 
 #[derive(Error, Debug, Clone, PartialEq)]
@@ -163,14 +269,28 @@ impl UserError for CreateRepoError {
             Self::RemoteError(remote::RemoteError::ApiError {
                 code: remote::ApiErrorCode::VaultReposAlreadyExists,
                 ..
-            }) => String::from("This location is already a Safe Box."),
+            }) => locale::message("error-repo-already-exists"),
             Self::RemoteError(remote::RemoteError::ApiError {
                 code: remote::ApiErrorCode::VaultReposMaxTotalLimitExceeded,
                 ..
-            }) => String::from("You cannot create more Safe Boxes. Please upgrade your account."),
+            }) => locale::message("error-repos-limit-exceeded"),
             Self::RemoteError(err) => err.user_error(),
         }
     }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::RemoteError(remote::RemoteError::ApiError {
+                code: remote::ApiErrorCode::VaultReposAlreadyExists,
+                ..
+            }) => ErrorCode::RepoAlreadyExists,
+            Self::RemoteError(remote::RemoteError::ApiError {
+                code: remote::ApiErrorCode::VaultReposMaxTotalLimitExceeded,
+                ..
+            }) => ErrorCode::RepoLimitExceeded,
+            Self::RemoteError(err) => err.error_code(),
+        }
+    }
 }
 
 #[derive(Error, Debug, Clone, PartialEq)]
@@ -180,6 +300,8 @@ pub enum RemoveRepoError {
     #[error("{0}")]
     InvalidPassword(#[from] InvalidPasswordError),
     #[error("{0}")]
+    TooManyAttempts(#[from] TooManyAttemptsError),
+    #[error("{0}")]
     RemoteError(#[from] remote::RemoteError),
 }
 
@@ -188,9 +310,19 @@ impl UserError for RemoveRepoError {
         match self {
             Self::RepoNotFound(err) => err.user_error(),
             Self::InvalidPassword(err) => err.user_error(),
+            Self::TooManyAttempts(err) => err.user_error(),
             Self::RemoteError(err) => err.user_error(),
         }
     }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::RepoNotFound(err) => err.error_code(),
+            Self::InvalidPassword(err) => err.error_code(),
+            Self::TooManyAttempts(err) => err.error_code(),
+            Self::RemoteError(err) => err.error_code(),
+        }
+    }
 }
 
 impl From<BuildCipherError> for RemoveRepoError {
@@ -198,6 +330,7 @@ impl From<BuildCipherError> for RemoveRepoError {
         match err {
             BuildCipherError::RepoNotFound(err) => Self::RepoNotFound(err),
             BuildCipherError::InvalidPassword(err) => Self::InvalidPassword(err),
+            BuildCipherError::TooManyAttempts(err) => Self::TooManyAttempts(err),
         }
     }
 }
@@ -214,7 +347,16 @@ impl UserError for SetAutoLockError {
     fn user_error(&self) -> String {
         match self {
             Self::RepoNotFound(err) => err.user_error(),
-            Self::StorageError(err) => format!("Storage error: {}", err),
+            Self::StorageError(err) => {
+                locale::message_args("error-storage", &[("error", err.to_string().into())])
+            }
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::RepoNotFound(err) => err.error_code(),
+            Self::StorageError(_) => ErrorCode::Storage,
         }
     }
 }
@@ -12,7 +12,7 @@ use crate::{
     remote::{models, RemoteError},
     remote_files::selectors as remote_files_selectors,
     store,
-    types::{DecryptedName, RepoId, TimeMillis},
+    types::{DecryptedName, MountId, RepoId, TimeMillis},
 };
 
 use super::{
@@ -22,13 +22,15 @@ use super::{
     },
     repo_tree::RepoTree,
     selectors,
-    state::{Repo, RepoAutoLock, RepoState},
+    state::{Repo, RepoAutoLock, RepoState, RepoUnlockAttempts, RepoUnlockLockout},
 };
 
 fn vault_repo_to_repo(
     repo: models::VaultRepo,
     base_url: &str,
     auto_lock: Option<RepoAutoLock>,
+    mount_permissions: Option<models::MountPermissions>,
+    account_id: Option<String>,
 ) -> Repo {
     let models::VaultRepo {
         id,
@@ -61,15 +63,35 @@ fn vault_repo_to_repo(
         web_url,
         last_activity: None,
         auto_lock,
+        mount_permissions,
+        unlocked_read_only: false,
+        account_id,
     }
 }
 
-fn repo_loaded(state: &mut store::State, repo: models::VaultRepo, auto_lock: Option<RepoAutoLock>) {
-    let mut repo = vault_repo_to_repo(repo, &state.config.base_url, auto_lock);
+fn repo_loaded(
+    state: &mut store::State,
+    repo: models::VaultRepo,
+    auto_lock: Option<RepoAutoLock>,
+    mount_permissions: Option<models::MountPermissions>,
+) {
+    let mut repo = vault_repo_to_repo(
+        repo,
+        &state.config.base_url,
+        auto_lock,
+        mount_permissions,
+        state.accounts.active_user_id.clone(),
+    );
 
     if let Some(existing) = state.repos.repos_by_id.get(&repo.id) {
         repo.state = existing.state.clone();
         repo.last_activity = existing.last_activity;
+        repo.unlocked_read_only = existing.unlocked_read_only;
+
+        // keep the last known permissions if they could not be loaded
+        if repo.mount_permissions.is_none() {
+            repo.mount_permissions = existing.mount_permissions.clone();
+        }
     }
 
     state.repos.repo_ids_by_remote_file_id.insert(
@@ -110,6 +132,7 @@ pub fn repos_loaded(
     mutation_notify: &store::MutationNotify,
     res: Result<Vec<models::VaultRepo>, RemoteError>,
     auto_locks: &HashMap<RepoId, RepoAutoLock>,
+    mount_permissions: &HashMap<MountId, models::MountPermissions>,
 ) {
     match res {
         Ok(repos) => {
@@ -118,10 +141,12 @@ pub fn repos_loaded(
             let remove_repo_ids = {
                 let new_repo_ids = repos.iter().map(|repo| &repo.id).collect::<HashSet<_>>();
 
+                // repos of other accounts are kept
                 state
                     .repos
                     .repos_by_id
                     .iter()
+                    .filter(|(_, repo)| repo.account_id == state.accounts.active_user_id)
                     .filter_map(|(repo_id, _)| {
                         if new_repo_ids.contains(repo_id) {
                             None
@@ -134,8 +159,9 @@ pub fn repos_loaded(
 
             for repo in repos {
                 let auto_lock = auto_locks.get(&repo.id).cloned();
+                let repo_mount_permissions = mount_permissions.get(&repo.mount_id).cloned();
 
-                repo_loaded(state, repo, auto_lock);
+                repo_loaded(state, repo, auto_lock, repo_mount_permissions);
             }
 
             remove_repos(state, mutation_state, &remove_repo_ids);
@@ -168,6 +194,7 @@ pub fn lock_repo(
     };
 
     repo.state = RepoState::Locked;
+    repo.unlocked_read_only = false;
 
     notify(store::Event::Repos);
 
@@ -181,6 +208,28 @@ pub fn lock_repo(
     Ok(())
 }
 
+pub fn lock_all_repos(
+    state: &mut store::State,
+    notify: &store::Notify,
+    mutation_state: &mut store::MutationState,
+    mutation_notify: &store::MutationNotify,
+) -> Vec<RepoId> {
+    let repo_ids: Vec<RepoId> = state
+        .repos
+        .repos_by_id
+        .values()
+        .filter(|repo| matches!(repo.state, RepoState::Unlocked { .. }))
+        .map(|repo| repo.id.clone())
+        .collect();
+
+    repo_ids
+        .into_iter()
+        .filter(|repo_id| {
+            lock_repo(state, notify, mutation_state, mutation_notify, repo_id).is_ok()
+        })
+        .collect()
+}
+
 pub fn check_unlock_repo<'a>(
     state: &'a mut store::State,
     repo_id: &RepoId,
@@ -201,6 +250,7 @@ pub fn unlock_repo(
     mutation_notify: &store::MutationNotify,
     repo_id: &RepoId,
     cipher: Arc<Cipher>,
+    read_only: bool,
     now: TimeMillis,
 ) -> Result<(), UnlockRepoError> {
     let repo = check_unlock_repo(state, repo_id)?;
@@ -208,6 +258,7 @@ pub fn unlock_repo(
     repo.state = RepoState::Unlocked {
         cipher: cipher.clone(),
     };
+    repo.unlocked_read_only = read_only;
     repo.last_activity = Some(now);
 
     notify(store::Event::Repos);
@@ -255,7 +306,7 @@ pub fn repo_created(
     mutation_notify: &store::MutationNotify,
     repo: models::VaultRepo,
 ) {
-    repo_loaded(state, repo, None);
+    repo_loaded(state, repo, None, None);
 
     notify(store::Event::Repos);
 
@@ -314,6 +365,63 @@ pub fn set_auto_lock(
     Ok(())
 }
 
+pub fn unlock_attempts_loaded(
+    state: &mut store::State,
+    notify: &store::Notify,
+    unlock_attempts: HashMap<RepoId, RepoUnlockAttempts>,
+) {
+    notify(store::Event::RepoUnlock);
+    notify(store::Event::RepoConfigBackup);
+
+    state.repos.unlock_attempts = unlock_attempts;
+}
+
+pub fn unlock_attempt_failed(
+    state: &mut store::State,
+    notify: &store::Notify,
+    repo_id: &RepoId,
+    now: TimeMillis,
+) {
+    notify(store::Event::RepoUnlock);
+    notify(store::Event::RepoConfigBackup);
+
+    let attempts = state
+        .repos
+        .unlock_attempts
+        .entry(repo_id.to_owned())
+        .or_default();
+
+    attempts.failed += 1;
+    attempts.last_failed = now.0;
+}
+
+/// Returns `true` if there were failed attempts to reset.
+pub fn unlock_attempt_succeeded(
+    state: &mut store::State,
+    notify: &store::Notify,
+    repo_id: &RepoId,
+) -> bool {
+    if state.repos.unlock_attempts.remove(repo_id).is_none() {
+        return false;
+    }
+
+    notify(store::Event::RepoUnlock);
+    notify(store::Event::RepoConfigBackup);
+
+    true
+}
+
+pub fn set_unlock_lockout(
+    state: &mut store::State,
+    notify: &store::Notify,
+    unlock_lockout: Option<RepoUnlockLockout>,
+) {
+    notify(store::Event::RepoUnlock);
+    notify(store::Event::RepoConfigBackup);
+
+    state.config.repos.unlock_lockout = unlock_lockout;
+}
+
 pub fn set_default_auto_lock(
     state: &mut store::State,
     notify: &store::Notify,
@@ -1,23 +1,29 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use crate::{
     cipher::Cipher,
     common::state::Status,
+    config::state::ReposConfig,
     store,
-    types::{DecryptedName, RepoId},
+    types::{DecryptedName, RepoId, TimeMillis},
 };
 
 use super::{
-    errors::{GetCipherError, RepoInfoError, RepoLockedError, RepoNotFoundError},
+    errors::{
+        GetCipherError, RepoInfoError, RepoLockedError, RepoNotFoundError, RepoReadOnlyError,
+        TooManyAttemptsError,
+    },
     state::{Repo, RepoAutoLock, RepoInfo, RepoState},
 };
 
+/// Only the repos of the active account are returned.
 pub fn select_repos<'a>(state: &'a store::State) -> Vec<&'a Repo> {
     let mut repos: Vec<&'a Repo> = state
         .repos
         .repos_by_id
         .iter()
         .map(|(_, repo)| repo)
+        .filter(|repo| repo.account_id == state.accounts.active_user_id)
         .collect();
 
     repos.sort_by(|a, b| a.added.partial_cmp(&b.added).unwrap());
@@ -36,6 +42,25 @@ pub fn select_repo<'a>(
         .ok_or(RepoNotFoundError)
 }
 
+/// Missing repos are not read-only, the error is reported by the operation
+/// itself.
+pub fn select_is_read_only(state: &store::State, repo_id: &RepoId) -> bool {
+    select_repo(state, repo_id)
+        .map(|repo| repo.is_read_only())
+        .unwrap_or(false)
+}
+
+pub fn select_check_writable(
+    state: &store::State,
+    repo_id: &RepoId,
+) -> Result<(), RepoReadOnlyError> {
+    if select_is_read_only(state, repo_id) {
+        Err(RepoReadOnlyError)
+    } else {
+        Ok(())
+    }
+}
+
 pub fn select_repo_mut<'a>(
     state: &'a mut store::State,
     repo_id: &RepoId,
@@ -130,3 +155,90 @@ pub fn select_auto_locks(state: &store::State) -> HashMap<RepoId, RepoAutoLock>
 pub fn select_default_auto_lock<'a>(state: &'a store::State) -> &'a RepoAutoLock {
     &state.config.repos.default_auto_lock
 }
+
+pub fn select_unlock_delay(config: &ReposConfig, failed: u32) -> Duration {
+    let backoff = &config.unlock_backoff;
+
+    let delay = match failed.checked_sub(backoff.free_attempts) {
+        Some(exponent) => backoff
+            .base_delay
+            .checked_mul(2u32.saturating_pow(exponent))
+            .unwrap_or(backoff.max_delay)
+            .min(backoff.max_delay),
+        None => Duration::ZERO,
+    };
+
+    match &config.unlock_lockout {
+        Some(lockout) if failed >= lockout.max_failed_attempts => delay.max(lockout.duration),
+        _ => delay,
+    }
+}
+
+/// Time after which the password of the repo can be checked again, `None` if
+/// there is no delay.
+pub fn select_unlock_retry_at(state: &store::State, repo_id: &RepoId) -> Option<TimeMillis> {
+    let attempts = state.repos.unlock_attempts.get(repo_id)?;
+
+    let delay = select_unlock_delay(&state.config.repos, attempts.failed);
+
+    if delay.is_zero() {
+        return None;
+    }
+
+    Some(TimeMillis(attempts.last_failed + delay.as_millis() as i64))
+}
+
+pub fn select_unlock_failed_attempts(state: &store::State, repo_id: &RepoId) -> u32 {
+    state
+        .repos
+        .unlock_attempts
+        .get(repo_id)
+        .map(|attempts| attempts.failed)
+        .unwrap_or(0)
+}
+
+pub fn select_check_unlock_attempts(
+    state: &store::State,
+    repo_id: &RepoId,
+    now: TimeMillis,
+) -> Result<(), TooManyAttemptsError> {
+    match select_unlock_retry_at(state, repo_id) {
+        Some(retry_at) if retry_at > now => Err(TooManyAttemptsError {
+            retry_after: Duration::from_millis((retry_at.0 - now.0) as u64),
+        }),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{config::state::ReposConfig, repos::state::RepoUnlockLockout};
+
+    use super::select_unlock_delay;
+
+    #[test]
+    fn test_select_unlock_delay() {
+        let mut config = ReposConfig::default();
+
+        assert_eq!(select_unlock_delay(&config, 0), Duration::ZERO);
+        assert_eq!(select_unlock_delay(&config, 2), Duration::ZERO);
+        assert_eq!(select_unlock_delay(&config, 3), Duration::from_secs(1));
+        assert_eq!(select_unlock_delay(&config, 4), Duration::from_secs(2));
+        assert_eq!(select_unlock_delay(&config, 6), Duration::from_secs(8));
+        assert_eq!(select_unlock_delay(&config, 20), Duration::from_secs(300));
+        assert_eq!(select_unlock_delay(&config, 100), Duration::from_secs(300));
+
+        config.unlock_lockout = Some(RepoUnlockLockout {
+            max_failed_attempts: 5,
+            duration: Duration::from_secs(15 * 60),
+        });
+
+        assert_eq!(select_unlock_delay(&config, 4), Duration::from_secs(2));
+        assert_eq!(
+            select_unlock_delay(&config, 5),
+            Duration::from_secs(15 * 60)
+        );
+    }
+}
@@ -1,9 +1,19 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+};
 
+use futures::future;
 use lazy_static::lazy_static;
+use zeroize::Zeroizing;
 
 use crate::{
+    audit_log::{
+        state::{AuditLogEvent, RepoLockReason},
+        AuditLogService,
+    },
     cipher::Cipher,
+    metadata_cache::MetadataCacheService,
     rclone,
     remote::{self, models},
     remote_files::RemoteFilesService,
@@ -11,6 +21,7 @@ use crate::{
     secure_storage::{errors::SecureStorageError, SecureStorageService},
     store,
     types::{DecryptedName, MountId, RemoteName, RemotePath, RepoId},
+    user_error::UserError,
     utils::remote_path_utils,
 };
 
@@ -22,7 +33,10 @@ use super::{
     mutations,
     password_validator::{check_password_validator, generate_password_validator},
     selectors,
-    state::{Repo, RepoAutoLock, RepoConfig, RepoCreated, RepoUnlockMode},
+    state::{
+        Repo, RepoAutoLock, RepoConfig, RepoCreated, RepoUnlockAttempts, RepoUnlockLockout,
+        RepoUnlockMode,
+    },
 };
 
 lazy_static! {
@@ -34,15 +48,23 @@ lazy_static! {
 }
 
 type RepoAutoLocks = HashMap<RepoId, RepoAutoLock>;
+type RepoUnlockAttemptsById = HashMap<RepoId, RepoUnlockAttempts>;
 
 pub const REPO_AUTO_LOCKS_STORAGE_KEY: &str = "vaultRepoAutoLocks";
+pub const REPO_UNLOCK_ATTEMPTS_STORAGE_KEY: &str = "vaultRepoUnlockAttempts";
 
 pub struct ReposService {
     remote: Arc<remote::Remote>,
     remote_files_service: Arc<RemoteFilesService>,
     secure_storage_service: Arc<SecureStorageService>,
+    metadata_cache_service: Arc<MetadataCacheService>,
+    audit_log_service: Arc<AuditLogService>,
     store: Arc<store::Store>,
     runtime: Arc<runtime::BoxRuntime>,
+
+    // password checks are serialized so that parallel attempts cannot bypass
+    // the backoff
+    build_cipher_mutex: Mutex<()>,
 }
 
 impl ReposService {
@@ -50,6 +72,8 @@ impl ReposService {
         remote: Arc<remote::Remote>,
         remote_files_service: Arc<RemoteFilesService>,
         secure_storage_service: Arc<SecureStorageService>,
+        metadata_cache_service: Arc<MetadataCacheService>,
+        audit_log_service: Arc<AuditLogService>,
         store: Arc<store::Store>,
         runtime: Arc<runtime::BoxRuntime>,
     ) -> Self {
@@ -57,8 +81,12 @@ impl ReposService {
             remote,
             remote_files_service,
             secure_storage_service,
+            metadata_cache_service,
+            audit_log_service,
             store,
             runtime,
+
+            build_cipher_mutex: Mutex::new(()),
         }
     }
 
@@ -68,7 +96,21 @@ impl ReposService {
             .map(|x| x.unwrap_or_default())
     }
 
+    pub fn get_unlock_attempts(&self) -> Result<RepoUnlockAttemptsById, SecureStorageError> {
+        self.secure_storage_service
+            .get::<RepoUnlockAttemptsById>(REPO_UNLOCK_ATTEMPTS_STORAGE_KEY)
+            .map(|x| x.unwrap_or_default())
+    }
+
     pub async fn load_repos(&self) -> Result<(), LoadReposError> {
+        let unlock_attempts = self.get_unlock_attempts()?;
+
+        self.store.mutate(|state, notify, _, _| {
+            mutations::unlock_attempts_loaded(state, notify, unlock_attempts);
+        });
+
+        self.restore_cached_repos()?;
+
         self.store
             .mutate(|state, notify, mutation_state, mutation_notify| {
                 mutations::repos_loading(state, notify, mutation_state, mutation_notify);
@@ -83,6 +125,16 @@ impl ReposService {
 
         let auto_locks = self.get_auto_locks()?;
 
+        let mount_permissions = match &res {
+            Ok(repos) => self.load_mount_permissions(repos).await,
+            Err(_) => HashMap::new(),
+        };
+
+        if let Ok(repos) = &res {
+            self.metadata_cache_service
+                .set_repos(repos, &mount_permissions);
+        }
+
         self.store
             .mutate(|state, notify, mutation_state, mutation_notify| {
                 mutations::repos_loaded(
@@ -92,38 +144,163 @@ impl ReposService {
                     mutation_notify,
                     res,
                     &auto_locks,
+                    &mount_permissions,
                 );
             });
 
         res_err
     }
 
+    /// The cached repos are shown while the repos are loading.
+    fn restore_cached_repos(&self) -> Result<(), LoadReposError> {
+        if self.store.with_state(|state| state.repos.status.loaded()) {
+            return Ok(());
+        }
+
+        if let Some(cached_repos) = self.metadata_cache_service.get_repos() {
+            let auto_locks = self.get_auto_locks()?;
+
+            self.store
+                .mutate(|state, notify, mutation_state, mutation_notify| {
+                    mutations::repos_loaded(
+                        state,
+                        notify,
+                        mutation_state,
+                        mutation_notify,
+                        Ok(cached_repos.repos),
+                        &auto_locks,
+                        &cached_repos.mount_permissions,
+                    );
+                });
+        }
+
+        Ok(())
+    }
+
+    /// Repos on shared mounts can be read-only. Mounts that fail to load are
+    /// skipped.
+    async fn load_mount_permissions(
+        &self,
+        repos: &[models::VaultRepo],
+    ) -> HashMap<MountId, models::MountPermissions> {
+        let mount_ids = repos
+            .iter()
+            .map(|repo| repo.mount_id.clone())
+            .collect::<HashSet<_>>();
+
+        let mounts = future::join_all(
+            mount_ids
+                .iter()
+                .map(|mount_id| self.remote.get_mount(mount_id)),
+        )
+        .await;
+
+        mount_ids
+            .into_iter()
+            .zip(mounts)
+            .filter_map(|(mount_id, res)| match res {
+                Ok(mount) => mount.permissions.map(|permissions| (mount_id, permissions)),
+                Err(err) => {
+                    log::warn!("Failed to load mount permissions: {}: {}", mount_id.0, err);
+
+                    None
+                }
+            })
+            .collect()
+    }
+
     pub fn lock_repo(&self, repo_id: &RepoId) -> Result<(), LockRepoError> {
+        self.lock_repo_with_reason(repo_id, RepoLockReason::Manual)
+    }
+
+    pub fn lock_repo_with_reason(
+        &self,
+        repo_id: &RepoId,
+        reason: RepoLockReason,
+    ) -> Result<(), LockRepoError> {
         self.store
             .mutate(|state, notify, mutation_state, mutation_notify| {
                 mutations::lock_repo(state, notify, mutation_state, mutation_notify, repo_id)
-            })
+            })?;
+
+        self.audit_log_service.record(AuditLogEvent::RepoLock {
+            repo_id: repo_id.to_owned(),
+            reason,
+        });
+
+        Ok(())
+    }
+
+    pub fn lock_all_repos(&self, reason: RepoLockReason) -> Vec<RepoId> {
+        let repo_ids = self
+            .store
+            .mutate(|state, notify, mutation_state, mutation_notify| {
+                mutations::lock_all_repos(state, notify, mutation_state, mutation_notify)
+            });
+
+        for repo_id in &repo_ids {
+            self.audit_log_service.record(AuditLogEvent::RepoLock {
+                repo_id: repo_id.to_owned(),
+                reason,
+            });
+        }
+
+        repo_ids
     }
 
+    /// Failed attempts are counted per repo and further checks are refused
+    /// until the backoff delay has passed.
     pub fn build_cipher(
         &self,
         repo_id: &RepoId,
         password: &str,
     ) -> Result<Cipher, BuildCipherError> {
+        let _build_cipher_guard = self.build_cipher_mutex.lock().unwrap();
+
+        let now = self.runtime.now();
+
         let (salt, password_validator, password_validator_encrypted) =
             self.store.with_state(|state| {
-                selectors::select_repo(state, repo_id).map(|repo| {
-                    (
-                        repo.salt.clone(),
-                        repo.password_validator.clone(),
-                        repo.password_validator_encrypted.clone(),
-                    )
-                })
+                selectors::select_check_unlock_attempts(state, repo_id, now)?;
+
+                selectors::select_repo(state, repo_id)
+                    .map(|repo| {
+                        (
+                            repo.salt.clone(),
+                            repo.password_validator.clone(),
+                            repo.password_validator_encrypted.clone(),
+                        )
+                    })
+                    .map_err(BuildCipherError::from)
             })?;
 
         let cipher = Cipher::new(vault_crypto::Cipher::new(password, salt.as_deref()));
 
-        if !check_password_validator(&cipher, &password_validator, &password_validator_encrypted) {
+        let valid =
+            check_password_validator(&cipher, &password_validator, &password_validator_encrypted);
+
+        let now = self.runtime.now();
+
+        self.store.mutate(|state, notify, _, _| {
+            let changed = if valid {
+                mutations::unlock_attempt_succeeded(state, notify, repo_id)
+            } else {
+                mutations::unlock_attempt_failed(state, notify, repo_id, now);
+
+                true
+            };
+
+            if changed {
+                if let Err(err) = self.secure_storage_service.set(
+                    REPO_UNLOCK_ATTEMPTS_STORAGE_KEY,
+                    &state.repos.unlock_attempts,
+                ) {
+                    log::warn!("Failed to save repo unlock attempts: {}", err);
+                }
+            }
+        });
+
+        if !valid {
             return Err(BuildCipherError::InvalidPassword(InvalidPasswordError));
         }
 
@@ -137,29 +314,23 @@ impl ReposService {
         mode: RepoUnlockMode,
     ) -> Result<(), UnlockRepoError> {
         match mode {
-            RepoUnlockMode::Unlock => {
-                self.store.mutate(|state, _, _, _| {
-                    mutations::check_unlock_repo(state, repo_id).map(|_| ())
-                })?;
-
-                let cipher = Arc::new(self.build_cipher(repo_id, password)?);
-
-                let now = self.runtime.now();
-
-                self.store
-                    .mutate(|state, notify, mutation_state, mutation_notify| {
-                        mutations::unlock_repo(
-                            state,
-                            notify,
-                            mutation_state,
-                            mutation_notify,
-                            repo_id,
-                            cipher,
-                            now,
-                        )
-                    })?;
-
-                Ok(())
+            RepoUnlockMode::Unlock | RepoUnlockMode::UnlockReadOnly => {
+                let read_only = matches!(mode, RepoUnlockMode::UnlockReadOnly);
+
+                let res = self.unlock_repo_cipher(repo_id, password, read_only);
+
+                self.audit_log_service.record(match &res {
+                    Ok(()) => AuditLogEvent::RepoUnlock {
+                        repo_id: repo_id.to_owned(),
+                        read_only,
+                    },
+                    Err(err) => AuditLogEvent::RepoUnlockFailure {
+                        repo_id: repo_id.to_owned(),
+                        error_code: err.error_code().as_str().to_owned(),
+                    },
+                });
+
+                res
             }
             RepoUnlockMode::Verify => {
                 self.build_cipher(repo_id, password)?;
@@ -169,6 +340,36 @@ impl ReposService {
         }
     }
 
+    fn unlock_repo_cipher(
+        &self,
+        repo_id: &RepoId,
+        password: &str,
+        read_only: bool,
+    ) -> Result<(), UnlockRepoError> {
+        self.store
+            .mutate(|state, _, _, _| mutations::check_unlock_repo(state, repo_id).map(|_| ()))?;
+
+        let cipher = Arc::new(self.build_cipher(repo_id, password)?);
+
+        let now = self.runtime.now();
+
+        self.store
+            .mutate(|state, notify, mutation_state, mutation_notify| {
+                mutations::unlock_repo(
+                    state,
+                    notify,
+                    mutation_state,
+                    mutation_notify,
+                    repo_id,
+                    cipher,
+                    read_only,
+                    now,
+                )
+            })?;
+
+        Ok(())
+    }
+
     pub async fn create_repo(
         &self,
         mount_id: &MountId,
@@ -232,6 +433,10 @@ impl ReposService {
                 self.generate_repo_config(repo, &password)
             });
 
+        self.audit_log_service.record(AuditLogEvent::RepoCreate {
+            repo_id: repo_id.clone(),
+        });
+
         Ok(RepoCreated { repo_id, config })
     }
 
@@ -265,7 +470,11 @@ impl ReposService {
                             mutation_notify,
                             repo_id.to_owned(),
                         )
-                    })?
+                    })?;
+
+                self.audit_log_service.record(AuditLogEvent::RepoRemove {
+                    repo_id: repo_id.to_owned(),
+                });
             }
             _ => {}
         }
@@ -298,6 +507,12 @@ impl ReposService {
         })
     }
 
+    pub fn set_unlock_lockout(&self, unlock_lockout: Option<RepoUnlockLockout>) {
+        self.store.mutate(|state, notify, _, _| {
+            mutations::set_unlock_lockout(state, notify, unlock_lockout);
+        });
+    }
+
     pub fn set_default_auto_lock(&self, auto_lock: RepoAutoLock) {
         self.store.mutate(|state, notify, _, _| {
             mutations::set_default_auto_lock(state, notify, auto_lock);
@@ -322,14 +537,14 @@ impl ReposService {
         let rclone_config = rclone::config::generate_config(&rclone::config::Config {
             name: Some(repo.name.0.clone()),
             path: repo.path.0.clone(),
-            password: password.to_owned(),
+            password: Zeroizing::new(password.to_owned()),
             salt: repo.salt.clone(),
         });
 
         RepoConfig {
             name: repo.name.clone(),
             location: repo.get_location(),
-            password: password.to_owned(),
+            password: Zeroizing::new(password.to_owned()),
             salt: repo.salt.clone(),
             rclone_config,
         }
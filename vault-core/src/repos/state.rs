@@ -1,11 +1,12 @@
 use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use serde::{Deserialize, Deserializer, Serialize};
+use zeroize::Zeroizing;
 
 use crate::{
     cipher::Cipher,
     common::state::Status,
-    remote::RemoteError,
+    remote::{models, RemoteError},
     remote_files::state::RemoteFilesLocation,
     types::{DecryptedName, MountId, RemoteFileId, RemotePath, RepoId, TimeMillis},
 };
@@ -105,6 +106,25 @@ impl<'de> Deserialize<'de> for RepoAutoLockAfter {
     }
 }
 
+/// Failed password attempts are persisted so that restarting the app does not
+/// reset the backoff.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RepoUnlockAttempts {
+    #[serde(rename = "f")]
+    pub failed: u32,
+    /// Unix timestamp in milliseconds
+    #[serde(rename = "t")]
+    pub last_failed: i64,
+}
+
+/// Password checks are refused for `duration` after `max_failed_attempts`
+/// failed attempts in a row.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepoUnlockLockout {
+    pub max_failed_attempts: u32,
+    pub duration: Duration,
+}
+
 #[derive(Debug, Clone)]
 pub struct Repo {
     pub id: RepoId,
@@ -119,9 +139,25 @@ pub struct Repo {
     pub state: RepoState,
     pub last_activity: Option<TimeMillis>,
     pub auto_lock: Option<RepoAutoLock>,
+    /// `None` if the permissions of the repo mount could not be loaded.
+    pub mount_permissions: Option<models::MountPermissions>,
+    pub unlocked_read_only: bool,
+    /// User ID of the account the repo was loaded with.
+    pub account_id: Option<String>,
 }
 
 impl Repo {
+    /// Files cannot be changed if the mount is not writable or if the repo
+    /// was unlocked in read-only mode.
+    pub fn is_read_only(&self) -> bool {
+        self.unlocked_read_only
+            || self
+                .mount_permissions
+                .as_ref()
+                .map(|permissions| !permissions.write)
+                .unwrap_or(false)
+    }
+
     pub fn get_location(&self) -> RemoteFilesLocation {
         RemoteFilesLocation {
             mount_id: self.mount_id.clone(),
@@ -141,14 +177,15 @@ pub struct RepoInfo<'a> {
 pub struct RepoConfig {
     pub name: DecryptedName,
     pub location: RemoteFilesLocation,
-    pub password: String,
+    pub password: Zeroizing<String>,
     pub salt: Option<String>,
-    pub rclone_config: String,
+    pub rclone_config: Zeroizing<String>,
 }
 
 #[derive(Debug, Clone)]
 pub enum RepoUnlockMode {
     Unlock,
+    UnlockReadOnly,
     Verify,
 }
 
@@ -164,6 +201,7 @@ pub struct ReposState {
     pub repos_by_id: HashMap<RepoId, Repo>,
     pub repo_ids_by_remote_file_id: HashMap<RemoteFileId, RepoId>,
     pub mount_repo_trees: HashMap<MountId, RepoTree>,
+    pub unlock_attempts: HashMap<RepoId, RepoUnlockAttempts>,
 }
 
 impl ReposState {
@@ -2,6 +2,11 @@ use std::sync::Arc;
 
 use thiserror::Error;
 
+use crate::{
+    locale,
+    user_error::{ErrorCode, UserError},
+};
+
 #[derive(Error, Debug, Clone)]
 pub enum SecureStorageError {
     #[error("serialization error: {0}")]
@@ -15,3 +20,13 @@ impl PartialEq for SecureStorageError {
         self.to_string() == other.to_string()
     }
 }
+
+impl UserError for SecureStorageError {
+    fn user_error(&self) -> String {
+        locale::message_args("error-storage", &[("error", self.to_string().into())])
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        ErrorCode::Storage
+    }
+}
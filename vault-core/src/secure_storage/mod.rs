@@ -1,6 +1,7 @@
 pub mod errors;
 pub mod memory_secure_storage;
 pub mod secure_storage;
+pub mod selectors;
 pub mod service;
 
 pub use self::{
@@ -0,0 +1,16 @@
+use crate::types::{DecryptedPath, RepoId};
+
+/// Key for state persisted per repo location and local folder. The key does
+/// not contain paths so that they are not stored in plaintext key names.
+pub fn get_location_storage_key(
+    prefix: &str,
+    repo_id: &RepoId,
+    remote_path: &DecryptedPath,
+    local_id: &str,
+) -> String {
+    format!(
+        "{}{}",
+        prefix,
+        hex::encode(md5::compute(format!("{}\n{}\n{}", repo_id.0, remote_path.0, local_id)).0)
+    )
+}
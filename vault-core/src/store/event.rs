@@ -5,6 +5,7 @@ pub enum Event {
     Dialogs,
     Auth,
     User,
+    Accounts,
     Eventstream,
     RemoteFiles,
     RemoteFilesBrowsers,
@@ -14,11 +15,14 @@ pub enum Event {
     RepoRemove,
     RepoConfigBackup,
     RepoSpaceUsage,
+    RepoDuplicates,
     RepoFiles,
     RepoFilesBrowsers,
     RepoFilesDetails,
     RepoFilesDetailsContentData,
     RepoFilesMove,
+    RepoFilesThumbnails,
+    RepoFilesVersions,
     Transfers,
     DirPickers,
     SpaceUsage,
@@ -32,6 +36,7 @@ impl Event {
             Self::Dialogs,
             Self::Auth,
             Self::User,
+            Self::Accounts,
             Self::Eventstream,
             Self::RemoteFiles,
             Self::RemoteFilesBrowsers,
@@ -41,11 +46,14 @@ impl Event {
             Self::RepoRemove,
             Self::RepoConfigBackup,
             Self::RepoSpaceUsage,
+            Self::RepoDuplicates,
             Self::RepoFiles,
             Self::RepoFilesBrowsers,
             Self::RepoFilesDetails,
             Self::RepoFilesDetailsContentData,
             Self::RepoFilesMove,
+            Self::RepoFilesThumbnails,
+            Self::RepoFilesVersions,
             Self::Transfers,
             Self::DirPickers,
             Self::SpaceUsage,
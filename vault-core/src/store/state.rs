@@ -1,14 +1,18 @@
 use crate::{
-    config::state::ConfigState, dialogs::state::DialogsState, dir_pickers::state::DirPickersState,
-    eventstream::state::EventstreamState, lifecycle::state::LifecycleState,
-    notifications::state::NotificationsState, oauth2::state::OAuth2State,
-    remote_files::state::RemoteFilesState, remote_files_browsers::state::RemoteFilesBrowsersState,
+    accounts::state::AccountsState, config::state::ConfigState, dialogs::state::DialogsState,
+    dir_pickers::state::DirPickersState, eventstream::state::EventstreamState,
+    lifecycle::state::LifecycleState, notifications::state::NotificationsState,
+    oauth2::state::OAuth2State, remote_files::state::RemoteFilesState,
+    remote_files_browsers::state::RemoteFilesBrowsersState,
     repo_config_backup::state::RepoConfigBackupsState, repo_create::state::RepoCreatesState,
-    repo_files::state::RepoFilesState, repo_files_browsers::state::RepoFilesBrowsersState,
+    repo_duplicates::state::RepoDuplicatesState, repo_files::state::RepoFilesState,
+    repo_files_browsers::state::RepoFilesBrowsersState,
     repo_files_details::state::RepoFilesDetailsState, repo_files_move::state::RepoFilesMoveState,
-    repo_remove::state::RepoRemovesState, repo_space_usage::state::RepoSpaceUsagesState,
-    repo_unlock::state::RepoUnlocksState, repos::state::ReposState,
-    space_usage::state::SpaceUsageState, transfers::state::TransfersState, user::state::UserState,
+    repo_files_thumbnails::state::RepoFilesThumbnailsState,
+    repo_files_versions::state::RepoFilesVersionsState, repo_remove::state::RepoRemovesState,
+    repo_space_usage::state::RepoSpaceUsagesState, repo_unlock::state::RepoUnlocksState,
+    repos::state::ReposState, space_usage::state::SpaceUsageState,
+    transfers::state::TransfersState, user::state::UserState,
 };
 
 #[derive(Debug, Clone, Default)]
@@ -19,6 +23,7 @@ pub struct State {
     pub dialogs: DialogsState,
     pub oauth2: OAuth2State,
     pub user: UserState,
+    pub accounts: AccountsState,
     pub eventstream: EventstreamState,
     pub remote_files: RemoteFilesState,
     pub remote_files_browsers: RemoteFilesBrowsersState,
@@ -28,10 +33,13 @@ pub struct State {
     pub repo_removes: RepoRemovesState,
     pub repo_config_backups: RepoConfigBackupsState,
     pub repo_space_usages: RepoSpaceUsagesState,
+    pub repo_duplicates: RepoDuplicatesState,
     pub repo_files: RepoFilesState,
     pub repo_files_browsers: RepoFilesBrowsersState,
     pub repo_files_details: RepoFilesDetailsState,
     pub repo_files_move: Option<RepoFilesMoveState>,
+    pub repo_files_thumbnails: RepoFilesThumbnailsState,
+    pub repo_files_versions: RepoFilesVersionsState,
     pub transfers: TransfersState,
     pub dir_pickers: DirPickersState,
     pub space_usage: SpaceUsageState,
@@ -45,6 +53,7 @@ impl State {
         self.dialogs.reset();
         self.oauth2.reset();
         self.user.reset();
+        self.accounts.reset();
         self.eventstream.reset();
         self.remote_files.reset();
         self.remote_files_browsers.reset();
@@ -54,10 +63,13 @@ impl State {
         self.repo_removes.reset();
         self.repo_config_backups.reset();
         self.repo_space_usages.reset();
+        self.repo_duplicates.reset();
         self.repo_files.reset();
         self.repo_files_browsers.reset();
         self.repo_files_details.reset();
         self.repo_files_move = None;
+        self.repo_files_thumbnails.reset();
+        self.repo_files_versions.reset();
         self.transfers.reset();
         self.dir_pickers.reset();
         self.space_usage.reset();
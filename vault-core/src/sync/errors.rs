@@ -0,0 +1,145 @@
+use thiserror::Error;
+
+use crate::{
+    common::errors::InvalidPathError,
+    remote::RemoteError,
+    repo_files::errors::{DeleteFileError, LoadFileError, LoadFilesError},
+    repo_files_list::errors::{FilesListRecursiveItemError, GetListRecursiveError},
+    repo_files_read::errors::GetFilesReaderError,
+    repo_files_versions::errors::CreateVersionError,
+    repos::errors::{GetCipherError, RepoLockedError, RepoNotFoundError},
+    transfers::errors::{TransferError, UploadableError},
+    user_error::{ErrorCode, UserError},
+};
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum SyncLocalError {
+    #[error("{0}")]
+    LocalFileError(String),
+}
+
+impl UserError for SyncLocalError {
+    fn user_error(&self) -> String {
+        match self {
+            Self::LocalFileError(_) => self.to_string(),
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::LocalFileError(_) => ErrorCode::SyncLocalFile,
+        }
+    }
+}
+
+impl From<std::io::Error> for SyncLocalError {
+    fn from(err: std::io::Error) -> Self {
+        SyncLocalError::LocalFileError(err.to_string())
+    }
+}
+
+impl From<UploadableError> for SyncLocalError {
+    fn from(err: UploadableError) -> Self {
+        SyncLocalError::LocalFileError(err.to_string())
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum SyncError {
+    #[error("{0}")]
+    RepoNotFound(#[from] RepoNotFoundError),
+    #[error("{0}")]
+    RepoLocked(#[from] RepoLockedError),
+    #[error("{0}")]
+    InvalidPath(#[from] InvalidPathError),
+    #[error("{0}")]
+    ListError(#[from] FilesListRecursiveItemError),
+    #[error("{0}")]
+    LocalError(#[from] SyncLocalError),
+    #[error("invalid sync state: {0}")]
+    InvalidState(String),
+    #[error("{0}")]
+    TransferError(#[from] TransferError),
+    #[error("{0}")]
+    DeleteFileError(#[from] DeleteFileError),
+    #[error("{0}")]
+    GetFilesReaderError(#[from] GetFilesReaderError),
+    #[error("{0}")]
+    CreateVersionError(#[from] CreateVersionError),
+    #[error("{0}")]
+    RemoteError(#[from] RemoteError),
+}
+
+impl UserError for SyncError {
+    fn user_error(&self) -> String {
+        match self {
+            Self::RepoNotFound(err) => err.user_error(),
+            Self::RepoLocked(err) => err.user_error(),
+            Self::InvalidPath(err) => err.user_error(),
+            Self::ListError(err) => err.user_error(),
+            Self::LocalError(err) => err.user_error(),
+            Self::InvalidState(_) => self.to_string(),
+            Self::TransferError(err) => err.user_error(),
+            Self::DeleteFileError(err) => err.user_error(),
+            Self::GetFilesReaderError(err) => err.user_error(),
+            Self::CreateVersionError(err) => err.user_error(),
+            Self::RemoteError(err) => err.user_error(),
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::RepoNotFound(err) => err.error_code(),
+            Self::RepoLocked(err) => err.error_code(),
+            Self::InvalidPath(err) => err.error_code(),
+            Self::ListError(err) => err.error_code(),
+            Self::LocalError(err) => err.error_code(),
+            Self::InvalidState(_) => ErrorCode::SyncLocalFile,
+            Self::TransferError(err) => err.error_code(),
+            Self::DeleteFileError(err) => err.error_code(),
+            Self::GetFilesReaderError(err) => err.error_code(),
+            Self::CreateVersionError(err) => err.error_code(),
+            Self::RemoteError(err) => err.error_code(),
+        }
+    }
+}
+
+impl From<GetCipherError> for SyncError {
+    fn from(err: GetCipherError) -> Self {
+        match err {
+            GetCipherError::RepoNotFound(err) => Self::RepoNotFound(err),
+            GetCipherError::RepoLocked(err) => Self::RepoLocked(err),
+        }
+    }
+}
+
+impl From<LoadFileError> for SyncError {
+    fn from(err: LoadFileError) -> Self {
+        match err {
+            LoadFileError::RepoNotFound(err) => Self::RepoNotFound(err),
+            LoadFileError::RepoLocked(err) => Self::RepoLocked(err),
+            LoadFileError::RemoteError(err) => Self::RemoteError(err),
+        }
+    }
+}
+
+impl From<LoadFilesError> for SyncError {
+    fn from(err: LoadFilesError) -> Self {
+        match err {
+            LoadFilesError::RepoNotFound(err) => Self::RepoNotFound(err),
+            LoadFilesError::RepoLocked(err) => Self::RepoLocked(err),
+            LoadFilesError::RemoteError(err) => Self::RemoteError(err),
+        }
+    }
+}
+
+impl From<GetListRecursiveError> for SyncError {
+    fn from(err: GetListRecursiveError) -> Self {
+        match err {
+            GetListRecursiveError::RepoNotFound(err) => Self::RepoNotFound(err),
+            GetListRecursiveError::RepoLocked(err) => Self::RepoLocked(err),
+            GetListRecursiveError::DecryptFilenameError(err) => Self::ListError(err.into()),
+            GetListRecursiveError::RemoteError(err) => Self::RemoteError(err),
+        }
+    }
+}
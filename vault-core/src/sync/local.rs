@@ -0,0 +1,39 @@
+use async_trait::async_trait;
+
+use crate::{
+    transfers::{downloadable::BoxDownloadable, uploadable::BoxUploadable},
+    types::DecryptedPath,
+};
+
+use super::{errors::SyncLocalError, state::SyncLocalFile};
+
+/// Local side of a sync. All paths are relative to the local root and start
+/// with a slash (e.g. "/dir/file.txt").
+#[async_trait]
+pub trait SyncLocal {
+    /// Identifies the local root.
+    fn id(&self) -> String;
+
+    /// Lists all files recursively. Dirs and the persisted state are not
+    /// returned. Any error fails the whole listing because a partial listing
+    /// would look like deletes.
+    async fn list(&self) -> Result<Vec<SyncLocalFile>, SyncLocalError>;
+
+    fn uploadable(&self, path: &DecryptedPath) -> BoxUploadable;
+
+    /// Parent dirs are created if needed. The existing file must only be
+    /// replaced once the download is done.
+    async fn downloadable(&self, path: &DecryptedPath) -> Result<BoxDownloadable, SyncLocalError>;
+
+    async fn delete(&self, path: &DecryptedPath) -> Result<(), SyncLocalError>;
+
+    /// The persisted state is kept in the local root so that it survives
+    /// logout and is lost together with the synced files.
+    async fn get_state(&self, key: &str) -> Result<Option<Vec<u8>>, SyncLocalError>;
+
+    async fn set_state(&self, key: &str, value: &[u8]) -> Result<(), SyncLocalError>;
+
+    async fn remove_state(&self, key: &str) -> Result<(), SyncLocalError>;
+}
+
+pub type BoxSyncLocal = Box<dyn SyncLocal + Send + Sync>;
@@ -0,0 +1,696 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::{
+    repo_files_versions::selectors as repo_files_versions_selectors,
+    types::{DecryptedPath, RepoId},
+};
+
+use super::state::{
+    SyncAction, SyncActionReason, SyncActionType, SyncDirection, SyncLocalFile, SyncPlan,
+    SyncRemoteFile, SyncSnapshot, SyncSnapshotFile,
+};
+
+pub type SyncLocalFiles = HashMap<DecryptedPath, SyncLocalFile>;
+pub type SyncRemoteFiles = HashMap<DecryptedPath, SyncRemoteFile>;
+
+/// The state is stored in the local root so the local id is not part of the
+/// key and a moved local dir keeps its state.
+pub fn get_state_key(repo_id: &RepoId, remote_path: &DecryptedPath) -> String {
+    hex::encode(md5::compute(format!("{}\n{}", repo_id.0, remote_path.0)).0)
+}
+
+pub fn is_in_versions_dir(path: &DecryptedPath) -> bool {
+    let versions_dir_path = repo_files_versions_selectors::get_versions_dir_path();
+
+    path == &versions_dir_path || path.0.starts_with(&format!("{}/", versions_dir_path.0))
+}
+
+pub fn is_local_changed(file: &SyncLocalFile, snapshot_file: &SyncSnapshotFile) -> bool {
+    file.size != snapshot_file.local_size || file.modified != snapshot_file.local_modified
+}
+
+pub fn is_remote_changed(file: &SyncRemoteFile, snapshot_file: &SyncSnapshotFile) -> bool {
+    file.size != snapshot_file.remote_size
+        || file.modified != snapshot_file.remote_modified
+        || (file.remote_hash.is_some()
+            && snapshot_file.remote_hash.is_some()
+            && file.remote_hash != snapshot_file.remote_hash)
+}
+
+fn is_unchanged(
+    local_file: &SyncLocalFile,
+    remote_file: &SyncRemoteFile,
+    snapshot_file: Option<&SyncSnapshotFile>,
+) -> bool {
+    snapshot_file
+        .map(|snapshot_file| {
+            !is_local_changed(local_file, snapshot_file)
+                && !is_remote_changed(remote_file, snapshot_file)
+        })
+        .unwrap_or(false)
+}
+
+fn is_same_content(
+    local_file: &SyncLocalFile,
+    remote_file: &SyncRemoteFile,
+    local_hash: Option<&String>,
+) -> bool {
+    local_file.size == remote_file.size
+        && matches!((local_hash, &remote_file.hash), (Some(a), Some(b)) if a == b)
+}
+
+/// Files that exist on both sides and do not match the snapshot. If their
+/// sizes match and the remote hash is known, the local file is hashed so that
+/// identical files are not transferred.
+pub fn get_hash_candidates(
+    local_files: &SyncLocalFiles,
+    remote_files: &SyncRemoteFiles,
+    snapshot: &SyncSnapshot,
+) -> Vec<DecryptedPath> {
+    let mut paths = local_files
+        .iter()
+        .filter_map(|(path, local_file)| {
+            let remote_file = remote_files.get(path)?;
+
+            if is_unchanged(local_file, remote_file, snapshot.files.get(path))
+                || local_file.size != remote_file.size
+                || remote_file.hash.is_none()
+            {
+                return None;
+            }
+
+            Some(path.clone())
+        })
+        .collect::<Vec<_>>();
+
+    paths.sort();
+
+    paths
+}
+
+fn get_action(
+    direction: SyncDirection,
+    local_file: Option<&SyncLocalFile>,
+    remote_file: Option<&SyncRemoteFile>,
+    snapshot_file: Option<&SyncSnapshotFile>,
+    local_hash: Option<&String>,
+) -> Option<(SyncActionType, SyncActionReason)> {
+    use SyncActionReason::{Changed, Conflict, Deleted, New};
+    use SyncActionType::{DeleteLocal, DeleteRemote, Download, Upload};
+
+    let local_changed = match (local_file, snapshot_file) {
+        (Some(local_file), Some(snapshot_file)) => is_local_changed(local_file, snapshot_file),
+        _ => true,
+    };
+    let remote_changed = match (remote_file, snapshot_file) {
+        (Some(remote_file), Some(snapshot_file)) => is_remote_changed(remote_file, snapshot_file),
+        _ => true,
+    };
+
+    match (local_file, remote_file) {
+        (Some(local_file), Some(remote_file)) => {
+            if is_unchanged(local_file, remote_file, snapshot_file)
+                || is_same_content(local_file, remote_file, local_hash)
+            {
+                return None;
+            }
+
+            match direction {
+                SyncDirection::Upload => Some((Upload, Changed)),
+                SyncDirection::Download => Some((Download, Changed)),
+                SyncDirection::TwoWay => match (snapshot_file, local_changed, remote_changed) {
+                    (Some(_), true, false) => Some((Upload, Changed)),
+                    (Some(_), false, true) => Some((Download, Changed)),
+                    _ if local_file.modified >= remote_file.modified => Some((Upload, Conflict)),
+                    _ => Some((Download, Conflict)),
+                },
+            }
+        }
+        (Some(_), None) => match (direction, snapshot_file) {
+            (SyncDirection::Upload, _) | (SyncDirection::TwoWay, None) => Some((Upload, New)),
+            (SyncDirection::Download, Some(_)) => Some((DeleteLocal, Deleted)),
+            // not synced before, the local file is left alone
+            (SyncDirection::Download, None) => None,
+            (SyncDirection::TwoWay, Some(_)) if local_changed => Some((Upload, Conflict)),
+            (SyncDirection::TwoWay, Some(_)) => Some((DeleteLocal, Deleted)),
+        },
+        (None, Some(_)) => match (direction, snapshot_file) {
+            (SyncDirection::Download, _) | (SyncDirection::TwoWay, None) => Some((Download, New)),
+            (SyncDirection::Upload, Some(_)) => Some((DeleteRemote, Deleted)),
+            // not synced before, the remote file is left alone
+            (SyncDirection::Upload, None) => None,
+            (SyncDirection::TwoWay, Some(_)) if remote_changed => Some((Download, Conflict)),
+            (SyncDirection::TwoWay, Some(_)) => Some((DeleteRemote, Deleted)),
+        },
+        (None, None) => None,
+    }
+}
+
+pub fn get_plan(
+    direction: SyncDirection,
+    local_files: &SyncLocalFiles,
+    remote_files: &SyncRemoteFiles,
+    snapshot: &SyncSnapshot,
+    local_hashes: &HashMap<DecryptedPath, String>,
+) -> SyncPlan {
+    let paths = local_files
+        .keys()
+        .chain(remote_files.keys())
+        .collect::<BTreeSet<_>>();
+
+    let mut plan = SyncPlan::default();
+
+    for path in paths {
+        let local_file = local_files.get(path);
+        let remote_file = remote_files.get(path);
+
+        match get_action(
+            direction,
+            local_file,
+            remote_file,
+            snapshot.files.get(path),
+            local_hashes.get(path),
+        ) {
+            Some((typ, reason)) => {
+                let size = match typ {
+                    SyncActionType::Upload | SyncActionType::DeleteLocal => {
+                        local_file.map(|file| file.size)
+                    }
+                    SyncActionType::Download | SyncActionType::DeleteRemote => {
+                        remote_file.map(|file| file.size)
+                    }
+                };
+
+                plan.actions.push(SyncAction {
+                    typ,
+                    reason,
+                    path: path.clone(),
+                    size: size.unwrap_or(0),
+                });
+            }
+            None => {
+                if local_file.is_some() && remote_file.is_some() {
+                    plan.unchanged_count += 1;
+                }
+            }
+        }
+    }
+
+    plan
+}
+
+fn get_snapshot_file(local_file: &SyncLocalFile, remote_file: &SyncRemoteFile) -> SyncSnapshotFile {
+    SyncSnapshotFile {
+        local_size: local_file.size,
+        local_modified: local_file.modified,
+        remote_size: remote_file.size,
+        remote_modified: remote_file.modified,
+        remote_hash: remote_file.remote_hash.clone(),
+    }
+}
+
+/// Builds the snapshot after a run. The side that was the source of a
+/// transfer is recorded as it was when planning, so changes made during the
+/// run are picked up by the next run. Failed paths keep their previous
+/// entries.
+pub fn get_snapshot(
+    plan: &SyncPlan,
+    failed_paths: &HashSet<DecryptedPath>,
+    previous_snapshot: &SyncSnapshot,
+    local_files: &SyncLocalFiles,
+    remote_files: &SyncRemoteFiles,
+    local_files_after: &SyncLocalFiles,
+    remote_files_after: &SyncRemoteFiles,
+) -> SyncSnapshot {
+    let actions = plan
+        .actions
+        .iter()
+        .map(|action| (&action.path, action.typ))
+        .collect::<HashMap<_, _>>();
+
+    let mut snapshot = SyncSnapshot::default();
+
+    let paths = local_files
+        .keys()
+        .chain(remote_files.keys())
+        .chain(previous_snapshot.files.keys())
+        .collect::<HashSet<_>>();
+
+    for path in paths {
+        let snapshot_file = if failed_paths.contains(path) {
+            previous_snapshot.files.get(path).cloned()
+        } else {
+            match actions.get(path) {
+                None => local_files
+                    .get(path)
+                    .zip(remote_files.get(path))
+                    .map(|(local_file, remote_file)| get_snapshot_file(local_file, remote_file)),
+                Some(SyncActionType::Upload) => local_files
+                    .get(path)
+                    .zip(remote_files_after.get(path))
+                    .map(|(local_file, remote_file)| get_snapshot_file(local_file, remote_file)),
+                Some(SyncActionType::Download) => local_files_after
+                    .get(path)
+                    .zip(remote_files.get(path))
+                    .map(|(local_file, remote_file)| get_snapshot_file(local_file, remote_file)),
+                Some(SyncActionType::DeleteLocal) | Some(SyncActionType::DeleteRemote) => None,
+            }
+        };
+
+        if let Some(snapshot_file) = snapshot_file {
+            snapshot.files.insert(path.clone(), snapshot_file);
+        }
+    }
+
+    snapshot
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use similar_asserts::assert_eq;
+
+    use crate::{
+        sync::state::{
+            SyncAction, SyncActionReason, SyncActionType, SyncDirection, SyncLocalFile, SyncPlan,
+            SyncRemoteFile, SyncSnapshot, SyncSnapshotFile,
+        },
+        types::{DecryptedPath, EncryptedPath},
+    };
+
+    use super::{get_hash_candidates, get_plan, get_snapshot, SyncLocalFiles, SyncRemoteFiles};
+
+    fn local(path: &str, size: i64, modified: i64) -> (DecryptedPath, SyncLocalFile) {
+        let path = DecryptedPath(path.into());
+
+        (
+            path.clone(),
+            SyncLocalFile {
+                path,
+                size,
+                modified,
+            },
+        )
+    }
+
+    fn remote(
+        path: &str,
+        size: i64,
+        modified: i64,
+        hash: Option<&str>,
+    ) -> (DecryptedPath, SyncRemoteFile) {
+        let path = DecryptedPath(path.into());
+
+        (
+            path.clone(),
+            SyncRemoteFile {
+                encrypted_path: EncryptedPath(format!("/enc{}", path.0)),
+                path,
+                size,
+                modified,
+                hash: hash.map(str::to_string),
+                remote_hash: Some(format!("rh{}", modified)),
+            },
+        )
+    }
+
+    fn synced(path: &str, size: i64, local_modified: i64, remote_modified: i64) -> SyncSnapshot {
+        SyncSnapshot {
+            files: HashMap::from([(
+                DecryptedPath(path.into()),
+                SyncSnapshotFile {
+                    local_size: size,
+                    local_modified,
+                    remote_size: size,
+                    remote_modified,
+                    remote_hash: Some(format!("rh{}", remote_modified)),
+                },
+            )]),
+        }
+    }
+
+    fn action(typ: SyncActionType, reason: SyncActionReason, path: &str, size: i64) -> SyncAction {
+        SyncAction {
+            typ,
+            reason,
+            path: DecryptedPath(path.into()),
+            size,
+        }
+    }
+
+    fn plan(
+        direction: SyncDirection,
+        local_files: &SyncLocalFiles,
+        remote_files: &SyncRemoteFiles,
+        snapshot: &SyncSnapshot,
+    ) -> SyncPlan {
+        get_plan(
+            direction,
+            local_files,
+            remote_files,
+            snapshot,
+            &HashMap::new(),
+        )
+    }
+
+    #[test]
+    fn test_get_plan_new_files() {
+        let local_files = HashMap::from([local("/a.txt", 1, 10)]);
+        let remote_files = HashMap::from([remote("/b.txt", 2, 20, None)]);
+        let snapshot = SyncSnapshot::default();
+
+        assert_eq!(
+            plan(
+                SyncDirection::Upload,
+                &local_files,
+                &remote_files,
+                &snapshot
+            )
+            .actions,
+            vec![action(
+                SyncActionType::Upload,
+                SyncActionReason::New,
+                "/a.txt",
+                1
+            )]
+        );
+        assert_eq!(
+            plan(
+                SyncDirection::Download,
+                &local_files,
+                &remote_files,
+                &snapshot
+            )
+            .actions,
+            vec![action(
+                SyncActionType::Download,
+                SyncActionReason::New,
+                "/b.txt",
+                2
+            )]
+        );
+        assert_eq!(
+            plan(
+                SyncDirection::TwoWay,
+                &local_files,
+                &remote_files,
+                &snapshot
+            )
+            .actions,
+            vec![
+                action(SyncActionType::Upload, SyncActionReason::New, "/a.txt", 1),
+                action(SyncActionType::Download, SyncActionReason::New, "/b.txt", 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_get_plan_unchanged() {
+        let local_files = HashMap::from([local("/a.txt", 1, 10)]);
+        let remote_files = HashMap::from([remote("/a.txt", 1, 20, None)]);
+        let snapshot = synced("/a.txt", 1, 10, 20);
+
+        for direction in [
+            SyncDirection::Upload,
+            SyncDirection::Download,
+            SyncDirection::TwoWay,
+        ] {
+            assert_eq!(
+                plan(direction, &local_files, &remote_files, &snapshot),
+                SyncPlan {
+                    actions: vec![],
+                    unchanged_count: 1,
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn test_get_plan_same_hash_without_snapshot() {
+        let local_files = HashMap::from([local("/a.txt", 1, 10)]);
+        let remote_files = HashMap::from([remote("/a.txt", 1, 20, Some("h1"))]);
+        let snapshot = SyncSnapshot::default();
+
+        assert_eq!(
+            get_hash_candidates(&local_files, &remote_files, &snapshot),
+            vec![DecryptedPath("/a.txt".into())]
+        );
+
+        let same = get_plan(
+            SyncDirection::TwoWay,
+            &local_files,
+            &remote_files,
+            &snapshot,
+            &HashMap::from([(DecryptedPath("/a.txt".into()), "h1".to_string())]),
+        );
+        assert_eq!(same.actions, vec![]);
+        assert_eq!(same.unchanged_count, 1);
+
+        let different = get_plan(
+            SyncDirection::TwoWay,
+            &local_files,
+            &remote_files,
+            &snapshot,
+            &HashMap::from([(DecryptedPath("/a.txt".into()), "h2".to_string())]),
+        );
+        assert_eq!(
+            different.actions,
+            vec![action(
+                SyncActionType::Download,
+                SyncActionReason::Conflict,
+                "/a.txt",
+                1
+            )]
+        );
+    }
+
+    #[test]
+    fn test_get_plan_two_way_changes() {
+        let snapshot = synced("/a.txt", 1, 10, 20);
+
+        let local_changed = plan(
+            SyncDirection::TwoWay,
+            &HashMap::from([local("/a.txt", 2, 30)]),
+            &HashMap::from([remote("/a.txt", 1, 20, None)]),
+            &snapshot,
+        );
+        assert_eq!(
+            local_changed.actions,
+            vec![action(
+                SyncActionType::Upload,
+                SyncActionReason::Changed,
+                "/a.txt",
+                2
+            )]
+        );
+
+        let remote_changed = plan(
+            SyncDirection::TwoWay,
+            &HashMap::from([local("/a.txt", 1, 10)]),
+            &HashMap::from([remote("/a.txt", 3, 40, None)]),
+            &snapshot,
+        );
+        assert_eq!(
+            remote_changed.actions,
+            vec![action(
+                SyncActionType::Download,
+                SyncActionReason::Changed,
+                "/a.txt",
+                3
+            )]
+        );
+
+        let both_changed = plan(
+            SyncDirection::TwoWay,
+            &HashMap::from([local("/a.txt", 2, 50)]),
+            &HashMap::from([remote("/a.txt", 3, 40, None)]),
+            &snapshot,
+        );
+        assert_eq!(
+            both_changed.actions,
+            vec![action(
+                SyncActionType::Upload,
+                SyncActionReason::Conflict,
+                "/a.txt",
+                2
+            )]
+        );
+    }
+
+    #[test]
+    fn test_get_plan_deletes() {
+        let snapshot = synced("/a.txt", 1, 10, 20);
+        let local_files = HashMap::from([local("/a.txt", 1, 10)]);
+        let remote_files = HashMap::from([remote("/a.txt", 1, 20, None)]);
+
+        assert_eq!(
+            plan(
+                SyncDirection::TwoWay,
+                &HashMap::new(),
+                &remote_files,
+                &snapshot
+            )
+            .actions,
+            vec![action(
+                SyncActionType::DeleteRemote,
+                SyncActionReason::Deleted,
+                "/a.txt",
+                1
+            )]
+        );
+        assert_eq!(
+            plan(
+                SyncDirection::TwoWay,
+                &local_files,
+                &HashMap::new(),
+                &snapshot
+            )
+            .actions,
+            vec![action(
+                SyncActionType::DeleteLocal,
+                SyncActionReason::Deleted,
+                "/a.txt",
+                1
+            )]
+        );
+        // a remote file that was deleted locally is deleted in upload mode
+        // but restored in download mode
+        assert_eq!(
+            plan(
+                SyncDirection::Upload,
+                &HashMap::new(),
+                &remote_files,
+                &snapshot
+            )
+            .actions,
+            vec![action(
+                SyncActionType::DeleteRemote,
+                SyncActionReason::Deleted,
+                "/a.txt",
+                1
+            )]
+        );
+        assert_eq!(
+            plan(
+                SyncDirection::Download,
+                &HashMap::new(),
+                &remote_files,
+                &snapshot
+            )
+            .actions,
+            vec![action(
+                SyncActionType::Download,
+                SyncActionReason::New,
+                "/a.txt",
+                1
+            )]
+        );
+        // a file that was changed on one side and deleted on the other is kept
+        assert_eq!(
+            plan(
+                SyncDirection::TwoWay,
+                &HashMap::from([local("/a.txt", 2, 30)]),
+                &HashMap::new(),
+                &snapshot
+            )
+            .actions,
+            vec![action(
+                SyncActionType::Upload,
+                SyncActionReason::Conflict,
+                "/a.txt",
+                2
+            )]
+        );
+        // files that were never synced are not deleted
+        assert_eq!(
+            plan(
+                SyncDirection::Upload,
+                &HashMap::new(),
+                &remote_files,
+                &SyncSnapshot::default()
+            )
+            .actions,
+            vec![]
+        );
+    }
+
+    #[test]
+    fn test_get_snapshot() {
+        let local_files = HashMap::from([local("/up.txt", 1, 10), local("/same.txt", 2, 10)]);
+        let remote_files = HashMap::from([
+            remote("/down.txt", 3, 20, None),
+            remote("/same.txt", 2, 20, None),
+        ]);
+        let local_files_after = HashMap::from([
+            local("/up.txt", 1, 11),
+            local("/down.txt", 3, 30),
+            local("/same.txt", 2, 10),
+        ]);
+        let remote_files_after = HashMap::from([
+            remote("/up.txt", 1, 40, None),
+            remote("/down.txt", 3, 20, None),
+            remote("/same.txt", 2, 20, None),
+        ]);
+        let plan = SyncPlan {
+            actions: vec![
+                action(
+                    SyncActionType::Download,
+                    SyncActionReason::New,
+                    "/down.txt",
+                    3,
+                ),
+                action(SyncActionType::Upload, SyncActionReason::New, "/up.txt", 1),
+            ],
+            unchanged_count: 1,
+        };
+
+        let snapshot = get_snapshot(
+            &plan,
+            &HashSet::new(),
+            &SyncSnapshot::default(),
+            &local_files,
+            &remote_files,
+            &local_files_after,
+            &remote_files_after,
+        );
+
+        assert_eq!(
+            snapshot.files.get(&DecryptedPath("/up.txt".into())),
+            Some(&SyncSnapshotFile {
+                local_size: 1,
+                local_modified: 10,
+                remote_size: 1,
+                remote_modified: 40,
+                remote_hash: Some("rh40".into()),
+            })
+        );
+        assert_eq!(
+            snapshot.files.get(&DecryptedPath("/down.txt".into())),
+            Some(&SyncSnapshotFile {
+                local_size: 3,
+                local_modified: 30,
+                remote_size: 3,
+                remote_modified: 20,
+                remote_hash: Some("rh20".into()),
+            })
+        );
+        assert_eq!(snapshot.files.len(), 3);
+
+        let failed_snapshot = get_snapshot(
+            &plan,
+            &HashSet::from([DecryptedPath("/up.txt".into())]),
+            &SyncSnapshot::default(),
+            &local_files,
+            &remote_files,
+            &local_files_after,
+            &remote_files_after,
+        );
+
+        assert_eq!(
+            failed_snapshot.files.get(&DecryptedPath("/up.txt".into())),
+            None
+        );
+        assert_eq!(failed_snapshot.files.len(), 2);
+    }
+}
@@ -0,0 +1,569 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+};
+
+use futures::{future::BoxFuture, AsyncReadExt, FutureExt, StreamExt};
+
+use crate::{
+    common::errors::InvalidPathError,
+    remote::ApiErrorCode,
+    repo_files::{
+        errors::LoadFileError, selectors as repo_files_selectors, state::RepoFile,
+        state::RepoFilesUploadConflictResolution, RepoFilesService,
+    },
+    repo_files_list::{state::RepoFilesListRecursiveItem, RepoFilesListService},
+    repo_files_read::RepoFilesReadService,
+    repo_files_versions::RepoFilesVersionsService,
+    repos::{errors::RepoNotFoundError, ReposService},
+    store,
+    transfers::{
+        errors::TransferError, selectors as transfers_selectors, state::TransferState,
+        state::TransferUploadRelativeName, TransfersService,
+    },
+    types::{DecryptedPath, EncryptedPath},
+    utils::{repo_encrypted_path_utils, repo_path_utils},
+};
+
+use super::{
+    errors::{SyncError, SyncLocalError},
+    local::BoxSyncLocal,
+    selectors::{self, SyncLocalFiles, SyncRemoteFiles},
+    state::{
+        SyncAction, SyncActionError, SyncActionReason, SyncActionType, SyncConfig, SyncPlan,
+        SyncRemoteFile, SyncResult, SyncSnapshot,
+    },
+};
+
+struct SyncContext {
+    state_key: String,
+    remote_root_path: EncryptedPath,
+    local_files: SyncLocalFiles,
+    remote_files: SyncRemoteFiles,
+    remote_repo_files: HashMap<DecryptedPath, RepoFile>,
+    snapshot: SyncSnapshot,
+    plan: SyncPlan,
+}
+
+pub struct SyncService {
+    repos_service: Arc<ReposService>,
+    repo_files_service: Arc<RepoFilesService>,
+    repo_files_list_service: Arc<RepoFilesListService>,
+    repo_files_read_service: Arc<RepoFilesReadService>,
+    repo_files_versions_service: Arc<RepoFilesVersionsService>,
+    transfers_service: Arc<TransfersService>,
+    store: Arc<store::Store>,
+}
+
+impl SyncService {
+    pub fn new(
+        repos_service: Arc<ReposService>,
+        repo_files_service: Arc<RepoFilesService>,
+        repo_files_list_service: Arc<RepoFilesListService>,
+        repo_files_read_service: Arc<RepoFilesReadService>,
+        repo_files_versions_service: Arc<RepoFilesVersionsService>,
+        transfers_service: Arc<TransfersService>,
+        store: Arc<store::Store>,
+    ) -> Self {
+        Self {
+            repos_service,
+            repo_files_service,
+            repo_files_list_service,
+            repo_files_read_service,
+            repo_files_versions_service,
+            transfers_service,
+            store,
+        }
+    }
+
+    /// Dry run. Nothing is changed on either side.
+    pub async fn plan(
+        &self,
+        config: &SyncConfig,
+        local: &BoxSyncLocal,
+    ) -> Result<SyncPlan, SyncError> {
+        Ok(self.get_context(config, local).await?.plan)
+    }
+
+    /// Uploads and downloads go through the transfers queue. Errors of single
+    /// actions do not stop the run, they are returned in the result and the
+    /// actions are planned again on the next run.
+    pub async fn run(
+        &self,
+        config: &SyncConfig,
+        local: &BoxSyncLocal,
+    ) -> Result<SyncResult, SyncError> {
+        let context = self.get_context(config, local).await?;
+
+        let errors = self.execute(config, local, &context).await;
+
+        let failed_paths = errors
+            .iter()
+            .map(|error| error.action.path.clone())
+            .collect::<HashSet<_>>();
+
+        let local_files_after = self.list_local(local).await?;
+        let (remote_files_after, _) = self.list_remote(config, &context.remote_root_path).await?;
+
+        let snapshot = selectors::get_snapshot(
+            &context.plan,
+            &failed_paths,
+            &context.snapshot,
+            &context.local_files,
+            &context.remote_files,
+            &local_files_after,
+            &remote_files_after,
+        );
+
+        self.set_snapshot(local, &context.state_key, &snapshot)
+            .await?;
+
+        Ok(SyncResult {
+            plan: context.plan,
+            errors,
+        })
+    }
+
+    /// Forgets the persisted state. The next run behaves like the first one.
+    pub async fn reset(&self, config: &SyncConfig, local: &BoxSyncLocal) -> Result<(), SyncError> {
+        let remote_path = repo_path_utils::normalize_path(&config.remote_path)?;
+
+        Ok(local
+            .remove_state(&selectors::get_state_key(&config.repo_id, &remote_path))
+            .await?)
+    }
+
+    async fn get_snapshot(
+        &self,
+        local: &BoxSyncLocal,
+        state_key: &str,
+    ) -> Result<SyncSnapshot, SyncError> {
+        match local.get_state(state_key).await? {
+            Some(bytes) => serde_json::from_slice(&bytes)
+                .map_err(|err| SyncError::InvalidState(err.to_string())),
+            None => Ok(SyncSnapshot::default()),
+        }
+    }
+
+    async fn set_snapshot(
+        &self,
+        local: &BoxSyncLocal,
+        state_key: &str,
+        snapshot: &SyncSnapshot,
+    ) -> Result<(), SyncError> {
+        let bytes =
+            serde_json::to_vec(snapshot).map_err(|err| SyncError::InvalidState(err.to_string()))?;
+
+        Ok(local.set_state(state_key, &bytes).await?)
+    }
+
+    async fn get_context(
+        &self,
+        config: &SyncConfig,
+        local: &BoxSyncLocal,
+    ) -> Result<SyncContext, SyncError> {
+        let remote_path = repo_path_utils::normalize_path(&config.remote_path)?;
+        let remote_root_path = self
+            .repos_service
+            .get_cipher(&config.repo_id)?
+            .encrypt_path(&remote_path);
+
+        let state_key = selectors::get_state_key(&config.repo_id, &remote_path);
+
+        let snapshot = self.get_snapshot(local, &state_key).await?;
+
+        let local_files = self.list_local(local).await?;
+        let (remote_files, remote_repo_files) = self.list_remote(config, &remote_root_path).await?;
+
+        let mut local_hashes = HashMap::new();
+
+        for path in selectors::get_hash_candidates(&local_files, &remote_files, &snapshot) {
+            local_hashes.insert(path.clone(), self.hash_local_file(local, &path).await?);
+        }
+
+        let plan = selectors::get_plan(
+            config.direction,
+            &local_files,
+            &remote_files,
+            &snapshot,
+            &local_hashes,
+        );
+
+        Ok(SyncContext {
+            state_key,
+            remote_root_path,
+            local_files,
+            remote_files,
+            remote_repo_files,
+            snapshot,
+            plan,
+        })
+    }
+
+    async fn list_local(&self, local: &BoxSyncLocal) -> Result<SyncLocalFiles, SyncError> {
+        Ok(local
+            .list()
+            .await?
+            .into_iter()
+            .map(|file| (file.path.clone(), file))
+            .collect())
+    }
+
+    /// A missing remote root is the same as an empty one. Any listing error
+    /// fails the whole listing because a partial listing would look like
+    /// deletes.
+    async fn list_remote(
+        &self,
+        config: &SyncConfig,
+        remote_root_path: &EncryptedPath,
+    ) -> Result<(SyncRemoteFiles, HashMap<DecryptedPath, RepoFile>), SyncError> {
+        match self
+            .repo_files_service
+            .load_file(&config.repo_id, remote_root_path)
+            .await
+        {
+            Ok(()) => {}
+            Err(LoadFileError::RemoteError(err))
+                if err.is_api_error_code(ApiErrorCode::NotFound) =>
+            {
+                return Ok(Default::default());
+            }
+            Err(err) => return Err(err.into()),
+        }
+
+        let root_file = self
+            .store
+            .with_state(|state| {
+                repo_files_selectors::select_file(
+                    state,
+                    &repo_files_selectors::get_file_id(&config.repo_id, remote_root_path),
+                )
+                .cloned()
+            })
+            .ok_or(SyncError::RepoNotFound(RepoNotFoundError))?;
+
+        let mut items_stream = self
+            .repo_files_list_service
+            .get_list_recursive(&root_file)
+            .await?;
+
+        let mut remote_files = HashMap::new();
+        let mut remote_repo_files = HashMap::new();
+
+        while let Some(item) = items_stream.next().await {
+            match item {
+                RepoFilesListRecursiveItem::File {
+                    relative_repo_path,
+                    file,
+                } => {
+                    if !file.typ.is_file() {
+                        continue;
+                    }
+
+                    let (path, size) = match (
+                        relative_repo_path,
+                        file.decrypted_path(),
+                        file.decrypted_size(),
+                    ) {
+                        (Ok(path), Ok(repo_path), Ok(Some(size)))
+                            if !path.is_root() && !selectors::is_in_versions_dir(repo_path) =>
+                        {
+                            (path, size)
+                        }
+                        _ => continue,
+                    };
+
+                    remote_files.insert(
+                        path.clone(),
+                        SyncRemoteFile {
+                            path: path.clone(),
+                            encrypted_path: file.encrypted_path.clone(),
+                            size,
+                            modified: file.modified.unwrap_or(0),
+                            hash: file.hash(),
+                            remote_hash: file.remote_hash.clone(),
+                        },
+                    );
+                    remote_repo_files.insert(path, file);
+                }
+                RepoFilesListRecursiveItem::Error { error, .. } => {
+                    return Err(SyncError::ListError(error));
+                }
+            }
+        }
+
+        Ok((remote_files, remote_repo_files))
+    }
+
+    async fn hash_local_file(
+        &self,
+        local: &BoxSyncLocal,
+        path: &DecryptedPath,
+    ) -> Result<String, SyncLocalError> {
+        let (mut reader, _) = local.uploadable(path).reader().await?;
+
+        let mut context = md5::Context::new();
+        let mut buf = vec![0; 64 * 1024];
+
+        loop {
+            let n = reader.read(&mut buf).await?;
+
+            if n == 0 {
+                break;
+            }
+
+            context.consume(&buf[..n]);
+        }
+
+        Ok(hex::encode(context.compute().0))
+    }
+
+    async fn execute(
+        &self,
+        config: &SyncConfig,
+        local: &BoxSyncLocal,
+        context: &SyncContext,
+    ) -> Vec<SyncActionError> {
+        let transfer_ids: Arc<Mutex<HashSet<u32>>> = Default::default();
+        let transfer_errors: Arc<Mutex<HashMap<u32, TransferError>>> = Default::default();
+
+        let listener_id = self.store.get_next_id();
+
+        self.store.on(
+            listener_id,
+            &[store::Event::Transfers],
+            self.get_failed_transfers_callback(transfer_ids.clone(), transfer_errors.clone()),
+        );
+
+        let mut errors = Vec::new();
+        let mut transfers = Vec::new();
+
+        for action in &context.plan.actions {
+            match self
+                .execute_action(config, local, context, action, &transfer_ids)
+                .await
+            {
+                Ok(Some(transfer)) => transfers.push((action, transfer)),
+                Ok(None) => {}
+                Err(error) => errors.push(SyncActionError {
+                    action: action.clone(),
+                    error,
+                }),
+            }
+        }
+
+        let results = futures::future::join_all(
+            transfers
+                .into_iter()
+                .map(|(action, (id, future))| future.map(move |res| (action, id, res))),
+        )
+        .await;
+
+        self.store.remove_listener(listener_id);
+
+        for (action, id, res) in results {
+            if let Err(err) = res {
+                let err = match err {
+                    TransferError::Aborted => {
+                        transfer_errors.lock().unwrap().remove(&id).unwrap_or(err)
+                    }
+                    err => err,
+                };
+
+                errors.push(SyncActionError {
+                    action: action.clone(),
+                    error: err.into(),
+                });
+            }
+        }
+
+        errors
+    }
+
+    /// Deletes are done immediately, transfers are queued and returned.
+    async fn execute_action(
+        &self,
+        config: &SyncConfig,
+        local: &BoxSyncLocal,
+        context: &SyncContext,
+        action: &SyncAction,
+        transfer_ids: &Mutex<HashSet<u32>>,
+    ) -> Result<Option<(u32, BoxFuture<'static, Result<(), TransferError>>)>, SyncError> {
+        let remote_file = context.remote_files.get(&action.path);
+
+        match action.typ {
+            SyncActionType::Upload => {
+                if let (SyncActionReason::Conflict, Some(remote_file)) =
+                    (action.reason, remote_file)
+                {
+                    self.repo_files_versions_service
+                        .clone()
+                        .create_version(&config.repo_id, &remote_file.encrypted_path)
+                        .await?;
+                }
+
+                // only overwrite the remote file if it was not changed since
+                // the listing
+                let conflict_resolution = match remote_file {
+                    Some(remote_file) => RepoFilesUploadConflictResolution::Overwrite {
+                        if_remote_size: None,
+                        if_remote_modified: None,
+                        if_remote_hash: remote_file.remote_hash.clone(),
+                    },
+                    None => RepoFilesUploadConflictResolution::Error,
+                };
+
+                let (id, future) = self
+                    .transfers_service
+                    .clone()
+                    .upload_with_conflict_resolution(
+                        config.repo_id.clone(),
+                        context.remote_root_path.clone(),
+                        TransferUploadRelativeName(action.path.0[1..].to_owned()),
+                        local.uploadable(&action.path),
+                        conflict_resolution,
+                    );
+
+                transfer_ids.lock().unwrap().insert(id);
+
+                Ok(Some((
+                    id,
+                    async move { future.await?.await.map(|_| ()) }.boxed(),
+                )))
+            }
+            SyncActionType::Download => {
+                let file = context
+                    .remote_repo_files
+                    .get(&action.path)
+                    .cloned()
+                    .ok_or(SyncError::TransferError(TransferError::TransferNotFound))?;
+
+                if let (SyncActionReason::Conflict, Some(_)) =
+                    (action.reason, context.local_files.get(&action.path))
+                {
+                    self.upload_conflict_copy(config, local, &file.encrypted_path, action)
+                        .await?;
+                }
+
+                let reader_provider = self
+                    .repo_files_read_service
+                    .clone()
+                    .get_files_reader(vec![file])?;
+
+                let downloadable = local.downloadable(&action.path).await?;
+
+                let (id, future) = self
+                    .transfers_service
+                    .clone()
+                    .download(reader_provider, downloadable);
+
+                transfer_ids.lock().unwrap().insert(id);
+
+                Ok(Some((id, async move { future.await?.await }.boxed())))
+            }
+            SyncActionType::DeleteLocal => {
+                local.delete(&action.path).await?;
+
+                Ok(None)
+            }
+            SyncActionType::DeleteRemote => {
+                if let Some(remote_file) = remote_file {
+                    self.repo_files_service
+                        .delete_file(&config.repo_id, &remote_file.encrypted_path)
+                        .await?;
+                }
+
+                Ok(None)
+            }
+        }
+    }
+
+    /// Uploads the local file next to the remote file at `encrypted_path`
+    /// under an unused name. Waits for the upload so that the local file is
+    /// not replaced before it is read.
+    async fn upload_conflict_copy(
+        &self,
+        config: &SyncConfig,
+        local: &BoxSyncLocal,
+        encrypted_path: &EncryptedPath,
+        action: &SyncAction,
+    ) -> Result<(), SyncError> {
+        let (encrypted_parent_path, name) = match (
+            repo_encrypted_path_utils::parent_path(encrypted_path),
+            repo_path_utils::path_to_name(&action.path),
+        ) {
+            (Some(encrypted_parent_path), Some(name)) => (encrypted_parent_path, name),
+            _ => return Err(SyncError::InvalidPath(InvalidPathError)),
+        };
+
+        let copy_name = self
+            .repo_files_service
+            .get_unused_name(&config.repo_id, &encrypted_parent_path, &name)
+            .await?;
+
+        let (_, future) = self
+            .transfers_service
+            .clone()
+            .upload_with_conflict_resolution(
+                config.repo_id.clone(),
+                encrypted_parent_path,
+                TransferUploadRelativeName(copy_name.0),
+                local.uploadable(&action.path),
+                RepoFilesUploadConflictResolution::Error,
+            );
+
+        future.await?.await?;
+
+        Ok(())
+    }
+
+    /// Transfers that failed and will not be retried automatically wait for a
+    /// manual retry, so their result futures would never resolve. They are
+    /// aborted and their errors are kept.
+    fn get_failed_transfers_callback(
+        &self,
+        transfer_ids: Arc<Mutex<HashSet<u32>>>,
+        transfer_errors: Arc<Mutex<HashMap<u32, TransferError>>>,
+    ) -> store::OnCallback {
+        let store = self.store.clone();
+        let transfers_service = self.transfers_service.clone();
+
+        Box::new(move |_, add_side_effect| {
+            let failed = store.with_state(|state| {
+                transfer_ids
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .filter_map(|id| {
+                        let transfer = transfers_selectors::select_transfer(state, *id)?;
+
+                        match &transfer.state {
+                            TransferState::Failed { error }
+                                if !transfers_selectors::can_retry(transfer)
+                                    || !transfers_selectors::select_can_autoretry(
+                                        state, transfer,
+                                    ) =>
+                            {
+                                Some((*id, error.clone()))
+                            }
+                            _ => None,
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            });
+
+            if !failed.is_empty() {
+                let transfers_service = transfers_service.clone();
+                let transfer_errors = transfer_errors.clone();
+
+                add_side_effect(Box::new(move || {
+                    for (id, error) in failed {
+                        transfer_errors.lock().unwrap().insert(id, error);
+
+                        transfers_service.clone().abort(id);
+                    }
+                }));
+            }
+        })
+    }
+}
@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::{DecryptedPath, EncryptedPath, RepoId};
+
+use super::errors::SyncError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncDirection {
+    /// The remote dir mirrors the local dir.
+    Upload,
+    /// The local dir mirrors the remote dir.
+    Download,
+    /// Changes on either side are applied to the other side. If a file was
+    /// changed on both sides the newer one wins and the other one is kept
+    /// (see `SyncActionReason::Conflict`).
+    TwoWay,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncConfig {
+    pub repo_id: RepoId,
+    pub remote_path: DecryptedPath,
+    pub direction: SyncDirection,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncLocalFile {
+    pub path: DecryptedPath,
+    pub size: i64,
+    pub modified: i64,
+}
+
+/// `path` is relative to the remote sync root, `hash` is the plaintext MD5
+/// from the file tags and `remote_hash` is the hash of the encrypted content.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncRemoteFile {
+    pub path: DecryptedPath,
+    pub encrypted_path: EncryptedPath,
+    pub size: i64,
+    pub modified: i64,
+    pub hash: Option<String>,
+    pub remote_hash: Option<String>,
+}
+
+/// Both sides of a file as they were after the last successful sync.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncSnapshotFile {
+    #[serde(rename = "ls")]
+    pub local_size: i64,
+    #[serde(rename = "lm")]
+    pub local_modified: i64,
+    #[serde(rename = "rs")]
+    pub remote_size: i64,
+    #[serde(rename = "rm")]
+    pub remote_modified: i64,
+    #[serde(rename = "rh", default, skip_serializing_if = "Option::is_none")]
+    pub remote_hash: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct SyncSnapshot {
+    pub files: HashMap<DecryptedPath, SyncSnapshotFile>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncActionType {
+    Upload,
+    Download,
+    DeleteLocal,
+    DeleteRemote,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncActionReason {
+    /// The file does not exist on the other side.
+    New,
+    /// The file was changed since the last sync (or differs on the first
+    /// sync).
+    Changed,
+    /// The file was deleted on the other side since the last sync.
+    Deleted,
+    /// The file was changed on both sides, the newer one wins. A losing
+    /// remote file is kept as a version, a losing local file is uploaded as
+    /// a copy next to the remote file (and downloaded by the next run).
+    Conflict,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncAction {
+    pub typ: SyncActionType,
+    pub reason: SyncActionReason,
+    pub path: DecryptedPath,
+    pub size: i64,
+}
+
+/// The dry-run report. Actions are sorted by path.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SyncPlan {
+    pub actions: Vec<SyncAction>,
+    pub unchanged_count: usize,
+}
+
+impl SyncPlan {
+    pub fn count(&self, typ: SyncActionType) -> usize {
+        self.actions
+            .iter()
+            .filter(|action| action.typ == typ)
+            .count()
+    }
+
+    pub fn transfer_bytes(&self) -> i64 {
+        self.actions
+            .iter()
+            .filter(|action| {
+                matches!(
+                    action.typ,
+                    SyncActionType::Upload | SyncActionType::Download
+                )
+            })
+            .map(|action| action.size)
+            .sum()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncActionError {
+    pub action: SyncAction,
+    pub error: SyncError,
+}
+
+/// Failed actions are retried on the next run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncResult {
+    pub plan: SyncPlan,
+    pub errors: Vec<SyncActionError>,
+}
@@ -2,11 +2,12 @@ use thiserror::Error;
 
 use crate::{
     cipher::errors::{DecryptFilenameError, DecryptSizeError},
+    locale,
     remote::RemoteError,
     repo_files::errors::{FileNameError, LoadFilesError, UploadFileReaderError},
     repo_files_read::errors::GetFilesReaderError,
-    repos::errors::{GetCipherError, RepoLockedError, RepoNotFoundError},
-    user_error::UserError,
+    repos::errors::{GetCipherError, RepoLockedError, RepoNotFoundError, RepoReadOnlyError},
+    user_error::{ErrorCode, UserError},
 };
 
 #[derive(Error, Debug, Clone, PartialEq)]
@@ -24,6 +25,13 @@ impl UserError for UploadableError {
             Self::NotRetriable => self.to_string(),
         }
     }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::LocalFileError(_) => ErrorCode::TransferLocalFile,
+            Self::NotRetriable => ErrorCode::TransferNotRetriable,
+        }
+    }
 }
 
 impl From<std::io::Error> for UploadableError {
@@ -53,6 +61,15 @@ impl UserError for DownloadableError {
             Self::Aborted => self.to_string(),
         }
     }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::LocalFileError(_) => ErrorCode::TransferLocalFile,
+            Self::NotOpenable => ErrorCode::TransferNotOpenable,
+            Self::NotRetriable => ErrorCode::TransferNotRetriable,
+            Self::Aborted => ErrorCode::TransferAborted,
+        }
+    }
 }
 
 impl From<std::io::Error> for DownloadableError {
@@ -68,6 +85,8 @@ pub enum TransferError {
     #[error("{0}")]
     RepoLocked(#[from] RepoLockedError),
     #[error("{0}")]
+    RepoReadOnly(#[from] RepoReadOnlyError),
+    #[error("{0}")]
     DecryptFilenameError(#[from] DecryptFilenameError),
     #[error("{0}")]
     DecryptSizeError(#[from] DecryptSizeError),
@@ -98,6 +117,7 @@ impl UserError for TransferError {
         match self {
             Self::RepoNotFound(err) => err.user_error(),
             Self::RepoLocked(err) => err.user_error(),
+            Self::RepoReadOnly(err) => err.user_error(),
             Self::DecryptFilenameError(err) => err.user_error(),
             Self::DecryptSizeError(err) => err.user_error(),
             Self::RemoteError(err) => err.user_error(),
@@ -109,7 +129,27 @@ impl UserError for TransferError {
             Self::TransferNotFound => self.to_string(),
             Self::AlreadyExists => self.to_string(),
             Self::IOError(_) => self.to_string(),
-            Self::Aborted => "Transfer has been aborted.".into(),
+            Self::Aborted => locale::message("error-transfer-aborted"),
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::RepoNotFound(err) => err.error_code(),
+            Self::RepoLocked(err) => err.error_code(),
+            Self::RepoReadOnly(err) => err.error_code(),
+            Self::DecryptFilenameError(err) => err.error_code(),
+            Self::DecryptSizeError(err) => err.error_code(),
+            Self::RemoteError(err) => err.error_code(),
+            Self::LocalFileError(_) => ErrorCode::TransferLocalFile,
+            Self::NotRetriable => ErrorCode::TransferNotRetriable,
+            Self::NotOpenable => ErrorCode::TransferNotOpenable,
+            Self::RemoteFileNotFound => ErrorCode::TransferRemoteFileNotFound,
+            Self::RemoteFilesEmpty => ErrorCode::TransferRemoteFilesEmpty,
+            Self::TransferNotFound => ErrorCode::TransferNotFound,
+            Self::AlreadyExists => ErrorCode::TransferAlreadyExists,
+            Self::IOError(_) => ErrorCode::TransferIO,
+            Self::Aborted => ErrorCode::TransferAborted,
         }
     }
 }
@@ -128,6 +168,7 @@ impl From<UploadFileReaderError> for TransferError {
         match err {
             UploadFileReaderError::RepoNotFound(err) => TransferError::RepoNotFound(err),
             UploadFileReaderError::RepoLocked(err) => TransferError::RepoLocked(err),
+            UploadFileReaderError::RepoReadOnly(err) => TransferError::RepoReadOnly(err),
             UploadFileReaderError::DecryptFilenameError(err) => {
                 TransferError::DecryptFilenameError(err)
             }
@@ -153,6 +194,7 @@ impl From<GetFilesReaderError> for TransferError {
             GetFilesReaderError::RepoLocked(err) => Self::RepoLocked(err),
             GetFilesReaderError::FileNotFound => Self::RemoteFileNotFound,
             GetFilesReaderError::FilesEmpty => Self::RemoteFilesEmpty,
+            GetFilesReaderError::RangeNotSatisfiable => Self::IOError(err.to_string()),
             GetFilesReaderError::DecryptFilenameError(err) => Self::DecryptFilenameError(err),
             GetFilesReaderError::DecryptSizeError(err) => Self::DecryptSizeError(err),
             GetFilesReaderError::RemoteError(err) => Self::RemoteError(err),
@@ -192,6 +192,7 @@ fn create_transfer(
         transferred_bytes: 0,
         attempts: 0,
         order: state.transfers.total_count,
+        account_id: state.accounts.active_user_id.clone(),
     };
 
     state.transfers.transfers.insert(id.clone(), transfer);
@@ -255,6 +256,7 @@ pub fn upload_transfer_processed(
     id: u32,
     size: SizeInfo,
     cipher: &Cipher,
+    overwrite: bool,
 ) -> Result<EncryptedName, TransferError> {
     let transfer = match state.transfers.transfers.get(&id) {
         Some(transfer) => transfer,
@@ -265,7 +267,11 @@ pub fn upload_transfer_processed(
         None => return Err(TransferError::TransferNotFound),
     };
 
-    let name = selectors::select_unused_name(state, transfer, upload_transfer);
+    let name = if overwrite {
+        upload_transfer.original_name.clone()
+    } else {
+        selectors::select_unused_name(state, transfer, upload_transfer)
+    };
     let encrypted_name = cipher.encrypt_filename(&name);
 
     let transfer = match state.transfers.transfers.get_mut(&id) {
@@ -683,6 +689,7 @@ mod tests {
                 transferred_bytes: 0,
                 attempts: 0,
                 order: 0,
+                account_id: None,
             }
         );
 
@@ -714,12 +721,14 @@ mod tests {
                 transferred_bytes: 0,
                 attempts: 1,
                 order: 0,
+                account_id: None,
             }
         );
 
         let (notify, _, _) = store_test_helpers::mutation();
-        let name = upload_transfer_processed(&mut state, &notify, 1, SizeInfo::Exact(11), &cipher)
-            .unwrap();
+        let name =
+            upload_transfer_processed(&mut state, &notify, 1, SizeInfo::Exact(11), &cipher, false)
+                .unwrap();
         assert_eq!(
             name,
             cipher.encrypt_filename(&DecryptedName("file (1).txt".into()))
@@ -750,6 +759,7 @@ mod tests {
                 transferred_bytes: 0,
                 attempts: 1,
                 order: 0,
+                account_id: None,
             }
         );
     }
@@ -826,6 +836,7 @@ mod tests {
                 transferred_bytes: 0,
                 attempts: 0,
                 order: 0,
+                account_id: None,
             }
         );
 
@@ -861,12 +872,14 @@ mod tests {
                 transferred_bytes: 0,
                 attempts: 1,
                 order: 0,
+                account_id: None,
             }
         );
 
         let (notify, _, _) = store_test_helpers::mutation();
-        let name = upload_transfer_processed(&mut state, &notify, 1, SizeInfo::Exact(11), &cipher)
-            .unwrap();
+        let name =
+            upload_transfer_processed(&mut state, &notify, 1, SizeInfo::Exact(11), &cipher, false)
+                .unwrap();
         assert_eq!(
             name,
             cipher.encrypt_filename(&DecryptedName("file (1).txt".into()))
@@ -901,6 +914,7 @@ mod tests {
                 transferred_bytes: 0,
                 attempts: 1,
                 order: 0,
+                account_id: None,
             }
         );
     }
@@ -951,6 +965,7 @@ mod tests {
                 transferred_bytes: 0,
                 attempts: 0,
                 order: 0,
+                account_id: None,
             }
         );
 
@@ -973,8 +988,9 @@ mod tests {
         start_transfer(&mut state, &notify, 2, TimeMillis(2));
 
         let (notify, _, _) = store_test_helpers::mutation();
-        let name = upload_transfer_processed(&mut state, &notify, 2, SizeInfo::Exact(10), &cipher)
-            .unwrap();
+        let name =
+            upload_transfer_processed(&mut state, &notify, 2, SizeInfo::Exact(10), &cipher, false)
+                .unwrap();
         assert_eq!(
             name,
             cipher.encrypt_filename(&DecryptedName("file.txt".into()))
@@ -1008,12 +1024,14 @@ mod tests {
                 transferred_bytes: 0,
                 attempts: 1,
                 order: 0,
+                account_id: None,
             }
         );
 
         let (notify, _, _) = store_test_helpers::mutation();
-        let name = upload_transfer_processed(&mut state, &notify, 1, SizeInfo::Exact(11), &cipher)
-            .unwrap();
+        let name =
+            upload_transfer_processed(&mut state, &notify, 1, SizeInfo::Exact(11), &cipher, false)
+                .unwrap();
         assert_eq!(
             name,
             cipher.encrypt_filename(&DecryptedName("file (1).txt".into()))
@@ -1044,6 +1062,7 @@ mod tests {
                 transferred_bytes: 0,
                 attempts: 1,
                 order: 0,
+                account_id: None,
             }
         );
     }
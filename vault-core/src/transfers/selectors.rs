@@ -3,12 +3,16 @@ use std::{cmp::min, time::Duration};
 use crate::{
     common::state::RemainingTime,
     config::state::TransfersConfig,
+    remote::ApiErrorCode,
     repo_files::selectors as repo_files_selectors,
     store,
     types::{DecryptedName, TimeMillis},
 };
 
-use super::state::{Transfer, TransferState, TransferType, TransfersState, UploadTransfer};
+use super::{
+    errors::TransferError,
+    state::{Transfer, TransferState, TransferType, TransfersState, UploadTransfer},
+};
 
 pub fn can_retry(transfer: &Transfer) -> bool {
     matches!(transfer.state, TransferState::Failed { .. }) && transfer.is_retriable
@@ -100,7 +104,15 @@ pub fn select_can_abort_all(state: &store::State) -> bool {
 }
 
 pub fn select_can_autoretry(state: &store::State, transfer: &Transfer) -> bool {
-    transfer.attempts < select_config(state).autoretry_attempts
+    // retrying will not help until space is freed up
+    let is_quota_exceeded = matches!(
+        &transfer.state,
+        TransferState::Failed {
+            error: TransferError::RemoteError(err)
+        } if err.is_api_error_code(ApiErrorCode::QuotaExceeded)
+    );
+
+    transfer.attempts < select_config(state).autoretry_attempts && !is_quota_exceeded
 }
 
 pub fn select_remaining_count(state: &store::State) -> usize {
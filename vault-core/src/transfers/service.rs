@@ -42,6 +42,7 @@ use super::{
 #[derive(Default)]
 struct TransfersServiceTransferStateUpload {
     uploadable: Option<Arc<BoxUploadable>>,
+    conflict_resolution: Option<RepoFilesUploadConflictResolution>,
     result_sender: Option<Sender<UploadResult>>,
 }
 
@@ -105,6 +106,27 @@ impl TransfersService {
         parent_path: EncryptedPath,
         name: TransferUploadRelativeName,
         uploadable: BoxUploadable,
+    ) -> (u32, CreateUploadResultFuture) {
+        self.upload_with_conflict_resolution(
+            repo_id,
+            parent_path,
+            name,
+            uploadable,
+            RepoFilesUploadConflictResolution::Error,
+        )
+    }
+
+    /// With `RepoFilesUploadConflictResolution::Error` an unused name is
+    /// chosen, with `Overwrite` the existing file is replaced and with
+    /// `OverwriteOrAutorename` an unused name is chosen if the existing file
+    /// cannot be replaced.
+    pub fn upload_with_conflict_resolution(
+        self: Arc<Self>,
+        repo_id: RepoId,
+        parent_path: EncryptedPath,
+        name: TransferUploadRelativeName,
+        uploadable: BoxUploadable,
+        conflict_resolution: RepoFilesUploadConflictResolution,
     ) -> (u32, CreateUploadResultFuture) {
         let id = self.get_next_id();
 
@@ -118,7 +140,14 @@ impl TransfersService {
 
         let cleanup_state = self.state.clone();
         let future = self
-            .create_upload(repo_id, parent_path, name, uploadable, id)
+            .create_upload(
+                repo_id,
+                parent_path,
+                name,
+                uploadable,
+                conflict_resolution,
+                id,
+            )
             .map_err(move |err| {
                 cleanup_state.write().unwrap().transfers.remove(&id);
                 err
@@ -134,11 +163,13 @@ impl TransfersService {
         parent_path: EncryptedPath,
         name: TransferUploadRelativeName,
         uploadable: BoxUploadable,
+        conflict_resolution: RepoFilesUploadConflictResolution,
         id: u32,
     ) -> CreateUploadResult {
         let size = uploadable.size().await?;
 
         let is_retriable = uploadable.is_retriable().await?;
+        let is_persistent = uploadable.is_persistent().await?;
 
         let result_receiver = self.store.mutate(|state, notify, _, _| {
             let result_receiver = match self.state.write().unwrap().transfers.get_mut(&id) {
@@ -148,6 +179,7 @@ impl TransfersService {
                     match &mut state.typ {
                         TransfersServiceTransferStateType::Upload(upload) => {
                             upload.uploadable = Some(Arc::new(uploadable));
+                            upload.conflict_resolution = Some(conflict_resolution);
                             upload.result_sender = Some(result_sender);
                         }
                         _ => {}
@@ -158,7 +190,6 @@ impl TransfersService {
                 None => return Err(TransferError::Aborted),
             };
 
-            let is_persistent = false;
             let is_openable = false;
 
             mutations::create_upload_transfer(
@@ -519,22 +550,38 @@ impl TransfersService {
             }
         }
 
-        let uploadable = self
+        let (uploadable, conflict_resolution) = self
             .state
             .read()
             .unwrap()
             .transfers
             .get(&id)
             .and_then(|state| match &state.typ {
-                TransfersServiceTransferStateType::Upload(upload) => upload.uploadable.clone(),
+                TransfersServiceTransferStateType::Upload(upload) => {
+                    match (
+                        upload.uploadable.clone(),
+                        upload.conflict_resolution.clone(),
+                    ) {
+                        (Some(uploadable), Some(conflict_resolution)) => {
+                            Some((uploadable, conflict_resolution))
+                        }
+                        _ => None,
+                    }
+                }
                 _ => None,
             })
             .ok_or(TransferError::TransferNotFound)?;
 
         let (reader, size) = uploadable.reader().await?;
 
+        let overwrite = matches!(
+            conflict_resolution,
+            RepoFilesUploadConflictResolution::Overwrite { .. }
+                | RepoFilesUploadConflictResolution::OverwriteOrAutorename { .. }
+        );
+
         let name = self.store.mutate(|state, notify, _, _| {
-            mutations::upload_transfer_processed(state, notify, id, size, &cipher)
+            mutations::upload_transfer_processed(state, notify, id, size, &cipher, overwrite)
         })?;
 
         let size = match size {
@@ -551,10 +598,24 @@ impl TransfersService {
                 name,
                 reader,
                 size,
-                RepoFilesUploadConflictResolution::Error,
+                conflict_resolution,
                 Some(self.clone().get_transfer_on_progress(id)),
             )
-            .await?;
+            .await
+            .map_err(TransferError::from);
+
+        let res = match res {
+            Ok(res) => res,
+            Err(TransferError::RemoteError(err))
+                if err.is_api_error_code(ApiErrorCode::Conflict)
+                    || err.is_api_error_code(ApiErrorCode::AlreadyExists) =>
+            {
+                self.handle_upload_conflict(id, &upload_transfer).await;
+
+                return Err(TransferError::RemoteError(err));
+            }
+            Err(err) => return Err(err),
+        };
 
         let sender = self
             .state
@@ -574,6 +635,29 @@ impl TransfersService {
         }))
     }
 
+    /// The retry picks an unused name from the reloaded files. Uploads with
+    /// `OverwriteOrAutorename` stop overwriting.
+    async fn handle_upload_conflict(&self, id: u32, upload_transfer: &UploadTransfer) {
+        if let Some(state) = self.state.write().unwrap().transfers.get_mut(&id) {
+            if let TransfersServiceTransferStateType::Upload(upload) = &mut state.typ {
+                if matches!(
+                    upload.conflict_resolution,
+                    Some(RepoFilesUploadConflictResolution::OverwriteOrAutorename { .. })
+                ) {
+                    upload.conflict_resolution = Some(RepoFilesUploadConflictResolution::Error);
+                }
+            }
+        }
+
+        if let Err(err) = self
+            .repo_files_service
+            .load_files(&upload_transfer.repo_id, &upload_transfer.parent_path)
+            .await
+        {
+            log::warn!("Failed to reload files after upload conflict: {}", err);
+        }
+    }
+
     async fn process_download_transfer(
         self: Arc<Self>,
         id: u32,
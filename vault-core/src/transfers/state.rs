@@ -84,6 +84,8 @@ pub struct Transfer {
     pub transferred_bytes: i64,
     pub attempts: usize,
     pub order: usize,
+    /// User ID of the account that was active when the transfer was created.
+    pub account_id: Option<String>,
 }
 
 impl Transfer {
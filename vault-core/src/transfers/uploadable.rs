@@ -8,6 +8,10 @@ use super::errors::UploadableError;
 pub trait Uploadable {
     async fn size(&self) -> Result<SizeInfo, UploadableError>;
     async fn is_retriable(&self) -> Result<bool, UploadableError>;
+    /// Persistent transfers stay in the list when they are done.
+    async fn is_persistent(&self) -> Result<bool, UploadableError> {
+        Ok(false)
+    }
     async fn reader(&self) -> Result<(BoxAsyncRead, SizeInfo), UploadableError>;
 }
 
@@ -0,0 +1,295 @@
+use std::collections::BTreeMap;
+
+/// Stable, machine-readable error codes. Frontends should decide on retry
+/// buttons or re-login prompts based on `as_str()` and never on the localized
+/// `user_error()` message. Codes must not be renamed once released.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorCode {
+    Unknown,
+    Canceled,
+    Storage,
+    InvalidPath,
+    InvalidName,
+    LocaleNotFound { name: String },
+
+    HttpResponse,
+    RemoteQuotaExceeded,
+    RemoteApiError { api_code: String },
+    RemoteUnexpectedStatus { status_code: u16 },
+
+    OAuth2InvalidToken,
+    OAuth2InvalidState,
+    OAuth2InvalidGrant,
+    OAuth2AuthorizationPending,
+    OAuth2SlowDown,
+    OAuth2AccessDenied,
+    OAuth2DeviceCodeExpired,
+    OAuth2Unknown,
+
+    AccountNotFound,
+    AccountActive,
+
+    AuditLogChainBroken { seq: u64 },
+    AuditLogHeadMismatch,
+
+    RepoNotFound,
+    RepoLocked,
+    RepoUnlocked,
+    RepoReadOnly,
+    RepoInvalidPassword,
+    RepoAlreadyExists,
+    RepoLimitExceeded,
+    RepoTooManyAttempts { retry_after_seconds: u64 },
+
+    CipherDecryptSize,
+    CipherDecodeName,
+    CipherDecryptName,
+    CipherNameUnicode,
+    CipherDecryptData,
+
+    FileNotFound,
+    FileAlreadyExists,
+    FileDirAlreadyExists,
+    FileFilesEmpty,
+    FileRenameRoot,
+    FileMoveRoot,
+    FileRangeNotSatisfiable,
+    FileIO,
+    FileAborted,
+
+    DetailsAlreadyLoading,
+    DetailsLoadFilterMismatch,
+    DetailsAlreadySaving,
+    DetailsNotDirty,
+    DetailsInvalidState,
+    DetailsAutosaveNotPossible,
+    DetailsDiscardChanges,
+    DetailsCannotSaveRoot,
+
+    TagsBase64,
+    TagsDecrypt,
+    TagsDeserialize,
+    TagsSerialize,
+    TagsEncrypt,
+    TagsMultipleValues,
+    TagsHashMismatch,
+    TagsSet,
+
+    ThumbnailDecode,
+    ThumbnailEncode,
+    ThumbnailNotSupported,
+    ThumbnailEncrypt,
+    ThumbnailDecrypt,
+
+    VersionsNotAFile,
+    VersionNotFound,
+
+    RenameInvalidPattern,
+
+    OfflineDiskCacheNotAvailable,
+    OfflineDiskCache,
+
+    SyncLocalFile,
+
+    TransferLocalFile,
+    TransferNotRetriable,
+    TransferNotOpenable,
+    TransferRemoteFileNotFound,
+    TransferRemoteFilesEmpty,
+    TransferNotFound,
+    TransferAlreadyExists,
+    TransferIO,
+    TransferAborted,
+
+    AutoUploadInvalidGlob,
+    AutoUploadWatch,
+}
+
+impl ErrorCode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Unknown => "unknown",
+            Self::Canceled => "canceled",
+            Self::Storage => "storage",
+            Self::InvalidPath => "invalid_path",
+            Self::InvalidName => "invalid_name",
+            Self::LocaleNotFound { .. } => "locale.not_found",
+
+            Self::HttpResponse => "http.response",
+            Self::RemoteQuotaExceeded => "remote.quota_exceeded",
+            Self::RemoteApiError { .. } => "remote.api_error",
+            Self::RemoteUnexpectedStatus { .. } => "remote.unexpected_status",
+
+            Self::OAuth2InvalidToken => "oauth2.invalid_token",
+            Self::OAuth2InvalidState => "oauth2.invalid_state",
+            Self::OAuth2InvalidGrant => "oauth2.invalid_grant",
+            Self::OAuth2AuthorizationPending => "oauth2.authorization_pending",
+            Self::OAuth2SlowDown => "oauth2.slow_down",
+            Self::OAuth2AccessDenied => "oauth2.access_denied",
+            Self::OAuth2DeviceCodeExpired => "oauth2.device_code_expired",
+            Self::OAuth2Unknown => "oauth2.unknown",
+
+            Self::AccountNotFound => "account.not_found",
+            Self::AccountActive => "account.active",
+
+            Self::AuditLogChainBroken { .. } => "audit_log.chain_broken",
+            Self::AuditLogHeadMismatch => "audit_log.head_mismatch",
+
+            Self::RepoNotFound => "repo.not_found",
+            Self::RepoLocked => "repo.locked",
+            Self::RepoUnlocked => "repo.unlocked",
+            Self::RepoReadOnly => "repo.read_only",
+            Self::RepoInvalidPassword => "repo.invalid_password",
+            Self::RepoAlreadyExists => "repo.already_exists",
+            Self::RepoLimitExceeded => "repo.limit_exceeded",
+            Self::RepoTooManyAttempts { .. } => "repo.too_many_attempts",
+
+            Self::CipherDecryptSize => "cipher.decrypt_size",
+            Self::CipherDecodeName => "cipher.decode_name",
+            Self::CipherDecryptName => "cipher.decrypt_name",
+            Self::CipherNameUnicode => "cipher.name_unicode",
+            Self::CipherDecryptData => "cipher.decrypt_data",
+
+            Self::FileNotFound => "file.not_found",
+            Self::FileAlreadyExists => "file.already_exists",
+            Self::FileDirAlreadyExists => "file.dir_already_exists",
+            Self::FileFilesEmpty => "file.files_empty",
+            Self::FileRenameRoot => "file.rename_root",
+            Self::FileMoveRoot => "file.move_root",
+            Self::FileRangeNotSatisfiable => "file.range_not_satisfiable",
+            Self::FileIO => "file.io",
+            Self::FileAborted => "file.aborted",
+
+            Self::DetailsAlreadyLoading => "details.already_loading",
+            Self::DetailsLoadFilterMismatch => "details.load_filter_mismatch",
+            Self::DetailsAlreadySaving => "details.already_saving",
+            Self::DetailsNotDirty => "details.not_dirty",
+            Self::DetailsInvalidState => "details.invalid_state",
+            Self::DetailsAutosaveNotPossible => "details.autosave_not_possible",
+            Self::DetailsDiscardChanges => "details.discard_changes",
+            Self::DetailsCannotSaveRoot => "details.cannot_save_root",
+
+            Self::TagsBase64 => "tags.base64",
+            Self::TagsDecrypt => "tags.decrypt",
+            Self::TagsDeserialize => "tags.deserialize",
+            Self::TagsSerialize => "tags.serialize",
+            Self::TagsEncrypt => "tags.encrypt",
+            Self::TagsMultipleValues => "tags.multiple_values",
+            Self::TagsHashMismatch => "tags.hash_mismatch",
+            Self::TagsSet => "tags.set",
+
+            Self::ThumbnailDecode => "thumbnail.decode",
+            Self::ThumbnailEncode => "thumbnail.encode",
+            Self::ThumbnailNotSupported => "thumbnail.not_supported",
+            Self::ThumbnailEncrypt => "thumbnail.encrypt",
+            Self::ThumbnailDecrypt => "thumbnail.decrypt",
+
+            Self::VersionsNotAFile => "versions.not_a_file",
+            Self::VersionNotFound => "versions.not_found",
+
+            Self::RenameInvalidPattern => "rename.invalid_pattern",
+
+            Self::OfflineDiskCacheNotAvailable => "offline.disk_cache_not_available",
+            Self::OfflineDiskCache => "offline.disk_cache",
+
+            Self::SyncLocalFile => "sync.local_file",
+
+            Self::TransferLocalFile => "transfer.local_file",
+            Self::TransferNotRetriable => "transfer.not_retriable",
+            Self::TransferNotOpenable => "transfer.not_openable",
+            Self::TransferRemoteFileNotFound => "transfer.remote_file_not_found",
+            Self::TransferRemoteFilesEmpty => "transfer.remote_files_empty",
+            Self::TransferNotFound => "transfer.not_found",
+            Self::TransferAlreadyExists => "transfer.already_exists",
+            Self::TransferIO => "transfer.io",
+            Self::TransferAborted => "transfer.aborted",
+
+            Self::AutoUploadInvalidGlob => "auto_upload.invalid_glob",
+            Self::AutoUploadWatch => "auto_upload.watch",
+        }
+    }
+
+    /// Structured details for codes that carry data, keyed in camelCase.
+    pub fn details(&self) -> BTreeMap<String, String> {
+        let mut details = BTreeMap::new();
+
+        match self {
+            Self::LocaleNotFound { name } => {
+                details.insert("name".into(), name.clone());
+            }
+            Self::RemoteApiError { api_code } => {
+                details.insert("apiCode".into(), api_code.clone());
+            }
+            Self::RemoteUnexpectedStatus { status_code } => {
+                details.insert("statusCode".into(), status_code.to_string());
+            }
+            Self::RepoTooManyAttempts {
+                retry_after_seconds,
+            } => {
+                details.insert("retryAfterSeconds".into(), retry_after_seconds.to_string());
+            }
+            Self::AuditLogChainBroken { seq } => {
+                details.insert("seq".into(), seq.to_string());
+            }
+            _ => {}
+        }
+
+        details
+    }
+}
+
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::{
+        remote::{ApiErrorCode, RemoteError},
+        repos::errors::{LockRepoError, RepoLockedError},
+        user_error::UserError,
+    };
+
+    use super::ErrorCode;
+
+    #[test]
+    fn test_error_code() {
+        assert_eq!(
+            LockRepoError::RepoLocked(RepoLockedError).error_code(),
+            ErrorCode::RepoLocked
+        );
+        assert_eq!(ErrorCode::RepoLocked.as_str(), "repo.locked");
+        assert_eq!(ErrorCode::RepoLocked.details(), BTreeMap::new());
+
+        let code = RemoteError::ApiError {
+            code: ApiErrorCode::Conflict,
+            message: "Conflict".into(),
+            request_id: None,
+            extra: None,
+            status_code: Some(409),
+        }
+        .error_code();
+
+        assert_eq!(code.as_str(), "remote.api_error");
+        assert_eq!(
+            code.details(),
+            BTreeMap::from([("apiCode".to_owned(), "Conflict".to_owned())])
+        );
+
+        let code = RemoteError::UnexpectedStatus {
+            status_code: 502,
+            message: "Bad Gateway".into(),
+        }
+        .error_code();
+
+        assert_eq!(code.as_str(), "remote.unexpected_status");
+        assert_eq!(
+            code.details(),
+            BTreeMap::from([("statusCode".to_owned(), "502".to_owned())])
+        );
+    }
+}
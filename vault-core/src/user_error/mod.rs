@@ -1,5 +1,11 @@
+pub mod error_code;
+
+pub use self::error_code::ErrorCode;
+
 pub trait UserError {
     fn user_error(&self) -> String;
+
+    fn error_code(&self) -> ErrorCode;
 }
 
 pub struct StringUserError(pub String);
@@ -8,4 +14,8 @@ impl UserError for StringUserError {
     fn user_error(&self) -> String {
         self.0.clone()
     }
+
+    fn error_code(&self) -> ErrorCode {
+        ErrorCode::Unknown
+    }
 }
@@ -13,3 +13,4 @@ pub mod remote_path_utils;
 pub mod repo_encrypted_path_utils;
 pub mod repo_path_utils;
 pub mod sender_writer;
+pub mod tee_reader;
@@ -0,0 +1,74 @@
+use futures::{
+    channel::mpsc,
+    ready,
+    task::{Context, Poll},
+    AsyncRead,
+};
+use pin_project_lite::pin_project;
+use std::pin::Pin;
+
+pin_project! {
+    /// TeeReader sends a copy of everything read from the inner reader to
+    /// the sender. If the inner reader fails or is dropped before the end, an
+    /// error is sent so that the receiving side does not see partial content
+    /// as complete.
+    pub struct TeeReader<R> {
+        #[pin]
+        inner: R,
+        sender: Option<mpsc::UnboundedSender<std::io::Result<Vec<u8>>>>,
+    }
+
+    impl<R> PinnedDrop for TeeReader<R> {
+        fn drop(this: Pin<&mut Self>) {
+            if let Some(sender) = this.project().sender.take() {
+                let _ = sender.unbounded_send(Err(std::io::Error::new(
+                    std::io::ErrorKind::Interrupted,
+                    "reader dropped before end",
+                )));
+            }
+        }
+    }
+}
+
+impl<R> TeeReader<R> {
+    pub fn new(inner: R, sender: mpsc::UnboundedSender<std::io::Result<Vec<u8>>>) -> Self {
+        Self {
+            inner,
+            sender: Some(sender),
+        }
+    }
+}
+
+impl<R: AsyncRead> AsyncRead for TeeReader<R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let this = self.project();
+
+        let res = ready!(this.inner.poll_read(cx, buf));
+
+        match &res {
+            Ok(0) => {
+                // dropping the sender ends the receiving stream
+                this.sender.take();
+            }
+            Ok(n) => {
+                if let Some(sender) = this.sender.as_ref() {
+                    if sender.unbounded_send(Ok(buf[..*n].to_vec())).is_err() {
+                        this.sender.take();
+                    }
+                }
+            }
+            Err(err) => {
+                if let Some(sender) = this.sender.take() {
+                    let _ = sender
+                        .unbounded_send(Err(std::io::Error::new(err.kind(), err.to_string())));
+                }
+            }
+        }
+
+        Poll::Ready(res)
+    }
+}
@@ -1,17 +1,18 @@
 use std::{sync::Arc, time::Duration};
 
-use futures::future::BoxFuture;
+use futures::{future::BoxFuture, stream::AbortRegistration};
 
 use crate::{
-    auth, config, dialogs, dir_pickers, eventstream, http, lifecycle, notifications, oauth2,
-    rclone, relative_time, remote, remote_files, remote_files_browsers, remote_files_dir_pickers,
-    repo_config_backup, repo_create, repo_files, repo_files_browsers, repo_files_details,
-    repo_files_dir_pickers, repo_files_list, repo_files_move, repo_files_read, repo_files_tags,
-    repo_locker, repo_remove, repo_space_usage, repo_unlock, repos, runtime, secure_storage, sort,
-    space_usage, store,
+    accounts, audit_log, auth, config, dialogs, dir_pickers, eventstream, http, lifecycle, locale,
+    metadata_cache, notifications, oauth2, panic_lock, rclone, relative_time, remote, remote_files,
+    remote_files_browsers, remote_files_dir_pickers, repo_config_backup, repo_create,
+    repo_duplicates, repo_files, repo_files_browsers, repo_files_details, repo_files_dir_pickers,
+    repo_files_list, repo_files_move, repo_files_offline, repo_files_read, repo_files_rename,
+    repo_files_tags, repo_files_thumbnails, repo_files_versions, repo_locker, repo_remove,
+    repo_space_usage, repo_unlock, repos, runtime, secure_storage, sort, space_usage, store, sync,
     transfers::{self, downloadable::BoxDownloadable},
     types::{DecryptedName, EncryptedPath, RepoFileId, RepoId, TimeMillis},
-    user,
+    user, user_error,
 };
 
 pub struct Vault {
@@ -25,7 +26,10 @@ pub struct Vault {
     pub auth_provider: Arc<Box<(dyn auth::AuthProvider + Send + Sync)>>,
     pub remote: Arc<remote::Remote>,
     pub user_service: Arc<user::UserService>,
+    pub accounts_service: Arc<accounts::AccountsService>,
+    pub audit_log_service: Arc<audit_log::AuditLogService>,
     pub eventstream_service: Arc<eventstream::EventStreamService>,
+    pub metadata_cache_service: Arc<metadata_cache::MetadataCacheService>,
     pub transfers_service: Arc<transfers::TransfersService>,
     pub remote_files_service: Arc<remote_files::RemoteFilesService>,
     pub remote_files_dir_pickers_service:
@@ -43,11 +47,18 @@ pub struct Vault {
     pub repo_files_read_service: Arc<repo_files_read::RepoFilesReadService>,
     pub repo_files_service: Arc<repo_files::RepoFilesService>,
     pub repo_files_dir_pickers_service: Arc<repo_files_dir_pickers::RepoFilesDirPickersService>,
+    pub repo_files_rename_service: Arc<repo_files_rename::RepoFilesRenameService>,
     pub repo_files_browsers_service: Arc<repo_files_browsers::RepoFilesBrowsersService>,
     pub repo_files_details_service: Arc<repo_files_details::RepoFilesDetailsService>,
     pub repo_files_move_service: Arc<repo_files_move::RepoFilesMoveService>,
+    pub repo_files_offline_service: Arc<repo_files_offline::RepoFilesOfflineService>,
+    pub repo_files_thumbnails_service: Arc<repo_files_thumbnails::RepoFilesThumbnailsService>,
+    pub repo_files_versions_service: Arc<repo_files_versions::RepoFilesVersionsService>,
+    pub repo_duplicates_service: Arc<repo_duplicates::RepoDuplicatesService>,
+    pub sync_service: Arc<sync::SyncService>,
     pub space_usage_service: Arc<space_usage::SpaceUsageService>,
     pub lifecycle_service: Arc<lifecycle::LifecycleService>,
+    pub panic_lock_service: Arc<panic_lock::PanicLockService>,
 }
 
 impl Vault {
@@ -92,6 +103,13 @@ impl Vault {
             auth_provider.clone(),
         ));
         let user_service = Arc::new(user::UserService::new(remote.clone(), store.clone()));
+        let accounts_service = accounts::AccountsService::new(
+            base_url.clone(),
+            http_client.clone(),
+            oauth2_service.clone(),
+            secure_storage_service.clone(),
+            store.clone(),
+        );
         let eventstream_service = eventstream::EventStreamService::new(
             base_url.clone(),
             eventstream_websocket_client,
@@ -99,9 +117,15 @@ impl Vault {
             store.clone(),
             runtime.clone(),
         );
+        let metadata_cache_service = metadata_cache::MetadataCacheService::new(
+            secure_storage_service.clone(),
+            store.clone(),
+        );
         let remote_files_service = Arc::new(remote_files::RemoteFilesService::new(
             remote.clone(),
+            accounts_service.clone(),
             dialogs_service.clone(),
+            metadata_cache_service.clone(),
             store.clone(),
         ));
         let remote_files_browsers_service =
@@ -114,15 +138,23 @@ impl Vault {
                 remote_files_service.clone(),
                 store.clone(),
             ));
+        let audit_log_service = Arc::new(audit_log::AuditLogService::new(
+            secure_storage_service.clone(),
+            store.clone(),
+            runtime.clone(),
+        ));
         let repos_service = Arc::new(repos::ReposService::new(
             remote.clone(),
             remote_files_service.clone(),
             secure_storage_service.clone(),
+            metadata_cache_service.clone(),
+            audit_log_service.clone(),
             store.clone(),
             runtime.clone(),
         ));
         let repo_locker_service = repo_locker::RepoLockerService::new(
             repos_service.clone(),
+            audit_log_service.clone(),
             store.clone(),
             runtime.clone(),
         );
@@ -134,9 +166,12 @@ impl Vault {
             repos_service.clone(),
             store.clone(),
         ));
-        let repo_config_backup_service = Arc::new(
-            repo_config_backup::RepoConfigBackupService::new(repos_service.clone(), store.clone()),
-        );
+        let repo_config_backup_service =
+            Arc::new(repo_config_backup::RepoConfigBackupService::new(
+                repos_service.clone(),
+                audit_log_service.clone(),
+                store.clone(),
+            ));
         let repo_space_usage_service = Arc::new(repo_space_usage::RepoSpaceUsageService::new(
             remote_files_service.clone(),
             store.clone(),
@@ -150,11 +185,20 @@ impl Vault {
             remote_files_service.clone(),
             store.clone(),
         ));
+        let repo_files_offline_service = repo_files_offline::RepoFilesOfflineService::new(
+            repos_service.clone(),
+            remote_files_service.clone(),
+            repo_files_list_service.clone(),
+            secure_storage_service.clone(),
+            store.clone(),
+            runtime.clone(),
+        );
         let repo_files_read_service = Arc::new(repo_files_read::RepoFilesReadService::new(
             repos_service.clone(),
             remote_files_service.clone(),
             repo_files_list_service.clone(),
             repo_files_tags_service.clone(),
+            repo_files_offline_service.clone(),
             store.clone(),
             runtime.clone(),
         ));
@@ -188,24 +232,63 @@ impl Vault {
             repo_files_dir_pickers_service.clone(),
             store.clone(),
         ));
+        let repo_files_rename_service = Arc::new(repo_files_rename::RepoFilesRenameService::new(
+            repos_service.clone(),
+            repo_files_service.clone(),
+            store.clone(),
+        ));
         let repo_files_browsers_service =
             Arc::new(repo_files_browsers::RepoFilesBrowsersService::new(
                 repo_files_service.clone(),
                 repo_files_read_service.clone(),
                 repo_files_move_service.clone(),
+                repo_files_rename_service.clone(),
+                store.clone(),
+                runtime.clone(),
+            ));
+        let repo_files_versions_service =
+            Arc::new(repo_files_versions::RepoFilesVersionsService::new(
+                repos_service.clone(),
+                remote_files_service.clone(),
+                repo_files_service.clone(),
+                repo_files_tags_service.clone(),
+                secure_storage_service.clone(),
                 store.clone(),
                 runtime.clone(),
             ));
+        let repo_duplicates_service = Arc::new(repo_duplicates::RepoDuplicatesService::new(
+            repo_files_service.clone(),
+            repo_files_list_service.clone(),
+            repo_files_read_service.clone(),
+            store.clone(),
+        ));
         let repo_files_details_service =
             Arc::new(repo_files_details::RepoFilesDetailsService::new(
                 repos_service.clone(),
                 repo_files_service.clone(),
                 repo_files_read_service.clone(),
+                repo_files_versions_service.clone(),
                 dialogs_service.clone(),
                 transfers_service.clone(),
                 store.clone(),
                 runtime.clone(),
             ));
+        let repo_files_thumbnails_service =
+            Arc::new(repo_files_thumbnails::RepoFilesThumbnailsService::new(
+                repos_service.clone(),
+                repo_files_read_service.clone(),
+                store.clone(),
+                runtime.clone(),
+            ));
+        let sync_service = Arc::new(sync::SyncService::new(
+            repos_service.clone(),
+            repo_files_service.clone(),
+            repo_files_list_service.clone(),
+            repo_files_read_service.clone(),
+            repo_files_versions_service.clone(),
+            transfers_service.clone(),
+            store.clone(),
+        ));
         let space_usage_service = Arc::new(space_usage::SpaceUsageService::new(
             remote.clone(),
             store.clone(),
@@ -218,9 +301,20 @@ impl Vault {
             repos_service.clone(),
             eventstream_service.clone(),
             space_usage_service.clone(),
+            metadata_cache_service.clone(),
+            repo_files_offline_service.clone(),
+            repo_files_thumbnails_service.clone(),
+            accounts_service.clone(),
+            audit_log_service.clone(),
             remote.clone(),
             store.clone(),
         );
+        let panic_lock_service = Arc::new(panic_lock::PanicLockService::new(
+            repos_service.clone(),
+            transfers_service.clone(),
+            repo_files_details_service.clone(),
+            lifecycle_service.clone(),
+        ));
 
         Self {
             store,
@@ -233,7 +327,10 @@ impl Vault {
             auth_provider,
             remote,
             user_service,
+            accounts_service,
+            audit_log_service,
             eventstream_service,
+            metadata_cache_service,
             transfers_service,
             remote_files_service,
             remote_files_dir_pickers_service,
@@ -250,11 +347,18 @@ impl Vault {
             repo_files_read_service,
             repo_files_service,
             repo_files_dir_pickers_service,
+            repo_files_rename_service,
             repo_files_browsers_service,
             repo_files_details_service,
             repo_files_move_service,
+            repo_files_offline_service,
+            repo_files_thumbnails_service,
+            repo_files_versions_service,
+            repo_duplicates_service,
+            sync_service,
             space_usage_service,
             lifecycle_service,
+            panic_lock_service,
         }
     }
 
@@ -276,7 +380,8 @@ impl Vault {
     where
         F: FnOnce(&store::State) -> R,
     {
-        self.store.with_state(f)
+        // user errors and sizes formatted from the state use the vault locale
+        locale::with_store_locale(&self.store, || self.store.with_state(f))
     }
 
     // subscription
@@ -308,6 +413,15 @@ impl Vault {
         self.lifecycle_service.app_hidden()
     }
 
+    // panic_lock
+
+    pub fn panic_lock(
+        &self,
+        wipe_secure_storage: bool,
+    ) -> Result<(), panic_lock::errors::PanicLockError> {
+        self.panic_lock_service.panic_lock(wipe_secure_storage)
+    }
+
     // relative_time
 
     pub fn relative_time(
@@ -325,12 +439,40 @@ impl Vault {
         })
     }
 
+    // locale
+
+    pub fn locale_get(&self) -> String {
+        self.with_state(|state| state.config.locale.name.clone())
+    }
+
+    pub fn locale_set(&self, name: &str) -> Result<(), locale::errors::LocaleNotFoundError> {
+        let locale = locale::get_locale(name)
+            .ok_or_else(|| locale::errors::LocaleNotFoundError(name.to_owned()))?;
+
+        self.store.mutate(|state, notify, _, _| {
+            config::mutations::set_locale(
+                state,
+                notify,
+                config::state::LocaleConfig {
+                    name: name.to_owned(),
+                    locale: Arc::new(locale),
+                },
+            );
+        });
+
+        Ok(())
+    }
+
     // notifications
 
     pub fn notifications_show(&self, message: String) {
         self.notifications_service.show(message)
     }
 
+    pub fn notifications_show_error(&self, error: &dyn user_error::UserError) {
+        self.notifications_service.show_error(error)
+    }
+
     pub fn notifications_remove(&self, notification_id: u32) {
         self.notifications_service.remove(notification_id)
     }
@@ -376,6 +518,22 @@ impl Vault {
         self.lifecycle_service.oauth2_finish_flow_url(url).await
     }
 
+    pub async fn oauth2_start_device_flow(
+        &self,
+    ) -> Result<oauth2::state::DeviceAuthorization, oauth2::errors::OAuth2Error> {
+        self.oauth2_service.start_device_flow().await
+    }
+
+    pub async fn oauth2_finish_device_flow(
+        &self,
+        device_authorization: &oauth2::state::DeviceAuthorization,
+        abort_registration: AbortRegistration,
+    ) -> Result<(), lifecycle::errors::OAuth2FinishDeviceFlowError> {
+        self.lifecycle_service
+            .oauth2_finish_device_flow(device_authorization, abort_registration)
+            .await
+    }
+
     // user
 
     pub async fn user_load(&self) -> Result<(), remote::RemoteError> {
@@ -386,6 +544,59 @@ impl Vault {
         self.user_service.ensure_profile_picture().await
     }
 
+    // accounts
+
+    pub fn accounts_switch(
+        &self,
+        user_id: &str,
+    ) -> BoxFuture<'static, Result<(), lifecycle::errors::SwitchAccountError>> {
+        let lifecycle_service = self.lifecycle_service.clone();
+        let user_id = user_id.to_owned();
+
+        Box::pin(async move { lifecycle_service.switch_account(&user_id).await })
+    }
+
+    pub fn accounts_add(&self) -> Result<String, accounts::errors::AccountsError> {
+        self.lifecycle_service.add_account()
+    }
+
+    pub fn accounts_remove(&self, user_id: &str) -> Result<(), accounts::errors::AccountsError> {
+        self.lifecycle_service.remove_account(user_id)
+    }
+
+    // audit_log
+
+    pub fn audit_log_set_disk_storage(
+        &self,
+        disk_storage: Option<Box<dyn audit_log::AuditLogDiskStorage + Send + Sync>>,
+    ) {
+        self.audit_log_service.set_disk_storage(disk_storage)
+    }
+
+    pub fn audit_log_entries(
+        &self,
+        filter: &audit_log::state::AuditLogFilter,
+    ) -> Vec<audit_log::state::AuditLogEntry> {
+        self.audit_log_service.entries(filter)
+    }
+
+    pub fn audit_log_export(&self) -> String {
+        self.audit_log_service.export()
+    }
+
+    pub fn audit_log_verify(&self) -> Result<(), audit_log::errors::VerifyAuditLogError> {
+        self.audit_log_service.verify()
+    }
+
+    // metadata_cache
+
+    pub fn metadata_cache_set_disk_cache(
+        &self,
+        disk_cache: Option<Box<dyn metadata_cache::MetadataDiskCache + Send + Sync>>,
+    ) {
+        self.metadata_cache_service.set_disk_cache(disk_cache)
+    }
+
     // remote_files_browsers
 
     pub fn remote_files_browsers_create(
@@ -490,6 +701,13 @@ impl Vault {
         self.repos_service.set_default_auto_lock(auto_lock)
     }
 
+    pub fn repos_set_unlock_lockout(
+        &self,
+        unlock_lockout: Option<repos::state::RepoUnlockLockout>,
+    ) {
+        self.repos_service.set_unlock_lockout(unlock_lockout)
+    }
+
     // repo_create
 
     pub fn repo_create_create(
@@ -682,6 +900,20 @@ impl Vault {
             .get_file_reader(repo_id, path)
     }
 
+    pub async fn repo_files_get_file_range_reader(
+        &self,
+        repo_id: &RepoId,
+        path: &EncryptedPath,
+        range: Option<&repo_files_read::state::RepoFileReadRange>,
+    ) -> Result<
+        repo_files_read::state::RepoFileRangeReader,
+        repo_files_read::errors::GetFilesReaderError,
+    > {
+        self.repo_files_service
+            .get_file_range_reader(repo_id, path, range)
+            .await
+    }
+
     pub async fn repo_files_delete_files(
         &self,
         files: &[(RepoId, EncryptedPath)],
@@ -862,6 +1094,31 @@ impl Vault {
             .await
     }
 
+    pub fn repo_files_browsers_rename_selected_preview(
+        &self,
+        browser_id: u32,
+        pattern: &repo_files_rename::state::RepoFilesRenamePattern,
+    ) -> Result<
+        Vec<repo_files_rename::state::RepoFilesRenamePreviewItem>,
+        repo_files_rename::errors::RenameFilesError,
+    > {
+        self.repo_files_browsers_service
+            .rename_selected_preview(browser_id, pattern)
+    }
+
+    pub async fn repo_files_browsers_rename_selected(
+        &self,
+        browser_id: u32,
+        pattern: &repo_files_rename::state::RepoFilesRenamePattern,
+    ) -> Result<
+        Vec<repo_files_rename::state::RepoFilesRenameResult>,
+        repo_files_rename::errors::RenameFilesError,
+    > {
+        self.repo_files_browsers_service
+            .rename_selected(browser_id, pattern)
+            .await
+    }
+
     // repo_files_details
 
     pub fn repo_files_details_create(
@@ -971,6 +1228,54 @@ impl Vault {
         self.repo_files_details_service.delete(details_id).await
     }
 
+    pub fn repo_files_details_conflict_text_merge(
+        &self,
+        details_id: u32,
+    ) -> Option<repo_files_details::state::RepoFilesDetailsTextMerge> {
+        self.store.with_state(|state| {
+            repo_files_details::selectors::select_conflict_text_merge(state, details_id).cloned()
+        })
+    }
+
+    pub async fn repo_files_details_conflict_keep_mine(
+        &self,
+        details_id: u32,
+    ) -> Result<(), repo_files_details::errors::SaveError> {
+        self.repo_files_details_service
+            .clone()
+            .conflict_keep_mine(details_id)
+            .await
+    }
+
+    pub async fn repo_files_details_conflict_merge(
+        &self,
+        details_id: u32,
+        content: Vec<u8>,
+    ) -> Result<(), repo_files_details::errors::SaveError> {
+        self.repo_files_details_service
+            .clone()
+            .conflict_merge(details_id, Some(content))
+            .await
+    }
+
+    pub fn repo_files_details_conflict_keep_theirs(
+        &self,
+        details_id: u32,
+    ) -> Result<(), repo_files_details::errors::SaveError> {
+        self.repo_files_details_service
+            .conflict_keep_theirs(details_id)
+    }
+
+    pub async fn repo_files_details_conflict_save_as_copy(
+        &self,
+        details_id: u32,
+    ) -> Result<EncryptedPath, repo_files_details::errors::SaveError> {
+        self.repo_files_details_service
+            .clone()
+            .conflict_save_as_copy(details_id)
+            .await
+    }
+
     // repo_files_move
 
     pub async fn repo_files_move_move_file(
@@ -1013,6 +1318,209 @@ impl Vault {
     ) -> Result<(), repo_files::errors::CreateDirError> {
         self.repo_files_move_service.create_dir().await
     }
+
+    // repo_files_offline
+
+    pub fn repo_files_offline_set_disk_cache(
+        &self,
+        disk_cache: Option<Box<dyn repo_files_offline::OfflineDiskCache + Send + Sync>>,
+    ) {
+        self.repo_files_offline_service.set_disk_cache(disk_cache)
+    }
+
+    pub async fn repo_files_offline_pin(
+        &self,
+        repo_id: RepoId,
+        path: EncryptedPath,
+    ) -> Result<(), repo_files_offline::errors::OfflineError> {
+        self.repo_files_offline_service.pin(repo_id, path).await
+    }
+
+    pub async fn repo_files_offline_unpin(
+        &self,
+        repo_id: &RepoId,
+        path: &EncryptedPath,
+    ) -> Result<(), repo_files_offline::errors::OfflineError> {
+        self.repo_files_offline_service.unpin(repo_id, path).await
+    }
+
+    pub fn repo_files_offline_is_pinned(&self, repo_id: &RepoId, path: &EncryptedPath) -> bool {
+        self.repo_files_offline_service.is_pinned(repo_id, path)
+    }
+
+    pub async fn repo_files_offline_refresh(
+        &self,
+    ) -> Result<(), repo_files_offline::errors::OfflineError> {
+        self.repo_files_offline_service.refresh().await
+    }
+
+    pub async fn repo_files_offline_set_quota(
+        &self,
+        quota: i64,
+    ) -> Result<(), repo_files_offline::errors::OfflineError> {
+        self.repo_files_offline_service.set_quota(quota).await
+    }
+
+    pub fn repo_files_offline_get_info(&self) -> repo_files_offline::state::OfflineInfo {
+        self.repo_files_offline_service.get_info()
+    }
+
+    pub async fn repo_files_offline_clear(
+        &self,
+    ) -> Result<(), repo_files_offline::errors::OfflineError> {
+        self.repo_files_offline_service.clear().await
+    }
+
+    // repo_files_thumbnails
+
+    pub fn repo_files_thumbnails_set_disk_cache(
+        &self,
+        disk_cache: Option<Box<dyn repo_files_thumbnails::ThumbnailsDiskCache + Send + Sync>>,
+    ) {
+        self.repo_files_thumbnails_service
+            .set_disk_cache(disk_cache)
+    }
+
+    pub fn repo_files_thumbnails_ensure(&self, file_ids: Vec<RepoFileId>) {
+        self.repo_files_thumbnails_service
+            .clone()
+            .ensure_thumbnails(file_ids)
+    }
+
+    pub async fn repo_files_thumbnails_get(
+        &self,
+        file_id: RepoFileId,
+    ) -> Result<
+        repo_files_thumbnails::state::RepoFileThumbnailBytes,
+        repo_files_thumbnails::errors::GetThumbnailError,
+    > {
+        self.repo_files_thumbnails_service
+            .clone()
+            .get_thumbnail(file_id)
+            .await
+    }
+
+    // repo_files_versions
+
+    pub fn repo_files_versions_get_settings(
+        &self,
+        repo_id: &RepoId,
+    ) -> Result<
+        repo_files_versions::state::RepoFilesVersionsSettings,
+        secure_storage::errors::SecureStorageError,
+    > {
+        self.repo_files_versions_service.get_settings(repo_id)
+    }
+
+    pub fn repo_files_versions_set_settings(
+        &self,
+        repo_id: &RepoId,
+        settings: repo_files_versions::state::RepoFilesVersionsSettings,
+    ) -> Result<(), secure_storage::errors::SecureStorageError> {
+        self.repo_files_versions_service
+            .set_settings(repo_id, settings)
+    }
+
+    pub async fn repo_files_versions_list(
+        &self,
+        repo_id: &RepoId,
+        path: &EncryptedPath,
+    ) -> Result<
+        Vec<repo_files_versions::state::RepoFileVersion>,
+        repo_files_versions::errors::ListVersionsError,
+    > {
+        self.repo_files_versions_service
+            .list_versions(repo_id, path)
+            .await
+    }
+
+    pub fn repo_files_versions_get_reader(
+        &self,
+        repo_id: &RepoId,
+        version_path: &EncryptedPath,
+    ) -> Result<
+        repo_files_read::state::RepoFileReaderProvider,
+        repo_files_read::errors::GetFilesReaderError,
+    > {
+        self.repo_files_versions_service
+            .clone()
+            .get_version_reader(repo_id, version_path)
+    }
+
+    pub async fn repo_files_versions_restore(
+        &self,
+        repo_id: &RepoId,
+        path: &EncryptedPath,
+        version_path: &EncryptedPath,
+    ) -> Result<(), repo_files_versions::errors::RestoreVersionError> {
+        self.repo_files_versions_service
+            .clone()
+            .restore_version(repo_id, path, version_path)
+            .await
+    }
+
+    pub async fn repo_files_versions_prune(
+        &self,
+        repo_id: &RepoId,
+        path: &EncryptedPath,
+    ) -> Result<(), repo_files_versions::errors::PruneVersionsError> {
+        self.repo_files_versions_service
+            .prune_versions(repo_id, path)
+            .await
+    }
+
+    // repo_duplicates
+
+    pub fn repo_duplicates_create(&self, repo_id: RepoId) -> u32 {
+        self.repo_duplicates_service.create(repo_id)
+    }
+
+    pub async fn repo_duplicates_scan(
+        &self,
+        duplicates_id: u32,
+    ) -> Result<(), repo_duplicates::errors::RepoDuplicatesError> {
+        self.repo_duplicates_service.scan(duplicates_id).await
+    }
+
+    pub async fn repo_duplicates_delete_all_but_newest(
+        &self,
+        duplicates_id: u32,
+        hashes: Option<Vec<String>>,
+    ) -> Result<(), repo_files::errors::DeleteFileError> {
+        self.repo_duplicates_service
+            .delete_all_but_newest(duplicates_id, hashes)
+            .await
+    }
+
+    pub fn repo_duplicates_destroy(&self, duplicates_id: u32) {
+        self.repo_duplicates_service.destroy(duplicates_id)
+    }
+
+    // sync
+
+    pub async fn sync_plan(
+        &self,
+        config: &sync::state::SyncConfig,
+        local: &sync::local::BoxSyncLocal,
+    ) -> Result<sync::state::SyncPlan, sync::errors::SyncError> {
+        self.sync_service.plan(config, local).await
+    }
+
+    pub async fn sync_run(
+        &self,
+        config: &sync::state::SyncConfig,
+        local: &sync::local::BoxSyncLocal,
+    ) -> Result<sync::state::SyncResult, sync::errors::SyncError> {
+        self.sync_service.run(config, local).await
+    }
+
+    pub async fn sync_reset(
+        &self,
+        config: &sync::state::SyncConfig,
+        local: &sync::local::BoxSyncLocal,
+    ) -> Result<(), sync::errors::SyncError> {
+        self.sync_service.reset(config, local).await
+    }
 }
 
 const _: () = {
@@ -4,9 +4,10 @@ use std::{
     sync::Arc,
 };
 use xsalsa20poly1305::XSalsa20Poly1305;
+use zeroize::Zeroizing;
 
 use super::{
-    cipher_keys::{derive_keys, DerivedKeys},
+    cipher_keys::derive_keys,
     constants::{DATA_KEY_LEN, NAME_CIPHER_BLOCK_SIZE, NAME_KEY_LEN},
     data_cipher::get_data_cipher,
     decrypt_reader::{AsyncDecryptReader, SyncDecryptReader},
@@ -18,61 +19,59 @@ use super::{
     nonce::Nonce,
 };
 
+/// Key material is zeroized when the cipher is dropped (`XSalsa20Poly1305`
+/// zeroizes its own key).
 pub struct Cipher {
-    name_key: [u8; NAME_KEY_LEN],
-    name_tweak: [u8; NAME_CIPHER_BLOCK_SIZE],
+    name_key: Zeroizing<[u8; NAME_KEY_LEN]>,
+    name_tweak: Zeroizing<[u8; NAME_CIPHER_BLOCK_SIZE]>,
     data_cipher: Arc<XSalsa20Poly1305>,
 }
 
 impl Cipher {
     pub fn new(password: &str, salt: Option<&str>) -> Self {
-        let DerivedKeys {
-            data_key,
-            name_key,
-            name_tweak,
-        } = derive_keys(password, salt);
+        let keys = derive_keys(password, salt);
 
-        Self::with_keys(data_key, name_key, name_tweak)
+        Self::with_keys(&keys.data_key, &keys.name_key, &keys.name_tweak)
     }
 
     pub fn with_keys(
-        data_key: [u8; DATA_KEY_LEN],
-        name_key: [u8; NAME_KEY_LEN],
-        name_tweak: [u8; NAME_CIPHER_BLOCK_SIZE],
+        data_key: &[u8; DATA_KEY_LEN],
+        name_key: &[u8; NAME_KEY_LEN],
+        name_tweak: &[u8; NAME_CIPHER_BLOCK_SIZE],
     ) -> Self {
-        let data_cipher = get_data_cipher(&data_key);
+        let data_cipher = get_data_cipher(data_key);
 
         Self {
-            name_key,
-            name_tweak,
+            name_key: Zeroizing::new(*name_key),
+            name_tweak: Zeroizing::new(*name_tweak),
             data_cipher: Arc::new(data_cipher),
         }
     }
 
     pub fn encrypt_filename(&self, plaintext: &str) -> String {
         encrypt_filename(
-            get_name_cipher(&self.name_key, &self.name_tweak),
+            get_name_cipher(&*self.name_key, &*self.name_tweak),
             &plaintext,
         )
     }
 
     pub fn encrypt_path(&self, plaintext: &str) -> String {
         encrypt_path(
-            get_name_cipher(&self.name_key, &self.name_tweak),
+            get_name_cipher(&*self.name_key, &*self.name_tweak),
             &plaintext,
         )
     }
 
     pub fn decrypt_filename(&self, ciphertext: &str) -> Result<String, DecryptFilenameError> {
         decrypt_filename(
-            get_name_cipher(&self.name_key, &self.name_tweak),
+            get_name_cipher(&*self.name_key, &*self.name_tweak),
             &ciphertext,
         )
     }
 
     pub fn decrypt_path(&self, ciphertext: &str) -> Result<String, DecryptFilenameError> {
         decrypt_path(
-            get_name_cipher(&self.name_key, &self.name_tweak),
+            get_name_cipher(&*self.name_key, &*self.name_tweak),
             &ciphertext,
         )
     }
@@ -103,6 +102,32 @@ impl Cipher {
         SyncDecryptReader::new(reader, self.data_cipher.clone())
     }
 
+    /// Decrypts blocks starting at `block_index` of a file with `nonce`
+    /// (see data_cipher::encrypted_range).
+    pub fn decrypt_blocks_reader_async<R>(
+        &self,
+        reader: R,
+        nonce: &Nonce,
+        block_index: u64,
+    ) -> AsyncDecryptReader<R> {
+        let mut nonce = nonce.clone();
+        nonce.add(block_index);
+
+        AsyncDecryptReader::new_with_nonce(reader, self.data_cipher.clone(), nonce)
+    }
+
+    pub fn decrypt_blocks_reader_sync<R>(
+        &self,
+        reader: R,
+        nonce: &Nonce,
+        block_index: u64,
+    ) -> SyncDecryptReader<R> {
+        let mut nonce = nonce.clone();
+        nonce.add(block_index);
+
+        SyncDecryptReader::new_with_nonce(reader, self.data_cipher.clone(), nonce)
+    }
+
     pub fn decrypt_data(&self, data: &[u8], out: &mut Vec<u8>) -> Result<usize, std::io::Error> {
         let reader = Cursor::new(data);
 
@@ -112,6 +137,13 @@ impl Cipher {
 
 #[cfg(test)]
 mod tests {
+    use std::io::Read;
+
+    use crate::{
+        constants::{BLOCK_DATA_SIZE, FILE_HEADER_SIZE},
+        data_cipher::{encrypted_range, parse_file_header},
+    };
+
     use super::Cipher;
 
     #[test]
@@ -303,4 +335,36 @@ mod tests {
 
         assert_eq!(std::str::from_utf8(&decrypted).unwrap(), "testdata");
     }
+
+    #[test]
+    fn test_decrypt_blocks() {
+        let cipher = Cipher::new("testpassword", None);
+
+        let data = (0..(3 * BLOCK_DATA_SIZE + 100))
+            .map(|i| (i % 251) as u8)
+            .collect::<Vec<_>>();
+
+        let mut encrypted = Vec::new();
+        cipher.encrypt_data(&data, &mut encrypted).unwrap();
+
+        let nonce = parse_file_header(&encrypted[..FILE_HEADER_SIZE]).unwrap();
+
+        let (start, end) = (BLOCK_DATA_SIZE as i64 + 10, 3 * BLOCK_DATA_SIZE as i64 + 20);
+        let range = encrypted_range(start, end, data.len() as i64);
+
+        let mut decrypted = Vec::new();
+        cipher
+            .decrypt_blocks_reader_sync(
+                &encrypted[range.start as usize..=range.end as usize],
+                &nonce,
+                range.block_index,
+            )
+            .read_to_end(&mut decrypted)
+            .unwrap();
+
+        assert_eq!(
+            &decrypted[range.skip as usize..range.skip as usize + (end - start + 1) as usize],
+            &data[start as usize..=end as usize]
+        );
+    }
 }
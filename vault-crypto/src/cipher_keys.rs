@@ -1,4 +1,5 @@
 use scrypt::{scrypt, ScryptParams};
+use zeroize::{Zeroize, Zeroizing};
 
 use super::constants::{DATA_KEY_LEN, DEFAULT_SALT, KEY_LEN, NAME_CIPHER_BLOCK_SIZE, NAME_KEY_LEN};
 
@@ -8,6 +9,14 @@ pub struct DerivedKeys {
     pub name_tweak: [u8; NAME_CIPHER_BLOCK_SIZE],
 }
 
+impl Drop for DerivedKeys {
+    fn drop(&mut self) {
+        self.data_key.zeroize();
+        self.name_key.zeroize();
+        self.name_tweak.zeroize();
+    }
+}
+
 pub fn derive_keys(password: &str, salt: Option<&str>) -> DerivedKeys {
     // hardcode derived keys for password "password" and salt "salt" to speed up
     // development and testing. with this speedup debug builds become usable.
@@ -39,13 +48,13 @@ pub fn derive_keys(password: &str, salt: Option<&str>) -> DerivedKeys {
     // https://github.com/rclone/rclone/blob/7be9855a706d1e09504f17949a90c54cd56fb2a5/backend/crypt/cipher.go#L219
     let log_n = 14; // log2 16384
     let scrypt_params = ScryptParams::new(log_n, 8, 1).unwrap();
-    let mut scrypt_output: [u8; KEY_LEN] = [0; KEY_LEN];
+    let mut scrypt_output = Zeroizing::new([0u8; KEY_LEN]);
 
     scrypt(
         password_bytes,
         salt_bytes,
         &scrypt_params,
-        &mut scrypt_output,
+        &mut *scrypt_output,
     )
     .unwrap();
 
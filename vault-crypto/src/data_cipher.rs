@@ -4,7 +4,10 @@ use xsalsa20poly1305::aead::{AeadInPlace, KeyInit};
 pub use xsalsa20poly1305::XSalsa20Poly1305;
 
 use super::{
-    constants::{BLOCK_DATA_SIZE, BLOCK_HEADER_SIZE, BLOCK_SIZE, FILE_HEADER_SIZE},
+    constants::{
+        BLOCK_DATA_SIZE, BLOCK_HEADER_SIZE, BLOCK_SIZE, FILE_HEADER_SIZE, FILE_MAGIC,
+        FILE_MAGIC_SIZE,
+    },
     errors::DecryptSizeError,
     nonce::Nonce,
     CipherError,
@@ -77,3 +80,92 @@ pub fn decrypt_size(size: i64) -> Result<i64, DecryptSizeError> {
 
     Ok(decrypted_size)
 }
+
+/// parse_file_header returns the file nonce from the encrypted file header
+pub fn parse_file_header(header: &[u8]) -> Result<Nonce, CipherError> {
+    if header.len() < FILE_HEADER_SIZE {
+        return Err(CipherError::EncryptedFileTooShort);
+    }
+    if &header[..FILE_MAGIC_SIZE] != FILE_MAGIC {
+        return Err(CipherError::EncryptedBadMagic);
+    }
+
+    Ok(Nonce::new(
+        header[FILE_MAGIC_SIZE..FILE_HEADER_SIZE]
+            .try_into()
+            .unwrap(),
+    ))
+}
+
+/// Inclusive range of encrypted bytes that have to be read to decrypt the
+/// inclusive range of decrypted bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncryptedRange {
+    /// index of the first block in the range
+    pub block_index: u64,
+    pub start: i64,
+    pub end: i64,
+    /// number of decrypted bytes in the first block before the range start
+    pub skip: i64,
+}
+
+/// encrypted_range calculates the encrypted blocks range for the decrypted
+/// range start..=end of a file with decrypted size `size`
+pub fn encrypted_range(start: i64, end: i64, size: i64) -> EncryptedRange {
+    let first_block = start / BLOCK_DATA_SIZE as i64;
+    let last_block = end / BLOCK_DATA_SIZE as i64;
+
+    let encrypted_start = FILE_HEADER_SIZE as i64 + first_block * BLOCK_SIZE as i64;
+    let encrypted_end =
+        (FILE_HEADER_SIZE as i64 + (last_block + 1) * BLOCK_SIZE as i64).min(encrypted_size(size));
+
+    EncryptedRange {
+        block_index: first_block as u64,
+        start: encrypted_start,
+        end: encrypted_end - 1,
+        skip: start - first_block * BLOCK_DATA_SIZE as i64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::constants::{BLOCK_DATA_SIZE, BLOCK_SIZE, FILE_HEADER_SIZE};
+
+    use super::{encrypted_range, EncryptedRange};
+
+    #[test]
+    fn test_encrypted_range() {
+        let data_size = BLOCK_DATA_SIZE as i64;
+        let header_size = FILE_HEADER_SIZE as i64;
+        let block_size = BLOCK_SIZE as i64;
+        let size = 3 * data_size + 100;
+
+        assert_eq!(
+            encrypted_range(0, 9, size),
+            EncryptedRange {
+                block_index: 0,
+                start: header_size,
+                end: header_size + block_size - 1,
+                skip: 0,
+            }
+        );
+        assert_eq!(
+            encrypted_range(data_size + 10, 2 * data_size, size),
+            EncryptedRange {
+                block_index: 1,
+                start: header_size + block_size,
+                end: header_size + 3 * block_size - 1,
+                skip: 10,
+            }
+        );
+        assert_eq!(
+            encrypted_range(3 * data_size + 50, size - 1, size),
+            EncryptedRange {
+                block_index: 3,
+                start: header_size + 3 * block_size,
+                end: header_size + 3 * block_size + 16 + 100 - 1,
+                skip: 50,
+            }
+        );
+    }
+}
@@ -59,6 +59,20 @@ impl<R> SyncDecryptReader<R> {
             data_cipher,
         }
     }
+
+    /// Reader for the ciphertext without the file header. `nonce` is the
+    /// nonce of the first block.
+    pub fn new_with_nonce(inner: R, data_cipher: Arc<XSalsa20Poly1305>, nonce: Nonce) -> Self {
+        Self {
+            inner,
+            state: DecryptReaderState::ReadingCiphertext {
+                nonce,
+                buffer: vec![0; BLOCK_SIZE],
+                pos: 0,
+            },
+            data_cipher,
+        }
+    }
 }
 
 impl<R: Read> Read for SyncDecryptReader<R> {
@@ -177,6 +191,20 @@ impl<R> AsyncDecryptReader<R> {
             data_cipher,
         }
     }
+
+    /// Reader for the ciphertext without the file header. `nonce` is the
+    /// nonce of the first block.
+    pub fn new_with_nonce(inner: R, data_cipher: Arc<XSalsa20Poly1305>, nonce: Nonce) -> Self {
+        Self {
+            inner,
+            state: DecryptReaderState::ReadingCiphertext {
+                nonce,
+                buffer: vec![0; BLOCK_SIZE],
+                pos: 0,
+            },
+            data_cipher,
+        }
+    }
 }
 
 impl<R: AsyncRead> AsyncRead for AsyncDecryptReader<R> {
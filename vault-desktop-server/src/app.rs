@@ -15,6 +15,8 @@ use crate::{
     handlers::register_routes,
     request_encryption::{encryption_middleware, EncryptionMiddlewareState},
     sessions::Sessions,
+    signed_urls::{SignedUrls, DEFAULT_SIGNED_URL_TTL},
+    webdav::{self, credentials::WebDavCredentials, server::WebDavInfo},
 };
 
 pub async fn app(
@@ -23,8 +25,34 @@ pub async fn app(
     tokio_runtime: Arc<tokio::runtime::Runtime>,
     encryption: Arc<Encryption>,
     file_handlers: Arc<FileHandlers>,
+    webdav_port: Option<u16>,
 ) {
     let sessions = Arc::new(Sessions::new());
+    let signed_urls = Arc::new(SignedUrls::new(DEFAULT_SIGNED_URL_TTL).unwrap());
+
+    let webdav_info = match webdav_port {
+        Some(webdav_port) => match webdav::server::bind(webdav_port).await {
+            Ok(listener) => {
+                let credentials = Arc::new(WebDavCredentials::random());
+                let webdav_info = WebDavInfo::new(webdav_port, &credentials);
+                let vault = web_vault.vault.clone();
+
+                tokio::spawn(async move {
+                    if let Err(err) = webdav::server::serve(listener, vault, credentials).await {
+                        log::warn!("WebDAV server failed: {}", err);
+                    }
+                });
+
+                Some(webdav_info)
+            }
+            Err(err) => {
+                log::warn!("WebDAV is not available: {}", err);
+
+                None
+            }
+        },
+        None => None,
+    };
 
     let app_state = AppState {
         base: Arc::new(web_vault),
@@ -32,6 +60,8 @@ pub async fn app(
         encryption,
         sessions,
         file_handlers,
+        signed_urls,
+        webdav_info,
     };
 
     let app = register_routes(Router::new())
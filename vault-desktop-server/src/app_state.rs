@@ -2,7 +2,10 @@ use std::sync::Arc;
 
 use vault_web_api::web_vault_base::WebVaultBase;
 
-use crate::{encryption::Encryption, file_handlers::FileHandlers, sessions::Sessions};
+use crate::{
+    encryption::Encryption, file_handlers::FileHandlers, sessions::Sessions,
+    signed_urls::SignedUrls, webdav::server::WebDavInfo,
+};
 
 #[derive(Clone)]
 pub struct AppState {
@@ -11,4 +14,6 @@ pub struct AppState {
     pub encryption: Arc<Encryption>,
     pub sessions: Arc<Sessions>,
     pub file_handlers: Arc<FileHandlers>,
+    pub signed_urls: Arc<SignedUrls>,
+    pub webdav_info: Option<WebDavInfo>,
 }
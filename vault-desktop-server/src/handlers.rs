@@ -5,7 +5,7 @@ use axum::{
     extract::{Query, State},
     http::{
         header::{self, CONTENT_TYPE},
-        StatusCode,
+        HeaderMap, StatusCode,
     },
     response::{sse::Event, IntoResponse, Response, Sse},
     routing::{get, post},
@@ -18,19 +18,19 @@ use thiserror::Error;
 
 use vault_core::{
     common::state::SizeInfo,
-    repo_files_read,
+    repo_files, repo_files_read,
     transfers::{
         self, downloadable,
         errors::{DownloadableError, TransferError},
     },
-    types::{EncryptedPath, RepoId},
+    types::{EncryptedPath, RepoFileId, RepoId},
     user_error::StringUserError,
     utils::reader_stream::ReaderStream,
 };
 use vault_crypto::constants::BLOCK_SIZE;
 use vault_native::transfers::{
     file_uploadable::FileUploadable, pick_file_downloadable::PickFileDownloadable,
-    temp_file_downloadable::TempFileDownloadable,
+    temp_file_downloadable::TempFileDownloadable, upload_helper,
 };
 use vault_web_api::{dto, web_vault_base::WebVaultBase};
 
@@ -38,7 +38,9 @@ use crate::{
     app_state::AppState,
     callbacks::CallbackId,
     extract::{ExtractBase, ExtractCallbacks, ExtractSessions},
-    upload_helper,
+    http_range,
+    signed_urls::{SignedUrlQuery, REPO_FILES_STREAM_PATH},
+    webdav::server::WebDavInfo,
 };
 
 #[derive(Error, Debug, Clone, PartialEq)]
@@ -69,7 +71,13 @@ pub fn register_routes(router: Router<AppState>) -> Router<AppState> {
         .route("/WebVault/logout", post(logout))
         .route("/WebVault/appVisible", post(app_visible))
         .route("/WebVault/appHidden", post(app_hidden))
+        .route("/WebVault/panicLock", post(panic_lock))
         .route("/WebVault/relativeTime", post(relative_time))
+        .route("/WebVault/localeGet", post(locale_get))
+        .route("/WebVault/localeSet", post(locale_set))
+        .route("/WebVault/auditLogEntries", post(audit_log_entries))
+        .route("/WebVault/auditLogExport", post(audit_log_export))
+        .route("/WebVault/auditLogVerify", post(audit_log_verify))
         .route(
             "/WebVault/notificationsSubscribe",
             post(notifications_subscribe),
@@ -114,6 +122,18 @@ pub fn register_routes(router: Router<AppState>) -> Router<AppState> {
             "/WebVault/oauth2FinishFlowUrl",
             post(oauth2_finish_flow_url),
         )
+        .route(
+            "/WebVault/oauth2StartDeviceFlow",
+            post(oauth2_start_device_flow),
+        )
+        .route(
+            "/WebVault/oauth2FinishDeviceFlow",
+            post(oauth2_finish_device_flow),
+        )
+        .route(
+            "/WebVault/oauth2CancelDeviceFlow",
+            post(oauth2_cancel_device_flow),
+        )
         .route("/WebVault/configGetBaseUrl", post(config_get_base_url))
         .route("/WebVault/userSubscribe", post(user_subscribe))
         .route("/WebVault/userData", post(user_data))
@@ -133,6 +153,11 @@ pub fn register_routes(router: Router<AppState>) -> Router<AppState> {
             "/WebVault/userEnsureProfilePicture",
             post(user_ensure_profile_picture),
         )
+        .route("/WebVault/accountsSubscribe", post(accounts_subscribe))
+        .route("/WebVault/accountsData", post(accounts_data))
+        .route("/WebVault/accountsSwitch", post(accounts_switch))
+        .route("/WebVault/accountsAdd", post(accounts_add))
+        .route("/WebVault/accountsRemove", post(accounts_remove))
         .route("/WebVault/fileIconSvg", post(file_icon_svg))
         .route("/WebVault/reposSubscribe", post(repos_subscribe))
         .route("/WebVault/reposData", post(repos_data))
@@ -145,6 +170,10 @@ pub fn register_routes(router: Router<AppState>) -> Router<AppState> {
             "/WebVault/reposSetDefaultAutoLock",
             post(repos_set_default_auto_lock),
         )
+        .route(
+            "/WebVault/reposSetUnlockLockout",
+            post(repos_set_unlock_lockout),
+        )
         .route("/WebVault/repoCreateCreate", post(repo_create_create))
         .route(
             "/WebVault/repoCreateInfoSubscribe",
@@ -241,6 +270,27 @@ pub fn register_routes(router: Router<AppState>) -> Router<AppState> {
             "/WebVault/repoSpaceUsageDestroy",
             post(repo_space_usage_destroy),
         )
+        .route(
+            "/WebVault/repoDuplicatesCreate",
+            post(repo_duplicates_create),
+        )
+        .route(
+            "/WebVault/repoDuplicatesInfoSubscribe",
+            post(repo_duplicates_info_subscribe),
+        )
+        .route(
+            "/WebVault/repoDuplicatesInfoData",
+            post(repo_duplicates_info_data),
+        )
+        .route("/WebVault/repoDuplicatesScan", post(repo_duplicates_scan))
+        .route(
+            "/WebVault/repoDuplicatesDeleteAllButNewest",
+            post(repo_duplicates_delete_all_but_newest),
+        )
+        .route(
+            "/WebVault/repoDuplicatesDestroy",
+            post(repo_duplicates_destroy),
+        )
         .route(
             "/WebVault/repoFilesFileSubscribe",
             post(repo_files_file_subscribe),
@@ -348,6 +398,14 @@ pub fn register_routes(router: Router<AppState>) -> Router<AppState> {
             "/WebVault/repoFilesBrowsersMoveSelected",
             post(repo_files_browsers_move_selected),
         )
+        .route(
+            "/WebVault/repoFilesBrowsersRenameSelectedPreview",
+            post(repo_files_browsers_rename_selected_preview),
+        )
+        .route(
+            "/WebVault/repoFilesBrowsersRenameSelected",
+            post(repo_files_browsers_rename_selected),
+        )
         .route(
             "/WebVault/repoFilesDetailsCreate",
             post(repo_files_details_create),
@@ -408,6 +466,30 @@ pub fn register_routes(router: Router<AppState>) -> Router<AppState> {
             "/WebVault/repoFilesDetailsDelete",
             post(repo_files_details_delete),
         )
+        .route(
+            "/WebVault/repoFilesDetailsConflictTextMergeSubscribe",
+            post(repo_files_details_conflict_text_merge_subscribe),
+        )
+        .route(
+            "/WebVault/repoFilesDetailsConflictTextMergeData",
+            post(repo_files_details_conflict_text_merge_data),
+        )
+        .route(
+            "/WebVault/repoFilesDetailsConflictKeepMine",
+            post(repo_files_details_conflict_keep_mine),
+        )
+        .route(
+            "/WebVault/repoFilesDetailsConflictMerge",
+            post(repo_files_details_conflict_merge),
+        )
+        .route(
+            "/WebVault/repoFilesDetailsConflictKeepTheirs",
+            post(repo_files_details_conflict_keep_theirs),
+        )
+        .route(
+            "/WebVault/repoFilesDetailsConflictSaveAsCopy",
+            post(repo_files_details_conflict_save_as_copy),
+        )
         .route(
             "/WebVault/repoFilesMoveInfoSubscribe",
             post(repo_files_move_info_subscribe),
@@ -432,6 +514,54 @@ pub fn register_routes(router: Router<AppState>) -> Router<AppState> {
             "/WebVault/repoFilesMoveCreateDir",
             post(repo_files_move_create_dir),
         )
+        .route(
+            "/WebVault/repoFilesVersionsGetSettings",
+            post(repo_files_versions_get_settings),
+        )
+        .route(
+            "/WebVault/repoFilesVersionsSetSettings",
+            post(repo_files_versions_set_settings),
+        )
+        .route(
+            "/WebVault/repoFilesVersionsList",
+            post(repo_files_versions_list),
+        )
+        .route(
+            "/WebVault/repoFilesVersionsRestore",
+            post(repo_files_versions_restore),
+        )
+        .route(
+            "/WebVault/repoFilesVersionsPrune",
+            post(repo_files_versions_prune),
+        )
+        .route(
+            "/WebVault/repoFilesOfflinePin",
+            post(repo_files_offline_pin),
+        )
+        .route(
+            "/WebVault/repoFilesOfflineUnpin",
+            post(repo_files_offline_unpin),
+        )
+        .route(
+            "/WebVault/repoFilesOfflineIsPinned",
+            post(repo_files_offline_is_pinned),
+        )
+        .route(
+            "/WebVault/repoFilesOfflineRefresh",
+            post(repo_files_offline_refresh),
+        )
+        .route(
+            "/WebVault/repoFilesOfflineSetQuota",
+            post(repo_files_offline_set_quota),
+        )
+        .route(
+            "/WebVault/repoFilesOfflineInfo",
+            post(repo_files_offline_info),
+        )
+        .route(
+            "/WebVault/repoFilesOfflineClear",
+            post(repo_files_offline_clear),
+        )
         .route("/WebVault/spaceUsageSubscribe", post(space_usage_subscribe))
         .route("/WebVault/spaceUsageData", post(space_usage_data))
         //
@@ -455,10 +585,25 @@ pub fn register_routes(router: Router<AppState>) -> Router<AppState> {
             "/WebVault/repoFilesDetailsGetFileStream",
             get(repo_files_details_get_file_stream),
         )
+        .route(
+            "/WebVault/repoFilesGetStreamUrl",
+            post(repo_files_get_stream_url),
+        )
+        .route(REPO_FILES_STREAM_PATH, get(repo_files_stream))
         .route(
             "/WebVault/repoFilesBrowsersDownloadSelected",
             post(repo_files_browsers_download_selected),
         )
+        .route(
+            "/WebVault/repoFilesThumbnailsEnsure",
+            post(repo_files_thumbnails_ensure),
+        )
+        .route(
+            "/WebVault/repoFilesThumbnailsGet",
+            get(repo_files_thumbnails_get),
+        )
+        //
+        .route("/WebVault/webdavInfo", post(webdav_info))
 }
 
 pub async fn session(
@@ -539,6 +684,15 @@ pub async fn app_hidden(ExtractBase(base): ExtractBase) {
     base.app_hidden();
 }
 
+// panic_lock
+
+pub async fn panic_lock(
+    ExtractBase(base): ExtractBase,
+    Json((wipe_secure_storage,)): Json<(bool,)>,
+) {
+    base.panic_lock(wipe_secure_storage);
+}
+
 // relative_time
 
 pub async fn relative_time(
@@ -548,6 +702,33 @@ pub async fn relative_time(
     Json(base.relative_time(value, with_modifier))
 }
 
+// locale
+
+pub async fn locale_get(ExtractBase(base): ExtractBase) -> Json<String> {
+    Json(base.locale_get())
+}
+
+pub async fn locale_set(ExtractBase(base): ExtractBase, Json((name,)): Json<(String,)>) {
+    base.locale_set(&name);
+}
+
+// audit_log
+
+pub async fn audit_log_entries(
+    ExtractBase(base): ExtractBase,
+    Json((filter,)): Json<(dto::AuditLogFilter,)>,
+) -> Json<Vec<dto::AuditLogEntry>> {
+    Json(base.audit_log_entries(filter))
+}
+
+pub async fn audit_log_export(ExtractBase(base): ExtractBase) -> Json<String> {
+    Json(base.audit_log_export())
+}
+
+pub async fn audit_log_verify(ExtractBase(base): ExtractBase) -> Json<bool> {
+    Json(base.audit_log_verify())
+}
+
 // notifications
 
 pub async fn notifications_subscribe(
@@ -662,6 +843,23 @@ pub async fn oauth2_finish_flow_url(
     Json(base.oauth2_finish_flow_url(url).await)
 }
 
+pub async fn oauth2_start_device_flow(
+    ExtractBase(base): ExtractBase,
+) -> Json<Option<dto::DeviceAuthorization>> {
+    Json(base.oauth2_start_device_flow().await)
+}
+
+pub async fn oauth2_finish_device_flow(
+    ExtractBase(base): ExtractBase,
+    Json((device_authorization,)): Json<(dto::DeviceAuthorization,)>,
+) -> Json<bool> {
+    Json(base.oauth2_finish_device_flow(device_authorization).await)
+}
+
+pub async fn oauth2_cancel_device_flow(ExtractBase(base): ExtractBase) {
+    base.oauth2_cancel_device_flow();
+}
+
 // config
 
 pub async fn config_get_base_url(ExtractBase(base): ExtractBase) -> Json<String> {
@@ -711,6 +909,38 @@ pub async fn user_ensure_profile_picture(ExtractBase(base): ExtractBase) {
     base.user_ensure_profile_picture();
 }
 
+// accounts
+
+pub async fn accounts_subscribe(
+    ExtractBase(base): ExtractBase,
+    ExtractCallbacks(callbacks): ExtractCallbacks,
+    Json((cb,)): Json<(CallbackId,)>,
+) -> Json<u32> {
+    Json(base.accounts_subscribe(callbacks.cb(cb)))
+}
+
+pub async fn accounts_data(
+    ExtractBase(base): ExtractBase,
+    Json((id,)): Json<(u32,)>,
+) -> Json<Option<Vec<dto::Account>>> {
+    Json(base.accounts_data(id))
+}
+
+pub async fn accounts_switch(
+    ExtractBase(base): ExtractBase,
+    Json((user_id,)): Json<(String,)>,
+) -> Json<bool> {
+    Json(base.accounts_switch(user_id).await)
+}
+
+pub async fn accounts_add(ExtractBase(base): ExtractBase) -> Json<Option<String>> {
+    Json(base.accounts_add())
+}
+
+pub async fn accounts_remove(ExtractBase(base): ExtractBase, Json((user_id,)): Json<(String,)>) {
+    base.accounts_remove(user_id);
+}
+
 // file_icon
 
 pub async fn file_icon_svg(
@@ -774,6 +1004,13 @@ pub async fn repos_set_default_auto_lock(
     base.repos_set_default_auto_lock(auto_lock);
 }
 
+pub async fn repos_set_unlock_lockout(
+    ExtractBase(base): ExtractBase,
+    Json((unlock_lockout,)): Json<(Option<dto::RepoUnlockLockout>,)>,
+) {
+    base.repos_set_unlock_lockout(unlock_lockout);
+}
+
 // repo_create
 
 pub async fn repo_create_create(ExtractBase(base): ExtractBase) -> Json<u32> {
@@ -1008,6 +1245,51 @@ pub async fn repo_space_usage_destroy(
     base.repo_space_usage_destroy(usage_id);
 }
 
+// repo_duplicates
+
+pub async fn repo_duplicates_create(
+    ExtractBase(base): ExtractBase,
+    Json((repo_id,)): Json<(String,)>,
+) -> Json<u32> {
+    Json(base.repo_duplicates_create(repo_id))
+}
+
+pub async fn repo_duplicates_info_subscribe(
+    ExtractBase(base): ExtractBase,
+    ExtractCallbacks(callbacks): ExtractCallbacks,
+    Json((duplicates_id, cb)): Json<(u32, CallbackId)>,
+) -> Json<u32> {
+    Json(base.repo_duplicates_info_subscribe(duplicates_id, callbacks.cb(cb)))
+}
+
+pub async fn repo_duplicates_info_data(
+    ExtractBase(base): ExtractBase,
+    Json((id,)): Json<(u32,)>,
+) -> Json<Option<dto::RepoDuplicatesInfo>> {
+    Json(base.repo_duplicates_info_data(id))
+}
+
+pub async fn repo_duplicates_scan(
+    ExtractBase(base): ExtractBase,
+    Json((duplicates_id,)): Json<(u32,)>,
+) {
+    base.repo_duplicates_scan(duplicates_id);
+}
+
+pub async fn repo_duplicates_delete_all_but_newest(
+    ExtractBase(base): ExtractBase,
+    Json((duplicates_id, hashes)): Json<(u32, Option<Vec<String>>)>,
+) {
+    base.repo_duplicates_delete_all_but_newest(duplicates_id, hashes);
+}
+
+pub async fn repo_duplicates_destroy(
+    ExtractBase(base): ExtractBase,
+    Json((duplicates_id,)): Json<(u32,)>,
+) {
+    base.repo_duplicates_destroy(duplicates_id);
+}
+
 // repo_files
 
 pub async fn repo_files_file_subscribe(
@@ -1231,6 +1513,23 @@ pub async fn repo_files_browsers_move_selected(
     base.repo_files_browsers_move_selected(browser_id, mode);
 }
 
+pub async fn repo_files_browsers_rename_selected_preview(
+    ExtractBase(base): ExtractBase,
+    Json((browser_id, pattern)): Json<(u32, dto::RepoFilesRenamePattern)>,
+) -> Json<dto::RepoFilesRenamePreview> {
+    Json(base.repo_files_browsers_rename_selected_preview(browser_id, pattern))
+}
+
+pub async fn repo_files_browsers_rename_selected(
+    ExtractBase(base): ExtractBase,
+    Json((browser_id, pattern)): Json<(u32, dto::RepoFilesRenamePattern)>,
+) -> Json<Vec<dto::RepoFilesRenameResult>> {
+    Json(
+        base.repo_files_browsers_rename_selected(browser_id, pattern)
+            .await,
+    )
+}
+
 // repo_files_details
 
 pub async fn repo_files_details_create(
@@ -1352,6 +1651,52 @@ pub async fn repo_files_details_delete(
     base.repo_files_details_delete(details_id);
 }
 
+pub async fn repo_files_details_conflict_text_merge_subscribe(
+    ExtractBase(base): ExtractBase,
+    ExtractCallbacks(callbacks): ExtractCallbacks,
+    Json((details_id, cb)): Json<(u32, CallbackId)>,
+) -> Json<u32> {
+    Json(base.repo_files_details_conflict_text_merge_subscribe(details_id, callbacks.cb(cb)))
+}
+
+pub async fn repo_files_details_conflict_text_merge_data(
+    ExtractBase(base): ExtractBase,
+    Json((id,)): Json<(u32,)>,
+) -> Json<Option<dto::RepoFilesDetailsTextMerge>> {
+    Json(base.repo_files_details_conflict_text_merge_data(id))
+}
+
+pub async fn repo_files_details_conflict_keep_mine(
+    ExtractBase(base): ExtractBase,
+    Json((details_id,)): Json<(u32,)>,
+) {
+    base.repo_files_details_conflict_keep_mine(details_id);
+}
+
+pub async fn repo_files_details_conflict_merge(
+    ExtractBase(base): ExtractBase,
+    Json((details_id, content_base64)): Json<(u32, String)>,
+) {
+    base.repo_files_details_conflict_merge(
+        details_id,
+        BASE64.decode(content_base64.as_bytes()).unwrap(),
+    );
+}
+
+pub async fn repo_files_details_conflict_keep_theirs(
+    ExtractBase(base): ExtractBase,
+    Json((details_id,)): Json<(u32,)>,
+) {
+    base.repo_files_details_conflict_keep_theirs(details_id);
+}
+
+pub async fn repo_files_details_conflict_save_as_copy(
+    ExtractBase(base): ExtractBase,
+    Json((details_id,)): Json<(u32,)>,
+) {
+    base.repo_files_details_conflict_save_as_copy(details_id);
+}
+
 // repo_files_move
 
 pub async fn repo_files_move_info_subscribe(
@@ -1388,6 +1733,129 @@ pub async fn repo_files_move_create_dir(ExtractBase(base): ExtractBase) {
     base.repo_files_move_create_dir();
 }
 
+// repo_files_thumbnails
+
+pub async fn repo_files_thumbnails_ensure(
+    ExtractBase(base): ExtractBase,
+    Json((file_ids,)): Json<(Vec<String>,)>,
+) {
+    base.repo_files_thumbnails_ensure(file_ids);
+}
+
+#[derive(Deserialize)]
+pub struct RepoFilesThumbnailsGetQuery {
+    #[serde(rename = "fileId")]
+    pub file_id: String,
+}
+
+pub async fn repo_files_thumbnails_get(
+    ExtractBase(base): ExtractBase,
+    Query(RepoFilesThumbnailsGetQuery { file_id }): Query<RepoFilesThumbnailsGetQuery>,
+) -> Response {
+    match base
+        .vault
+        .repo_files_thumbnails_get(RepoFileId(file_id))
+        .await
+    {
+        Ok(thumbnail) => (
+            [
+                (header::CONTENT_TYPE, thumbnail.content_type),
+                (header::CACHE_CONTROL, "no-store".to_owned()),
+            ],
+            thumbnail.bytes,
+        )
+            .into_response(),
+        Err(err) => (StatusCode::NOT_FOUND, Json(dto::ErrorResponse::from(&err))).into_response(),
+    }
+}
+
+// repo_files_versions
+
+pub async fn repo_files_versions_get_settings(
+    ExtractBase(base): ExtractBase,
+    Json((repo_id,)): Json<(String,)>,
+) -> Json<Option<dto::RepoFilesVersionsSettings>> {
+    Json(base.repo_files_versions_get_settings(repo_id))
+}
+
+pub async fn repo_files_versions_set_settings(
+    ExtractBase(base): ExtractBase,
+    Json((repo_id, settings)): Json<(String, dto::RepoFilesVersionsSettings)>,
+) -> Json<bool> {
+    Json(base.repo_files_versions_set_settings(repo_id, settings))
+}
+
+pub async fn repo_files_versions_list(
+    ExtractBase(base): ExtractBase,
+    Json((repo_id, encrypted_path)): Json<(String, String)>,
+) -> Json<Option<Vec<dto::RepoFileVersion>>> {
+    Json(base.repo_files_versions_list(repo_id, encrypted_path).await)
+}
+
+pub async fn repo_files_versions_restore(
+    ExtractBase(base): ExtractBase,
+    Json((repo_id, encrypted_path, version_encrypted_path)): Json<(String, String, String)>,
+) -> Json<bool> {
+    Json(
+        base.repo_files_versions_restore(repo_id, encrypted_path, version_encrypted_path)
+            .await,
+    )
+}
+
+pub async fn repo_files_versions_prune(
+    ExtractBase(base): ExtractBase,
+    Json((repo_id, encrypted_path)): Json<(String, String)>,
+) -> Json<bool> {
+    Json(
+        base.repo_files_versions_prune(repo_id, encrypted_path)
+            .await,
+    )
+}
+
+// repo_files_offline
+
+pub async fn repo_files_offline_pin(
+    ExtractBase(base): ExtractBase,
+    Json((repo_id, encrypted_path)): Json<(String, String)>,
+) -> Json<bool> {
+    Json(base.repo_files_offline_pin(repo_id, encrypted_path).await)
+}
+
+pub async fn repo_files_offline_unpin(
+    ExtractBase(base): ExtractBase,
+    Json((repo_id, encrypted_path)): Json<(String, String)>,
+) -> Json<bool> {
+    Json(base.repo_files_offline_unpin(repo_id, encrypted_path).await)
+}
+
+pub async fn repo_files_offline_is_pinned(
+    ExtractBase(base): ExtractBase,
+    Json((repo_id, encrypted_path)): Json<(String, String)>,
+) -> Json<bool> {
+    Json(base.repo_files_offline_is_pinned(repo_id, encrypted_path))
+}
+
+pub async fn repo_files_offline_refresh(ExtractBase(base): ExtractBase) -> Json<bool> {
+    Json(base.repo_files_offline_refresh().await)
+}
+
+pub async fn repo_files_offline_set_quota(
+    ExtractBase(base): ExtractBase,
+    Json((quota,)): Json<(i64,)>,
+) -> Json<bool> {
+    Json(base.repo_files_offline_set_quota(quota).await)
+}
+
+pub async fn repo_files_offline_info(
+    ExtractBase(base): ExtractBase,
+) -> Json<dto::RepoFilesOfflineInfo> {
+    Json(base.repo_files_offline_info())
+}
+
+pub async fn repo_files_offline_clear(ExtractBase(base): ExtractBase) -> Json<bool> {
+    Json(base.repo_files_offline_clear().await)
+}
+
 // space_usage
 
 pub async fn space_usage_subscribe(
@@ -1710,6 +2178,63 @@ pub async fn repo_files_details_get_file_stream(
     Ok(res)
 }
 
+pub async fn repo_files_get_stream_url(
+    State(state): State<AppState>,
+    Json((repo_id, encrypted_path)): Json<(String, String)>,
+) -> Result<Json<String>, ApiError> {
+    state
+        .signed_urls
+        .sign(&RepoId(repo_id), &EncryptedPath(encrypted_path))
+        .map(Json)
+        .map_err(|err| err.to_string().into())
+}
+
+pub async fn repo_files_stream(
+    State(state): State<AppState>,
+    ExtractBase(base): ExtractBase,
+    Query(SignedUrlQuery { token }): Query<SignedUrlQuery>,
+    headers: HeaderMap,
+) -> Response {
+    let (repo_id, path) = match state.signed_urls.verify(&token) {
+        Ok(res) => res,
+        Err(err) => return (StatusCode::FORBIDDEN, err.to_string()).into_response(),
+    };
+
+    let range = headers
+        .get(header::RANGE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(http_range::parse_range_header);
+
+    let reader = match base
+        .vault
+        .repo_files_get_file_range_reader(&repo_id, &path, range.as_ref())
+        .await
+    {
+        Ok(reader) => reader,
+        Err(repo_files_read::errors::GetFilesReaderError::RangeNotSatisfiable) => {
+            let size = base.vault.with_state(|state| {
+                repo_files::selectors::select_file(
+                    state,
+                    &repo_files::selectors::get_file_id(&repo_id, &path),
+                )
+                .and_then(|file| file.decrypted_size().ok().flatten())
+                .unwrap_or(0)
+            });
+
+            return http_range::range_not_satisfiable_response(size);
+        }
+        Err(err) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(dto::ErrorResponse::from(&err)),
+            )
+                .into_response()
+        }
+    };
+
+    http_range::file_range_response(reader)
+}
+
 pub async fn repo_files_browsers_download_selected(
     State(state): State<AppState>,
     ExtractBase(base): ExtractBase,
@@ -1737,3 +2262,9 @@ pub async fn repo_files_browsers_download_selected(
         None => base.vault.notifications_show("Not implemented".into()),
     }
 }
+
+// webdav
+
+pub async fn webdav_info(State(state): State<AppState>) -> Json<Option<WebDavInfo>> {
+    Json(state.webdav_info.clone())
+}
@@ -0,0 +1,116 @@
+use axum::{
+    body::Body,
+    http::{header, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use vault_core::{
+    repo_files_read::state::{RepoFileRangeReader, RepoFileReadRange},
+    utils::reader_stream::ReaderStream,
+};
+use vault_crypto::constants::BLOCK_SIZE;
+
+/// Parses a single range `Range` header value. Multiple ranges and invalid
+/// values return None and the whole file is served.
+pub fn parse_range_header(value: &str) -> Option<RepoFileReadRange> {
+    let range = value.trim().strip_prefix("bytes=")?.trim();
+
+    if range.contains(',') {
+        return None;
+    }
+
+    let (start, end) = range.split_once('-')?;
+    let (start, end) = (start.trim(), end.trim());
+
+    if start.is_empty() {
+        return end.parse().ok().map(RepoFileReadRange::Suffix);
+    }
+
+    let start = start.parse().ok()?;
+    let end = if end.is_empty() {
+        None
+    } else {
+        Some(end.parse().ok()?)
+    };
+
+    Some(RepoFileReadRange::FromStart { start, end })
+}
+
+pub fn file_range_response(reader: RepoFileRangeReader) -> Response {
+    let content_type = reader
+        .content_type
+        .unwrap_or_else(|| mime::APPLICATION_OCTET_STREAM.to_string());
+
+    let stream = ReaderStream::new(reader.reader, BLOCK_SIZE);
+
+    let mut res = Body::from_stream(stream).into_response();
+    let res_headers = res.headers_mut();
+    res_headers.insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+    if let Ok(content_type) = content_type.try_into() {
+        res_headers.insert(header::CONTENT_TYPE, content_type);
+    }
+
+    match reader.range {
+        Some(range) => {
+            res_headers.insert(
+                header::CONTENT_LENGTH,
+                (range.end() - range.start() + 1).into(),
+            );
+            res_headers.insert(
+                header::CONTENT_RANGE,
+                format!("bytes {}-{}/{}", range.start(), range.end(), reader.size)
+                    .try_into()
+                    .unwrap(),
+            );
+            *res.status_mut() = StatusCode::PARTIAL_CONTENT;
+        }
+        None => {
+            res_headers.insert(header::CONTENT_LENGTH, reader.size.into());
+            *res.status_mut() = StatusCode::OK;
+        }
+    }
+
+    res
+}
+
+pub fn range_not_satisfiable_response(size: i64) -> Response {
+    (
+        StatusCode::RANGE_NOT_SATISFIABLE,
+        [(header::CONTENT_RANGE, format!("bytes */{}", size))],
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use similar_asserts::assert_eq;
+
+    use vault_core::repo_files_read::state::RepoFileReadRange;
+
+    use super::parse_range_header;
+
+    #[test]
+    fn test_parse_range_header() {
+        assert_eq!(
+            parse_range_header("bytes=0-"),
+            Some(RepoFileReadRange::FromStart {
+                start: 0,
+                end: None
+            })
+        );
+        assert_eq!(
+            parse_range_header("bytes=10-19"),
+            Some(RepoFileReadRange::FromStart {
+                start: 10,
+                end: Some(19)
+            })
+        );
+        assert_eq!(
+            parse_range_header("bytes=-500"),
+            Some(RepoFileReadRange::Suffix(500))
+        );
+        assert_eq!(parse_range_header("bytes=0-1,5-6"), None);
+        assert_eq!(parse_range_header("items=0-1"), None);
+        assert_eq!(parse_range_header("bytes=a-b"), None);
+        assert_eq!(parse_range_header("bytes=-"), None);
+    }
+}
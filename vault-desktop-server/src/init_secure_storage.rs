@@ -1,11 +1,12 @@
 use vault_core::secure_storage::{MemorySecureStorage, SecureStorage};
 
-use crate::{
+use vault_native::{
     data_path::get_data_path,
     file_secure_storage::{get_file_secure_storage_path, FileSecureStorage},
-    keyring_secure_storage::KeyringSecureStorage,
 };
 
+use crate::keyring_secure_storage::KeyringSecureStorage;
+
 pub fn init_keyring_secure_storage(
     app_id: &str,
 ) -> (Box<dyn SecureStorage + Send + Sync>, Option<String>) {
@@ -1,15 +1,15 @@
 pub mod app;
 pub mod app_state;
 pub mod callbacks;
-pub mod data_path;
 pub mod encryption;
 pub mod extract;
 pub mod file_handlers;
-pub mod keyring_secure_storage;
-pub mod file_secure_storage;
 pub mod handlers;
+pub mod http_range;
 pub mod init_secure_storage;
+pub mod keyring_secure_storage;
 pub mod request_encryption;
 pub mod request_id;
 pub mod sessions;
-pub mod upload_helper;
+pub mod signed_urls;
+pub mod webdav;
@@ -7,11 +7,21 @@ use vault_desktop_server::{
     file_handlers::FileHandlers,
     init_secure_storage::{init_file_secure_storage, init_keyring_secure_storage},
 };
-use vault_native::vault::build_vault;
+use vault_native::{
+    data_path::get_data_path,
+    file_audit_log_disk_storage::FileAuditLogDiskStorage,
+    file_metadata_disk_cache::FileMetadataDiskCache,
+    file_offline_disk_cache::FileOfflineDiskCache,
+    file_thumbnails_disk_cache::{FileThumbnailsDiskCache, DEFAULT_MAX_SIZE},
+    vault::build_vault,
+};
 use vault_web_api::web_vault_base::WebVaultBase;
 
 fn main() {
     let port = 1421;
+    let webdav_port = std::env::var("VAULT_WEBDAV_PORT")
+        .ok()
+        .and_then(|port| port.parse().ok());
 
     let base_url = String::from("https://app.koofr.net");
     // let base_url = String::from("https://127.0.0.1:3443");
@@ -39,7 +49,7 @@ fn main() {
         base_url: base_url.clone(),
         auth_base_url: oauth2_auth_base_url,
         client_id: oauth2_client_id,
-        client_secret: oauth2_client_secret,
+        client_secret: Some(oauth2_client_secret),
         redirect_uri: oauth2_redirect_uri,
     };
 
@@ -55,6 +65,39 @@ fn main() {
         vault.notifications_show(err);
     }
 
+    match get_data_path(&app_id)
+        .map_err(|err| err.to_string())
+        .and_then(|data_path| FileAuditLogDiskStorage::new(data_path.join("audit-log.json")))
+    {
+        Ok(disk_storage) => vault.audit_log_set_disk_storage(Some(Box::new(disk_storage))),
+        Err(err) => log::warn!("Audit log disk storage not available: {}", err),
+    }
+
+    match get_data_path(&app_id)
+        .map_err(|err| err.to_string())
+        .and_then(|data_path| {
+            FileThumbnailsDiskCache::new(data_path.join("thumbnails"), DEFAULT_MAX_SIZE)
+        }) {
+        Ok(disk_cache) => vault.repo_files_thumbnails_set_disk_cache(Some(Box::new(disk_cache))),
+        Err(err) => log::warn!("Thumbnails disk cache not available: {}", err),
+    }
+
+    match get_data_path(&app_id)
+        .map_err(|err| err.to_string())
+        .and_then(|data_path| FileOfflineDiskCache::new(data_path.join("offline")))
+    {
+        Ok(disk_cache) => vault.repo_files_offline_set_disk_cache(Some(Box::new(disk_cache))),
+        Err(err) => log::warn!("Offline disk cache not available: {}", err),
+    }
+
+    match get_data_path(&app_id)
+        .map_err(|err| err.to_string())
+        .and_then(|data_path| FileMetadataDiskCache::new(data_path.join("metadata")))
+    {
+        Ok(disk_cache) => vault.metadata_cache_set_disk_cache(Some(Box::new(disk_cache))),
+        Err(err) => log::warn!("Metadata disk cache not available: {}", err),
+    }
+
     let web_vault = WebVaultBase::new(vault);
 
     web_vault.load();
@@ -66,6 +109,14 @@ fn main() {
     });
 
     tokio_runtime.clone().block_on(async move {
-        app(port, web_vault, tokio_runtime, encryption, file_handlers).await
+        app(
+            port,
+            web_vault,
+            tokio_runtime,
+            encryption,
+            file_handlers,
+            webdav_port,
+        )
+        .await
     });
 }
@@ -10,7 +10,10 @@ use axum::{
 use http_body_util::BodyExt;
 use serde::{Deserialize, Serialize};
 
-use crate::{encryption::Encryption, request_id::RequestId, sessions::Sessions};
+use crate::{
+    encryption::Encryption, request_id::RequestId, sessions::Sessions,
+    signed_urls::REPO_FILES_STREAM_PATH,
+};
 
 #[derive(Clone)]
 pub struct EncryptionMiddlewareState {
@@ -27,6 +30,11 @@ pub async fn encryption_middleware(
         return Ok(next.run(req).await);
     }
 
+    // signed urls are verified in the handler
+    if req.method() == Method::GET && req.uri().path() == REPO_FILES_STREAM_PATH {
+        return Ok(next.run(req).await);
+    }
+
     match *req.method() {
         Method::GET => {
             let req = decrypt_request_get(&state.encryption, &state.sessions, req).await?;
@@ -215,6 +223,7 @@ mod tests {
         encryption::Encryption,
         request_id::RequestId,
         sessions::{SessionMessage, Sessions},
+        signed_urls::REPO_FILES_STREAM_PATH,
     };
 
     use super::{
@@ -255,7 +264,8 @@ mod tests {
         };
         let app = Router::new()
             .route("/get", get(get_handler))
-            .route("/post", post(post_handler));
+            .route("/post", post(post_handler))
+            .route(REPO_FILES_STREAM_PATH, get(get_handler));
         let app =
             axum::middleware::from_fn_with_state(state.clone(), encryption_middleware).layer(app);
 
@@ -304,6 +314,25 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_get_repo_files_stream() {
+        let (app, _) = build_app();
+
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri(format!("{}?token=t", REPO_FILES_STREAM_PATH))
+            .body(Body::empty())
+            .unwrap();
+
+        let (res, body) = app.oneshot(req).await.unwrap().into_parts();
+        let body = body.collect().await.unwrap().to_bytes();
+        assert_eq!(res.status, StatusCode::OK);
+        assert_eq!(
+            String::from_utf8(body.to_vec()).unwrap(),
+            format!("GET {}?token=t", REPO_FILES_STREAM_PATH)
+        );
+    }
+
     #[tokio::test]
     async fn test_get_session_ok() {
         let (app, state) = build_app();
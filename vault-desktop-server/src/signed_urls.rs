@@ -0,0 +1,172 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use vault_core::types::{EncryptedPath, RepoId};
+
+use crate::encryption::{Encryption, EncryptionError};
+
+/// Requests to this path are verified with the signed URL token instead of
+/// the request encryption.
+pub const REPO_FILES_STREAM_PATH: &str = "/WebVault/repoFilesStream";
+
+pub const DEFAULT_SIGNED_URL_TTL: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Error, Debug, Clone)]
+pub enum SignedUrlError {
+    #[error("invalid token")]
+    InvalidToken,
+    #[error("url expired")]
+    Expired,
+    #[error("{0}")]
+    EncryptionError(#[from] EncryptionError),
+}
+
+#[derive(Deserialize, Serialize)]
+struct SignedUrlPayload {
+    #[serde(rename = "repoId")]
+    repo_id: String,
+    path: String,
+    /// unix timestamp in seconds
+    expires: u64,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct SignedUrlQuery {
+    pub token: String,
+}
+
+/// Signed URLs let media elements request file streams without the request
+/// encryption envelope. The token is the payload encrypted with a key that
+/// only lives as long as the server.
+pub struct SignedUrls {
+    encryption: Encryption,
+    ttl: Duration,
+}
+
+impl SignedUrls {
+    pub fn new(ttl: Duration) -> Result<Self, EncryptionError> {
+        let (encryption, _) = Encryption::random()?;
+
+        Ok(Self { encryption, ttl })
+    }
+
+    pub fn sign(&self, repo_id: &RepoId, path: &EncryptedPath) -> Result<String, SignedUrlError> {
+        self.sign_expires(repo_id, path, now() + self.ttl.as_secs())
+    }
+
+    fn sign_expires(
+        &self,
+        repo_id: &RepoId,
+        path: &EncryptedPath,
+        expires: u64,
+    ) -> Result<String, SignedUrlError> {
+        let payload = serde_json::to_vec(&SignedUrlPayload {
+            repo_id: repo_id.0.clone(),
+            path: path.0.clone(),
+            expires,
+        })
+        .unwrap();
+
+        let query = serde_urlencoded::to_string(&SignedUrlQuery {
+            token: self.encryption.encrypt(&payload)?,
+        })
+        .unwrap();
+
+        Ok(format!("{}?{}", REPO_FILES_STREAM_PATH, query))
+    }
+
+    pub fn verify(&self, token: &str) -> Result<(RepoId, EncryptedPath), SignedUrlError> {
+        let payload = self
+            .encryption
+            .decrypt(token.as_bytes())
+            .map_err(|_| SignedUrlError::InvalidToken)?;
+
+        let payload: SignedUrlPayload =
+            serde_json::from_slice(&payload).map_err(|_| SignedUrlError::InvalidToken)?;
+
+        if payload.expires < now() {
+            return Err(SignedUrlError::Expired);
+        }
+
+        Ok((RepoId(payload.repo_id), EncryptedPath(payload.path)))
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use similar_asserts::assert_eq;
+
+    use vault_core::types::{EncryptedPath, RepoId};
+
+    use super::{
+        now, SignedUrlError, SignedUrlQuery, SignedUrls, DEFAULT_SIGNED_URL_TTL,
+        REPO_FILES_STREAM_PATH,
+    };
+
+    fn get_token(url: &str) -> String {
+        let (path, query) = url.split_once('?').unwrap();
+
+        assert_eq!(path, REPO_FILES_STREAM_PATH);
+
+        serde_urlencoded::from_str::<SignedUrlQuery>(query)
+            .unwrap()
+            .token
+    }
+
+    #[test]
+    fn test_sign_verify() {
+        let signed_urls = SignedUrls::new(DEFAULT_SIGNED_URL_TTL).unwrap();
+        let repo_id = RepoId("r1".into());
+        let path = EncryptedPath("/a b/c+d".into());
+
+        let token = get_token(&signed_urls.sign(&repo_id, &path).unwrap());
+
+        assert_eq!(signed_urls.verify(&token).unwrap(), (repo_id, path));
+    }
+
+    #[test]
+    fn test_verify_expired() {
+        let signed_urls = SignedUrls::new(DEFAULT_SIGNED_URL_TTL).unwrap();
+
+        let token = get_token(
+            &signed_urls
+                .sign_expires(&RepoId("r1".into()), &EncryptedPath("/".into()), now() - 1)
+                .unwrap(),
+        );
+
+        assert!(matches!(
+            signed_urls.verify(&token),
+            Err(SignedUrlError::Expired)
+        ));
+    }
+
+    #[test]
+    fn test_verify_other_key() {
+        let signed_urls = SignedUrls::new(DEFAULT_SIGNED_URL_TTL).unwrap();
+        let other_signed_urls = SignedUrls::new(DEFAULT_SIGNED_URL_TTL).unwrap();
+
+        let token = get_token(
+            &other_signed_urls
+                .sign(&RepoId("r1".into()), &EncryptedPath("/".into()))
+                .unwrap(),
+        );
+
+        assert!(matches!(
+            signed_urls.verify(&token),
+            Err(SignedUrlError::InvalidToken)
+        ));
+        assert!(matches!(
+            signed_urls.verify("invalid"),
+            Err(SignedUrlError::InvalidToken)
+        ));
+    }
+}
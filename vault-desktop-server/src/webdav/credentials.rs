@@ -0,0 +1,66 @@
+use data_encoding::BASE64URL_NOPAD;
+use rand_core::{OsRng, RngCore};
+use serde::Serialize;
+
+pub const WEBDAV_USERNAME: &str = "vault";
+
+/// Random basic auth credentials generated for every server run.
+#[derive(Debug, Clone, Serialize)]
+pub struct WebDavCredentials {
+    pub username: String,
+    pub password: String,
+}
+
+impl WebDavCredentials {
+    pub fn random() -> Self {
+        let mut password = [0; 24];
+
+        OsRng.fill_bytes(&mut password);
+
+        Self {
+            username: WEBDAV_USERNAME.to_owned(),
+            password: BASE64URL_NOPAD.encode(&password),
+        }
+    }
+
+    pub fn verify_authorization(&self, authorization: Option<&str>) -> bool {
+        let credentials = match authorization
+            .and_then(|value| value.strip_prefix("Basic "))
+            .and_then(|value| data_encoding::BASE64.decode(value.trim().as_bytes()).ok())
+        {
+            Some(credentials) => credentials,
+            None => return false,
+        };
+
+        let expected = format!("{}:{}", self.username, self.password);
+
+        constant_time_eq(&credentials, expected.as_bytes())
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WebDavCredentials;
+
+    fn basic(value: &str) -> String {
+        format!("Basic {}", data_encoding::BASE64.encode(value.as_bytes()))
+    }
+
+    #[test]
+    fn test_verify_authorization() {
+        let credentials = WebDavCredentials::random();
+
+        assert!(credentials
+            .verify_authorization(Some(&basic(&format!("vault:{}", credentials.password)))));
+        assert!(!credentials.verify_authorization(Some(&basic("vault:wrong"))));
+        assert!(!credentials
+            .verify_authorization(Some(&basic(&format!("other:{}", credentials.password)))));
+        assert!(!credentials.verify_authorization(Some("Bearer token")));
+        assert!(!credentials.verify_authorization(None));
+        assert_ne!(credentials.password, WebDavCredentials::random().password);
+    }
+}
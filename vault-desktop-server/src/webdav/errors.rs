@@ -0,0 +1,151 @@
+use axum::http::StatusCode;
+use vault_core::{
+    remote::{ApiErrorCode, RemoteError},
+    repo_files::errors::{
+        CopyFileError, CreateDirError, DeleteFileError, LoadFileError, LoadFilesError,
+        MoveFileError, UploadFileReaderError,
+    },
+    repo_files_read::errors::GetFilesReaderError,
+    repos::errors::GetCipherError,
+};
+
+use super::paths::WebDavPathError;
+
+pub trait WebDavStatus {
+    fn status_code(&self) -> StatusCode;
+}
+
+impl WebDavStatus for RemoteError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            RemoteError::ApiError { code, .. } => match code {
+                ApiErrorCode::NotFound => StatusCode::NOT_FOUND,
+                ApiErrorCode::AlreadyExists => StatusCode::PRECONDITION_FAILED,
+                ApiErrorCode::Conflict | ApiErrorCode::NotDir => StatusCode::CONFLICT,
+                ApiErrorCode::MoveIntoSelf => StatusCode::FORBIDDEN,
+                ApiErrorCode::InvalidPath => StatusCode::BAD_REQUEST,
+                _ => StatusCode::BAD_GATEWAY,
+            },
+            _ => StatusCode::BAD_GATEWAY,
+        }
+    }
+}
+
+impl WebDavStatus for WebDavPathError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::InvalidPath => StatusCode::BAD_REQUEST,
+            Self::RepoNotFound => StatusCode::NOT_FOUND,
+        }
+    }
+}
+
+impl WebDavStatus for GetCipherError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::RepoNotFound(_) => StatusCode::NOT_FOUND,
+            Self::RepoLocked(_) => StatusCode::LOCKED,
+        }
+    }
+}
+
+impl WebDavStatus for LoadFileError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::RepoNotFound(_) => StatusCode::NOT_FOUND,
+            Self::RepoLocked(_) => StatusCode::LOCKED,
+            Self::RemoteError(err) => err.status_code(),
+        }
+    }
+}
+
+impl WebDavStatus for LoadFilesError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::RepoNotFound(_) => StatusCode::NOT_FOUND,
+            Self::RepoLocked(_) => StatusCode::LOCKED,
+            Self::RemoteError(err) => err.status_code(),
+        }
+    }
+}
+
+impl WebDavStatus for GetFilesReaderError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::RepoNotFound(_) | Self::FileNotFound => StatusCode::NOT_FOUND,
+            Self::RepoLocked(_) => StatusCode::LOCKED,
+            Self::RangeNotSatisfiable => StatusCode::RANGE_NOT_SATISFIABLE,
+            Self::RemoteError(err) => err.status_code(),
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl WebDavStatus for UploadFileReaderError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::RepoNotFound(_) => StatusCode::NOT_FOUND,
+            Self::RepoLocked(_) => StatusCode::LOCKED,
+            Self::RepoReadOnly(_) => StatusCode::FORBIDDEN,
+            // parent dirs are created if they do not exist
+            Self::RemoteError(err) => err.status_code(),
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl WebDavStatus for CreateDirError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::RepoNotFound(_) => StatusCode::NOT_FOUND,
+            Self::RepoLocked(_) => StatusCode::LOCKED,
+            Self::RepoReadOnly(_) => StatusCode::FORBIDDEN,
+            Self::RemoteError(err) => match err.status_code() {
+                // the parent dir does not exist
+                StatusCode::NOT_FOUND => StatusCode::CONFLICT,
+                StatusCode::PRECONDITION_FAILED => StatusCode::METHOD_NOT_ALLOWED,
+                status_code => status_code,
+            },
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl WebDavStatus for DeleteFileError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::RepoNotFound(_) => StatusCode::NOT_FOUND,
+            Self::RepoLocked(_) => StatusCode::LOCKED,
+            Self::RepoReadOnly(_) => StatusCode::FORBIDDEN,
+            Self::Canceled => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::RemoteError(err) => err.status_code(),
+        }
+    }
+}
+
+impl WebDavStatus for CopyFileError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::InvalidPath => StatusCode::BAD_REQUEST,
+            Self::RepoNotFound(_) => StatusCode::NOT_FOUND,
+            Self::RepoLocked(_) => StatusCode::LOCKED,
+            Self::RepoReadOnly(_) => StatusCode::FORBIDDEN,
+            Self::DecryptFilenameError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::RemoteError(err) => err.status_code(),
+        }
+    }
+}
+
+impl WebDavStatus for MoveFileError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::InvalidPath => StatusCode::BAD_REQUEST,
+            Self::RepoNotFound(_) => StatusCode::NOT_FOUND,
+            Self::RepoLocked(_) => StatusCode::LOCKED,
+            Self::RepoReadOnly(_) => StatusCode::FORBIDDEN,
+            Self::DecryptFilenameError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::MoveRoot => StatusCode::FORBIDDEN,
+            Self::RemoteError(err) => err.status_code(),
+        }
+    }
+}
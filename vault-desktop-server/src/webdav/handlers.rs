@@ -0,0 +1,544 @@
+use std::{
+    sync::Arc,
+    time::{Duration, UNIX_EPOCH},
+};
+
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use futures::TryStreamExt;
+use vault_core::{
+    repo_files::{
+        self,
+        state::{RepoFile, RepoFileType, RepoFilesUploadConflictResolution},
+    },
+    repo_files_read::errors::GetFilesReaderError,
+    repos,
+    types::{DecryptedPath, EncryptedPath, RepoId},
+    utils::{reader_stream::ReaderStream, repo_encrypted_path_utils},
+    Vault,
+};
+use vault_crypto::constants::BLOCK_SIZE;
+
+use crate::http_range;
+
+use super::{
+    credentials::WebDavCredentials,
+    errors::WebDavStatus,
+    paths::{self, WebDavPath, WebDavRepoDir},
+    propfind::{self, PropfindEntry},
+    sync_stream::SyncStream,
+};
+
+const ALLOW: &str = "OPTIONS, PROPFIND, GET, HEAD, PUT, MKCOL, DELETE, MOVE, COPY";
+
+#[derive(Clone)]
+pub struct WebDavState {
+    pub vault: Arc<Vault>,
+    pub credentials: Arc<WebDavCredentials>,
+}
+
+struct RepoTarget {
+    repo_dir: WebDavRepoDir,
+    path: DecryptedPath,
+    encrypted_path: EncryptedPath,
+}
+
+enum Target {
+    Root,
+    Repo(RepoTarget),
+}
+
+impl RepoTarget {
+    fn repo_id(&self) -> &RepoId {
+        &self.repo_dir.repo_id
+    }
+
+    fn is_root(&self) -> bool {
+        self.path.is_root()
+    }
+}
+
+fn get_repo_dirs(vault: &Vault) -> Vec<WebDavRepoDir> {
+    vault.with_state(|state| {
+        paths::get_repo_dirs(
+            repos::selectors::select_repos(state)
+                .into_iter()
+                .map(|repo| (repo.id.clone(), repo.name.0.clone())),
+        )
+    })
+}
+
+/// Repos that are locked return 423 Locked.
+fn resolve(vault: &Vault, uri_path: &str) -> Result<Target, StatusCode> {
+    match paths::parse_path(uri_path, &get_repo_dirs(vault)).map_err(|err| err.status_code())? {
+        WebDavPath::Root => Ok(Target::Root),
+        WebDavPath::Repo { repo_dir, path } => {
+            let cipher = vault
+                .repos_service
+                .get_cipher(&repo_dir.repo_id)
+                .map_err(|err| err.status_code())?;
+
+            let encrypted_path = cipher.encrypt_path(&path);
+
+            Ok(Target::Repo(RepoTarget {
+                repo_dir,
+                path,
+                encrypted_path,
+            }))
+        }
+    }
+}
+
+fn resolve_repo(vault: &Vault, uri_path: &str) -> Result<RepoTarget, StatusCode> {
+    match resolve(vault, uri_path)? {
+        Target::Root => Err(StatusCode::METHOD_NOT_ALLOWED),
+        Target::Repo(target) => Ok(target),
+    }
+}
+
+/// Content operations count as repo activity so that auto-lock does not lock
+/// a repo while it is being used. PROPFIND does not, because file managers
+/// poll it in the background.
+fn touch_repo(vault: &Vault, target: &RepoTarget) {
+    let _ = vault.repos_touch_repo(target.repo_id());
+}
+
+async fn load_file(vault: &Vault, target: &RepoTarget) -> Result<Option<RepoFile>, StatusCode> {
+    vault
+        .repo_files_service
+        .load_file(target.repo_id(), &target.encrypted_path)
+        .await
+        .map_err(|err| err.status_code())?;
+
+    Ok(vault.with_state(|state| {
+        repo_files::selectors::select_file(
+            state,
+            &repo_files::selectors::get_file_id(target.repo_id(), &target.encrypted_path),
+        )
+        .cloned()
+    }))
+}
+
+async fn file_exists(vault: &Vault, target: &RepoTarget) -> bool {
+    matches!(load_file(vault, target).await, Ok(Some(_)))
+}
+
+pub async fn handle(State(state): State<WebDavState>, request: Request) -> Response {
+    let authorization = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok());
+
+    if !state.credentials.verify_authorization(authorization) {
+        return (
+            StatusCode::UNAUTHORIZED,
+            [(header::WWW_AUTHENTICATE, r#"Basic realm="Vault""#)],
+        )
+            .into_response();
+    }
+
+    let vault = state.vault.clone();
+    let uri_path = request.uri().path().to_owned();
+
+    let res = match request.method().as_str() {
+        "OPTIONS" => Ok(options()),
+        "PROPFIND" => propfind(&vault, &uri_path, request.headers()).await,
+        "GET" => get(&vault, &uri_path, request.headers(), false).await,
+        "HEAD" => get(&vault, &uri_path, request.headers(), true).await,
+        "PUT" => put(&vault, &uri_path, request).await,
+        "MKCOL" => mkcol(&vault, &uri_path).await,
+        "DELETE" => delete(&vault, &uri_path).await,
+        "MOVE" => move_copy(&vault, &uri_path, request.headers(), true).await,
+        "COPY" => move_copy(&vault, &uri_path, request.headers(), false).await,
+        _ => Ok((StatusCode::METHOD_NOT_ALLOWED, [(header::ALLOW, ALLOW)]).into_response()),
+    };
+
+    res.unwrap_or_else(|status_code| status_code.into_response())
+}
+
+fn options() -> Response {
+    (
+        StatusCode::OK,
+        [
+            (header::ALLOW, ALLOW),
+            (header::HeaderName::from_static("dav"), "1"),
+            (header::HeaderName::from_static("ms-author-via"), "DAV"),
+        ],
+    )
+        .into_response()
+}
+
+fn repo_file_entry(repo_dir: &WebDavRepoDir, file: &RepoFile) -> Option<PropfindEntry> {
+    let is_dir = file.typ == RepoFileType::Dir;
+
+    Some(PropfindEntry {
+        href: paths::get_href(Some(&repo_dir.name), file.decrypted_path().ok()?, is_dir),
+        display_name: file.decrypted_name().ok()?.0.clone(),
+        is_dir,
+        size: file.decrypted_size().ok().flatten(),
+        modified: file.modified,
+        content_type: file.content_type.clone(),
+    })
+}
+
+fn repo_dir_entry(repo_dir: &WebDavRepoDir) -> PropfindEntry {
+    PropfindEntry {
+        href: paths::get_href(Some(&repo_dir.name), &DecryptedPath("/".into()), true),
+        display_name: repo_dir.name.clone(),
+        is_dir: true,
+        size: None,
+        modified: None,
+        content_type: None,
+    }
+}
+
+async fn propfind(
+    vault: &Vault,
+    uri_path: &str,
+    headers: &HeaderMap,
+) -> Result<Response, StatusCode> {
+    // infinite depth is treated as 1
+    let depth_0 = headers
+        .get(header::HeaderName::from_static("depth"))
+        .map(|value| value.as_bytes() == b"0")
+        .unwrap_or(false);
+
+    let mut entries = Vec::new();
+
+    match resolve(vault, uri_path)? {
+        Target::Root => {
+            entries.push(PropfindEntry {
+                href: paths::get_href(None, &DecryptedPath("/".into()), true),
+                display_name: String::new(),
+                is_dir: true,
+                size: None,
+                modified: None,
+                content_type: None,
+            });
+
+            if !depth_0 {
+                entries.extend(get_repo_dirs(vault).iter().map(repo_dir_entry));
+            }
+        }
+        Target::Repo(target) => {
+            let is_dir = if target.is_root() {
+                entries.push(repo_dir_entry(&target.repo_dir));
+
+                true
+            } else {
+                let file = load_file(vault, &target)
+                    .await?
+                    .ok_or(StatusCode::NOT_FOUND)?;
+
+                entries.extend(repo_file_entry(&target.repo_dir, &file));
+
+                file.typ == RepoFileType::Dir
+            };
+
+            if is_dir && !depth_0 {
+                vault
+                    .repo_files_service
+                    .load_files(target.repo_id(), &target.encrypted_path)
+                    .await
+                    .map_err(|err| err.status_code())?;
+
+                vault.with_state(|state| {
+                    entries.extend(
+                        repo_files::selectors::select_files(
+                            state,
+                            target.repo_id(),
+                            &target.encrypted_path,
+                        )
+                        .filter_map(|file| repo_file_entry(&target.repo_dir, file)),
+                    );
+                });
+            }
+        }
+    }
+
+    Ok((
+        StatusCode::MULTI_STATUS,
+        [(header::CONTENT_TYPE, "application/xml; charset=utf-8")],
+        propfind::multistatus(&entries),
+    )
+        .into_response())
+}
+
+fn file_headers(file: &RepoFile) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+
+    headers.insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+    headers.insert(
+        header::CONTENT_TYPE,
+        file.content_type
+            .as_deref()
+            .and_then(|content_type| content_type.try_into().ok())
+            .unwrap_or(HeaderValue::from_static("application/octet-stream")),
+    );
+    if let Some(size) = file.decrypted_size().ok().flatten() {
+        headers.insert(header::CONTENT_LENGTH, size.into());
+    }
+    if let Some(modified) = file.modified {
+        let modified = UNIX_EPOCH + Duration::from_millis(modified.max(0) as u64);
+
+        if let Ok(modified) = httpdate::fmt_http_date(modified).try_into() {
+            headers.insert(header::LAST_MODIFIED, modified);
+        }
+    }
+
+    headers
+}
+
+async fn get(
+    vault: &Vault,
+    uri_path: &str,
+    headers: &HeaderMap,
+    head: bool,
+) -> Result<Response, StatusCode> {
+    let target = match resolve(vault, uri_path)? {
+        Target::Root if head => return Ok(StatusCode::OK.into_response()),
+        Target::Root => return Err(StatusCode::METHOD_NOT_ALLOWED),
+        Target::Repo(target) => target,
+    };
+
+    touch_repo(vault, &target);
+
+    let file = if target.is_root() {
+        None
+    } else {
+        Some(
+            load_file(vault, &target)
+                .await?
+                .ok_or(StatusCode::NOT_FOUND)?,
+        )
+    };
+
+    let file = match file {
+        Some(file) if file.typ == RepoFileType::File => file,
+        _ if head => return Ok(StatusCode::OK.into_response()),
+        _ => return Err(StatusCode::METHOD_NOT_ALLOWED),
+    };
+
+    if head {
+        return Ok((StatusCode::OK, file_headers(&file)).into_response());
+    }
+
+    let range = headers
+        .get(header::RANGE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(http_range::parse_range_header);
+
+    if let Some(range) = range {
+        return match vault
+            .repo_files_get_file_range_reader(
+                target.repo_id(),
+                &target.encrypted_path,
+                Some(&range),
+            )
+            .await
+        {
+            Ok(reader) => Ok(http_range::file_range_response(reader)),
+            Err(GetFilesReaderError::RangeNotSatisfiable) => {
+                Ok(http_range::range_not_satisfiable_response(
+                    file.decrypted_size().ok().flatten().unwrap_or(0),
+                ))
+            }
+            Err(err) => Err(err.status_code()),
+        };
+    }
+
+    // whole file downloads are visible in transfers
+    let reader = vault
+        .repo_files_get_file_reader(target.repo_id(), &target.encrypted_path)
+        .map_err(|err| err.status_code())?
+        .reader()
+        .await
+        .map_err(|err| err.status_code())?;
+
+    let (_, reader) = vault.transfers_download_reader(reader);
+
+    Ok((
+        StatusCode::OK,
+        file_headers(&file),
+        Body::from_stream(ReaderStream::new(reader.reader, BLOCK_SIZE)),
+    )
+        .into_response())
+}
+
+async fn put(vault: &Vault, uri_path: &str, request: Request) -> Result<Response, StatusCode> {
+    let target = resolve_repo(vault, uri_path)?;
+
+    touch_repo(vault, &target);
+
+    let (parent_path, name) = repo_encrypted_path_utils::split_parent_name(&target.encrypted_path)
+        .ok_or(StatusCode::METHOD_NOT_ALLOWED)?;
+
+    let existing = load_file(vault, &target).await.ok().flatten();
+
+    if matches!(&existing, Some(file) if file.typ == RepoFileType::Dir) {
+        return Err(StatusCode::METHOD_NOT_ALLOWED);
+    }
+
+    let size = request
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok());
+
+    let reader = SyncStream::new(
+        request
+            .into_body()
+            .into_data_stream()
+            .map_err(std::io::Error::other),
+    )
+    .into_async_read();
+
+    vault
+        .repo_files_service
+        .clone()
+        .upload_file_reader(
+            target.repo_id(),
+            &parent_path,
+            name,
+            Box::pin(reader),
+            size,
+            RepoFilesUploadConflictResolution::Overwrite {
+                if_remote_size: None,
+                if_remote_modified: None,
+                if_remote_hash: None,
+            },
+            None,
+        )
+        .await
+        .map_err(|err| err.status_code())?;
+
+    Ok(if existing.is_some() {
+        StatusCode::NO_CONTENT
+    } else {
+        StatusCode::CREATED
+    }
+    .into_response())
+}
+
+async fn mkcol(vault: &Vault, uri_path: &str) -> Result<Response, StatusCode> {
+    let target = resolve_repo(vault, uri_path)?;
+
+    touch_repo(vault, &target);
+
+    let (parent_path, name) = repo_encrypted_path_utils::split_parent_name(&target.encrypted_path)
+        .ok_or(StatusCode::METHOD_NOT_ALLOWED)?;
+
+    vault
+        .repo_files_service
+        .create_dir_name(target.repo_id(), &parent_path, name)
+        .await
+        .map_err(|err| err.status_code())?;
+
+    Ok(StatusCode::CREATED.into_response())
+}
+
+async fn delete(vault: &Vault, uri_path: &str) -> Result<Response, StatusCode> {
+    let target = resolve_repo(vault, uri_path)?;
+
+    touch_repo(vault, &target);
+
+    if target.is_root() {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    vault
+        .repo_files_service
+        .delete_file(target.repo_id(), &target.encrypted_path)
+        .await
+        .map_err(|err| err.status_code())?;
+
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+async fn move_copy(
+    vault: &Vault,
+    uri_path: &str,
+    headers: &HeaderMap,
+    is_move: bool,
+) -> Result<Response, StatusCode> {
+    let target = resolve_repo(vault, uri_path)?;
+
+    touch_repo(vault, &target);
+
+    let destination = headers
+        .get(header::HeaderName::from_static("destination"))
+        .and_then(|value| value.to_str().ok())
+        .ok_or(StatusCode::BAD_REQUEST)?;
+
+    let destination = match resolve(vault, paths::get_destination_uri_path(destination))? {
+        Target::Root => return Err(StatusCode::FORBIDDEN),
+        Target::Repo(destination) => destination,
+    };
+
+    // files cannot be moved between repos because they use different keys
+    if destination.repo_id() != target.repo_id() {
+        return Err(StatusCode::BAD_GATEWAY);
+    }
+
+    if target.is_root() || destination.is_root() {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    // the remote is case-insensitive so a destination that differs only in
+    // case is the source itself and deleting it would lose the file
+    if target.encrypted_path.0.to_lowercase() == destination.encrypted_path.0.to_lowercase() {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let overwrite = headers
+        .get(header::HeaderName::from_static("overwrite"))
+        .map(|value| value.as_bytes() != b"F")
+        .unwrap_or(true);
+
+    let destination_exists = file_exists(vault, &destination).await;
+
+    if destination_exists {
+        if !overwrite {
+            return Err(StatusCode::PRECONDITION_FAILED);
+        }
+
+        vault
+            .repo_files_service
+            .delete_file(destination.repo_id(), &destination.encrypted_path)
+            .await
+            .map_err(|err| err.status_code())?;
+    }
+
+    if is_move {
+        vault
+            .repo_files_service
+            .move_file_to_path(
+                target.repo_id(),
+                &target.encrypted_path,
+                &destination.encrypted_path,
+            )
+            .await
+            .map_err(|err| err.status_code())?;
+    } else {
+        vault
+            .repo_files_service
+            .copy_file_to_path(
+                target.repo_id(),
+                &target.encrypted_path,
+                &destination.encrypted_path,
+            )
+            .await
+            .map_err(|err| err.status_code())?;
+    }
+
+    Ok(if destination_exists {
+        StatusCode::NO_CONTENT
+    } else {
+        StatusCode::CREATED
+    }
+    .into_response())
+}
@@ -0,0 +1,7 @@
+pub mod credentials;
+pub mod errors;
+pub mod handlers;
+pub mod paths;
+pub mod propfind;
+pub mod server;
+pub mod sync_stream;
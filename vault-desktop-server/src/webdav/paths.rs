@@ -0,0 +1,203 @@
+use thiserror::Error;
+
+use vault_core::{
+    types::{DecryptedPath, RepoId},
+    utils::repo_path_utils,
+};
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum WebDavPathError {
+    #[error("invalid path")]
+    InvalidPath,
+    #[error("repo not found")]
+    RepoNotFound,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WebDavRepoDir {
+    pub name: String,
+    pub repo_id: RepoId,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WebDavPath {
+    Root,
+    Repo {
+        repo_dir: WebDavRepoDir,
+        path: DecryptedPath,
+    },
+}
+
+/// Repos are top level dirs named by the repo name. If multiple repos have
+/// the same name, the repo id is appended.
+pub fn get_repo_dirs(repos: impl IntoIterator<Item = (RepoId, String)>) -> Vec<WebDavRepoDir> {
+    let repos = repos.into_iter().collect::<Vec<_>>();
+
+    let mut repo_dirs = repos
+        .iter()
+        .map(|(repo_id, name)| {
+            let is_duplicate = repos
+                .iter()
+                .any(|(other_id, other_name)| other_id != repo_id && other_name == name);
+
+            WebDavRepoDir {
+                name: if is_duplicate {
+                    format!("{} ({})", name, repo_id.0)
+                } else {
+                    name.clone()
+                },
+                repo_id: repo_id.clone(),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    repo_dirs.sort_by(|a, b| a.name.cmp(&b.name));
+
+    repo_dirs
+}
+
+pub fn parse_path(
+    uri_path: &str,
+    repo_dirs: &[WebDavRepoDir],
+) -> Result<WebDavPath, WebDavPathError> {
+    let names = uri_path
+        .split('/')
+        .filter(|name| !name.is_empty())
+        .map(|name| match urlencoding::decode(name) {
+            Ok(name) if !name.contains('/') && !name.contains('\\') => Ok(name.into_owned()),
+            _ => Err(WebDavPathError::InvalidPath),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let (repo_name, names) = match names.split_first() {
+        Some(x) => x,
+        None => return Ok(WebDavPath::Root),
+    };
+
+    let repo_dir = repo_dirs
+        .iter()
+        .find(|repo_dir| &repo_dir.name == repo_name)
+        .ok_or(WebDavPathError::RepoNotFound)?;
+
+    let path = repo_path_utils::normalize_path(&DecryptedPath(format!("/{}", names.join("/"))))
+        .map_err(|_| WebDavPathError::InvalidPath)?;
+
+    Ok(WebDavPath::Repo {
+        repo_dir: repo_dir.clone(),
+        path,
+    })
+}
+
+/// Destination header contains an absolute URI.
+pub fn get_destination_uri_path(destination: &str) -> &str {
+    match destination.split_once("://") {
+        Some((_, rest)) => rest.find('/').map(|idx| &rest[idx..]).unwrap_or("/"),
+        None => destination,
+    }
+}
+
+pub fn get_href(repo_dir_name: Option<&str>, path: &DecryptedPath, is_dir: bool) -> String {
+    let mut href = String::from("/");
+
+    if let Some(repo_dir_name) = repo_dir_name {
+        href.push_str(&urlencoding::encode(repo_dir_name));
+
+        for name in path.0.split('/').filter(|name| !name.is_empty()) {
+            href.push('/');
+            href.push_str(&urlencoding::encode(name));
+        }
+
+        if is_dir {
+            href.push('/');
+        }
+    }
+
+    href
+}
+
+#[cfg(test)]
+mod tests {
+    use similar_asserts::assert_eq;
+
+    use vault_core::types::{DecryptedPath, RepoId};
+
+    use super::{
+        get_destination_uri_path, get_href, get_repo_dirs, parse_path, WebDavPath, WebDavPathError,
+        WebDavRepoDir,
+    };
+
+    fn repo_dirs() -> Vec<WebDavRepoDir> {
+        get_repo_dirs(vec![
+            (RepoId("r1".into()), "My safe box".into()),
+            (RepoId("r2".into()), "Photos".into()),
+            (RepoId("r3".into()), "Photos".into()),
+        ])
+    }
+
+    #[test]
+    fn test_get_repo_dirs() {
+        assert_eq!(
+            repo_dirs()
+                .into_iter()
+                .map(|repo_dir| repo_dir.name)
+                .collect::<Vec<_>>(),
+            vec!["My safe box", "Photos (r2)", "Photos (r3)"]
+        );
+    }
+
+    #[test]
+    fn test_parse_path() {
+        let repo_dirs = repo_dirs();
+
+        assert_eq!(parse_path("/", &repo_dirs), Ok(WebDavPath::Root));
+        assert_eq!(
+            parse_path("/My%20safe%20box/", &repo_dirs),
+            Ok(WebDavPath::Repo {
+                repo_dir: repo_dirs[0].clone(),
+                path: DecryptedPath("/".into())
+            })
+        );
+        assert_eq!(
+            parse_path("/Photos%20(r3)/dir/file%20%C5%A1.txt", &repo_dirs),
+            Ok(WebDavPath::Repo {
+                repo_dir: repo_dirs[2].clone(),
+                path: DecryptedPath("/dir/file š.txt".into())
+            })
+        );
+        assert_eq!(
+            parse_path("/Photos", &repo_dirs),
+            Err(WebDavPathError::RepoNotFound)
+        );
+        assert_eq!(
+            parse_path("/My%20safe%20box/../x", &repo_dirs),
+            Err(WebDavPathError::InvalidPath)
+        );
+        assert_eq!(
+            parse_path("/My%20safe%20box/a%2Fb", &repo_dirs),
+            Err(WebDavPathError::InvalidPath)
+        );
+    }
+
+    #[test]
+    fn test_get_destination_uri_path() {
+        assert_eq!(
+            get_destination_uri_path("http://127.0.0.1:1422/Repo/file.txt"),
+            "/Repo/file.txt"
+        );
+        assert_eq!(get_destination_uri_path("http://127.0.0.1:1422"), "/");
+        assert_eq!(get_destination_uri_path("/Repo/file.txt"), "/Repo/file.txt");
+    }
+
+    #[test]
+    fn test_get_href() {
+        assert_eq!(get_href(None, &DecryptedPath("/".into()), true), "/");
+        assert_eq!(
+            get_href(Some("My safe box"), &DecryptedPath("/".into()), true),
+            "/My%20safe%20box/"
+        );
+        assert_eq!(
+            get_href(Some("Repo"), &DecryptedPath("/dir/a&b.txt".into()), false),
+            "/Repo/dir/a%26b.txt"
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use std::time::{Duration, UNIX_EPOCH};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropfindEntry {
+    pub href: String,
+    pub display_name: String,
+    pub is_dir: bool,
+    pub size: Option<i64>,
+    /// unix timestamp in milliseconds
+    pub modified: Option<i64>,
+    pub content_type: Option<String>,
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn write_entry(xml: &mut String, entry: &PropfindEntry) {
+    xml.push_str("<D:response><D:href>");
+    xml.push_str(&escape(&entry.href));
+    xml.push_str("</D:href><D:propstat><D:prop><D:displayname>");
+    xml.push_str(&escape(&entry.display_name));
+    xml.push_str("</D:displayname>");
+
+    if entry.is_dir {
+        xml.push_str("<D:resourcetype><D:collection/></D:resourcetype>");
+    } else {
+        xml.push_str("<D:resourcetype/>");
+        xml.push_str(&format!(
+            "<D:getcontentlength>{}</D:getcontentlength>",
+            entry.size.unwrap_or(0)
+        ));
+
+        if let Some(content_type) = &entry.content_type {
+            xml.push_str(&format!(
+                "<D:getcontenttype>{}</D:getcontenttype>",
+                escape(content_type)
+            ));
+        }
+    }
+
+    if let Some(modified) = entry.modified {
+        let modified = UNIX_EPOCH + Duration::from_millis(modified.max(0) as u64);
+
+        xml.push_str(&format!(
+            "<D:getlastmodified>{}</D:getlastmodified>",
+            httpdate::fmt_http_date(modified)
+        ));
+    }
+
+    xml.push_str("</D:prop><D:status>HTTP/1.1 200 OK</D:status></D:propstat></D:response>");
+}
+
+pub fn multistatus(entries: &[PropfindEntry]) -> String {
+    let mut xml =
+        String::from(r#"<?xml version="1.0" encoding="utf-8"?><D:multistatus xmlns:D="DAV:">"#);
+
+    for entry in entries {
+        write_entry(&mut xml, entry);
+    }
+
+    xml.push_str("</D:multistatus>");
+
+    xml
+}
+
+#[cfg(test)]
+mod tests {
+    use similar_asserts::assert_eq;
+
+    use super::{multistatus, PropfindEntry};
+
+    #[test]
+    fn test_multistatus() {
+        assert_eq!(
+            multistatus(&[
+                PropfindEntry {
+                    href: "/Repo/".into(),
+                    display_name: "Repo".into(),
+                    is_dir: true,
+                    size: None,
+                    modified: None,
+                    content_type: None,
+                },
+                PropfindEntry {
+                    href: "/Repo/a%26b.txt".into(),
+                    display_name: "a&b.txt".into(),
+                    is_dir: false,
+                    size: Some(4),
+                    modified: Some(1700000000000),
+                    content_type: Some("text/plain".into()),
+                },
+            ]),
+            concat!(
+                r#"<?xml version="1.0" encoding="utf-8"?><D:multistatus xmlns:D="DAV:">"#,
+                "<D:response><D:href>/Repo/</D:href><D:propstat><D:prop>",
+                "<D:displayname>Repo</D:displayname>",
+                "<D:resourcetype><D:collection/></D:resourcetype>",
+                "</D:prop><D:status>HTTP/1.1 200 OK</D:status></D:propstat></D:response>",
+                "<D:response><D:href>/Repo/a%26b.txt</D:href><D:propstat><D:prop>",
+                "<D:displayname>a&amp;b.txt</D:displayname>",
+                "<D:resourcetype/><D:getcontentlength>4</D:getcontentlength>",
+                "<D:getcontenttype>text/plain</D:getcontenttype>",
+                "<D:getlastmodified>Tue, 14 Nov 2023 22:13:20 GMT</D:getlastmodified>",
+                "</D:prop><D:status>HTTP/1.1 200 OK</D:status></D:propstat></D:response>",
+                "</D:multistatus>"
+            )
+        );
+    }
+}
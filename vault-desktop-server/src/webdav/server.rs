@@ -0,0 +1,49 @@
+use std::{net::SocketAddr, sync::Arc};
+
+use axum::Router;
+use serde::Serialize;
+use tokio::net::TcpListener;
+use vault_core::Vault;
+
+use super::{
+    credentials::WebDavCredentials,
+    handlers::{handle, WebDavState},
+};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WebDavInfo {
+    pub url: String,
+    pub username: String,
+    pub password: String,
+}
+
+impl WebDavInfo {
+    pub fn new(port: u16, credentials: &WebDavCredentials) -> Self {
+        Self {
+            url: format!("http://127.0.0.1:{}/", port),
+            username: credentials.username.clone(),
+            password: credentials.password.clone(),
+        }
+    }
+}
+
+pub fn router(vault: Arc<Vault>, credentials: Arc<WebDavCredentials>) -> Router {
+    Router::new()
+        .fallback(handle)
+        .with_state(WebDavState { vault, credentials })
+}
+
+pub async fn bind(port: u16) -> std::io::Result<TcpListener> {
+    let addr = SocketAddr::from(([127, 0, 0, 1], port));
+    let listener = TcpListener::bind(addr).await?;
+    log::info!("WebDAV is listening on http://{}", addr);
+    Ok(listener)
+}
+
+pub async fn serve(
+    listener: TcpListener,
+    vault: Arc<Vault>,
+    credentials: Arc<WebDavCredentials>,
+) -> std::io::Result<()> {
+    axum::serve(listener, router(vault, credentials).into_make_service()).await
+}
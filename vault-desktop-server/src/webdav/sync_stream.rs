@@ -0,0 +1,25 @@
+use std::{
+    pin::Pin,
+    sync::Mutex,
+    task::{Context, Poll},
+};
+
+use futures::Stream;
+
+/// Request bodies are not Sync but BoxAsyncRead has to be. The stream is only
+/// accessed through `&mut` so the mutex is never locked.
+pub struct SyncStream<S>(Mutex<S>);
+
+impl<S> SyncStream<S> {
+    pub fn new(stream: S) -> Self {
+        Self(Mutex::new(stream))
+    }
+}
+
+impl<S: Stream + Unpin> Stream for SyncStream<S> {
+    type Item = S::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(self.0.get_mut().unwrap()).poll_next(cx)
+    }
+}
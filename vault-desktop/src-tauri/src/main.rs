@@ -4,8 +4,8 @@
 use std::sync::Arc;
 
 use futures::{channel::oneshot, FutureExt, TryFutureExt};
-use tauri::{api::dialog::FileDialogBuilder, RunEvent};
-use vault_core::oauth2::OAuth2Config;
+use tauri::{api::dialog::FileDialogBuilder, GlobalShortcutManager, RunEvent};
+use vault_core::{locale, oauth2::OAuth2Config};
 use vault_desktop_server::{
     app::app,
     encryption::Encryption,
@@ -15,6 +15,8 @@ use vault_desktop_server::{
 use vault_native::vault::build_vault;
 use vault_web_api::web_vault_base::WebVaultBase;
 
+const PANIC_LOCK_SHORTCUT: &str = "CmdOrCtrl+Shift+Alt+L";
+
 struct TauriState {
     pub port: u16,
     pub app_secret: String,
@@ -22,6 +24,9 @@ struct TauriState {
 
 fn main() {
     let port = 1421;
+    let webdav_port = std::env::var("VAULT_WEBDAV_PORT")
+        .ok()
+        .and_then(|port| port.parse().ok());
 
     let base_url = String::from("https://app.koofr.net");
     // let base_url = String::from("https://127.0.0.1:3443");
@@ -49,7 +54,7 @@ fn main() {
         base_url: base_url.clone(),
         auth_base_url: oauth2_auth_base_url,
         client_id: oauth2_client_id,
-        client_secret: oauth2_client_secret,
+        client_secret: Some(oauth2_client_secret),
         redirect_uri: oauth2_redirect_uri,
     };
 
@@ -65,6 +70,8 @@ fn main() {
         vault.notifications_show(err);
     }
 
+    let panic_lock_vault = vault.clone();
+
     let web_vault = WebVaultBase::new(vault);
 
     web_vault.load();
@@ -113,6 +120,7 @@ fn main() {
         tokio_runtime,
         encryption,
         file_handlers,
+        webdav_port,
     ));
 
     tauri::Builder::default()
@@ -123,9 +131,29 @@ fn main() {
         ])
         .build(tauri::generate_context!())
         .expect("error while running tauri application")
-        .run(|_app, event| match event {
+        .run(move |app, event| match event {
             RunEvent::Ready => {
                 fix_current_dir();
+
+                let vault = panic_lock_vault.clone();
+
+                if let Err(err) =
+                    app.global_shortcut_manager()
+                        .register(PANIC_LOCK_SHORTCUT, move || {
+                            if let Err(err) = vault.panic_lock(false) {
+                                vault.notifications_show_error(&err);
+                            }
+                        })
+                {
+                    let message = panic_lock_vault.with_state(|_| {
+                        locale::message_args(
+                            "notification-panic-lock-shortcut-error",
+                            &[("error", err.to_string().into())],
+                        )
+                    });
+
+                    panic_lock_vault.notifications_show(message);
+                }
             }
             _ => {}
         });
@@ -1,12 +1,19 @@
-use std::{net::SocketAddr, sync::Arc};
+use std::{
+    net::SocketAddr,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
 use futures::FutureExt;
+use tokio::task::JoinHandle;
 
 use super::{
     app_state::AppState,
     context::Context,
     debug_interceptor::get_debug_interceptor,
     errors::FakeRemoteServerStartError,
+    faults::FaultProfile,
     files::objects::object_provider::BoxObjectProvider,
     router::build_router,
     server::{FakeRemoteServer, FakeRemoteServerListener},
@@ -18,17 +25,21 @@ pub struct FakeRemoteAppConfig {
     pub http_addr: SocketAddr,
     pub https_addr: SocketAddr,
     pub object_provider: Arc<BoxObjectProvider>,
+    /// State is persisted to and restored from the data path
+    pub data_path: Option<PathBuf>,
     pub user_id: String,
     pub mount_id: String,
     pub oauth2_access_token: String,
     pub oauth2_refresh_token: String,
     pub create_vault_repo: bool,
+    pub fault_profile: Option<FaultProfile>,
 }
 
 pub struct FakeRemoteApp {
     app_state: AppState,
     http_server: FakeRemoteServer,
     https_server: FakeRemoteServer,
+    state_saver: Mutex<Option<JoinHandle<()>>>,
 }
 
 impl FakeRemoteApp {
@@ -36,9 +47,19 @@ impl FakeRemoteApp {
         config: FakeRemoteAppConfig,
         tokio_runtime: Arc<tokio::runtime::Runtime>,
     ) -> Self {
-        let mut app_state = AppState::new(config.object_provider.clone());
+        let mut app_state = AppState::new(config.object_provider.clone(), config.data_path.clone());
+
+        let loaded = app_state.state_store.load().await.unwrap();
 
-        init_state(&app_state, config.clone()).await;
+        if loaded {
+            log::info!("State loaded from the data path");
+
+            app_state
+                .fault_injector
+                .set_profile(config.fault_profile.clone());
+        } else {
+            init_state(&app_state, config.clone()).await;
+        }
 
         let reset_app_state = app_state.clone();
         let reset_config = config.clone();
@@ -75,6 +96,7 @@ impl FakeRemoteApp {
             app_state,
             http_server,
             https_server,
+            state_saver: Mutex::new(None),
         }
     }
 
@@ -89,6 +111,22 @@ impl FakeRemoteApp {
             .start(build_router(self.app_state.clone()))
             .await?;
 
+        if self.app_state.state_store.is_persistent() {
+            let state_store = self.app_state.state_store.clone();
+
+            *self.state_saver.lock().unwrap() = Some(tokio::spawn(async move {
+                let mut interval = tokio::time::interval(Duration::from_secs(1));
+
+                loop {
+                    interval.tick().await;
+
+                    if let Err(err) = state_store.save_if_dirty().await {
+                        log::warn!("Failed to save state: {}", err);
+                    }
+                }
+            }));
+        }
+
         Ok((http_url, https_url))
     }
 
@@ -96,6 +134,14 @@ impl FakeRemoteApp {
         self.http_server.stop().await;
 
         self.https_server.stop().await;
+
+        if let Some(state_saver) = self.state_saver.lock().unwrap().take() {
+            state_saver.abort();
+        }
+
+        if let Err(err) = self.app_state.state_store.save().await {
+            log::warn!("Failed to save state: {}", err);
+        }
     }
 }
 
@@ -104,6 +150,7 @@ async fn init_state(
         state,
         users_service,
         vault_repos_create_service,
+        fault_injector,
         ..
     }: &AppState,
     FakeRemoteAppConfig {
@@ -112,9 +159,12 @@ async fn init_state(
         oauth2_access_token,
         oauth2_refresh_token,
         create_vault_repo,
+        fault_profile,
         ..
     }: FakeRemoteAppConfig,
 ) {
+    fault_injector.set_profile(fault_profile);
+
     let _ = users_service.create_user(Some(user_id.clone()), Some(mount_id.clone()));
 
     {
@@ -147,4 +197,8 @@ async fn reset(app_state: AppState, config: FakeRemoteAppConfig) {
     app_state.state.write().unwrap().reset();
 
     init_state(&app_state, config).await;
+
+    if let Err(err) = app_state.state_store.save().await {
+        log::warn!("Failed to save state: {}", err);
+    }
 }
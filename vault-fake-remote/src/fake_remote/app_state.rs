@@ -1,10 +1,15 @@
-use std::sync::{Arc, RwLock};
+use std::{
+    path::PathBuf,
+    sync::{Arc, RwLock},
+};
 
 use super::{
     eventstream,
+    faults::FaultInjector,
     files::{objects::object_provider::BoxObjectProvider, service::FilesService},
     interceptor::Interceptor,
     state::FakeRemoteState,
+    state_store::StateStore,
     users_service::UsersService,
     vault_repos_service::{VaultReposCreateService, VaultReposRemoveService},
 };
@@ -18,11 +23,13 @@ pub struct AppState {
     pub vault_repos_create_service: Arc<VaultReposCreateService>,
     pub vault_repos_remove_service: Arc<VaultReposRemoveService>,
     pub eventstream_listeners: Arc<eventstream::Listeners>,
+    pub fault_injector: Arc<FaultInjector>,
+    pub state_store: Arc<StateStore>,
     pub interceptor: Arc<Option<Interceptor>>,
 }
 
 impl AppState {
-    pub fn new(object_provider: Arc<BoxObjectProvider>) -> Self {
+    pub fn new(object_provider: Arc<BoxObjectProvider>, data_path: Option<PathBuf>) -> Self {
         let state = Arc::new(RwLock::new(FakeRemoteState::default()));
         let eventstream_listeners = Arc::new(eventstream::Listeners::new());
         let vault_repos_remove_service = Arc::new(VaultReposRemoveService::new(state.clone()));
@@ -37,6 +44,11 @@ impl AppState {
             state.clone(),
             files_service.clone(),
         ));
+        let state_store = Arc::new(StateStore::new(
+            state.clone(),
+            object_provider.clone(),
+            data_path,
+        ));
 
         Self {
             state,
@@ -46,6 +58,8 @@ impl AppState {
             vault_repos_create_service,
             vault_repos_remove_service,
             eventstream_listeners,
+            fault_injector: Arc::new(FaultInjector::new()),
+            state_store,
             interceptor: Default::default(),
         }
     }
@@ -247,6 +247,8 @@ pub fn get_debug_interceptor(
                 state.oauth2_access_tokens.clear();
                 state.oauth2_refresh_tokens.clear();
                 state.oauth2_codes.clear();
+                state.oauth2_code_challenges.clear();
+                state.oauth2_device_codes.clear();
 
                 return InterceptorResult::Response((StatusCode::OK, "ok").into_response());
             }
@@ -309,6 +311,10 @@ pub fn get_debug_interceptor(
                 );
             }
 
+            // handled by the router
+            "/debug/faults" => return InterceptorResult::Ignore,
+            path if path.starts_with("/debug/snapshots") => return InterceptorResult::Ignore,
+
             "/debug" => {
                 state.write().unwrap().uploads_pause.take();
 
@@ -26,6 +26,7 @@ pub enum ApiErrorCode {
     VaultReposAlreadyExists,
     VaultReposMountNotAllowed,
     VaultReposMaxTotalLimitExceeded,
+    QuotaExceeded,
     Other,
 }
 
@@ -35,6 +36,9 @@ pub enum FakeRemoteError {
     Unauthorized(String),
     #[error("bad request: {0}")]
     BadRequest(String),
+    /// OAuth 2 error code, e.g. authorization_pending
+    #[error("oauth2 error: {0}")]
+    OAuth2Error(String),
     #[error("{0:?}: {1:?}: {2}")]
     ApiError(
         StatusCode,
@@ -65,6 +69,11 @@ impl IntoResponse for FakeRemoteError {
                 message,
                 None,
             ),
+            Self::OAuth2Error(error) => (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({ "error": error })),
+            )
+                .into_response(),
             Self::ApiError(status_code, code, message, headers) => {
                 api_error_response(status_code, code, message, headers)
             }
@@ -2,11 +2,13 @@ use std::{
     collections::HashSet,
     str::FromStr,
     sync::{Arc, RwLock},
+    time::Duration,
 };
 
 use axum::{
     extract::ws::{Message as WsMessage, WebSocket, WebSocketUpgrade},
     response::Response,
+    Extension,
 };
 use futures::{
     stream::{SplitSink, SplitStream},
@@ -21,6 +23,7 @@ use crate::fake_remote::{
         get_authorization_access_token, get_user_id_by_access_token, ExtractEventstreamListeners,
         ExtractState,
     },
+    faults::EventstreamDisconnectAfter,
     files::Path,
     state::FakeRemoteState,
 };
@@ -30,10 +33,13 @@ use super::{Listeners, Message, Request, Subject};
 pub async fn eventstream(
     ExtractState(state): ExtractState,
     ExtractEventstreamListeners(listeners): ExtractEventstreamListeners,
+    disconnect_after: Option<Extension<EventstreamDisconnectAfter>>,
     ws: WebSocketUpgrade,
 ) -> Result<Response, FakeRemoteError> {
-    Ok(ws.on_upgrade(move |socket| async {
-        let mut handler = EventstreamHandler::new(state, listeners, socket);
+    let disconnect_after = disconnect_after.map(|Extension(disconnect_after)| disconnect_after.0);
+
+    Ok(ws.on_upgrade(move |socket| async move {
+        let mut handler = EventstreamHandler::new(state, listeners, socket, disconnect_after);
 
         match handler.handle().await {
             Ok(()) => {}
@@ -52,6 +58,7 @@ struct EventstreamHandler {
     socket_reader: SplitStream<WebSocket>,
     sender: mpsc::Sender<Message>,
     receiver: mpsc::Receiver<Message>,
+    disconnect_after: Option<Duration>,
 }
 
 impl EventstreamHandler {
@@ -59,6 +66,7 @@ impl EventstreamHandler {
         state: Arc<RwLock<FakeRemoteState>>,
         listeners: Arc<Listeners>,
         socket: WebSocket,
+        disconnect_after: Option<Duration>,
     ) -> Self {
         let listener_ids = HashSet::new();
 
@@ -74,16 +82,31 @@ impl EventstreamHandler {
             socket_reader,
             sender,
             receiver,
+            disconnect_after,
         }
     }
 
     async fn handle(&mut self) -> Result<(), FakeRemoteError> {
+        let disconnect_after = self.disconnect_after;
+        let disconnect = async move {
+            match disconnect_after {
+                Some(duration) => tokio::time::sleep(duration).await,
+                None => futures::future::pending().await,
+            }
+        };
+        tokio::pin!(disconnect);
+
         loop {
             tokio::select! {
                 res = self.socket_reader.next() => if !self.handle_socket_message(res).await? {
                     return Ok(());
                 },
-                res = self.receiver.recv() => self.handle_receiver_message(res).await?
+                res = self.receiver.recv() => self.handle_receiver_message(res).await?,
+                _ = &mut disconnect => {
+                    log::info!("eventstream disconnected by fault profile");
+
+                    return Ok(());
+                }
             }
         }
     }
@@ -12,8 +12,10 @@ use super::{
     context::Context,
     errors::FakeRemoteError,
     eventstream,
+    faults::FaultInjector,
     files::service::FilesService,
     state::FakeRemoteState,
+    state_store::StateStore,
     users_service::UsersService,
     vault_repos_service::{VaultReposCreateService, VaultReposRemoveService},
 };
@@ -150,3 +152,25 @@ impl FromRequestParts<AppState> for ExtractEventstreamListeners {
         Ok(Self(state.eventstream_listeners.clone()))
     }
 }
+
+pub struct ExtractFaultInjector(pub Arc<FaultInjector>);
+
+#[async_trait]
+impl FromRequestParts<AppState> for ExtractFaultInjector {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(_: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        Ok(Self(state.fault_injector.clone()))
+    }
+}
+
+pub struct ExtractStateStore(pub Arc<StateStore>);
+
+#[async_trait]
+impl FromRequestParts<AppState> for ExtractStateStore {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(_: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        Ok(Self(state.state_store.clone()))
+    }
+}
@@ -0,0 +1,409 @@
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+use axum::{
+    body::{BoxBody, Bytes, HttpBody, StreamBody},
+    extract::State,
+    http::Request,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use http::{header, Method, StatusCode};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    app_state::AppState,
+    errors::{ApiErrorCode, FakeRemoteError},
+};
+
+/// Faults injected into matching requests. Rules are evaluated in order,
+/// latencies of all firing rules add up and the first firing non-latency
+/// fault wins.
+///
+/// Every rule rolls for the n-th request it matches with the seed, the rule
+/// index and n, so a sequential request flow gets the same faults on every
+/// run.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FaultProfile {
+    #[serde(default)]
+    pub seed: u64,
+    #[serde(default)]
+    pub rules: Vec<FaultRule>,
+}
+
+impl FaultProfile {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FaultRule {
+    /// Path pattern. `*` matches one path segment and `**` matches any number
+    /// of segments (e.g. "/content/api/v2.1/mounts/*/files/**").
+    pub route: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+    #[serde(default = "default_probability")]
+    pub probability: f64,
+    /// The rule stops firing after it fired `times` times.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub times: Option<u64>,
+    pub fault: Fault,
+}
+
+fn default_probability() -> f64 {
+    1.0
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all_fields = "camelCase")]
+pub enum Fault {
+    /// Uniformly distributed delay before the request is handled.
+    Latency {
+        min_ms: u64,
+        max_ms: u64,
+    },
+    Error {
+        status: u16,
+    },
+    /// 429 or 503 with a Retry-After header.
+    RateLimited {
+        #[serde(default = "default_rate_limited_status")]
+        status: u16,
+        retry_after_secs: u64,
+    },
+    /// The response body is aborted after `after_bytes` bytes.
+    TruncatedBody {
+        after_bytes: usize,
+    },
+    /// The eventstream websocket is closed `after_ms` after it was opened.
+    EventstreamDisconnect {
+        after_ms: u64,
+    },
+    /// 401 as if the access token expired, clients have to refresh it.
+    TokenExpired,
+}
+
+fn default_rate_limited_status() -> u16 {
+    429
+}
+
+/// Set as a request extension for the eventstream handler.
+#[derive(Debug, Clone, Copy)]
+pub struct EventstreamDisconnectAfter(pub Duration);
+
+#[derive(Debug, Default)]
+pub struct FaultSelection {
+    pub delay: Duration,
+    pub fault: Option<Fault>,
+}
+
+#[derive(Debug, Default)]
+struct FaultInjectorState {
+    profile: Option<FaultProfile>,
+    // number of matched requests per rule
+    counters: Vec<u64>,
+    // number of fired faults per rule
+    fired: Vec<u64>,
+}
+
+#[derive(Debug, Default)]
+pub struct FaultInjector {
+    state: Mutex<FaultInjectorState>,
+}
+
+impl FaultInjector {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn get_profile(&self) -> Option<FaultProfile> {
+        self.state.lock().unwrap().profile.clone()
+    }
+
+    /// Setting a profile restarts the rolls from the beginning.
+    pub fn set_profile(&self, profile: Option<FaultProfile>) {
+        let mut state = self.state.lock().unwrap();
+
+        let rules_len = profile
+            .as_ref()
+            .map(|profile| profile.rules.len())
+            .unwrap_or(0);
+
+        state.counters = vec![0; rules_len];
+        state.fired = vec![0; rules_len];
+        state.profile = profile;
+    }
+
+    pub fn select(&self, method: &Method, path: &str) -> FaultSelection {
+        let mut state = self.state.lock().unwrap();
+        let FaultInjectorState {
+            profile,
+            counters,
+            fired,
+        } = &mut *state;
+
+        let mut selection = FaultSelection::default();
+
+        let profile = match profile {
+            Some(profile) => profile,
+            None => return selection,
+        };
+
+        for (rule_index, rule) in profile.rules.iter().enumerate() {
+            if !rule_matches(rule, method, path) {
+                continue;
+            }
+
+            let n = counters[rule_index];
+            counters[rule_index] += 1;
+
+            if roll(profile.seed, rule_index, n, 0) >= rule.probability
+                || rule
+                    .times
+                    .filter(|times| fired[rule_index] >= *times)
+                    .is_some()
+            {
+                continue;
+            }
+
+            fired[rule_index] += 1;
+
+            match &rule.fault {
+                Fault::Latency { min_ms, max_ms } => {
+                    let range = max_ms.saturating_sub(*min_ms) as f64;
+                    let ms = min_ms + (roll(profile.seed, rule_index, n, 1) * range).round() as u64;
+
+                    selection.delay += Duration::from_millis(ms);
+                }
+                fault => {
+                    selection.fault = Some(fault.clone());
+
+                    break;
+                }
+            }
+        }
+
+        selection
+    }
+}
+
+fn rule_matches(rule: &FaultRule, method: &Method, path: &str) -> bool {
+    if let Some(rule_method) = &rule.method {
+        if !rule_method.eq_ignore_ascii_case(method.as_str()) {
+            return false;
+        }
+    }
+
+    let pattern = rule
+        .route
+        .trim_start_matches('/')
+        .split('/')
+        .collect::<Vec<_>>();
+    let segments = path.trim_start_matches('/').split('/').collect::<Vec<_>>();
+
+    route_matches(&pattern, &segments)
+}
+
+fn route_matches(pattern: &[&str], segments: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => segments.is_empty(),
+        Some((&"**", rest)) => {
+            (0..=segments.len()).any(|skip| route_matches(rest, &segments[skip..]))
+        }
+        Some((part, rest)) => match segments.split_first() {
+            Some((segment, segments_rest)) => {
+                (*part == "*" || part == segment) && route_matches(rest, segments_rest)
+            }
+            None => false,
+        },
+    }
+}
+
+fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9E3779B97F4A7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
+
+/// Returns a number in [0, 1).
+fn roll(seed: u64, rule_index: usize, n: u64, salt: u64) -> f64 {
+    let x = splitmix64(splitmix64(splitmix64(seed ^ salt) ^ rule_index as u64) ^ n);
+
+    (x >> 11) as f64 / (1u64 << 53) as f64
+}
+
+fn truncate_body(body: BoxBody, after_bytes: usize) -> BoxBody {
+    let stream = futures::stream::unfold(Some((body, after_bytes)), |state| async move {
+        let (mut body, remaining) = state?;
+
+        if remaining == 0 {
+            return Some((
+                Err(axum::Error::new(std::io::Error::from(
+                    std::io::ErrorKind::BrokenPipe,
+                ))),
+                None,
+            ));
+        }
+
+        match body.data().await? {
+            Ok(chunk) => {
+                let len = chunk.len().min(remaining);
+                let chunk: Bytes = chunk.slice(..len);
+
+                Some((Ok(chunk), Some((body, remaining - len))))
+            }
+            Err(err) => Some((Err(err), None)),
+        }
+    });
+
+    axum::body::boxed(StreamBody::new(stream))
+}
+
+fn fault_error_response(status: u16, retry_after_secs: Option<u64>) -> Response {
+    FakeRemoteError::ApiError(
+        StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+        ApiErrorCode::Other,
+        "injected fault".into(),
+        retry_after_secs.map(|retry_after_secs| {
+            HashMap::from([(header::RETRY_AFTER, retry_after_secs.to_string())])
+        }),
+    )
+    .into_response()
+}
+
+pub async fn faults_middleware<B>(
+    State(state): State<AppState>,
+    mut request: Request<B>,
+    next: Next<B>,
+) -> Response {
+    if request.method() == Method::OPTIONS || request.uri().path().starts_with("/debug") {
+        return next.run(request).await;
+    }
+
+    let selection = state
+        .fault_injector
+        .select(request.method(), request.uri().path());
+
+    if !selection.delay.is_zero() {
+        tokio::time::sleep(selection.delay).await;
+    }
+
+    match selection.fault {
+        None | Some(Fault::Latency { .. }) => next.run(request).await,
+        Some(Fault::Error { status }) => fault_error_response(status, None),
+        Some(Fault::RateLimited {
+            status,
+            retry_after_secs,
+        }) => fault_error_response(status, Some(retry_after_secs)),
+        Some(Fault::TruncatedBody { after_bytes }) => {
+            let (parts, body) = next.run(request).await.into_parts();
+
+            Response::from_parts(parts, truncate_body(body, after_bytes))
+        }
+        Some(Fault::EventstreamDisconnect { after_ms }) => {
+            request
+                .extensions_mut()
+                .insert(EventstreamDisconnectAfter(Duration::from_millis(after_ms)));
+
+            next.run(request).await
+        }
+        Some(Fault::TokenExpired) => {
+            FakeRemoteError::Unauthorized("access token expired".into()).into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::Method;
+
+    use super::{Fault, FaultInjector, FaultProfile, FaultRule};
+
+    fn rule(route: &str, probability: f64, fault: Fault) -> FaultRule {
+        FaultRule {
+            route: route.into(),
+            method: None,
+            probability,
+            times: None,
+            fault,
+        }
+    }
+
+    #[test]
+    fn test_route_matches() {
+        let matches = |route: &str, path: &str| {
+            let injector = FaultInjector::new();
+            injector.set_profile(Some(FaultProfile {
+                seed: 0,
+                rules: vec![rule(route, 1.0, Fault::TokenExpired)],
+            }));
+            injector.select(&Method::GET, path).fault.is_some()
+        };
+
+        assert!(matches("**", "/api/v2.1/user"));
+        assert!(matches("/api/v2.1/user", "/api/v2.1/user"));
+        assert!(!matches("/api/v2.1/user", "/api/v2.1/user/bookmarks"));
+        assert!(matches("/api/v2.1/mounts/*", "/api/v2.1/mounts/m1"));
+        assert!(!matches("/api/v2.1/mounts/*", "/api/v2.1/mounts/m1/bundle"));
+        assert!(matches(
+            "/api/v2.1/mounts/*/files/**",
+            "/api/v2.1/mounts/m1/files/info"
+        ));
+        assert!(matches(
+            "/**/files/get",
+            "/content/api/v2.1/mounts/m1/files/get"
+        ));
+        assert!(!matches(
+            "/**/files/get",
+            "/content/api/v2.1/mounts/m1/files/put"
+        ));
+    }
+
+    #[test]
+    fn test_select_reproducible() {
+        let profile = FaultProfile::from_json(
+            r#"{
+                "seed": 42,
+                "rules": [
+                    {"route": "**", "fault": {"type": "Latency", "minMs": 10, "maxMs": 50}},
+                    {"route": "**", "method": "GET", "probability": 0.3, "fault": {"type": "Error", "status": 503}}
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let run = || {
+            let injector = FaultInjector::new();
+            injector.set_profile(Some(profile.clone()));
+
+            (0..100)
+                .map(|_| {
+                    let selection = injector.select(&Method::GET, "/api/v2.1/user");
+                    (selection.delay, selection.fault)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let selections = run();
+
+        assert_eq!(selections, run());
+        assert!(selections
+            .iter()
+            .all(|(delay, _)| delay.as_millis() >= 10 && delay.as_millis() <= 50));
+
+        let errors = selections
+            .iter()
+            .filter(|(_, fault)| fault.is_some())
+            .count();
+        assert!(errors > 10 && errors < 50, "errors: {}", errors);
+
+        let injector = FaultInjector::new();
+        injector.set_profile(Some(profile));
+        assert!(injector
+            .select(&Method::PUT, "/api/v2.1/user")
+            .fault
+            .is_none());
+    }
+}
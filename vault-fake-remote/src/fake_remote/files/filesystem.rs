@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 use http::StatusCode;
+use serde::{Deserialize, Serialize};
 use vault_core::{
     remote::{models, remote::RemoteFileTagsSetConditions},
     remote_files_tags::set_tags::set_tags,
@@ -74,7 +75,7 @@ pub struct FilesTagsSetConditions {
     pub if_old_tags: Option<HashMap<String, Vec<String>>>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FilesystemFile {
     pub file: models::FilesFile,
     // children paths
@@ -82,20 +83,51 @@ pub struct FilesystemFile {
     pub object_id: Option<String>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct FilesystemObject {
     pub refs: usize,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Filesystem {
     // paths to files
     pub files: HashMap<NormalizedPath, FilesystemFile>,
     // object ids to objects
     pub objects: HashMap<String, FilesystemObject>,
+    // quota in bytes, None is unlimited
+    pub space_total: Option<i64>,
 }
 
 impl Filesystem {
+    /// Copies count as separate files, the same as on the real remote.
+    pub fn space_used(&self) -> i64 {
+        self.files.values().map(|file| file.file.size).sum()
+    }
+
+    pub fn check_space(&self, added_size: i64) -> Result<(), FakeRemoteError> {
+        match self.space_total {
+            Some(space_total) if added_size > 0 && self.space_used() + added_size > space_total => {
+                Err(FakeRemoteError::ApiError(
+                    StatusCode::INSUFFICIENT_STORAGE,
+                    ApiErrorCode::QuotaExceeded,
+                    "Quota exceeded".into(),
+                    None,
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn get_tree_size(&self, file: &FilesystemFile) -> i64 {
+        file.file.size
+            + file
+                .children
+                .iter()
+                .filter_map(|child_path| self.files.get(child_path))
+                .map(|child| self.get_tree_size(child))
+                .sum::<i64>()
+    }
+
     pub fn get_file(&self, path: &NormalizedPath) -> Result<&FilesystemFile, FakeRemoteError> {
         self.files.get(&path).ok_or(FakeRemoteError::ApiError(
             StatusCode::NOT_FOUND,
@@ -239,6 +271,17 @@ impl Filesystem {
 
         let full_path = parent_path.join_name(&name);
 
+        let replaced_size = match conflict_resolution {
+            CreateFileConflictResolution::Overwrite { .. } => self
+                .files
+                .get(&full_path.normalize())
+                .map(|file| file.file.size)
+                .unwrap_or(0),
+            _ => 0,
+        };
+
+        self.check_space(size - replaced_size)?;
+
         let existing_file = match conflict_resolution {
             CreateFileConflictResolution::Overwrite { .. } => {
                 if self.files.contains_key(&full_path.normalize()) {
@@ -422,6 +465,8 @@ impl Filesystem {
 
         let file = self.get_file(&path.normalize())?.clone();
 
+        self.check_space(self.get_tree_size(&file))?;
+
         let to_file = models::FilesFile {
             name: RemoteName(to_path.name().unwrap().0),
             ..file.file.clone()
@@ -1,4 +1,10 @@
-use std::{fmt::Debug, io::SeekFrom, ops::RangeInclusive, path::PathBuf, pin::Pin};
+use std::{
+    fmt::Debug,
+    io::SeekFrom,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    pin::Pin,
+};
 
 use async_trait::async_trait;
 use futures::{AsyncRead, AsyncReadExt, AsyncWriteExt};
@@ -7,18 +13,36 @@ use tokio_util::compat::{TokioAsyncReadCompatExt, TokioAsyncWriteCompatExt};
 
 use super::object_provider::{ObjectProvider, ObjectProviderError};
 
+/// Objects are stored in `<data_path>/objects` and snapshot objects in
+/// `<data_path>/snapshots/<name>/objects`.
 #[derive(Debug)]
 pub struct FsObjectProvider {
     data_path: PathBuf,
 }
 
 impl FsObjectProvider {
-    pub fn new(data_path: PathBuf) -> Self {
-        Self { data_path }
+    pub fn new(data_path: PathBuf) -> Result<Self, std::io::Error> {
+        std::fs::create_dir_all(data_path.join("objects"))?;
+
+        Ok(Self { data_path })
     }
 
     fn get_object_path(&self, object_id: &str) -> PathBuf {
-        self.data_path.join(&object_id)
+        self.data_path.join("objects").join(&object_id)
+    }
+
+    fn get_snapshot_objects_path(&self, name: &str) -> PathBuf {
+        self.data_path.join("snapshots").join(name).join("objects")
+    }
+}
+
+/// Hard links are instant even for large fixtures, copy is a fallback for
+/// filesystems without them.
+async fn link_or_copy(from: &Path, to: &Path) -> Result<(), std::io::Error> {
+    match tokio::fs::hard_link(from, to).await {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => Ok(()),
+        Err(_) => tokio::fs::copy(from, to).await.map(|_| ()),
     }
 }
 
@@ -66,4 +90,51 @@ impl ObjectProvider for FsObjectProvider {
 
         Ok(tokio::fs::remove_file(&path).await?)
     }
+
+    async fn snapshot_create(
+        &self,
+        name: String,
+        object_ids: Vec<String>,
+    ) -> Result<(), ObjectProviderError> {
+        let snapshot_objects_path = self.get_snapshot_objects_path(&name);
+
+        match tokio::fs::remove_dir_all(&snapshot_objects_path).await {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => return Err(err.into()),
+            _ => {}
+        }
+
+        tokio::fs::create_dir_all(&snapshot_objects_path).await?;
+
+        for object_id in object_ids {
+            link_or_copy(
+                &self.get_object_path(&object_id),
+                &snapshot_objects_path.join(&object_id),
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn snapshot_restore(
+        &self,
+        name: String,
+        object_ids: Vec<String>,
+    ) -> Result<(), ObjectProviderError> {
+        let snapshot_objects_path = self.get_snapshot_objects_path(&name);
+
+        for object_id in object_ids {
+            link_or_copy(
+                &snapshot_objects_path.join(&object_id),
+                &self.get_object_path(&object_id),
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn snapshot_delete(&self, name: String) -> Result<(), ObjectProviderError> {
+        Ok(tokio::fs::remove_dir_all(self.get_snapshot_objects_path(&name)).await?)
+    }
 }
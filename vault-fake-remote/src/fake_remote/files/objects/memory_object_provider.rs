@@ -1,4 +1,10 @@
-use std::{collections::HashMap, io::SeekFrom, ops::RangeInclusive, pin::Pin, sync::Mutex};
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    io::SeekFrom,
+    ops::RangeInclusive,
+    pin::Pin,
+    sync::Mutex,
+};
 
 use async_trait::async_trait;
 use futures::{io::Cursor, AsyncRead, AsyncReadExt, AsyncSeekExt};
@@ -7,16 +13,23 @@ use super::object_provider::{ObjectProvider, ObjectProviderError};
 
 pub struct MemoryObjectProvider {
     objects: Mutex<HashMap<String, Vec<u8>>>,
+    // snapshot names to objects
+    snapshots: Mutex<HashMap<String, HashMap<String, Vec<u8>>>>,
 }
 
 impl MemoryObjectProvider {
     pub fn new() -> Self {
         Self {
             objects: Default::default(),
+            snapshots: Default::default(),
         }
     }
 }
 
+fn not_found_error() -> ObjectProviderError {
+    ObjectProviderError::IOError(std::io::Error::from(std::io::ErrorKind::NotFound).into())
+}
+
 impl std::fmt::Debug for MemoryObjectProvider {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("MemoryObjectProvider").finish()
@@ -36,11 +49,7 @@ impl ObjectProvider for MemoryObjectProvider {
             .unwrap()
             .get(&object_id)
             .cloned()
-            .ok_or_else(|| {
-                ObjectProviderError::IOError(
-                    std::io::Error::from(std::io::ErrorKind::NotFound).into(),
-                )
-            })?;
+            .ok_or_else(not_found_error)?;
 
         let mut reader = Cursor::new(buf);
 
@@ -75,9 +84,67 @@ impl ObjectProvider for MemoryObjectProvider {
 
         match object {
             Some(_) => Ok(()),
-            None => Err(ObjectProviderError::IOError(
-                std::io::Error::from(std::io::ErrorKind::NotFound).into(),
-            )),
+            None => Err(not_found_error()),
+        }
+    }
+
+    async fn snapshot_create(
+        &self,
+        name: String,
+        object_ids: Vec<String>,
+    ) -> Result<(), ObjectProviderError> {
+        let snapshot_objects = {
+            let objects = self.objects.lock().unwrap();
+
+            object_ids
+                .into_iter()
+                .map(|object_id| {
+                    let object = objects
+                        .get(&object_id)
+                        .cloned()
+                        .ok_or_else(not_found_error)?;
+
+                    Ok((object_id, object))
+                })
+                .collect::<Result<HashMap<_, _>, ObjectProviderError>>()?
+        };
+
+        self.snapshots
+            .lock()
+            .unwrap()
+            .insert(name, snapshot_objects);
+
+        Ok(())
+    }
+
+    async fn snapshot_restore(
+        &self,
+        name: String,
+        object_ids: Vec<String>,
+    ) -> Result<(), ObjectProviderError> {
+        let snapshots = self.snapshots.lock().unwrap();
+        let snapshot_objects = snapshots.get(&name).ok_or_else(not_found_error)?;
+
+        let mut objects = self.objects.lock().unwrap();
+
+        for object_id in object_ids {
+            if let Entry::Vacant(entry) = objects.entry(object_id) {
+                let object = snapshot_objects
+                    .get(entry.key())
+                    .cloned()
+                    .ok_or_else(not_found_error)?;
+
+                entry.insert(object);
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn snapshot_delete(&self, name: String) -> Result<(), ObjectProviderError> {
+        match self.snapshots.lock().unwrap().remove(&name) {
+            Some(_) => Ok(()),
+            None => Err(not_found_error()),
         }
     }
 }
@@ -29,6 +29,22 @@ pub trait ObjectProvider: Debug {
         reader: Pin<Box<dyn AsyncRead + Send + Sync + 'static>>,
     ) -> Result<u64, ObjectProviderError>;
     async fn delete(&self, object_id: String) -> Result<(), ObjectProviderError>;
+
+    /// Keeps the objects for a named state snapshot (replacing an existing
+    /// one). Objects are never modified, only deleted, so the snapshot copies
+    /// stay valid.
+    async fn snapshot_create(
+        &self,
+        name: String,
+        object_ids: Vec<String>,
+    ) -> Result<(), ObjectProviderError>;
+    /// Brings back the snapshot objects that were deleted since.
+    async fn snapshot_restore(
+        &self,
+        name: String,
+        object_ids: Vec<String>,
+    ) -> Result<(), ObjectProviderError>;
+    async fn snapshot_delete(&self, name: String) -> Result<(), ObjectProviderError>;
 }
 
 pub type BoxObjectProvider = Box<dyn ObjectProvider + Send + Sync>;
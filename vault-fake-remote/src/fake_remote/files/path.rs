@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-use serde::de;
+use serde::{de, Deserialize, Serialize};
 use vault_core::{common::errors::InvalidPathError, utils::path_utils};
 
 use super::Name;
@@ -83,7 +83,7 @@ impl<'de> de::Deserialize<'de> for Path {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct NormalizedPath(pub String);
 
 impl NormalizedPath {
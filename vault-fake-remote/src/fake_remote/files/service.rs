@@ -103,9 +103,22 @@ impl FilesService {
             )]
             .into(),
             objects: HashMap::new(),
+            space_total: None,
         }
     }
 
+    pub fn set_space_total(
+        &self,
+        mount_id: &str,
+        space_total: Option<i64>,
+    ) -> Result<(), FakeRemoteError> {
+        let mut state = self.state.write().unwrap();
+
+        self.get_filesystem_mut(&mut state, mount_id)?.space_total = space_total;
+
+        Ok(())
+    }
+
     pub fn bundle(&self, mount_id: &str, path: &Path) -> Result<models::Bundle, FakeRemoteError> {
         let state = self.state.read().unwrap();
 
@@ -197,8 +210,19 @@ impl FilesService {
                 hash,
                 HashMap::new(),
                 &conflict_resolution,
-                object_id,
-            )?
+                object_id.clone(),
+            )
+        };
+
+        let file = match file {
+            Ok(file) => file,
+            Err(err) => {
+                if let Err(err) = self.delete_object_file(object_id).await {
+                    log::warn!("Failed to delete local file: {:?}", err);
+                }
+
+                return Err(err);
+            }
         };
 
         self.cleanup_objects(mount_id).await?;
@@ -7,9 +7,11 @@ use axum::{
     response::{IntoResponse, Response},
     Form, Json,
 };
+use data_encoding::BASE64URL_NOPAD;
 use futures::TryStreamExt;
-use http::{header, HeaderMap, HeaderName, HeaderValue};
+use http::{header, HeaderMap, HeaderName, HeaderValue, Uri};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use urlencoding::encode;
 use vault_core::{
     remote::models,
@@ -21,11 +23,13 @@ use super::{
     context::Context,
     errors::{ApiErrorCode, FakeRemoteError},
     extract::{
-        ExtractFilesService, ExtractState, ExtractVaultReposCreateService,
-        ExtractVaultReposRemoveService,
+        ExtractFaultInjector, ExtractFilesService, ExtractState, ExtractStateStore,
+        ExtractVaultReposCreateService, ExtractVaultReposRemoveService,
     },
+    faults::FaultProfile,
     files,
-    state::FakeRemoteState,
+    state::{FakeRemoteState, OAuth2DeviceCode, OAuth2DeviceCodeStatus},
+    state_store::SnapshotInfo,
 };
 
 static PROFILE_PICTURE_PNG: &'static [u8] = &[
@@ -48,6 +52,8 @@ pub struct OAuth2AuthQuery {
     redirect_uri: String,
     state: String,
     user_id: Option<String>,
+    code_challenge: Option<String>,
+    code_challenge_method: Option<String>,
 }
 
 pub async fn oauth2_auth(
@@ -61,6 +67,15 @@ pub async fn oauth2_auth(
         .or(state.default_user_id.clone())
         .ok_or(FakeRemoteError::BadRequest("missing user id".into()))?;
 
+    if let Some(code_challenge_method) = &query.code_challenge_method {
+        if code_challenge_method != "S256" {
+            return Err(FakeRemoteError::BadRequest(format!(
+                "unsupported code_challenge_method: {}",
+                code_challenge_method
+            )));
+        }
+    }
+
     let refresh_token = uuid::Uuid::new_v4().to_string();
     let code = uuid::Uuid::new_v4().to_string();
 
@@ -69,6 +84,12 @@ pub async fn oauth2_auth(
         .insert(refresh_token.clone(), user_id);
     state.oauth2_codes.insert(code.clone(), refresh_token);
 
+    if let Some(code_challenge) = query.code_challenge {
+        state
+            .oauth2_code_challenges
+            .insert(code.clone(), code_challenge);
+    }
+
     let uri = format!(
         "{}?code={}&state={}",
         query.redirect_uri,
@@ -109,9 +130,14 @@ pub async fn oauth2_logout(
 #[serde(tag = "grant_type")]
 pub enum OAuth2TokenForm {
     #[serde(rename = "authorization_code")]
-    AuthorizationCode { code: String },
+    AuthorizationCode {
+        code: String,
+        code_verifier: Option<String>,
+    },
     #[serde(rename = "refresh_token")]
     RefreshToken { refresh_token: String },
+    #[serde(rename = "urn:ietf:params:oauth:grant-type:device_code")]
+    DeviceCode { device_code: String },
 }
 
 #[derive(Serialize)]
@@ -128,11 +154,55 @@ pub async fn oauth2_token(
     let mut state = state.write().unwrap();
 
     let refresh_token = match form {
-        OAuth2TokenForm::AuthorizationCode { code } => state
-            .oauth2_codes
-            .remove(&code)
-            .ok_or(FakeRemoteError::Unauthorized("invalid grant".into()))?,
+        OAuth2TokenForm::AuthorizationCode {
+            code,
+            code_verifier,
+        } => {
+            let refresh_token = state
+                .oauth2_codes
+                .remove(&code)
+                .ok_or(FakeRemoteError::Unauthorized("invalid grant".into()))?;
+
+            if let Some(code_challenge) = state.oauth2_code_challenges.remove(&code) {
+                let code_verifier = code_verifier.ok_or(FakeRemoteError::Unauthorized(
+                    "missing code verifier".into(),
+                ))?;
+
+                if BASE64URL_NOPAD.encode(&Sha256::digest(code_verifier.as_bytes()))
+                    != code_challenge
+                {
+                    return Err(FakeRemoteError::Unauthorized(
+                        "invalid code verifier".into(),
+                    ));
+                }
+            }
+
+            refresh_token
+        }
         OAuth2TokenForm::RefreshToken { refresh_token } => refresh_token,
+        OAuth2TokenForm::DeviceCode { device_code } => {
+            let status = state
+                .oauth2_device_codes
+                .get(&device_code)
+                .map(|device_authorization| device_authorization.status.clone())
+                .ok_or(FakeRemoteError::OAuth2Error("invalid_grant".into()))?;
+
+            match status {
+                OAuth2DeviceCodeStatus::Pending => {
+                    return Err(FakeRemoteError::OAuth2Error("authorization_pending".into()));
+                }
+                OAuth2DeviceCodeStatus::Approved { refresh_token } => {
+                    state.oauth2_device_codes.remove(&device_code);
+
+                    refresh_token
+                }
+                OAuth2DeviceCodeStatus::Denied => {
+                    state.oauth2_device_codes.remove(&device_code);
+
+                    return Err(FakeRemoteError::OAuth2Error("access_denied".into()));
+                }
+            }
+        }
     };
 
     let user_id = state
@@ -154,6 +224,104 @@ pub async fn oauth2_token(
     }))
 }
 
+#[derive(Serialize)]
+pub struct OAuth2DeviceAuthorization {
+    pub device_code: String,
+    pub user_code: String,
+    pub verification_uri: String,
+    pub verification_uri_complete: String,
+    pub expires_in: i32,
+    pub interval: i32,
+}
+
+pub async fn oauth2_device_code(
+    ExtractState(state): ExtractState,
+    uri: Uri,
+    headers: HeaderMap,
+) -> Result<Json<OAuth2DeviceAuthorization>, FakeRemoteError> {
+    let mut state = state.write().unwrap();
+
+    // http2 requests have the authority in the uri instead of the host header
+    let host = uri
+        .authority()
+        .map(|authority| authority.to_string())
+        .or_else(|| {
+            headers
+                .get(header::HOST)
+                .and_then(|host| host.to_str().ok())
+                .map(str::to_owned)
+        })
+        .ok_or(FakeRemoteError::BadRequest("missing host".into()))?;
+
+    let device_code = uuid::Uuid::new_v4().to_string();
+    let user_code = uuid::Uuid::new_v4().simple().to_string()[..8].to_uppercase();
+    let verification_uri = format!("https://{}/oauth2/device", host);
+    let verification_uri_complete = format!("{}?user_code={}", verification_uri, user_code);
+
+    state.oauth2_device_codes.insert(
+        device_code.clone(),
+        OAuth2DeviceCode {
+            user_code: user_code.clone(),
+            status: OAuth2DeviceCodeStatus::Pending,
+        },
+    );
+
+    Ok(Json(OAuth2DeviceAuthorization {
+        device_code,
+        user_code,
+        verification_uri,
+        verification_uri_complete,
+        expires_in: 600,
+        interval: 1,
+    }))
+}
+
+#[derive(Deserialize)]
+pub struct OAuth2DeviceQuery {
+    user_code: String,
+    user_id: Option<String>,
+    deny: Option<bool>,
+}
+
+/// Approves (or denies) the device authorization as the user would on
+/// another device.
+pub async fn oauth2_device(
+    ExtractState(state): ExtractState,
+    Query(query): Query<OAuth2DeviceQuery>,
+) -> Result<StatusCode, FakeRemoteError> {
+    let mut state = state.write().unwrap();
+
+    let user_id = query
+        .user_id
+        .or(state.default_user_id.clone())
+        .ok_or(FakeRemoteError::BadRequest("missing user id".into()))?;
+
+    let status = if query.deny.unwrap_or(false) {
+        OAuth2DeviceCodeStatus::Denied
+    } else {
+        let refresh_token = uuid::Uuid::new_v4().to_string();
+
+        state
+            .oauth2_refresh_tokens
+            .insert(refresh_token.clone(), user_id);
+
+        OAuth2DeviceCodeStatus::Approved { refresh_token }
+    };
+
+    let device_authorization = state
+        .oauth2_device_codes
+        .values_mut()
+        .find(|device_authorization| {
+            device_authorization.user_code == query.user_code
+                && matches!(device_authorization.status, OAuth2DeviceCodeStatus::Pending)
+        })
+        .ok_or(FakeRemoteError::BadRequest("invalid user code".into()))?;
+
+    device_authorization.status = status;
+
+    Ok(StatusCode::OK)
+}
+
 pub async fn user(
     ExtractState(state): ExtractState,
     context: Context,
@@ -190,8 +358,7 @@ pub async fn places(
             .map(|user| {
                 user.mounts
                     .iter()
-                    .filter_map(|mount_id| state.mounts.get(mount_id))
-                    .cloned()
+                    .filter_map(|mount_id| state.get_mount(mount_id))
                     .collect()
             })
             .unwrap_or(vec![]),
@@ -236,7 +403,7 @@ pub async fn mounts_details(
 
         let mount_id = resolve_mount_id(&context, &state, mountable);
 
-        state.mounts.get(&mount_id).unwrap().clone()
+        state.get_mount(&mount_id).unwrap()
     };
 
     Ok(Json(mount.clone()))
@@ -761,8 +928,8 @@ pub async fn vault_repos_all(
         .collect();
     let mounts: HashMap<_, _> = repos
         .iter()
-        .filter_map(|repo| state.mounts.get(&repo.mount_id.0))
-        .map(|mount| (mount.id.clone(), mount.clone()))
+        .filter_map(|repo| state.get_mount(&repo.mount_id.0))
+        .map(|mount| (mount.id.clone(), mount))
         .collect();
 
     let bundle = models::VaultReposBundle { repos, mounts };
@@ -799,3 +966,59 @@ pub async fn vault_repos_remove(
 
     Ok(StatusCode::NO_CONTENT)
 }
+
+pub async fn debug_faults_get(
+    ExtractFaultInjector(fault_injector): ExtractFaultInjector,
+) -> Json<Option<FaultProfile>> {
+    Json(fault_injector.get_profile())
+}
+
+pub async fn debug_faults_put(
+    ExtractFaultInjector(fault_injector): ExtractFaultInjector,
+    Json(profile): Json<FaultProfile>,
+) -> StatusCode {
+    fault_injector.set_profile(Some(profile));
+
+    StatusCode::NO_CONTENT
+}
+
+pub async fn debug_faults_delete(
+    ExtractFaultInjector(fault_injector): ExtractFaultInjector,
+) -> StatusCode {
+    fault_injector.set_profile(None);
+
+    StatusCode::NO_CONTENT
+}
+
+pub async fn debug_snapshots_list(
+    ExtractStateStore(state_store): ExtractStateStore,
+) -> Result<Json<Vec<SnapshotInfo>>, FakeRemoteError> {
+    Ok(Json(state_store.snapshots_list().await?))
+}
+
+pub async fn debug_snapshots_create(
+    ExtractStateStore(state_store): ExtractStateStore,
+    Path(name): Path<String>,
+) -> Result<StatusCode, FakeRemoteError> {
+    state_store.snapshot_create(&name).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn debug_snapshots_delete(
+    ExtractStateStore(state_store): ExtractStateStore,
+    Path(name): Path<String>,
+) -> Result<StatusCode, FakeRemoteError> {
+    state_store.snapshot_delete(&name).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn debug_snapshots_restore(
+    ExtractStateStore(state_store): ExtractStateStore,
+    Path(name): Path<String>,
+) -> Result<StatusCode, FakeRemoteError> {
+    state_store.snapshot_restore(&name).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -5,6 +5,7 @@ pub mod debug_interceptor;
 pub mod errors;
 pub mod eventstream;
 pub mod extract;
+pub mod faults;
 pub mod files;
 pub mod fix_response_json;
 pub mod handlers;
@@ -12,6 +13,7 @@ pub mod interceptor;
 pub mod router;
 pub mod server;
 pub mod state;
+pub mod state_store;
 pub mod users_service;
 pub mod utils;
 pub mod vault_repos_service;
@@ -8,8 +8,9 @@ use tower::ServiceBuilder;
 use tower_http::cors;
 
 use super::{
-    app_state::AppState, eventstream, fix_response_json::fix_response_json, handlers,
-    interceptor::interceptor_middleware,
+    app_state::AppState, eventstream, faults::faults_middleware,
+    fix_response_json::fix_response_json, handlers, interceptor::interceptor_middleware,
+    state_store::state_store_middleware,
 };
 
 pub fn build_router(app_state: AppState) -> Router {
@@ -46,6 +47,8 @@ pub fn build_router(app_state: AppState) -> Router {
         .route("/oauth2/auth", get(handlers::oauth2_auth))
         .route("/oauth2/logout", get(handlers::oauth2_logout))
         .route("/oauth2/token", post(handlers::oauth2_token))
+        .route("/oauth2/device/code", post(handlers::oauth2_device_code))
+        .route("/oauth2/device", get(handlers::oauth2_device))
         .route("/api/v2.1/user", get(handlers::user))
         .route(
             "/content/api/v2.1/users/:user_id/profile-picture",
@@ -103,11 +106,34 @@ pub fn build_router(app_state: AppState) -> Router {
             delete(handlers::vault_repos_remove),
         )
         .route("/events", get(eventstream::handler::eventstream))
+        .route(
+            "/debug/faults",
+            get(handlers::debug_faults_get)
+                .put(handlers::debug_faults_put)
+                .delete(handlers::debug_faults_delete),
+        )
+        .route("/debug/snapshots", get(handlers::debug_snapshots_list))
+        .route(
+            "/debug/snapshots/:name",
+            put(handlers::debug_snapshots_create).delete(handlers::debug_snapshots_delete),
+        )
+        .route(
+            "/debug/snapshots/:name/restore",
+            post(handlers::debug_snapshots_restore),
+        )
         .layer(middleware::from_fn(fix_response_json))
+        .layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            faults_middleware,
+        ))
         .layer(middleware::from_fn_with_state(
             app_state.clone(),
             interceptor_middleware,
         ))
+        .layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            state_store_middleware,
+        ))
         .layer(ServiceBuilder::new().layer(cors))
         .with_state(app_state)
 }
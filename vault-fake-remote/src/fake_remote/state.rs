@@ -1,10 +1,13 @@
 use std::collections::HashMap;
 
+use serde::{Deserialize, Serialize};
 use vault_core::remote::models;
 
 use super::files::Filesystem;
 
-#[derive(Debug)]
+const MIB: i64 = 1024 * 1024;
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct UserContainer {
     pub user: models::User,
     // mount ids
@@ -13,7 +16,20 @@ pub struct UserContainer {
     pub user_vault_repos: Vec<String>,
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum OAuth2DeviceCodeStatus {
+    Pending,
+    Approved { refresh_token: String },
+    Denied,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OAuth2DeviceCode {
+    pub user_code: String,
+    pub status: OAuth2DeviceCodeStatus,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct FakeRemoteState {
     pub default_user_id: Option<String>,
     pub users: HashMap<String, UserContainer>,
@@ -24,6 +40,10 @@ pub struct FakeRemoteState {
     pub oauth2_refresh_tokens: HashMap<String, String>,
     /// codes to refresh tokens
     pub oauth2_codes: HashMap<String, String>,
+    /// codes to PKCE code challenges
+    pub oauth2_code_challenges: HashMap<String, String>,
+    /// device codes to device authorizations
+    pub oauth2_device_codes: HashMap<String, OAuth2DeviceCode>,
 
     pub mounts: HashMap<String, models::Mount>,
 
@@ -37,4 +57,16 @@ impl FakeRemoteState {
     pub fn reset(&mut self) {
         *self = Default::default();
     }
+
+    /// Space is reported in MiB from the mount filesystem.
+    pub fn get_mount(&self, mount_id: &str) -> Option<models::Mount> {
+        let mut mount = self.mounts.get(mount_id)?.clone();
+
+        if let Some(fs) = self.filesystems.get(mount_id) {
+            mount.space_total = fs.space_total.map(|space_total| space_total / MIB);
+            mount.space_used = Some((fs.space_used() + MIB - 1) / MIB);
+        }
+
+        Some(mount)
+    }
 }
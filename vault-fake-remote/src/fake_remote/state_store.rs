@@ -0,0 +1,537 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, RwLock,
+    },
+};
+
+use axum::{extract::State, http::Request, middleware::Next, response::Response};
+use http::{Method, StatusCode};
+use serde::Serialize;
+use thiserror::Error;
+
+use super::{
+    app_state::AppState,
+    errors::{ApiErrorCode, FakeRemoteError},
+    files::objects::object_provider::{BoxObjectProvider, ObjectProviderError},
+    state::FakeRemoteState,
+    utils::now_ms,
+};
+
+#[derive(Error, Debug, Clone)]
+pub enum StateStoreError {
+    #[error("invalid snapshot name: {0}")]
+    InvalidSnapshotName(String),
+    #[error("snapshot not found: {0}")]
+    SnapshotNotFound(String),
+    #[error("{0}")]
+    IOError(Arc<std::io::Error>),
+    #[error("{0}")]
+    SerializeError(String),
+    #[error("{0}")]
+    ObjectProviderError(#[from] ObjectProviderError),
+}
+
+impl From<std::io::Error> for StateStoreError {
+    fn from(err: std::io::Error) -> Self {
+        Self::IOError(Arc::new(err))
+    }
+}
+
+impl From<serde_json::Error> for StateStoreError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::SerializeError(err.to_string())
+    }
+}
+
+impl From<StateStoreError> for FakeRemoteError {
+    fn from(err: StateStoreError) -> Self {
+        match err {
+            StateStoreError::InvalidSnapshotName(_) => Self::BadRequest(err.to_string()),
+            StateStoreError::SnapshotNotFound(_) => Self::ApiError(
+                StatusCode::NOT_FOUND,
+                ApiErrorCode::NotFound,
+                err.to_string(),
+                None,
+            ),
+            _ => Self::ApiError(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                ApiErrorCode::Other,
+                err.to_string(),
+                None,
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotInfo {
+    pub name: String,
+    pub created: i64,
+}
+
+/// Persists the state to `<data_path>/state.json` and keeps named snapshots
+/// in `<data_path>/snapshots/<name>/state.json`. Without a data path the
+/// state is not persisted and snapshots are kept in memory.
+pub struct StateStore {
+    state: Arc<RwLock<FakeRemoteState>>,
+    object_provider: Arc<BoxObjectProvider>,
+    data_path: Option<PathBuf>,
+    dirty: AtomicBool,
+    // snapshot names to created and serialized states
+    memory_snapshots: Mutex<HashMap<String, (i64, Vec<u8>)>>,
+    // snapshot operations must not interleave
+    snapshots_mutex: tokio::sync::Mutex<()>,
+}
+
+impl StateStore {
+    pub fn new(
+        state: Arc<RwLock<FakeRemoteState>>,
+        object_provider: Arc<BoxObjectProvider>,
+        data_path: Option<PathBuf>,
+    ) -> Self {
+        Self {
+            state,
+            object_provider,
+            data_path,
+            dirty: AtomicBool::new(false),
+            memory_snapshots: Mutex::new(HashMap::new()),
+            snapshots_mutex: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    pub fn is_persistent(&self) -> bool {
+        self.data_path.is_some()
+    }
+
+    fn state_path(&self) -> Option<PathBuf> {
+        self.data_path
+            .as_ref()
+            .map(|data_path| data_path.join("state.json"))
+    }
+
+    fn snapshot_state_path(&self, name: &str) -> Option<PathBuf> {
+        self.data_path
+            .as_ref()
+            .map(|data_path| data_path.join("snapshots").join(name).join("state.json"))
+    }
+
+    fn serialize_state(&self) -> Result<Vec<u8>, StateStoreError> {
+        Ok(serde_json::to_vec(&*self.state.read().unwrap())?)
+    }
+
+    /// Returns false if there is no persisted state.
+    pub async fn load(&self) -> Result<bool, StateStoreError> {
+        let state_path = match self.state_path() {
+            Some(state_path) => state_path,
+            None => return Ok(false),
+        };
+
+        let bytes = match tokio::fs::read(&state_path).await {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+            Err(err) => return Err(err.into()),
+        };
+
+        *self.state.write().unwrap() = serde_json::from_slice(&bytes)?;
+
+        Ok(true)
+    }
+
+    pub async fn save(&self) -> Result<(), StateStoreError> {
+        let state_path = match self.state_path() {
+            Some(state_path) => state_path,
+            None => return Ok(()),
+        };
+
+        self.dirty.store(false, Ordering::SeqCst);
+
+        let res = write_atomic(state_path, self.serialize_state()?).await;
+
+        if res.is_err() {
+            self.dirty.store(true, Ordering::SeqCst);
+        }
+
+        res
+    }
+
+    pub fn mark_dirty(&self) {
+        self.dirty.store(true, Ordering::SeqCst);
+    }
+
+    pub async fn save_if_dirty(&self) -> Result<(), StateStoreError> {
+        if self.dirty.load(Ordering::SeqCst) {
+            self.save().await
+        } else {
+            Ok(())
+        }
+    }
+
+    pub async fn snapshots_list(&self) -> Result<Vec<SnapshotInfo>, StateStoreError> {
+        let _guard = self.snapshots_mutex.lock().await;
+
+        let mut snapshots = match &self.data_path {
+            Some(data_path) => {
+                let mut snapshots = vec![];
+
+                let mut entries = match tokio::fs::read_dir(data_path.join("snapshots")).await {
+                    Ok(entries) => entries,
+                    Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(snapshots),
+                    Err(err) => return Err(err.into()),
+                };
+
+                while let Some(entry) = entries.next_entry().await? {
+                    let name = match entry.file_name().into_string() {
+                        Ok(name) => name,
+                        Err(_) => continue,
+                    };
+
+                    let metadata = match tokio::fs::metadata(entry.path().join("state.json")).await
+                    {
+                        Ok(metadata) => metadata,
+                        Err(_) => continue,
+                    };
+
+                    let created = metadata
+                        .modified()?
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .map(|duration| duration.as_millis() as i64)
+                        .unwrap_or(0);
+
+                    snapshots.push(SnapshotInfo { name, created });
+                }
+
+                snapshots
+            }
+            None => self
+                .memory_snapshots
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|(name, (created, _))| SnapshotInfo {
+                    name: name.clone(),
+                    created: *created,
+                })
+                .collect(),
+        };
+
+        snapshots.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(snapshots)
+    }
+
+    /// Replaces an existing snapshot with the same name.
+    pub async fn snapshot_create(&self, name: &str) -> Result<(), StateStoreError> {
+        validate_snapshot_name(name)?;
+
+        let _guard = self.snapshots_mutex.lock().await;
+
+        let (bytes, object_ids) = {
+            let state = self.state.read().unwrap();
+
+            (serde_json::to_vec(&*state)?, get_object_ids(&state))
+        };
+
+        self.object_provider
+            .snapshot_create(name.to_owned(), object_ids.into_iter().collect())
+            .await?;
+
+        match self.snapshot_state_path(name) {
+            Some(snapshot_state_path) => write_atomic(snapshot_state_path, bytes).await?,
+            None => {
+                self.memory_snapshots
+                    .lock()
+                    .unwrap()
+                    .insert(name.to_owned(), (now_ms(), bytes));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Objects that are not in the snapshot are deleted.
+    pub async fn snapshot_restore(&self, name: &str) -> Result<(), StateStoreError> {
+        validate_snapshot_name(name)?;
+
+        let _guard = self.snapshots_mutex.lock().await;
+
+        let bytes = match self.snapshot_state_path(name) {
+            Some(snapshot_state_path) => match tokio::fs::read(&snapshot_state_path).await {
+                Ok(bytes) => bytes,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                    return Err(StateStoreError::SnapshotNotFound(name.to_owned()))
+                }
+                Err(err) => return Err(err.into()),
+            },
+            None => match self.memory_snapshots.lock().unwrap().get(name) {
+                Some((_, bytes)) => bytes.clone(),
+                None => return Err(StateStoreError::SnapshotNotFound(name.to_owned())),
+            },
+        };
+
+        let snapshot_state: FakeRemoteState = serde_json::from_slice(&bytes)?;
+        let snapshot_object_ids = get_object_ids(&snapshot_state);
+
+        self.object_provider
+            .snapshot_restore(
+                name.to_owned(),
+                snapshot_object_ids.iter().cloned().collect(),
+            )
+            .await?;
+
+        let old_state = std::mem::replace(&mut *self.state.write().unwrap(), snapshot_state);
+
+        let old_object_ids = old_state
+            .filesystems
+            .values()
+            .flat_map(|fs| fs.objects.keys())
+            .filter(|object_id| !snapshot_object_ids.contains(*object_id))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        for object_id in old_object_ids {
+            if let Err(err) = self.object_provider.delete(object_id.clone()).await {
+                log::warn!("Failed to delete local file: {}: {:?}", object_id, err);
+            }
+        }
+
+        self.save().await
+    }
+
+    pub async fn snapshot_delete(&self, name: &str) -> Result<(), StateStoreError> {
+        validate_snapshot_name(name)?;
+
+        let _guard = self.snapshots_mutex.lock().await;
+
+        match self.snapshot_state_path(name) {
+            Some(snapshot_state_path) => match tokio::fs::remove_file(&snapshot_state_path).await {
+                Ok(()) => {}
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                    return Err(StateStoreError::SnapshotNotFound(name.to_owned()))
+                }
+                Err(err) => return Err(err.into()),
+            },
+            None => {
+                if self.memory_snapshots.lock().unwrap().remove(name).is_none() {
+                    return Err(StateStoreError::SnapshotNotFound(name.to_owned()));
+                }
+            }
+        }
+
+        match self.object_provider.snapshot_delete(name.to_owned()).await {
+            Err(ObjectProviderError::IOError(err))
+                if err.kind() != std::io::ErrorKind::NotFound =>
+            {
+                return Err(ObjectProviderError::IOError(err).into())
+            }
+            _ => {}
+        }
+
+        if let Some(data_path) = &self.data_path {
+            let _ = tokio::fs::remove_dir(data_path.join("snapshots").join(name)).await;
+        }
+
+        Ok(())
+    }
+}
+
+/// Snapshot names are used as dir names.
+fn validate_snapshot_name(name: &str) -> Result<(), StateStoreError> {
+    let is_valid = !name.is_empty()
+        && name.len() <= 100
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.');
+
+    if is_valid {
+        Ok(())
+    } else {
+        Err(StateStoreError::InvalidSnapshotName(name.to_owned()))
+    }
+}
+
+/// Object ids of all referenced objects.
+fn get_object_ids(state: &FakeRemoteState) -> HashSet<String> {
+    state
+        .filesystems
+        .values()
+        .flat_map(|fs| fs.objects.iter())
+        .filter(|(_, object)| object.refs > 0)
+        .map(|(object_id, _)| object_id.clone())
+        .collect()
+}
+
+/// A crash while writing must not leave a truncated state behind.
+async fn write_atomic(path: PathBuf, bytes: Vec<u8>) -> Result<(), StateStoreError> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+
+    let tmp_path = path.with_extension("json.tmp");
+
+    tokio::fs::write(&tmp_path, bytes).await?;
+    tokio::fs::rename(&tmp_path, &path).await?;
+
+    Ok(())
+}
+
+/// Marks the state dirty after requests that could have changed it. The
+/// app saves dirty state periodically.
+pub async fn state_store_middleware<B>(
+    State(state): State<AppState>,
+    request: Request<B>,
+    next: Next<B>,
+) -> Response {
+    let path = request.uri().path();
+
+    let is_mutation = !matches!(
+        *request.method(),
+        Method::GET | Method::HEAD | Method::OPTIONS
+    ) || path.starts_with("/oauth2")
+        || path.starts_with("/debug");
+
+    let response = next.run(request).await;
+
+    if is_mutation && state.state_store.is_persistent() {
+        state.state_store.mark_dirty();
+    }
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use futures::{io::Cursor, AsyncReadExt};
+
+    use super::super::{
+        app_state::AppState,
+        context::Context,
+        files::{
+            filesystem::CreateFileConflictResolution,
+            objects::fs_object_provider::FsObjectProvider, Name, Path,
+        },
+    };
+
+    fn create_app_state(data_path: &std::path::Path) -> AppState {
+        AppState::new(
+            Arc::new(Box::new(
+                FsObjectProvider::new(data_path.to_owned()).unwrap(),
+            )),
+            Some(data_path.to_owned()),
+        )
+    }
+
+    async fn read_file(app_state: &AppState, mount_id: &str, path: &str) -> Option<String> {
+        let (_, object_id) = app_state
+            .files_service
+            .get_file(mount_id, &Path(path.into()))
+            .ok()?;
+
+        let mut content = String::new();
+
+        app_state
+            .files_service
+            .get_object_reader(object_id, None)
+            .await
+            .unwrap()
+            .read_to_string(&mut content)
+            .await
+            .unwrap();
+
+        Some(content)
+    }
+
+    #[tokio::test]
+    async fn test_persist_snapshot_restore() {
+        let data_path = std::env::temp_dir().join(format!(
+            "vault-fake-remote-state-store-{}",
+            uuid::Uuid::new_v4()
+        ));
+
+        let app_state = create_app_state(&data_path);
+
+        let (user_id, mount_id) = app_state.users_service.create_user(None, None);
+        let context = Context {
+            user_id,
+            user_agent: None,
+        };
+
+        app_state
+            .files_service
+            .create_file(
+                &context,
+                &mount_id,
+                &Path::root(),
+                Name("file.txt".into()),
+                None,
+                &CreateFileConflictResolution::Error,
+                Box::pin(Cursor::new(b"content".to_vec())),
+            )
+            .await
+            .unwrap();
+
+        app_state.state_store.snapshot_create("s1").await.unwrap();
+
+        app_state
+            .files_service
+            .delete_file(&context, &mount_id, &Path("/file.txt".into()), false)
+            .await
+            .unwrap();
+
+        app_state.state_store.save().await.unwrap();
+
+        // restart
+        let app_state = create_app_state(&data_path);
+
+        assert!(app_state.state_store.load().await.unwrap());
+        assert!(app_state
+            .state
+            .read()
+            .unwrap()
+            .users
+            .contains_key(&context.user_id));
+        assert_eq!(read_file(&app_state, &mount_id, "/file.txt").await, None);
+
+        let snapshots = app_state.state_store.snapshots_list().await.unwrap();
+        assert_eq!(
+            snapshots
+                .iter()
+                .map(|s| s.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["s1"]
+        );
+
+        app_state.state_store.snapshot_restore("s1").await.unwrap();
+
+        assert_eq!(
+            read_file(&app_state, &mount_id, "/file.txt").await,
+            Some("content".into())
+        );
+
+        app_state.state_store.snapshot_delete("s1").await.unwrap();
+
+        assert!(app_state
+            .state_store
+            .snapshots_list()
+            .await
+            .unwrap()
+            .is_empty());
+
+        // restored state was saved
+        let app_state = create_app_state(&data_path);
+
+        assert!(app_state.state_store.load().await.unwrap());
+        assert_eq!(
+            read_file(&app_state, &mount_id, "/file.txt").await,
+            Some("content".into())
+        );
+
+        std::fs::remove_dir_all(&data_path).unwrap();
+    }
+}
@@ -53,11 +53,21 @@ impl UsersService {
             is_primary: true,
             space_total: Some(10240),
             space_used: Some(0),
+            permissions: Some(models::MountPermissions {
+                read: true,
+                write: true,
+                owner: true,
+                mount: true,
+                create_receiver: true,
+                create_link: true,
+                comment: true,
+            }),
         };
 
         state.mounts.insert(mount_id.clone(), mount);
 
-        let fs = self.files_service.create_filesystem();
+        let mut fs = self.files_service.create_filesystem();
+        fs.space_total = Some(10240 * 1024 * 1024);
 
         state.filesystems.insert(mount_id.clone(), fs);
 
@@ -4,6 +4,7 @@ use clap::Parser;
 use tokio::signal;
 use vault_fake_remote::fake_remote::{
     app::{FakeRemoteApp, FakeRemoteAppConfig},
+    faults::FaultProfile,
     files::objects::{
         fs_object_provider::FsObjectProvider, memory_object_provider::MemoryObjectProvider,
         object_provider::BoxObjectProvider,
@@ -21,7 +22,8 @@ struct Args {
     #[arg(long, default_value = "127.0.0.1:3443")]
     https_addr: SocketAddr,
 
-    /// Data path (default empty, in-memory)
+    /// Data path (default empty, in-memory). Objects, state and snapshots are
+    /// stored in the data path and the state is restored on start
     #[arg(long)]
     data_path: Option<PathBuf>,
 
@@ -44,6 +46,10 @@ struct Args {
     /// Create a default vault repo (safe box)
     #[arg(long, default_value = "false")]
     create_vault_repo: bool,
+
+    /// Fault profile JSON file (can also be set with PUT /debug/faults)
+    #[arg(long)]
+    fault_profile: Option<PathBuf>,
 }
 
 fn main() {
@@ -62,7 +68,7 @@ fn main() {
 
             log::info!("Data path: {:?}", data_path);
 
-            Arc::new(Box::new(FsObjectProvider::new(data_path)))
+            Arc::new(Box::new(FsObjectProvider::new(data_path).unwrap()))
         }
         None => {
             log::info!("Data in memory");
@@ -71,15 +77,25 @@ fn main() {
         }
     };
 
+    let fault_profile = args.fault_profile.as_ref().map(|path| {
+        let profile = FaultProfile::from_json(&std::fs::read_to_string(path).unwrap()).unwrap();
+
+        log::info!("Fault profile: {:?}", path);
+
+        profile
+    });
+
     let config = FakeRemoteAppConfig {
         http_addr: args.http_addr,
         https_addr: args.https_addr,
         object_provider,
+        data_path: args.data_path,
         user_id: args.user_id,
         mount_id: args.mount_id,
         oauth2_access_token: args.oauth2_access_token,
         oauth2_refresh_token: args.oauth2_refresh_token,
         create_vault_repo: args.create_vault_repo,
+        fault_profile,
     };
 
     tokio_runtime.clone().block_on(async move {
@@ -18,24 +18,31 @@ use std::{
     time::Duration,
 };
 
+use futures::stream::AbortHandle;
 use lazy_static::lazy_static;
 use thiserror::Error;
 
 use vault_core::{
+    accounts::state as accounts_state,
+    audit_log::state as audit_log_state,
     common::state as common_state,
     dialogs::state as dialogs_state,
     files::{self, file_category, files_filter},
     lifecycle,
     notifications::state as notifications_state,
-    oauth2::OAuth2Config,
+    oauth2::{self, state as oauth2_state, OAuth2Config},
     relative_time,
     remote_files::state as remote_files_state,
     remote_files_browsers::state::{self as remote_files_browsers_state, RemoteFilesBrowserItemId},
+    repo_duplicates::state as repo_duplicates_state,
     repo_files::state as repo_files_state,
     repo_files_browsers::state as repo_files_browsers_state,
     repo_files_details::state as repo_files_details_state,
     repo_files_move::state as repo_files_move_state,
+    repo_files_offline::state as repo_files_offline_state,
     repo_files_read,
+    repo_files_rename::state as repo_files_rename_state,
+    repo_files_versions::state as repo_files_versions_state,
     repo_remove::state as repo_remove_state,
     repo_unlock::state as repo_unlock_state,
     repos::{self, selectors as repos_selectors, state as repos_state},
@@ -54,10 +61,12 @@ use vault_core::{
         TimeMillis,
     },
     user::state as user_state,
-    user_error::UserError,
+    user_error::{self, UserError},
     Vault,
 };
 use vault_native::{
+    file_audit_log_disk_storage::FileAuditLogDiskStorage,
+    file_offline_disk_cache::FileOfflineDiskCache,
     native_runtime::now,
     transfers::{
         file_downloadable::FileDownloadable, file_uploadable::FileUploadable,
@@ -146,15 +155,38 @@ pub trait SubscriptionCallback: Send + Sync + Debug {
     fn on_change(&self);
 }
 
+// errors
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ErrorCode {
+    pub code: String,
+    pub details: HashMap<String, String>,
+}
+
+impl From<&user_error::ErrorCode> for ErrorCode {
+    fn from(error_code: &user_error::ErrorCode) -> Self {
+        Self {
+            code: error_code.as_str().to_owned(),
+            details: error_code.details().into_iter().collect(),
+        }
+    }
+}
+
 // status
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum Status {
     Initial,
-    Loading { loaded: bool },
+    Loading {
+        loaded: bool,
+    },
     Loaded,
     // Error is a reserved keyword in uniffi UDL
-    Err { error: String, loaded: bool },
+    Err {
+        error: String,
+        error_code: ErrorCode,
+        loaded: bool,
+    },
 }
 
 impl<E: std::error::Error + Clone + PartialEq + UserError> From<&common_state::Status<E>>
@@ -167,6 +199,7 @@ impl<E: std::error::Error + Clone + PartialEq + UserError> From<&common_state::S
             common_state::Status::Loaded => Self::Loaded,
             common_state::Status::Error { error, loaded } => Self::Err {
                 error: error.user_error(),
+                error_code: (&error.error_code()).into(),
                 loaded: *loaded,
             },
         }
@@ -237,6 +270,123 @@ impl From<relative_time::RelativeTime> for RelativeTime {
     }
 }
 
+// audit_log
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum RepoLockReason {
+    Manual,
+    Inactivity,
+    AppHidden,
+    Panic,
+    AccountSwitch,
+}
+
+impl From<audit_log_state::RepoLockReason> for RepoLockReason {
+    fn from(reason: audit_log_state::RepoLockReason) -> Self {
+        match reason {
+            audit_log_state::RepoLockReason::Manual => Self::Manual,
+            audit_log_state::RepoLockReason::Inactivity => Self::Inactivity,
+            audit_log_state::RepoLockReason::AppHidden => Self::AppHidden,
+            audit_log_state::RepoLockReason::Panic => Self::Panic,
+            audit_log_state::RepoLockReason::AccountSwitch => Self::AccountSwitch,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum AuditLogEvent {
+    RepoUnlock {
+        repo_id: String,
+        read_only: bool,
+    },
+    RepoUnlockFailure {
+        repo_id: String,
+        error_code: String,
+    },
+    RepoLock {
+        repo_id: String,
+        reason: RepoLockReason,
+    },
+    RepoCreate {
+        repo_id: String,
+    },
+    RepoRemove {
+        repo_id: String,
+    },
+    RepoConfigBackup {
+        repo_id: String,
+    },
+    Logout,
+}
+
+impl From<audit_log_state::AuditLogEvent> for AuditLogEvent {
+    fn from(event: audit_log_state::AuditLogEvent) -> Self {
+        use audit_log_state::AuditLogEvent;
+
+        match event {
+            AuditLogEvent::RepoUnlock { repo_id, read_only } => Self::RepoUnlock {
+                repo_id: repo_id.0,
+                read_only,
+            },
+            AuditLogEvent::RepoUnlockFailure {
+                repo_id,
+                error_code,
+            } => Self::RepoUnlockFailure {
+                repo_id: repo_id.0,
+                error_code,
+            },
+            AuditLogEvent::RepoLock { repo_id, reason } => Self::RepoLock {
+                repo_id: repo_id.0,
+                reason: reason.into(),
+            },
+            AuditLogEvent::RepoCreate { repo_id } => Self::RepoCreate { repo_id: repo_id.0 },
+            AuditLogEvent::RepoRemove { repo_id } => Self::RepoRemove { repo_id: repo_id.0 },
+            AuditLogEvent::RepoConfigBackup { repo_id } => {
+                Self::RepoConfigBackup { repo_id: repo_id.0 }
+            }
+            AuditLogEvent::Logout => Self::Logout,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuditLogEntry {
+    pub seq: u64,
+    pub time: i64,
+    pub user_id: Option<String>,
+    pub event: AuditLogEvent,
+    pub prev_hash: String,
+    pub hash: String,
+}
+
+impl From<audit_log_state::AuditLogEntry> for AuditLogEntry {
+    fn from(entry: audit_log_state::AuditLogEntry) -> Self {
+        Self {
+            seq: entry.seq,
+            time: entry.time,
+            user_id: entry.user_id,
+            event: entry.event.into(),
+            prev_hash: entry.prev_hash,
+            hash: entry.hash,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuditLogFilter {
+    pub repo_id: Option<String>,
+    pub since: Option<i64>,
+}
+
+impl Into<audit_log_state::AuditLogFilter> for AuditLogFilter {
+    fn into(self) -> audit_log_state::AuditLogFilter {
+        audit_log_state::AuditLogFilter {
+            repo_id: self.repo_id.map(RepoId),
+            since: self.since,
+        }
+    }
+}
+
 // files
 
 #[derive(Clone, Debug, PartialEq)]
@@ -538,6 +688,7 @@ pub trait DownloadStreamProvider: Send + Sync + Debug {
 pub struct Notification {
     pub id: u32,
     pub message: String,
+    pub error_code: Option<ErrorCode>,
 }
 
 impl From<&notifications_state::Notification> for Notification {
@@ -545,6 +696,7 @@ impl From<&notifications_state::Notification> for Notification {
         Self {
             id: notification.id,
             message: notification.message.clone(),
+            error_code: notification.error_code.as_ref().map(Into::into),
         }
     }
 }
@@ -624,6 +776,46 @@ pub trait OAuth2FinishFlowDone: Send + Sync + Debug {
     fn on_done(&self);
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub struct DeviceAuthorization {
+    pub device_code: String,
+    pub user_code: String,
+    pub verification_uri: String,
+    pub verification_uri_complete: Option<String>,
+    pub expires_at: i64,
+    pub interval_ms: u64,
+}
+
+impl From<&oauth2_state::DeviceAuthorization> for DeviceAuthorization {
+    fn from(device_authorization: &oauth2_state::DeviceAuthorization) -> Self {
+        Self {
+            device_code: device_authorization.device_code.clone(),
+            user_code: device_authorization.user_code.clone(),
+            verification_uri: device_authorization.verification_uri.clone(),
+            verification_uri_complete: device_authorization.verification_uri_complete.clone(),
+            expires_at: device_authorization.expires_at.0,
+            interval_ms: device_authorization.interval.as_millis() as u64,
+        }
+    }
+}
+
+impl Into<oauth2_state::DeviceAuthorization> for DeviceAuthorization {
+    fn into(self) -> oauth2_state::DeviceAuthorization {
+        oauth2_state::DeviceAuthorization {
+            device_code: self.device_code,
+            user_code: self.user_code,
+            verification_uri: self.verification_uri,
+            verification_uri_complete: self.verification_uri_complete,
+            expires_at: TimeMillis(self.expires_at),
+            interval: Duration::from_millis(self.interval_ms),
+        }
+    }
+}
+
+pub trait OAuth2StartDeviceFlowDone: Send + Sync + Debug {
+    fn on_done(&self, device_authorization: Option<DeviceAuthorization>);
+}
+
 // user
 
 #[derive(Clone, Debug, PartialEq)]
@@ -647,6 +839,31 @@ impl From<&user_state::User> for User {
     }
 }
 
+// accounts
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Account {
+    pub user_id: String,
+    pub email: String,
+    pub full_name: String,
+    pub is_active: bool,
+}
+
+impl<'a> From<&accounts_state::AccountInfo<'a>> for Account {
+    fn from(info: &accounts_state::AccountInfo<'a>) -> Self {
+        Self {
+            user_id: info.account.user_id.clone(),
+            email: info.account.email.clone(),
+            full_name: info.account.full_name.clone(),
+            is_active: info.is_active,
+        }
+    }
+}
+
+pub trait AccountsSwitchDone: Send + Sync + Debug {
+    fn on_done(&self);
+}
+
 // remote_files
 
 #[derive(Clone, Debug, PartialEq)]
@@ -968,6 +1185,21 @@ impl Into<repos_state::RepoAutoLock> for RepoAutoLock {
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RepoUnlockLockout {
+    pub max_failed_attempts: u32,
+    pub duration_seconds: u64,
+}
+
+impl Into<repos_state::RepoUnlockLockout> for RepoUnlockLockout {
+    fn into(self) -> repos_state::RepoUnlockLockout {
+        repos_state::RepoUnlockLockout {
+            max_failed_attempts: self.max_failed_attempts,
+            duration: Duration::from_secs(self.duration_seconds),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum RepoAutoLockAfter {
     NoLimit,
@@ -1031,6 +1263,7 @@ pub struct Repo {
     pub added: i64,
     pub web_url: String,
     pub auto_lock: RepoAutoLock,
+    pub read_only: bool,
 }
 
 impl From<(&repos_state::Repo, &repos_state::RepoAutoLock)> for Repo {
@@ -1044,6 +1277,7 @@ impl From<(&repos_state::Repo, &repos_state::RepoAutoLock)> for Repo {
             added: repo.added,
             web_url: repo.web_url.clone(),
             auto_lock: repo.auto_lock.as_ref().unwrap_or(default_auto_lock).into(),
+            read_only: repo.is_read_only(),
         }
     }
 }
@@ -1097,9 +1331,9 @@ impl From<&repos_state::RepoConfig> for RepoConfig {
         Self {
             name: config.name.0.clone(),
             location: (&config.location).into(),
-            password: config.password.clone(),
+            password: config.password.to_string(),
             salt: config.salt.clone(),
-            rclone_config: config.rclone_config.clone(),
+            rclone_config: config.rclone_config.to_string(),
         }
     }
 }
@@ -1144,6 +1378,7 @@ pub enum RepoCreateInfo {
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum RepoUnlockMode {
     Unlock,
+    UnlockReadOnly,
     Verify,
 }
 
@@ -1151,6 +1386,7 @@ impl Into<repos_state::RepoUnlockMode> for RepoUnlockMode {
     fn into(self) -> repos_state::RepoUnlockMode {
         match self {
             Self::Unlock => repos_state::RepoUnlockMode::Unlock,
+            Self::UnlockReadOnly => repos_state::RepoUnlockMode::UnlockReadOnly,
             Self::Verify => repos_state::RepoUnlockMode::Verify,
         }
     }
@@ -1173,6 +1409,8 @@ impl Into<repo_unlock_state::RepoUnlockOptions> for RepoUnlockOptions {
 pub struct RepoUnlockInfo {
     pub status: Status,
     pub repo_name: Option<String>,
+    pub failed_attempts: u32,
+    pub retry_at: Option<i64>,
 }
 
 pub trait RepoUnlockUnlocked: Send + Sync + Debug {
@@ -1225,6 +1463,7 @@ pub struct RepoFile {
     pub decrypted_path: Option<String>,
     pub name: String,
     pub name_error: Option<String>,
+    pub name_error_code: Option<ErrorCode>,
     pub ext: Option<String>,
     pub content_type: Option<String>,
     pub typ: RepoFileType,
@@ -1256,6 +1495,12 @@ impl From<&repo_files_state::RepoFile> for RepoFile {
                     Some(error.user_error())
                 }
             },
+            name_error_code: match &file.name {
+                repo_files_state::RepoFileName::Decrypted { .. } => None,
+                repo_files_state::RepoFileName::DecryptError { error, .. } => {
+                    Some((&error.error_code()).into())
+                }
+            },
             ext: file.ext.clone(),
             content_type: file.content_type.clone(),
             typ: (&file.typ).into(),
@@ -1362,7 +1607,10 @@ pub enum TransferState {
     Waiting,
     Processing,
     Transferring,
-    Failed { error: String },
+    Failed {
+        error: String,
+        error_code: ErrorCode,
+    },
     Done,
 }
 
@@ -1374,6 +1622,7 @@ impl From<&transfers_state::TransferState> for TransferState {
             transfers_state::TransferState::Transferring => Self::Transferring,
             transfers_state::TransferState::Failed { error } => Self::Failed {
                 error: error.user_error(),
+                error_code: (&error.error_code()).into(),
             },
             transfers_state::TransferState::Done => Self::Done,
         }
@@ -1530,6 +1779,8 @@ pub struct RepoFilesBrowserInfo {
     pub items: Vec<RepoFilesBrowserItem>,
     pub repo_status: Status,
     pub is_locked: bool,
+    pub is_read_only: bool,
+    pub is_reconnecting: bool,
 }
 
 impl<'a> From<&repo_files_browsers_state::RepoFilesBrowserInfo<'a>> for RepoFilesBrowserInfo {
@@ -1552,6 +1803,8 @@ impl<'a> From<&repo_files_browsers_state::RepoFilesBrowserInfo<'a>> for RepoFile
             items: info.items.iter().map(Into::into).collect(),
             repo_status: (&info.repo_status).into(),
             is_locked: info.is_locked,
+            is_read_only: info.is_read_only,
+            is_reconnecting: info.is_reconnecting,
         }
     }
 }
@@ -1560,6 +1813,180 @@ pub trait RepoFilesBrowserDirCreated: Send + Sync + Debug {
     fn on_created(&self, encrypted_path: String);
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub enum RepoFilesRenameCase {
+    Lower,
+    Upper,
+    Title,
+}
+
+impl Into<repo_files_rename_state::RepoFilesRenameCase> for RepoFilesRenameCase {
+    fn into(self) -> repo_files_rename_state::RepoFilesRenameCase {
+        match self {
+            Self::Lower => repo_files_rename_state::RepoFilesRenameCase::Lower,
+            Self::Upper => repo_files_rename_state::RepoFilesRenameCase::Upper,
+            Self::Title => repo_files_rename_state::RepoFilesRenameCase::Title,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum RepoFilesRenameRule {
+    Replace {
+        find: String,
+        replace: String,
+        case_sensitive: bool,
+    },
+    Regex {
+        pattern: String,
+        replace: String,
+    },
+    Numbering {
+        template: String,
+        start: u64,
+        step: u64,
+        padding: u32,
+    },
+    Case {
+        letter_case: RepoFilesRenameCase,
+    },
+    Extension {
+        extension: Option<String>,
+    },
+}
+
+impl Into<repo_files_rename_state::RepoFilesRenameRule> for RepoFilesRenameRule {
+    fn into(self) -> repo_files_rename_state::RepoFilesRenameRule {
+        match self {
+            Self::Replace {
+                find,
+                replace,
+                case_sensitive,
+            } => repo_files_rename_state::RepoFilesRenameRule::Replace {
+                find,
+                replace,
+                case_sensitive,
+            },
+            Self::Regex { pattern, replace } => {
+                repo_files_rename_state::RepoFilesRenameRule::Regex { pattern, replace }
+            }
+            Self::Numbering {
+                template,
+                start,
+                step,
+                padding,
+            } => repo_files_rename_state::RepoFilesRenameRule::Numbering {
+                template,
+                start,
+                step,
+                padding: padding as usize,
+            },
+            Self::Case { letter_case } => {
+                repo_files_rename_state::RepoFilesRenameRule::Case(letter_case.into())
+            }
+            Self::Extension { extension } => {
+                repo_files_rename_state::RepoFilesRenameRule::Extension(extension)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RepoFilesRenamePattern {
+    pub rules: Vec<RepoFilesRenameRule>,
+    pub include_extension: bool,
+}
+
+impl Into<repo_files_rename_state::RepoFilesRenamePattern> for RepoFilesRenamePattern {
+    fn into(self) -> repo_files_rename_state::RepoFilesRenamePattern {
+        repo_files_rename_state::RepoFilesRenamePattern {
+            rules: self.rules.into_iter().map(Into::into).collect(),
+            include_extension: self.include_extension,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum RepoFilesRenamePreviewStatus {
+    Unchanged,
+    Ok,
+    InvalidName,
+    AlreadyExists,
+    Duplicate,
+    DecryptNameError {
+        error: String,
+        error_code: ErrorCode,
+    },
+}
+
+impl From<&repo_files_rename_state::RepoFilesRenamePreviewStatus> for RepoFilesRenamePreviewStatus {
+    fn from(status: &repo_files_rename_state::RepoFilesRenamePreviewStatus) -> Self {
+        match status {
+            repo_files_rename_state::RepoFilesRenamePreviewStatus::Unchanged => Self::Unchanged,
+            repo_files_rename_state::RepoFilesRenamePreviewStatus::Ok => Self::Ok,
+            repo_files_rename_state::RepoFilesRenamePreviewStatus::InvalidName => Self::InvalidName,
+            repo_files_rename_state::RepoFilesRenamePreviewStatus::AlreadyExists => {
+                Self::AlreadyExists
+            }
+            repo_files_rename_state::RepoFilesRenamePreviewStatus::Duplicate => Self::Duplicate,
+            repo_files_rename_state::RepoFilesRenamePreviewStatus::DecryptNameError(error) => {
+                Self::DecryptNameError {
+                    error: error.user_error(),
+                    error_code: (&error.error_code()).into(),
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RepoFilesRenamePreviewItem {
+    pub file_id: String,
+    pub name: String,
+    pub new_name: String,
+    pub status: RepoFilesRenamePreviewStatus,
+}
+
+impl From<&repo_files_rename_state::RepoFilesRenamePreviewItem> for RepoFilesRenamePreviewItem {
+    fn from(item: &repo_files_rename_state::RepoFilesRenamePreviewItem) -> Self {
+        Self {
+            file_id: item.file_id.0.clone(),
+            name: item.name.0.clone(),
+            new_name: item.new_name.0.clone(),
+            status: (&item.status).into(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RepoFilesRenamePreview {
+    pub items: Vec<RepoFilesRenamePreviewItem>,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RepoFilesRenameResult {
+    pub file_id: String,
+    pub name: String,
+    pub new_name: String,
+    pub error: Option<String>,
+}
+
+impl From<&repo_files_rename_state::RepoFilesRenameResult> for RepoFilesRenameResult {
+    fn from(res: &repo_files_rename_state::RepoFilesRenameResult) -> Self {
+        Self {
+            file_id: res.file_id.0.clone(),
+            name: res.name.0.clone(),
+            new_name: res.new_name.0.clone(),
+            error: res.result.as_ref().err().map(|err| err.user_error()),
+        }
+    }
+}
+
+pub trait RepoFilesRenameDone: Send + Sync + Debug {
+    fn on_done(&self, results: Vec<RepoFilesRenameResult>);
+}
+
 // repo_files_details
 
 #[derive(Clone, Debug, PartialEq)]
@@ -1595,6 +2022,7 @@ pub struct RepoFilesDetailsInfo {
     pub is_editing: bool,
     pub is_dirty: bool,
     pub should_destroy: bool,
+    pub can_edit: bool,
     pub can_save: bool,
     pub can_download: bool,
     pub can_copy: bool,
@@ -1602,6 +2030,9 @@ pub struct RepoFilesDetailsInfo {
     pub can_delete: bool,
     pub repo_status: Status,
     pub is_locked: bool,
+    pub is_read_only: bool,
+    pub is_conflict: bool,
+    pub conflict_status: Status,
 }
 
 impl<'a> From<&repo_files_details_state::RepoFilesDetailsInfo<'a>> for RepoFilesDetailsInfo {
@@ -1623,6 +2054,7 @@ impl<'a> From<&repo_files_details_state::RepoFilesDetailsInfo<'a>> for RepoFiles
             is_editing: info.is_editing,
             is_dirty: info.is_dirty,
             should_destroy: info.should_destroy,
+            can_edit: info.can_edit,
             can_save: info.can_save,
             can_download: info.can_download,
             can_copy: info.can_copy,
@@ -1630,6 +2062,57 @@ impl<'a> From<&repo_files_details_state::RepoFilesDetailsInfo<'a>> for RepoFiles
             can_delete: info.can_delete,
             repo_status: (&info.repo_status).into(),
             is_locked: info.is_locked,
+            is_read_only: info.is_read_only,
+            is_conflict: info.is_conflict,
+            conflict_status: (&info.conflict_status).into(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum RepoFilesDetailsTextMergeChunk {
+    Resolved {
+        text: String,
+    },
+    Conflict {
+        base: String,
+        mine: String,
+        theirs: String,
+    },
+}
+
+impl From<&repo_files_details_state::RepoFilesDetailsTextMergeChunk>
+    for RepoFilesDetailsTextMergeChunk
+{
+    fn from(chunk: &repo_files_details_state::RepoFilesDetailsTextMergeChunk) -> Self {
+        match chunk {
+            repo_files_details_state::RepoFilesDetailsTextMergeChunk::Resolved(text) => {
+                Self::Resolved { text: text.clone() }
+            }
+            repo_files_details_state::RepoFilesDetailsTextMergeChunk::Conflict {
+                base,
+                mine,
+                theirs,
+            } => Self::Conflict {
+                base: base.clone(),
+                mine: mine.clone(),
+                theirs: theirs.clone(),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RepoFilesDetailsTextMerge {
+    pub chunks: Vec<RepoFilesDetailsTextMergeChunk>,
+    pub has_conflicts: bool,
+}
+
+impl From<&repo_files_details_state::RepoFilesDetailsTextMerge> for RepoFilesDetailsTextMerge {
+    fn from(text_merge: &repo_files_details_state::RepoFilesDetailsTextMerge) -> Self {
+        Self {
+            chunks: text_merge.chunks.iter().map(Into::into).collect(),
+            has_conflicts: text_merge.has_conflicts(),
         }
     }
 }
@@ -1669,66 +2152,231 @@ pub struct RepoFilesMoveInfo {
     pub can_move: bool,
 }
 
-// local_files
+// repo_files_versions
 
 #[derive(Clone, Debug, PartialEq)]
-pub enum LocalFileType {
-    Dir,
-    File,
+pub struct RepoFilesVersionsSettings {
+    pub enabled: bool,
+    pub max_count: Option<u32>,
+    pub max_age_days: Option<u32>,
+}
+
+impl From<&repo_files_versions_state::RepoFilesVersionsSettings> for RepoFilesVersionsSettings {
+    fn from(settings: &repo_files_versions_state::RepoFilesVersionsSettings) -> Self {
+        Self {
+            enabled: settings.enabled,
+            max_count: settings.max_count.map(|x| x as u32),
+            max_age_days: settings.max_age_days,
+        }
+    }
+}
+
+impl Into<repo_files_versions_state::RepoFilesVersionsSettings> for RepoFilesVersionsSettings {
+    fn into(self) -> repo_files_versions_state::RepoFilesVersionsSettings {
+        repo_files_versions_state::RepoFilesVersionsSettings {
+            enabled: self.enabled,
+            max_count: self.max_count.map(|x| x as usize),
+            max_age_days: self.max_age_days,
+        }
+    }
 }
 
+/// `encrypted_path` points to the file in the versions dir and can be
+/// downloaded like any other repo file.
 #[derive(Clone, Debug, PartialEq)]
-pub struct LocalFile {
+pub struct RepoFileVersion {
     pub id: String,
-    pub name: String,
-    pub ext: Option<String>,
-    pub typ: LocalFileType,
-    pub size_display: String,
+    pub repo_id: String,
+    pub encrypted_path: String,
+    pub original_path: String,
+    pub created: i64,
     pub modified: Option<i64>,
-    pub category: FileCategory,
-    pub file_icon_attrs: FileIconAttrs,
+    pub size: Option<i64>,
+    pub size_display: Option<String>,
 }
 
-// version
+impl From<&repo_files_versions_state::RepoFileVersion> for RepoFileVersion {
+    fn from(version: &repo_files_versions_state::RepoFileVersion) -> Self {
+        Self {
+            id: version.id.0.clone(),
+            repo_id: version.repo_id.0.clone(),
+            encrypted_path: version.encrypted_path.0.clone(),
+            original_path: version.original_path.0.clone(),
+            created: version.created,
+            modified: version.modified,
+            size: version.size,
+            size_display: version
+                .size
+                .map(|size| vault_core::files::file_size::size_display(size)),
+        }
+    }
+}
+
+pub trait RepoFilesVersionsListDone: Send + Sync + Debug {
+    fn on_done(&self, versions: Vec<RepoFileVersion>);
+}
+
+pub trait RepoFilesVersionsDone: Send + Sync + Debug {
+    fn on_done(&self);
+}
+
+// repo_files_offline
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct Version {
-    pub git_revision: Option<String>,
-    pub git_revision_url: Option<String>,
-    pub git_release: Option<String>,
-    pub git_release_url: Option<String>,
+pub struct RepoFilesOfflineInfo {
+    pub quota: i64,
+    pub used: i64,
+    pub pinned_used: i64,
+    pub pins_count: u32,
+    pub entries_count: u32,
 }
 
-impl From<vault_version::Version> for Version {
-    fn from(version: vault_version::Version) -> Self {
+impl From<&repo_files_offline_state::OfflineInfo> for RepoFilesOfflineInfo {
+    fn from(info: &repo_files_offline_state::OfflineInfo) -> Self {
         Self {
-            git_revision: version.git_revision,
-            git_revision_url: version.git_revision_url,
-            git_release: version.git_release,
-            git_release_url: version.git_release_url,
+            quota: info.quota,
+            used: info.used,
+            pinned_used: info.pinned_used,
+            pins_count: info.pins_count as u32,
+            entries_count: info.entries_count as u32,
         }
     }
 }
 
-// subscription_data
+pub trait RepoFilesOfflineDone: Send + Sync + Debug {
+    fn on_done(&self);
+}
 
-type Data<T> = Arc<Mutex<HashMap<u32, T>>>;
+// repo_duplicates
 
-#[derive(Clone)]
-struct VersionedFileBytes {
-    value: Option<Vec<u8>>,
-    version: u32,
+#[derive(Clone, Debug, PartialEq)]
+pub struct RepoDuplicatesFile {
+    pub file_id: String,
+    pub encrypted_path: String,
+    pub path: String,
+    pub modified: Option<i64>,
 }
 
-#[derive(Default)]
-struct SubscriptionData {
-    notifications: Data<Vec<Notification>>,
-    dialogs: Data<Vec<u32>>,
-    dialog: Data<Option<Dialog>>,
-    oauth2_status: Data<Status>,
-    user: Data<Option<User>>,
-    user_profile_picture_loaded: Data<bool>,
-    remote_files_browsers_info: Data<Option<RemoteFilesBrowserInfo>>,
+impl From<&repo_duplicates_state::RepoDuplicatesFile> for RepoDuplicatesFile {
+    fn from(file: &repo_duplicates_state::RepoDuplicatesFile) -> Self {
+        Self {
+            file_id: file.file_id.0.clone(),
+            encrypted_path: file.encrypted_path.0.clone(),
+            path: file.path.0.clone(),
+            modified: file.modified,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RepoDuplicatesGroup {
+    pub hash: String,
+    pub size: i64,
+    pub size_display: String,
+    pub reclaimable_size: i64,
+    pub reclaimable_size_display: String,
+    pub files: Vec<RepoDuplicatesFile>,
+}
+
+impl From<&repo_duplicates_state::RepoDuplicatesGroup> for RepoDuplicatesGroup {
+    fn from(group: &repo_duplicates_state::RepoDuplicatesGroup) -> Self {
+        Self {
+            hash: group.hash.clone(),
+            size: group.size,
+            size_display: vault_core::files::file_size::size_display(group.size),
+            reclaimable_size: group.reclaimable_size(),
+            reclaimable_size_display: vault_core::files::file_size::size_display(
+                group.reclaimable_size(),
+            ),
+            files: group.files.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RepoDuplicatesInfo {
+    pub repo_id: String,
+    pub status: Status,
+    pub groups: Vec<RepoDuplicatesGroup>,
+    pub reclaimable_size: i64,
+    pub reclaimable_size_display: String,
+}
+
+impl<'a> From<&repo_duplicates_state::RepoDuplicatesInfo<'a>> for RepoDuplicatesInfo {
+    fn from(info: &repo_duplicates_state::RepoDuplicatesInfo<'a>) -> Self {
+        Self {
+            repo_id: info.repo_id.0.clone(),
+            status: info.status.into(),
+            groups: info.groups.iter().map(Into::into).collect(),
+            reclaimable_size: info.reclaimable_size,
+            reclaimable_size_display: vault_core::files::file_size::size_display(
+                info.reclaimable_size,
+            ),
+        }
+    }
+}
+
+// local_files
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum LocalFileType {
+    Dir,
+    File,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LocalFile {
+    pub id: String,
+    pub name: String,
+    pub ext: Option<String>,
+    pub typ: LocalFileType,
+    pub size_display: String,
+    pub modified: Option<i64>,
+    pub category: FileCategory,
+    pub file_icon_attrs: FileIconAttrs,
+}
+
+// version
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Version {
+    pub git_revision: Option<String>,
+    pub git_revision_url: Option<String>,
+    pub git_release: Option<String>,
+    pub git_release_url: Option<String>,
+}
+
+impl From<vault_version::Version> for Version {
+    fn from(version: vault_version::Version) -> Self {
+        Self {
+            git_revision: version.git_revision,
+            git_revision_url: version.git_revision_url,
+            git_release: version.git_release,
+            git_release_url: version.git_release_url,
+        }
+    }
+}
+
+// subscription_data
+
+type Data<T> = Arc<Mutex<HashMap<u32, T>>>;
+
+#[derive(Clone)]
+struct VersionedFileBytes {
+    value: Option<Vec<u8>>,
+    version: u32,
+}
+
+#[derive(Default)]
+struct SubscriptionData {
+    notifications: Data<Vec<Notification>>,
+    dialogs: Data<Vec<u32>>,
+    dialog: Data<Option<Dialog>>,
+    oauth2_status: Data<Status>,
+    user: Data<Option<User>>,
+    accounts: Data<Vec<Account>>,
+    user_profile_picture_loaded: Data<bool>,
+    remote_files_browsers_info: Data<Option<RemoteFilesBrowserInfo>>,
     remote_files_browsers_breadcrumbs: Data<Vec<RemoteFilesBrowserBreadcrumb>>,
     repos: Data<Repos>,
     repos_repo: Data<RepoInfo>,
@@ -1745,8 +2393,10 @@ struct SubscriptionData {
     repo_files_details_info: Data<Option<RepoFilesDetailsInfo>>,
     repo_files_details_file: Data<Option<RepoFile>>,
     repo_files_details_content_bytes: Data<VersionedFileBytes>,
+    repo_files_details_conflict_text_merge: Data<Option<RepoFilesDetailsTextMerge>>,
     repo_files_move_is_visible: Data<bool>,
     repo_files_move_info: Data<Option<RepoFilesMoveInfo>>,
+    repo_duplicates_info: Data<Option<RepoDuplicatesInfo>>,
 }
 
 lazy_static! {
@@ -1773,6 +2423,7 @@ pub struct MobileVault {
 
     subscription_data: SubscriptionData,
     subscription: MobileSubscription,
+    oauth2_device_flow_abort_handle: Arc<Mutex<Option<AbortHandle>>>,
 }
 
 impl MobileVault {
@@ -1823,7 +2474,8 @@ impl MobileVault {
             base_url: base_url.clone(),
             auth_base_url: oauth2_auth_base_url.clone(),
             client_id: oauth2_client_id,
-            client_secret: oauth2_client_secret,
+            // an empty secret is used by public clients that only use PKCE
+            client_secret: Some(oauth2_client_secret).filter(|secret| !secret.is_empty()),
             redirect_uri: oauth2_redirect_uri,
         };
 
@@ -1858,6 +2510,7 @@ impl MobileVault {
 
             subscription_data,
             subscription,
+            oauth2_device_flow_abort_handle: Arc::new(Mutex::new(None)),
         }
     }
 
@@ -1943,6 +2596,13 @@ impl MobileVault {
             .with_state(|state| lifecycle::selectors::select_is_visible(state))
     }
 
+    // panic_lock
+
+    pub fn panic_lock(&self, wipe_secure_storage: bool) {
+        self.errors
+            .handle_result(self.vault.panic_lock(wipe_secure_storage));
+    }
+
     // relative_time
 
     pub fn relative_time(&self, value: i64, with_modifier: bool) -> RelativeTime {
@@ -1951,6 +2611,50 @@ impl MobileVault {
             .into()
     }
 
+    // locale
+
+    pub fn locale_get(&self) -> String {
+        self.vault.locale_get()
+    }
+
+    pub fn locale_set(&self, name: String) {
+        self.errors.handle_result(self.vault.locale_set(&name));
+    }
+
+    // audit_log
+
+    /// The log is stored in `data_dir`, it must be set before the vault is
+    /// loaded.
+    pub fn audit_log_set_data_dir(&self, data_dir: String) {
+        match FileAuditLogDiskStorage::new(PathBuf::from(data_dir).join("audit-log.json")) {
+            Ok(disk_storage) => self
+                .vault
+                .audit_log_set_disk_storage(Some(Box::new(disk_storage))),
+            Err(err) => log::warn!("Audit log disk storage not available: {}", err),
+        }
+    }
+
+    pub fn audit_log_entries(&self, filter: AuditLogFilter) -> Vec<AuditLogEntry> {
+        self.vault
+            .audit_log_entries(&filter.into())
+            .into_iter()
+            .map(Into::into)
+            .collect()
+    }
+
+    pub fn audit_log_export(&self) -> String {
+        self.vault.audit_log_export()
+    }
+
+    pub fn audit_log_verify(&self) -> bool {
+        let res = self.vault.audit_log_verify();
+        let valid = res.is_ok();
+
+        self.errors.handle_result(res);
+
+        valid
+    }
+
     // notifications
 
     pub fn notifications_subscribe(&self, cb: Box<dyn SubscriptionCallback>) -> u32 {
@@ -2096,6 +2800,58 @@ impl MobileVault {
         })
     }
 
+    pub fn oauth2_start_device_flow(self: Arc<Self>, cb: Box<dyn OAuth2StartDeviceFlowDone>) {
+        self.clone().spawn(async move {
+            match self.vault.oauth2_start_device_flow().await {
+                Ok(device_authorization) => cb.on_done(Some((&device_authorization).into())),
+                Err(err) => {
+                    self.errors.handle_error(err);
+
+                    cb.on_done(None);
+                }
+            }
+        })
+    }
+
+    pub fn oauth2_finish_device_flow(
+        self: Arc<Self>,
+        device_authorization: DeviceAuthorization,
+        cb: Box<dyn OAuth2FinishFlowDone>,
+    ) {
+        let (abort_handle, abort_registration) = AbortHandle::new_pair();
+
+        if let Some(previous_abort_handle) = self
+            .oauth2_device_flow_abort_handle
+            .lock()
+            .unwrap()
+            .replace(abort_handle)
+        {
+            previous_abort_handle.abort();
+        }
+
+        self.clone().spawn_result(async move {
+            let res = self
+                .vault
+                .oauth2_finish_device_flow(&device_authorization.into(), abort_registration)
+                .await;
+
+            cb.on_done();
+
+            match res {
+                Err(lifecycle::errors::OAuth2FinishDeviceFlowError::OAuth2Error(
+                    oauth2::errors::OAuth2Error::Canceled,
+                )) => Ok(()),
+                res => res,
+            }
+        })
+    }
+
+    pub fn oauth2_cancel_device_flow(&self) {
+        if let Some(abort_handle) = self.oauth2_device_flow_abort_handle.lock().unwrap().take() {
+            abort_handle.abort();
+        }
+    }
+
     // user
 
     pub fn user_subscribe(&self, cb: Box<dyn SubscriptionCallback>) -> u32 {
@@ -2155,6 +2911,56 @@ impl MobileVault {
             .spawn_result(async move { self.vault.user_ensure_profile_picture().await })
     }
 
+    // accounts
+
+    pub fn accounts_subscribe(&self, cb: Box<dyn SubscriptionCallback>) -> u32 {
+        self.subscribe(
+            &[Event::Accounts],
+            cb,
+            self.subscription_data.accounts.clone(),
+            move |vault| {
+                vault.with_state(|state| {
+                    vault_core::accounts::selectors::select_accounts(state)
+                        .iter()
+                        .map(Into::into)
+                        .collect()
+                })
+            },
+        )
+    }
+
+    pub fn accounts_data(&self, id: u32) -> Option<Vec<Account>> {
+        self.get_data(id, self.subscription_data.accounts.clone())
+    }
+
+    pub fn accounts_switch(self: Arc<Self>, user_id: String, cb: Box<dyn AccountsSwitchDone>) {
+        self.clone().spawn(async move {
+            let res = self.vault.accounts_switch(&user_id).await;
+
+            if res.is_ok() {
+                cb.on_done();
+            }
+
+            self.errors.handle_result(res);
+        });
+    }
+
+    /// Returns the login URL of the new account.
+    pub fn accounts_add(&self) -> Option<String> {
+        match self.vault.accounts_add() {
+            Ok(url) => Some(url),
+            Err(err) => {
+                self.errors.handle_error(err);
+                None
+            }
+        }
+    }
+
+    pub fn accounts_remove(&self, user_id: String) {
+        self.errors
+            .handle_result(self.vault.accounts_remove(&user_id));
+    }
+
     // file_icon
 
     pub fn file_icon_png(&self, props: FileIconProps, scale: u32) -> FileIconPng {
@@ -2394,6 +3200,11 @@ impl MobileVault {
         self.vault.repos_set_default_auto_lock(auto_lock.into());
     }
 
+    pub fn repos_set_unlock_lockout(&self, unlock_lockout: Option<RepoUnlockLockout>) {
+        self.vault
+            .repos_set_unlock_lockout(unlock_lockout.map(Into::into));
+    }
+
     // repo_create
 
     pub fn repo_create_create(self: Arc<Self>) -> u32 {
@@ -2451,7 +3262,7 @@ impl MobileVault {
                                     .iter()
                                     .map(RemoteFilesBreadcrumb::from)
                                     .collect();
-                                let password = form.password.clone();
+                                let password = form.password.to_string();
                                 let salt = form.salt.clone();
                                 let fill_from_rclone_config_error = form
                                     .fill_from_rclone_config_error
@@ -2534,6 +3345,8 @@ impl MobileVault {
                         RepoUnlockInfo {
                             status: info.status.into(),
                             repo_name: info.repo_name.map(|x| x.0.clone()),
+                            failed_attempts: info.failed_attempts,
+                            retry_at: info.retry_at.map(|x| x.0),
                         }
                     })
                 })
@@ -3050,7 +3863,11 @@ impl MobileVault {
         cb: Box<dyn SubscriptionCallback>,
     ) -> u32 {
         self.subscribe(
-            &[Event::RepoFilesBrowsers, Event::RepoFiles],
+            &[
+                Event::RepoFilesBrowsers,
+                Event::RepoFiles,
+                Event::Eventstream,
+            ],
             cb,
             self.subscription_data.repo_files_browsers_info.clone(),
             move |vault| {
@@ -3262,6 +4079,40 @@ impl MobileVault {
         })
     }
 
+    pub fn repo_files_browsers_rename_selected_preview(
+        &self,
+        browser_id: u32,
+        pattern: RepoFilesRenamePattern,
+    ) -> RepoFilesRenamePreview {
+        match self
+            .vault
+            .repo_files_browsers_rename_selected_preview(browser_id, &pattern.into())
+        {
+            Ok(items) => RepoFilesRenamePreview {
+                items: items.iter().map(Into::into).collect(),
+                error: None,
+            },
+            Err(err) => RepoFilesRenamePreview {
+                items: vec![],
+                error: Some(err.user_error()),
+            },
+        }
+    }
+
+    pub fn repo_files_browsers_rename_selected(
+        self: Arc<Self>,
+        browser_id: u32,
+        pattern: RepoFilesRenamePattern,
+        cb: Box<dyn RepoFilesRenameDone>,
+    ) {
+        self.clone().spawn_result(async move {
+            self.vault
+                .repo_files_browsers_rename_selected(browser_id, &pattern.into())
+                .await
+                .map(|results| cb.on_done(results.iter().map(Into::into).collect()))
+        })
+    }
+
     // repo_files_details
 
     pub fn repo_files_details_create(
@@ -3449,6 +4300,80 @@ impl MobileVault {
         });
     }
 
+    pub fn repo_files_details_conflict_text_merge_subscribe(
+        &self,
+        details_id: u32,
+        cb: Box<dyn SubscriptionCallback>,
+    ) -> u32 {
+        self.subscribe(
+            &[Event::RepoFilesDetails],
+            cb,
+            self.subscription_data
+                .repo_files_details_conflict_text_merge
+                .clone(),
+            move |vault| {
+                vault.with_state(|state| {
+                    vault_core::repo_files_details::selectors::select_conflict_text_merge(
+                        state, details_id,
+                    )
+                    .map(Into::into)
+                })
+            },
+        )
+    }
+
+    pub fn repo_files_details_conflict_text_merge_data(
+        &self,
+        id: u32,
+    ) -> Option<RepoFilesDetailsTextMerge> {
+        self.get_data(
+            id,
+            self.subscription_data
+                .repo_files_details_conflict_text_merge
+                .clone(),
+        )
+        .flatten()
+    }
+
+    pub fn repo_files_details_conflict_keep_mine(self: Arc<Self>, details_id: u32) {
+        self.clone().spawn(async move {
+            // error is displayed in the details component
+            let _ = self
+                .vault
+                .clone()
+                .repo_files_details_conflict_keep_mine(details_id)
+                .await;
+        });
+    }
+
+    pub fn repo_files_details_conflict_merge(self: Arc<Self>, details_id: u32, content: Vec<u8>) {
+        self.clone().spawn(async move {
+            // error is displayed in the details component
+            let _ = self
+                .vault
+                .clone()
+                .repo_files_details_conflict_merge(details_id, content)
+                .await;
+        });
+    }
+
+    pub fn repo_files_details_conflict_keep_theirs(&self, details_id: u32) {
+        self.errors.handle_result(
+            self.vault
+                .repo_files_details_conflict_keep_theirs(details_id),
+        );
+    }
+
+    pub fn repo_files_details_conflict_save_as_copy(self: Arc<Self>, details_id: u32) {
+        self.clone().spawn_result(async move {
+            self.vault
+                .clone()
+                .repo_files_details_conflict_save_as_copy(details_id)
+                .await
+                .map(|_| ())
+        });
+    }
+
     // repo_files_move
 
     pub fn repo_files_move_is_visible_subscribe(&self, cb: Box<dyn SubscriptionCallback>) -> u32 {
@@ -3513,6 +4438,257 @@ impl MobileVault {
         self.vault.repo_files_move_cancel()
     }
 
+    // repo_files_versions
+
+    pub fn repo_files_versions_get_settings(
+        &self,
+        repo_id: String,
+    ) -> Option<RepoFilesVersionsSettings> {
+        match self
+            .vault
+            .repo_files_versions_get_settings(&RepoId(repo_id))
+        {
+            Ok(settings) => Some((&settings).into()),
+            Err(err) => {
+                self.errors.handle_error(err);
+
+                None
+            }
+        }
+    }
+
+    pub fn repo_files_versions_set_settings(
+        &self,
+        repo_id: String,
+        settings: RepoFilesVersionsSettings,
+    ) {
+        self.errors.handle_result(
+            self.vault
+                .repo_files_versions_set_settings(&RepoId(repo_id), settings.into()),
+        );
+    }
+
+    /// Versions are sorted from newest to oldest.
+    pub fn repo_files_versions_list(
+        self: Arc<Self>,
+        repo_id: String,
+        encrypted_path: String,
+        cb: Box<dyn RepoFilesVersionsListDone>,
+    ) {
+        self.clone().spawn_result(async move {
+            self.vault
+                .repo_files_versions_list(&RepoId(repo_id), &EncryptedPath(encrypted_path))
+                .await
+                .map(|versions| cb.on_done(versions.iter().map(Into::into).collect()))
+        });
+    }
+
+    pub fn repo_files_versions_restore(
+        self: Arc<Self>,
+        repo_id: String,
+        encrypted_path: String,
+        version_encrypted_path: String,
+        cb: Box<dyn RepoFilesVersionsDone>,
+    ) {
+        self.clone().spawn(async move {
+            let res = self
+                .vault
+                .repo_files_versions_restore(
+                    &RepoId(repo_id),
+                    &EncryptedPath(encrypted_path),
+                    &EncryptedPath(version_encrypted_path),
+                )
+                .await;
+
+            if res.is_ok() {
+                cb.on_done();
+            }
+
+            self.errors.handle_result(res);
+        });
+    }
+
+    pub fn repo_files_versions_prune(
+        self: Arc<Self>,
+        repo_id: String,
+        encrypted_path: String,
+        cb: Box<dyn RepoFilesVersionsDone>,
+    ) {
+        self.clone().spawn(async move {
+            let res = self
+                .vault
+                .repo_files_versions_prune(&RepoId(repo_id), &EncryptedPath(encrypted_path))
+                .await;
+
+            if res.is_ok() {
+                cb.on_done();
+            }
+
+            self.errors.handle_result(res);
+        });
+    }
+
+    // repo_files_offline
+
+    /// Offline files are stored in `cache_dir`, it is created if it does not
+    /// exist.
+    pub fn repo_files_offline_set_cache_dir(&self, cache_dir: String) {
+        match FileOfflineDiskCache::new(cache_dir.into()) {
+            Ok(disk_cache) => self
+                .vault
+                .repo_files_offline_set_disk_cache(Some(Box::new(disk_cache))),
+            Err(err) => log::warn!("Offline disk cache not available: {}", err),
+        }
+    }
+
+    pub fn repo_files_offline_pin(
+        self: Arc<Self>,
+        repo_id: String,
+        encrypted_path: String,
+        cb: Box<dyn RepoFilesOfflineDone>,
+    ) {
+        self.clone().spawn(async move {
+            let res = self
+                .vault
+                .repo_files_offline_pin(RepoId(repo_id), EncryptedPath(encrypted_path))
+                .await;
+
+            if res.is_ok() {
+                cb.on_done();
+            }
+
+            self.errors.handle_result(res);
+        });
+    }
+
+    pub fn repo_files_offline_unpin(
+        self: Arc<Self>,
+        repo_id: String,
+        encrypted_path: String,
+        cb: Box<dyn RepoFilesOfflineDone>,
+    ) {
+        self.clone().spawn(async move {
+            let res = self
+                .vault
+                .repo_files_offline_unpin(&RepoId(repo_id), &EncryptedPath(encrypted_path))
+                .await;
+
+            if res.is_ok() {
+                cb.on_done();
+            }
+
+            self.errors.handle_result(res);
+        });
+    }
+
+    pub fn repo_files_offline_is_pinned(&self, repo_id: String, encrypted_path: String) -> bool {
+        self.vault
+            .repo_files_offline_is_pinned(&RepoId(repo_id), &EncryptedPath(encrypted_path))
+    }
+
+    pub fn repo_files_offline_refresh(self: Arc<Self>, cb: Box<dyn RepoFilesOfflineDone>) {
+        self.clone().spawn(async move {
+            let res = self.vault.repo_files_offline_refresh().await;
+
+            if res.is_ok() {
+                cb.on_done();
+            }
+
+            self.errors.handle_result(res);
+        });
+    }
+
+    pub fn repo_files_offline_set_quota(
+        self: Arc<Self>,
+        quota: i64,
+        cb: Box<dyn RepoFilesOfflineDone>,
+    ) {
+        self.clone().spawn(async move {
+            let res = self.vault.repo_files_offline_set_quota(quota).await;
+
+            if res.is_ok() {
+                cb.on_done();
+            }
+
+            self.errors.handle_result(res);
+        });
+    }
+
+    pub fn repo_files_offline_info(&self) -> RepoFilesOfflineInfo {
+        (&self.vault.repo_files_offline_get_info()).into()
+    }
+
+    pub fn repo_files_offline_clear(self: Arc<Self>, cb: Box<dyn RepoFilesOfflineDone>) {
+        self.clone().spawn(async move {
+            let res = self.vault.repo_files_offline_clear().await;
+
+            if res.is_ok() {
+                cb.on_done();
+            }
+
+            self.errors.handle_result(res);
+        });
+    }
+
+    // repo_duplicates
+
+    pub fn repo_duplicates_create(&self, repo_id: String) -> u32 {
+        self.vault.repo_duplicates_create(RepoId(repo_id))
+    }
+
+    pub fn repo_duplicates_info_subscribe(
+        &self,
+        duplicates_id: u32,
+        cb: Box<dyn SubscriptionCallback>,
+    ) -> u32 {
+        self.subscribe(
+            &[Event::RepoDuplicates],
+            cb,
+            self.subscription_data.repo_duplicates_info.clone(),
+            move |vault| {
+                vault.with_state(|state| {
+                    vault_core::repo_duplicates::selectors::select_info(state, duplicates_id)
+                        .as_ref()
+                        .map(Into::into)
+                })
+            },
+        )
+    }
+
+    pub fn repo_duplicates_info_data(&self, id: u32) -> Option<RepoDuplicatesInfo> {
+        self.get_data(id, self.subscription_data.repo_duplicates_info.clone())
+            .flatten()
+    }
+
+    pub fn repo_duplicates_scan(self: Arc<Self>, duplicates_id: u32) {
+        self.clone().spawn(async move {
+            // error is displayed in the duplicates component
+            let _ = self.vault.repo_duplicates_scan(duplicates_id).await;
+        });
+    }
+
+    /// If `hashes` is set only the matching groups are processed.
+    pub fn repo_duplicates_delete_all_but_newest(
+        self: Arc<Self>,
+        duplicates_id: u32,
+        hashes: Option<Vec<String>>,
+    ) {
+        self.clone().spawn_result(async move {
+            match self
+                .vault
+                .repo_duplicates_delete_all_but_newest(duplicates_id, hashes)
+                .await
+            {
+                Err(vault_core::repo_files::errors::DeleteFileError::Canceled) => Ok(()),
+                res => res,
+            }
+        });
+    }
+
+    pub fn repo_duplicates_destroy(&self, duplicates_id: u32) {
+        self.vault.repo_duplicates_destroy(duplicates_id);
+    }
+
     // local_files
 
     pub fn local_files_file_info(
@@ -3624,11 +4800,13 @@ impl FakeRemote {
             http_addr: http_addr.parse().unwrap(),
             https_addr: https_addr.parse().unwrap(),
             object_provider: Arc::new(Box::new(MemoryObjectProvider::new())),
+            data_path: None,
             user_id: "b2977f16-4766-4528-a26f-4b0b13bf2c9c".into(),
             mount_id: "9fd62581-3bad-478a-702b-01937d2bf7f1".into(),
             oauth2_access_token: "f1fed68a-6b5c-4067-928e-40ed48dd2589".into(),
             oauth2_refresh_token: "a126768a-ce0b-4b93-8a9b-809f02f4c000".into(),
             create_vault_repo: false,
+            fault_profile: None,
         };
 
         let fake_remote_app = RT.block_on(vault_fake_remote::fake_remote::app::FakeRemoteApp::new(
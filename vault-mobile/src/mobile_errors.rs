@@ -16,7 +16,7 @@ impl MobileErrors {
     }
 
     pub fn handle_error(&self, user_error: impl UserError) {
-        self.handle_error_str(user_error.user_error());
+        self.vault.notifications_show_error(&user_error);
     }
 
     pub fn handle_result(&self, result: Result<(), impl UserError>) {
@@ -45,6 +45,7 @@ fn test_repo() {
                         after: RepoAutoLockAfter::Inactive1Hour,
                         on_app_hidden: false,
                     },
+                    read_only: false,
                 }
             );
 
@@ -66,6 +67,7 @@ fn test_repo() {
                         after: RepoAutoLockAfter::Inactive1Hour,
                         on_app_hidden: false,
                     },
+                    read_only: false,
                 }
             );
         }
@@ -0,0 +1,377 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use async_trait::async_trait;
+use tokio::task::JoinHandle;
+
+use vault_core::{
+    common::state::{BoxAsyncRead, SizeInfo},
+    repo_files::state::RepoFilesUploadConflictResolution,
+    transfers::{
+        errors::UploadableError, state::TransferUploadRelativeName, uploadable::Uploadable,
+    },
+    types::EncryptedPath,
+    Vault,
+};
+
+use crate::{file_utils, transfers::file_uploadable::FileUploadable};
+
+use super::{
+    config::AutoUploadConfig,
+    errors::AutoUploadError,
+    filter::AutoUploadFilter,
+    uploaded::{self, UploadedFile, UploadedFiles},
+    watcher::{FileWatcher, WatchEvent},
+};
+
+/// Auto uploads stay in the transfers list when they are done.
+struct AutoUploadUploadable(FileUploadable);
+
+#[async_trait]
+impl Uploadable for AutoUploadUploadable {
+    async fn size(&self) -> Result<SizeInfo, UploadableError> {
+        self.0.size().await
+    }
+
+    async fn is_retriable(&self) -> Result<bool, UploadableError> {
+        self.0.is_retriable().await
+    }
+
+    async fn is_persistent(&self) -> Result<bool, UploadableError> {
+        Ok(true)
+    }
+
+    async fn reader(&self) -> Result<(BoxAsyncRead, SizeInfo), UploadableError> {
+        self.0.reader().await
+    }
+}
+
+struct PendingFile {
+    size: i64,
+    modified: i64,
+    changed: Instant,
+}
+
+struct AutoUploadTask {
+    vault: Arc<Vault>,
+    config: AutoUploadConfig,
+    filter: AutoUploadFilter,
+    uploaded: Arc<Mutex<UploadedFiles>>,
+    uploading: Arc<Mutex<HashSet<String>>>,
+    pending: HashMap<String, PendingFile>,
+}
+
+impl AutoUploadTask {
+    fn local_path(&self, path: &str) -> PathBuf {
+        self.config.local_path.join(path)
+    }
+
+    /// Returns the size and modification time if the file should be
+    /// uploaded.
+    fn get_candidate(&self, path: &str, metadata: &std::fs::Metadata) -> Option<(i64, i64)> {
+        if !metadata.is_file() || !self.filter.matches(path) {
+            return None;
+        }
+
+        let size = metadata.len() as i64;
+        let modified = file_utils::modified_millis(metadata);
+
+        if self.uploading.lock().unwrap().contains(path)
+            || self.uploaded.lock().unwrap().contains(path, size, modified)
+        {
+            return None;
+        }
+
+        Some((size, modified))
+    }
+
+    fn stat_candidate(&self, path: &str) -> Option<(i64, i64)> {
+        std::fs::symlink_metadata(self.local_path(path))
+            .ok()
+            .and_then(|metadata| self.get_candidate(path, &metadata))
+    }
+
+    /// Every change restarts the wait for the file to become stable.
+    fn on_changed(&mut self, path: String) {
+        match self.stat_candidate(&path) {
+            Some((size, modified)) => {
+                self.pending.insert(
+                    path,
+                    PendingFile {
+                        size,
+                        modified,
+                        changed: Instant::now(),
+                    },
+                );
+            }
+            None => {
+                self.pending.remove(&path);
+            }
+        }
+    }
+
+    async fn rescan(&mut self) {
+        let files = match file_utils::list_files_recursive(&self.config.local_path).await {
+            Ok(files) => files,
+            Err(err) => {
+                log::warn!("Auto upload list failed: {}", err);
+
+                return;
+            }
+        };
+
+        let existing = files
+            .iter()
+            .map(|(path, _)| path.clone())
+            .collect::<HashSet<_>>();
+
+        if let Err(err) = self.uploaded.lock().unwrap().retain_existing(&existing) {
+            log::warn!("Auto upload save failed: {}", err);
+        }
+
+        let now = Instant::now();
+
+        for (path, metadata) in files {
+            if self.pending.contains_key(&path) {
+                continue;
+            }
+
+            if let Some((size, modified)) = self.get_candidate(&path, &metadata) {
+                self.pending.insert(
+                    path,
+                    PendingFile {
+                        size,
+                        modified,
+                        changed: now,
+                    },
+                );
+            }
+        }
+    }
+
+    fn process_pending(&mut self) {
+        // files stay pending while the repo is locked
+        let cipher = match self.vault.repos_service.get_cipher(&self.config.repo_id) {
+            Ok(cipher) => cipher,
+            Err(_) => return,
+        };
+
+        let now = Instant::now();
+
+        let ready_paths = self
+            .pending
+            .iter()
+            .filter(|(_, file)| now.duration_since(file.changed) >= self.config.stable_duration)
+            .map(|(path, _)| path.clone())
+            .collect::<Vec<_>>();
+
+        for path in ready_paths {
+            let (size, modified) = match self.stat_candidate(&path) {
+                Some(candidate) => candidate,
+                None => {
+                    self.pending.remove(&path);
+
+                    continue;
+                }
+            };
+
+            let file = self.pending.get_mut(&path).unwrap();
+
+            if file.size != size || file.modified != modified {
+                file.size = size;
+                file.modified = modified;
+                file.changed = now;
+
+                continue;
+            }
+
+            self.pending.remove(&path);
+
+            self.upload(
+                path,
+                size,
+                modified,
+                cipher.encrypt_path(&self.config.remote_path),
+            );
+        }
+    }
+
+    fn upload(&self, path: String, size: i64, modified: i64, parent_path: EncryptedPath) {
+        let local_path = self.local_path(&path);
+
+        // a changed file replaces the file it was uploaded to, unless that
+        // file was changed remotely in the meantime, then it is uploaded next
+        // to it. new files get an unused name.
+        let (name, conflict_resolution) = match self.uploaded.lock().unwrap().get(&path) {
+            Some(UploadedFile {
+                remote_name,
+                remote_hash: Some(remote_hash),
+                ..
+            }) => (
+                remote_name.clone(),
+                RepoFilesUploadConflictResolution::OverwriteOrAutorename {
+                    if_remote_size: None,
+                    if_remote_modified: None,
+                    if_remote_hash: Some(remote_hash.clone()),
+                },
+            ),
+            _ => (path.clone(), RepoFilesUploadConflictResolution::Error),
+        };
+
+        let (_, create_future) = self
+            .vault
+            .transfers_service
+            .clone()
+            .upload_with_conflict_resolution(
+                self.config.repo_id.clone(),
+                parent_path,
+                TransferUploadRelativeName(name),
+                Box::new(AutoUploadUploadable(FileUploadable {
+                    path: local_path.clone(),
+                    cleanup: None,
+                })),
+                conflict_resolution,
+            );
+
+        self.uploading.lock().unwrap().insert(path.clone());
+
+        let uploaded = self.uploaded.clone();
+        let uploading = self.uploading.clone();
+        let delete_after_upload = self.config.delete_after_upload;
+
+        tokio::spawn(async move {
+            let res = match create_future.await {
+                Ok(future) => future.await,
+                Err(err) => Err(err),
+            };
+
+            match res {
+                Ok(result) => {
+                    let remote_name = match path.rfind('/') {
+                        Some(idx) => format!("{}/{}", &path[..idx], result.name.0),
+                        None => result.name.0,
+                    };
+
+                    if let Err(err) = uploaded.lock().unwrap().insert(
+                        path.clone(),
+                        UploadedFile {
+                            size,
+                            modified,
+                            remote_name,
+                            remote_hash: result.remote_file.hash,
+                        },
+                    ) {
+                        log::warn!("Auto upload save failed: {}", err);
+                    }
+
+                    if delete_after_upload {
+                        delete_if_unchanged(&local_path, size, modified).await;
+                    }
+                }
+                Err(err) => log::warn!("Auto upload failed: {}: {}", path, err),
+            }
+
+            uploading.lock().unwrap().remove(&path);
+        });
+    }
+
+    async fn run(mut self, mut watcher: FileWatcher) {
+        let mut interval = tokio::time::interval(self.config.debounce);
+
+        self.rescan().await;
+
+        loop {
+            tokio::select! {
+                res = watcher.next() => {
+                    let events = match res {
+                        Ok(events) => events,
+                        Err(err) => {
+                            log::warn!("Auto upload watch failed: {}", err);
+
+                            return;
+                        }
+                    };
+
+                    let mut rescan = false;
+
+                    for event in events {
+                        match event {
+                            WatchEvent::Changed(path) => self.on_changed(path),
+                            WatchEvent::Rescan => rescan = true,
+                        }
+                    }
+
+                    if rescan {
+                        self.rescan().await;
+                    }
+                }
+                _ = interval.tick() => self.process_pending(),
+            }
+        }
+    }
+}
+
+/// The file could have been changed again while it was uploading.
+async fn delete_if_unchanged(path: &Path, size: i64, modified: i64) {
+    match tokio::fs::symlink_metadata(path).await {
+        Ok(metadata)
+            if metadata.len() as i64 == size
+                && file_utils::modified_millis(&metadata) == modified =>
+        {
+            if let Err(err) = tokio::fs::remove_file(path).await {
+                log::warn!("Auto upload delete failed: {:?}: {}", path, err);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Watches a local dir and uploads new and changed files. Watching stops
+/// when the uploader is dropped.
+pub struct AutoUploader {
+    join_handle: JoinHandle<()>,
+}
+
+impl AutoUploader {
+    pub fn start(
+        vault: Arc<Vault>,
+        tokio_handle: &tokio::runtime::Handle,
+        config: AutoUploadConfig,
+    ) -> Result<Self, AutoUploadError> {
+        let filter = AutoUploadFilter::new(&config.include, &config.exclude)?;
+
+        let uploaded = UploadedFiles::load(
+            vault.secure_storage_service.clone(),
+            uploaded::get_storage_key(&config.repo_id, &config.remote_path, &config.local_path),
+        )?;
+
+        let watcher = {
+            let _guard = tokio_handle.enter();
+
+            FileWatcher::new(config.local_path.clone(), config.poll_interval)?
+        };
+
+        let task = AutoUploadTask {
+            vault,
+            config,
+            filter,
+            uploaded: Arc::new(Mutex::new(uploaded)),
+            uploading: Arc::new(Mutex::new(HashSet::new())),
+            pending: HashMap::new(),
+        };
+
+        let join_handle = tokio_handle.spawn(task.run(watcher));
+
+        Ok(Self { join_handle })
+    }
+}
+
+impl Drop for AutoUploader {
+    fn drop(&mut self) {
+        self.join_handle.abort();
+    }
+}
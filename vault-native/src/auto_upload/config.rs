@@ -0,0 +1,38 @@
+use std::{path::PathBuf, time::Duration};
+
+use vault_core::types::{DecryptedPath, RepoId};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AutoUploadConfig {
+    pub local_path: PathBuf,
+    pub repo_id: RepoId,
+    pub remote_path: DecryptedPath,
+    /// Globs matched against paths relative to `local_path` (e.g.
+    /// "**/*.jpg"). If empty, all files are included.
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+    pub delete_after_upload: bool,
+    /// How often pending files are checked.
+    pub debounce: Duration,
+    /// A file is uploaded once its size and modification time have not
+    /// changed for this long.
+    pub stable_duration: Duration,
+    /// Used where inotify is not available.
+    pub poll_interval: Duration,
+}
+
+impl AutoUploadConfig {
+    pub fn new(local_path: PathBuf, repo_id: RepoId, remote_path: DecryptedPath) -> Self {
+        Self {
+            local_path,
+            repo_id,
+            remote_path,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            delete_after_upload: false,
+            debounce: Duration::from_secs(1),
+            stable_duration: Duration::from_secs(3),
+            poll_interval: Duration::from_secs(30),
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use thiserror::Error;
+
+use vault_core::{
+    secure_storage::errors::SecureStorageError,
+    user_error::{ErrorCode, UserError},
+};
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum AutoUploadError {
+    #[error("invalid glob: {0}")]
+    InvalidGlob(String),
+    #[error("watch error: {0}")]
+    WatchError(String),
+    #[error("storage error: {0}")]
+    StorageError(#[from] SecureStorageError),
+}
+
+impl UserError for AutoUploadError {
+    fn user_error(&self) -> String {
+        match self {
+            Self::InvalidGlob(_) => self.to_string(),
+            Self::WatchError(_) => self.to_string(),
+            Self::StorageError(err) => vault_core::locale::message_args(
+                "error-storage",
+                &[("error", err.to_string().into())],
+            ),
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::InvalidGlob(_) => ErrorCode::AutoUploadInvalidGlob,
+            Self::WatchError(_) => ErrorCode::AutoUploadWatch,
+            Self::StorageError(_) => ErrorCode::Storage,
+        }
+    }
+}
+
+impl From<std::io::Error> for AutoUploadError {
+    fn from(err: std::io::Error) -> Self {
+        Self::WatchError(err.to_string())
+    }
+}
@@ -0,0 +1,68 @@
+use globset::{Glob, GlobSet, GlobSetBuilder};
+
+use super::errors::AutoUploadError;
+
+fn build_glob_set(globs: &[String]) -> Result<GlobSet, AutoUploadError> {
+    let mut builder = GlobSetBuilder::new();
+
+    for glob in globs {
+        builder.add(Glob::new(glob).map_err(|err| AutoUploadError::InvalidGlob(err.to_string()))?);
+    }
+
+    builder
+        .build()
+        .map_err(|err| AutoUploadError::InvalidGlob(err.to_string()))
+}
+
+#[derive(Debug, Clone)]
+pub struct AutoUploadFilter {
+    include: Option<GlobSet>,
+    exclude: GlobSet,
+}
+
+impl AutoUploadFilter {
+    pub fn new(include: &[String], exclude: &[String]) -> Result<Self, AutoUploadError> {
+        Ok(Self {
+            include: if include.is_empty() {
+                None
+            } else {
+                Some(build_glob_set(include)?)
+            },
+            exclude: build_glob_set(exclude)?,
+        })
+    }
+
+    /// `path` is relative to the watched dir (e.g. "dir/file.txt").
+    pub fn matches(&self, path: &str) -> bool {
+        self.include
+            .as_ref()
+            .map(|include| include.is_match(path))
+            .unwrap_or(true)
+            && !self.exclude.is_match(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AutoUploadFilter;
+
+    #[test]
+    fn test_matches() {
+        let filter = AutoUploadFilter::new(&[], &[]).unwrap();
+        assert!(filter.matches("file.txt"));
+        assert!(filter.matches("dir/file.txt"));
+
+        let filter = AutoUploadFilter::new(
+            &["*.jpg".to_owned(), "*.png".to_owned()],
+            &[".thumbnails/**".to_owned(), "*.tmp.jpg".to_owned()],
+        )
+        .unwrap();
+        assert!(filter.matches("image.jpg"));
+        assert!(filter.matches("2024/image.png"));
+        assert!(!filter.matches("notes.txt"));
+        assert!(!filter.matches(".thumbnails/image.jpg"));
+        assert!(!filter.matches("image.tmp.jpg"));
+
+        assert!(AutoUploadFilter::new(&["[".to_owned()], &[]).is_err());
+    }
+}
@@ -0,0 +1,8 @@
+pub mod auto_uploader;
+pub mod config;
+pub mod errors;
+pub mod filter;
+pub mod uploaded;
+pub mod watcher;
+
+pub use self::auto_uploader::AutoUploader;
@@ -0,0 +1,95 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+    sync::Arc,
+};
+
+use serde::{Deserialize, Serialize};
+use vault_core::{
+    secure_storage::{
+        errors::SecureStorageError, selectors as secure_storage_selectors, SecureStorageService,
+    },
+    types::{DecryptedPath, RepoId},
+};
+
+const STORAGE_KEY_PREFIX: &str = "vaultAutoUpload_";
+
+pub fn get_storage_key(repo_id: &RepoId, remote_path: &DecryptedPath, local_path: &Path) -> String {
+    secure_storage_selectors::get_location_storage_key(
+        STORAGE_KEY_PREFIX,
+        repo_id,
+        remote_path,
+        &local_path.to_string_lossy(),
+    )
+}
+
+/// `remote_name` and `remote_hash` identify the uploaded remote file so that
+/// a changed local file replaces it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UploadedFile {
+    pub size: i64,
+    pub modified: i64,
+    pub remote_name: String,
+    pub remote_hash: Option<String>,
+}
+
+/// Files that were already uploaded, by relative path. A file is uploaded
+/// again if its size or modification time changes.
+pub struct UploadedFiles {
+    secure_storage_service: Arc<SecureStorageService>,
+    key: String,
+    files: HashMap<String, UploadedFile>,
+}
+
+impl UploadedFiles {
+    pub fn load(
+        secure_storage_service: Arc<SecureStorageService>,
+        key: String,
+    ) -> Result<Self, SecureStorageError> {
+        let files = secure_storage_service.get(&key)?.unwrap_or_default();
+
+        Ok(Self {
+            secure_storage_service,
+            key,
+            files,
+        })
+    }
+
+    pub fn contains(&self, path: &str, size: i64, modified: i64) -> bool {
+        self.files
+            .get(path)
+            .filter(|file| file.size == size && file.modified == modified)
+            .is_some()
+    }
+
+    pub fn get(&self, path: &str) -> Option<&UploadedFile> {
+        self.files.get(path)
+    }
+
+    pub fn insert(&mut self, path: String, file: UploadedFile) -> Result<(), SecureStorageError> {
+        self.files.insert(path, file);
+
+        self.save()
+    }
+
+    /// Forgets files that no longer exist so that the set does not grow
+    /// forever with `delete_after_upload`.
+    pub fn retain_existing(
+        &mut self,
+        existing: &HashSet<String>,
+    ) -> Result<(), SecureStorageError> {
+        let len = self.files.len();
+
+        self.files.retain(|path, _| existing.contains(path));
+
+        if self.files.len() != len {
+            self.save()?;
+        }
+
+        Ok(())
+    }
+
+    fn save(&self) -> Result<(), SecureStorageError> {
+        self.secure_storage_service.set(&self.key, &self.files)
+    }
+}
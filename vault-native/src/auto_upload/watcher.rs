@@ -0,0 +1,215 @@
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WatchEvent {
+    /// A file was created, written or moved into the watched dir. The path is
+    /// relative to the watched dir (e.g. "dir/file.txt").
+    Changed(String),
+    /// Events might have been missed, the whole dir has to be listed again.
+    Rescan,
+}
+
+#[cfg(target_os = "linux")]
+pub use self::inotify_watcher::FileWatcher;
+
+#[cfg(not(target_os = "linux"))]
+pub use self::poll_watcher::FileWatcher;
+
+#[cfg(target_os = "linux")]
+mod inotify_watcher {
+    use std::{
+        collections::HashMap,
+        os::fd::{AsRawFd, RawFd},
+        path::PathBuf,
+        time::Duration,
+    };
+
+    use nix::sys::inotify::{AddWatchFlags, InitFlags, Inotify, WatchDescriptor};
+    use tokio::io::unix::AsyncFd;
+
+    use super::{super::errors::AutoUploadError, WatchEvent};
+
+    /// nix does not close the inotify fd.
+    struct InotifyFd(Inotify);
+
+    impl AsRawFd for InotifyFd {
+        fn as_raw_fd(&self) -> RawFd {
+            self.0.as_raw_fd()
+        }
+    }
+
+    impl Drop for InotifyFd {
+        fn drop(&mut self) {
+            let _ = nix::unistd::close(self.0.as_raw_fd());
+        }
+    }
+
+    fn watch_flags() -> AddWatchFlags {
+        AddWatchFlags::IN_CLOSE_WRITE
+            | AddWatchFlags::IN_MOVED_TO
+            | AddWatchFlags::IN_CREATE
+            | AddWatchFlags::IN_ONLYDIR
+            | AddWatchFlags::IN_DONT_FOLLOW
+    }
+
+    /// Watches the dir and all its subdirs with inotify. Subdirs created
+    /// later are watched as soon as they appear.
+    pub struct FileWatcher {
+        root: PathBuf,
+        inotify: AsyncFd<InotifyFd>,
+        dirs: HashMap<WatchDescriptor, String>,
+    }
+
+    impl FileWatcher {
+        /// Must be called from a tokio runtime.
+        pub fn new(root: PathBuf, _poll_interval: Duration) -> Result<Self, AutoUploadError> {
+            let inotify = Inotify::init(InitFlags::IN_NONBLOCK | InitFlags::IN_CLOEXEC)
+                .map_err(|err| AutoUploadError::WatchError(err.to_string()))?;
+
+            let mut watcher = Self {
+                root,
+                inotify: AsyncFd::new(InotifyFd(inotify))?,
+                dirs: HashMap::new(),
+            };
+
+            watcher.add_watches(String::new())?;
+
+            Ok(watcher)
+        }
+
+        fn dir_path(&self, relative_path: &str) -> PathBuf {
+            if relative_path.is_empty() {
+                self.root.clone()
+            } else {
+                self.root.join(relative_path)
+            }
+        }
+
+        /// Watches the dir and its subdirs.
+        fn add_watches(&mut self, relative_path: String) -> Result<(), AutoUploadError> {
+            let mut dirs = vec![relative_path];
+
+            while let Some(relative_path) = dirs.pop() {
+                let dir_path = self.dir_path(&relative_path);
+
+                let wd = self
+                    .inotify
+                    .get_ref()
+                    .0
+                    .add_watch(&dir_path, watch_flags())
+                    .map_err(|err| {
+                        AutoUploadError::WatchError(format!("{}: {}", dir_path.display(), err))
+                    })?;
+
+                self.dirs.insert(wd, relative_path.clone());
+
+                for entry in std::fs::read_dir(&dir_path)? {
+                    let entry = entry?;
+
+                    if entry.file_type()?.is_dir() {
+                        if let Ok(name) = entry.file_name().into_string() {
+                            dirs.push(join_relative_path(&relative_path, &name));
+                        }
+                    }
+                }
+            }
+
+            Ok(())
+        }
+
+        pub async fn next(&mut self) -> Result<Vec<WatchEvent>, AutoUploadError> {
+            let events = loop {
+                let mut guard = self.inotify.readable().await?;
+
+                match guard.try_io(|inotify| {
+                    inotify
+                        .get_ref()
+                        .0
+                        .read_events()
+                        .map_err(std::io::Error::from)
+                }) {
+                    Ok(res) => break res?,
+                    Err(_would_block) => continue,
+                }
+            };
+
+            let mut watch_events = Vec::new();
+
+            for event in events {
+                if event.mask.contains(AddWatchFlags::IN_Q_OVERFLOW) {
+                    watch_events.push(WatchEvent::Rescan);
+
+                    continue;
+                }
+
+                if event.mask.contains(AddWatchFlags::IN_IGNORED) {
+                    self.dirs.remove(&event.wd);
+
+                    continue;
+                }
+
+                let (dir_relative_path, name) = match (
+                    self.dirs.get(&event.wd),
+                    event.name.and_then(|name| name.into_string().ok()),
+                ) {
+                    (Some(dir_relative_path), Some(name)) => (dir_relative_path, name),
+                    _ => continue,
+                };
+
+                let relative_path = join_relative_path(dir_relative_path, &name);
+
+                if event.mask.contains(AddWatchFlags::IN_ISDIR) {
+                    // files could have been added to the new dir before it
+                    // was watched
+                    match self.add_watches(relative_path) {
+                        Ok(()) => {}
+                        Err(err) => log::warn!("Auto upload watch failed: {}", err),
+                    }
+
+                    watch_events.push(WatchEvent::Rescan);
+                } else {
+                    watch_events.push(WatchEvent::Changed(relative_path));
+                }
+            }
+
+            Ok(watch_events)
+        }
+    }
+
+    fn join_relative_path(parent: &str, name: &str) -> String {
+        if parent.is_empty() {
+            name.to_owned()
+        } else {
+            format!("{}/{}", parent, name)
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod poll_watcher {
+    use std::{path::PathBuf, time::Duration};
+
+    use super::{super::errors::AutoUploadError, WatchEvent};
+
+    /// Lists the dir periodically where inotify is not available.
+    pub struct FileWatcher {
+        poll_interval: Duration,
+    }
+
+    impl FileWatcher {
+        pub fn new(root: PathBuf, poll_interval: Duration) -> Result<Self, AutoUploadError> {
+            if !root.is_dir() {
+                return Err(AutoUploadError::WatchError(format!(
+                    "Not a dir: {}",
+                    root.display()
+                )));
+            }
+
+            Ok(Self { poll_interval })
+        }
+
+        pub async fn next(&mut self) -> Result<Vec<WatchEvent>, AutoUploadError> {
+            tokio::time::sleep(self.poll_interval).await;
+
+            Ok(vec![WatchEvent::Rescan])
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use std::{fs, path::PathBuf};
+
+use vault_core::audit_log::AuditLogDiskStorage;
+
+/// Stores the log in a single file. The log is replaced on every event so it
+/// is written to a temp file first.
+pub struct FileAuditLogDiskStorage {
+    path: PathBuf,
+}
+
+impl FileAuditLogDiskStorage {
+    pub fn new(path: PathBuf) -> Result<Self, String> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+        }
+
+        Ok(Self { path })
+    }
+}
+
+impl AuditLogDiskStorage for FileAuditLogDiskStorage {
+    fn get(&self) -> Result<Option<Vec<u8>>, String> {
+        match fs::read(&self.path) {
+            Ok(value) => Ok(Some(value)),
+            Err(err) if matches!(err.kind(), std::io::ErrorKind::NotFound) => Ok(None),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+
+    fn set(&self, value: &[u8]) -> Result<(), String> {
+        let temp_path = self
+            .path
+            .with_extension(format!("{}.tmp", uuid::Uuid::new_v4()));
+
+        let res = fs::write(&temp_path, value).and_then(|()| fs::rename(&temp_path, &self.path));
+
+        if res.is_err() {
+            let _ = fs::remove_file(&temp_path);
+        }
+
+        res.map_err(|err| err.to_string())
+    }
+}
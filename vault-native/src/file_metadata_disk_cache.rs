@@ -0,0 +1,88 @@
+use std::{fs, path::PathBuf};
+
+use vault_core::metadata_cache::MetadataDiskCache;
+
+/// Stores each entry in a separate file. Keys are hex digests so they are
+/// safe to use as file names. Entries are replaced on every load so they are
+/// written to a temp file first.
+pub struct FileMetadataDiskCache {
+    path: PathBuf,
+}
+
+impl FileMetadataDiskCache {
+    pub fn new(path: PathBuf) -> Result<Self, String> {
+        fs::create_dir_all(&path).map_err(|err| err.to_string())?;
+
+        Ok(Self { path })
+    }
+
+    fn get_key_path(&self, key: &str) -> PathBuf {
+        self.path.join(key)
+    }
+}
+
+impl MetadataDiskCache for FileMetadataDiskCache {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, String> {
+        match fs::read(self.get_key_path(key)) {
+            Ok(value) => Ok(Some(value)),
+            Err(err) if matches!(err.kind(), std::io::ErrorKind::NotFound) => Ok(None),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+
+    fn set(&self, key: &str, value: &[u8]) -> Result<(), String> {
+        let temp_path = self
+            .path
+            .join(format!("{}.{}.tmp", key, uuid::Uuid::new_v4()));
+
+        let res = fs::write(&temp_path, value)
+            .and_then(|()| fs::rename(&temp_path, self.get_key_path(key)));
+
+        if res.is_err() {
+            let _ = fs::remove_file(&temp_path);
+        }
+
+        res.map_err(|err| err.to_string())
+    }
+
+    fn remove(&self, key: &str) -> Result<(), String> {
+        match fs::remove_file(self.get_key_path(key)) {
+            Ok(()) => Ok(()),
+            Err(err) if matches!(err.kind(), std::io::ErrorKind::NotFound) => Ok(()),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+
+    fn remove_prefix(&self, prefix: &str) -> Result<(), String> {
+        let entries = match fs::read_dir(&self.path) {
+            Ok(entries) => entries,
+            Err(err) if matches!(err.kind(), std::io::ErrorKind::NotFound) => return Ok(()),
+            Err(err) => return Err(err.to_string()),
+        };
+
+        for entry in entries {
+            let entry = entry.map_err(|err| err.to_string())?;
+
+            // temp files start with the key so they are removed as well
+            if entry.file_name().to_string_lossy().starts_with(prefix) {
+                match fs::remove_file(entry.path()) {
+                    Ok(()) => {}
+                    Err(err) if matches!(err.kind(), std::io::ErrorKind::NotFound) => {}
+                    Err(err) => return Err(err.to_string()),
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn clear(&self) -> Result<(), String> {
+        match fs::remove_dir_all(&self.path) {
+            Ok(()) => {}
+            Err(err) if matches!(err.kind(), std::io::ErrorKind::NotFound) => {}
+            Err(err) => return Err(err.to_string()),
+        }
+
+        fs::create_dir_all(&self.path).map_err(|err| err.to_string())
+    }
+}
@@ -0,0 +1,109 @@
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use tokio::fs;
+use tokio_util::compat::{FuturesAsyncReadCompatExt, TokioAsyncReadCompatExt};
+
+use vault_core::{common::state::BoxAsyncRead, repo_files_offline::OfflineDiskCache};
+
+/// Not a hex digest so it cannot collide with the keys.
+const INDEX_FILE_NAME: &str = "index";
+
+/// Stores each file in a separate file. Keys are hex digests so they are safe
+/// to use as file names. Values are written to a temp file first so that a
+/// failed write does not replace the previous value. The index is stored in
+/// the same dir so clearing the dir also removes it.
+pub struct FileOfflineDiskCache {
+    path: PathBuf,
+}
+
+impl FileOfflineDiskCache {
+    pub fn new(path: PathBuf) -> Result<Self, String> {
+        std::fs::create_dir_all(&path).map_err(|err| err.to_string())?;
+
+        Ok(Self { path })
+    }
+
+    fn get_key_path(&self, key: &str) -> PathBuf {
+        self.path.join(key)
+    }
+}
+
+#[async_trait]
+impl OfflineDiskCache for FileOfflineDiskCache {
+    async fn get(&self, key: &str) -> Result<Option<BoxAsyncRead>, String> {
+        match fs::File::open(self.get_key_path(key)).await {
+            Ok(file) => Ok(Some(Box::pin(file.compat()))),
+            Err(err) if matches!(err.kind(), std::io::ErrorKind::NotFound) => Ok(None),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+
+    async fn set(&self, key: &str, reader: BoxAsyncRead) -> Result<i64, String> {
+        let temp_path = self
+            .path
+            .join(format!("{}.{}.tmp", key, uuid::Uuid::new_v4()));
+
+        let res = async {
+            let mut file = fs::File::create(&temp_path).await?;
+
+            let size = tokio::io::copy(&mut reader.compat(), &mut file).await?;
+
+            file.sync_all().await?;
+
+            fs::rename(&temp_path, self.get_key_path(key)).await?;
+
+            Ok::<_, std::io::Error>(size as i64)
+        }
+        .await;
+
+        if res.is_err() {
+            let _ = fs::remove_file(&temp_path).await;
+        }
+
+        res.map_err(|err| err.to_string())
+    }
+
+    async fn remove(&self, key: &str) -> Result<(), String> {
+        match fs::remove_file(self.get_key_path(key)).await {
+            Ok(()) => Ok(()),
+            Err(err) if matches!(err.kind(), std::io::ErrorKind::NotFound) => Ok(()),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+
+    async fn clear(&self) -> Result<(), String> {
+        match fs::remove_dir_all(&self.path).await {
+            Ok(()) => {}
+            Err(err) if matches!(err.kind(), std::io::ErrorKind::NotFound) => {}
+            Err(err) => return Err(err.to_string()),
+        }
+
+        fs::create_dir_all(&self.path)
+            .await
+            .map_err(|err| err.to_string())
+    }
+
+    fn get_index(&self) -> Result<Option<Vec<u8>>, String> {
+        match std::fs::read(self.get_key_path(INDEX_FILE_NAME)) {
+            Ok(value) => Ok(Some(value)),
+            Err(err) if matches!(err.kind(), std::io::ErrorKind::NotFound) => Ok(None),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+
+    fn set_index(&self, value: &[u8]) -> Result<(), String> {
+        let temp_path = self
+            .path
+            .join(format!("{}.{}.tmp", INDEX_FILE_NAME, uuid::Uuid::new_v4()));
+
+        let res = std::fs::write(&temp_path, value)
+            .and_then(|()| std::fs::rename(&temp_path, self.get_key_path(INDEX_FILE_NAME)));
+
+        if res.is_err() {
+            let _ = std::fs::remove_file(&temp_path);
+        }
+
+        res.map_err(|err| err.to_string())
+    }
+}
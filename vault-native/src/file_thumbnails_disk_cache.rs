@@ -0,0 +1,175 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::SystemTime,
+};
+
+use vault_core::repo_files_thumbnails::ThumbnailsDiskCache;
+
+/// Thumbnails are small so this is enough for tens of thousands of them.
+pub const DEFAULT_MAX_SIZE: u64 = 200 * 1024 * 1024;
+
+/// Stores each thumbnail in a separate file. Keys are hex digests so they are
+/// safe to use as file names. When the cache grows over `max_size` the oldest
+/// thumbnails are removed.
+pub struct FileThumbnailsDiskCache {
+    path: PathBuf,
+    max_size: u64,
+    size: Mutex<u64>,
+}
+
+impl FileThumbnailsDiskCache {
+    pub fn new(path: PathBuf, max_size: u64) -> Result<Self, String> {
+        fs::create_dir_all(&path).map_err(|err| err.to_string())?;
+
+        let size = Self::list_entries(&path)?
+            .iter()
+            .map(|(_, _, size)| size)
+            .sum();
+
+        Ok(Self {
+            path,
+            max_size,
+            size: Mutex::new(size),
+        })
+    }
+
+    fn get_key_path(&self, key: &str) -> PathBuf {
+        self.path.join(key)
+    }
+
+    fn list_entries(path: &Path) -> Result<Vec<(PathBuf, SystemTime, u64)>, String> {
+        let mut entries = Vec::new();
+
+        for entry in fs::read_dir(path).map_err(|err| err.to_string())? {
+            let entry = entry.map_err(|err| err.to_string())?;
+
+            if let Ok(metadata) = entry.metadata() {
+                if metadata.is_file() {
+                    let modified = metadata.modified().map_err(|err| err.to_string())?;
+
+                    entries.push((entry.path(), modified, metadata.len()));
+                }
+            }
+        }
+
+        Ok(entries)
+    }
+
+    fn evict(&self, size: &mut u64) -> Result<(), String> {
+        let mut entries = Self::list_entries(&self.path)?;
+
+        entries.sort_by_key(|(_, modified, _)| *modified);
+
+        for (path, _, len) in entries {
+            if *size <= self.max_size {
+                break;
+            }
+
+            match fs::remove_file(&path) {
+                Ok(()) => *size = size.saturating_sub(len),
+                Err(err) if matches!(err.kind(), std::io::ErrorKind::NotFound) => {}
+                Err(err) => return Err(err.to_string()),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl ThumbnailsDiskCache for FileThumbnailsDiskCache {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, String> {
+        match fs::read(self.get_key_path(key)) {
+            Ok(value) => Ok(Some(value)),
+            Err(err) if matches!(err.kind(), std::io::ErrorKind::NotFound) => Ok(None),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+
+    fn set(&self, key: &str, value: &[u8]) -> Result<(), String> {
+        let path = self.get_key_path(key);
+
+        let mut size = self.size.lock().unwrap();
+
+        let old_len = fs::metadata(&path).map(|metadata| metadata.len()).ok();
+
+        fs::write(&path, value).map_err(|err| err.to_string())?;
+
+        *size = size.saturating_sub(old_len.unwrap_or(0)) + value.len() as u64;
+
+        if *size > self.max_size {
+            self.evict(&mut size)?;
+        }
+
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> Result<(), String> {
+        let path = self.get_key_path(key);
+
+        let mut size = self.size.lock().unwrap();
+
+        let len = match fs::metadata(&path) {
+            Ok(metadata) => metadata.len(),
+            Err(err) if matches!(err.kind(), std::io::ErrorKind::NotFound) => return Ok(()),
+            Err(err) => return Err(err.to_string()),
+        };
+
+        match fs::remove_file(&path) {
+            Ok(()) => {
+                *size = size.saturating_sub(len);
+
+                Ok(())
+            }
+            Err(err) if matches!(err.kind(), std::io::ErrorKind::NotFound) => Ok(()),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+
+    fn clear(&self) -> Result<(), String> {
+        let mut size = self.size.lock().unwrap();
+
+        match fs::remove_dir_all(&self.path) {
+            Ok(()) => {}
+            Err(err) if matches!(err.kind(), std::io::ErrorKind::NotFound) => {}
+            Err(err) => return Err(err.to_string()),
+        }
+
+        *size = 0;
+
+        fs::create_dir_all(&self.path).map_err(|err| err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use similar_asserts::assert_eq;
+    use vault_core::repo_files_thumbnails::ThumbnailsDiskCache;
+
+    use super::FileThumbnailsDiskCache;
+
+    #[test]
+    pub fn test_max_size() {
+        let path = std::env::temp_dir().join(format!("vault-thumbnails-{}", uuid::Uuid::new_v4()));
+
+        let disk_cache = FileThumbnailsDiskCache::new(path.clone(), 10).unwrap();
+
+        disk_cache.set("a", b"aaaaaa").unwrap();
+        std::thread::sleep(Duration::from_millis(20));
+        disk_cache.set("b", b"bbbbbb").unwrap();
+
+        assert_eq!(disk_cache.get("a").unwrap(), None);
+        assert_eq!(disk_cache.get("b").unwrap(), Some(b"bbbbbb".to_vec()));
+
+        let disk_cache = FileThumbnailsDiskCache::new(path.clone(), 10).unwrap();
+        assert_eq!(*disk_cache.size.lock().unwrap(), 6);
+
+        disk_cache.clear().unwrap();
+        assert_eq!(disk_cache.get("b").unwrap(), None);
+
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+}
@@ -1,4 +1,7 @@
-use std::path::PathBuf;
+use std::{
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
 
 use tokio::fs;
 
@@ -52,11 +55,64 @@ pub fn cleanup_name(name: &str) -> String {
     }
 }
 
+/// Milliseconds since the Unix epoch, 0 if not available.
+pub fn modified_millis(metadata: &std::fs::Metadata) -> i64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+/// Lists all files under `root` with paths relative to `root` (e.g.
+/// "dir/file.txt"). Symlinks and names that are not valid UTF-8 are skipped.
+/// Any IO error fails the whole listing.
+pub async fn list_files_recursive(
+    root: &Path,
+) -> std::io::Result<Vec<(String, std::fs::Metadata)>> {
+    let mut files = Vec::new();
+    let mut dirs = vec![(root.to_path_buf(), String::new())];
+
+    while let Some((dir_path, dir_relative_path)) = dirs.pop() {
+        let mut entries = fs::read_dir(&dir_path).await?;
+
+        while let Some(entry) = entries.next_entry().await? {
+            let name = match entry.file_name().into_string() {
+                Ok(name) => name,
+                Err(name) => {
+                    log::warn!("Skipping invalid file name: {:?}", name);
+
+                    continue;
+                }
+            };
+
+            let relative_path = if dir_relative_path.is_empty() {
+                name
+            } else {
+                format!("{}/{}", dir_relative_path, name)
+            };
+
+            let metadata = entry.metadata().await?;
+
+            if metadata.is_dir() {
+                dirs.push((entry.path(), relative_path));
+            } else if metadata.is_file() {
+                files.push((relative_path, metadata));
+            }
+        }
+    }
+
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+
+    Ok(files)
+}
+
 #[cfg(test)]
 mod tests {
     use similar_asserts::assert_eq;
 
-    use super::cleanup_name;
+    use super::{cleanup_name, list_files_recursive};
 
     #[test]
     pub fn test_cleanup_name() {
@@ -65,4 +121,27 @@ mod tests {
         assert_eq!(cleanup_name("foo:bar.txt"), "foobar.txt");
         assert_eq!(cleanup_name("\"/\\|?*"), "invalid name");
     }
+
+    #[tokio::test]
+    pub async fn test_list_files_recursive() {
+        let root = std::env::temp_dir().join(format!("vault-list-{}", uuid::Uuid::new_v4()));
+
+        std::fs::create_dir_all(root.join("dir/empty")).unwrap();
+        std::fs::write(root.join("a.txt"), "a").unwrap();
+        std::fs::write(root.join("dir/b.txt"), "bb").unwrap();
+
+        let files = list_files_recursive(&root)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(path, metadata)| (path, metadata.len()))
+            .collect::<Vec<_>>();
+
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(
+            files,
+            vec![("a.txt".to_string(), 1), ("dir/b.txt".to_string(), 2)]
+        );
+    }
 }
@@ -1,6 +1,14 @@
+pub mod auto_upload;
+pub mod data_path;
+pub mod file_audit_log_disk_storage;
+pub mod file_metadata_disk_cache;
+pub mod file_offline_disk_cache;
+pub mod file_secure_storage;
+pub mod file_thumbnails_disk_cache;
 pub mod file_utils;
 pub mod native_eventstream_websocket_client;
 pub mod native_http_client;
 pub mod native_runtime;
-pub mod vault;
+pub mod sync_local;
 pub mod transfers;
+pub mod vault;
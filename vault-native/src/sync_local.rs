@@ -0,0 +1,132 @@
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use tokio::fs;
+
+use vault_core::{
+    sync::{errors::SyncLocalError, local::SyncLocal, state::SyncLocalFile},
+    transfers::{downloadable::BoxDownloadable, uploadable::BoxUploadable},
+    types::DecryptedPath,
+};
+
+use crate::{
+    file_utils,
+    transfers::{file_downloadable::FileDownloadable, file_uploadable::FileUploadable},
+};
+
+const TEMP_SUFFIX: &str = ".vault-sync-tmp";
+const STATE_DIR_NAME: &str = ".vault-sync";
+
+/// Syncs a local dir. Downloads are written to a temp file next to the target
+/// which replaces the target once the download is done. The persisted state
+/// is stored in a hidden dir in the root.
+pub struct FileSyncLocal {
+    pub root: PathBuf,
+}
+
+impl FileSyncLocal {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    fn local_path(&self, path: &DecryptedPath) -> PathBuf {
+        self.root.join(path.0.trim_start_matches('/'))
+    }
+
+    fn state_path(&self, key: &str) -> PathBuf {
+        self.root.join(STATE_DIR_NAME).join(format!("{}.json", key))
+    }
+}
+
+#[async_trait]
+impl SyncLocal for FileSyncLocal {
+    fn id(&self) -> String {
+        self.root.to_string_lossy().to_string()
+    }
+
+    async fn list(&self) -> Result<Vec<SyncLocalFile>, SyncLocalError> {
+        Ok(file_utils::list_files_recursive(&self.root)
+            .await?
+            .into_iter()
+            .filter(|(path, _)| {
+                !path.ends_with(TEMP_SUFFIX) && !path.starts_with(&format!("{}/", STATE_DIR_NAME))
+            })
+            .map(|(path, metadata)| SyncLocalFile {
+                path: DecryptedPath(format!("/{}", path)),
+                size: metadata.len() as i64,
+                modified: file_utils::modified_millis(&metadata),
+            })
+            .collect())
+    }
+
+    fn uploadable(&self, path: &DecryptedPath) -> BoxUploadable {
+        Box::new(FileUploadable {
+            path: self.local_path(path),
+            cleanup: None,
+        })
+    }
+
+    async fn downloadable(&self, path: &DecryptedPath) -> Result<BoxDownloadable, SyncLocalError> {
+        let target_path = self.local_path(path);
+
+        if let Some(parent_path) = target_path.parent() {
+            fs::create_dir_all(parent_path).await?;
+        }
+
+        let mut temp_path = target_path.clone().into_os_string();
+        temp_path.push(TEMP_SUFFIX);
+
+        Ok(Box::new(FileDownloadable {
+            original_path: temp_path.into(),
+            append_name: false,
+            autorename: false,
+            on_open: None,
+            on_done: Box::new(move |temp_path, _| {
+                std::fs::rename(temp_path, &target_path)?;
+
+                Ok(())
+            }),
+
+            path: None,
+            content_type: None,
+        }))
+    }
+
+    async fn delete(&self, path: &DecryptedPath) -> Result<(), SyncLocalError> {
+        match fs::remove_file(self.local_path(path)).await {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    async fn get_state(&self, key: &str) -> Result<Option<Vec<u8>>, SyncLocalError> {
+        match fs::read(self.state_path(key)).await {
+            Ok(value) => Ok(Some(value)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    async fn set_state(&self, key: &str, value: &[u8]) -> Result<(), SyncLocalError> {
+        let state_path = self.state_path(key);
+
+        fs::create_dir_all(self.root.join(STATE_DIR_NAME)).await?;
+
+        let mut temp_path = state_path.clone().into_os_string();
+        temp_path.push(TEMP_SUFFIX);
+
+        fs::write(&temp_path, value).await?;
+        fs::rename(&temp_path, &state_path).await?;
+
+        Ok(())
+    }
+
+    async fn remove_state(&self, key: &str) -> Result<(), SyncLocalError> {
+        match fs::remove_file(self.state_path(key)).await {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
@@ -26,6 +26,10 @@ impl Uploadable for FileUploadable {
         Ok(true)
     }
 
+    async fn is_persistent(&self) -> Result<bool, UploadableError> {
+        Ok(false)
+    }
+
     async fn reader(&self) -> Result<(BoxAsyncRead, SizeInfo), UploadableError> {
         let file = File::open(&self.path).await?;
         let size = SizeInfo::Exact(file_size(&file.metadata().await?));
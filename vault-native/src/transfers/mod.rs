@@ -2,3 +2,4 @@ pub mod file_downloadable;
 pub mod file_uploadable;
 pub mod pick_file_downloadable;
 pub mod temp_file_downloadable;
+pub mod upload_helper;
@@ -12,7 +12,7 @@ use web_sys::AbortSignal;
 
 use vault_core::{
     common::state::{BoxAsyncRead, SizeInfo},
-    user_error::UserError,
+    user_error::{ErrorCode, UserError},
     utils::on_end_reader::OnEndReader,
     Vault,
 };
@@ -72,6 +72,9 @@ impl UserError for ReaderToBlobError {
     fn user_error(&self) -> String {
         self.to_string()
     }
+    fn error_code(&self) -> ErrorCode {
+        ErrorCode::FileIO
+    }
 }
 
 pub async fn reader_to_blob(
@@ -129,6 +132,9 @@ impl UserError for ReaderToFileStreamError {
     fn user_error(&self) -> String {
         self.to_string()
     }
+    fn error_code(&self) -> ErrorCode {
+        ErrorCode::FileIO
+    }
 }
 
 pub async fn reader_to_file_stream(
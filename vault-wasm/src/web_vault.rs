@@ -53,9 +53,24 @@ extern "C" {
     #[wasm_bindgen(typescript_type = "Uint8Array | undefined")]
     pub type Bytes;
 
+    #[wasm_bindgen(typescript_type = "string[]")]
+    pub type StringVec;
+
+    #[wasm_bindgen(typescript_type = "string[] | undefined")]
+    pub type StringVecOption;
+
+    #[wasm_bindgen(typescript_type = "DeviceAuthorization")]
+    pub type DeviceAuthorization;
+
+    #[wasm_bindgen(typescript_type = "DeviceAuthorization | undefined")]
+    pub type DeviceAuthorizationOption;
+
     #[wasm_bindgen(typescript_type = "User | undefined")]
     pub type UserOption;
 
+    #[wasm_bindgen(typescript_type = "Account[] | undefined")]
+    pub type AccountVecOption;
+
     #[wasm_bindgen(typescript_type = "FileIconProps")]
     pub type FileIconProps;
 
@@ -68,6 +83,15 @@ extern "C" {
     #[wasm_bindgen(typescript_type = "RepoAutoLock")]
     pub type RepoAutoLock;
 
+    #[wasm_bindgen(typescript_type = "RepoUnlockLockout | undefined")]
+    pub type RepoUnlockLockoutOption;
+
+    #[wasm_bindgen(typescript_type = "AuditLogFilter")]
+    pub type AuditLogFilter;
+
+    #[wasm_bindgen(typescript_type = "AuditLogEntry[]")]
+    pub type AuditLogEntryVec;
+
     #[wasm_bindgen(typescript_type = "RepoCreateInfo | undefined")]
     pub type RepoCreateInfoOption;
 
@@ -86,6 +110,9 @@ extern "C" {
     #[wasm_bindgen(typescript_type = "RepoSpaceUsageInfo | undefined")]
     pub type RepoSpaceUsageInfoOption;
 
+    #[wasm_bindgen(typescript_type = "RepoDuplicatesInfo | undefined")]
+    pub type RepoDuplicatesInfoOption;
+
     #[wasm_bindgen(typescript_type = "RepoFile | undefined")]
     pub type RepoFileOption;
 
@@ -107,12 +134,24 @@ extern "C" {
     #[wasm_bindgen(typescript_type = "RepoFilesBrowserItem[]")]
     pub type RepoFilesBrowserItemVec;
 
+    #[wasm_bindgen(typescript_type = "RepoFilesRenamePattern")]
+    pub type RepoFilesRenamePattern;
+
+    #[wasm_bindgen(typescript_type = "RepoFilesRenamePreview")]
+    pub type RepoFilesRenamePreview;
+
+    #[wasm_bindgen(typescript_type = "RepoFilesRenameResult[]")]
+    pub type RepoFilesRenameResultVec;
+
     #[wasm_bindgen(typescript_type = "RepoFilesDetailsOptions")]
     pub type RepoFilesDetailsOptions;
 
     #[wasm_bindgen(typescript_type = "RepoFilesDetailsInfo | undefined")]
     pub type RepoFilesDetailsInfoOption;
 
+    #[wasm_bindgen(typescript_type = "RepoFilesDetailsTextMerge | undefined")]
+    pub type RepoFilesDetailsTextMergeOption;
+
     #[wasm_bindgen(typescript_type = "RepoFilesMoveMode")]
     pub type RepoFilesMoveMode;
 
@@ -131,6 +170,18 @@ extern "C" {
     #[wasm_bindgen(typescript_type = "DirPickerItem[] | undefined")]
     pub type DirPickerItemVecOption;
 
+    #[wasm_bindgen(typescript_type = "RepoFilesVersionsSettings")]
+    pub type RepoFilesVersionsSettings;
+
+    #[wasm_bindgen(typescript_type = "RepoFilesVersionsSettings | undefined")]
+    pub type RepoFilesVersionsSettingsOption;
+
+    #[wasm_bindgen(typescript_type = "RepoFileVersion[] | undefined")]
+    pub type RepoFileVersionVecOption;
+
+    #[wasm_bindgen(typescript_type = "RepoFilesOfflineInfo")]
+    pub type RepoFilesOfflineInfo;
+
     #[wasm_bindgen(typescript_type = "SpaceUsage | undefined")]
     pub type SpaceUsageOption;
 }
@@ -183,7 +234,8 @@ impl WebVault {
             base_url: base_url.clone(),
             auth_base_url: oauth2_auth_base_url.clone(),
             client_id: oauth2_client_id,
-            client_secret: oauth2_client_secret,
+            // an empty secret is used by public clients that only use PKCE
+            client_secret: Some(oauth2_client_secret).filter(|secret| !secret.is_empty()),
             redirect_uri: oauth2_redirect_uri,
         };
 
@@ -245,6 +297,13 @@ impl WebVault {
         self.base.app_hidden();
     }
 
+    // panic_lock
+
+    #[wasm_bindgen(js_name = panicLock)]
+    pub fn panic_lock(&self, wipe_secure_storage: bool) {
+        self.base.panic_lock(wipe_secure_storage);
+    }
+
     // relative_time
 
     #[wasm_bindgen(js_name = relativeTime)]
@@ -252,6 +311,39 @@ impl WebVault {
         to_js(&self.base.relative_time(value, with_modifier))
     }
 
+    // locale
+
+    #[wasm_bindgen(js_name = localeGet)]
+    pub fn locale_get(&self) -> String {
+        self.base.locale_get()
+    }
+
+    #[wasm_bindgen(js_name = localeSet)]
+    pub fn locale_set(&self, name: String) {
+        self.base.locale_set(&name);
+    }
+
+    // audit_log
+
+    #[wasm_bindgen(js_name = auditLogEntries)]
+    pub fn audit_log_entries(&self, filter: AuditLogFilter) -> AuditLogEntryVec {
+        to_js(
+            &self
+                .base
+                .audit_log_entries(serde_wasm_bindgen::from_value(filter.into()).unwrap()),
+        )
+    }
+
+    #[wasm_bindgen(js_name = auditLogExport)]
+    pub fn audit_log_export(&self) -> String {
+        self.base.audit_log_export()
+    }
+
+    #[wasm_bindgen(js_name = auditLogVerify)]
+    pub fn audit_log_verify(&self) -> bool {
+        self.base.audit_log_verify()
+    }
+
     // notifications
 
     #[wasm_bindgen(js_name = notificationsSubscribe)]
@@ -344,6 +436,28 @@ impl WebVault {
         self.base.oauth2_finish_flow_url(url).await
     }
 
+    #[wasm_bindgen(js_name = oauth2StartDeviceFlow)]
+    pub async fn oauth2_start_device_flow(&self) -> DeviceAuthorizationOption {
+        to_js(&self.base.oauth2_start_device_flow().await)
+    }
+
+    #[wasm_bindgen(js_name = oauth2FinishDeviceFlow)]
+    pub async fn oauth2_finish_device_flow(
+        &self,
+        device_authorization: DeviceAuthorization,
+    ) -> bool {
+        self.base
+            .oauth2_finish_device_flow(
+                serde_wasm_bindgen::from_value(device_authorization.into()).unwrap(),
+            )
+            .await
+    }
+
+    #[wasm_bindgen(js_name = oauth2CancelDeviceFlow)]
+    pub fn oauth2_cancel_device_flow(&self) {
+        self.base.oauth2_cancel_device_flow();
+    }
+
     // config
 
     #[wasm_bindgen(js_name = configGetBaseUrl)]
@@ -386,6 +500,33 @@ impl WebVault {
         self.base.user_ensure_profile_picture();
     }
 
+    // accounts
+
+    #[wasm_bindgen(js_name = accountsSubscribe)]
+    pub fn accounts_subscribe(&self, cb: js_sys::Function) -> u32 {
+        self.base.accounts_subscribe(to_cb(cb))
+    }
+
+    #[wasm_bindgen(js_name = accountsData)]
+    pub fn accounts_data(&self, id: u32) -> AccountVecOption {
+        to_js(&self.base.accounts_data(id))
+    }
+
+    #[wasm_bindgen(js_name = accountsSwitch)]
+    pub async fn accounts_switch(&self, user_id: String) -> bool {
+        self.base.accounts_switch(user_id).await
+    }
+
+    #[wasm_bindgen(js_name = accountsAdd)]
+    pub fn accounts_add(&self) -> Option<String> {
+        self.base.accounts_add()
+    }
+
+    #[wasm_bindgen(js_name = accountsRemove)]
+    pub fn accounts_remove(&self, user_id: String) {
+        self.base.accounts_remove(user_id);
+    }
+
     // file_icon
 
     #[wasm_bindgen(js_name = fileIconSvg)]
@@ -440,6 +581,13 @@ impl WebVault {
             .repos_set_default_auto_lock(serde_wasm_bindgen::from_value(auto_lock.into()).unwrap());
     }
 
+    #[wasm_bindgen(js_name = reposSetUnlockLockout)]
+    pub fn repos_set_unlock_lockout(&self, unlock_lockout: RepoUnlockLockoutOption) {
+        self.base.repos_set_unlock_lockout(
+            serde_wasm_bindgen::from_value(unlock_lockout.into()).unwrap(),
+        );
+    }
+
     // repo_create
 
     #[wasm_bindgen(js_name = repoCreateCreate)]
@@ -628,6 +776,46 @@ impl WebVault {
         self.base.repo_space_usage_destroy(usage_id);
     }
 
+    // repo_duplicates
+
+    #[wasm_bindgen(js_name = repoDuplicatesCreate)]
+    pub fn repo_duplicates_create(&self, repo_id: String) -> u32 {
+        self.base.repo_duplicates_create(repo_id)
+    }
+
+    #[wasm_bindgen(js_name = repoDuplicatesInfoSubscribe)]
+    pub fn repo_duplicates_info_subscribe(&self, duplicates_id: u32, cb: js_sys::Function) -> u32 {
+        self.base
+            .repo_duplicates_info_subscribe(duplicates_id, to_cb(cb))
+    }
+
+    #[wasm_bindgen(js_name = repoDuplicatesInfoData)]
+    pub fn repo_duplicates_info_data(&self, id: u32) -> RepoDuplicatesInfoOption {
+        to_js(&self.base.repo_duplicates_info_data(id))
+    }
+
+    #[wasm_bindgen(js_name = repoDuplicatesScan)]
+    pub fn repo_duplicates_scan(&self, duplicates_id: u32) {
+        self.base.repo_duplicates_scan(duplicates_id);
+    }
+
+    #[wasm_bindgen(js_name = repoDuplicatesDeleteAllButNewest)]
+    pub fn repo_duplicates_delete_all_but_newest(
+        &self,
+        duplicates_id: u32,
+        hashes: StringVecOption,
+    ) {
+        self.base.repo_duplicates_delete_all_but_newest(
+            duplicates_id,
+            serde_wasm_bindgen::from_value(hashes.into()).unwrap(),
+        );
+    }
+
+    #[wasm_bindgen(js_name = repoDuplicatesDestroy)]
+    pub fn repo_duplicates_destroy(&self, duplicates_id: u32) {
+        self.base.repo_duplicates_destroy(duplicates_id);
+    }
+
     // repo_files
 
     #[wasm_bindgen(js_name = repoFilesFileSubscribe)]
@@ -826,6 +1014,51 @@ impl WebVault {
         );
     }
 
+    #[wasm_bindgen(js_name = repoFilesBrowsersRenameSelectedPreview)]
+    pub fn repo_files_browsers_rename_selected_preview(
+        &self,
+        browser_id: u32,
+        pattern: RepoFilesRenamePattern,
+    ) -> RepoFilesRenamePreview {
+        to_js(&self.base.repo_files_browsers_rename_selected_preview(
+            browser_id,
+            serde_wasm_bindgen::from_value(pattern.into()).unwrap(),
+        ))
+    }
+
+    #[wasm_bindgen(js_name = repoFilesBrowsersRenameSelected)]
+    pub async fn repo_files_browsers_rename_selected(
+        &self,
+        browser_id: u32,
+        pattern: RepoFilesRenamePattern,
+    ) -> RepoFilesRenameResultVec {
+        to_js(
+            &self
+                .base
+                .repo_files_browsers_rename_selected(
+                    browser_id,
+                    serde_wasm_bindgen::from_value(pattern.into()).unwrap(),
+                )
+                .await,
+        )
+    }
+
+    // repo_files_thumbnails
+
+    #[wasm_bindgen(js_name = repoFilesThumbnailsEnsure)]
+    pub fn repo_files_thumbnails_ensure(&self, file_ids: StringVec) {
+        self.base
+            .repo_files_thumbnails_ensure(serde_wasm_bindgen::from_value(file_ids.into()).unwrap());
+    }
+
+    #[wasm_bindgen(js_name = repoFilesThumbnailsGet)]
+    pub async fn repo_files_thumbnails_get(&self, file_id: String) -> Bytes {
+        match self.base.repo_files_thumbnails_get(file_id).await {
+            Some(bytes) => helpers::bytes_to_array(&bytes).into(),
+            None => JsValue::UNDEFINED.into(),
+        }
+    }
+
     // repo_files_details
 
     #[wasm_bindgen(js_name = repoFilesDetailsCreate)]
@@ -925,6 +1158,47 @@ impl WebVault {
         self.base.repo_files_details_delete(details_id);
     }
 
+    #[wasm_bindgen(js_name = repoFilesDetailsConflictTextMergeSubscribe)]
+    pub fn repo_files_details_conflict_text_merge_subscribe(
+        &self,
+        details_id: u32,
+        cb: js_sys::Function,
+    ) -> u32 {
+        self.base
+            .repo_files_details_conflict_text_merge_subscribe(details_id, to_cb(cb))
+    }
+
+    #[wasm_bindgen(js_name = repoFilesDetailsConflictTextMergeData)]
+    pub fn repo_files_details_conflict_text_merge_data(
+        &self,
+        id: u32,
+    ) -> RepoFilesDetailsTextMergeOption {
+        to_js(&self.base.repo_files_details_conflict_text_merge_data(id))
+    }
+
+    #[wasm_bindgen(js_name = repoFilesDetailsConflictKeepMine)]
+    pub fn repo_files_details_conflict_keep_mine(&self, details_id: u32) {
+        self.base.repo_files_details_conflict_keep_mine(details_id);
+    }
+
+    #[wasm_bindgen(js_name = repoFilesDetailsConflictMerge)]
+    pub fn repo_files_details_conflict_merge(&self, details_id: u32, content: Vec<u8>) {
+        self.base
+            .repo_files_details_conflict_merge(details_id, content);
+    }
+
+    #[wasm_bindgen(js_name = repoFilesDetailsConflictKeepTheirs)]
+    pub fn repo_files_details_conflict_keep_theirs(&self, details_id: u32) {
+        self.base
+            .repo_files_details_conflict_keep_theirs(details_id);
+    }
+
+    #[wasm_bindgen(js_name = repoFilesDetailsConflictSaveAsCopy)]
+    pub fn repo_files_details_conflict_save_as_copy(&self, details_id: u32) {
+        self.base
+            .repo_files_details_conflict_save_as_copy(details_id);
+    }
+
     // repo_files_move
 
     #[wasm_bindgen(js_name = repoFilesMoveInfoSubscribe)]
@@ -958,6 +1232,103 @@ impl WebVault {
         self.base.repo_files_move_create_dir();
     }
 
+    // repo_files_versions
+
+    #[wasm_bindgen(js_name = repoFilesVersionsGetSettings)]
+    pub fn repo_files_versions_get_settings(
+        &self,
+        repo_id: String,
+    ) -> RepoFilesVersionsSettingsOption {
+        to_js(&self.base.repo_files_versions_get_settings(repo_id))
+    }
+
+    #[wasm_bindgen(js_name = repoFilesVersionsSetSettings)]
+    pub fn repo_files_versions_set_settings(
+        &self,
+        repo_id: String,
+        settings: RepoFilesVersionsSettings,
+    ) -> bool {
+        self.base.repo_files_versions_set_settings(
+            repo_id,
+            serde_wasm_bindgen::from_value(settings.into()).unwrap(),
+        )
+    }
+
+    #[wasm_bindgen(js_name = repoFilesVersionsList)]
+    pub async fn repo_files_versions_list(
+        &self,
+        repo_id: String,
+        encrypted_path: String,
+    ) -> RepoFileVersionVecOption {
+        to_js(
+            &self
+                .base
+                .repo_files_versions_list(repo_id, encrypted_path)
+                .await,
+        )
+    }
+
+    #[wasm_bindgen(js_name = repoFilesVersionsRestore)]
+    pub async fn repo_files_versions_restore(
+        &self,
+        repo_id: String,
+        encrypted_path: String,
+        version_encrypted_path: String,
+    ) -> bool {
+        self.base
+            .repo_files_versions_restore(repo_id, encrypted_path, version_encrypted_path)
+            .await
+    }
+
+    #[wasm_bindgen(js_name = repoFilesVersionsPrune)]
+    pub async fn repo_files_versions_prune(&self, repo_id: String, encrypted_path: String) -> bool {
+        self.base
+            .repo_files_versions_prune(repo_id, encrypted_path)
+            .await
+    }
+
+    // repo_files_offline
+
+    #[wasm_bindgen(js_name = repoFilesOfflinePin)]
+    pub async fn repo_files_offline_pin(&self, repo_id: String, encrypted_path: String) -> bool {
+        self.base
+            .repo_files_offline_pin(repo_id, encrypted_path)
+            .await
+    }
+
+    #[wasm_bindgen(js_name = repoFilesOfflineUnpin)]
+    pub async fn repo_files_offline_unpin(&self, repo_id: String, encrypted_path: String) -> bool {
+        self.base
+            .repo_files_offline_unpin(repo_id, encrypted_path)
+            .await
+    }
+
+    #[wasm_bindgen(js_name = repoFilesOfflineIsPinned)]
+    pub fn repo_files_offline_is_pinned(&self, repo_id: String, encrypted_path: String) -> bool {
+        self.base
+            .repo_files_offline_is_pinned(repo_id, encrypted_path)
+    }
+
+    #[wasm_bindgen(js_name = repoFilesOfflineRefresh)]
+    pub async fn repo_files_offline_refresh(&self) -> bool {
+        self.base.repo_files_offline_refresh().await
+    }
+
+    #[wasm_bindgen(js_name = repoFilesOfflineSetQuota)]
+    pub async fn repo_files_offline_set_quota(&self, quota: f64) -> bool {
+        self.base.repo_files_offline_set_quota(quota as i64).await
+    }
+
+    #[wasm_bindgen(js_name = repoFilesOfflineInfo)]
+    pub fn repo_files_offline_info(&self) -> RepoFilesOfflineInfo {
+        to_js(&self.base.repo_files_offline_info())
+    }
+
+    #[wasm_bindgen(js_name = repoFilesOfflineClear)]
+    pub async fn repo_files_offline_clear(&self) -> bool {
+        self.base.repo_files_offline_clear().await
+    }
+
     // space_usage
 
     #[wasm_bindgen(js_name = spaceUsageSubscribe)]
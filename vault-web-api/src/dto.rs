@@ -1,9 +1,11 @@
-use std::time::Duration;
+use std::{collections::HashMap, time::Duration};
 
 use serde::{Deserialize, Serialize};
 use tsify::Tsify;
 
 use vault_core::{
+    accounts::state as accounts_state,
+    audit_log::state as audit_log_state,
     common::state as common_state,
     dialogs::state as dialogs_state,
     dir_pickers::state as dir_pickers_state,
@@ -13,14 +15,19 @@ use vault_core::{
         files_filter,
     },
     notifications::state as notifications_state,
+    oauth2::state as oauth2_state,
     relative_time,
     remote_files::state as remote_files_state,
     repo_config_backup::state as repo_config_backup_state,
+    repo_duplicates::state as repo_duplicates_state,
     repo_files::state as repo_files_state,
     repo_files_browsers::state as repo_files_browsers_state,
     repo_files_details::state as repo_files_details_state,
     repo_files_move::state as repo_files_move_state,
+    repo_files_offline::state as repo_files_offline_state,
+    repo_files_rename::state as repo_files_rename_state,
     repo_files_tags,
+    repo_files_versions::state as repo_files_versions_state,
     repo_remove::state as repo_remove_state,
     repo_space_usage::state as repo_space_usage_state,
     repo_unlock::state as repo_unlock_state,
@@ -30,18 +37,57 @@ use vault_core::{
     space_usage::state as space_usage_state,
     store,
     transfers::{selectors as transfers_selectors, state as transfers_state},
-    types::{DecryptedName, TimeMillis},
+    types::{DecryptedName, RepoId, TimeMillis},
     user::state as user_state,
-    user_error::UserError,
+    user_error::{self, UserError},
 };
 
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Tsify)]
+pub struct ErrorCode {
+    pub code: String,
+    pub details: HashMap<String, String>,
+}
+
+impl From<&user_error::ErrorCode> for ErrorCode {
+    fn from(error_code: &user_error::ErrorCode) -> Self {
+        Self {
+            code: error_code.as_str().to_owned(),
+            details: error_code.details().into_iter().collect(),
+        }
+    }
+}
+
+/// Body of HTTP error responses.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Tsify)]
+pub struct ErrorResponse {
+    pub error: String,
+    #[serde(rename = "errorCode")]
+    pub error_code: ErrorCode,
+}
+
+impl<E: UserError> From<&E> for ErrorResponse {
+    fn from(error: &E) -> Self {
+        Self {
+            error: error.user_error(),
+            error_code: (&error.error_code()).into(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Tsify)]
 #[serde(tag = "type")]
 pub enum Status {
     Initial,
-    Loading { loaded: bool },
+    Loading {
+        loaded: bool,
+    },
     Loaded,
-    Error { error: String, loaded: bool },
+    Error {
+        error: String,
+        #[serde(rename = "errorCode")]
+        error_code: ErrorCode,
+        loaded: bool,
+    },
 }
 
 impl<E: std::error::Error + Clone + PartialEq + UserError> From<&common_state::Status<E>>
@@ -54,12 +100,55 @@ impl<E: std::error::Error + Clone + PartialEq + UserError> From<&common_state::S
             common_state::Status::Loaded => Self::Loaded,
             common_state::Status::Error { error, loaded } => Self::Error {
                 error: error.user_error(),
+                error_code: (&error.error_code()).into(),
                 loaded: *loaded,
             },
         }
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Tsify)]
+pub struct DeviceAuthorization {
+    #[serde(rename = "deviceCode")]
+    pub device_code: String,
+    #[serde(rename = "userCode")]
+    pub user_code: String,
+    #[serde(rename = "verificationUri")]
+    pub verification_uri: String,
+    #[serde(rename = "verificationUriComplete")]
+    pub verification_uri_complete: Option<String>,
+    #[serde(rename = "expiresAt")]
+    pub expires_at: f64,
+    #[serde(rename = "intervalMs")]
+    pub interval_ms: f64,
+}
+
+impl From<&oauth2_state::DeviceAuthorization> for DeviceAuthorization {
+    fn from(device_authorization: &oauth2_state::DeviceAuthorization) -> Self {
+        Self {
+            device_code: device_authorization.device_code.clone(),
+            user_code: device_authorization.user_code.clone(),
+            verification_uri: device_authorization.verification_uri.clone(),
+            verification_uri_complete: device_authorization.verification_uri_complete.clone(),
+            expires_at: device_authorization.expires_at.0 as f64,
+            interval_ms: device_authorization.interval.as_millis() as f64,
+        }
+    }
+}
+
+impl Into<oauth2_state::DeviceAuthorization> for DeviceAuthorization {
+    fn into(self) -> oauth2_state::DeviceAuthorization {
+        oauth2_state::DeviceAuthorization {
+            device_code: self.device_code,
+            user_code: self.user_code,
+            verification_uri: self.verification_uri,
+            verification_uri_complete: self.verification_uri_complete,
+            expires_at: TimeMillis(self.expires_at as i64),
+            interval: Duration::from_millis(self.interval_ms as u64),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Tsify)]
 #[serde(tag = "type")]
 pub enum SizeInfo {
@@ -319,6 +408,8 @@ impl Into<vault_file_icon::FileIconProps> for FileIconProps {
 pub struct Notification {
     pub id: u32,
     pub message: String,
+    #[serde(rename = "errorCode")]
+    pub error_code: Option<ErrorCode>,
 }
 
 impl From<&notifications_state::Notification> for Notification {
@@ -326,6 +417,7 @@ impl From<&notifications_state::Notification> for Notification {
         Self {
             id: notification.id,
             message: notification.message.clone(),
+            error_code: notification.error_code.as_ref().map(Into::into),
         }
     }
 }
@@ -429,6 +521,28 @@ impl From<&user_state::User> for User {
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Tsify)]
+pub struct Account {
+    #[serde(rename = "userId")]
+    pub user_id: String,
+    pub email: String,
+    #[serde(rename = "fullName")]
+    pub full_name: String,
+    #[serde(rename = "isActive")]
+    pub is_active: bool,
+}
+
+impl<'a> From<&accounts_state::AccountInfo<'a>> for Account {
+    fn from(info: &accounts_state::AccountInfo<'a>) -> Self {
+        Self {
+            user_id: info.account.user_id.clone(),
+            email: info.account.email.clone(),
+            full_name: info.account.full_name.clone(),
+            is_active: info.is_active,
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Tsify)]
 pub enum RepoState {
     Locked,
@@ -469,6 +583,23 @@ impl Into<repos_state::RepoAutoLock> for RepoAutoLock {
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Tsify)]
+pub struct RepoUnlockLockout {
+    #[serde(rename = "maxFailedAttempts")]
+    pub max_failed_attempts: u32,
+    #[serde(rename = "durationSeconds")]
+    pub duration_seconds: f64,
+}
+
+impl Into<repos_state::RepoUnlockLockout> for RepoUnlockLockout {
+    fn into(self) -> repos_state::RepoUnlockLockout {
+        repos_state::RepoUnlockLockout {
+            max_failed_attempts: self.max_failed_attempts,
+            duration: Duration::from_secs_f64(self.duration_seconds),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Tsify)]
 #[serde(tag = "type")]
 pub enum RepoAutoLockAfter {
@@ -536,6 +667,8 @@ pub struct Repo {
     pub web_url: String,
     #[serde(rename = "autoLock")]
     pub auto_lock: RepoAutoLock,
+    #[serde(rename = "readOnly")]
+    pub read_only: bool,
 }
 
 impl From<(&repos_state::Repo, &repos_state::RepoAutoLock)> for Repo {
@@ -549,6 +682,7 @@ impl From<(&repos_state::Repo, &repos_state::RepoAutoLock)> for Repo {
             added: repo.added as f64,
             web_url: repo.web_url.clone(),
             auto_lock: repo.auto_lock.as_ref().unwrap_or(default_auto_lock).into(),
+            read_only: repo.is_read_only(),
         }
     }
 }
@@ -619,9 +753,9 @@ impl From<&repos_state::RepoConfig> for RepoConfig {
         Self {
             name: config.name.0.clone(),
             location: (&config.location).into(),
-            password: config.password.clone(),
+            password: config.password.to_string(),
             salt: config.salt.clone(),
-            rclone_config: config.rclone_config.clone(),
+            rclone_config: config.rclone_config.to_string(),
         }
     }
 }
@@ -697,6 +831,7 @@ pub enum RepoCreateInfo {
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Tsify)]
 pub enum RepoUnlockMode {
     Unlock,
+    UnlockReadOnly,
     Verify,
 }
 
@@ -704,6 +839,7 @@ impl Into<repos_state::RepoUnlockMode> for RepoUnlockMode {
     fn into(self) -> repos_state::RepoUnlockMode {
         match self {
             Self::Unlock => repos_state::RepoUnlockMode::Unlock,
+            Self::UnlockReadOnly => repos_state::RepoUnlockMode::UnlockReadOnly,
             Self::Verify => repos_state::RepoUnlockMode::Verify,
         }
     }
@@ -727,6 +863,10 @@ pub struct RepoUnlockInfo {
     pub status: Status,
     #[serde(rename = "repoName")]
     pub repo_name: Option<String>,
+    #[serde(rename = "failedAttempts")]
+    pub failed_attempts: u32,
+    #[serde(rename = "retryAt")]
+    pub retry_at: Option<f64>,
 }
 
 impl<'a> From<&repo_unlock_state::RepoUnlockInfo<'a>> for RepoUnlockInfo {
@@ -734,6 +874,8 @@ impl<'a> From<&repo_unlock_state::RepoUnlockInfo<'a>> for RepoUnlockInfo {
         Self {
             status: info.status.into(),
             repo_name: info.repo_name.map(|x| x.0.to_owned()),
+            failed_attempts: info.failed_attempts,
+            retry_at: info.retry_at.map(|x| x.0 as f64),
         }
     }
 }
@@ -786,6 +928,69 @@ impl<'a> From<&repo_space_usage_state::RepoSpaceUsageInfo<'a>> for RepoSpaceUsag
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Tsify)]
+pub struct RepoDuplicatesFile {
+    #[serde(rename = "fileId")]
+    pub file_id: String,
+    #[serde(rename = "encryptedPath")]
+    pub encrypted_path: String,
+    pub path: String,
+    pub modified: Option<f64>,
+}
+
+impl From<&repo_duplicates_state::RepoDuplicatesFile> for RepoDuplicatesFile {
+    fn from(file: &repo_duplicates_state::RepoDuplicatesFile) -> Self {
+        Self {
+            file_id: file.file_id.0.clone(),
+            encrypted_path: file.encrypted_path.0.clone(),
+            path: file.path.0.clone(),
+            modified: file.modified.map(|x| x as f64),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Tsify)]
+pub struct RepoDuplicatesGroup {
+    pub hash: String,
+    #[serde(rename = "sizeDisplay")]
+    pub size_display: String,
+    #[serde(rename = "reclaimableSizeDisplay")]
+    pub reclaimable_size_display: String,
+    pub files: Vec<RepoDuplicatesFile>,
+}
+
+impl From<&repo_duplicates_state::RepoDuplicatesGroup> for RepoDuplicatesGroup {
+    fn from(group: &repo_duplicates_state::RepoDuplicatesGroup) -> Self {
+        Self {
+            hash: group.hash.clone(),
+            size_display: size_display(group.size),
+            reclaimable_size_display: size_display(group.reclaimable_size()),
+            files: group.files.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Tsify)]
+pub struct RepoDuplicatesInfo {
+    #[serde(rename = "repoId")]
+    pub repo_id: String,
+    pub status: Status,
+    pub groups: Vec<RepoDuplicatesGroup>,
+    #[serde(rename = "reclaimableSizeDisplay")]
+    pub reclaimable_size_display: String,
+}
+
+impl<'a> From<&repo_duplicates_state::RepoDuplicatesInfo<'a>> for RepoDuplicatesInfo {
+    fn from(info: &repo_duplicates_state::RepoDuplicatesInfo<'a>) -> Self {
+        Self {
+            repo_id: info.repo_id.0.clone(),
+            status: info.status.into(),
+            groups: info.groups.iter().map(Into::into).collect(),
+            reclaimable_size_display: size_display(info.reclaimable_size),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Tsify)]
 pub enum RemoteFileType {
     Dir,
@@ -847,6 +1052,8 @@ impl From<&repo_files_state::RepoFileType> for RepoFileType {
 pub struct RepoFileTags {
     hash: Option<String>,
     error: Option<String>,
+    #[serde(rename = "errorCode")]
+    error_code: Option<ErrorCode>,
 }
 
 impl
@@ -868,6 +1075,7 @@ impl
             },
             Some(Err(err)) => RepoFileTags {
                 error: Some(err.user_error()),
+                error_code: Some((&err.error_code()).into()),
                 ..Default::default()
             },
             None => Default::default(),
@@ -887,6 +1095,8 @@ pub struct RepoFile {
     pub name: String,
     #[serde(rename = "nameError")]
     pub name_error: Option<String>,
+    #[serde(rename = "nameErrorCode")]
+    pub name_error_code: Option<ErrorCode>,
     pub ext: Option<String>,
     #[serde(rename = "contentType")]
     pub content_type: Option<String>,
@@ -925,6 +1135,12 @@ impl From<&repo_files_state::RepoFile> for RepoFile {
                     Some(error.user_error())
                 }
             },
+            name_error_code: match &file.name {
+                repo_files_state::RepoFileName::Decrypted { .. } => None,
+                repo_files_state::RepoFileName::DecryptError { error, .. } => {
+                    Some((&error.error_code()).into())
+                }
+            },
             ext: file.ext.clone(),
             content_type: file.content_type.clone(),
             typ: (&file.typ).into(),
@@ -1041,6 +1257,10 @@ pub struct RepoFilesBrowserItem {
     pub file_id: String,
     #[serde(rename = "isSelected")]
     pub is_selected: bool,
+    #[serde(rename = "isThumbnailSupported")]
+    pub is_thumbnail_supported: bool,
+    #[serde(rename = "thumbnailStatus")]
+    pub thumbnail_status: Option<Status>,
 }
 
 impl<'a> From<&repo_files_browsers_state::RepoFilesBrowserItem<'a>> for RepoFilesBrowserItem {
@@ -1048,6 +1268,8 @@ impl<'a> From<&repo_files_browsers_state::RepoFilesBrowserItem<'a>> for RepoFile
         Self {
             file_id: item.file.id.0.clone(),
             is_selected: item.is_selected,
+            is_thumbnail_supported: item.is_thumbnail_supported,
+            thumbnail_status: item.thumbnail.map(|thumbnail| (&thumbnail.status).into()),
         }
     }
 }
@@ -1086,6 +1308,10 @@ pub struct RepoFilesBrowserInfo {
     pub repo_status: Status,
     #[serde(rename = "isLocked")]
     pub is_locked: bool,
+    #[serde(rename = "isReadOnly")]
+    pub is_read_only: bool,
+    #[serde(rename = "isReconnecting")]
+    pub is_reconnecting: bool,
 }
 
 impl<'a> From<&repo_files_browsers_state::RepoFilesBrowserInfo<'a>> for RepoFilesBrowserInfo {
@@ -1112,6 +1338,8 @@ impl<'a> From<&repo_files_browsers_state::RepoFilesBrowserInfo<'a>> for RepoFile
                 .unwrap_or(vec![]),
             repo_status: (&info.repo_status).into(),
             is_locked: info.is_locked,
+            is_read_only: info.is_read_only,
+            is_reconnecting: info.is_reconnecting,
         }
     }
 }
@@ -1163,6 +1391,8 @@ pub struct RepoFilesDetailsInfo {
     pub is_dirty: bool,
     #[serde(rename = "shouldDestroy")]
     pub should_destroy: bool,
+    #[serde(rename = "canEdit")]
+    pub can_edit: bool,
     #[serde(rename = "canSave")]
     pub can_save: bool,
     #[serde(rename = "canDownload")]
@@ -1177,6 +1407,12 @@ pub struct RepoFilesDetailsInfo {
     pub repo_status: Status,
     #[serde(rename = "isLocked")]
     pub is_locked: bool,
+    #[serde(rename = "isReadOnly")]
+    pub is_read_only: bool,
+    #[serde(rename = "isConflict")]
+    pub is_conflict: bool,
+    #[serde(rename = "conflictStatus")]
+    pub conflict_status: Status,
 }
 
 impl<'a> From<&repo_files_details_state::RepoFilesDetailsInfo<'a>> for RepoFilesDetailsInfo {
@@ -1197,6 +1433,7 @@ impl<'a> From<&repo_files_details_state::RepoFilesDetailsInfo<'a>> for RepoFiles
             is_editing: info.is_editing,
             is_dirty: info.is_dirty,
             should_destroy: info.should_destroy,
+            can_edit: info.can_edit,
             can_save: info.can_save,
             can_download: info.can_download,
             can_copy: info.can_copy,
@@ -1204,6 +1441,121 @@ impl<'a> From<&repo_files_details_state::RepoFilesDetailsInfo<'a>> for RepoFiles
             can_delete: info.can_delete,
             repo_status: (&info.repo_status).into(),
             is_locked: info.is_locked,
+            is_read_only: info.is_read_only,
+            is_conflict: info.is_conflict,
+            conflict_status: (&info.conflict_status).into(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Tsify)]
+#[serde(tag = "type")]
+pub enum RepoFilesDetailsTextMergeChunk {
+    Resolved {
+        text: String,
+    },
+    Conflict {
+        base: String,
+        mine: String,
+        theirs: String,
+    },
+}
+
+impl From<&repo_files_details_state::RepoFilesDetailsTextMergeChunk>
+    for RepoFilesDetailsTextMergeChunk
+{
+    fn from(chunk: &repo_files_details_state::RepoFilesDetailsTextMergeChunk) -> Self {
+        match chunk {
+            repo_files_details_state::RepoFilesDetailsTextMergeChunk::Resolved(text) => {
+                Self::Resolved { text: text.clone() }
+            }
+            repo_files_details_state::RepoFilesDetailsTextMergeChunk::Conflict {
+                base,
+                mine,
+                theirs,
+            } => Self::Conflict {
+                base: base.clone(),
+                mine: mine.clone(),
+                theirs: theirs.clone(),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Tsify)]
+pub struct RepoFilesDetailsTextMerge {
+    pub chunks: Vec<RepoFilesDetailsTextMergeChunk>,
+    #[serde(rename = "hasConflicts")]
+    pub has_conflicts: bool,
+}
+
+impl From<&repo_files_details_state::RepoFilesDetailsTextMerge> for RepoFilesDetailsTextMerge {
+    fn from(text_merge: &repo_files_details_state::RepoFilesDetailsTextMerge) -> Self {
+        Self {
+            chunks: text_merge.chunks.iter().map(Into::into).collect(),
+            has_conflicts: text_merge.has_conflicts(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Tsify)]
+pub struct RepoFilesVersionsSettings {
+    pub enabled: bool,
+    #[serde(rename = "maxCount")]
+    pub max_count: Option<u32>,
+    #[serde(rename = "maxAgeDays")]
+    pub max_age_days: Option<u32>,
+}
+
+impl From<&repo_files_versions_state::RepoFilesVersionsSettings> for RepoFilesVersionsSettings {
+    fn from(settings: &repo_files_versions_state::RepoFilesVersionsSettings) -> Self {
+        Self {
+            enabled: settings.enabled,
+            max_count: settings.max_count.map(|x| x as u32),
+            max_age_days: settings.max_age_days,
+        }
+    }
+}
+
+impl Into<repo_files_versions_state::RepoFilesVersionsSettings> for RepoFilesVersionsSettings {
+    fn into(self) -> repo_files_versions_state::RepoFilesVersionsSettings {
+        repo_files_versions_state::RepoFilesVersionsSettings {
+            enabled: self.enabled,
+            max_count: self.max_count.map(|x| x as usize),
+            max_age_days: self.max_age_days,
+        }
+    }
+}
+
+/// `encryptedPath` points to the file in the versions dir and can be
+/// downloaded like any other repo file.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Tsify)]
+pub struct RepoFileVersion {
+    pub id: String,
+    #[serde(rename = "repoId")]
+    pub repo_id: String,
+    #[serde(rename = "encryptedPath")]
+    pub encrypted_path: String,
+    #[serde(rename = "originalPath")]
+    pub original_path: String,
+    pub created: f64,
+    pub modified: Option<f64>,
+    pub size: Option<f64>,
+    #[serde(rename = "sizeDisplay")]
+    pub size_display: String,
+}
+
+impl From<&repo_files_versions_state::RepoFileVersion> for RepoFileVersion {
+    fn from(version: &repo_files_versions_state::RepoFileVersion) -> Self {
+        Self {
+            id: version.id.0.clone(),
+            repo_id: version.repo_id.0.clone(),
+            encrypted_path: version.encrypted_path.0.clone(),
+            original_path: version.original_path.0.clone(),
+            created: version.created as f64,
+            modified: version.modified.map(|modified| modified as f64),
+            size: version.size.map(|size| size as f64),
+            size_display: version.size.map(size_display).unwrap_or_default(),
         }
     }
 }
@@ -1253,6 +1605,184 @@ pub struct RepoFilesMoveInfo {
     pub can_move: bool,
 }
 
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Tsify)]
+pub enum RepoFilesRenameCase {
+    Lower,
+    Upper,
+    Title,
+}
+
+impl Into<repo_files_rename_state::RepoFilesRenameCase> for RepoFilesRenameCase {
+    fn into(self) -> repo_files_rename_state::RepoFilesRenameCase {
+        match self {
+            Self::Lower => repo_files_rename_state::RepoFilesRenameCase::Lower,
+            Self::Upper => repo_files_rename_state::RepoFilesRenameCase::Upper,
+            Self::Title => repo_files_rename_state::RepoFilesRenameCase::Title,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Tsify)]
+#[serde(tag = "type")]
+pub enum RepoFilesRenameRule {
+    Replace {
+        find: String,
+        replace: String,
+        #[serde(rename = "caseSensitive")]
+        case_sensitive: bool,
+    },
+    Regex {
+        pattern: String,
+        replace: String,
+    },
+    Numbering {
+        template: String,
+        start: u32,
+        step: u32,
+        padding: u32,
+    },
+    Case {
+        case: RepoFilesRenameCase,
+    },
+    Extension {
+        extension: Option<String>,
+    },
+}
+
+impl Into<repo_files_rename_state::RepoFilesRenameRule> for RepoFilesRenameRule {
+    fn into(self) -> repo_files_rename_state::RepoFilesRenameRule {
+        match self {
+            Self::Replace {
+                find,
+                replace,
+                case_sensitive,
+            } => repo_files_rename_state::RepoFilesRenameRule::Replace {
+                find,
+                replace,
+                case_sensitive,
+            },
+            Self::Regex { pattern, replace } => {
+                repo_files_rename_state::RepoFilesRenameRule::Regex { pattern, replace }
+            }
+            Self::Numbering {
+                template,
+                start,
+                step,
+                padding,
+            } => repo_files_rename_state::RepoFilesRenameRule::Numbering {
+                template,
+                start: start as u64,
+                step: step as u64,
+                padding: padding as usize,
+            },
+            Self::Case { case } => repo_files_rename_state::RepoFilesRenameRule::Case(case.into()),
+            Self::Extension { extension } => {
+                repo_files_rename_state::RepoFilesRenameRule::Extension(extension)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Tsify)]
+pub struct RepoFilesRenamePattern {
+    pub rules: Vec<RepoFilesRenameRule>,
+    #[serde(rename = "includeExtension")]
+    pub include_extension: bool,
+}
+
+impl Into<repo_files_rename_state::RepoFilesRenamePattern> for RepoFilesRenamePattern {
+    fn into(self) -> repo_files_rename_state::RepoFilesRenamePattern {
+        repo_files_rename_state::RepoFilesRenamePattern {
+            rules: self.rules.into_iter().map(Into::into).collect(),
+            include_extension: self.include_extension,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Tsify)]
+#[serde(tag = "type")]
+pub enum RepoFilesRenamePreviewStatus {
+    Unchanged,
+    Ok,
+    InvalidName,
+    AlreadyExists,
+    Duplicate,
+    DecryptNameError {
+        error: String,
+        #[serde(rename = "errorCode")]
+        error_code: ErrorCode,
+    },
+}
+
+impl From<&repo_files_rename_state::RepoFilesRenamePreviewStatus> for RepoFilesRenamePreviewStatus {
+    fn from(status: &repo_files_rename_state::RepoFilesRenamePreviewStatus) -> Self {
+        match status {
+            repo_files_rename_state::RepoFilesRenamePreviewStatus::Unchanged => Self::Unchanged,
+            repo_files_rename_state::RepoFilesRenamePreviewStatus::Ok => Self::Ok,
+            repo_files_rename_state::RepoFilesRenamePreviewStatus::InvalidName => Self::InvalidName,
+            repo_files_rename_state::RepoFilesRenamePreviewStatus::AlreadyExists => {
+                Self::AlreadyExists
+            }
+            repo_files_rename_state::RepoFilesRenamePreviewStatus::Duplicate => Self::Duplicate,
+            repo_files_rename_state::RepoFilesRenamePreviewStatus::DecryptNameError(error) => {
+                Self::DecryptNameError {
+                    error: error.user_error(),
+                    error_code: (&error.error_code()).into(),
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Tsify)]
+pub struct RepoFilesRenamePreviewItem {
+    #[serde(rename = "fileId")]
+    pub file_id: String,
+    pub name: String,
+    #[serde(rename = "newName")]
+    pub new_name: String,
+    pub status: RepoFilesRenamePreviewStatus,
+}
+
+impl From<&repo_files_rename_state::RepoFilesRenamePreviewItem> for RepoFilesRenamePreviewItem {
+    fn from(item: &repo_files_rename_state::RepoFilesRenamePreviewItem) -> Self {
+        Self {
+            file_id: item.file_id.0.clone(),
+            name: item.name.0.clone(),
+            new_name: item.new_name.0.clone(),
+            status: (&item.status).into(),
+        }
+    }
+}
+
+/// `error` is set if the pattern is invalid.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Tsify)]
+pub struct RepoFilesRenamePreview {
+    pub items: Vec<RepoFilesRenamePreviewItem>,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Tsify)]
+pub struct RepoFilesRenameResult {
+    #[serde(rename = "fileId")]
+    pub file_id: String,
+    pub name: String,
+    #[serde(rename = "newName")]
+    pub new_name: String,
+    pub error: Option<String>,
+}
+
+impl From<&repo_files_rename_state::RepoFilesRenameResult> for RepoFilesRenameResult {
+    fn from(res: &repo_files_rename_state::RepoFilesRenameResult) -> Self {
+        Self {
+            file_id: res.file_id.0.clone(),
+            name: res.name.0.clone(),
+            new_name: res.new_name.0.clone(),
+            error: res.result.as_ref().err().map(|err| err.user_error()),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Tsify)]
 pub enum TransferType {
     Upload,
@@ -1275,7 +1805,11 @@ pub enum TransferState {
     Waiting,
     Processing,
     Transferring,
-    Failed { error: String },
+    Failed {
+        error: String,
+        #[serde(rename = "errorCode")]
+        error_code: ErrorCode,
+    },
     Done,
 }
 
@@ -1287,6 +1821,7 @@ impl From<&transfers_state::TransferState> for TransferState {
             transfers_state::TransferState::Transferring => Self::Transferring,
             transfers_state::TransferState::Failed { error } => Self::Failed {
                 error: error.user_error(),
+                error_code: (&error.error_code()).into(),
             },
             transfers_state::TransferState::Done => Self::Done,
         }
@@ -1456,6 +1991,39 @@ impl From<&space_usage_state::SpaceUsageSeverity> for SpaceUsageSeverity {
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Tsify)]
+pub struct RepoFilesOfflineInfo {
+    pub quota: i64,
+    #[serde(rename = "quotaDisplay")]
+    pub quota_display: String,
+    pub used: i64,
+    #[serde(rename = "usedDisplay")]
+    pub used_display: String,
+    #[serde(rename = "pinnedUsed")]
+    pub pinned_used: i64,
+    #[serde(rename = "pinnedUsedDisplay")]
+    pub pinned_used_display: String,
+    #[serde(rename = "pinsCount")]
+    pub pins_count: u32,
+    #[serde(rename = "entriesCount")]
+    pub entries_count: u32,
+}
+
+impl From<&repo_files_offline_state::OfflineInfo> for RepoFilesOfflineInfo {
+    fn from(info: &repo_files_offline_state::OfflineInfo) -> Self {
+        Self {
+            quota: info.quota,
+            quota_display: size_display(info.quota),
+            used: info.used,
+            used_display: size_display(info.used),
+            pinned_used: info.pinned_used,
+            pinned_used_display: size_display(info.pinned_used),
+            pins_count: info.pins_count as u32,
+            entries_count: info.entries_count as u32,
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Tsify)]
 pub struct SpaceUsage {
     #[serde(rename = "usedDisplay")]
@@ -1476,3 +2044,134 @@ impl From<&space_usage_state::SpaceUsage> for SpaceUsage {
         }
     }
 }
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Tsify)]
+pub enum RepoLockReason {
+    Manual,
+    Inactivity,
+    AppHidden,
+    Panic,
+    AccountSwitch,
+}
+
+impl From<&audit_log_state::RepoLockReason> for RepoLockReason {
+    fn from(reason: &audit_log_state::RepoLockReason) -> Self {
+        match reason {
+            audit_log_state::RepoLockReason::Manual => Self::Manual,
+            audit_log_state::RepoLockReason::Inactivity => Self::Inactivity,
+            audit_log_state::RepoLockReason::AppHidden => Self::AppHidden,
+            audit_log_state::RepoLockReason::Panic => Self::Panic,
+            audit_log_state::RepoLockReason::AccountSwitch => Self::AccountSwitch,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Tsify)]
+#[serde(tag = "type")]
+pub enum AuditLogEvent {
+    RepoUnlock {
+        #[serde(rename = "repoId")]
+        repo_id: String,
+        #[serde(rename = "readOnly")]
+        read_only: bool,
+    },
+    RepoUnlockFailure {
+        #[serde(rename = "repoId")]
+        repo_id: String,
+        #[serde(rename = "errorCode")]
+        error_code: String,
+    },
+    RepoLock {
+        #[serde(rename = "repoId")]
+        repo_id: String,
+        reason: RepoLockReason,
+    },
+    RepoCreate {
+        #[serde(rename = "repoId")]
+        repo_id: String,
+    },
+    RepoRemove {
+        #[serde(rename = "repoId")]
+        repo_id: String,
+    },
+    RepoConfigBackup {
+        #[serde(rename = "repoId")]
+        repo_id: String,
+    },
+    Logout,
+}
+
+impl From<&audit_log_state::AuditLogEvent> for AuditLogEvent {
+    fn from(event: &audit_log_state::AuditLogEvent) -> Self {
+        use audit_log_state::AuditLogEvent;
+
+        match event {
+            AuditLogEvent::RepoUnlock { repo_id, read_only } => Self::RepoUnlock {
+                repo_id: repo_id.0.clone(),
+                read_only: *read_only,
+            },
+            AuditLogEvent::RepoUnlockFailure {
+                repo_id,
+                error_code,
+            } => Self::RepoUnlockFailure {
+                repo_id: repo_id.0.clone(),
+                error_code: error_code.clone(),
+            },
+            AuditLogEvent::RepoLock { repo_id, reason } => Self::RepoLock {
+                repo_id: repo_id.0.clone(),
+                reason: reason.into(),
+            },
+            AuditLogEvent::RepoCreate { repo_id } => Self::RepoCreate {
+                repo_id: repo_id.0.clone(),
+            },
+            AuditLogEvent::RepoRemove { repo_id } => Self::RepoRemove {
+                repo_id: repo_id.0.clone(),
+            },
+            AuditLogEvent::RepoConfigBackup { repo_id } => Self::RepoConfigBackup {
+                repo_id: repo_id.0.clone(),
+            },
+            AuditLogEvent::Logout => Self::Logout,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Tsify)]
+pub struct AuditLogEntry {
+    pub seq: u64,
+    pub time: i64,
+    #[serde(rename = "userId")]
+    pub user_id: Option<String>,
+    pub event: AuditLogEvent,
+    #[serde(rename = "prevHash")]
+    pub prev_hash: String,
+    pub hash: String,
+}
+
+impl From<&audit_log_state::AuditLogEntry> for AuditLogEntry {
+    fn from(entry: &audit_log_state::AuditLogEntry) -> Self {
+        Self {
+            seq: entry.seq,
+            time: entry.time,
+            user_id: entry.user_id.clone(),
+            event: (&entry.event).into(),
+            prev_hash: entry.prev_hash.clone(),
+            hash: entry.hash.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Tsify)]
+pub struct AuditLogFilter {
+    #[serde(rename = "repoId")]
+    pub repo_id: Option<String>,
+    pub since: Option<i64>,
+}
+
+impl Into<audit_log_state::AuditLogFilter> for AuditLogFilter {
+    fn into(self) -> audit_log_state::AuditLogFilter {
+        audit_log_state::AuditLogFilter {
+            repo_id: self.repo_id.map(RepoId),
+            since: self.since,
+        }
+    }
+}
@@ -4,14 +4,14 @@ use std::{
     time::Duration,
 };
 
-use futures::{future::BoxFuture, FutureExt};
+use futures::{future::BoxFuture, stream::AbortHandle, FutureExt};
 
 use vault_core::{
-    common, dialogs,
+    accounts, common, dialogs,
     dir_pickers::state::DirPickerItemId,
-    files, notifications, oauth2, remote_files, repo_config_backup, repo_create, repo_files,
-    repo_files_browsers, repo_files_details, repo_files_move, repo_remove, repo_space_usage,
-    repo_unlock, repos,
+    files, lifecycle, notifications, oauth2, remote_files, repo_config_backup, repo_create,
+    repo_duplicates, repo_files, repo_files_browsers, repo_files_details, repo_files_move,
+    repo_remove, repo_space_usage, repo_unlock, repos,
     store::{self, Event, Subscription},
     transfers,
     types::{DecryptedName, EncryptedPath, RepoFileId, RepoId, TimeMillis},
@@ -33,6 +33,7 @@ pub struct SubscriptionData {
     pub dialog: Data<Option<dto::Dialog>>,
     pub oauth2_status: Data<dto::Status>,
     pub user: Data<Option<dto::User>>,
+    pub accounts: Data<Vec<dto::Account>>,
     pub user_profile_picture_loaded: Data<bool>,
     pub repos: Data<dto::Repos>,
     pub repos_repo: Data<dto::RepoInfo>,
@@ -41,6 +42,7 @@ pub struct SubscriptionData {
     pub repo_remove_info: Data<Option<dto::RepoRemoveInfo>>,
     pub repo_config_backup_info: Data<Option<dto::RepoConfigBackupInfo>>,
     pub repo_space_usage_info: Data<Option<dto::RepoSpaceUsageInfo>>,
+    pub repo_duplicates_info: Data<Option<dto::RepoDuplicatesInfo>>,
     pub repo_files_file: Data<Option<dto::RepoFile>>,
     pub transfers_is_active: Data<bool>,
     pub transfers_summary: Data<dto::TransfersSummary>,
@@ -50,6 +52,7 @@ pub struct SubscriptionData {
     pub repo_files_details_info: Data<Option<dto::RepoFilesDetailsInfo>>,
     pub repo_files_details_file: Data<Option<dto::RepoFile>>,
     pub repo_files_details_content_bytes: Data<dto::Versioned<Option<Vec<u8>>>>,
+    pub repo_files_details_conflict_text_merge: Data<Option<dto::RepoFilesDetailsTextMerge>>,
     pub repo_files_move_info: Data<Option<dto::RepoFilesMoveInfo>>,
     pub space_usage: Data<Option<dto::SpaceUsage>>,
 }
@@ -60,6 +63,7 @@ pub struct WebVaultBase {
     pub subscription_data: SubscriptionData,
     pub subscription: Subscription,
     pub file_icon_factory: vault_file_icon::FileIconFactory,
+    pub oauth2_device_flow_abort_handle: Arc<Mutex<Option<AbortHandle>>>,
 }
 
 impl WebVaultBase {
@@ -78,6 +82,7 @@ impl WebVaultBase {
             subscription_data,
             subscription,
             file_icon_factory,
+            oauth2_device_flow_abort_handle: Arc::new(Mutex::new(None)),
         }
     }
 
@@ -184,6 +189,12 @@ impl WebVaultBase {
         self.vault.app_hidden();
     }
 
+    // panic_lock
+
+    pub fn panic_lock(&self, wipe_secure_storage: bool) {
+        self.handle_result(self.vault.panic_lock(wipe_secure_storage));
+    }
+
     // relative_time
 
     pub fn relative_time(&self, value: f64, with_modifier: bool) -> dto::RelativeTime {
@@ -193,6 +204,39 @@ impl WebVaultBase {
         )
     }
 
+    // locale
+
+    pub fn locale_get(&self) -> String {
+        self.vault.locale_get()
+    }
+
+    pub fn locale_set(&self, name: &str) {
+        self.handle_result(self.vault.locale_set(name));
+    }
+
+    // audit_log
+
+    pub fn audit_log_entries(&self, filter: dto::AuditLogFilter) -> Vec<dto::AuditLogEntry> {
+        self.vault
+            .audit_log_entries(&filter.into())
+            .iter()
+            .map(Into::into)
+            .collect()
+    }
+
+    pub fn audit_log_export(&self) -> String {
+        self.vault.audit_log_export()
+    }
+
+    pub fn audit_log_verify(&self) -> bool {
+        let res = self.vault.audit_log_verify();
+        let valid = res.is_ok();
+
+        self.handle_result(res);
+
+        valid
+    }
+
     // notifications
 
     pub fn notifications_subscribe(&self, cb: Callback) -> u32 {
@@ -334,6 +378,54 @@ impl WebVaultBase {
         success
     }
 
+    pub async fn oauth2_start_device_flow(&self) -> Option<dto::DeviceAuthorization> {
+        match self.vault.oauth2_start_device_flow().await {
+            Ok(device_authorization) => Some((&device_authorization).into()),
+            Err(err) => {
+                self.handle_error(err);
+                None
+            }
+        }
+    }
+
+    pub async fn oauth2_finish_device_flow(
+        &self,
+        device_authorization: dto::DeviceAuthorization,
+    ) -> bool {
+        let (abort_handle, abort_registration) = AbortHandle::new_pair();
+
+        if let Some(previous_abort_handle) = self
+            .oauth2_device_flow_abort_handle
+            .lock()
+            .unwrap()
+            .replace(abort_handle)
+        {
+            previous_abort_handle.abort();
+        }
+
+        let res = self
+            .vault
+            .oauth2_finish_device_flow(&device_authorization.into(), abort_registration)
+            .await;
+
+        let success = res.is_ok();
+
+        match res {
+            Err(lifecycle::errors::OAuth2FinishDeviceFlowError::OAuth2Error(
+                oauth2::errors::OAuth2Error::Canceled,
+            )) => {}
+            res => self.handle_result(res),
+        }
+
+        success
+    }
+
+    pub fn oauth2_cancel_device_flow(&self) {
+        if let Some(abort_handle) = self.oauth2_device_flow_abort_handle.lock().unwrap().take() {
+            abort_handle.abort();
+        }
+    }
+
     // config
 
     pub fn config_get_base_url(&self) -> String {
@@ -401,6 +493,53 @@ impl WebVaultBase {
         });
     }
 
+    // accounts
+
+    pub fn accounts_subscribe(&self, cb: Callback) -> u32 {
+        self.subscribe(
+            &[Event::Accounts],
+            cb,
+            self.subscription_data.accounts.clone(),
+            move |vault| {
+                vault.with_state(|state| {
+                    accounts::selectors::select_accounts(state)
+                        .iter()
+                        .map(Into::into)
+                        .collect()
+                })
+            },
+        )
+    }
+
+    pub fn accounts_data(&self, id: u32) -> Option<Vec<dto::Account>> {
+        self.get_data(id, self.subscription_data.accounts.clone())
+    }
+
+    pub async fn accounts_switch(&self, user_id: String) -> bool {
+        let res = self.vault.accounts_switch(&user_id).await;
+
+        let success = res.is_ok();
+
+        self.handle_result(res);
+
+        success
+    }
+
+    /// Returns the login URL of the new account.
+    pub fn accounts_add(&self) -> Option<String> {
+        match self.vault.accounts_add() {
+            Ok(url) => Some(url),
+            Err(err) => {
+                self.handle_error(err);
+                None
+            }
+        }
+    }
+
+    pub fn accounts_remove(&self, user_id: String) {
+        self.handle_result(self.vault.accounts_remove(&user_id));
+    }
+
     // file_icon
 
     pub fn file_icon_svg(&self, props: dto::FileIconProps) -> String {
@@ -469,6 +608,11 @@ impl WebVaultBase {
         self.vault.repos_set_default_auto_lock(auto_lock.into());
     }
 
+    pub fn repos_set_unlock_lockout(&self, unlock_lockout: Option<dto::RepoUnlockLockout>) {
+        self.vault
+            .repos_set_unlock_lockout(unlock_lockout.map(Into::into));
+    }
+
     // repo_create
 
     pub fn repo_create_create(&self) -> u32 {
@@ -529,7 +673,7 @@ impl WebVaultBase {
                                         selectors::select_location_dir_picker_create_dir_enabled(
                                             state, create_id,
                                         ),
-                                    password: form.password.clone(),
+                                    password: form.password.to_string(),
                                     salt: form.salt.clone(),
                                     fill_from_rclone_config_error: form
                                         .fill_from_rclone_config_error
@@ -642,12 +786,9 @@ impl WebVaultBase {
             self.subscription_data.repo_unlock_info.clone(),
             move |vault| {
                 vault.with_state(|state| {
-                    repo_unlock::selectors::select_info(state, unlock_id).map(|info| {
-                        dto::RepoUnlockInfo {
-                            status: info.status.into(),
-                            repo_name: info.repo_name.map(|x| x.0.clone()),
-                        }
-                    })
+                    repo_unlock::selectors::select_info(state, unlock_id)
+                        .as_ref()
+                        .map(Into::into)
                 })
             },
         )
@@ -776,6 +917,66 @@ impl WebVaultBase {
         self.vault.repo_space_usage_destroy(usage_id);
     }
 
+    // repo_duplicates
+
+    pub fn repo_duplicates_create(&self, repo_id: String) -> u32 {
+        self.vault.repo_duplicates_create(RepoId(repo_id))
+    }
+
+    pub fn repo_duplicates_info_subscribe(&self, duplicates_id: u32, cb: Callback) -> u32 {
+        self.subscribe(
+            &[Event::RepoDuplicates],
+            cb,
+            self.subscription_data.repo_duplicates_info.clone(),
+            move |vault| {
+                vault.with_state(|state| {
+                    repo_duplicates::selectors::select_info(state, duplicates_id)
+                        .as_ref()
+                        .map(Into::into)
+                })
+            },
+        )
+    }
+
+    pub fn repo_duplicates_info_data(&self, id: u32) -> Option<dto::RepoDuplicatesInfo> {
+        self.get_data(id, self.subscription_data.repo_duplicates_info.clone())
+            .flatten()
+    }
+
+    pub fn repo_duplicates_scan(&self, duplicates_id: u32) {
+        self.spawn(move |vault| {
+            async move {
+                // error is displayed in the duplicates component
+                let _ = vault.repo_duplicates_scan(duplicates_id).await;
+            }
+            .boxed()
+        });
+    }
+
+    /// If `hashes` is set only the matching groups are processed.
+    pub fn repo_duplicates_delete_all_but_newest(
+        &self,
+        duplicates_id: u32,
+        hashes: Option<Vec<String>>,
+    ) {
+        self.spawn_result(move |vault| {
+            async move {
+                match vault
+                    .repo_duplicates_delete_all_but_newest(duplicates_id, hashes)
+                    .await
+                {
+                    Err(repo_files::errors::DeleteFileError::Canceled) => Ok(()),
+                    res => res,
+                }
+            }
+            .boxed()
+        });
+    }
+
+    pub fn repo_duplicates_destroy(&self, duplicates_id: u32) {
+        self.vault.repo_duplicates_destroy(duplicates_id);
+    }
+
     // repo_files
 
     pub fn repo_files_file_subscribe(&self, file_id: String, cb: Callback) -> u32 {
@@ -991,7 +1192,12 @@ impl WebVaultBase {
 
     pub fn repo_files_browsers_info_subscribe(&self, browser_id: u32, cb: Callback) -> u32 {
         self.subscribe(
-            &[Event::RepoFilesBrowsers, Event::RepoFiles],
+            &[
+                Event::RepoFilesBrowsers,
+                Event::RepoFiles,
+                Event::RepoFilesThumbnails,
+                Event::Eventstream,
+            ],
             cb,
             self.subscription_data.repo_files_browsers_info.clone(),
             move |vault| {
@@ -1101,6 +1307,62 @@ impl WebVaultBase {
         });
     }
 
+    pub fn repo_files_browsers_rename_selected_preview(
+        &self,
+        browser_id: u32,
+        pattern: dto::RepoFilesRenamePattern,
+    ) -> dto::RepoFilesRenamePreview {
+        match self
+            .vault
+            .repo_files_browsers_rename_selected_preview(browser_id, &pattern.into())
+        {
+            Ok(items) => dto::RepoFilesRenamePreview {
+                items: items.iter().map(Into::into).collect(),
+                error: None,
+            },
+            Err(err) => dto::RepoFilesRenamePreview {
+                items: vec![],
+                error: Some(err.user_error()),
+            },
+        }
+    }
+
+    pub async fn repo_files_browsers_rename_selected(
+        &self,
+        browser_id: u32,
+        pattern: dto::RepoFilesRenamePattern,
+    ) -> Vec<dto::RepoFilesRenameResult> {
+        match self
+            .vault
+            .repo_files_browsers_rename_selected(browser_id, &pattern.into())
+            .await
+        {
+            Ok(results) => results.iter().map(Into::into).collect(),
+            Err(err) => {
+                self.handle_error(err);
+
+                vec![]
+            }
+        }
+    }
+
+    // repo_files_thumbnails
+
+    pub fn repo_files_thumbnails_ensure(&self, file_ids: Vec<String>) {
+        self.vault
+            .repo_files_thumbnails_ensure(file_ids.into_iter().map(RepoFileId).collect());
+    }
+
+    /// thumbnail errors are displayed in browser items so they are not
+    /// handled here
+    pub async fn repo_files_thumbnails_get(&self, file_id: String) -> Option<Vec<u8>> {
+        self.vault
+            .repo_files_thumbnails_get(RepoFileId(file_id))
+            .await
+            .ok()
+            .map(|thumbnail| thumbnail.bytes)
+    }
+
     // repo_files_details
 
     pub fn repo_files_details_create(
@@ -1279,6 +1541,85 @@ impl WebVaultBase {
         });
     }
 
+    pub fn repo_files_details_conflict_text_merge_subscribe(
+        &self,
+        details_id: u32,
+        cb: Callback,
+    ) -> u32 {
+        self.subscribe(
+            &[Event::RepoFilesDetails],
+            cb,
+            self.subscription_data
+                .repo_files_details_conflict_text_merge
+                .clone(),
+            move |vault| {
+                vault.with_state(|state| {
+                    repo_files_details::selectors::select_conflict_text_merge(state, details_id)
+                        .map(Into::into)
+                })
+            },
+        )
+    }
+
+    pub fn repo_files_details_conflict_text_merge_data(
+        &self,
+        id: u32,
+    ) -> Option<dto::RepoFilesDetailsTextMerge> {
+        self.get_data(
+            id,
+            self.subscription_data
+                .repo_files_details_conflict_text_merge
+                .clone(),
+        )
+        .flatten()
+    }
+
+    pub fn repo_files_details_conflict_keep_mine(&self, details_id: u32) {
+        self.spawn(move |vault| {
+            async move {
+                // error is displayed in the details component
+                let _ = vault
+                    .clone()
+                    .repo_files_details_conflict_keep_mine(details_id)
+                    .await;
+            }
+            .boxed()
+        });
+    }
+
+    pub fn repo_files_details_conflict_merge(&self, details_id: u32, content: Vec<u8>) {
+        self.spawn(move |vault| {
+            async move {
+                // error is displayed in the details component
+                let _ = vault
+                    .clone()
+                    .repo_files_details_conflict_merge(details_id, content)
+                    .await;
+            }
+            .boxed()
+        });
+    }
+
+    pub fn repo_files_details_conflict_keep_theirs(&self, details_id: u32) {
+        self.handle_result(
+            self.vault
+                .repo_files_details_conflict_keep_theirs(details_id),
+        );
+    }
+
+    pub fn repo_files_details_conflict_save_as_copy(&self, details_id: u32) {
+        self.spawn_result(move |vault| {
+            async move {
+                vault
+                    .clone()
+                    .repo_files_details_conflict_save_as_copy(details_id)
+                    .await
+                    .map(|_| ())
+            }
+            .boxed()
+        });
+    }
+
     // repo_files_move
 
     pub fn repo_files_move_info_subscribe(&self, cb: Callback) -> u32 {
@@ -1347,6 +1688,163 @@ impl WebVaultBase {
         });
     }
 
+    // repo_files_versions
+
+    pub fn repo_files_versions_get_settings(
+        &self,
+        repo_id: String,
+    ) -> Option<dto::RepoFilesVersionsSettings> {
+        match self
+            .vault
+            .repo_files_versions_get_settings(&RepoId(repo_id))
+        {
+            Ok(settings) => Some((&settings).into()),
+            Err(err) => {
+                self.handle_error(err);
+
+                None
+            }
+        }
+    }
+
+    pub fn repo_files_versions_set_settings(
+        &self,
+        repo_id: String,
+        settings: dto::RepoFilesVersionsSettings,
+    ) -> bool {
+        let res = self
+            .vault
+            .repo_files_versions_set_settings(&RepoId(repo_id), settings.into());
+
+        let success = res.is_ok();
+
+        self.handle_result(res);
+
+        success
+    }
+
+    /// Returns versions sorted from newest to oldest.
+    pub async fn repo_files_versions_list(
+        &self,
+        repo_id: String,
+        encrypted_path: String,
+    ) -> Option<Vec<dto::RepoFileVersion>> {
+        match self
+            .vault
+            .repo_files_versions_list(&RepoId(repo_id), &EncryptedPath(encrypted_path))
+            .await
+        {
+            Ok(versions) => Some(versions.iter().map(Into::into).collect()),
+            Err(err) => {
+                self.handle_error(err);
+
+                None
+            }
+        }
+    }
+
+    pub async fn repo_files_versions_restore(
+        &self,
+        repo_id: String,
+        encrypted_path: String,
+        version_encrypted_path: String,
+    ) -> bool {
+        let res = self
+            .vault
+            .repo_files_versions_restore(
+                &RepoId(repo_id),
+                &EncryptedPath(encrypted_path),
+                &EncryptedPath(version_encrypted_path),
+            )
+            .await;
+
+        let success = res.is_ok();
+
+        self.handle_result(res);
+
+        success
+    }
+
+    pub async fn repo_files_versions_prune(&self, repo_id: String, encrypted_path: String) -> bool {
+        let res = self
+            .vault
+            .repo_files_versions_prune(&RepoId(repo_id), &EncryptedPath(encrypted_path))
+            .await;
+
+        let success = res.is_ok();
+
+        self.handle_result(res);
+
+        success
+    }
+
+    // repo_files_offline
+
+    pub async fn repo_files_offline_pin(&self, repo_id: String, encrypted_path: String) -> bool {
+        let res = self
+            .vault
+            .repo_files_offline_pin(RepoId(repo_id), EncryptedPath(encrypted_path))
+            .await;
+
+        let success = res.is_ok();
+
+        self.handle_result(res);
+
+        success
+    }
+
+    pub async fn repo_files_offline_unpin(&self, repo_id: String, encrypted_path: String) -> bool {
+        let res = self
+            .vault
+            .repo_files_offline_unpin(&RepoId(repo_id), &EncryptedPath(encrypted_path))
+            .await;
+
+        let success = res.is_ok();
+
+        self.handle_result(res);
+
+        success
+    }
+
+    pub fn repo_files_offline_is_pinned(&self, repo_id: String, encrypted_path: String) -> bool {
+        self.vault
+            .repo_files_offline_is_pinned(&RepoId(repo_id), &EncryptedPath(encrypted_path))
+    }
+
+    pub async fn repo_files_offline_refresh(&self) -> bool {
+        let res = self.vault.repo_files_offline_refresh().await;
+
+        let success = res.is_ok();
+
+        self.handle_result(res);
+
+        success
+    }
+
+    pub async fn repo_files_offline_set_quota(&self, quota: i64) -> bool {
+        let res = self.vault.repo_files_offline_set_quota(quota).await;
+
+        let success = res.is_ok();
+
+        self.handle_result(res);
+
+        success
+    }
+
+    pub fn repo_files_offline_info(&self) -> dto::RepoFilesOfflineInfo {
+        (&self.vault.repo_files_offline_get_info()).into()
+    }
+
+    pub async fn repo_files_offline_clear(&self) -> bool {
+        let res = self.vault.repo_files_offline_clear().await;
+
+        let success = res.is_ok();
+
+        self.handle_result(res);
+
+        success
+    }
+
     // space_usage
 
     pub fn space_usage_subscribe(&self, cb: Callback) -> u32 {